@@ -0,0 +1,12 @@
+-- Scopes limit what a bearer token may do beyond reading and managing the
+-- owner's snakes: 'games:create', 'leaderboards:write', 'admin' (the admin
+-- scope is still gated on users.is_admin). Tokens issued before scopes
+-- existed acted as the whole account, so backfill them with every scope.
+ALTER TABLE api_tokens
+ADD COLUMN scopes TEXT[] NOT NULL DEFAULT '{}' CHECK (
+    scopes <@ ARRAY['games:create', 'leaderboards:write', 'admin']::TEXT[]
+);
+
+UPDATE api_tokens
+SET
+    scopes = ARRAY['games:create', 'leaderboards:write', 'admin'];
@@ -0,0 +1,12 @@
+-- Fixed one-minute request counters for the API write rate limiter.
+-- `bucket_key` is `token:<sha256 of the bearer secret>` for token-authed
+-- requests or `ip:<client address>` otherwise. One row per key per minute;
+-- pruned by the rate-limit prune cron alongside the other attempt tables.
+CREATE TABLE api_rate_limit_buckets (
+    bucket_key TEXT NOT NULL,
+    window_start TIMESTAMPTZ NOT NULL,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (bucket_key, window_start)
+);
+
+CREATE INDEX idx_api_rate_limit_buckets_window_start ON api_rate_limit_buckets (window_start);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_games_run_tag_updated_at;
+DROP INDEX IF EXISTS idx_games_updated_at;
+ALTER TABLE games DROP COLUMN run_tag;
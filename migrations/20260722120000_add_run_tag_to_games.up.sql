@@ -0,0 +1,7 @@
+-- Free-form label set by API clients when creating games (e.g. a stress
+-- test run id) so they can poll just their own batch for changes.
+ALTER TABLE games ADD COLUMN run_tag TEXT;
+
+-- Incremental status polling: GET /api/games/status?since=&run_tag=
+CREATE INDEX idx_games_updated_at ON games (updated_at);
+CREATE INDEX idx_games_run_tag_updated_at ON games (run_tag, updated_at) WHERE run_tag IS NOT NULL;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_snake_turns_created_at;
@@ -0,0 +1,2 @@
+-- Admin stats aggregate move latency over the last hour of snake_turns
+CREATE INDEX idx_snake_turns_created_at ON snake_turns (created_at);
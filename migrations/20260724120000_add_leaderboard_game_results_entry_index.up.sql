@@ -0,0 +1,3 @@
+-- Entry game history pages through an entry's results newest first
+CREATE INDEX idx_leaderboard_game_results_entry_id
+    ON leaderboard_game_results (leaderboard_entry_id);
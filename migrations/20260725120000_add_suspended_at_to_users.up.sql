@@ -0,0 +1,5 @@
+-- Admin account suspension. A suspended user can't create games or join
+-- leaderboards, and their active entries are paused with
+-- disabled_reason = 'suspended' so lifting the suspension resumes exactly
+-- those (manual pauses stay paused).
+ALTER TABLE users ADD COLUMN suspended_at TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+-- How many times the stuck-game watchdog has re-enqueued this game's runner.
+-- Once it hits STUCK_GAME_MAX_REQUEUES the watchdog gives up and ends the
+-- game instead of retrying forever.
+ALTER TABLE games ADD COLUMN watchdog_requeues INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+DROP INDEX idx_games_finished_created_at;
@@ -0,0 +1,3 @@
+-- Lets the game retention job find its oldest finished games without
+-- scanning the whole table.
+CREATE INDEX idx_games_finished_created_at ON games (created_at) WHERE status = 'finished';
@@ -0,0 +1 @@
+DROP TABLE game_frame_archives;
@@ -0,0 +1,10 @@
+-- Pointer to a finished game's frames once they've been offloaded to GCS.
+-- The turns rows stay (latency stats and turn timing hang off them); only
+-- their frame_data is cleared. Readers fetch the frames back through
+-- frame_archive when a row exists here.
+CREATE TABLE game_frame_archives (
+    game_id UUID PRIMARY KEY REFERENCES games (game_id) ON DELETE CASCADE,
+    gcs_path TEXT NOT NULL,
+    frame_count INTEGER NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Site-wide maintenance flag, a single row. While enabled the matchmaker
+-- skips its runs and game creation is refused; games already running finish
+-- normally. Every server process caches it for a few seconds.
+CREATE TABLE maintenance_mode (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    message TEXT NOT NULL DEFAULT '',
+    updated_by UUID REFERENCES users (user_id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO maintenance_mode (id) VALUES (TRUE);
@@ -0,0 +1 @@
+ALTER TABLE snake_turns DROP COLUMN http_status, DROP COLUMN error_kind;
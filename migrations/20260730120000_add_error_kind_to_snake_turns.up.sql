@@ -0,0 +1,7 @@
+-- Why a snake's /move call failed, for the admin snake error report. NULL
+-- when the move came back fine (and on rows from before this column).
+-- `timed_out` is still set for timeouts and network errors.
+ALTER TABLE snake_turns
+    ADD COLUMN error_kind TEXT
+        CHECK (error_kind IN ('timeout', 'network', 'http_status', 'parse')),
+    ADD COLUMN http_status SMALLINT;
@@ -0,0 +1,16 @@
+-- Every privileged mutation an admin makes, with before/after snapshots.
+-- The actor's login is copied so entries stay readable after the account is
+-- deleted. target_id is TEXT because some targets (a job name) aren't UUIDs.
+CREATE TABLE admin_audit_log (
+    admin_audit_log_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    actor_id UUID REFERENCES users (user_id) ON DELETE SET NULL,
+    actor_login TEXT NOT NULL,
+    action TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT,
+    before JSONB,
+    after JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_admin_audit_log_created_at ON admin_audit_log (created_at DESC);
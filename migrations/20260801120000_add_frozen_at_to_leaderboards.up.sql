@@ -0,0 +1,4 @@
+-- End-of-season freeze. While set, the matchmaker skips the leaderboard and
+-- finished games' rating updates are deferred (their leaderboard_games rows
+-- simply have no results yet); unfreezing applies them in finish order.
+ALTER TABLE leaderboards ADD COLUMN frozen_at TIMESTAMPTZ;
@@ -0,0 +1,9 @@
+-- Runtime tunables editable from the admin settings page, one row per
+-- overridden key. Keys without a row use the default compiled into the
+-- server. Every server process caches the set for a few seconds.
+CREATE TABLE settings (
+    key TEXT PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_by UUID REFERENCES users (user_id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,25 @@
+-- Periodic copies of the admin dashboard's key numbers, so metrics can be
+-- looked at after the fact (post-incident, post-stress-test) instead of
+-- only live. Pool and runtime columns describe the process that captured
+-- the snapshot.
+CREATE TABLE admin_metric_snapshots (
+    admin_metric_snapshot_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    jobs_ready BIGINT NOT NULL,
+    jobs_running BIGINT NOT NULL,
+    jobs_scheduled BIGINT NOT NULL,
+    games_waiting BIGINT NOT NULL,
+    games_running BIGINT NOT NULL,
+    games_created_last_hour BIGINT NOT NULL,
+    games_finished_last_hour BIGINT NOT NULL,
+    moves_last_hour BIGINT NOT NULL,
+    move_timeout_rate DOUBLE PRECISION NOT NULL,
+    move_p50_ms DOUBLE PRECISION,
+    move_p99_ms DOUBLE PRECISION,
+    db_pool_utilization DOUBLE PRECISION NOT NULL,
+    db_acquire_wait_ms DOUBLE PRECISION NOT NULL,
+    tokio_alive_tasks BIGINT NOT NULL,
+    rss_bytes BIGINT
+);
+
+CREATE INDEX idx_admin_metric_snapshots_captured_at ON admin_metric_snapshots (captured_at);
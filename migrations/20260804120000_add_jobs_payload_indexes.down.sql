@@ -0,0 +1,2 @@
+DROP INDEX idx_jobs_name_leaderboard_game_id;
+DROP INDEX idx_jobs_name_game_id;
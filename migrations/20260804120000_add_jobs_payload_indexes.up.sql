@@ -0,0 +1,4 @@
+-- Lets the consistency check look up a game's runner job and a leaderboard
+-- game's rating job by payload without scanning the whole jobs table.
+CREATE INDEX idx_jobs_name_game_id ON jobs (name, (payload->>'game_id'));
+CREATE INDEX idx_jobs_name_leaderboard_game_id ON jobs (name, (payload->>'leaderboard_game_id'));
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_leaderboard_game_results_entry_history;
+CREATE INDEX idx_leaderboard_game_results_entry_id
+    ON leaderboard_game_results (leaderboard_entry_id);
+ALTER TABLE leaderboard_game_results DROP COLUMN game_created_at;
@@ -0,0 +1,16 @@
+-- Entry game history pages newest first by (game created_at, leaderboard
+-- game id). Copying the game's created_at onto each result lets a single
+-- index serve that order instead of sorting every result the entry has.
+ALTER TABLE leaderboard_game_results ADD COLUMN game_created_at TIMESTAMPTZ;
+
+UPDATE leaderboard_game_results lgr
+SET game_created_at = lg.created_at
+FROM leaderboard_games lg
+WHERE lg.leaderboard_game_id = lgr.leaderboard_game_id;
+
+ALTER TABLE leaderboard_game_results ALTER COLUMN game_created_at SET NOT NULL;
+
+-- The new index leads with leaderboard_entry_id, so it covers the old one
+DROP INDEX IF EXISTS idx_leaderboard_game_results_entry_id;
+CREATE INDEX idx_leaderboard_game_results_entry_history
+    ON leaderboard_game_results (leaderboard_entry_id, game_created_at DESC, leaderboard_game_id DESC);
@@ -0,0 +1,4 @@
+ALTER TABLE leaderboard_games DROP COLUMN rating_deferred_at;
+DROP TRIGGER IF EXISTS set_games_finished_at ON games;
+DROP FUNCTION IF EXISTS set_games_finished_at();
+ALTER TABLE games DROP COLUMN finished_at;
@@ -0,0 +1,42 @@
+-- When a game finished. updated_at moves on any later write, so it can't
+-- be used to replay games in the order they finished.
+ALTER TABLE games ADD COLUMN finished_at TIMESTAMPTZ;
+
+CREATE OR REPLACE FUNCTION set_games_finished_at()
+RETURNS TRIGGER AS $$
+BEGIN
+   IF NEW.status = 'finished' THEN
+      NEW.finished_at = COALESCE(NEW.finished_at, NOW());
+   ELSE
+      NEW.finished_at = NULL;
+   END IF;
+   RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER set_games_finished_at
+  BEFORE INSERT OR UPDATE OF status ON games
+  FOR EACH ROW
+  EXECUTE FUNCTION set_games_finished_at();
+
+-- updated_at is the best record there is for games that already finished.
+-- Skip the updated_at trigger so the backfill doesn't move it.
+ALTER TABLE games DISABLE TRIGGER update_games_updated_at;
+UPDATE games SET finished_at = updated_at WHERE status = 'finished';
+ALTER TABLE games ENABLE TRIGGER update_games_updated_at;
+
+-- Set when a rating update is deferred because the leaderboard is frozen;
+-- unfreezing applies exactly these games
+ALTER TABLE leaderboard_games ADD COLUMN rating_deferred_at TIMESTAMPTZ;
+
+-- Games deferred by a freeze that is still in place
+UPDATE leaderboard_games lg
+SET rating_deferred_at = l.frozen_at
+FROM leaderboards l, games g
+WHERE l.leaderboard_id = lg.leaderboard_id
+  AND g.game_id = lg.game_id
+  AND l.frozen_at IS NOT NULL
+  AND g.status = 'finished'
+  AND g.finished_at >= l.frozen_at
+  AND NOT EXISTS (SELECT 1 FROM leaderboard_game_results lgr
+                  WHERE lgr.leaderboard_game_id = lg.leaderboard_game_id);
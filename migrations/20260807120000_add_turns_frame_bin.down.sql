@@ -0,0 +1 @@
+ALTER TABLE turns DROP COLUMN frame_bin;
@@ -0,0 +1,4 @@
+-- Frames in the compact binary encoding (see engine::frame_codec). New
+-- turns store their frame here and leave frame_data NULL; frame_data keeps
+-- the JSON frames of turns written before this column existed.
+ALTER TABLE turns ADD COLUMN frame_bin BYTEA;
@@ -0,0 +1,7 @@
+DROP INDEX idx_game_battlesnakes_entry_created_at;
+CREATE INDEX game_battlesnakes_battlesnake_id_idx ON game_battlesnakes (battlesnake_id);
+DROP INDEX idx_game_battlesnakes_battlesnake_created_at;
+
+CREATE INDEX games_status_idx ON games (status);
+DROP INDEX idx_games_status_created_at_game_id;
+DROP INDEX idx_games_created_at_game_id;
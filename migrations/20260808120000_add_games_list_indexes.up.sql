@@ -0,0 +1,12 @@
+-- Keyset pagination for the /games page: newest first, ties broken by id,
+-- optionally narrowed to one status.
+CREATE INDEX idx_games_created_at_game_id ON games (created_at DESC, game_id DESC);
+CREATE INDEX idx_games_status_created_at_game_id ON games (status, created_at DESC, game_id DESC);
+DROP INDEX games_status_idx;
+
+-- A snake's recent games, whether it played directly or through a
+-- leaderboard entry.
+CREATE INDEX idx_game_battlesnakes_battlesnake_created_at ON game_battlesnakes (battlesnake_id, created_at);
+DROP INDEX game_battlesnakes_battlesnake_id_idx;
+CREATE INDEX idx_game_battlesnakes_entry_created_at ON game_battlesnakes (leaderboard_entry_id, created_at)
+    WHERE leaderboard_entry_id IS NOT NULL;
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS notify_turn_inserted ON turns;
+DROP FUNCTION IF EXISTS notify_turn_inserted();
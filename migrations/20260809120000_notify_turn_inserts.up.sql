@@ -0,0 +1,14 @@
+-- Announce each stored turn as `<game_id>:<turn_number>` so web servers
+-- can push live frames for games played by a separate worker process.
+CREATE OR REPLACE FUNCTION notify_turn_inserted()
+RETURNS TRIGGER AS $$
+BEGIN
+   PERFORM pg_notify('game_turns', NEW.game_id::text || ':' || NEW.turn_number);
+   RETURN NULL;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER notify_turn_inserted
+  AFTER INSERT ON turns
+  FOR EACH ROW
+  EXECUTE FUNCTION notify_turn_inserted();
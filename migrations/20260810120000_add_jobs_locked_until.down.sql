@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_jobs_priority_run_at;
+ALTER TABLE jobs DROP COLUMN locked_until;
@@ -0,0 +1,11 @@
+-- Each claimed job carries its own lock deadline, pushed forward by the
+-- worker's heartbeat while the job runs. Once it passes, the worker is
+-- presumed dead and any other worker may claim the job.
+ALTER TABLE jobs ADD COLUMN locked_until TIMESTAMPTZ;
+
+-- Jobs already running under the old worker get a generous deadline
+-- rather than being reclaimed mid-run by the first new worker to poll
+UPDATE jobs SET locked_until = locked_at + INTERVAL '2 hours' WHERE locked_at IS NOT NULL;
+
+-- Pickup: due jobs in priority order
+CREATE INDEX idx_jobs_priority_run_at ON jobs (priority DESC, run_at);
@@ -0,0 +1,46 @@
+-- Fold every partition's rows back into the legacy tables and restore
+-- them as plain tables
+
+ALTER TABLE snake_turns DETACH PARTITION snake_turns_legacy;
+INSERT INTO snake_turns_legacy (
+  snake_turn_id, turn_id, game_battlesnake_id, direction, created_at,
+  latency_ms, timed_out, error_kind, http_status
+)
+SELECT
+  snake_turn_id, turn_id, game_battlesnake_id, direction, created_at,
+  latency_ms, timed_out, error_kind, http_status
+FROM snake_turns;
+DROP TABLE snake_turns;
+
+ALTER TABLE turns DETACH PARTITION turns_legacy;
+INSERT INTO turns_legacy (turn_id, game_id, turn_number, frame_data, created_at, frame_bin)
+SELECT turn_id, game_id, turn_number, frame_data, created_at, frame_bin FROM turns;
+DROP TABLE turns;
+
+DROP FUNCTION IF EXISTS ensure_monthly_partitions(regclass, INT);
+
+ALTER TABLE turns_legacy RENAME TO turns;
+ALTER TABLE turns DROP CONSTRAINT turns_legacy_pkey;
+ALTER TABLE turns ADD CONSTRAINT turns_pkey PRIMARY KEY (turn_id);
+ALTER TABLE turns RENAME CONSTRAINT turns_legacy_game_id_turn_number_key TO turns_game_id_turn_number_key;
+ALTER TABLE turns RENAME CONSTRAINT turns_legacy_game_id_fkey TO turns_game_id_fkey;
+ALTER INDEX turns_legacy_game_id_idx RENAME TO turns_game_id_idx;
+ALTER INDEX turns_legacy_game_id_turn_number_idx RENAME TO turns_game_id_turn_number_idx;
+
+CREATE TRIGGER notify_turn_inserted
+  AFTER INSERT ON turns
+  FOR EACH ROW
+  EXECUTE FUNCTION notify_turn_inserted();
+
+ALTER TABLE snake_turns_legacy RENAME TO snake_turns;
+ALTER TABLE snake_turns DROP CONSTRAINT snake_turns_legacy_pkey;
+ALTER TABLE snake_turns ADD CONSTRAINT snake_turns_pkey PRIMARY KEY (snake_turn_id);
+ALTER TABLE snake_turns RENAME CONSTRAINT snake_turns_legacy_turn_id_game_battlesnake_id_key TO snake_turns_turn_id_game_battlesnake_id_key;
+ALTER TABLE snake_turns RENAME CONSTRAINT snake_turns_legacy_game_battlesnake_id_fkey TO snake_turns_game_battlesnake_id_fkey;
+ALTER INDEX idx_snake_turns_legacy_created_at RENAME TO idx_snake_turns_created_at;
+ALTER INDEX snake_turns_legacy_game_battlesnake_id_idx RENAME TO snake_turns_game_battlesnake_id_idx;
+ALTER INDEX snake_turns_legacy_turn_id_idx RENAME TO snake_turns_turn_id_idx;
+
+ALTER TABLE snake_turns
+  ADD CONSTRAINT snake_turns_turn_id_fkey
+  FOREIGN KEY (turn_id) REFERENCES turns(turn_id) ON DELETE CASCADE;
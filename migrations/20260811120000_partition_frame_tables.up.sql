@@ -0,0 +1,137 @@
+-- Range-partition the per-turn tables by month of created_at. turns and
+-- snake_turns are where the row count lives (a game writes one turn per
+-- move and one snake_turn per snake per move), so retention deletes and
+-- the admin time-window queries over snake_turns only touch the months
+-- they cover.
+--
+-- games and game_battlesnakes stay unpartitioned: a partitioned table's
+-- primary key must include the partition key, which would turn game_id and
+-- game_battlesnake_id into composite keys for every table that references
+-- them.
+--
+-- Partition keys also have to be part of any unique constraint, so:
+--   * turns loses UNIQUE (game_id, turn_number); the game runner is the
+--     only writer of a game's turns and resets them before a retry
+--   * snake_turns loses its foreign key to turns (turn_id alone is no
+--     longer unique); turns are only deleted with their game, and
+--     game_battlesnakes still cascades snake_turns away at the same time
+--
+-- Existing rows are not copied: each old table is attached as a single
+-- partition covering everything before next month, and monthly partitions
+-- take over from there.
+
+-- Keep a parent's monthly partitions created months_ahead months past the
+-- current one. Months already covered (by the legacy partition) are
+-- skipped. Returns how many partitions were created.
+CREATE OR REPLACE FUNCTION ensure_monthly_partitions(parent regclass, months_ahead INT)
+RETURNS INT AS $$
+DECLARE
+   base TIMESTAMP := date_trunc('month', now() AT TIME ZONE 'UTC');
+   lo TIMESTAMP;
+   partition_name TEXT;
+   created INT := 0;
+BEGIN
+   FOR i IN 0..months_ahead LOOP
+      lo := base + make_interval(months => i);
+      partition_name := parent::text || '_y' || to_char(lo, 'YYYY') || 'm' || to_char(lo, 'MM');
+      CONTINUE WHEN to_regclass(partition_name) IS NOT NULL;
+      BEGIN
+         EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %s FOR VALUES FROM (%L) TO (%L)',
+            partition_name,
+            parent,
+            lo AT TIME ZONE 'UTC',
+            (lo + INTERVAL '1 month') AT TIME ZONE 'UTC'
+         );
+         created := created + 1;
+      EXCEPTION
+         WHEN invalid_object_definition THEN
+            -- Overlaps the legacy partition
+            NULL;
+         WHEN check_violation THEN
+            -- The default partition already holds rows for this month
+            RAISE WARNING 'partition % not created: default partition has rows in its range', partition_name;
+      END;
+   END LOOP;
+   RETURN created;
+END;
+$$ language 'plpgsql';
+
+ALTER TABLE snake_turns DROP CONSTRAINT snake_turns_turn_id_fkey;
+
+-- turns
+
+ALTER TABLE turns RENAME TO turns_legacy;
+ALTER TABLE turns_legacy RENAME CONSTRAINT turns_pkey TO turns_legacy_pkey;
+ALTER TABLE turns_legacy RENAME CONSTRAINT turns_game_id_turn_number_key TO turns_legacy_game_id_turn_number_key;
+ALTER TABLE turns_legacy RENAME CONSTRAINT turns_game_id_fkey TO turns_legacy_game_id_fkey;
+ALTER INDEX turns_game_id_idx RENAME TO turns_legacy_game_id_idx;
+ALTER INDEX turns_game_id_turn_number_idx RENAME TO turns_legacy_game_id_turn_number_idx;
+DROP TRIGGER notify_turn_inserted ON turns_legacy;
+ALTER TABLE turns_legacy DROP CONSTRAINT turns_legacy_pkey;
+ALTER TABLE turns_legacy ADD CONSTRAINT turns_legacy_pkey PRIMARY KEY (turn_id, created_at);
+
+CREATE TABLE turns (
+  turn_id UUID NOT NULL DEFAULT uuid_generate_v4(),
+  game_id UUID NOT NULL REFERENCES games(game_id) ON DELETE CASCADE,
+  turn_number INTEGER NOT NULL,
+  frame_data JSONB,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  frame_bin BYTEA,
+  PRIMARY KEY (turn_id, created_at)
+) PARTITION BY RANGE (created_at);
+
+CREATE INDEX turns_game_id_turn_number_idx ON turns (game_id, turn_number);
+
+CREATE TRIGGER notify_turn_inserted
+  AFTER INSERT ON turns
+  FOR EACH ROW
+  EXECUTE FUNCTION notify_turn_inserted();
+
+-- snake_turns
+
+ALTER TABLE snake_turns RENAME TO snake_turns_legacy;
+ALTER TABLE snake_turns_legacy RENAME CONSTRAINT snake_turns_pkey TO snake_turns_legacy_pkey;
+ALTER TABLE snake_turns_legacy RENAME CONSTRAINT snake_turns_turn_id_game_battlesnake_id_key TO snake_turns_legacy_turn_id_game_battlesnake_id_key;
+ALTER TABLE snake_turns_legacy RENAME CONSTRAINT snake_turns_game_battlesnake_id_fkey TO snake_turns_legacy_game_battlesnake_id_fkey;
+ALTER INDEX idx_snake_turns_created_at RENAME TO idx_snake_turns_legacy_created_at;
+ALTER INDEX snake_turns_game_battlesnake_id_idx RENAME TO snake_turns_legacy_game_battlesnake_id_idx;
+ALTER INDEX snake_turns_turn_id_idx RENAME TO snake_turns_legacy_turn_id_idx;
+ALTER TABLE snake_turns_legacy DROP CONSTRAINT snake_turns_legacy_pkey;
+ALTER TABLE snake_turns_legacy ADD CONSTRAINT snake_turns_legacy_pkey PRIMARY KEY (snake_turn_id, created_at);
+
+CREATE TABLE snake_turns (
+  snake_turn_id UUID NOT NULL DEFAULT uuid_generate_v4(),
+  turn_id UUID NOT NULL,
+  game_battlesnake_id UUID NOT NULL REFERENCES game_battlesnakes(game_battlesnake_id) ON DELETE CASCADE,
+  direction TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  latency_ms INTEGER,
+  timed_out BOOLEAN NOT NULL DEFAULT false,
+  error_kind TEXT CONSTRAINT snake_turns_error_kind_check CHECK (error_kind IN ('timeout', 'network', 'http_status', 'parse')),
+  http_status SMALLINT,
+  PRIMARY KEY (snake_turn_id, created_at)
+) PARTITION BY RANGE (created_at);
+
+CREATE INDEX idx_snake_turns_created_at ON snake_turns (created_at);
+CREATE INDEX snake_turns_game_battlesnake_id_idx ON snake_turns (game_battlesnake_id);
+CREATE INDEX snake_turns_turn_id_idx ON snake_turns (turn_id);
+
+-- Attach the old tables, then the default partition (catches rows outside
+-- every monthly partition if maintenance ever falls behind) and the first
+-- monthly ones
+
+DO $$
+DECLARE
+   next_month TIMESTAMPTZ := (date_trunc('month', now() AT TIME ZONE 'UTC') + INTERVAL '1 month') AT TIME ZONE 'UTC';
+BEGIN
+   EXECUTE format('ALTER TABLE turns ATTACH PARTITION turns_legacy FOR VALUES FROM (MINVALUE) TO (%L)', next_month);
+   EXECUTE format('ALTER TABLE snake_turns ATTACH PARTITION snake_turns_legacy FOR VALUES FROM (MINVALUE) TO (%L)', next_month);
+END;
+$$;
+
+CREATE TABLE turns_default PARTITION OF turns DEFAULT;
+CREATE TABLE snake_turns_default PARTITION OF snake_turns DEFAULT;
+
+SELECT ensure_monthly_partitions('turns', 3);
+SELECT ensure_monthly_partitions('snake_turns', 3);
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS tournament_matches_loser_next_match_id_idx;
+
+ALTER TABLE tournament_matches
+    DROP COLUMN IF EXISTS loser_next_match_id,
+    DROP COLUMN IF EXISTS bracket;
+
+ALTER TABLE tournaments
+    DROP COLUMN IF EXISTS auto_advance,
+    DROP COLUMN IF EXISTS format;
@@ -0,0 +1,17 @@
+-- Tournament formats beyond single elimination. Double elimination adds a
+-- losers bracket and a grand final; round robin pairs every snake with
+-- every other one and crowns whoever tops the standings.
+ALTER TABLE tournaments
+    ADD COLUMN format TEXT NOT NULL DEFAULT 'single_elimination',
+    -- Queue each round as soon as the previous one finishes instead of
+    -- waiting for the owner to click "Run Round".
+    ADD COLUMN auto_advance BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE tournament_matches
+    -- winners / losers / grand_final for elimination brackets, pool for
+    -- round robin
+    ADD COLUMN bracket TEXT NOT NULL DEFAULT 'winners',
+    -- Double elimination: the match this match's loser drops into
+    ADD COLUMN loser_next_match_id UUID REFERENCES tournament_matches(match_id) ON DELETE SET NULL;
+
+CREATE INDEX tournament_matches_loser_next_match_id_idx ON tournament_matches (loser_next_match_id);
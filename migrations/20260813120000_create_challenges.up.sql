@@ -0,0 +1,26 @@
+-- One user's snake challenging another user's snake to an unranked game.
+-- Public opponents are played right away (the row is created `accepted`
+-- with its game); private ones wait in the opponent's inbox until they
+-- accept or decline.
+CREATE TABLE challenges (
+    challenge_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    challenger_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    challenger_battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    opponent_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    opponent_battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    board_size TEXT NOT NULL,
+    game_type TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (
+        status IN ('pending', 'accepted', 'declined', 'canceled')
+    ),
+    game_id UUID REFERENCES games (game_id) ON DELETE SET NULL,
+    responded_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX challenges_opponent_user_id_idx ON challenges (opponent_user_id, created_at DESC);
+CREATE INDEX challenges_challenger_user_id_idx ON challenges (challenger_user_id, created_at DESC);
+
+CREATE TRIGGER update_challenges_updated_at BEFORE
+UPDATE ON challenges FOR EACH ROW EXECUTE FUNCTION update_updated_at_column ();
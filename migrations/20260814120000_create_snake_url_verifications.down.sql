@@ -0,0 +1 @@
+DROP TABLE IF EXISTS snake_url_verifications;
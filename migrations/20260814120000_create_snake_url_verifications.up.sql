@@ -0,0 +1,23 @@
+-- Proof that a snake's owner controls its URL: the arena fetched the URL's
+-- GET / and found the snake's token in the response. Verification is tied
+-- to the URL it was done against, so changing a snake's URL un-verifies it.
+CREATE TABLE snake_url_verifications (
+    battlesnake_id UUID PRIMARY KEY REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    token TEXT NOT NULL DEFAULT 'arena-verify-' || replace(gen_random_uuid ()::text, '-', ''),
+    verified_url TEXT,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_snake_url_verifications_updated_at
+    BEFORE UPDATE ON snake_url_verifications
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column ();
+
+-- Snakes already on leaderboards keep playing: treat their current URL as
+-- verified rather than pausing every existing entry.
+INSERT INTO snake_url_verifications (battlesnake_id, verified_url, verified_at)
+SELECT DISTINCT b.battlesnake_id, b.url, NOW()
+FROM battlesnakes b
+JOIN leaderboard_entries le ON le.battlesnake_id = b.battlesnake_id;
@@ -0,0 +1,35 @@
+-- Per-user choice of how to hear about each group of notifications. Users
+-- without a row get the defaults: in-app on, email off.
+CREATE TABLE notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users (user_id) ON DELETE CASCADE,
+    rank_milestones_in_app BOOLEAN NOT NULL DEFAULT TRUE,
+    rank_milestones_email BOOLEAN NOT NULL DEFAULT FALSE,
+    timeout_streaks_in_app BOOLEAN NOT NULL DEFAULT TRUE,
+    timeout_streaks_email BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_notification_preferences_updated_at BEFORE
+UPDATE ON notification_preferences FOR EACH ROW EXECUTE FUNCTION update_updated_at_column ();
+
+-- Every notification raised for a user. A row is recorded even when the
+-- user only wants email (`in_app` false hides it from their inbox), so
+-- `dedup_key` stops a retried job from delivering the same event twice.
+CREATE TABLE notifications (
+    notification_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (
+        kind IN ('top_ten_entered', 'top_ten_left', 'timeout_streak')
+    ),
+    message TEXT NOT NULL,
+    link TEXT,
+    in_app BOOLEAN NOT NULL,
+    dedup_key TEXT NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, dedup_key)
+);
+
+CREATE INDEX notifications_user_id_idx ON notifications (user_id, created_at DESC)
+WHERE in_app;
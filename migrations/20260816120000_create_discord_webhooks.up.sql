@@ -0,0 +1,14 @@
+-- A user's own Discord webhook, posted to when their leaderboard snakes
+-- finish games, move a long way in the rankings, or get auto-paused.
+CREATE TABLE discord_webhooks (
+    user_id UUID PRIMARY KEY REFERENCES users (user_id) ON DELETE CASCADE,
+    webhook_url TEXT NOT NULL,
+    notify_game_results BOOLEAN NOT NULL DEFAULT TRUE,
+    notify_rank_changes BOOLEAN NOT NULL DEFAULT TRUE,
+    notify_pauses BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_discord_webhooks_updated_at BEFORE
+UPDATE ON discord_webhooks FOR EACH ROW EXECUTE FUNCTION update_updated_at_column ();
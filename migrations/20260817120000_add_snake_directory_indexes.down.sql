@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_leaderboard_entries_battlesnake_id;
+DROP INDEX IF EXISTS idx_battlesnakes_public_created_at;
+DROP INDEX IF EXISTS idx_users_display_name_trgm;
+DROP INDEX IF EXISTS idx_users_github_login_trgm;
+DROP INDEX IF EXISTS idx_battlesnakes_public_name_trgm;
+-- pg_trgm is left installed: other objects may have come to depend on it
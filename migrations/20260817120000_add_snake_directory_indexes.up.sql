@@ -0,0 +1,14 @@
+-- Public snake directory (/snakes): substring search over snake names and
+-- owner logins, and keyset pagination over public snakes, newest first.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_battlesnakes_public_name_trgm ON battlesnakes USING gin (name gin_trgm_ops)
+    WHERE visibility = 'public';
+CREATE INDEX idx_users_github_login_trgm ON users USING gin (github_login gin_trgm_ops);
+CREATE INDEX idx_users_display_name_trgm ON users USING gin (display_name gin_trgm_ops);
+
+CREATE INDEX idx_battlesnakes_public_created_at ON battlesnakes (created_at DESC, battlesnake_id DESC)
+    WHERE visibility = 'public';
+
+-- Leaderboard memberships for a page of snakes
+CREATE INDEX idx_leaderboard_entries_battlesnake_id ON leaderboard_entries (battlesnake_id);
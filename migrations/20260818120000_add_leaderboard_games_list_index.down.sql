@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_leaderboard_games_leaderboard_created_at;
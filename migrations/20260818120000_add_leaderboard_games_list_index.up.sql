@@ -0,0 +1,3 @@
+-- The /games list narrowed to one leaderboard walks its links from the
+-- window start on.
+CREATE INDEX idx_leaderboard_games_leaderboard_created_at ON leaderboard_games (leaderboard_id, created_at);
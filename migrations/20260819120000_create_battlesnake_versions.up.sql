@@ -0,0 +1,20 @@
+-- Each deploy of a snake we've seen: a new URL, or a new `version` reported
+-- from its GET /. Rating charts and game histories mark where one version
+-- ends and the next begins.
+CREATE TABLE battlesnake_versions (
+    battlesnake_version_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    -- NULL until the snake reports one
+    version TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_battlesnake_versions_battlesnake_created_at
+    ON battlesnake_versions (battlesnake_id, created_at);
+
+-- Existing snakes start on the version they have now, dated from when they
+-- were created, so their first change shows up as a change.
+INSERT INTO battlesnake_versions (battlesnake_id, url, created_at)
+SELECT battlesnake_id, url, created_at
+FROM battlesnakes;
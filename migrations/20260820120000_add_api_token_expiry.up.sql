@@ -0,0 +1,4 @@
+-- When a token stops working on its own. NULL never expires, which is what
+-- every token issued before expiry existed keeps.
+ALTER TABLE api_tokens
+ADD COLUMN expires_at TIMESTAMPTZ;
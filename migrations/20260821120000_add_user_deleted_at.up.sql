@@ -0,0 +1,4 @@
+-- Set once an account has been deleted and its personal data scrubbed.
+-- The row stays so the games and ratings it took part in keep their history.
+ALTER TABLE users
+ADD COLUMN deleted_at TIMESTAMPTZ;
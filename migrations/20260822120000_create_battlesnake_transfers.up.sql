@@ -0,0 +1,29 @@
+-- Handing a snake to another user. The owner offers it, and the snake only
+-- changes hands once the recipient accepts. `include_history` keeps the
+-- snake's leaderboard entries (and with them its ratings and results);
+-- without it the new owner starts the leaderboards fresh.
+CREATE TABLE battlesnake_transfers (
+    battlesnake_transfer_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    from_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    to_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    include_history BOOLEAN NOT NULL DEFAULT TRUE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (
+        status IN ('pending', 'accepted', 'declined', 'canceled')
+    ),
+    responded_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (from_user_id <> to_user_id)
+);
+
+-- A snake can only be on offer to one user at a time
+CREATE UNIQUE INDEX battlesnake_transfers_pending_idx ON battlesnake_transfers (battlesnake_id)
+WHERE
+    status = 'pending';
+
+CREATE INDEX battlesnake_transfers_to_user_id_idx ON battlesnake_transfers (to_user_id, created_at DESC);
+CREATE INDEX battlesnake_transfers_from_user_id_idx ON battlesnake_transfers (from_user_id, created_at DESC);
+
+CREATE TRIGGER update_battlesnake_transfers_updated_at BEFORE
+UPDATE ON battlesnake_transfers FOR EACH ROW EXECUTE FUNCTION update_updated_at_column ();
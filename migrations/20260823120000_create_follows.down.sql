@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS leaderboard_follows;
+DROP TABLE IF EXISTS snake_follows;
@@ -0,0 +1,16 @@
+-- Snakes and leaderboards a user follows, for their feed
+CREATE TABLE snake_follows (
+    user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, battlesnake_id)
+);
+
+CREATE INDEX snake_follows_battlesnake_id_idx ON snake_follows (battlesnake_id);
+
+CREATE TABLE leaderboard_follows (
+    user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    leaderboard_id UUID NOT NULL REFERENCES leaderboards (leaderboard_id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, leaderboard_id)
+);
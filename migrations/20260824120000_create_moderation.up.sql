@@ -0,0 +1,34 @@
+-- Snake names and shouts show up on public pages, so they're screened
+-- against an admin-maintained word list. Terms are stored normalized (see
+-- `models::moderation::normalize`) so matching is a plain comparison.
+CREATE TABLE moderation_terms (
+    moderation_term_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    term TEXT NOT NULL UNIQUE CHECK (term <> ''),
+    created_by_user_id UUID REFERENCES users (user_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The admin review queue. A shout that hits the word list is blanked before
+-- it's stored and queued here; an existing snake name is queued when a term
+-- added later matches it (new names that match are refused outright).
+CREATE TABLE moderation_flags (
+    moderation_flag_id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    battlesnake_id UUID NOT NULL REFERENCES battlesnakes (battlesnake_id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('name', 'shout')),
+    content TEXT NOT NULL,
+    matched_term TEXT NOT NULL,
+    -- The game a shout was made in
+    game_id UUID REFERENCES games (game_id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'removed')),
+    reviewed_by_user_id UUID REFERENCES users (user_id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A snake that repeats the same shout every turn (or every game) is one
+-- item to review, not hundreds
+CREATE UNIQUE INDEX moderation_flags_pending_idx ON moderation_flags (battlesnake_id, kind, content)
+WHERE
+    status = 'pending';
+
+CREATE INDEX moderation_flags_status_idx ON moderation_flags (status, created_at DESC);
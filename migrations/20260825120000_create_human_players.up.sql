@@ -0,0 +1,21 @@
+-- "Play as a snake": a signed-in user steers one of their own snakes in an
+-- unranked game from the browser. The runner takes that seat's move from
+-- `heading`, which the player's browser keeps current over a WebSocket,
+-- instead of calling the snake's URL.
+CREATE TABLE human_players (
+    game_battlesnake_id UUID PRIMARY KEY REFERENCES game_battlesnakes (game_battlesnake_id) ON DELETE CASCADE,
+    game_id UUID NOT NULL REFERENCES games (game_id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE,
+    -- NULL until the first key press; the snake keeps going the way it was
+    heading TEXT CHECK (heading IN ('up', 'down', 'left', 'right')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One human seat per game
+CREATE UNIQUE INDEX human_players_game_id_idx ON human_players (game_id);
+
+CREATE INDEX human_players_user_id_idx ON human_players (user_id, created_at DESC);
+
+CREATE TRIGGER update_human_players_updated_at BEFORE
+UPDATE ON human_players FOR EACH ROW EXECUTE FUNCTION update_updated_at_column ();
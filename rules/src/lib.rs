@@ -4,6 +4,9 @@ pub mod royale;
 pub mod standard;
 pub mod types;
 
+#[cfg(test)]
+mod official;
+
 pub use royale::RoyaleSettings;
 pub use types::{
     BOARD_SIZE_MEDIUM, BoardState, Direction, EliminationCause, Point, RulesError,
@@ -0,0 +1,592 @@
+//! Parity tests against the official rules.
+//!
+//! `reference` below is a transcription of the standard ruleset from
+//! BattlesnakeOfficial/rules (`standard.go` and its pipeline stages), the
+//! implementation play.battlesnake.com runs. It deliberately keeps the Go
+//! code's shape rather than this crate's: its own board types, the official
+//! elimination cause strings, moves looked up by snake ID, and the checks in
+//! the order Go makes them. The property tests resolve random boards with
+//! both and require identical results, so a change to [`crate::standard`]
+//! that drifts from the official semantics fails here even when it still
+//! agrees with the hand-written cases in `standard.rs`.
+//!
+//! Food spawning and royale hazard placement are random on both sides and
+//! aren't compared here; hazard damage is.
+
+mod reference {
+    use crate::types::Point;
+
+    pub const SNAKE_MAX_HEALTH: i32 = 100;
+
+    pub const NOT_ELIMINATED: &str = "";
+    pub const ELIMINATED_BY_COLLISION: &str = "snake-collision";
+    pub const ELIMINATED_BY_SELF_COLLISION: &str = "snake-self-collision";
+    pub const ELIMINATED_BY_OUT_OF_HEALTH: &str = "out-of-health";
+    pub const ELIMINATED_BY_HEAD_TO_HEAD_COLLISION: &str = "head-collision";
+    pub const ELIMINATED_BY_OUT_OF_BOUNDS: &str = "wall-collision";
+    pub const ELIMINATED_BY_HAZARD: &str = "hazard";
+
+    pub const MOVE_UP: &str = "up";
+    pub const MOVE_DOWN: &str = "down";
+    pub const MOVE_LEFT: &str = "left";
+    pub const MOVE_RIGHT: &str = "right";
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Snake {
+        pub id: String,
+        pub body: Vec<Point>,
+        pub health: i32,
+        pub eliminated_cause: &'static str,
+        pub eliminated_on_turn: i32,
+        pub eliminated_by: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BoardState {
+        pub turn: i32,
+        pub height: i32,
+        pub width: i32,
+        pub food: Vec<Point>,
+        pub snakes: Vec<Snake>,
+        pub hazards: Vec<Point>,
+    }
+
+    pub struct SnakeMove {
+        pub id: String,
+        pub mv: &'static str,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Error {
+        ZeroLengthSnake,
+        NoMoveFound,
+    }
+
+    type Stage = fn(&mut BoardState, i32, &[SnakeMove]) -> Result<bool, Error>;
+
+    /// `StandardRuleset.Execute`: run the stages in order, stopping at the
+    /// first one that ends the game. The engine bumps the turn afterwards.
+    pub fn execute(
+        b: &mut BoardState,
+        hazard_damage_per_turn: i32,
+        moves: &[SnakeMove],
+    ) -> Result<bool, Error> {
+        let stages: [Stage; 6] = [
+            game_over_standard,
+            move_snakes_standard,
+            reduce_snake_health_standard,
+            damage_hazards_standard,
+            feed_snakes_standard,
+            eliminate_snakes_standard,
+        ];
+        for stage in stages {
+            if stage(b, hazard_damage_per_turn, moves)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn game_over_standard(b: &mut BoardState, _: i32, _: &[SnakeMove]) -> Result<bool, Error> {
+        let num_snakes_remaining = b
+            .snakes
+            .iter()
+            .filter(|snake| snake.eliminated_cause == NOT_ELIMINATED)
+            .count();
+        Ok(num_snakes_remaining <= 1)
+    }
+
+    fn move_snakes_standard(
+        b: &mut BoardState,
+        _: i32,
+        moves: &[SnakeMove],
+    ) -> Result<bool, Error> {
+        // If no moves are passed, pass on modifying the initial board state
+        if moves.is_empty() {
+            return Ok(false);
+        }
+
+        // Sanity check that all non-eliminated snakes have moves and bodies
+        for snake in &b.snakes {
+            if snake.eliminated_cause != NOT_ELIMINATED {
+                continue;
+            }
+            if snake.body.is_empty() {
+                return Err(Error::ZeroLengthSnake);
+            }
+            if !moves.iter().any(|m| m.id == snake.id) {
+                return Err(Error::NoMoveFound);
+            }
+        }
+
+        for snake in &mut b.snakes {
+            if snake.eliminated_cause != NOT_ELIMINATED {
+                continue;
+            }
+            for m in moves {
+                if m.id != snake.id {
+                    continue;
+                }
+                let head = snake.body[0];
+                let new_head = match m.mv {
+                    MOVE_DOWN => Point::new(head.x, head.y - 1),
+                    MOVE_LEFT => Point::new(head.x - 1, head.y),
+                    MOVE_RIGHT => Point::new(head.x + 1, head.y),
+                    MOVE_UP => Point::new(head.x, head.y + 1),
+                    other => unreachable!("moves are generated valid, got {other}"),
+                };
+                // Append new head, pop old tail
+                snake.body.truncate(snake.body.len() - 1);
+                snake.body.insert(0, new_head);
+            }
+        }
+        Ok(false)
+    }
+
+    fn reduce_snake_health_standard(
+        b: &mut BoardState,
+        _: i32,
+        _: &[SnakeMove],
+    ) -> Result<bool, Error> {
+        for snake in &mut b.snakes {
+            if snake.eliminated_cause == NOT_ELIMINATED {
+                snake.health -= 1;
+            }
+        }
+        Ok(false)
+    }
+
+    fn damage_hazards_standard(
+        b: &mut BoardState,
+        hazard_damage_per_turn: i32,
+        _: &[SnakeMove],
+    ) -> Result<bool, Error> {
+        let turn = b.turn;
+        for snake in &mut b.snakes {
+            if snake.eliminated_cause != NOT_ELIMINATED {
+                continue;
+            }
+            let head = snake.body[0];
+            for p in &b.hazards {
+                if head != *p {
+                    continue;
+                }
+                // If there's a food in this square, don't reduce health
+                if b.food.contains(p) {
+                    continue;
+                }
+
+                // Snake is in a hazard, reduce health
+                snake.health = (snake.health - hazard_damage_per_turn).clamp(0, SNAKE_MAX_HEALTH);
+                if snake_is_out_of_health(snake) {
+                    eliminate_snake(snake, ELIMINATED_BY_HAZARD, "", turn + 1);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn feed_snakes_standard(b: &mut BoardState, _: i32, _: &[SnakeMove]) -> Result<bool, Error> {
+        let mut new_food = Vec::new();
+        for food in &b.food {
+            let mut food_has_been_eaten = false;
+            for snake in &mut b.snakes {
+                // Ignore eliminated and zero-length snakes, they can't eat
+                if snake.eliminated_cause != NOT_ELIMINATED || snake.body.is_empty() {
+                    continue;
+                }
+                if snake.body[0] == *food {
+                    feed_snake(snake);
+                    food_has_been_eaten = true;
+                }
+            }
+            // Persist food to next BoardState if not eaten
+            if !food_has_been_eaten {
+                new_food.push(*food);
+            }
+        }
+        b.food = new_food;
+        Ok(false)
+    }
+
+    fn feed_snake(snake: &mut Snake) {
+        let tail = snake.body[snake.body.len() - 1];
+        snake.body.push(tail);
+        snake.health = SNAKE_MAX_HEALTH;
+    }
+
+    fn eliminate_snakes_standard(
+        b: &mut BoardState,
+        _: i32,
+        _: &[SnakeMove],
+    ) -> Result<bool, Error> {
+        // First order snake indices by length. Go's `sort.Slice` isn't stable
+        // in general, but it insertion-sorts (stably) below 12 elements, and
+        // no game has that many snakes.
+        let mut snake_indices_by_length: Vec<usize> = (0..b.snakes.len()).collect();
+        snake_indices_by_length
+            .sort_by(|&i, &j| b.snakes[j].body.len().cmp(&b.snakes[i].body.len()));
+
+        // First, iterate over all non-eliminated snakes and eliminate the ones
+        // that are out of health or have moved out of bounds
+        let (width, height, turn) = (b.width, b.height, b.turn);
+        for snake in &mut b.snakes {
+            if snake.eliminated_cause != NOT_ELIMINATED {
+                continue;
+            }
+            if snake.body.is_empty() {
+                return Err(Error::ZeroLengthSnake);
+            }
+            if snake_is_out_of_health(snake) {
+                eliminate_snake(snake, ELIMINATED_BY_OUT_OF_HEALTH, "", turn + 1);
+                continue;
+            }
+            if snake_is_out_of_bounds(snake, width, height) {
+                eliminate_snake(snake, ELIMINATED_BY_OUT_OF_BOUNDS, "", turn + 1);
+                continue;
+            }
+        }
+
+        // Next, look for any collisions. Collision eliminations are applied
+        // after this check so that snakes can collide with each other and be
+        // properly eliminated.
+        let mut collision_eliminations: Vec<(String, &'static str, String)> = Vec::new();
+        for snake in &b.snakes {
+            if snake.eliminated_cause != NOT_ELIMINATED {
+                continue;
+            }
+            if snake.body.is_empty() {
+                return Err(Error::ZeroLengthSnake);
+            }
+
+            // Check for self-collisions first
+            if snake_has_body_collided(snake, snake) {
+                collision_eliminations.push((
+                    snake.id.clone(),
+                    ELIMINATED_BY_SELF_COLLISION,
+                    snake.id.clone(),
+                ));
+                continue;
+            }
+
+            // Check for body collisions with other snakes second
+            let body_collided_with = snake_indices_by_length
+                .iter()
+                .map(|&other_index| &b.snakes[other_index])
+                .filter(|other| other.eliminated_cause == NOT_ELIMINATED)
+                .find(|other| snake.id != other.id && snake_has_body_collided(snake, other));
+            if let Some(other) = body_collided_with {
+                collision_eliminations.push((
+                    snake.id.clone(),
+                    ELIMINATED_BY_COLLISION,
+                    other.id.clone(),
+                ));
+                continue;
+            }
+
+            // Check for head-to-heads last
+            let lost_head_to_head_with = snake_indices_by_length
+                .iter()
+                .map(|&other_index| &b.snakes[other_index])
+                .filter(|other| other.eliminated_cause == NOT_ELIMINATED)
+                .find(|other| snake.id != other.id && snake_has_lost_head_to_head(snake, other));
+            if let Some(other) = lost_head_to_head_with {
+                collision_eliminations.push((
+                    snake.id.clone(),
+                    ELIMINATED_BY_HEAD_TO_HEAD_COLLISION,
+                    other.id.clone(),
+                ));
+            }
+        }
+
+        // Apply collision eliminations
+        for (id, cause, by) in collision_eliminations {
+            if let Some(snake) = b.snakes.iter_mut().find(|snake| snake.id == id) {
+                eliminate_snake(snake, cause, &by, turn + 1);
+            }
+        }
+        Ok(false)
+    }
+
+    fn snake_is_out_of_health(s: &Snake) -> bool {
+        s.health <= 0
+    }
+
+    fn snake_is_out_of_bounds(s: &Snake, width: i32, height: i32) -> bool {
+        s.body
+            .iter()
+            .any(|p| p.x < 0 || p.x >= width || p.y < 0 || p.y >= height)
+    }
+
+    fn snake_has_body_collided(s: &Snake, other: &Snake) -> bool {
+        let head = s.body[0];
+        other.body.iter().skip(1).any(|body| *body == head)
+    }
+
+    fn snake_has_lost_head_to_head(s: &Snake, other: &Snake) -> bool {
+        if s.body[0] == other.body[0] {
+            return s.body.len() <= other.body.len();
+        }
+        false
+    }
+
+    fn eliminate_snake(s: &mut Snake, cause: &'static str, by: &str, turn: i32) {
+        s.eliminated_cause = cause;
+        s.eliminated_by = by.to_string();
+        s.eliminated_on_turn = turn;
+    }
+}
+
+mod tests {
+    use super::reference;
+    use crate::standard::execute_turn;
+    use crate::types::*;
+    use proptest::collection::vec as prop_vec;
+    use proptest::prelude::*;
+
+    fn cause_name(cause: &EliminationCause) -> &'static str {
+        match cause {
+            EliminationCause::NotEliminated => reference::NOT_ELIMINATED,
+            EliminationCause::OutOfHealth => reference::ELIMINATED_BY_OUT_OF_HEALTH,
+            EliminationCause::OutOfBounds => reference::ELIMINATED_BY_OUT_OF_BOUNDS,
+            EliminationCause::SelfCollision => reference::ELIMINATED_BY_SELF_COLLISION,
+            EliminationCause::Collision => reference::ELIMINATED_BY_COLLISION,
+            EliminationCause::HeadToHeadCollision => {
+                reference::ELIMINATED_BY_HEAD_TO_HEAD_COLLISION
+            }
+            EliminationCause::Hazard => reference::ELIMINATED_BY_HAZARD,
+        }
+    }
+
+    fn move_name(direction: Direction) -> &'static str {
+        match direction {
+            Direction::Up => reference::MOVE_UP,
+            Direction::Down => reference::MOVE_DOWN,
+            Direction::Left => reference::MOVE_LEFT,
+            Direction::Right => reference::MOVE_RIGHT,
+        }
+    }
+
+    fn to_reference(board: &BoardState) -> reference::BoardState {
+        reference::BoardState {
+            turn: board.turn,
+            height: board.height,
+            width: board.width,
+            food: board.food.clone(),
+            snakes: board
+                .snakes
+                .iter()
+                .map(|s| reference::Snake {
+                    id: s.id.clone(),
+                    body: s.body.clone(),
+                    health: s.health,
+                    eliminated_cause: cause_name(&s.eliminated_cause),
+                    eliminated_on_turn: s.eliminated_on_turn,
+                    eliminated_by: s.eliminated_by.clone(),
+                })
+                .collect(),
+            hazards: board.hazards.clone(),
+        }
+    }
+
+    /// Resolve one turn with both implementations and require the same
+    /// outcome. Returns whether the game continues.
+    fn resolve_both(
+        board: &mut BoardState,
+        expected: &mut reference::BoardState,
+        moves: &[SnakeMove],
+        settings: &StandardSettings,
+    ) -> Result<bool, TestCaseError> {
+        let reference_moves: Vec<reference::SnakeMove> = moves
+            .iter()
+            .map(|m| reference::SnakeMove {
+                id: m.id.clone(),
+                mv: move_name(m.direction),
+            })
+            .collect();
+
+        let actual = execute_turn(board, moves, settings);
+        let official =
+            reference::execute(expected, settings.hazard_damage_per_turn, &reference_moves);
+
+        match (actual, official) {
+            (Ok(actual_over), Ok(official_over)) => {
+                prop_assert_eq!(actual_over, official_over, "game-over disagrees");
+                if !official_over {
+                    expected.turn += 1;
+                }
+                prop_assert_eq!(&to_reference(board), &*expected);
+                Ok(!official_over)
+            }
+            (Err(RulesError::NoMoveFound(_)), Err(reference::Error::NoMoveFound))
+            | (Err(RulesError::ZeroLengthSnake(_)), Err(reference::Error::ZeroLengthSnake)) => {
+                Ok(false)
+            }
+            (actual, official) => Err(TestCaseError::fail(format!(
+                "outcomes differ: {actual:?} vs official {official:?}"
+            ))),
+        }
+    }
+
+    // --- Strategy functions ---
+
+    /// A body as a walk from the head. Steps may stand still, so bodies can
+    /// be stacked as at the start of a game, and may cross the snake itself
+    /// or the walls: the rules have to resolve whatever the moves produce.
+    fn arb_body(width: i32, height: i32) -> impl Strategy<Value = Vec<Point>> {
+        let steps = prop_vec(
+            prop_oneof![
+                Just((0, 0)),
+                Just((0, 1)),
+                Just((0, -1)),
+                Just((-1, 0)),
+                Just((1, 0)),
+            ],
+            0..=7,
+        );
+        (0..width, 0..height, steps).prop_map(|(x, y, steps)| {
+            let mut body = vec![Point::new(x, y)];
+            for (dx, dy) in steps {
+                let last = body[body.len() - 1];
+                body.push(Point::new(last.x + dx, last.y + dy));
+            }
+            body
+        })
+    }
+
+    fn arb_snake(width: i32, height: i32) -> impl Strategy<Value = Snake> {
+        (
+            arb_body(width, height),
+            prop_oneof![1..=3i32, 1..=SNAKE_MAX_HEALTH],
+            prop::bool::weighted(0.1),
+        )
+            .prop_map(|(body, health, eliminated)| Snake {
+                id: String::new(),
+                body,
+                health,
+                eliminated_cause: if eliminated {
+                    EliminationCause::OutOfHealth
+                } else {
+                    EliminationCause::NotEliminated
+                },
+                eliminated_by: String::new(),
+                eliminated_on_turn: 0,
+            })
+    }
+
+    fn arb_direction() -> impl Strategy<Value = Direction> {
+        prop_oneof![
+            Just(Direction::Up),
+            Just(Direction::Down),
+            Just(Direction::Left),
+            Just(Direction::Right),
+        ]
+    }
+
+    fn arb_point(width: i32, height: i32) -> impl Strategy<Value = Point> {
+        (0..width, 0..height).prop_map(|(x, y)| Point::new(x, y))
+    }
+
+    /// Small boards crowded with snakes, so most turns have collisions,
+    /// meals or hazard hits. Food squares are distinct, as the official
+    /// engine never stacks food; hazards may stack.
+    fn arb_board() -> impl Strategy<Value = BoardState> {
+        (2..=11i32, 2..=11i32)
+            .prop_flat_map(|(width, height)| {
+                (
+                    Just((width, height)),
+                    prop_vec(arb_snake(width, height), 1..=6),
+                    prop_vec(arb_point(width, height), 0..=6),
+                    prop_vec(arb_point(width, height), 0..=8),
+                    0..=300i32,
+                )
+            })
+            .prop_map(|((width, height), snakes, food, hazards, turn)| {
+                let snakes = snakes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, snake)| Snake {
+                        id: format!("snake-{i}"),
+                        eliminated_on_turn: if snake.eliminated_cause.is_eliminated() {
+                            turn
+                        } else {
+                            0
+                        },
+                        ..snake
+                    })
+                    .collect();
+                let mut food = food;
+                food.sort();
+                food.dedup();
+                BoardState {
+                    turn,
+                    width,
+                    height,
+                    food,
+                    snakes,
+                    hazards,
+                }
+            })
+    }
+
+    /// One move per snake, occasionally missing one so the error paths are
+    /// compared too.
+    fn moves_for(board: &BoardState, directions: &[(Direction, bool)]) -> Vec<SnakeMove> {
+        board
+            .snakes
+            .iter()
+            .zip(directions.iter().cycle())
+            .filter(|(_, (_, submitted))| *submitted)
+            .map(|(snake, (direction, _))| SnakeMove {
+                id: snake.id.clone(),
+                direction: *direction,
+            })
+            .collect()
+    }
+
+    fn arb_turn_moves() -> impl Strategy<Value = Vec<(Direction, bool)>> {
+        prop_vec((arb_direction(), prop::bool::weighted(0.97)), 6)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1024))]
+
+        #[test]
+        fn prop_turn_matches_official_rules(
+            mut board in arb_board(),
+            directions in arb_turn_moves(),
+            hazard_damage_per_turn in 0..=100i32,
+        ) {
+            let settings = StandardSettings {
+                hazard_damage_per_turn,
+                ..StandardSettings::default()
+            };
+            let mut expected = to_reference(&board);
+            let moves = moves_for(&board, &directions);
+            resolve_both(&mut board, &mut expected, &moves, &settings)?;
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(256))]
+
+        #[test]
+        fn prop_game_matches_official_rules(
+            mut board in arb_board(),
+            turns in prop_vec(arb_turn_moves(), 1..=40),
+            hazard_damage_per_turn in 0..=30i32,
+        ) {
+            let settings = StandardSettings {
+                hazard_damage_per_turn,
+                ..StandardSettings::default()
+            };
+            let mut expected = to_reference(&board);
+            for directions in &turns {
+                let moves = moves_for(&board, directions);
+                if !resolve_both(&mut board, &mut expected, &moves, &settings)? {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Benchmarks for the work done once per game or once per turn: rating
+//! updates after every leaderboard game, match selection on every
+//! matchmaker pass, and frame encoding for every stored turn.
+//!
+//! Run with `cargo bench -p arena`; criterion compares against the previous
+//! run's saved baseline.
+
+use std::hint::black_box;
+
+use arena::bench::{
+    EngineGameFrame, FrameCoord, FrameSnake, LeaderboardEntry, calculate_rating_updates,
+    decode_frame, encode_frame, select_match,
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::SeedableRng as _;
+use uuid::Uuid;
+
+fn entries(count: usize, rng: &mut impl rand::Rng) -> Vec<LeaderboardEntry> {
+    (0..count)
+        .map(|_| {
+            let mu: f64 = rng.gen_range(10.0..40.0);
+            let sigma: f64 = rng.gen_range(1.0..8.333);
+            LeaderboardEntry {
+                leaderboard_entry_id: Uuid::new_v4(),
+                leaderboard_id: Uuid::nil(),
+                battlesnake_id: Uuid::new_v4(),
+                mu,
+                sigma,
+                display_score: mu - 3.0 * sigma,
+                games_played: rng.gen_range(0..500),
+                first_place_finishes: 0,
+                non_first_finishes: 0,
+                disabled_at: None,
+                disabled_reason: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            }
+        })
+        .collect()
+}
+
+/// A late-game 11x11 frame: `snakes` snakes of length 20, plus food and
+/// hazards
+fn frame(snakes: usize) -> EngineGameFrame {
+    let coord = |i: usize| FrameCoord {
+        x: (i % 11) as i32,
+        y: (i / 11 % 11) as i32,
+    };
+    EngineGameFrame {
+        turn: 250,
+        snakes: (0..snakes)
+            .map(|s| FrameSnake {
+                id: Uuid::new_v4().to_string(),
+                name: format!("snake-{s}"),
+                body: (0..20).map(|i| coord(s * 20 + i)).collect(),
+                health: 87,
+                color: "#ff7043".to_string(),
+                head_type: "default".to_string(),
+                tail_type: "default".to_string(),
+                latency: "123".to_string(),
+                shout: String::new(),
+                squad: String::new(),
+                api_version: "1".to_string(),
+                author: "bench".to_string(),
+                death: None,
+                eliminated_cause: String::new(),
+                eliminated_by: String::new(),
+            })
+            .collect(),
+        food: (0..10).map(|i| coord(i * 7)).collect(),
+        hazards: (0..30).map(coord).collect(),
+    }
+}
+
+fn rating_updates(c: &mut Criterion) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let mut group = c.benchmark_group("calculate_rating_updates");
+    for snakes in [2, 4, 8] {
+        let game: Vec<(LeaderboardEntry, i32)> =
+            entries(snakes, &mut rng).into_iter().zip(1..).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(snakes), &game, |b, game| {
+            b.iter(|| calculate_rating_updates(black_box(game)))
+        });
+    }
+    group.finish();
+}
+
+fn match_selection(c: &mut Criterion) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let mut group = c.benchmark_group("select_match");
+    for pool in [100, 1_000, 10_000] {
+        let pool_entries = entries(pool, &mut rng);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(pool),
+            &pool_entries,
+            |b, pool_entries| b.iter(|| select_match(&mut rng, black_box(pool_entries), 4)),
+        );
+    }
+    group.finish();
+}
+
+fn frame_serialization(c: &mut Criterion) {
+    let frame = frame(4);
+    let encoded = encode_frame(&frame);
+
+    let mut group = c.benchmark_group("frame");
+    group.bench_function("encode", |b| b.iter(|| encode_frame(black_box(&frame))));
+    group.bench_function("decode", |b| {
+        b.iter(|| decode_frame(black_box(&encoded)).unwrap())
+    });
+    // The board viewer's JSON, served for every frame a viewer fetches
+    group.bench_function("to_json", |b| {
+        b.iter(|| serde_json::to_value(black_box(&frame)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    rating_updates,
+    match_selection,
+    frame_serialization
+);
+criterion_main!(benches);
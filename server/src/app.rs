@@ -0,0 +1,308 @@
+//! Process startup shared by the `arena` web binary and the `arena-worker`
+//! game worker. Both build the same [`AppState`]; the worker never serves
+//! HTTP, so game execution can be scaled apart from web traffic.
+
+use cja::{
+    server::run_server,
+    setup::{setup_sentry, setup_tracing},
+};
+use color_eyre::eyre::eyre;
+use tracing::info;
+
+use crate::{
+    config, cron, game_channels, job_worker, jobs, play_import, routes, shutdown,
+    snake_failure_log, state::AppState, telemetry,
+};
+
+/// Which binary is starting
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// `arena`: the web server, plus jobs and cron unless they are disabled
+    /// (`JOBS_DISABLED`, `CRON_DISABLED`) to leave them to workers
+    Web,
+    /// `arena-worker`: jobs and cron only, never the web server
+    Worker,
+}
+
+/// Run the process for `role` until it fails or is told to shut down
+pub fn main(role: Role) -> color_eyre::Result<()> {
+    // Initialize Sentry for error tracking
+    let _sentry_guard = setup_sentry();
+
+    // One-shot subcommand: copy play's DB into the migration staging
+    // tables, then exit (no server, no job workers).
+    if role == Role::Web && std::env::args().nth(1).as_deref() == Some("import-play") {
+        return tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?
+            .block_on(async { play_import::run_import().await });
+    }
+
+    // Read all configuration once, here, before anything else. Downstream
+    // code takes values from this struct (via AppState) rather than
+    // reaching for the environment itself.
+    let mut config = config::AppConfig::from_env()?;
+    if role == Role::Worker {
+        config.features.server = false;
+    }
+
+    // Configure tokio worker threads as a multiplier on CPU core count.
+    // Since game execution is I/O-bound (snake API calls ~500ms each),
+    // we want many more threads than cores to maximize throughput.
+    let core_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let worker_threads = core_count * config.tokio_worker_multiplier;
+    eprintln!(
+        "Tokio workers: {worker_threads} ({core_count} cores x {} multiplier)",
+        config.tokio_worker_multiplier
+    );
+
+    // Create and run the tokio runtime
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads)
+        .enable_all()
+        .build()?
+        .block_on(async { run_application(role, config).await })
+}
+
+async fn run_application(role: Role, config: config::AppConfig) -> cja::Result<()> {
+    // Initialize tracing (returns Eyes shutdown handle if configured)
+    let otlp_enabled = config.otlp_endpoint.is_some();
+    let eyes_shutdown_handle = if config.gcp_logging {
+        telemetry::setup_gcp_tracing(
+            &config.rust_log,
+            config.eyes.as_ref(),
+            config.otlp_endpoint.as_deref(),
+        )?
+    } else if let Some(endpoint) = config.otlp_endpoint.as_deref() {
+        telemetry::setup_local_tracing(&config.rust_log, config.eyes.as_ref(), endpoint)?
+    } else {
+        setup_tracing("arent")?
+    };
+
+    let app_state = AppState::from_config(config).await?;
+    let shutdown = app_state.shutdown.clone();
+    let drain = std::time::Duration::from_secs(app_state.config.job.shutdown_drain_secs);
+
+    // Spawn application tasks
+    info!(role = ?role, "Spawning application tasks");
+    let mut tasks = spawn_application_tasks(app_state).await?;
+
+    // Wait for any task to complete - they all run forever, so if one exits
+    // it's an error - or for SIGTERM, which drains the job workers first
+    if !tasks.is_empty() {
+        let first = tokio::select! {
+            first = wait_for_first_task(&mut tasks) => Some(first),
+            () = shutdown::wait_for_signal() => None,
+        };
+
+        let Some((name, result)) = first else {
+            info!(
+                drain_secs = drain.as_secs(),
+                "Shutdown signal received, draining running games"
+            );
+            shutdown.trigger();
+            drain_tasks(tasks, &shutdown, drain).await;
+            return shutdown_telemetry(eyes_shutdown_handle, otlp_enabled).await;
+        };
+
+        match result {
+            Ok(Ok(())) => {
+                tracing::error!(task = name, "Task exited unexpectedly");
+                return Err(eyre!("Task '{}' exited unexpectedly", name));
+            }
+            Ok(Err(e)) => {
+                tracing::error!(task = name, error = ?e, "Task failed with error");
+                return Err(e);
+            }
+            Err(join_error) => {
+                tracing::error!(task = name, error = ?join_error, "Task panicked");
+                return Err(eyre!("Task '{}' panicked: {}", name, join_error));
+            }
+        }
+    }
+
+    shutdown_telemetry(eyes_shutdown_handle, otlp_enabled).await
+}
+
+/// Graceful shutdown of Eyes tracing and OTLP export, whichever are configured
+async fn shutdown_telemetry(
+    handle: Option<cja::setup::EyesShutdownHandle>,
+    otlp_enabled: bool,
+) -> cja::Result<()> {
+    if let Some(handle) = handle {
+        info!("Shutting down Eyes tracing...");
+        if let Err(e) = handle.shutdown().await {
+            tracing::warn!("Error shutting down Eyes: {e}");
+        }
+    }
+    if otlp_enabled {
+        info!("Flushing OTLP spans...");
+        telemetry::shutdown_otlp().await;
+    }
+
+    Ok(())
+}
+
+struct NamedTask {
+    name: &'static str,
+    handle: tokio::task::JoinHandle<cja::Result<()>>,
+    /// Waited on at shutdown (job workers finishing their current job)
+    /// rather than abandoned
+    drains: bool,
+}
+
+impl NamedTask {
+    fn spawn<F>(name: &'static str, future: F) -> Self
+    where
+        F: std::future::Future<Output = cja::Result<()>> + Send + 'static,
+    {
+        Self {
+            name,
+            handle: tokio::spawn(future),
+            drains: false,
+        }
+    }
+
+    fn spawn_draining<F>(name: &'static str, future: F) -> Self
+    where
+        F: std::future::Future<Output = cja::Result<()>> + Send + 'static,
+    {
+        Self {
+            drains: true,
+            ..Self::spawn(name, future)
+        }
+    }
+}
+
+/// Wait for the first task to complete and return its name and result
+async fn wait_for_first_task(
+    tasks: &mut [NamedTask],
+) -> (
+    &'static str,
+    Result<cja::Result<()>, tokio::task::JoinError>,
+) {
+    let handles = tasks.iter_mut().map(|t| &mut t.handle);
+    let (result, index, _remaining) = futures::future::select_all(handles).await;
+    (tasks[index].name, result)
+}
+
+/// After a shutdown signal, wait for the draining tasks to finish. Games
+/// still running at the drain deadline checkpoint themselves, so the extra
+/// margin only covers that bookkeeping.
+async fn drain_tasks(
+    tasks: Vec<NamedTask>,
+    shutdown: &shutdown::Shutdown,
+    drain: std::time::Duration,
+) {
+    const CHECKPOINT_MARGIN: std::time::Duration = std::time::Duration::from_secs(5);
+
+    let draining = tasks.into_iter().filter(|t| t.drains).map(|t| async move {
+        if let Ok(Err(e)) = t.handle.await {
+            tracing::warn!(task = t.name, error = ?e, "Task failed while draining");
+        }
+    });
+    let workers = tokio::time::timeout(
+        drain + CHECKPOINT_MARGIN,
+        futures::future::join_all(draining),
+    );
+    tokio::pin!(workers);
+    // Past the deadline, running games checkpoint and the workers get the
+    // margin to finish handing them back
+    let drained = tokio::select! {
+        drained = &mut workers => drained,
+        () = shutdown.expire_after(drain) => workers.await,
+    };
+    if drained.is_err() {
+        tracing::error!("Job workers did not drain before the deadline; exiting anyway");
+    } else {
+        info!("Job workers drained");
+    }
+}
+
+/// Spawn all application background tasks
+async fn spawn_application_tasks(app_state: AppState) -> cja::Result<Vec<NamedTask>> {
+    let mut tasks = vec![];
+    let features = app_state.config.features;
+    let job = &app_state.config.job;
+
+    // Build the cron registry once so we can both report it to Eyes and hand
+    // it to the cron worker. Built unconditionally so the boot manifest
+    // reflects the full deployed shape even when CRON is disabled for this
+    // process.
+    let cron_registry = cron::cron_registry();
+
+    // Emit the Eyes boot manifest describing this app's jobs and cron. This is
+    // a no-op unless EYES_ORG_ID/EYES_APP_ID are configured.
+    cja::eyes_manifest::send_boot_manifest::<jobs::Jobs, AppState>(
+        Some(env!("CARGO_PKG_VERSION")),
+        option_env!("VERGEN_GIT_SHA"),
+        Some(&cron_registry),
+    );
+
+    if features.server {
+        info!("Server Enabled");
+        tasks.push(NamedTask::spawn(
+            "server",
+            run_server(routes::routes(app_state.clone())),
+        ));
+        // Games may be played by a separate worker, so live frames come
+        // from the database rather than straight from the game runner
+        tasks.push(NamedTask::spawn(
+            "turn-listener",
+            game_channels::forward_turn_notifications(
+                app_state.db.clone(),
+                app_state.game_channels.clone(),
+            ),
+        ));
+    } else {
+        info!("Server Disabled");
+    }
+
+    if features.jobs {
+        info!("Jobs Enabled");
+        info!("Job poll interval: {}ms", job.poll_interval_ms);
+        info!("Job visibility timeout: {}s", job.visibility_timeout_secs);
+        info!("Job max retries: {}", job.max_retries);
+        info!("Job workers: {}", job.workers);
+
+        // Unique across processes, so a worker only ever heartbeats and
+        // settles jobs it claimed itself
+        let process_id = format!(
+            "{}-{}",
+            hostname::get().map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().into_owned()),
+            std::process::id()
+        );
+        for i in 0..job.workers {
+            let name: &'static str = Box::leak(format!("jobs-{i}").into_boxed_str());
+            tasks.push(NamedTask::spawn_draining(
+                name,
+                job_worker::run_worker(
+                    app_state.clone(),
+                    format!("{process_id}-{name}"),
+                    app_state.shutdown.worker_token(),
+                ),
+            ));
+        }
+        tasks.push(NamedTask::spawn(
+            "snake-failure-summaries",
+            snake_failure_log::report_summaries(),
+        ));
+    } else {
+        info!("Jobs Disabled");
+    }
+
+    if features.cron {
+        info!("Cron Enabled");
+        tasks.push(NamedTask::spawn(
+            "cron",
+            cron::run_cron(app_state.clone(), cron_registry),
+        ));
+    } else {
+        info!("Cron Disabled");
+    }
+
+    info!("All application tasks spawned successfully");
+    Ok(tasks)
+}
@@ -5,7 +5,7 @@ use std::io::Write;
 use chrono::{Duration, Utc};
 use color_eyre::eyre::{Context as _, eyre};
 use google_cloud_storage::{
-    client::{Client as GcsClient, ClientConfig},
+    client::Client as GcsClient,
     http::objects::upload::{Media, UploadObjectRequest, UploadType},
 };
 use sqlx::{FromRow, PgPool};
@@ -311,9 +311,9 @@ pub async fn backup_single_game(
         }
     };
 
-    let bucket = match &app_state.config.gcs_bucket {
-        Some(b) => b.clone(),
-        None => {
+    let (gcs_client, bucket) = match (&app_state.gcs, &app_state.config.gcs_bucket) {
+        (Some(client), Some(b)) => (client, b.clone()),
+        _ => {
             return Err(eyre!("GCS bucket not configured").into());
         }
     };
@@ -337,16 +337,9 @@ pub async fn backup_single_game(
         exported_at: Utc::now(),
     };
 
-    // Initialize GCS client
-    let config = ClientConfig::default()
-        .with_auth()
-        .await
-        .wrap_err("Failed to configure GCS client")?;
-    let gcs_client = GcsClient::new(config);
-
     // Generate path and upload
     let path = gcs_path(&game);
-    compress_and_upload_to_gcs(&gcs_client, &bucket, &path, &export).await?;
+    compress_and_upload_to_gcs(gcs_client, &bucket, &path, &export).await?;
 
     // Record in local database
     upsert_game_record(&app_state.db, &game, &path).await?;
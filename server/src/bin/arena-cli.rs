@@ -59,9 +59,21 @@ enum TokenCommands {
         /// Name for the token (e.g., "My laptop", "CI")
         #[arg(short, long)]
         name: Option<String>,
+        /// Scope to grant; repeat for several (games:create, leaderboards:write, admin).
+        /// Defaults to every scope your current token has.
+        #[arg(short, long = "scope")]
+        scopes: Vec<String>,
+        /// Days until the token expires; omit for a token that never expires
+        #[arg(long)]
+        expires_in_days: Option<i64>,
     },
-    /// List all active API tokens
+    /// List your API tokens, including expired ones
     List,
+    /// Replace a token's secret (the old one stops working)
+    Rotate {
+        /// Token ID to rotate
+        id: String,
+    },
     /// Revoke an API token
     Revoke {
         /// Token ID to revoke
@@ -119,6 +131,12 @@ enum GamesCommands {
         /// Filter by snake ID
         #[arg(long)]
         snake: Option<String>,
+        /// Filter by leaderboard ID
+        #[arg(long)]
+        leaderboard: Option<String>,
+        /// Filter by status (waiting, running, finished)
+        #[arg(long)]
+        status: Option<String>,
         /// Maximum number of games to return
         #[arg(long, default_value = "20")]
         limit: u32,
@@ -196,7 +214,11 @@ async fn handle_token_command(command: TokenCommands) -> color_eyre::Result<()>
     let base_url = config.api_url();
 
     match command {
-        TokenCommands::Create { name } => {
+        TokenCommands::Create {
+            name,
+            scopes,
+            expires_in_days,
+        } => {
             let name = name.unwrap_or_else(|| {
                 hostname::get()
                     .ok()
@@ -205,9 +227,19 @@ async fn handle_token_command(command: TokenCommands) -> color_eyre::Result<()>
             });
 
             let response = client
-                .post(format!("{}/api/tokens", base_url))
+                .post(format!("{}/api/v1/tokens", base_url))
                 .bearer_auth(token)
-                .json(&serde_json::json!({ "name": name }))
+                .json(&{
+                    let mut body = serde_json::json!({ "name": name });
+                    if !scopes.is_empty() {
+                        body["scopes"] = serde_json::json!(scopes);
+                    }
+                    if let Some(days) = expires_in_days {
+                        body["expires_at"] =
+                            serde_json::json!(chrono::Utc::now() + chrono::Duration::days(days));
+                    }
+                    body
+                })
                 .send()
                 .await
                 .wrap_err("Failed to create token")?;
@@ -222,12 +254,17 @@ async fn handle_token_command(command: TokenCommands) -> color_eyre::Result<()>
             println!("Token created successfully!");
             println!("ID: {}", result["id"]);
             println!("Name: {}", result["name"]);
+            println!("Scopes: {}", result["scopes"]);
+            println!(
+                "Expires: {}",
+                result["expires_at"].as_str().unwrap_or("Never")
+            );
             println!("\nSecret (save this - it won't be shown again):");
             println!("{}", result["secret"]);
         }
         TokenCommands::List => {
             let response = client
-                .get(format!("{}/api/tokens", base_url))
+                .get(format!("{}/api/v1/tokens", base_url))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -242,24 +279,61 @@ async fn handle_token_command(command: TokenCommands) -> color_eyre::Result<()>
             let tokens: Vec<serde_json::Value> = response.json().await?;
 
             if tokens.is_empty() {
-                println!("No active tokens found.");
+                println!("No tokens found.");
             } else {
-                println!("{:<38} {:<20} {:<20}", "ID", "NAME", "LAST USED");
-                println!("{}", "-".repeat(78));
+                println!(
+                    "{:<38} {:<20} {:<36} {:<32} {:<20}",
+                    "ID", "NAME", "SCOPES", "LAST USED", "EXPIRES"
+                );
+                println!("{}", "-".repeat(150));
                 for token in tokens {
                     let last_used = token["last_used_at"].as_str().unwrap_or("Never");
+                    let expires = token["expires_at"].as_str().unwrap_or("Never");
+                    let scopes = token["scopes"]
+                        .as_array()
+                        .map(|s| {
+                            s.iter()
+                                .filter_map(|v| v.as_str())
+                                .collect::<Vec<_>>()
+                                .join(",")
+                        })
+                        .unwrap_or_default();
                     println!(
-                        "{:<38} {:<20} {:<20}",
+                        "{:<38} {:<20} {:<36} {:<32} {:<20}",
                         token["id"].as_str().unwrap_or(""),
                         token["name"].as_str().unwrap_or(""),
-                        last_used
+                        scopes,
+                        last_used,
+                        expires
                     );
                 }
             }
         }
+        TokenCommands::Rotate { id } => {
+            let response = client
+                .post(format!("{}/api/v1/tokens/{}/rotate", base_url, id))
+                .bearer_auth(token)
+                .send()
+                .await
+                .wrap_err("Failed to rotate token")?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(eyre!("Token not found or already revoked."));
+            }
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(eyre!("Failed to rotate token: {} - {}", status, body));
+            }
+
+            let result: serde_json::Value = response.json().await?;
+            println!("Token rotated successfully!");
+            println!("\nNew secret (save this - it won't be shown again):");
+            println!("{}", result["secret"]);
+        }
         TokenCommands::Revoke { id } => {
             let response = client
-                .delete(format!("{}/api/tokens/{}", base_url, id))
+                .delete(format!("{}/api/v1/tokens/{}", base_url, id))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -297,7 +371,7 @@ async fn handle_snakes_command(
     match command {
         SnakesCommands::List => {
             let response = client
-                .get(format!("{}/api/snakes", base_url))
+                .get(format!("{}/api/v1/snakes", base_url))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -342,7 +416,7 @@ async fn handle_snakes_command(
         }
         SnakesCommands::Create { name, url, public } => {
             let response = client
-                .post(format!("{}/api/snakes", base_url))
+                .post(format!("{}/api/v1/snakes", base_url))
                 .bearer_auth(token)
                 .json(&serde_json::json!({
                     "name": name,
@@ -373,7 +447,7 @@ async fn handle_snakes_command(
         }
         SnakesCommands::Show { id } => {
             let response = client
-                .get(format!("{}/api/snakes/{}", base_url, id))
+                .get(format!("{}/api/v1/snakes/{}", base_url, id))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -420,7 +494,7 @@ async fn handle_snakes_command(
             }
 
             let response = client
-                .put(format!("{}/api/snakes/{}", base_url, id))
+                .put(format!("{}/api/v1/snakes/{}", base_url, id))
                 .bearer_auth(token)
                 .json(&update)
                 .send()
@@ -449,7 +523,7 @@ async fn handle_snakes_command(
         }
         SnakesCommands::Delete { id } => {
             let response = client
-                .delete(format!("{}/api/snakes/{}", base_url, id))
+                .delete(format!("{}/api/v1/snakes/{}", base_url, id))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -537,7 +611,7 @@ async fn login() -> color_eyre::Result<()> {
     // Validate the token by trying to list tokens
     let client = reqwest::Client::new();
     let response = client
-        .get(format!("{}/api/tokens", base_url))
+        .get(format!("{}/api/v1/tokens", base_url))
         .bearer_auth(&token)
         .send()
         .await
@@ -576,11 +650,22 @@ async fn handle_games_command(command: GamesCommands) -> color_eyre::Result<()>
     let base_url = config.api_url();
 
     match command {
-        GamesCommands::List { snake, limit } => {
-            let mut url = format!("{}/api/games?limit={}", base_url, limit);
+        GamesCommands::List {
+            snake,
+            leaderboard,
+            status,
+            limit,
+        } => {
+            let mut url = format!("{}/api/v1/games?limit={}", base_url, limit);
             if let Some(snake_id) = snake {
                 url.push_str(&format!("&snake_id={}", snake_id));
             }
+            if let Some(leaderboard_id) = leaderboard {
+                url.push_str(&format!("&leaderboard_id={}", leaderboard_id));
+            }
+            if let Some(status) = status {
+                url.push_str(&format!("&status={}", status));
+            }
 
             let response = client
                 .get(&url)
@@ -607,7 +692,7 @@ async fn handle_games_command(command: GamesCommands) -> color_eyre::Result<()>
             let snake_ids: Vec<&str> = snakes.split(',').map(|s| s.trim()).collect();
 
             let response = client
-                .post(format!("{}/api/games", base_url))
+                .post(format!("{}/api/v1/games", base_url))
                 .bearer_auth(token)
                 .json(&serde_json::json!({
                     "snakes": snake_ids,
@@ -629,7 +714,7 @@ async fn handle_games_command(command: GamesCommands) -> color_eyre::Result<()>
         }
         GamesCommands::Show { id } => {
             let response = client
-                .get(format!("{}/api/games/{}/details", base_url, id))
+                .get(format!("{}/api/v1/games/{}/details", base_url, id))
                 .bearer_auth(token)
                 .send()
                 .await
@@ -656,7 +741,7 @@ async fn handle_games_command(command: GamesCommands) -> color_eyre::Result<()>
                 // Poll loop
                 loop {
                     let response = client
-                        .get(format!("{}/api/games/{}/details", base_url, id))
+                        .get(format!("{}/api/v1/games/{}/details", base_url, id))
                         .bearer_auth(token)
                         .send()
                         .await
@@ -0,0 +1,1549 @@
+//! SQLite results database: runs, their games and outcomes, and admin stats samples.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::load::GameVariant;
+use crate::poll::{PollGameResult, PollGameStatus};
+use crate::report::{
+    AdminStatsSummary, ConfigReport, Report, Timeline, TimelineGame, TimelineSnapshot,
+    TimingSource, percentile, run_info_sync,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminStatsResponse {
+    job_queue: AdminJobQueue,
+    game_counts: AdminGameCounts,
+    games_created: AdminTimeWindow,
+    games_finished: AdminTimeWindow,
+    avg_game_duration_secs: Option<f64>,
+    #[serde(default)]
+    recent_errors: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct AdminJobQueue {
+    ready: i64,
+    running: i64,
+    scheduled: i64,
+    total: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct AdminGameCounts {
+    waiting: i64,
+    running: i64,
+    finished: i64,
+    total: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct AdminTimeWindow {
+    last_hour: i64,
+    last_24h: i64,
+    last_7d: i64,
+}
+
+// ============================================================================
+
+// Completion Database (SQLite)
+
+// ============================================================================
+
+#[derive(Clone)]
+pub struct CompletionDb {
+    conn: Arc<Mutex<rusqlite::Connection>>,
+    pub run_id: Uuid,
+}
+
+impl CompletionDb {
+    fn init_schema_sync(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS runs (
+                run_id TEXT PRIMARY KEY,
+                started_at TEXT NOT NULL,
+                base_url TEXT NOT NULL,
+                pattern TEXT NOT NULL,
+                duration_secs INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS games (
+                game_id TEXT PRIMARY KEY,
+                run_id TEXT NOT NULL REFERENCES runs(run_id),
+                created_at TEXT NOT NULL,
+                enqueued_at TEXT,
+                server_created_at TEXT,
+                status TEXT NOT NULL DEFAULT 'created',
+                server_updated_at TEXT,
+                first_seen_finished_at TEXT,
+                poll_count INTEGER NOT NULL DEFAULT 0
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_games_run_id ON games(run_id);
+            CREATE INDEX IF NOT EXISTS idx_games_status ON games(status);
+
+            CREATE TABLE IF NOT EXISTS admin_stats_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL REFERENCES runs(run_id),
+                captured_at TEXT NOT NULL,
+                phase TEXT NOT NULL,
+                jobs_ready INTEGER NOT NULL,
+                jobs_running INTEGER NOT NULL,
+                jobs_scheduled INTEGER NOT NULL,
+                jobs_total INTEGER NOT NULL,
+                games_waiting INTEGER NOT NULL,
+                games_running INTEGER NOT NULL,
+                games_finished INTEGER NOT NULL,
+                games_total INTEGER NOT NULL,
+                games_created_last_hour INTEGER NOT NULL,
+                games_finished_last_hour INTEGER NOT NULL,
+                avg_game_duration_secs REAL,
+                raw_json TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_admin_stats_run_id ON admin_stats_snapshots(run_id);
+            ",
+        )?;
+
+        // Columns added since the table was introduced; result files from
+        // older runs lack them
+        for (table, column, column_type) in [
+            ("games", "create_latency_ms", "REAL"),
+            ("games", "board", "TEXT"),
+            ("games", "game_type", "TEXT"),
+            // Gameplay results, fetched once a game finishes
+            ("games", "turns", "INTEGER"),
+            ("games", "winner_snake_id", "TEXT"),
+            ("games", "end_reason", "TEXT"),
+            // The run summary written by finish_run
+            ("runs", "ended_at", "TEXT"),
+            ("runs", "interrupted", "INTEGER"),
+            ("runs", "total_games", "INTEGER"),
+            ("runs", "finished_games", "INTEGER"),
+            ("runs", "stuck_running", "INTEGER"),
+            ("runs", "not_started", "INTEGER"),
+            ("runs", "p95_completion_ms", "REAL"),
+            ("runs", "throughput_per_min", "REAL"),
+        ] {
+            if conn
+                .prepare(&format!("SELECT {} FROM {} LIMIT 0", column, table))
+                .is_err()
+            {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {};",
+                    table, column, column_type
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn insert_run_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        base_url: &str,
+        pattern: &str,
+        duration_secs: u64,
+    ) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO runs (run_id, started_at, base_url, pattern, duration_secs) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                run_id.to_string(),
+                Utc::now().to_rfc3339(),
+                base_url,
+                pattern,
+                duration_secs as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn new(
+        path: &str,
+        base_url: &str,
+        pattern: &str,
+        duration_secs: u64,
+    ) -> Result<Self, rusqlite::Error> {
+        let conn = rusqlite::Connection::open(path)?;
+        Self::init_schema_sync(&conn)?;
+        let run_id = Uuid::new_v4();
+        Self::insert_run_sync(&conn, run_id, base_url, pattern, duration_secs)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            run_id,
+        })
+    }
+
+    /// Start another run in the same results DB, sharing this connection
+    pub fn new_run(
+        &self,
+        base_url: &str,
+        pattern: &str,
+        duration_secs: u64,
+    ) -> Result<Self, rusqlite::Error> {
+        let run_id = Uuid::new_v4();
+        Self::insert_run_sync(
+            &self.conn.lock().unwrap(),
+            run_id,
+            base_url,
+            pattern,
+            duration_secs,
+        )?;
+        Ok(Self {
+            conn: self.conn.clone(),
+            run_id,
+        })
+    }
+
+    /// Record how the run ended, with the report's headline numbers when
+    /// there is a report. An earlier interruption stays recorded when a
+    /// resumed run finishes cleanly.
+    fn finish_run_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        report: Option<&Report>,
+        interrupted: bool,
+    ) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "UPDATE runs SET ended_at = ?2, interrupted = MAX(COALESCE(interrupted, 0), ?3),
+                 total_games = ?4, finished_games = ?5, stuck_running = ?6, not_started = ?7,
+                 p95_completion_ms = ?8, throughput_per_min = ?9
+             WHERE run_id = ?1",
+            params![
+                run_id.to_string(),
+                Utc::now().to_rfc3339(),
+                interrupted,
+                report.map(|r| r.total_games as i64),
+                report.map(|r| r.finished as i64),
+                report.map(|r| r.stuck_running as i64),
+                report.map(|r| r.not_started as i64),
+                report.and_then(|r| r.p95_completion_ms),
+                report.and_then(|r| r.throughput_per_min),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn finish_run(
+        &self,
+        report: Option<&Report>,
+        interrupted: bool,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        Self::finish_run_sync(&conn, self.run_id, report, interrupted)
+    }
+
+    /// Reopen a run recorded by an earlier invocation
+    pub fn open_run(
+        path: &str,
+        run_id: Uuid,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = rusqlite::Connection::open(path)?;
+        Self::init_schema_sync(&conn)?;
+        run_info_sync(&conn, run_id)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            run_id,
+        })
+    }
+
+    pub fn record_game_created_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        game_id: Uuid,
+        latency: Duration,
+        variant: &GameVariant,
+    ) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT OR IGNORE INTO games (game_id, run_id, created_at, create_latency_ms, board, game_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                game_id.to_string(),
+                run_id.to_string(),
+                Utc::now().to_rfc3339(),
+                latency.as_secs_f64() * 1000.0,
+                variant.board,
+                variant.game_type,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn record_game_created(
+        &self,
+        game_id: Uuid,
+        latency: Duration,
+        variant: GameVariant,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::record_game_created_sync(&conn, run_id, game_id, latency, &variant)
+        })
+        .await
+        .unwrap()
+    }
+
+    fn get_unfinished_game_ids_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+    ) -> Result<Vec<Uuid>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut stmt =
+            conn.prepare("SELECT game_id FROM games WHERE run_id = ?1 AND status != 'finished'")?;
+        let ids = stmt
+            .query_map(params![run_id.to_string()], |row| {
+                let id_str: String = row.get(0)?;
+                Ok(id_str)
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .collect();
+        Ok(ids)
+    }
+
+    pub async fn get_unfinished_game_ids(
+        &self,
+    ) -> Result<Vec<Uuid>, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::get_unfinished_game_ids_sync(&conn, run_id)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Finished games whose results haven't been fetched yet
+    fn get_games_missing_results_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+    ) -> Result<Vec<Uuid>, rusqlite::Error> {
+        let mut stmt = conn.prepare(
+            "SELECT game_id FROM games WHERE run_id = ?1 AND status = 'finished' AND turns IS NULL",
+        )?;
+        let ids = stmt
+            .query_map(params![run_id.to_string()], |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .collect();
+        Ok(ids)
+    }
+
+    pub async fn get_games_missing_results(&self) -> Result<Vec<Uuid>, rusqlite::Error> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::get_games_missing_results_sync(&conn, run_id)
+        })
+        .await
+        .unwrap()
+    }
+
+    fn record_game_results_sync(
+        conn: &rusqlite::Connection,
+        results: &[PollGameResult],
+    ) -> Result<(), rusqlite::Error> {
+        for result in results {
+            conn.execute(
+                "UPDATE games SET turns = ?1, winner_snake_id = ?2, end_reason = ?3 WHERE game_id = ?4",
+                params![
+                    result.turns,
+                    result.winner.map(|id| id.to_string()),
+                    result.end_reason(),
+                    result.id.to_string(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub async fn record_game_results(
+        &self,
+        results: Vec<PollGameResult>,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::record_game_results_sync(&conn, &results)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub fn update_game_statuses_sync(
+        conn: &rusqlite::Connection,
+        statuses: &[PollGameStatus],
+    ) -> Result<(), rusqlite::Error> {
+        let now = Utc::now().to_rfc3339();
+        for status in statuses {
+            let server_updated_at = status.updated_at.map(|dt| dt.to_rfc3339());
+            let enqueued_at = status.enqueued_at.map(|dt| dt.to_rfc3339());
+            let server_created_at = status.created_at.map(|dt| dt.to_rfc3339());
+
+            // Set first_seen_finished_at only on first transition to finished
+            if status.status == "finished" {
+                conn.execute(
+                    "UPDATE games SET status = ?1, server_updated_at = ?2, enqueued_at = ?3, server_created_at = ?4, poll_count = poll_count + 1, first_seen_finished_at = COALESCE(first_seen_finished_at, ?5) WHERE game_id = ?6",
+                    params![
+                        status.status,
+                        server_updated_at,
+                        enqueued_at,
+                        server_created_at,
+                        now,
+                        status.id.to_string(),
+                    ],
+                )?;
+            } else {
+                conn.execute(
+                    "UPDATE games SET status = ?1, server_updated_at = ?2, enqueued_at = ?3, server_created_at = ?4, poll_count = poll_count + 1 WHERE game_id = ?5",
+                    params![
+                        status.status,
+                        server_updated_at,
+                        enqueued_at,
+                        server_created_at,
+                        status.id.to_string(),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn update_game_statuses(
+        &self,
+        statuses: Vec<PollGameStatus>,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::update_game_statuses_sync(&conn, &statuses)
+        })
+        .await
+        .unwrap()
+    }
+
+    fn record_admin_stats_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        phase: &str,
+        stats: &AdminStatsResponse,
+        raw_json: &str,
+    ) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO admin_stats_snapshots (run_id, captured_at, phase, jobs_ready, jobs_running, jobs_scheduled, jobs_total, games_waiting, games_running, games_finished, games_total, games_created_last_hour, games_finished_last_hour, avg_game_duration_secs, raw_json) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![
+                run_id.to_string(),
+                Utc::now().to_rfc3339(),
+                phase,
+                stats.job_queue.ready,
+                stats.job_queue.running,
+                stats.job_queue.scheduled,
+                stats.job_queue.total,
+                stats.game_counts.waiting,
+                stats.game_counts.running,
+                stats.game_counts.finished,
+                stats.game_counts.total,
+                stats.games_created.last_hour,
+                stats.games_finished.last_hour,
+                stats.avg_game_duration_secs,
+                raw_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub async fn record_admin_stats(
+        &self,
+        phase: &str,
+        stats: &AdminStatsResponse,
+        raw_json: &str,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        let phase = phase.to_string();
+        let raw_json = raw_json.to_string();
+        // AdminStatsResponse fields are all Copy, so capture them before the move
+        let job_queue = AdminJobQueue {
+            ready: stats.job_queue.ready,
+            running: stats.job_queue.running,
+            scheduled: stats.job_queue.scheduled,
+            total: stats.job_queue.total,
+        };
+        let game_counts = AdminGameCounts {
+            waiting: stats.game_counts.waiting,
+            running: stats.game_counts.running,
+            finished: stats.game_counts.finished,
+            total: stats.game_counts.total,
+        };
+        let games_created = AdminTimeWindow {
+            last_hour: stats.games_created.last_hour,
+            last_24h: stats.games_created.last_24h,
+            last_7d: stats.games_created.last_7d,
+        };
+        let games_finished = AdminTimeWindow {
+            last_hour: stats.games_finished.last_hour,
+            last_24h: stats.games_finished.last_24h,
+            last_7d: stats.games_finished.last_7d,
+        };
+        let avg_game_duration_secs = stats.avg_game_duration_secs;
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let stats_copy = AdminStatsResponse {
+                job_queue,
+                game_counts,
+                games_created,
+                games_finished,
+                avg_game_duration_secs,
+                recent_errors: vec![],
+            };
+            Self::record_admin_stats_sync(&conn, run_id, &phase, &stats_copy, &raw_json)
+        })
+        .await
+        .unwrap()
+    }
+
+    fn total_count_sync(conn: &rusqlite::Connection, run_id: Uuid) -> Result<u64, rusqlite::Error> {
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM games WHERE run_id = ?1",
+            params![run_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    pub fn generate_report_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        poll_interval_secs: u64,
+    ) -> Result<Report, Box<dyn std::error::Error + Send + Sync>> {
+        let total_games = Self::total_count_sync(conn, run_id)?;
+        let run_id_str = run_id.to_string();
+
+        let finished: u64 = conn.query_row(
+            "SELECT COUNT(*) FROM games WHERE run_id = ?1 AND status = 'finished'",
+            params![run_id_str],
+            |row| row.get::<_, i64>(0),
+        )? as u64;
+
+        let stuck_running: u64 = conn.query_row(
+            "SELECT COUNT(*) FROM games WHERE run_id = ?1 AND status = 'running'",
+            params![run_id_str],
+            |row| row.get::<_, i64>(0),
+        )? as u64;
+
+        let not_started = total_games
+            .saturating_sub(finished)
+            .saturating_sub(stuck_running);
+
+        // Try server-side timing first (server_updated_at - enqueued_at)
+        let server_timing_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM games WHERE run_id = ?1 AND status = 'finished' AND server_updated_at IS NOT NULL AND enqueued_at IS NOT NULL",
+            params![run_id_str],
+            |row| row.get(0),
+        )?;
+
+        let (timing_source, completion_times_ms) = if server_timing_count > 0 {
+            // Use server-side timing
+            let mut stmt = conn.prepare(
+                "SELECT server_updated_at, enqueued_at FROM games WHERE run_id = ?1 AND status = 'finished' AND server_updated_at IS NOT NULL AND enqueued_at IS NOT NULL",
+            )?;
+            let times: Vec<f64> = stmt
+                .query_map(params![run_id_str], |row| {
+                    let updated: String = row.get(0)?;
+                    let enqueued: String = row.get(1)?;
+                    Ok((updated, enqueued))
+                })?
+                .filter_map(|r| r.ok())
+                .filter_map(|(updated, enqueued)| {
+                    let updated = chrono::DateTime::parse_from_rfc3339(&updated).ok()?;
+                    let enqueued = chrono::DateTime::parse_from_rfc3339(&enqueued).ok()?;
+                    let diff = updated.signed_duration_since(enqueued);
+                    Some(diff.num_milliseconds() as f64)
+                })
+                .collect();
+            (TimingSource::ServerSide, times)
+        } else {
+            // Fall back to client-observed timing
+            let mut stmt = conn.prepare(
+                "SELECT first_seen_finished_at, created_at FROM games WHERE run_id = ?1 AND status = 'finished' AND first_seen_finished_at IS NOT NULL",
+            )?;
+            let times: Vec<f64> = stmt
+                .query_map(params![run_id_str], |row| {
+                    let finished: String = row.get(0)?;
+                    let created: String = row.get(1)?;
+                    Ok((finished, created))
+                })?
+                .filter_map(|r| r.ok())
+                .filter_map(|(finished, created)| {
+                    let finished = chrono::DateTime::parse_from_rfc3339(&finished).ok()?;
+                    let created = chrono::DateTime::parse_from_rfc3339(&created).ok()?;
+                    let diff = finished.signed_duration_since(created);
+                    Some(diff.num_milliseconds() as f64)
+                })
+                .collect();
+            (TimingSource::ClientObserved { poll_interval_secs }, times)
+        };
+
+        // Calculate percentile stats from completion times
+        let (avg_completion_ms, p50_completion_ms, p95_completion_ms, p99_completion_ms) =
+            if completion_times_ms.is_empty() {
+                (None, None, None, None)
+            } else {
+                let mut sorted = completion_times_ms.clone();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                let len = sorted.len();
+                let avg = sorted.iter().sum::<f64>() / len as f64;
+                let p50 = sorted[len * 50 / 100];
+                let p95 = sorted[len * 95 / 100];
+                let p99_idx = (len * 99 / 100).min(len.saturating_sub(1));
+                let p99 = sorted[p99_idx];
+                (Some(avg), Some(p50), Some(p95), Some(p99))
+            };
+
+        // Overall duration and throughput
+        let first_game_created: Option<String> = conn
+            .query_row(
+                "SELECT MIN(created_at) FROM games WHERE run_id = ?1",
+                params![run_id_str],
+                |row| row.get(0),
+            )
+            .ok();
+
+        let last_game_finished: Option<String> = conn
+            .query_row(
+                "SELECT MAX(COALESCE(server_updated_at, first_seen_finished_at)) FROM games WHERE run_id = ?1 AND status = 'finished'",
+                params![run_id_str],
+                |row| row.get(0),
+            )
+            .ok();
+
+        let overall_duration_secs = match (&first_game_created, &last_game_finished) {
+            (Some(first), Some(last)) => {
+                let first = chrono::DateTime::parse_from_rfc3339(first).ok();
+                let last = chrono::DateTime::parse_from_rfc3339(last).ok();
+                match (first, last) {
+                    (Some(f), Some(l)) => {
+                        Some(l.signed_duration_since(f).num_milliseconds() as f64 / 1000.0)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let throughput_per_min = match (finished, overall_duration_secs) {
+            (f, Some(d)) if f > 0 && d > 0.0 => Some(f as f64 / (d / 60.0)),
+            _ => None,
+        };
+
+        // Admin stats summary
+        let admin_stats_summary = Self::generate_admin_stats_summary_sync(conn, run_id)?;
+        let by_config = Self::config_breakdown_sync(
+            conn,
+            run_id,
+            matches!(timing_source, TimingSource::ServerSide),
+        )?;
+
+        Ok(Report {
+            run_id,
+            total_games,
+            finished,
+            stuck_running,
+            not_started,
+            timing_source,
+            avg_completion_ms,
+            p50_completion_ms,
+            p95_completion_ms,
+            p99_completion_ms,
+            first_game_created,
+            last_game_finished,
+            overall_duration_secs,
+            throughput_per_min,
+            admin_stats_summary,
+            by_config,
+        })
+    }
+
+    /// Completion stats per board/type, timed the same way as the overall
+    /// report. Games from runs before the columns existed are left out.
+    fn config_breakdown_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+        server_side_timing: bool,
+    ) -> Result<Vec<ConfigReport>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut stmt = conn.prepare(
+            "SELECT game_type, board, status, enqueued_at, server_updated_at, created_at, first_seen_finished_at
+             FROM games WHERE run_id = ?1 AND board IS NOT NULL AND game_type IS NOT NULL",
+        )?;
+        let rows = stmt
+            .query_map(params![run_id.to_string()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?
+            .filter_map(|r| r.ok());
+
+        let elapsed_ms = |from: Option<&str>, to: Option<&str>| {
+            let from = chrono::DateTime::parse_from_rfc3339(from?).ok()?;
+            let to = chrono::DateTime::parse_from_rfc3339(to?).ok()?;
+            Some(to.signed_duration_since(from).num_milliseconds() as f64)
+        };
+
+        let mut configs: BTreeMap<(String, String), (ConfigReport, Vec<f64>)> = BTreeMap::new();
+        for (game_type, board, status, enqueued, updated, created, first_seen) in rows {
+            let (config, times) = configs
+                .entry((game_type.clone(), board.clone()))
+                .or_insert_with(|| {
+                    (
+                        ConfigReport {
+                            game_type,
+                            board,
+                            total: 0,
+                            finished: 0,
+                            stuck_running: 0,
+                            p50_completion_ms: None,
+                            p95_completion_ms: None,
+                        },
+                        Vec::new(),
+                    )
+                });
+            config.total += 1;
+            match status.as_str() {
+                "finished" => {
+                    config.finished += 1;
+                    let time = if server_side_timing {
+                        elapsed_ms(enqueued.as_deref(), updated.as_deref())
+                    } else {
+                        elapsed_ms(Some(&created), first_seen.as_deref())
+                    };
+                    times.extend(time);
+                }
+                "running" => config.stuck_running += 1,
+                _ => {}
+            }
+        }
+
+        Ok(configs
+            .into_values()
+            .map(|(mut config, mut times)| {
+                times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                config.p50_completion_ms = percentile(&times, 50);
+                config.p95_completion_ms = percentile(&times, 95);
+                config
+            })
+            .collect())
+    }
+
+    fn generate_admin_stats_summary_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+    ) -> Result<Option<AdminStatsSummary>, Box<dyn std::error::Error + Send + Sync>> {
+        let run_id_str = run_id.to_string();
+
+        let snapshot_count: u64 = conn.query_row(
+            "SELECT COUNT(*) FROM admin_stats_snapshots WHERE run_id = ?1",
+            params![run_id_str],
+            |row| row.get::<_, i64>(0),
+        )? as u64;
+
+        if snapshot_count == 0 {
+            return Ok(None);
+        }
+
+        let peak_jobs_ready: i64 = conn.query_row(
+            "SELECT MAX(jobs_ready) FROM admin_stats_snapshots WHERE run_id = ?1",
+            params![run_id_str],
+            |row| row.get(0),
+        )?;
+        let peak_jobs_running: i64 = conn.query_row(
+            "SELECT MAX(jobs_running) FROM admin_stats_snapshots WHERE run_id = ?1",
+            params![run_id_str],
+            |row| row.get(0),
+        )?;
+        let peak_games_waiting: i64 = conn.query_row(
+            "SELECT MAX(games_waiting) FROM admin_stats_snapshots WHERE run_id = ?1",
+            params![run_id_str],
+            |row| row.get(0),
+        )?;
+        let peak_games_running: i64 = conn.query_row(
+            "SELECT MAX(games_running) FROM admin_stats_snapshots WHERE run_id = ?1",
+            params![run_id_str],
+            |row| row.get(0),
+        )?;
+
+        // Final state from latest snapshot
+        let (
+            final_jobs_ready,
+            final_jobs_total,
+            final_games_finished,
+            final_games_total,
+            final_avg_game_duration_secs,
+            final_raw_json,
+        ): (i64, i64, i64, i64, Option<f64>, String) = conn.query_row(
+            "SELECT jobs_ready, jobs_total, games_finished, games_total, avg_game_duration_secs, raw_json FROM admin_stats_snapshots WHERE run_id = ?1 ORDER BY captured_at DESC LIMIT 1",
+            params![run_id_str],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+        )?;
+
+        // Check for errors in final snapshot raw_json
+        let has_errors = serde_json::from_str::<serde_json::Value>(&final_raw_json)
+            .ok()
+            .and_then(|v| v.get("recent_errors").cloned())
+            .and_then(|v| v.as_array().cloned())
+            .is_some_and(|arr| !arr.is_empty());
+
+        Ok(Some(AdminStatsSummary {
+            snapshot_count,
+            peak_jobs_ready,
+            peak_jobs_running,
+            peak_games_waiting,
+            peak_games_running,
+            final_jobs_ready,
+            final_jobs_total,
+            final_games_finished,
+            final_games_total,
+            final_avg_game_duration_secs,
+            has_errors,
+        }))
+    }
+
+    pub fn load_timeline_sync(
+        conn: &rusqlite::Connection,
+        run_id: Uuid,
+    ) -> Result<Timeline, Box<dyn std::error::Error + Send + Sync>> {
+        let run_id_str = run_id.to_string();
+        let parse = |s: &str| chrono::DateTime::parse_from_rfc3339(s).ok();
+
+        let (started_at, base_url, pattern): (String, String, String) = conn.query_row(
+            "SELECT started_at, base_url, pattern FROM runs WHERE run_id = ?1",
+            params![run_id_str],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT created_at, status, create_latency_ms, enqueued_at, server_updated_at FROM games WHERE run_id = ?1 ORDER BY created_at",
+        )?;
+        let games = stmt
+            .query_map(params![run_id_str], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<f64>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(|(created, status, create_latency_ms, enqueued, updated)| {
+                let completion_ms = match (status.as_str(), enqueued, updated) {
+                    ("finished", Some(enqueued), Some(updated)) => Some(
+                        parse(&updated)?
+                            .signed_duration_since(parse(&enqueued)?)
+                            .num_milliseconds() as f64,
+                    ),
+                    _ => None,
+                };
+                Some(TimelineGame {
+                    created_at: parse(&created)?,
+                    status,
+                    create_latency_ms,
+                    completion_ms,
+                })
+            })
+            .collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT captured_at, jobs_ready, jobs_running, games_waiting, games_running FROM admin_stats_snapshots WHERE run_id = ?1 ORDER BY captured_at",
+        )?;
+        let snapshots = stmt
+            .query_map(params![run_id_str], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(
+                |(captured, jobs_ready, jobs_running, games_waiting, games_running)| {
+                    Some(TimelineSnapshot {
+                        captured_at: parse(&captured)?,
+                        jobs_ready,
+                        jobs_running,
+                        games_waiting,
+                        games_running,
+                    })
+                },
+            )
+            .collect();
+
+        Ok(Timeline {
+            started_at,
+            base_url,
+            pattern,
+            games,
+            snapshots,
+        })
+    }
+
+    pub async fn load_timeline(
+        &self,
+    ) -> Result<Timeline, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::load_timeline_sync(&conn, run_id)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn generate_report(
+        &self,
+        poll_interval_secs: u64,
+    ) -> Result<Report, Box<dyn std::error::Error + Send + Sync>> {
+        let conn = self.conn.clone();
+        let run_id = self.run_id;
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            Self::generate_report_sync(&conn, run_id, poll_interval_secs)
+        })
+        .await
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use crate::load::tests::default_variant;
+
+    #[test]
+    fn test_open_run_requires_a_recorded_run() {
+        let path = std::env::temp_dir().join(format!("stress-test-{}.db", Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let db = CompletionDb::new(path, "http://test", "test", 30).unwrap();
+        let game = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &db.conn.lock().unwrap(),
+            db.run_id,
+            game,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+
+        let reopened = CompletionDb::open_run(path, db.run_id).unwrap();
+        let unfinished =
+            CompletionDb::get_unfinished_game_ids_sync(&reopened.conn.lock().unwrap(), db.run_id)
+                .unwrap();
+        assert_eq!(unfinished, vec![game]);
+        assert!(CompletionDb::open_run(path, Uuid::new_v4()).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_report_breaks_down_by_game_config() {
+        let (conn, run_id) = new_in_memory_db();
+        let royale = GameVariant {
+            board: "19x19".to_string(),
+            game_type: "royale".to_string(),
+            weight: 1,
+        };
+        let standard_games: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let royale_game = Uuid::new_v4();
+        for game in &standard_games {
+            CompletionDb::record_game_created_sync(
+                &conn,
+                run_id,
+                *game,
+                Duration::ZERO,
+                &default_variant(),
+            )
+            .unwrap();
+        }
+        CompletionDb::record_game_created_sync(&conn, run_id, royale_game, Duration::ZERO, &royale)
+            .unwrap();
+
+        let now = Utc::now();
+        let status = |id, status: &str, secs| PollGameStatus {
+            id,
+            status: status.to_string(),
+            updated_at: Some(now),
+            enqueued_at: Some(now - chrono::Duration::seconds(secs)),
+            created_at: Some(now - chrono::Duration::seconds(secs)),
+        };
+        CompletionDb::update_game_statuses_sync(
+            &conn,
+            &[
+                status(standard_games[0], "finished", 2),
+                status(standard_games[1], "finished", 4),
+                status(royale_game, "running", 1),
+            ],
+        )
+        .unwrap();
+
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        let configs: Vec<_> = report
+            .by_config
+            .iter()
+            .map(|c| {
+                (
+                    format!("{}:{}", c.game_type, c.board),
+                    c.total,
+                    c.finished,
+                    c.stuck_running,
+                    c.p50_completion_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            configs,
+            vec![
+                ("royale:19x19".to_string(), 1, 0, 1, None),
+                ("standard:11x11".to_string(), 3, 2, 0, Some(4000.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish_run_records_summary() {
+        let (conn, run_id) = new_in_memory_db();
+        let summary = |conn: &rusqlite::Connection| -> (Option<String>, Option<i64>, Option<i64>) {
+            conn.query_row(
+                "SELECT ended_at, interrupted, total_games FROM runs WHERE run_id = ?1",
+                params![run_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(summary(&conn), (None, None, None));
+
+        // Interrupted before a report could be generated
+        CompletionDb::finish_run_sync(&conn, run_id, None, true).unwrap();
+        let (ended_at, interrupted, total) = summary(&conn);
+        assert!(ended_at.is_some());
+        assert_eq!((interrupted, total), (Some(1), None));
+
+        // A clean resume fills in the numbers but keeps the interruption
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            Uuid::new_v4(),
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        CompletionDb::finish_run_sync(&conn, run_id, Some(&report), false).unwrap();
+        let (_, interrupted, total) = summary(&conn);
+        assert_eq!((interrupted, total), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_game_results_recorded() {
+        let (conn, run_id) = new_in_memory_db();
+        let game_id = Uuid::new_v4();
+        let winner = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game_id,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        assert!(
+            CompletionDb::get_games_missing_results_sync(&conn, run_id)
+                .unwrap()
+                .is_empty()
+        );
+
+        let status = PollGameStatus {
+            id: game_id,
+            status: "finished".to_string(),
+            updated_at: None,
+            enqueued_at: None,
+            created_at: None,
+        };
+        CompletionDb::update_game_statuses_sync(&conn, &[status]).unwrap();
+        assert_eq!(
+            CompletionDb::get_games_missing_results_sync(&conn, run_id).unwrap(),
+            vec![game_id]
+        );
+
+        let result: PollGameResult = serde_json::from_value(serde_json::json!({
+            "id": game_id,
+            "status": "finished",
+            "turns": 87,
+            "winner": winner,
+            "snakes": [
+                { "id": winner, "placement": 1, "death": null },
+                { "id": Uuid::new_v4(), "placement": 2, "death": { "cause": "head-collision", "turn": 87 } },
+                { "id": Uuid::new_v4(), "placement": 3, "death": { "cause": "wall-collision", "turn": 12 } },
+            ],
+        }))
+        .unwrap();
+        assert_eq!(result.end_reason(), Some("head-collision"));
+        CompletionDb::record_game_results_sync(&conn, &[result]).unwrap();
+
+        let stored: (i32, String, String) = conn
+            .query_row(
+                "SELECT turns, winner_snake_id, end_reason FROM games WHERE game_id = ?1",
+                params![game_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            stored,
+            (87, winner.to_string(), "head-collision".to_string())
+        );
+        assert!(
+            CompletionDb::get_games_missing_results_sync(&conn, run_id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_new_run_shares_results_db() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        CompletionDb::init_schema_sync(&conn).unwrap();
+        let run_id = Uuid::new_v4();
+        CompletionDb::insert_run_sync(&conn, run_id, "http://blue", "test", 30).unwrap();
+        let blue = CompletionDb {
+            conn: Arc::new(Mutex::new(conn)),
+            run_id,
+        };
+        let green = blue.new_run("http://green", "test", 30).unwrap();
+        assert_ne!(blue.run_id, green.run_id);
+
+        let conn = blue.conn.lock().unwrap();
+        let url: String = conn
+            .query_row(
+                "SELECT base_url FROM runs WHERE run_id = ?1",
+                params![green.run_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(url, "http://green");
+    }
+
+    // ========================================================================
+
+    // Completion DB Tests
+
+    // ========================================================================
+
+    pub(crate) fn new_in_memory_db() -> (rusqlite::Connection, Uuid) {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        CompletionDb::init_schema_sync(&conn).unwrap();
+        let run_id = Uuid::new_v4();
+        CompletionDb::insert_run_sync(&conn, run_id, "http://test", "test", 30).unwrap();
+        (conn, run_id)
+    }
+
+    #[test]
+    fn test_completion_db_creation() {
+        let (conn, _run_id) = new_in_memory_db();
+        // Verify tables exist
+        let tables: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+        assert!(tables.contains(&"runs".to_string()));
+        assert!(tables.contains(&"games".to_string()));
+        assert!(tables.contains(&"admin_stats_snapshots".to_string()));
+    }
+
+    #[test]
+    fn test_record_and_query_games() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let game1 = Uuid::new_v4();
+        let game2 = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game1,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game2,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+
+        let unfinished = CompletionDb::get_unfinished_game_ids_sync(&conn, run_id).unwrap();
+        assert_eq!(unfinished.len(), 2);
+        assert!(unfinished.contains(&game1));
+        assert!(unfinished.contains(&game2));
+
+        // Verify UUID TEXT roundtrip
+        let stored: String = conn
+            .query_row(
+                "SELECT game_id FROM games WHERE game_id = ?1",
+                params![game1.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(Uuid::parse_str(&stored).unwrap(), game1);
+    }
+
+    #[test]
+    fn test_update_game_statuses() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let game1 = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game1,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+
+        // First update: still running
+        let statuses = vec![PollGameStatus {
+            id: game1,
+            status: "running".to_string(),
+            updated_at: Some(Utc::now()),
+            enqueued_at: Some(Utc::now()),
+            created_at: Some(Utc::now()),
+        }];
+        CompletionDb::update_game_statuses_sync(&conn, &statuses).unwrap();
+
+        let status: String = conn
+            .query_row(
+                "SELECT status FROM games WHERE game_id = ?1",
+                params![game1.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(status, "running");
+
+        let first_seen: Option<String> = conn
+            .query_row(
+                "SELECT first_seen_finished_at FROM games WHERE game_id = ?1",
+                params![game1.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(first_seen.is_none());
+
+        // Second update: finished
+        let statuses = vec![PollGameStatus {
+            id: game1,
+            status: "finished".to_string(),
+            updated_at: Some(Utc::now()),
+            enqueued_at: Some(Utc::now()),
+            created_at: Some(Utc::now()),
+        }];
+        CompletionDb::update_game_statuses_sync(&conn, &statuses).unwrap();
+
+        let first_seen: Option<String> = conn
+            .query_row(
+                "SELECT first_seen_finished_at FROM games WHERE game_id = ?1",
+                params![game1.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(first_seen.is_some());
+        let first_seen_val = first_seen.unwrap();
+
+        // Third update: should not change first_seen_finished_at
+        let statuses = vec![PollGameStatus {
+            id: game1,
+            status: "finished".to_string(),
+            updated_at: Some(Utc::now()),
+            enqueued_at: Some(Utc::now()),
+            created_at: Some(Utc::now()),
+        }];
+        CompletionDb::update_game_statuses_sync(&conn, &statuses).unwrap();
+
+        let first_seen_after: String = conn
+            .query_row(
+                "SELECT first_seen_finished_at FROM games WHERE game_id = ?1",
+                params![game1.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(first_seen_val, first_seen_after);
+    }
+
+    #[test]
+    fn test_report_generation() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let game1 = Uuid::new_v4();
+        let game2 = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game1,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game2,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+
+        // Finish game1
+        let statuses = vec![PollGameStatus {
+            id: game1,
+            status: "finished".to_string(),
+            updated_at: Some(Utc::now()),
+            enqueued_at: Some(Utc::now() - chrono::Duration::seconds(5)),
+            created_at: Some(Utc::now() - chrono::Duration::seconds(10)),
+        }];
+        CompletionDb::update_game_statuses_sync(&conn, &statuses).unwrap();
+
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        assert_eq!(report.total_games, 2);
+        assert_eq!(report.finished, 1);
+        // game2 still at 'created' status
+        assert_eq!(report.not_started, 1);
+        assert_eq!(report.stuck_running, 0);
+    }
+
+    #[test]
+    fn test_report_all_games_accounted_for() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let game1 = Uuid::new_v4();
+        let game2 = Uuid::new_v4();
+        let game3 = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game1,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game2,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game3,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+
+        // game1 finished, game2 running (stuck), game3 still created
+        CompletionDb::update_game_statuses_sync(
+            &conn,
+            &[PollGameStatus {
+                id: game1,
+                status: "finished".to_string(),
+                updated_at: Some(Utc::now()),
+                enqueued_at: None,
+                created_at: None,
+            }],
+        )
+        .unwrap();
+        CompletionDb::update_game_statuses_sync(
+            &conn,
+            &[PollGameStatus {
+                id: game2,
+                status: "running".to_string(),
+                updated_at: None,
+                enqueued_at: None,
+                created_at: None,
+            }],
+        )
+        .unwrap();
+
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        assert_eq!(
+            report.total_games,
+            report.finished + report.stuck_running + report.not_started
+        );
+        assert_eq!(report.finished, 1);
+        assert_eq!(report.stuck_running, 1);
+        assert_eq!(report.not_started, 1);
+    }
+
+    #[test]
+    fn test_record_admin_stats() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let stats = AdminStatsResponse {
+            job_queue: AdminJobQueue {
+                ready: 10,
+                running: 5,
+                scheduled: 3,
+                total: 18,
+            },
+            game_counts: AdminGameCounts {
+                waiting: 8,
+                running: 4,
+                finished: 100,
+                total: 112,
+            },
+            games_created: AdminTimeWindow {
+                last_hour: 50,
+                last_24h: 200,
+                last_7d: 500,
+            },
+            games_finished: AdminTimeWindow {
+                last_hour: 45,
+                last_24h: 190,
+                last_7d: 490,
+            },
+            avg_game_duration_secs: Some(12.5),
+            recent_errors: vec![],
+        };
+        let raw_json = serde_json::to_string(&stats).unwrap();
+
+        CompletionDb::record_admin_stats_sync(&conn, run_id, "load", &stats, &raw_json).unwrap();
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM admin_stats_snapshots WHERE run_id = ?1",
+                params![run_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let phase: String = conn
+            .query_row(
+                "SELECT phase FROM admin_stats_snapshots WHERE run_id = ?1",
+                params![run_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(phase, "load");
+    }
+
+    #[test]
+    fn test_admin_stats_summary_in_report() {
+        let (conn, run_id) = new_in_memory_db();
+
+        let stats = AdminStatsResponse {
+            job_queue: AdminJobQueue {
+                ready: 10,
+                running: 5,
+                scheduled: 3,
+                total: 18,
+            },
+            game_counts: AdminGameCounts {
+                waiting: 8,
+                running: 4,
+                finished: 100,
+                total: 112,
+            },
+            games_created: AdminTimeWindow {
+                last_hour: 50,
+                last_24h: 200,
+                last_7d: 500,
+            },
+            games_finished: AdminTimeWindow {
+                last_hour: 45,
+                last_24h: 190,
+                last_7d: 490,
+            },
+            avg_game_duration_secs: Some(12.5),
+            recent_errors: vec![],
+        };
+        let raw_json = serde_json::to_string(&stats).unwrap();
+        CompletionDb::record_admin_stats_sync(&conn, run_id, "load", &stats, &raw_json).unwrap();
+
+        // Add a second snapshot with higher peaks
+        let stats2 = AdminStatsResponse {
+            job_queue: AdminJobQueue {
+                ready: 20,
+                running: 8,
+                scheduled: 1,
+                total: 29,
+            },
+            game_counts: AdminGameCounts {
+                waiting: 15,
+                running: 6,
+                finished: 110,
+                total: 131,
+            },
+            games_created: AdminTimeWindow {
+                last_hour: 60,
+                last_24h: 210,
+                last_7d: 510,
+            },
+            games_finished: AdminTimeWindow {
+                last_hour: 55,
+                last_24h: 200,
+                last_7d: 500,
+            },
+            avg_game_duration_secs: Some(11.0),
+            recent_errors: vec![],
+        };
+        let raw_json2 = serde_json::to_string(&stats2).unwrap();
+        CompletionDb::record_admin_stats_sync(&conn, run_id, "polling", &stats2, &raw_json2)
+            .unwrap();
+
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        let summary = report.admin_stats_summary.unwrap();
+
+        assert_eq!(summary.snapshot_count, 2);
+        assert_eq!(summary.peak_jobs_ready, 20);
+        assert_eq!(summary.peak_jobs_running, 8);
+        assert_eq!(summary.peak_games_waiting, 15);
+        assert_eq!(summary.peak_games_running, 6);
+        // Final state from latest snapshot
+        assert_eq!(summary.final_games_finished, 110);
+        assert_eq!(summary.final_games_total, 131);
+        assert!(!summary.has_errors);
+    }
+
+    #[test]
+    fn test_report_without_admin_stats() {
+        let (conn, run_id) = new_in_memory_db();
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        assert!(report.admin_stats_summary.is_none());
+    }
+}
@@ -0,0 +1,843 @@
+//! Game creation: the HTTP client, variant selection, in-flight limiting and retries.
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::db::CompletionDb;
+use crate::stats::Stats;
+
+pub fn create_http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .pool_max_idle_per_host(100)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+#[derive(Debug)]
+struct CreateGameResult {
+    game_id: Uuid,
+    latency: Duration,
+}
+
+#[derive(Debug)]
+enum GameCreationError {
+    Request(reqwest::Error),
+    Api {
+        status: StatusCode,
+        body: String,
+        /// The response's `Retry-After`, in seconds form
+        retry_after: Option<Duration>,
+    },
+    Parse(String),
+}
+
+impl std::fmt::Display for GameCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "Request error: {}", e),
+            Self::Api { status, body, .. } => write!(f, "API error {}: {}", status, body),
+            Self::Parse(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GameCreationError {}
+
+impl GameCreationError {
+    fn category(&self) -> FailureCategory {
+        match self {
+            Self::Request(e) if e.is_timeout() => FailureCategory::Timeout,
+            Self::Request(_) => FailureCategory::Connection,
+            Self::Api { status, .. } if *status == StatusCode::TOO_MANY_REQUESTS => {
+                FailureCategory::RateLimited
+            }
+            Self::Api { status, .. } if status.is_server_error() => FailureCategory::ServerError,
+            Self::Api { .. } => FailureCategory::ClientError,
+            Self::Parse(_) => FailureCategory::Parse,
+        }
+    }
+}
+
+/// Why a Create Game call failed, for the per-category failure counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCategory {
+    Timeout,
+    /// 429
+    RateLimited,
+    /// 5xx
+    ServerError,
+    /// Any other non-success status
+    ClientError,
+    /// Couldn't connect, or the connection failed mid-request
+    Connection,
+    /// A success status with a body we couldn't read
+    Parse,
+}
+
+impl FailureCategory {
+    pub const ALL: [FailureCategory; 6] = [
+        FailureCategory::Timeout,
+        FailureCategory::RateLimited,
+        FailureCategory::ServerError,
+        FailureCategory::ClientError,
+        FailureCategory::Connection,
+        FailureCategory::Parse,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureCategory::Timeout => "timeout",
+            FailureCategory::RateLimited => "429",
+            FailureCategory::ServerError => "5xx",
+            FailureCategory::ClientError => "4xx",
+            FailureCategory::Connection => "connection",
+            FailureCategory::Parse => "parse",
+        }
+    }
+}
+
+async fn create_game(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    snakes: &[Uuid],
+    board: &str,
+    game_type: &str,
+) -> Result<CreateGameResult, GameCreationError> {
+    let start = Instant::now();
+
+    let response = client
+        .post(format!("{}/api/v1/games", base_url))
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "snakes": snakes,
+            "board": board,
+            "game_type": game_type,
+        }))
+        .send()
+        .await;
+
+    let latency = start.elapsed();
+
+    match response {
+        Ok(resp) if resp.status().is_success() => {
+            let body: serde_json::Value = resp
+                .json()
+                .await
+                .map_err(|e| GameCreationError::Parse(e.to_string()))?;
+
+            let game_id = body["id"]
+                .as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .ok_or_else(|| GameCreationError::Parse("Missing game id".to_string()))?;
+
+            Ok(CreateGameResult { game_id, latency })
+        }
+        Ok(resp) => {
+            let status = resp.status();
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs);
+            let body = resp.text().await.unwrap_or_default();
+            Err(GameCreationError::Api {
+                status,
+                body,
+                retry_after,
+            })
+        }
+        Err(e) => Err(GameCreationError::Request(e)),
+    }
+}
+
+// ============================================================================
+
+// Load Patterns
+
+// ============================================================================
+
+#[derive(Clone)]
+pub struct LoadConfig {
+    pub base_url: String,
+    pub token: String,
+    pub snakes: Vec<Uuid>,
+    /// Board/type combinations to create, picked at random by weight
+    pub variants: Vec<GameVariant>,
+    pub completion_db: Option<CompletionDb>,
+    pub in_flight_limit: Option<InFlightLimit>,
+    /// Retries per creation for 429 and 503 responses
+    pub max_retries: u32,
+    /// Random subset size per game; every snake in the pool when unset
+    pub snakes_per_game: Option<SnakesPerGame>,
+}
+
+/// `--snakes-per-game`: how many snakes from the pool go in each game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnakesPerGame {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl SnakesPerGame {
+    /// Parse "3" or "2-4"
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid snake count '{}'", n.trim()))
+        };
+        let (min, max) = match s.split_once('-') {
+            Some((min, max)) => (parse(min)?, parse(max)?),
+            None => {
+                let n = parse(s)?;
+                (n, n)
+            }
+        };
+        if min == 0 || min > max {
+            return Err(format!(
+                "Snakes per game must be a positive count or range like '2-4', got '{}'",
+                s
+            ));
+        }
+        Ok(Self { min, max })
+    }
+
+    /// A random subset of `pool`, capped at the pool's size
+    fn pick(self, pool: &[Uuid], rng: &mut impl rand::Rng) -> Vec<Uuid> {
+        use rand::seq::SliceRandom as _;
+
+        let count = rng.gen_range(self.min..=self.max).min(pool.len());
+        pool.choose_multiple(rng, count).copied().collect()
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wait for a slot; the wait isn't counted in create latency
+    Queue,
+    /// Skip the creation and count it as dropped
+    Drop,
+}
+
+/// The `--max-in-flight` cap, shared by every pattern in the run
+#[derive(Clone)]
+pub struct InFlightLimit {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    overflow: Overflow,
+}
+
+impl InFlightLimit {
+    pub fn new(max_in_flight: usize, overflow: Overflow) -> Self {
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(max_in_flight)),
+            overflow,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GameVariant {
+    pub board: String,
+    #[serde(rename = "type")]
+    pub game_type: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// Parse a `--mix` like "standard:11x11=70,royale:19x19=30". A missing
+/// weight counts as 1.
+pub fn parse_mix(s: &str) -> Result<Vec<GameVariant>, String> {
+    const FORMAT: &str =
+        "Mix format: 'type:board=weight,...' (e.g., 'standard:11x11=70,royale:19x19=30')";
+    s.split(',')
+        .map(|entry| {
+            let entry = entry.trim();
+            let (config, weight) = match entry.split_once('=') {
+                Some((config, weight)) => (
+                    config,
+                    weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid weight in '{}'", entry))?,
+                ),
+                None => (entry, 1),
+            };
+            let (game_type, board) = config.split_once(':').ok_or(FORMAT)?;
+            if game_type.trim().is_empty() || board.trim().is_empty() {
+                return Err(FORMAT.to_string());
+            }
+            if weight == 0 {
+                return Err(format!("Weight must be positive in '{}'", entry));
+            }
+            Ok(GameVariant {
+                board: board.trim().to_string(),
+                game_type: game_type.trim().to_string(),
+                weight,
+            })
+        })
+        .collect()
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// The variant a roll in `0..total weight` lands on
+fn pick_variant(variants: &[GameVariant], mut roll: u32) -> &GameVariant {
+    for variant in variants {
+        if roll < variant.weight {
+            return variant;
+        }
+        roll -= variant.weight;
+    }
+    &variants[variants.len() - 1]
+}
+
+/// A creation's place under the run's in-flight cap, held until it finishes
+pub struct InFlightSlot {
+    _permit: Option<tokio::sync::OwnedSemaphorePermit>,
+}
+
+/// Take a slot under the run's in-flight cap before spawning a creation, so
+/// queued creations wait in the pattern loop rather than as parked tasks.
+/// `None` means skip the creation: the cap is full under `--when-full drop`,
+/// or the run was stopped while waiting.
+pub async fn acquire_slot(
+    config: &LoadConfig,
+    stats: &Stats,
+    cancel: &CancellationToken,
+) -> Option<InFlightSlot> {
+    let Some(limit) = &config.in_flight_limit else {
+        return Some(InFlightSlot { _permit: None });
+    };
+    match limit.semaphore.clone().try_acquire_owned() {
+        Ok(permit) => Some(InFlightSlot {
+            _permit: Some(permit),
+        }),
+        Err(_) if limit.overflow == Overflow::Drop => {
+            stats.dropped.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+        Err(_) => {
+            stats.queued.fetch_add(1, Ordering::Relaxed);
+            stats.waiting.fetch_add(1, Ordering::Relaxed);
+            let permit = tokio::select! {
+                _ = cancel.cancelled() => None,
+                // The semaphore is never closed
+                permit = limit.semaphore.clone().acquire_owned() => permit.ok(),
+            };
+            stats.waiting.fetch_sub(1, Ordering::Relaxed);
+            Some(InFlightSlot {
+                _permit: Some(permit?),
+            })
+        }
+    }
+}
+
+/// [`create_and_record`] holding a slot from [`acquire_slot`]
+pub async fn create_limited(
+    client: reqwest::Client,
+    config: LoadConfig,
+    stats: Arc<Stats>,
+    _slot: InFlightSlot,
+) {
+    stats.in_flight.fetch_add(1, Ordering::Relaxed);
+    create_and_record(client, config, stats.clone()).await;
+    stats.in_flight.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// First retry's backoff when the server sends no `Retry-After`; doubles per
+/// attempt up to [`RETRY_MAX_DELAY`]
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Whether a failed creation is worth retrying: throttled or unavailable
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// How long to wait before retry number `attempt` (0-based). A `Retry-After`
+/// is honored as a floor with up to 20% jitter on top, so throttled clients
+/// don't all come back at once; otherwise exponential backoff with equal
+/// jitter. `jitter` is uniform in [0, 1).
+fn retry_delay(attempt: u32, retry_after: Option<Duration>, jitter: f64) -> Duration {
+    match retry_after {
+        Some(after) => after + after.mul_f64(0.2 * jitter),
+        None => {
+            let backoff = RETRY_BASE_DELAY
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(RETRY_MAX_DELAY);
+            backoff / 2 + (backoff / 2).mul_f64(jitter)
+        }
+    }
+}
+
+/// [`create_game`], retrying 429s and 503s up to the run's `--max-retries`.
+/// The latency reported is the successful attempt's alone.
+async fn create_game_with_retries(
+    client: &reqwest::Client,
+    config: &LoadConfig,
+    snakes: &[Uuid],
+    variant: &GameVariant,
+    stats: &Stats,
+) -> Result<CreateGameResult, GameCreationError> {
+    let mut attempt = 0;
+    loop {
+        let result = create_game(
+            client,
+            &config.base_url,
+            &config.token,
+            snakes,
+            &variant.board,
+            &variant.game_type,
+        )
+        .await;
+        match result {
+            Err(GameCreationError::Api {
+                status,
+                retry_after,
+                ..
+            }) if is_retryable(status) && attempt < config.max_retries => {
+                stats.record_retry(status);
+                let delay = retry_delay(attempt, retry_after, rand::random());
+                tracing::debug!(
+                    status = status.as_u16(),
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "retrying game creation"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Create one game and record the outcome in the stats and completion db
+async fn create_and_record(client: reqwest::Client, config: LoadConfig, stats: Arc<Stats>) {
+    let total_weight: u32 = config.variants.iter().map(|v| v.weight).sum();
+    let variant = pick_variant(
+        &config.variants,
+        rand::random::<u32>() % total_weight.max(1),
+    );
+    let snakes = match config.snakes_per_game {
+        Some(per_game) => per_game.pick(&config.snakes, &mut rand::thread_rng()),
+        None => config.snakes.clone(),
+    };
+    match create_game_with_retries(&client, &config, &snakes, variant, &stats).await {
+        Ok(result) => {
+            stats.record_success(result.game_id, result.latency);
+            if let Some(ref db) = config.completion_db
+                && let Err(e) = db
+                    .record_game_created(result.game_id, result.latency, variant.clone())
+                    .await
+            {
+                tracing::warn!(error = %e, "failed to record game in completion db");
+            }
+            tracing::info!(
+                game_id = %result.game_id,
+                latency_ms = result.latency.as_millis() as u64,
+                "game_created"
+            );
+        }
+        Err(e) => {
+            let category = e.category();
+            stats.record_failure(category);
+            tracing::warn!(error = %e, category = category.as_str(), "game_creation_failed");
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicU64;
+
+    pub(crate) fn default_variant() -> GameVariant {
+        GameVariant {
+            board: "11x11".to_string(),
+            game_type: "standard".to_string(),
+            weight: 1,
+        }
+    }
+
+    #[test]
+    fn test_pick_variant_by_weight() {
+        let variants = vec![
+            GameVariant {
+                weight: 3,
+                ..default_variant()
+            },
+            GameVariant {
+                board: "19x19".to_string(),
+                ..default_variant()
+            },
+        ];
+        assert_eq!(pick_variant(&variants, 0).board, "11x11");
+        assert_eq!(pick_variant(&variants, 2).board, "11x11");
+        assert_eq!(pick_variant(&variants, 3).board, "19x19");
+    }
+
+    #[test]
+    fn test_failures_are_counted_by_category() {
+        let api_error = |status| GameCreationError::Api {
+            status,
+            body: String::new(),
+            retry_after: None,
+        };
+        assert_eq!(
+            api_error(StatusCode::TOO_MANY_REQUESTS).category(),
+            FailureCategory::RateLimited
+        );
+        assert_eq!(
+            api_error(StatusCode::BAD_GATEWAY).category(),
+            FailureCategory::ServerError
+        );
+        assert_eq!(
+            api_error(StatusCode::BAD_REQUEST).category(),
+            FailureCategory::ClientError
+        );
+        assert_eq!(
+            GameCreationError::Parse("Missing game id".to_string()).category(),
+            FailureCategory::Parse
+        );
+
+        let stats = Stats::new();
+        stats.record_failure(FailureCategory::RateLimited);
+        stats.record_failure(FailureCategory::RateLimited);
+        stats.record_failure(FailureCategory::Timeout);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.failed, 3);
+        assert_eq!(snapshot.failures_of(FailureCategory::RateLimited), 2);
+        assert_eq!(snapshot.failure_breakdown(), "timeout: 1 | 429: 2");
+    }
+
+    #[tokio::test]
+    async fn test_connection_failures_are_categorized() {
+        // Nothing listens on a port we just released
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let err = create_game(
+            &create_http_client(),
+            &format!("http://{}", addr),
+            "token",
+            &[],
+            "11x11",
+            "standard",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.category(), FailureCategory::Connection);
+    }
+
+    /// A server that accepts Create Game requests and answers one only when
+    /// `release` is notified
+    async fn stalled_create_server(release: Arc<tokio::sync::Notify>) -> String {
+        let app = axum::Router::new().route(
+            "/api/v1/games",
+            axum::routing::post(move || {
+                let release = release.clone();
+                async move {
+                    release.notified().await;
+                    axum::Json(serde_json::json!({ "id": Uuid::new_v4() }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}", addr)
+    }
+
+    fn capped_config(base_url: String, overflow: Overflow) -> LoadConfig {
+        LoadConfig {
+            base_url,
+            token: "token".to_string(),
+            snakes: Vec::new(),
+            variants: vec![GameVariant {
+                board: "11x11".to_string(),
+                game_type: "standard".to_string(),
+                weight: 1,
+            }],
+            completion_db: None,
+            in_flight_limit: Some(InFlightLimit::new(2, overflow)),
+            max_retries: 0,
+            snakes_per_game: None,
+        }
+    }
+
+    async fn wait_for(condition: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_cap_drops_when_full() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let config = capped_config(stalled_create_server(release.clone()).await, Overflow::Drop);
+        let client = create_http_client();
+        let stats = Arc::new(Stats::new());
+
+        let cancel = CancellationToken::new();
+        let mut handles = Vec::new();
+        for _ in 0..5 {
+            if let Some(slot) = acquire_slot(&config, &stats, &cancel).await {
+                handles.push(tokio::spawn(create_limited(
+                    client.clone(),
+                    config.clone(),
+                    stats.clone(),
+                    slot,
+                )));
+            }
+        }
+        assert_eq!(handles.len(), 2);
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 3);
+        wait_for(|| stats.in_flight.load(Ordering::Relaxed) == 2).await;
+
+        wait_for(|| {
+            release.notify_waiters();
+            stats.successful.load(Ordering::Relaxed) == 2
+        })
+        .await;
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.dropped, 3);
+        assert_eq!(snapshot.in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_cap_queues_when_full() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let config = capped_config(
+            stalled_create_server(release.clone()).await,
+            Overflow::Queue,
+        );
+        let client = create_http_client();
+        let stats = Arc::new(Stats::new());
+
+        let cancel = CancellationToken::new();
+        let mut handles = Vec::new();
+        for _ in 0..2 {
+            let slot = acquire_slot(&config, &stats, &cancel).await.unwrap();
+            handles.push(tokio::spawn(create_limited(
+                client.clone(),
+                config.clone(),
+                stats.clone(),
+                slot,
+            )));
+        }
+        wait_for(|| stats.in_flight.load(Ordering::Relaxed) == 2).await;
+
+        // The third waits for a slot before anything is spawned
+        let queued = tokio::spawn({
+            let (config, stats, cancel) = (config.clone(), stats.clone(), cancel.clone());
+            async move { acquire_slot(&config, &stats, &cancel).await }
+        });
+        wait_for(|| stats.waiting.load(Ordering::Relaxed) == 1).await;
+        assert_eq!(stats.in_flight.load(Ordering::Relaxed), 2);
+        wait_for(|| {
+            release.notify_waiters();
+            queued.is_finished()
+        })
+        .await;
+        let slot = queued.await.unwrap().expect("a slot frees up");
+        handles.push(tokio::spawn(create_limited(
+            client.clone(),
+            config.clone(),
+            stats.clone(),
+            slot,
+        )));
+
+        // Answer in-flight requests until the queued one has gone through too
+        wait_for(|| {
+            release.notify_waiters();
+            stats.successful.load(Ordering::Relaxed) == 3
+        })
+        .await;
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.queued, 1);
+        assert_eq!(snapshot.waiting, 0);
+        assert_eq!(snapshot.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_queued_slot_gives_up_on_cancel() {
+        let config = capped_config("http://unused".to_string(), Overflow::Queue);
+        let stats = Stats::new();
+        let cancel = CancellationToken::new();
+        let _held = [
+            acquire_slot(&config, &stats, &cancel).await.unwrap(),
+            acquire_slot(&config, &stats, &cancel).await.unwrap(),
+        ];
+
+        cancel.cancel();
+        assert!(acquire_slot(&config, &stats, &cancel).await.is_none());
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.queued, 1);
+        assert_eq!(snapshot.waiting, 0);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        // Retry-After is a floor
+        let after = Some(Duration::from_secs(2));
+        assert_eq!(retry_delay(0, after, 0.0), Duration::from_secs(2));
+        assert_eq!(retry_delay(5, after, 0.5), Duration::from_millis(2200));
+
+        // Otherwise exponential with equal jitter, capped
+        assert_eq!(retry_delay(0, None, 0.0), Duration::from_millis(125));
+        assert_eq!(retry_delay(2, None, 0.0), Duration::from_millis(500));
+        assert_eq!(retry_delay(2, None, 0.999).as_millis(), 999);
+        assert_eq!(retry_delay(30, None, 0.0), RETRY_MAX_DELAY / 2);
+    }
+
+    #[tokio::test]
+    async fn test_creation_retries_throttled_requests() {
+        // 429 twice, then succeed
+        let calls = Arc::new(AtomicU64::new(0));
+        let app = axum::Router::new().route(
+            "/api/v1/games",
+            axum::routing::post({
+                let calls = calls.clone();
+                move || {
+                    let calls = calls.clone();
+                    async move {
+                        if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+                            axum::response::IntoResponse::into_response((
+                                StatusCode::TOO_MANY_REQUESTS,
+                                [(axum::http::header::RETRY_AFTER, "0")],
+                            ))
+                        } else {
+                            axum::response::IntoResponse::into_response(axum::Json(
+                                serde_json::json!({ "id": Uuid::new_v4() }),
+                            ))
+                        }
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let variant = GameVariant {
+            board: "11x11".to_string(),
+            game_type: "standard".to_string(),
+            weight: 1,
+        };
+        let mut config = LoadConfig {
+            base_url: format!("http://{}", addr),
+            token: "token".to_string(),
+            snakes: Vec::new(),
+            variants: vec![variant.clone()],
+            completion_db: None,
+            in_flight_limit: None,
+            max_retries: 1,
+            snakes_per_game: None,
+        };
+        let client = create_http_client();
+        let stats = Stats::new();
+
+        // One retry isn't enough
+        let err = create_game_with_retries(&client, &config, &[], &variant, &stats)
+            .await
+            .unwrap_err();
+        assert_eq!(err.category(), FailureCategory::RateLimited);
+        assert_eq!(stats.retries_rate_limited.load(Ordering::Relaxed), 1);
+
+        calls.store(0, Ordering::Relaxed);
+        config.max_retries = 2;
+        create_game_with_retries(&client, &config, &[], &variant, &stats)
+            .await
+            .unwrap();
+        assert_eq!(stats.retries_rate_limited.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.retries_unavailable.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_parse_mix() {
+        assert_eq!(
+            parse_mix("standard:11x11=70, royale:19x19=30").unwrap(),
+            vec![
+                GameVariant {
+                    weight: 70,
+                    ..default_variant()
+                },
+                GameVariant {
+                    board: "19x19".to_string(),
+                    game_type: "royale".to_string(),
+                    weight: 30,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_mix("standard:11x11").unwrap(),
+            vec![default_variant()]
+        );
+        assert!(parse_mix("standard=70").is_err());
+        assert!(parse_mix("standard:11x11=0").is_err());
+        assert!(parse_mix("standard:11x11=lots").is_err());
+        assert!(parse_mix(":11x11=1").is_err());
+    }
+
+    #[test]
+    fn test_snakes_per_game_parse() {
+        assert_eq!(
+            SnakesPerGame::parse("2-4").unwrap(),
+            SnakesPerGame { min: 2, max: 4 }
+        );
+        assert_eq!(
+            SnakesPerGame::parse("3").unwrap(),
+            SnakesPerGame { min: 3, max: 3 }
+        );
+        assert!(SnakesPerGame::parse("0").is_err());
+        assert!(SnakesPerGame::parse("4-2").is_err());
+        assert!(SnakesPerGame::parse("two").is_err());
+    }
+
+    #[test]
+    fn test_snakes_per_game_pick() {
+        use rand::SeedableRng as _;
+
+        let pool: Vec<Uuid> = (0..8).map(|_| Uuid::new_v4()).collect();
+        let per_game = SnakesPerGame { min: 2, max: 4 };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut sizes = std::collections::BTreeSet::new();
+        for _ in 0..200 {
+            let picked = per_game.pick(&pool, &mut rng);
+            assert!((2..=4).contains(&picked.len()));
+            assert!(picked.iter().all(|id| pool.contains(id)));
+            let unique: std::collections::HashSet<_> = picked.iter().collect();
+            assert_eq!(unique.len(), picked.len());
+            sizes.insert(picked.len());
+        }
+        assert_eq!(sizes.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        // A pool smaller than the count is used whole
+        assert_eq!(per_game.pick(&pool[..1], &mut rng), pool[..1].to_vec());
+    }
+}
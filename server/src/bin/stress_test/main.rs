@@ -0,0 +1,1005 @@
+//! Stress test binary for Arena - generates load via the Create Game API.
+//!
+//! Supports configurable load patterns (steady stream, batch, ramp, Poisson, sine wave),
+//! periodic stats output, structured tracing events for Eyes integration, and game
+//! completion tracking via SQLite. `--mock-snakes` serves and registers local mock
+//! snakes so created games actually run and finish, and `--spectators` streams their
+//! frames over the board viewer's WebSocket. `--metrics-addr` serves live counters
+//! for Prometheus. `stress-test compare <run_a> <run_b>`
+//! diffs two runs from the results DB for before/after checks of server changes, and
+//! `stress-test poll --run-id <id>` resumes polling for a run the tool didn't finish.
+//! `--assert "success_rate>=99,stuck==0"` fails the run with a non-zero exit for CI.
+//! Repeating `--url` (or `--targets <file>`) drives several environments with the same
+//! load at once, each with its own stats and results-DB run, plus a side-by-side summary.
+//! `--warmup 60s` runs unmeasured load first so cold caches don't skew comparisons.
+//! Ctrl+C stops load generation and moves on to polling and the report rather than
+//! losing the run.
+
+mod db;
+mod load;
+mod mock_snakes;
+mod patterns;
+mod poll;
+mod report;
+mod scenarios;
+mod spectators;
+mod stats;
+mod targets;
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use clap::Parser;
+use color_eyre::eyre::{Context as _, eyre};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::db::CompletionDb;
+use crate::load::{
+    GameVariant, InFlightLimit, LoadConfig, Overflow, SnakesPerGame, create_http_client, parse_mix,
+};
+use crate::mock_snakes::{register_mock_snakes, start_mock_snake_server};
+use crate::patterns::{
+    BatchPattern, LoadPattern, PoissonPattern, RampPattern, SinePattern, SteadyStreamPattern,
+    parse_duration,
+};
+use crate::poll::{admin_stats_load_phase, poll_and_report};
+use crate::report::{Report, compare_runs};
+use crate::scenarios::{PhasePlan, Scenario, Thresholds, parse_assertions, run_phase};
+use crate::spectators::spectator_task;
+use crate::stats::{
+    Stats, StatsSnapshot, format_duration, start_metrics_server, stats_output_task,
+};
+use crate::targets::{Target, print_target_summary, resolve_targets, target_report_path};
+
+#[derive(Parser)]
+#[command(name = "stress-test")]
+#[command(about = "Stress test Arena by generating game creation load")]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Arena API base URL. Repeat to drive several environments (e.g.
+    /// blue/green) with the same load at once, each with its own stats and
+    /// results-DB run. [default: http://localhost:3000]
+    #[arg(long)]
+    url: Vec<String>,
+
+    /// File of further target base URLs, one per line (`#` starts a comment)
+    #[arg(long)]
+    targets: Option<String>,
+
+    /// Comma-separated snake UUIDs to use for games. Optional with
+    /// --scenario if the scenario lists its own snakes.
+    #[arg(long)]
+    snakes: Option<String>,
+
+    /// Pick this many snakes at random from the pool for each game, as a
+    /// count or range (e.g., "2-4"), instead of sending every snake
+    #[arg(long)]
+    snakes_per_game: Option<String>,
+
+    /// Serve N mock snakes locally and register them (as `stress-mock-N`)
+    /// in place of --snakes, so games run to completion no matter which
+    /// snakes exist on the target
+    #[arg(long, conflicts_with = "snakes")]
+    mock_snakes: Option<usize>,
+
+    /// Address the mock snake server listens on
+    #[arg(long, default_value = "0.0.0.0:0")]
+    mock_snake_bind: String,
+
+    /// Base URL the Arena server reaches the mock snakes at
+    /// [default: http://127.0.0.1:<bound port>]
+    #[arg(long)]
+    mock_snake_url: Option<String>,
+
+    /// API token for authentication. Needs the `games:create` scope (plus
+    /// `admin` unless --no-admin-stats), e.g. `arena auth token create -s games:create`
+    #[arg(long, env = "ARENA_TOKEN", required = true)]
+    token: Option<String>,
+
+    #[command(flatten)]
+    patterns: PatternArgs,
+
+    /// TOML scenario file describing phases, snake sets, board/type mixes,
+    /// and pass/fail thresholds. Replaces the pattern flags and --duration.
+    #[arg(long, conflicts_with_all = ["steady", "batch", "ramp", "poisson", "sine", "duration"])]
+    scenario: Option<String>,
+
+    /// Pass/fail checks on the final numbers, as comma-separated
+    /// `metric<op>value` (e.g., "success_rate>=99,p95_completion_ms<=30000,stuck==0").
+    /// Any failure exits non-zero, alongside scenario thresholds.
+    #[arg(long = "assert")]
+    assertions: Option<String>,
+
+    /// Test duration (e.g., "5m", "1h", "30s")
+    #[arg(long, default_value = "1m")]
+    duration: String,
+
+    /// Run the first phase's load for this long (e.g., "60s") before the
+    /// measured run. Warmup games are left out of the stats, the results
+    /// DB, and the report, so cold caches don't skew comparisons.
+    #[arg(long)]
+    warmup: Option<String>,
+
+    /// Most Create Game requests in flight at once, across every pattern.
+    /// Unlimited by default.
+    #[arg(long)]
+    max_in_flight: Option<usize>,
+
+    /// What to do with a creation when --max-in-flight requests are already
+    /// in flight: wait for a slot, or skip it
+    #[arg(long, value_enum, default_value = "queue", requires = "max_in_flight")]
+    when_full: Overflow,
+
+    /// Times to retry a creation that gets a 429 or 503, honoring
+    /// `Retry-After` and otherwise backing off exponentially with jitter
+    #[arg(long, default_value = "0")]
+    max_retries: u32,
+
+    /// Concurrent spectators streaming frames from the games this run
+    /// creates, to load the board viewer's WebSocket alongside creation
+    #[arg(long, default_value = "0")]
+    spectators: usize,
+
+    /// Serve the run's creation, latency, and spectator counters at
+    /// `/metrics` on this address (e.g. "0.0.0.0:9091") for Prometheus to
+    /// scrape during long soak tests
+    #[arg(long)]
+    metrics_addr: Option<String>,
+
+    /// Stats output interval in seconds
+    #[arg(long, default_value = "10")]
+    stats_interval: u64,
+
+    /// Board size for games
+    #[arg(long, default_value = "11x11")]
+    board: String,
+
+    /// Weighted blend of game types and board sizes, as type:board=weight
+    /// (e.g., "standard:11x11=70,royale:19x19=30"). Replaces --board and
+    /// --type, and is the default mix for scenario phases.
+    #[arg(long, conflicts_with_all = ["board", "game_type"])]
+    mix: Option<String>,
+
+    /// Game type
+    #[arg(long = "type", default_value = "standard")]
+    game_type: String,
+
+    /// SQLite database path for completion tracking
+    #[arg(long, default_value = "stress_test_results.db")]
+    db: String,
+
+    /// Poll interval in seconds for checking game completion
+    #[arg(long, default_value = "5")]
+    poll_interval: u64,
+
+    /// Seconds to wait after load generation before starting completion polling
+    #[arg(long, default_value = "0")]
+    poll_after: u64,
+
+    /// Maximum seconds to poll for game completion before giving up
+    #[arg(long, default_value = "300")]
+    poll_timeout: u64,
+
+    /// Skip completion polling and report on whatever has been seen so far;
+    /// `stress-test poll` can pick the run up later
+    #[arg(long, default_value = "false")]
+    no_poll: bool,
+
+    /// Disable admin stats collection
+    #[arg(long, default_value = "false")]
+    no_admin_stats: bool,
+
+    /// Path for the self-contained HTML report written after the run
+    #[arg(long, default_value = "stress_test_report.html")]
+    html_report: String,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Diff two runs in the results DB and report regressions of B against A
+    Compare {
+        /// Baseline run ID
+        run_a: Uuid,
+
+        /// Run ID to check against the baseline
+        run_b: Uuid,
+
+        /// SQLite database holding both runs
+        #[arg(long, default_value = "stress_test_results.db")]
+        db: String,
+
+        /// Percent change in the wrong direction that counts as a regression
+        #[arg(long, default_value = "10")]
+        tolerance: f64,
+    },
+
+    /// Resume completion polling and reporting for an interrupted run
+    Poll(PollArgs),
+}
+
+#[derive(clap::Args)]
+struct PollArgs {
+    /// Run to resume, as printed at the start of the run
+    #[arg(long)]
+    run_id: Uuid,
+
+    /// Arena API base URL
+    #[arg(long, default_value = "http://localhost:3000")]
+    url: String,
+
+    /// API token for authentication
+    #[arg(long, env = "ARENA_TOKEN")]
+    token: String,
+
+    /// SQLite database holding the run
+    #[arg(long, default_value = "stress_test_results.db")]
+    db: String,
+
+    /// Poll interval in seconds for checking game completion
+    #[arg(long, default_value = "5")]
+    poll_interval: u64,
+
+    /// Maximum seconds to poll for game completion before giving up
+    #[arg(long, default_value = "300")]
+    poll_timeout: u64,
+
+    /// Disable admin stats collection
+    #[arg(long, default_value = "false")]
+    no_admin_stats: bool,
+
+    /// Path for the self-contained HTML report
+    #[arg(long, default_value = "stress_test_report.html")]
+    html_report: String,
+}
+
+/// Load pattern flags. A scenario phase takes the same keys, so a phase reads
+/// like the command line it replaces.
+#[derive(clap::Args, Clone, Debug, Default)]
+struct PatternArgs {
+    /// Steady stream rate: N/s (e.g., "10/s" for 10 games per second)
+    #[arg(long)]
+    steady: Option<String>,
+
+    /// Batch pattern: games,interval (e.g., "100,30s" for 100 games every 30 seconds)
+    #[arg(long)]
+    batch: Option<String>,
+
+    /// Ramp pattern: start..end over duration, linear or in N steps
+    /// (e.g., "1/s..50/s over 10m" or "1/s..50/s over 10m in 5 steps").
+    /// Holds the end rate once the ramp completes.
+    #[arg(long)]
+    ramp: Option<String>,
+
+    /// Poisson arrivals at a mean rate: N/s (e.g., "10/s"). Gaps between
+    /// games are random, like organic traffic.
+    #[arg(long)]
+    poisson: Option<String>,
+
+    /// Sine-wave rate cycling between two rates (e.g., "5/s..50/s every 10m"),
+    /// starting at the low end, to emulate a daily cycle compressed in time
+    #[arg(long)]
+    sine: Option<String>,
+}
+
+impl PatternArgs {
+    /// Build the selected patterns, with a description like "steady:10/s+batch:100,30s"
+    fn build(&self) -> Result<(Vec<Box<dyn LoadPattern>>, String), String> {
+        let mut patterns: Vec<Box<dyn LoadPattern>> = Vec::new();
+        let mut descs = Vec::new();
+
+        if let Some(ref steady) = self.steady {
+            let pattern = SteadyStreamPattern::from_str(steady)
+                .map_err(|e| format!("Invalid steady pattern: {}", e))?;
+            descs.push(format!("steady:{}", steady));
+            patterns.push(Box::new(pattern));
+        }
+        if let Some(ref batch) = self.batch {
+            let pattern = BatchPattern::from_str(batch)
+                .map_err(|e| format!("Invalid batch pattern: {}", e))?;
+            descs.push(format!("batch:{}", batch));
+            patterns.push(Box::new(pattern));
+        }
+        if let Some(ref ramp) = self.ramp {
+            let pattern =
+                RampPattern::from_str(ramp).map_err(|e| format!("Invalid ramp pattern: {}", e))?;
+            descs.push(format!("ramp:{}", ramp));
+            patterns.push(Box::new(pattern));
+        }
+        if let Some(ref poisson) = self.poisson {
+            let pattern = PoissonPattern::from_str(poisson)
+                .map_err(|e| format!("Invalid poisson pattern: {}", e))?;
+            descs.push(format!("poisson:{}", poisson));
+            patterns.push(Box::new(pattern));
+        }
+        if let Some(ref sine) = self.sine {
+            let pattern =
+                SinePattern::from_str(sine).map_err(|e| format!("Invalid sine pattern: {}", e))?;
+            descs.push(format!("sine:{}", sine));
+            patterns.push(Box::new(pattern));
+        }
+
+        if patterns.is_empty() {
+            return Err(
+                "At least one load pattern (steady, batch, ramp, poisson, or sine) is required"
+                    .to_string(),
+            );
+        }
+        Ok((patterns, descs.join("+")))
+    }
+}
+
+// ============================================================================
+
+// Interrupts
+
+// ============================================================================
+
+/// Ctrl+C steps the run forward instead of killing it: the first press stops
+/// load generation (or, once load is done, polling), the next skips polling
+/// for the report, and one more exits on the spot. `interrupted` records
+/// that any of this happened.
+async fn watch_interrupts(
+    stop_load: CancellationToken,
+    stop_polling: CancellationToken,
+    interrupted: Arc<std::sync::atomic::AtomicBool>,
+) {
+    while tokio::signal::ctrl_c().await.is_ok() {
+        interrupted.store(true, Ordering::Relaxed);
+        println!();
+        if !stop_load.is_cancelled() {
+            println!("Interrupted: stopping load generation (Ctrl+C again to skip polling)");
+            tracing::warn!(stage = "load", "stress_test_interrupted");
+            stop_load.cancel();
+        } else if !stop_polling.is_cancelled() {
+            println!("Interrupted: skipping to the report (Ctrl+C again to exit now)");
+            tracing::warn!(stage = "poll", "stress_test_interrupted");
+            stop_polling.cancel();
+        } else {
+            println!("Interrupted: exiting without a report");
+            std::process::exit(130);
+        }
+    }
+}
+
+/// Warm up against every target with throwaway counters and no results DB,
+/// then start the measured counters from zero. The warmup runs under a child
+/// of `stop_load`, so Ctrl+C still cuts it short but finishing it never stops
+/// the measured load.
+async fn run_warmup(
+    client: &reqwest::Client,
+    targets: &mut [Target],
+    phases: Vec<PhasePlan>,
+    stats_interval: u64,
+    stop_load: &CancellationToken,
+) {
+    let warmup_stop = stop_load.child_token();
+    let warmups: Vec<_> = targets
+        .iter()
+        .map(|target| {
+            let stats = Arc::new(Stats::new());
+            let output = tokio::spawn(stats_output_task(
+                stats.clone(),
+                format!("{}[warmup] ", target.label),
+                stats_interval,
+                warmup_stop.clone(),
+            ));
+            let config = LoadConfig {
+                completion_db: None,
+                ..target.config.clone()
+            };
+            (stats, config, output)
+        })
+        .collect();
+    futures::future::join_all(
+        warmups
+            .iter()
+            .zip(phases)
+            .map(|((stats, config, _), phase)| {
+                run_phase(client, config, stats, phase, &warmup_stop)
+            }),
+    )
+    .await;
+    warmup_stop.cancel();
+    for (_, _, output) in warmups {
+        let _ = output.await;
+    }
+    for target in targets {
+        target.stats = Arc::new(Stats::new());
+    }
+}
+
+/// `stress-test poll`: pick an existing run back up where polling stopped
+async fn resume_polling(args: PollArgs) -> color_eyre::Result<()> {
+    let completion_db = CompletionDb::open_run(&args.db, args.run_id)
+        .map_err(|e| eyre!("Failed to open run {}: {}", args.run_id, e))?;
+    let unfinished = completion_db
+        .get_unfinished_game_ids()
+        .await
+        .map_err(|e| eyre!("Failed to read run {}: {}", args.run_id, e))?;
+
+    println!("Resuming run {} from {}", args.run_id, args.db);
+    println!("Unfinished games: {}", unfinished.len());
+
+    // No load to stop, so the first Ctrl+C skips straight to the report
+    let stop_load = CancellationToken::new();
+    stop_load.cancel();
+    let stop_polling = CancellationToken::new();
+    let interrupted = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    tokio::spawn(watch_interrupts(
+        stop_load,
+        stop_polling.clone(),
+        interrupted.clone(),
+    ));
+
+    let report = poll_and_report(
+        &create_http_client(),
+        &args.url,
+        &args.token,
+        &completion_db,
+        args.poll_interval,
+        args.poll_timeout,
+        !args.no_admin_stats,
+        &args.html_report,
+        "",
+        stop_polling,
+    )
+    .await;
+    if let Err(e) = completion_db
+        .finish_run(report.as_ref(), interrupted.load(Ordering::Relaxed))
+        .await
+    {
+        tracing::error!(error = %e, "failed to record run summary");
+    }
+    report.ok_or_else(|| eyre!("Failed to generate completion report"))?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    // Setup tracing (JSON for Eyes compatibility)
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive("stress_test=info".parse().unwrap()),
+        )
+        .json()
+        .init();
+
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Compare {
+            run_a,
+            run_b,
+            db,
+            tolerance,
+        }) => return compare_runs(&db, run_a, run_b, tolerance),
+        Some(Command::Poll(args)) => return resume_polling(args).await,
+        None => {}
+    }
+    let token = cli
+        .token
+        .clone()
+        .ok_or_else(|| eyre!("--token is required"))?;
+
+    let client = create_http_client();
+
+    let urls = resolve_targets(&cli.url, cli.targets.as_deref())?;
+    if urls.len() > 1 && cli.metrics_addr.is_some() {
+        return Err(eyre!("--metrics-addr supports a single target"));
+    }
+    let multi_target = urls.len() > 1;
+
+    // Parse and validate snake UUIDs
+    let snakes: Vec<Uuid> = cli
+        .snakes
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(Uuid::parse_str)
+        .collect::<Result<Vec<_>, _>>()
+        .wrap_err("Invalid snake UUID format")?;
+
+    // One mock snake server; each target registers its own snakes against it
+    let mock_snake_url = match cli.mock_snakes {
+        Some(count) => {
+            let addr = start_mock_snake_server(&cli.mock_snake_bind).await?;
+            let snake_base_url = cli
+                .mock_snake_url
+                .clone()
+                .unwrap_or_else(|| format!("http://127.0.0.1:{}", addr.port()));
+            println!(
+                "Mock snakes: {} at {} (listening on {})",
+                count, snake_base_url, addr
+            );
+            Some((count, snake_base_url))
+        }
+        None => None,
+    };
+
+    let default_mix = match cli.mix {
+        Some(ref mix) => parse_mix(mix).map_err(|e| eyre!("Invalid mix: {}", e))?,
+        None => vec![GameVariant {
+            board: cli.board.clone(),
+            game_type: cli.game_type.clone(),
+            weight: 1,
+        }],
+    };
+
+    let assertions = match cli.assertions {
+        Some(ref assertions) => {
+            parse_assertions(assertions).map_err(|e| eyre!("Invalid assertion: {}", e))?
+        }
+        None => Vec::new(),
+    };
+
+    let scenario = cli.scenario.as_deref().map(Scenario::load).transpose()?;
+    let run_desc = match (&scenario, &cli.scenario) {
+        (Some(scenario), Some(path)) => {
+            format!("scenario:{}", scenario.name.as_deref().unwrap_or(path))
+        }
+        _ => cli.patterns.build().map_err(|e| eyre!(e))?.1,
+    };
+    let snakes_per_game = cli
+        .snakes_per_game
+        .as_deref()
+        .map(SnakesPerGame::parse)
+        .transpose()
+        .map_err(|e| eyre!(e))?;
+    let warmup = cli
+        .warmup
+        .as_deref()
+        .map(parse_duration)
+        .transpose()
+        .map_err(|e| eyre!("Invalid warmup: {}", e))?;
+    let default_thresholds = Thresholds::default();
+    let thresholds = scenario
+        .as_ref()
+        .map_or(&default_thresholds, |s| &s.thresholds);
+
+    // Build the phases: a scenario's, or a single one from the CLI flags
+    let plan_phases = |snakes: &[Uuid]| -> color_eyre::Result<Vec<PhasePlan>> {
+        if let Some(ref scenario) = scenario {
+            return scenario
+                .plan(snakes, &default_mix)
+                .map_err(|e| eyre!("Invalid scenario: {}", e));
+        }
+        if snakes.is_empty() {
+            return Err(eyre!("At least one snake UUID is required"));
+        }
+        let duration =
+            parse_duration(&cli.duration).map_err(|e| eyre!("Invalid duration: {}", e))?;
+        let (patterns, pattern_desc) = cli.patterns.build().map_err(|e| eyre!(e))?;
+        Ok(vec![PhasePlan {
+            name: "load".to_string(),
+            duration,
+            patterns,
+            pattern_desc,
+            snakes: snakes.to_vec(),
+            variants: default_mix.clone(),
+        }])
+    };
+
+    // Set up each target: its snakes, phases, counters, and results-DB run
+    let mut targets = Vec::new();
+    let mut target_phases = Vec::new();
+    let mut warmup_phases = Vec::new();
+    let mut results_db: Option<CompletionDb> = None;
+    for url in &urls {
+        let snakes = match mock_snake_url {
+            Some((count, ref snake_base_url)) => {
+                register_mock_snakes(&client, url, &token, snake_base_url, count).await?
+            }
+            None => snakes.clone(),
+        };
+
+        let phases = plan_phases(&snakes)?;
+        if let Some(per_game) = snakes_per_game
+            && let Some(phase) = phases.iter().find(|p| p.snakes.len() < per_game.min)
+        {
+            return Err(eyre!(
+                "Phase '{}' has {} snake(s), fewer than --snakes-per-game's minimum of {}",
+                phase.name,
+                phase.snakes.len(),
+                per_game.min
+            ));
+        }
+        // The warmup gets fresh patterns shaped like the first phase's
+        if let Some(duration) = warmup {
+            let first = plan_phases(&snakes)?.remove(0);
+            warmup_phases.push(PhasePlan {
+                name: "warmup".to_string(),
+                duration,
+                ..first
+            });
+        }
+        let total_duration: Duration = phases.iter().map(|p| p.duration).sum();
+
+        // Create completion database; further targets share its connection
+        let completion_db = match results_db {
+            Some(ref db) => db.new_run(url, &run_desc, total_duration.as_secs()),
+            None => CompletionDb::new(&cli.db, url, &run_desc, total_duration.as_secs()),
+        }
+        .map_err(|e| eyre!("Failed to create completion database: {}", e))?;
+        results_db.get_or_insert_with(|| completion_db.clone());
+
+        let config = LoadConfig {
+            base_url: url.clone(),
+            token: token.clone(),
+            snakes,
+            variants: Vec::new(),
+            completion_db: Some(completion_db.clone()),
+            in_flight_limit: cli
+                .max_in_flight
+                .map(|max| InFlightLimit::new(max, cli.when_full)),
+            max_retries: cli.max_retries,
+            snakes_per_game,
+        };
+        targets.push(Target {
+            url: url.clone(),
+            label: if multi_target {
+                format!("[{}] ", url)
+            } else {
+                String::new()
+            },
+            stats: Arc::new(Stats::new()),
+            completion_db,
+            config,
+        });
+        target_phases.push(phases);
+    }
+    let total_duration: Duration = target_phases[0].iter().map(|p| p.duration).sum();
+
+    let cancel = CancellationToken::new();
+
+    println!("Starting stress test against {}", urls.join(", "));
+    println!("Run: {}", run_desc);
+    println!("Duration: {}", format_duration(total_duration));
+    println!("Phases: {}", target_phases[0].len());
+    println!("Results DB: {}", cli.db);
+    for target in &targets {
+        println!("Run ID: {}{}", target.label, target.completion_db.run_id);
+    }
+    if let Some(warmup) = warmup {
+        println!("Warmup: {} (not measured)", format_duration(warmup));
+    }
+
+    // Ctrl+C moves the run along rather than killing it
+    let stop_load = CancellationToken::new();
+    let stop_polling = CancellationToken::new();
+    let interrupted = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    if cli.no_poll {
+        stop_polling.cancel();
+    }
+    tokio::spawn(watch_interrupts(
+        stop_load.clone(),
+        stop_polling.clone(),
+        interrupted.clone(),
+    ));
+
+    if !warmup_phases.is_empty() {
+        println!();
+        run_warmup(
+            &client,
+            &mut targets,
+            warmup_phases,
+            cli.stats_interval,
+            &stop_load,
+        )
+        .await;
+    }
+
+    if let Some(ref bind) = cli.metrics_addr {
+        let target = &targets[0];
+        let addr = start_metrics_server(
+            bind,
+            target.stats.clone(),
+            target.completion_db.run_id,
+            run_desc.clone(),
+        )
+        .await?;
+        println!("Metrics: http://{}/metrics", addr);
+    }
+    println!();
+
+    let mut background = Vec::new();
+    let mut admin_stats_handles = Vec::new();
+    for target in &targets {
+        // Spawn load-phase admin stats collector
+        if !cli.no_admin_stats {
+            admin_stats_handles.push(tokio::spawn(admin_stats_load_phase(
+                client.clone(),
+                target.url.clone(),
+                token.clone(),
+                target.completion_db.clone(),
+                Duration::from_secs(cli.poll_interval),
+            )));
+        }
+
+        // Spawn stats output task
+        background.push(tokio::spawn(stats_output_task(
+            target.stats.clone(),
+            target.label.clone(),
+            cli.stats_interval,
+            cancel.clone(),
+        )));
+
+        // Spawn spectators; they follow the games the phases create
+        for _ in 0..cli.spectators {
+            background.push(tokio::spawn(spectator_task(
+                target.url.clone(),
+                target.stats.clone(),
+                cancel.clone(),
+            )));
+        }
+    }
+
+    // Run the phases back to back, every target's copy of a phase at once
+    let mut target_phases: Vec<_> = target_phases.into_iter().map(Vec::into_iter).collect();
+    loop {
+        if stop_load.is_cancelled() {
+            break;
+        }
+        let phases: Vec<_> = target_phases
+            .iter_mut()
+            .filter_map(Iterator::next)
+            .collect();
+        if phases.is_empty() {
+            break;
+        }
+        futures::future::join_all(targets.iter().zip(phases).map(|(target, phase)| {
+            run_phase(&client, &target.config, &target.stats, phase, &stop_load)
+        }))
+        .await;
+    }
+    stop_load.cancel();
+    cancel.cancel();
+    for handle in background {
+        let _ = handle.await;
+    }
+
+    // Abort load-phase admin stats collectors
+    for handle in admin_stats_handles {
+        handle.abort();
+        let _ = handle.await;
+    }
+
+    // Final creation stats output
+    let final_snapshots: Vec<StatsSnapshot> = targets.iter().map(|t| t.stats.snapshot()).collect();
+    for (target, final_snapshot) in targets.iter().zip(&final_snapshots) {
+        println!();
+        if multi_target {
+            println!("=== Creation Results ({}) ===", target.url);
+        } else {
+            println!("=== Creation Results ===");
+        }
+        println!("Total games: {}", final_snapshot.total_games);
+        println!("Successful: {}", final_snapshot.successful);
+        println!("Failed: {}", final_snapshot.failed);
+        for (category, count) in final_snapshot.failures_by_category {
+            if count > 0 {
+                println!("  {}: {}", category.as_str(), count);
+            }
+        }
+        if cli.max_retries > 0 {
+            println!(
+                "Retries: {} after 429, {} after 503",
+                final_snapshot.retries_rate_limited, final_snapshot.retries_unavailable
+            );
+        }
+        if let Some(max) = cli.max_in_flight {
+            println!("Max in flight: {}", max);
+            println!("Queued for a slot: {}", final_snapshot.queued);
+            println!("Dropped (cap full): {}", final_snapshot.dropped);
+        }
+        println!("Success rate: {:.1}%", final_snapshot.success_rate);
+        println!("Average rate: {:.1} games/sec", final_snapshot.rate);
+        println!("Avg latency: {:.0}ms", final_snapshot.avg_latency_ms);
+        println!("p50 latency: {:.0}ms", final_snapshot.p50_latency_ms);
+        println!("p95 latency: {:.0}ms", final_snapshot.p95_latency_ms);
+        println!("p99 latency: {:.0}ms", final_snapshot.p99_latency_ms);
+        if cli.spectators > 0 {
+            println!();
+            println!("=== Spectator Results ===");
+            println!("Spectators: {}", cli.spectators);
+            println!("Connections: {}", final_snapshot.spectator_connections);
+            println!("Failed: {}", final_snapshot.spectator_failures);
+            println!("Frames received: {}", final_snapshot.spectator_frames);
+            println!(
+                "First frame p95: {:.0}ms",
+                final_snapshot.spectator_first_frame_p95_ms
+            );
+        }
+    }
+
+    // Wait before polling if configured
+    if cli.poll_after > 0 && !stop_polling.is_cancelled() {
+        println!();
+        println!("Waiting {}s before polling...", cli.poll_after);
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(cli.poll_after)) => {}
+            _ = stop_polling.cancelled() => {}
+        }
+    }
+
+    // Targets poll side by side so none of them waits on another's games
+    let report_paths: Vec<String> = (0..targets.len())
+        .map(|i| target_report_path(&cli.html_report, i, targets.len()))
+        .collect();
+    let reports: Vec<Option<Report>> =
+        futures::future::join_all(targets.iter().zip(&report_paths).map(|(target, path)| {
+            poll_and_report(
+                &client,
+                &target.url,
+                &token,
+                &target.completion_db,
+                cli.poll_interval,
+                cli.poll_timeout,
+                !cli.no_admin_stats,
+                path,
+                &target.label,
+                stop_polling.clone(),
+            )
+        }))
+        .await;
+
+    // Always leave a summary on each run, even for an interrupted one
+    for (target, report) in targets.iter().zip(&reports) {
+        if let Err(e) = target
+            .completion_db
+            .finish_run(report.as_ref(), interrupted.load(Ordering::Relaxed))
+            .await
+        {
+            tracing::error!(error = %e, target = %target.url, "failed to record run summary");
+            println!("ERROR: Failed to record run summary: {}", e);
+        }
+    }
+
+    if multi_target {
+        print_target_summary(&targets, &final_snapshots, &reports);
+    }
+
+    // Scenario thresholds and --assert checks decide the exit status
+    let mut failed = 0;
+    for ((target, final_snapshot), report) in targets.iter().zip(&final_snapshots).zip(&reports) {
+        let mut results = thresholds.check(final_snapshot, report.as_ref());
+        results.extend(
+            assertions
+                .iter()
+                .map(|a| a.check(final_snapshot, report.as_ref())),
+        );
+        if results.is_empty() {
+            continue;
+        }
+        println!();
+        if multi_target {
+            println!("=== Thresholds ({}) ===", target.url);
+        } else {
+            println!("=== Thresholds ===");
+        }
+        for result in &results {
+            let verdict = if result.passed { "PASS" } else { "FAIL" };
+            println!("  [{}] {}: {}", verdict, result.name, result.detail);
+        }
+        failed += results.iter().filter(|r| !r.passed).count();
+    }
+    if failed > 0 {
+        return Err(eyre!("{} threshold(s) failed", failed));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicU64;
+
+    use crate::load::tests::default_variant;
+
+    #[test]
+    fn test_compare_subcommand_needs_no_token() {
+        let cli = Cli::try_parse_from([
+            "stress-test",
+            "compare",
+            &Uuid::nil().to_string(),
+            &Uuid::nil().to_string(),
+        ])
+        .unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Compare { tolerance, .. }) if tolerance == 10.0)
+        );
+    }
+
+    #[test]
+    fn test_poll_subcommand_parses() {
+        let run_id = Uuid::new_v4();
+        let cli = Cli::try_parse_from([
+            "stress-test",
+            "poll",
+            "--run-id",
+            &run_id.to_string(),
+            "--token",
+            "t",
+        ])
+        .unwrap();
+        assert!(matches!(cli.command, Some(Command::Poll(args)) if args.run_id == run_id));
+    }
+
+    #[tokio::test]
+    async fn test_warmup_parses_and_resets_stats() {
+        let cli = Cli::try_parse_from(["stress-test", "--token", "t", "--warmup", "30s"]).unwrap();
+        assert_eq!(
+            cli.warmup.as_deref().map(parse_duration),
+            Some(Ok(Duration::from_secs(30)))
+        );
+
+        let created = Arc::new(AtomicU64::new(0));
+        let app = axum::Router::new().route(
+            "/api/v1/games",
+            axum::routing::post({
+                let created = created.clone();
+                move || async move {
+                    created.fetch_add(1, Ordering::Relaxed);
+                    axum::Json(serde_json::json!({ "id": Uuid::new_v4() }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let completion_db = CompletionDb::new(":memory:", &url, "test", 1).unwrap();
+        let config = LoadConfig {
+            base_url: url.clone(),
+            token: "t".to_string(),
+            snakes: vec![Uuid::nil()],
+            variants: Vec::new(),
+            completion_db: Some(completion_db.clone()),
+            in_flight_limit: None,
+            max_retries: 0,
+            snakes_per_game: None,
+        };
+        let measured = Arc::new(Stats::new());
+        let mut targets = vec![Target {
+            url,
+            label: String::new(),
+            stats: measured.clone(),
+            completion_db,
+            config,
+        }];
+        let phase = PhasePlan {
+            name: "warmup".to_string(),
+            duration: Duration::from_millis(300),
+            patterns: vec![Box::new(SteadyStreamPattern::from_str("50/s").unwrap())],
+            pattern_desc: "steady 50/s".to_string(),
+            snakes: vec![Uuid::nil()],
+            variants: vec![default_variant()],
+        };
+
+        let stop_load = CancellationToken::new();
+        run_warmup(
+            &create_http_client(),
+            &mut targets,
+            vec![phase],
+            60,
+            &stop_load,
+        )
+        .await;
+
+        assert!(created.load(Ordering::Relaxed) > 0);
+        assert!(!stop_load.is_cancelled());
+        assert!(!Arc::ptr_eq(&targets[0].stats, &measured));
+        assert_eq!(targets[0].stats.snapshot().total_games, 0);
+        assert_eq!(measured.snapshot().total_games, 0);
+    }
+}
@@ -0,0 +1,216 @@
+//! Local mock snakes, so created games actually run to completion.
+
+use color_eyre::eyre::Context as _;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Pick a move that stays on the board and off every snake's body, so games
+/// between mock snakes run for a while and then finish on their own.
+/// `roll` chooses among the safe moves.
+fn mock_move(request: &serde_json::Value, roll: usize) -> &'static str {
+    let point =
+        |p: &serde_json::Value| (p["x"].as_i64().unwrap_or(-1), p["y"].as_i64().unwrap_or(-1));
+    let board = &request["board"];
+    let width = board["width"].as_i64().unwrap_or(11);
+    let height = board["height"].as_i64().unwrap_or(11);
+    let head = point(&request["you"]["head"]);
+    let occupied: std::collections::HashSet<(i64, i64)> = board["snakes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|snake| snake["body"].as_array().into_iter().flatten())
+        .map(point)
+        .collect();
+
+    let safe: Vec<&'static str> = [
+        ("up", 0, 1),
+        ("down", 0, -1),
+        ("left", -1, 0),
+        ("right", 1, 0),
+    ]
+    .into_iter()
+    .filter(|(_, dx, dy)| {
+        let next = (head.0 + dx, head.1 + dy);
+        (0..width).contains(&next.0) && (0..height).contains(&next.1) && !occupied.contains(&next)
+    })
+    .map(|(direction, ..)| direction)
+    .collect();
+
+    if safe.is_empty() {
+        "up"
+    } else {
+        safe[roll % safe.len()]
+    }
+}
+
+async fn mock_snake_info() -> axum::Json<serde_json::Value> {
+    axum::Json(serde_json::json!({
+        "apiversion": "1",
+        "author": "stress-test",
+        "color": "#888888",
+        "head": "default",
+        "tail": "default",
+    }))
+}
+
+async fn mock_snake_ack() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn mock_snake_move(
+    axum::Json(request): axum::Json<serde_json::Value>,
+) -> axum::Json<serde_json::Value> {
+    axum::Json(serde_json::json!({ "move": mock_move(&request, rand::random()) }))
+}
+
+/// Serve mock snakes at `/{n}` on `bind`, returning the bound address. Every
+/// path prefix behaves the same; the index only keeps registered URLs apart.
+pub async fn start_mock_snake_server(bind: &str) -> color_eyre::Result<std::net::SocketAddr> {
+    use axum::routing::{get, post};
+
+    let app = axum::Router::new()
+        .route("/{snake}", get(mock_snake_info))
+        .route("/{snake}/", get(mock_snake_info))
+        .route("/{snake}/start", post(mock_snake_ack))
+        .route("/{snake}/move", post(mock_snake_move))
+        .route("/{snake}/end", post(mock_snake_ack));
+    let listener = tokio::net::TcpListener::bind(bind)
+        .await
+        .wrap_err_with(|| format!("Failed to bind mock snake server to {}", bind))?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::error!(error = %e, "mock snake server stopped");
+        }
+    });
+    Ok(addr)
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisteredSnake {
+    id: Uuid,
+    name: String,
+    url: String,
+}
+
+/// Make sure the token's user has snakes `stress-mock-0..count` pointing at
+/// the mock server. Existing ones are reused (and repointed if the URL
+/// changed) so repeated runs don't pile up snakes.
+pub async fn register_mock_snakes(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    snake_base_url: &str,
+    count: usize,
+) -> color_eyre::Result<Vec<Uuid>> {
+    let existing: Vec<RegisteredSnake> = client
+        .get(format!("{}/api/v1/snakes", base_url))
+        .bearer_auth(token)
+        .send()
+        .await?
+        .error_for_status()
+        .wrap_err("Failed to list snakes")?
+        .json()
+        .await?;
+
+    let mut ids = Vec::with_capacity(count);
+    for i in 0..count {
+        let name = format!("stress-mock-{}", i);
+        let url = format!("{}/{}", snake_base_url.trim_end_matches('/'), i);
+        let request = match existing.iter().find(|s| s.name == name) {
+            Some(snake) if snake.url == url => {
+                ids.push(snake.id);
+                continue;
+            }
+            Some(snake) => client
+                .put(format!("{}/api/v1/snakes/{}", base_url, snake.id))
+                .json(&serde_json::json!({ "url": url })),
+            None => client
+                .post(format!("{}/api/v1/snakes", base_url))
+                .json(&serde_json::json!({ "name": name, "url": url, "is_public": false })),
+        };
+        let snake: RegisteredSnake = request
+            .bearer_auth(token)
+            .send()
+            .await?
+            .error_for_status()
+            .wrap_err_with(|| format!("Failed to register mock snake {}", name))?
+            .json()
+            .await?;
+        ids.push(snake.id);
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::load::create_http_client;
+
+    #[test]
+    fn test_mock_move_avoids_walls_and_bodies() {
+        // Cornered at (0,0): the wall blocks down and left, its own body up
+        let request = serde_json::json!({
+            "board": {
+                "width": 11,
+                "height": 11,
+                "snakes": [{ "body": [{ "x": 0, "y": 0 }, { "x": 0, "y": 1 }, { "x": 0, "y": 2 }] }],
+            },
+            "you": { "head": { "x": 0, "y": 0 } },
+        });
+        for roll in 0..4 {
+            assert_eq!(mock_move(&request, roll), "right");
+        }
+
+        // Boxed in entirely: still answer with something
+        let request = serde_json::json!({
+            "board": { "width": 1, "height": 1, "snakes": [] },
+            "you": { "head": { "x": 0, "y": 0 } },
+        });
+        assert_eq!(mock_move(&request, 3), "up");
+    }
+
+    #[tokio::test]
+    async fn test_mock_snake_server_answers_like_a_snake() {
+        let addr = start_mock_snake_server("127.0.0.1:0").await.unwrap();
+        let client = create_http_client();
+        let base = format!("http://{}/0", addr);
+
+        let info: serde_json::Value = client
+            .get(&base)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(info["apiversion"], "1");
+
+        let request = serde_json::json!({
+            "board": { "width": 3, "height": 1, "snakes": [{ "body": [{ "x": 0, "y": 0 }] }] },
+            "you": { "head": { "x": 0, "y": 0 } },
+        });
+        let response: serde_json::Value = client
+            .post(format!("{}/move", base))
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["move"], "right");
+
+        let status = client
+            .post(format!("{}/end", base))
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert!(status.is_success());
+    }
+}
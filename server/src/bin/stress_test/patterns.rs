@@ -0,0 +1,509 @@
+//! Load patterns: how fast, and in what shape, games are created.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+use crate::load::{LoadConfig, acquire_slot, create_limited};
+use crate::stats::Stats;
+
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    if let Some(stripped) = s.strip_suffix('s') {
+        let secs: u64 = stripped
+            .parse()
+            .map_err(|_| "Invalid seconds".to_string())?;
+        Ok(Duration::from_secs(secs))
+    } else if let Some(stripped) = s.strip_suffix('m') {
+        let mins: u64 = stripped
+            .parse()
+            .map_err(|_| "Invalid minutes".to_string())?;
+        Ok(Duration::from_secs(mins * 60))
+    } else if let Some(stripped) = s.strip_suffix('h') {
+        let hours: u64 = stripped.parse().map_err(|_| "Invalid hours".to_string())?;
+        Ok(Duration::from_secs(hours * 3600))
+    } else {
+        Err("Duration must end with 's', 'm', or 'h'".to_string())
+    }
+}
+
+/// Parse a creation rate like "10/s"
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    let Some(number) = s.strip_suffix("/s") else {
+        return Err("Rate must end with '/s' (e.g., '10/s')".to_string());
+    };
+    let rate: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| "Invalid rate number".to_string())?;
+    if rate <= 0.0 {
+        return Err("Rate must be positive".to_string());
+    }
+    Ok(rate)
+}
+
+#[async_trait]
+pub trait LoadPattern: Send + Sync {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    );
+}
+
+// Steady stream pattern
+pub struct SteadyStreamPattern {
+    rate_per_second: f64,
+}
+
+impl SteadyStreamPattern {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        Ok(Self {
+            rate_per_second: parse_rate(s)?,
+        })
+    }
+}
+
+#[async_trait]
+impl LoadPattern for SteadyStreamPattern {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    ) {
+        let interval_duration = Duration::from_secs_f64(1.0 / self.rate_per_second);
+        let mut interval = tokio::time::interval(interval_duration);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Burst);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => {
+                    let Some(slot) = acquire_slot(config, stats, &cancel).await else {
+                        continue;
+                    };
+                    tokio::spawn(create_limited(client.clone(), config.clone(), stats.clone(), slot));
+                }
+            }
+        }
+    }
+}
+
+// Batch pattern
+pub struct BatchPattern {
+    batch_size: u32,
+    interval: Duration,
+}
+
+impl BatchPattern {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = s.split(',').collect();
+        if parts.len() != 2 {
+            return Err("Batch format: 'count,interval' (e.g., '100,30s')".to_string());
+        }
+        let batch_size: u32 = parts[0]
+            .trim()
+            .parse()
+            .map_err(|_| "Invalid batch size".to_string())?;
+        let interval = parse_duration(parts[1].trim())?;
+        if batch_size == 0 {
+            return Err("Batch size must be positive".to_string());
+        }
+        Ok(Self {
+            batch_size,
+            interval,
+        })
+    }
+}
+
+#[async_trait]
+impl LoadPattern for BatchPattern {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    ) {
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => {
+                    // Spawn batch_size concurrent requests
+                    let mut handles = Vec::new();
+                    for _ in 0..self.batch_size {
+                        if let Some(slot) = acquire_slot(config, stats, &cancel).await {
+                            handles.push(tokio::spawn(create_limited(
+                                client.clone(),
+                                config.clone(),
+                                stats.clone(),
+                                slot,
+                            )));
+                        }
+                        if cancel.is_cancelled() {
+                            break;
+                        }
+                    }
+
+                    futures::future::join_all(handles).await;
+                }
+            }
+        }
+    }
+}
+
+// Ramp pattern
+pub struct RampPattern {
+    start_rate: f64,
+    end_rate: f64,
+    over: Duration,
+    /// `None` ramps linearly; `Some(n)` holds each of n evenly spaced rates
+    /// for an equal share of the ramp
+    steps: Option<u32>,
+}
+
+impl RampPattern {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        const FORMAT: &str =
+            "Ramp format: 'start..end over duration [in N steps]' (e.g., '1/s..50/s over 10m')";
+
+        let (rates, rest) = s.split_once(" over ").ok_or(FORMAT)?;
+        let (start, end) = rates.split_once("..").ok_or(FORMAT)?;
+        let (over, steps) = match rest.split_once(" in ") {
+            Some((over, steps)) => {
+                let count = steps
+                    .trim()
+                    .strip_suffix("steps")
+                    .ok_or(FORMAT)?
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| "Invalid step count".to_string())?;
+                if count < 2 {
+                    return Err("A stepped ramp needs at least 2 steps".to_string());
+                }
+                (over, Some(count))
+            }
+            None => (rest, None),
+        };
+
+        let over = parse_duration(over)?;
+        if over.is_zero() {
+            return Err("Ramp duration must be positive".to_string());
+        }
+        Ok(Self {
+            start_rate: parse_rate(start)?,
+            end_rate: parse_rate(end)?,
+            over,
+            steps,
+        })
+    }
+
+    /// Target creation rate `elapsed` into the run
+    fn rate_at(&self, elapsed: Duration) -> f64 {
+        let progress = (elapsed.as_secs_f64() / self.over.as_secs_f64()).min(1.0);
+        let fraction = match self.steps {
+            None => progress,
+            Some(steps) => {
+                let step = ((progress * steps as f64) as u32).min(steps - 1);
+                step as f64 / (steps - 1) as f64
+            }
+        };
+        self.start_rate + (self.end_rate - self.start_rate) * fraction
+    }
+}
+
+#[async_trait]
+impl LoadPattern for RampPattern {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    ) {
+        run_variable_rate(
+            "ramp",
+            |elapsed| self.rate_at(elapsed),
+            |rate| Duration::from_secs_f64(1.0 / rate),
+            client,
+            config,
+            stats,
+            cancel,
+        )
+        .await;
+    }
+}
+
+/// Create games at a rate that changes over the run. `gap` turns the current
+/// target rate into the wait before the next game.
+async fn run_variable_rate(
+    pattern: &'static str,
+    rate_at: impl Fn(Duration) -> f64,
+    gap: impl Fn(f64) -> Duration,
+    client: &reqwest::Client,
+    config: &LoadConfig,
+    stats: &Arc<Stats>,
+    cancel: CancellationToken,
+) {
+    let start = Instant::now();
+    let mut next = tokio::time::Instant::now();
+    let mut logged_rate = None;
+
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = tokio::time::sleep_until(next) => {
+                let rate = rate_at(start.elapsed());
+                // Log the target each time it moves by a whole game/s so the
+                // stats lines can be lined up against it
+                if logged_rate != Some(rate.floor()) {
+                    logged_rate = Some(rate.floor());
+                    tracing::info!(pattern, target_rate = rate, "target_rate");
+                }
+
+                next += gap(rate);
+                let Some(slot) = acquire_slot(config, stats, &cancel).await else {
+                    continue;
+                };
+                tokio::spawn(create_limited(client.clone(), config.clone(), stats.clone(), slot));
+            }
+        }
+    }
+}
+
+// Poisson pattern
+pub struct PoissonPattern {
+    mean_rate: f64,
+}
+
+impl PoissonPattern {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        Ok(Self {
+            mean_rate: parse_rate(s)?,
+        })
+    }
+}
+
+/// Exponentially distributed gap for a Poisson process at `rate`, from a
+/// uniform sample in (0, 1]
+fn exponential_gap(rate: f64, uniform: f64) -> Duration {
+    Duration::from_secs_f64(-uniform.ln() / rate)
+}
+
+#[async_trait]
+impl LoadPattern for PoissonPattern {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    ) {
+        run_variable_rate(
+            "poisson",
+            |_| self.mean_rate,
+            // random::<f64>() is in [0, 1); flip it so ln() never sees 0
+            |rate| exponential_gap(rate, 1.0 - rand::random::<f64>()),
+            client,
+            config,
+            stats,
+            cancel,
+        )
+        .await;
+    }
+}
+
+// Sine-wave pattern
+pub struct SinePattern {
+    low_rate: f64,
+    high_rate: f64,
+    period: Duration,
+}
+
+impl SinePattern {
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        const FORMAT: &str = "Sine format: 'low..high every period' (e.g., '5/s..50/s every 10m')";
+
+        let (rates, period) = s.split_once(" every ").ok_or(FORMAT)?;
+        let (low, high) = rates.split_once("..").ok_or(FORMAT)?;
+        let period = parse_duration(period)?;
+        if period.is_zero() {
+            return Err("Sine period must be positive".to_string());
+        }
+        Ok(Self {
+            low_rate: parse_rate(low)?,
+            high_rate: parse_rate(high)?,
+            period,
+        })
+    }
+
+    /// Target rate `elapsed` into the run: the low rate at the start of each
+    /// period, the high rate halfway through
+    fn rate_at(&self, elapsed: Duration) -> f64 {
+        let phase = elapsed.as_secs_f64() / self.period.as_secs_f64() * std::f64::consts::TAU;
+        self.low_rate + (self.high_rate - self.low_rate) * (1.0 - phase.cos()) / 2.0
+    }
+}
+
+#[async_trait]
+impl LoadPattern for SinePattern {
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        config: &LoadConfig,
+        stats: &Arc<Stats>,
+        cancel: CancellationToken,
+    ) {
+        run_variable_rate(
+            "sine",
+            |elapsed| self.rate_at(elapsed),
+            |rate| Duration::from_secs_f64(1.0 / rate),
+            client,
+            config,
+            stats,
+            cancel,
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_seconds() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("1s").unwrap(), Duration::from_secs(1));
+        assert_eq!(parse_duration("0s").unwrap(), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1m").unwrap(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_parse_duration_hours() {
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("abc").is_err());
+        assert!(parse_duration("30x").is_err());
+    }
+
+    #[test]
+    fn test_steady_stream_pattern_parsing() {
+        let pattern = SteadyStreamPattern::from_str("10/s").unwrap();
+        assert!((pattern.rate_per_second - 10.0).abs() < f64::EPSILON);
+
+        let pattern = SteadyStreamPattern::from_str("0.5/s").unwrap();
+        assert!((pattern.rate_per_second - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_steady_stream_pattern_invalid() {
+        assert!(SteadyStreamPattern::from_str("10").is_err());
+        assert!(SteadyStreamPattern::from_str("abc/s").is_err());
+        assert!(SteadyStreamPattern::from_str("0/s").is_err());
+        assert!(SteadyStreamPattern::from_str("-1/s").is_err());
+    }
+
+    #[test]
+    fn test_batch_pattern_parsing() {
+        let pattern = BatchPattern::from_str("100,30s").unwrap();
+        assert_eq!(pattern.batch_size, 100);
+        assert_eq!(pattern.interval, Duration::from_secs(30));
+
+        let pattern = BatchPattern::from_str("50, 1m").unwrap();
+        assert_eq!(pattern.batch_size, 50);
+        assert_eq!(pattern.interval, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_batch_pattern_invalid() {
+        assert!(BatchPattern::from_str("100").is_err());
+        assert!(BatchPattern::from_str("100,30s,extra").is_err());
+        assert!(BatchPattern::from_str("abc,30s").is_err());
+        assert!(BatchPattern::from_str("0,30s").is_err());
+    }
+
+    #[test]
+    fn test_ramp_pattern_parsing() {
+        let pattern = RampPattern::from_str("1/s..50/s over 10m").unwrap();
+        assert!((pattern.start_rate - 1.0).abs() < f64::EPSILON);
+        assert!((pattern.end_rate - 50.0).abs() < f64::EPSILON);
+        assert_eq!(pattern.over, Duration::from_secs(600));
+        assert_eq!(pattern.steps, None);
+
+        let pattern = RampPattern::from_str("1/s..50/s over 10m in 5 steps").unwrap();
+        assert_eq!(pattern.steps, Some(5));
+    }
+
+    #[test]
+    fn test_ramp_pattern_invalid() {
+        assert!(RampPattern::from_str("1/s..50/s").is_err());
+        assert!(RampPattern::from_str("1/s over 10m").is_err());
+        assert!(RampPattern::from_str("0/s..50/s over 10m").is_err());
+        assert!(RampPattern::from_str("1/s..50/s over 0s").is_err());
+        assert!(RampPattern::from_str("1/s..50/s over 10m in 1 steps").is_err());
+    }
+
+    #[test]
+    fn test_ramp_rate_linear_and_stepped() {
+        let linear = RampPattern::from_str("10/s..20/s over 100s").unwrap();
+        assert!((linear.rate_at(Duration::ZERO) - 10.0).abs() < 1e-9);
+        assert!((linear.rate_at(Duration::from_secs(50)) - 15.0).abs() < 1e-9);
+        assert!((linear.rate_at(Duration::from_secs(500)) - 20.0).abs() < 1e-9);
+
+        let stepped = RampPattern::from_str("10/s..40/s over 100s in 4 steps").unwrap();
+        assert!((stepped.rate_at(Duration::from_secs(10)) - 10.0).abs() < 1e-9);
+        assert!((stepped.rate_at(Duration::from_secs(30)) - 20.0).abs() < 1e-9);
+        assert!((stepped.rate_at(Duration::from_secs(99)) - 40.0).abs() < 1e-9);
+        assert!((stepped.rate_at(Duration::from_secs(500)) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_poisson_pattern_parsing() {
+        let pattern = PoissonPattern::from_str("10/s").unwrap();
+        assert!((pattern.mean_rate - 10.0).abs() < f64::EPSILON);
+        assert!(PoissonPattern::from_str("0/s").is_err());
+        assert!(PoissonPattern::from_str("10").is_err());
+    }
+
+    #[test]
+    fn test_exponential_gap() {
+        assert_eq!(exponential_gap(10.0, 1.0), Duration::ZERO);
+        // ln(e^-1) = -1, so the gap is 1/rate
+        let gap = exponential_gap(4.0, (-1.0f64).exp());
+        assert!((gap.as_secs_f64() - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sine_pattern() {
+        let pattern = SinePattern::from_str("5/s..25/s every 100s").unwrap();
+        assert_eq!(pattern.period, Duration::from_secs(100));
+        assert!((pattern.rate_at(Duration::ZERO) - 5.0).abs() < 1e-9);
+        assert!((pattern.rate_at(Duration::from_secs(25)) - 15.0).abs() < 1e-9);
+        assert!((pattern.rate_at(Duration::from_secs(50)) - 25.0).abs() < 1e-9);
+        assert!((pattern.rate_at(Duration::from_secs(100)) - 5.0).abs() < 1e-9);
+
+        assert!(SinePattern::from_str("5/s..25/s").is_err());
+        assert!(SinePattern::from_str("5/s..25/s every 0s").is_err());
+    }
+}
@@ -0,0 +1,371 @@
+//! Completion polling: tracking created games until they finish and fetching results.
+
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::db::{AdminStatsResponse, CompletionDb};
+use crate::report::{Report, render_html_report};
+
+#[derive(Debug, Deserialize)]
+pub struct PollGameStatus {
+    pub id: Uuid,
+    pub status: String,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub enqueued_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The parts of `GET /api/v1/games/{id}/result` kept in the results DB
+#[derive(Debug, Deserialize)]
+pub struct PollGameResult {
+    pub id: Uuid,
+    pub turns: i32,
+    pub winner: Option<Uuid>,
+    snakes: Vec<PollSnakeResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PollSnakeResult {
+    death: Option<PollSnakeDeath>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PollSnakeDeath {
+    cause: String,
+    turn: i32,
+}
+
+impl PollGameResult {
+    /// The cause of the last elimination, which is what ended the game
+    pub fn end_reason(&self) -> Option<&str> {
+        self.snakes
+            .iter()
+            .filter_map(|s| s.death.as_ref())
+            .max_by_key(|d| d.turn)
+            .map(|d| d.cause.as_str())
+    }
+}
+
+// ============================================================================
+
+// Completion Poller
+
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+async fn completion_poller(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    db: &CompletionDb,
+    poll_interval: Duration,
+    poll_timeout: Duration,
+    collect_admin_stats: bool,
+    label: &str,
+    cancel: CancellationToken,
+) {
+    let start = Instant::now();
+    let mut admin_stats_available = collect_admin_stats;
+    let mut admin_stats_warned = false;
+    let mut interval = tokio::time::interval(poll_interval);
+
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = interval.tick() => {
+                // Check timeout
+                if start.elapsed() > poll_timeout {
+                    tracing::info!("Poll timeout reached after {:?}", poll_timeout);
+                    break;
+                }
+
+                // Results for games that finished on an earlier tick
+                fetch_game_results(client, base_url, token, db).await;
+
+                // Get unfinished game IDs
+                let unfinished = match db.get_unfinished_game_ids().await {
+                    Ok(ids) => ids,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to get unfinished game IDs");
+                        continue;
+                    }
+                };
+
+                if unfinished.is_empty() {
+                    tracing::info!("All games finished");
+                    break;
+                }
+
+                // Batch into chunks of 500
+                let total_unfinished = unfinished.len();
+                for chunk in unfinished.chunks(500) {
+                    let body = serde_json::json!({ "game_ids": chunk });
+                    match client
+                        .post(format!("{}/api/v1/games/status", base_url))
+                        .bearer_auth(token)
+                        .json(&body)
+                        .send()
+                        .await
+                    {
+                        Ok(resp) if resp.status().is_success() => {
+                            match resp.json::<Vec<PollGameStatus>>().await {
+                                Ok(statuses) => {
+                                    if let Err(e) = db.update_game_statuses(statuses).await {
+                                        tracing::warn!(error = %e, "failed to update game statuses");
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::warn!(error = %e, "failed to parse poll response");
+                                }
+                            }
+                        }
+                        Ok(resp) => {
+                            tracing::warn!(status = %resp.status(), "poll request returned error status");
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "poll request failed");
+                        }
+                    }
+                }
+
+                println!(
+                    "{}[polling] {}/{} games still unfinished ({:.0}s elapsed)",
+                    label,
+                    total_unfinished,
+                    total_unfinished, // will show what we started with this tick
+                    start.elapsed().as_secs_f64()
+                );
+
+                // Collect admin stats if available
+                if admin_stats_available {
+                    match fetch_admin_stats(client, base_url, token).await {
+                        Ok((stats, raw_json)) => {
+                            if let Err(e) = db.record_admin_stats("polling", &stats, &raw_json).await {
+                                tracing::warn!(error = %e, "failed to record admin stats");
+                            }
+                        }
+                        Err(AdminStatsError::Forbidden) => {
+                            if !admin_stats_warned {
+                                tracing::warn!("Admin stats unavailable (403) - disabling collection");
+                                admin_stats_warned = true;
+                            }
+                            admin_stats_available = false;
+                        }
+                        Err(AdminStatsError::Other(e)) => {
+                            tracing::warn!(error = %e, "failed to fetch admin stats");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Most `/result` requests in flight at once while polling
+const RESULT_FETCH_CONCURRENCY: usize = 16;
+
+/// Fetch and store turns, winner, and end reason for finished games that
+/// don't have them yet. Failures are left for the next tick.
+async fn fetch_game_results(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    db: &CompletionDb,
+) {
+    use futures::StreamExt as _;
+
+    let missing = match db.get_games_missing_results().await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to get games missing results");
+            return;
+        }
+    };
+    if missing.is_empty() {
+        return;
+    }
+
+    let results: Vec<PollGameResult> = futures::stream::iter(missing)
+        .map(|game_id| async move {
+            let resp = client
+                .get(format!("{}/api/v1/games/{}/result", base_url, game_id))
+                .bearer_auth(token)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            match resp {
+                Ok(resp) => match resp.json::<PollGameResult>().await {
+                    Ok(result) => Some(result),
+                    Err(e) => {
+                        tracing::warn!(game_id = %game_id, error = %e, "failed to parse game result");
+                        None
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!(game_id = %game_id, error = %e, "game result request failed");
+                    None
+                }
+            }
+        })
+        .buffer_unordered(RESULT_FETCH_CONCURRENCY)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await;
+
+    if let Err(e) = db.record_game_results(results).await {
+        tracing::warn!(error = %e, "failed to record game results");
+    }
+}
+
+// ============================================================================
+
+// Admin Stats Fetching
+
+// ============================================================================
+
+enum AdminStatsError {
+    Forbidden,
+    Other(String),
+}
+
+async fn fetch_admin_stats(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+) -> Result<(AdminStatsResponse, String), AdminStatsError> {
+    let resp = client
+        .get(format!("{}/api/v1/admin/stats", base_url))
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| AdminStatsError::Other(e.to_string()))?;
+
+    if resp.status() == StatusCode::FORBIDDEN || resp.status() == StatusCode::NOT_FOUND {
+        return Err(AdminStatsError::Forbidden);
+    }
+
+    if !resp.status().is_success() {
+        return Err(AdminStatsError::Other(format!("status {}", resp.status())));
+    }
+
+    let raw_json = resp
+        .text()
+        .await
+        .map_err(|e| AdminStatsError::Other(e.to_string()))?;
+
+    let stats: AdminStatsResponse = serde_json::from_str(&raw_json)
+        .map_err(|e| AdminStatsError::Other(format!("parse error: {}", e)))?;
+
+    Ok((stats, raw_json))
+}
+
+pub async fn admin_stats_load_phase(
+    client: reqwest::Client,
+    base_url: String,
+    token: String,
+    db: CompletionDb,
+    poll_interval: Duration,
+) {
+    let mut interval = tokio::time::interval(poll_interval);
+
+    loop {
+        interval.tick().await;
+        match fetch_admin_stats(&client, &base_url, &token).await {
+            Ok((stats, raw_json)) => {
+                if let Err(e) = db.record_admin_stats("load", &stats, &raw_json).await {
+                    tracing::warn!(error = %e, "failed to record load-phase admin stats");
+                }
+            }
+            Err(AdminStatsError::Forbidden) => {
+                tracing::warn!(
+                    "Admin stats unavailable (403) during load phase - stopping collection"
+                );
+                return;
+            }
+            Err(AdminStatsError::Other(e)) => {
+                tracing::warn!(error = %e, "failed to fetch load-phase admin stats");
+            }
+        }
+    }
+}
+
+// ============================================================================
+
+// Main
+
+// ============================================================================
+
+/// Poll the run's unfinished games to completion (or timeout, or until
+/// `stop_polling`), then print the completion report, write the HTML report,
+/// and record the summary on the run
+#[allow(clippy::too_many_arguments)]
+pub async fn poll_and_report(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    db: &CompletionDb,
+    poll_interval: u64,
+    poll_timeout: u64,
+    collect_admin_stats: bool,
+    html_report: &str,
+    label: &str,
+    stop_polling: CancellationToken,
+) -> Option<Report> {
+    // Start completion polling, unless it was skipped before it began
+    if !stop_polling.is_cancelled() {
+        println!();
+        println!(
+            "{}Starting completion polling (interval: {}s, timeout: {}s)...",
+            label, poll_interval, poll_timeout
+        );
+
+        completion_poller(
+            client,
+            base_url,
+            token,
+            db,
+            Duration::from_secs(poll_interval),
+            Duration::from_secs(poll_timeout),
+            collect_admin_stats,
+            label,
+            stop_polling,
+        )
+        .await;
+    }
+
+    // Generate and print completion report, then the HTML version
+    match db.generate_report(poll_interval).await {
+        Ok(report) => {
+            report.print();
+            match db.load_timeline().await {
+                Ok(timeline) => {
+                    match std::fs::write(html_report, render_html_report(&report, &timeline)) {
+                        Ok(()) => {
+                            println!();
+                            println!("HTML report: {}", html_report);
+                        }
+                        Err(e) => {
+                            tracing::error!(error = %e, "failed to write html report");
+                            println!("ERROR: Failed to write HTML report: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to load report timeline");
+                    println!("ERROR: Failed to load HTML report data: {}", e);
+                }
+            }
+            Some(report)
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "failed to generate completion report");
+            println!();
+            println!("ERROR: Failed to generate completion report: {}", e);
+            None
+        }
+    }
+}
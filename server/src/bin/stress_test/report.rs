@@ -0,0 +1,779 @@
+//! Run reports: the text summary, the HTML report and run-to-run comparison.
+
+use std::time::Duration;
+
+use color_eyre::eyre::{Context as _, eyre};
+use rusqlite::params;
+use uuid::Uuid;
+
+use crate::db::CompletionDb;
+use crate::stats::format_duration;
+
+pub struct Report {
+    pub run_id: Uuid,
+    pub total_games: u64,
+    pub finished: u64,
+    pub stuck_running: u64,
+    pub not_started: u64,
+    pub timing_source: TimingSource,
+    pub avg_completion_ms: Option<f64>,
+    pub p50_completion_ms: Option<f64>,
+    pub p95_completion_ms: Option<f64>,
+    pub p99_completion_ms: Option<f64>,
+    pub first_game_created: Option<String>,
+    pub last_game_finished: Option<String>,
+    pub overall_duration_secs: Option<f64>,
+    pub throughput_per_min: Option<f64>,
+    pub admin_stats_summary: Option<AdminStatsSummary>,
+    /// Per board/type, for mixed runs
+    pub by_config: Vec<ConfigReport>,
+}
+
+pub struct ConfigReport {
+    pub game_type: String,
+    pub board: String,
+    pub total: u64,
+    pub finished: u64,
+    pub stuck_running: u64,
+    pub p50_completion_ms: Option<f64>,
+    pub p95_completion_ms: Option<f64>,
+}
+
+pub enum TimingSource {
+    ServerSide,
+    ClientObserved { poll_interval_secs: u64 },
+}
+
+pub struct AdminStatsSummary {
+    pub snapshot_count: u64,
+    pub peak_jobs_ready: i64,
+    pub peak_jobs_running: i64,
+    pub peak_games_waiting: i64,
+    pub peak_games_running: i64,
+    pub final_jobs_ready: i64,
+    pub final_jobs_total: i64,
+    pub final_games_finished: i64,
+    pub final_games_total: i64,
+    pub final_avg_game_duration_secs: Option<f64>,
+    pub has_errors: bool,
+}
+
+impl Report {
+    pub fn print(&self) {
+        println!();
+        println!("=== Completion Report (run {}) ===", self.run_id);
+        println!();
+        println!("Games:");
+        println!("  Total:          {}", self.total_games);
+        println!("  Finished:       {}", self.finished);
+        println!("  Stuck (running): {}", self.stuck_running);
+        println!("  Not started:    {}", self.not_started);
+
+        if self.stuck_running > 0 {
+            println!();
+            println!("  NOTE: Games stuck at 'running' may indicate server memory pressure.");
+        }
+
+        println!();
+        match &self.timing_source {
+            TimingSource::ServerSide => println!("Timing: server-side (enqueued_at -> updated_at)"),
+            TimingSource::ClientObserved { poll_interval_secs } => println!(
+                "Timing: client-observed (poll interval: {}s)",
+                poll_interval_secs
+            ),
+        }
+
+        if let Some(avg) = self.avg_completion_ms {
+            println!("  Avg completion:  {:.0}ms", avg);
+        }
+        if let Some(p50) = self.p50_completion_ms {
+            println!("  p50 completion:  {:.0}ms", p50);
+        }
+        if let Some(p95) = self.p95_completion_ms {
+            println!("  p95 completion:  {:.0}ms", p95);
+        }
+        if let Some(p99) = self.p99_completion_ms {
+            println!("  p99 completion:  {:.0}ms", p99);
+        }
+
+        if self.by_config.len() > 1 {
+            let ms = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{:.0}ms", v));
+            println!();
+            println!("By game config:");
+            for config in &self.by_config {
+                println!(
+                    "  {:<20} total: {:<6} finished: {:<6} stuck: {:<4} p50: {:<8} p95: {}",
+                    format!("{}:{}", config.game_type, config.board),
+                    config.total,
+                    config.finished,
+                    config.stuck_running,
+                    ms(config.p50_completion_ms),
+                    ms(config.p95_completion_ms),
+                );
+            }
+        }
+
+        println!();
+        if let Some(ref first) = self.first_game_created {
+            println!("First created:     {}", first);
+        }
+        if let Some(ref last) = self.last_game_finished {
+            println!("Last finished:     {}", last);
+        }
+        if let Some(duration) = self.overall_duration_secs {
+            println!("Overall duration:  {:.1}s", duration);
+        }
+        if let Some(throughput) = self.throughput_per_min {
+            println!("Throughput:        {:.1} games/min", throughput);
+        }
+
+        if let Some(ref summary) = self.admin_stats_summary {
+            println!();
+            println!(
+                "=== Server Metrics ({} snapshots) ===",
+                summary.snapshot_count
+            );
+            println!("  Peak jobs ready:    {}", summary.peak_jobs_ready);
+            println!("  Peak jobs running:  {}", summary.peak_jobs_running);
+            println!("  Peak games waiting: {}", summary.peak_games_waiting);
+            println!("  Peak games running: {}", summary.peak_games_running);
+            println!();
+            println!("  Final jobs ready:   {}", summary.final_jobs_ready);
+            println!("  Final jobs total:   {}", summary.final_jobs_total);
+            println!("  Final games finished: {}", summary.final_games_finished);
+            println!("  Final games total:  {}", summary.final_games_total);
+            if let Some(avg_dur) = summary.final_avg_game_duration_secs {
+                println!("  Server avg game duration: {:.1}s", avg_dur);
+            }
+            if summary.has_errors {
+                println!("  WARNING: Recent job errors detected on server");
+            }
+        }
+    }
+}
+
+// ============================================================================
+
+// HTML Report
+
+// ============================================================================
+
+/// Everything the HTML report plots, read back from the completion db
+pub struct Timeline {
+    pub started_at: String,
+    pub base_url: String,
+    pub pattern: String,
+    pub games: Vec<TimelineGame>,
+    pub snapshots: Vec<TimelineSnapshot>,
+}
+
+pub struct TimelineGame {
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub status: String,
+    pub create_latency_ms: Option<f64>,
+    /// Server-side enqueue-to-finish time, for finished games
+    pub completion_ms: Option<f64>,
+}
+
+pub struct TimelineSnapshot {
+    pub captured_at: chrono::DateTime<chrono::FixedOffset>,
+    pub jobs_ready: i64,
+    pub jobs_running: i64,
+    pub games_waiting: i64,
+    pub games_running: i64,
+}
+
+/// One time bucket of a chart
+#[derive(Debug, PartialEq)]
+struct Bucket {
+    start_secs: f64,
+    count: usize,
+    p50: Option<f64>,
+    p95: Option<f64>,
+    p99: Option<f64>,
+}
+
+/// Percentile of already-sorted values, ranked like [`LatencyHistogram::percentile`]
+pub fn percentile(sorted: &[f64], pct: usize) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    Some(sorted[(sorted.len() * pct / 100).min(sorted.len() - 1)])
+}
+
+/// Group `(seconds since start, value)` points into fixed-width buckets,
+/// counting every point and taking percentiles of the ones with a value
+fn bucketize(points: &[(f64, Option<f64>)], bucket_secs: f64) -> Vec<Bucket> {
+    let Some(last) = points.iter().map(|(t, _)| *t).reduce(f64::max) else {
+        return Vec::new();
+    };
+    let bucket_count = (last / bucket_secs) as usize + 1;
+    let mut values: Vec<(usize, Vec<f64>)> = vec![(0, Vec::new()); bucket_count];
+    for (t, value) in points {
+        let slot = &mut values[((t.max(0.0) / bucket_secs) as usize).min(bucket_count - 1)];
+        slot.0 += 1;
+        slot.1.extend(value);
+    }
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, (count, mut vals))| {
+            vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            Bucket {
+                start_secs: i as f64 * bucket_secs,
+                count,
+                p50: percentile(&vals, 50),
+                p95: percentile(&vals, 95),
+                p99: percentile(&vals, 99),
+            }
+        })
+        .collect()
+}
+
+/// About 60 buckets across the run, never finer than a second
+fn bucket_width(span_secs: f64) -> f64 {
+    (span_secs / 60.0).max(1.0).ceil()
+}
+
+struct Series {
+    name: &'static str,
+    color: &'static str,
+    points: Vec<(f64, f64)>,
+}
+
+const CHART_WIDTH: f64 = 760.0;
+const CHART_HEIGHT: f64 = 240.0;
+const CHART_MARGIN: f64 = 50.0;
+
+/// A line chart with seconds-since-start on the x axis, as inline SVG
+fn line_chart(y_label: &str, series: &[Series]) -> maud::Markup {
+    let all = series.iter().flat_map(|s| s.points.iter());
+    let max_x = all.clone().map(|(x, _)| *x).fold(1.0, f64::max);
+    let max_y = all.map(|(_, y)| *y).fold(0.0, f64::max).max(1.0) * 1.1;
+    let plot_w = CHART_WIDTH - 2.0 * CHART_MARGIN;
+    let plot_h = CHART_HEIGHT - 2.0 * CHART_MARGIN;
+    let sx = |x: f64| CHART_MARGIN + x / max_x * plot_w;
+    let sy = |y: f64| CHART_HEIGHT - CHART_MARGIN - y / max_y * plot_h;
+
+    maud::html! {
+        svg width=(CHART_WIDTH) height=(CHART_HEIGHT) xmlns="http://www.w3.org/2000/svg" {
+            line x1=(CHART_MARGIN) y1=(sy(0.0)) x2=(CHART_WIDTH - CHART_MARGIN) y2=(sy(0.0)) stroke="#999" {}
+            line x1=(CHART_MARGIN) y1=(sy(0.0)) x2=(CHART_MARGIN) y2=(CHART_MARGIN) stroke="#999" {}
+            @for i in 0..=4 {
+                @let y = max_y * i as f64 / 4.0;
+                text x=(CHART_MARGIN - 6.0) y=(sy(y) + 4.0) text-anchor="end" font-size="11" { (format!("{:.0}", y)) }
+                @let x = max_x * i as f64 / 4.0;
+                text x=(sx(x)) y=(CHART_HEIGHT - CHART_MARGIN + 16.0) text-anchor="middle" font-size="11" {
+                    (format_duration(Duration::from_secs_f64(x)))
+                }
+            }
+            text x="12" y=(CHART_HEIGHT / 2.0) font-size="11" transform=(format!("rotate(-90 12 {})", CHART_HEIGHT / 2.0)) text-anchor="middle" { (y_label) }
+            @for s in series {
+                polyline fill="none" stroke=(s.color) stroke-width="2"
+                    points=(s.points.iter().map(|(x, y)| format!("{:.1},{:.1}", sx(*x), sy(*y))).collect::<Vec<_>>().join(" ")) {}
+            }
+            @for (i, s) in series.iter().enumerate() {
+                @let lx = CHART_MARGIN + 10.0 + i as f64 * 140.0;
+                rect x=(lx) y="12" width="12" height="12" fill=(s.color) {}
+                text x=(lx + 18.0) y="22" font-size="12" { (s.name) }
+            }
+        }
+    }
+}
+
+pub fn render_html_report(report: &Report, timeline: &Timeline) -> String {
+    let start = timeline.games.iter().map(|g| g.created_at).min();
+    let since_start = |t: chrono::DateTime<chrono::FixedOffset>| {
+        start.map_or(0.0, |s| {
+            t.signed_duration_since(s).num_milliseconds() as f64 / 1000.0
+        })
+    };
+
+    let creations: Vec<(f64, Option<f64>)> = timeline
+        .games
+        .iter()
+        .map(|g| (since_start(g.created_at), g.create_latency_ms))
+        .collect();
+    let completions: Vec<(f64, Option<f64>)> = timeline
+        .games
+        .iter()
+        .map(|g| (since_start(g.created_at), g.completion_ms))
+        .collect();
+    let span = creations.iter().map(|(t, _)| *t).fold(0.0, f64::max);
+    let width = bucket_width(span);
+    let created_buckets = bucketize(&creations, width);
+    let completion_buckets = bucketize(&completions, width);
+
+    let percentile_series = |buckets: &[Bucket]| {
+        let line = |pick: fn(&Bucket) -> Option<f64>| -> Vec<(f64, f64)> {
+            buckets
+                .iter()
+                .filter_map(|b| pick(b).map(|v| (b.start_secs, v)))
+                .collect()
+        };
+        vec![
+            Series {
+                name: "p50",
+                color: "#2e7d32",
+                points: line(|b| b.p50),
+            },
+            Series {
+                name: "p95",
+                color: "#f9a825",
+                points: line(|b| b.p95),
+            },
+            Series {
+                name: "p99",
+                color: "#c62828",
+                points: line(|b| b.p99),
+            },
+        ]
+    };
+
+    let rate = vec![Series {
+        name: "games/s",
+        color: "#1565c0",
+        points: created_buckets
+            .iter()
+            .map(|b| (b.start_secs, b.count as f64 / width))
+            .collect(),
+    }];
+
+    let snapshot_series = |name, color, pick: fn(&TimelineSnapshot) -> i64| Series {
+        name,
+        color,
+        points: timeline
+            .snapshots
+            .iter()
+            .map(|s| (since_start(s.captured_at).max(0.0), pick(s) as f64))
+            .collect(),
+    };
+    let server = vec![
+        snapshot_series("jobs ready", "#1565c0", |s| s.jobs_ready),
+        snapshot_series("jobs running", "#6a1b9a", |s| s.jobs_running),
+        snapshot_series("games waiting", "#f9a825", |s| s.games_waiting),
+        snapshot_series("games running", "#2e7d32", |s| s.games_running),
+    ];
+
+    let started = timeline
+        .games
+        .iter()
+        .filter(|g| g.status == "running" || g.status == "finished")
+        .count() as u64;
+    let funnel = [
+        ("Created", report.total_games),
+        ("Started", started),
+        ("Finished", report.finished),
+    ];
+    let funnel_max = report.total_games.max(1) as f64;
+    let ms = |v: Option<f64>| v.map_or_else(|| "n/a".to_string(), |v| format!("{:.0}ms", v));
+
+    let page = maud::html! {
+        (maud::DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                title { "Stress test " (report.run_id) }
+                style {
+                    "body { font-family: sans-serif; margin: 24px; color: #222; }"
+                    "table { border-collapse: collapse; }"
+                    "th, td { text-align: left; padding: 6px 12px; border-bottom: 1px solid #ddd; }"
+                    "section { margin-top: 32px; }"
+                }
+            }
+            body {
+                h1 { "Stress test report" }
+                table {
+                    tr { th { "Run" } td { (report.run_id) } }
+                    tr { th { "Started" } td { (timeline.started_at) } }
+                    tr { th { "Target" } td { (timeline.base_url) } }
+                    tr { th { "Pattern" } td { (timeline.pattern) } }
+                    tr { th { "Games" } td { (report.total_games) " created, " (report.finished) " finished, "
+                        (report.stuck_running) " stuck running, " (report.not_started) " not started" } }
+                    tr { th { "Completion" } td { "p50 " (ms(report.p50_completion_ms)) ", p95 " (ms(report.p95_completion_ms))
+                        ", p99 " (ms(report.p99_completion_ms)) } }
+                    @if let Some(throughput) = report.throughput_per_min {
+                        tr { th { "Throughput" } td { (format!("{:.1}", throughput)) " games/min" } }
+                    }
+                }
+
+                section {
+                    h2 { "Creation rate" }
+                    (line_chart("games/s", &rate))
+                }
+                section {
+                    h2 { "Create latency" }
+                    (line_chart("ms", &percentile_series(&created_buckets)))
+                }
+                section {
+                    h2 { "Completion time by creation time" }
+                    (line_chart("ms", &percentile_series(&completion_buckets)))
+                }
+                section {
+                    h2 { "Completion funnel" }
+                    svg width=(CHART_WIDTH) height="110" xmlns="http://www.w3.org/2000/svg" {
+                        @for (i, (label, count)) in funnel.iter().enumerate() {
+                            @let y = 10.0 + i as f64 * 32.0;
+                            text x="0" y=(y + 16.0) font-size="12" { (label) }
+                            rect x="80" y=(y) height="22" fill="#1565c0"
+                                width=(format!("{:.1}", (CHART_WIDTH - 200.0) * *count as f64 / funnel_max)) {}
+                            text x=(90.0 + (CHART_WIDTH - 200.0) * *count as f64 / funnel_max) y=(y + 16.0) font-size="12" { (count) }
+                        }
+                    }
+                }
+                section {
+                    h2 { "Server metrics" }
+                    @if timeline.snapshots.is_empty() {
+                        p { "No admin stats snapshots were collected (--no-admin-stats, or the token lacks the admin scope)." }
+                    } @else {
+                        (line_chart("count", &server))
+                    }
+                }
+            }
+        }
+    };
+    page.into_string()
+}
+
+// ============================================================================
+
+// Run Comparison
+
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    HigherIsBetter,
+    LowerIsBetter,
+    /// Shown for context, never a regression
+    Neutral,
+}
+
+#[derive(Debug)]
+struct MetricDiff {
+    name: &'static str,
+    a: Option<f64>,
+    b: Option<f64>,
+    direction: Direction,
+}
+
+impl MetricDiff {
+    fn change_pct(&self) -> Option<f64> {
+        match (self.a, self.b) {
+            (Some(a), Some(b)) if a != 0.0 => Some((b - a) / a * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Whether run B is worse than run A by more than `tolerance_pct`. A count
+    /// that was zero in A (stuck games, say) regresses on any increase.
+    fn is_regression(&self, tolerance_pct: f64) -> bool {
+        let (Some(a), Some(b)) = (self.a, self.b) else {
+            return false;
+        };
+        match self.direction {
+            Direction::Neutral => false,
+            Direction::LowerIsBetter if a == 0.0 => b > 0.0,
+            Direction::LowerIsBetter => self.change_pct().is_some_and(|c| c > tolerance_pct),
+            Direction::HigherIsBetter => self.change_pct().is_some_and(|c| c < -tolerance_pct),
+        }
+    }
+}
+
+/// The metrics worth diffing between two runs' completion reports
+fn compare_reports(a: &Report, b: &Report) -> Vec<MetricDiff> {
+    let diff = |name, direction, pick: fn(&Report) -> Option<f64>| MetricDiff {
+        name,
+        a: pick(a),
+        b: pick(b),
+        direction,
+    };
+    let peak = |pick: fn(&AdminStatsSummary) -> i64| {
+        move |r: &Report| r.admin_stats_summary.as_ref().map(|s| pick(s) as f64)
+    };
+
+    vec![
+        diff("Games created", Direction::Neutral, |r| {
+            Some(r.total_games as f64)
+        }),
+        diff("Throughput (games/min)", Direction::HigherIsBetter, |r| {
+            r.throughput_per_min
+        }),
+        diff("p50 completion (ms)", Direction::LowerIsBetter, |r| {
+            r.p50_completion_ms
+        }),
+        diff("p95 completion (ms)", Direction::LowerIsBetter, |r| {
+            r.p95_completion_ms
+        }),
+        diff("p99 completion (ms)", Direction::LowerIsBetter, |r| {
+            r.p99_completion_ms
+        }),
+        diff("Stuck (running)", Direction::LowerIsBetter, |r| {
+            Some(r.stuck_running as f64)
+        }),
+        diff("Not started", Direction::LowerIsBetter, |r| {
+            Some(r.not_started as f64)
+        }),
+        MetricDiff {
+            name: "Peak jobs ready",
+            a: peak(|s| s.peak_jobs_ready)(a),
+            b: peak(|s| s.peak_jobs_ready)(b),
+            direction: Direction::LowerIsBetter,
+        },
+        MetricDiff {
+            name: "Peak jobs running",
+            a: peak(|s| s.peak_jobs_running)(a),
+            b: peak(|s| s.peak_jobs_running)(b),
+            direction: Direction::Neutral,
+        },
+        MetricDiff {
+            name: "Peak games waiting",
+            a: peak(|s| s.peak_games_waiting)(a),
+            b: peak(|s| s.peak_games_waiting)(b),
+            direction: Direction::LowerIsBetter,
+        },
+        MetricDiff {
+            name: "Peak games running",
+            a: peak(|s| s.peak_games_running)(a),
+            b: peak(|s| s.peak_games_running)(b),
+            direction: Direction::Neutral,
+        },
+    ]
+}
+
+/// `started_at` and `pattern` for a run, or an error naming the missing run
+pub fn run_info_sync(
+    conn: &rusqlite::Connection,
+    run_id: Uuid,
+) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
+    conn.query_row(
+        "SELECT started_at, pattern FROM runs WHERE run_id = ?1",
+        params![run_id.to_string()],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("run {} not found", run_id).into(),
+        e => e.into(),
+    })
+}
+
+/// `stress-test compare`: diff two runs from the results DB, print the table,
+/// and fail if B regressed against A
+pub fn compare_runs(
+    db: &str,
+    run_a: Uuid,
+    run_b: Uuid,
+    tolerance_pct: f64,
+) -> color_eyre::Result<()> {
+    let conn =
+        rusqlite::Connection::open_with_flags(db, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .wrap_err_with(|| format!("Failed to open results DB {}", db))?;
+
+    let mut reports = Vec::new();
+    for run_id in [run_a, run_b] {
+        let (started_at, pattern) = run_info_sync(&conn, run_id).map_err(|e| eyre!(e))?;
+        // The poll interval only labels client-observed timing in print()
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 0)
+            .map_err(|e| eyre!("Failed to load run {}: {}", run_id, e))?;
+        reports.push((started_at, pattern, report));
+    }
+    let diffs = compare_reports(&reports[0].2, &reports[1].2);
+
+    println!("=== Run Comparison ===");
+    for (label, (started_at, pattern, report)) in ["A", "B"].iter().zip(&reports) {
+        println!(
+            "  {}: {} ({}, started {})",
+            label, report.run_id, pattern, started_at
+        );
+    }
+    println!();
+    println!("  {:<24} {:>12} {:>12} {:>9}", "Metric", "A", "B", "Change");
+    let value = |v: Option<f64>| v.map_or_else(|| "n/a".to_string(), |v| format!("{:.1}", v));
+    for diff in &diffs {
+        let change = diff
+            .change_pct()
+            .map_or_else(|| "-".to_string(), |c| format!("{:+.1}%", c));
+        let flag = if diff.is_regression(tolerance_pct) {
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "  {:<24} {:>12} {:>12} {:>9}{}",
+            diff.name,
+            value(diff.a),
+            value(diff.b),
+            change,
+            flag
+        );
+    }
+
+    let regressions: Vec<&str> = diffs
+        .iter()
+        .filter(|d| d.is_regression(tolerance_pct))
+        .map(|d| d.name)
+        .collect();
+    println!();
+    if regressions.is_empty() {
+        println!("No regressions beyond {}%", tolerance_pct);
+        Ok(())
+    } else {
+        Err(eyre!(
+            "{} regression(s): {}",
+            regressions.len(),
+            regressions.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Utc;
+
+    use crate::db::tests::new_in_memory_db;
+    use crate::load::tests::default_variant;
+    use crate::poll::PollGameStatus;
+
+    #[test]
+    fn test_bucketize() {
+        let points = [
+            (0.0, Some(10.0)),
+            (0.5, Some(30.0)),
+            (1.2, None),
+            (3.9, Some(50.0)),
+        ];
+        let buckets = bucketize(&points, 2.0);
+        assert_eq!(
+            buckets,
+            vec![
+                Bucket {
+                    start_secs: 0.0,
+                    count: 3,
+                    p50: Some(30.0),
+                    p95: Some(30.0),
+                    p99: Some(30.0),
+                },
+                Bucket {
+                    start_secs: 2.0,
+                    count: 1,
+                    p50: Some(50.0),
+                    p95: Some(50.0),
+                    p99: Some(50.0),
+                },
+            ]
+        );
+        assert!(bucketize(&[], 1.0).is_empty());
+        assert_eq!(bucket_width(30.0), 1.0);
+        assert_eq!(bucket_width(600.0), 10.0);
+    }
+
+    #[test]
+    fn test_html_report_from_completion_db() {
+        let (conn, run_id) = new_in_memory_db();
+        let game = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            game,
+            Duration::from_millis(42),
+            &default_variant(),
+        )
+        .unwrap();
+        let now = Utc::now();
+        CompletionDb::update_game_statuses_sync(
+            &conn,
+            &[PollGameStatus {
+                id: game,
+                status: "finished".to_string(),
+                updated_at: Some(now),
+                enqueued_at: Some(now - chrono::Duration::seconds(3)),
+                created_at: Some(now - chrono::Duration::seconds(4)),
+            }],
+        )
+        .unwrap();
+
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        let timeline = CompletionDb::load_timeline_sync(&conn, run_id).unwrap();
+        assert_eq!(timeline.games.len(), 1);
+        assert_eq!(timeline.games[0].create_latency_ms, Some(42.0));
+        assert_eq!(timeline.games[0].completion_ms, Some(3000.0));
+
+        let html = render_html_report(&report, &timeline);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(&run_id.to_string()));
+        assert!(html.contains("Completion funnel"));
+        assert!(html.contains("<polyline"));
+        assert!(html.contains("No admin stats snapshots"));
+    }
+
+    #[test]
+    fn test_compare_flags_regressions() {
+        let diff = |a, b, direction| MetricDiff {
+            name: "m",
+            a: Some(a),
+            b: Some(b),
+            direction,
+        };
+        assert!(diff(100.0, 115.0, Direction::LowerIsBetter).is_regression(10.0));
+        assert!(!diff(100.0, 105.0, Direction::LowerIsBetter).is_regression(10.0));
+        assert!(!diff(100.0, 50.0, Direction::LowerIsBetter).is_regression(10.0));
+        assert!(diff(0.0, 1.0, Direction::LowerIsBetter).is_regression(10.0));
+        assert!(diff(100.0, 80.0, Direction::HigherIsBetter).is_regression(10.0));
+        assert!(!diff(100.0, 120.0, Direction::HigherIsBetter).is_regression(10.0));
+        assert!(!diff(1.0, 100.0, Direction::Neutral).is_regression(10.0));
+        assert_eq!(
+            diff(200.0, 150.0, Direction::Neutral).change_pct(),
+            Some(-25.0)
+        );
+    }
+
+    #[test]
+    fn test_compare_reports_from_completion_db() {
+        let (conn, run_a) = new_in_memory_db();
+        let run_b = Uuid::new_v4();
+        CompletionDb::insert_run_sync(&conn, run_b, "http://test", "test", 30).unwrap();
+        let stuck = Uuid::new_v4();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_a,
+            Uuid::new_v4(),
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_b,
+            stuck,
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        CompletionDb::update_game_statuses_sync(
+            &conn,
+            &[PollGameStatus {
+                id: stuck,
+                status: "running".to_string(),
+                updated_at: None,
+                enqueued_at: None,
+                created_at: None,
+            }],
+        )
+        .unwrap();
+
+        let a = CompletionDb::generate_report_sync(&conn, run_a, 0).unwrap();
+        let b = CompletionDb::generate_report_sync(&conn, run_b, 0).unwrap();
+        let regressions: Vec<_> = compare_reports(&a, &b)
+            .into_iter()
+            .filter(|d| d.is_regression(10.0))
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(regressions, vec!["Stuck (running)"]);
+
+        assert!(run_info_sync(&conn, Uuid::new_v4()).is_err());
+        assert_eq!(run_info_sync(&conn, run_b).unwrap().1, "test");
+    }
+}
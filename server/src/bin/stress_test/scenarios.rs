@@ -0,0 +1,637 @@
+//! Scenario files, pass/fail thresholds and `--assert` expressions.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::Context as _;
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::PatternArgs;
+use crate::load::{GameVariant, LoadConfig};
+use crate::patterns::{LoadPattern, parse_duration};
+use crate::report::Report;
+use crate::stats::{Stats, StatsSnapshot, format_duration};
+
+/// A multi-phase stress test, loaded from a TOML file with --scenario:
+///
+/// ```toml
+/// name = "nightly"
+/// snakes = ["<uuid>", "<uuid>"]            # default snake set
+/// mix = [{ board = "11x11", type = "standard", weight = 3 },
+///        { board = "19x19", type = "royale" }]
+///
+/// [snake_sets]
+/// big = ["<uuid>", "<uuid>", "<uuid>", "<uuid>"]
+///
+/// [[phases]]
+/// name = "warmup"
+/// duration = "1m"
+/// steady = "1/s"
+///
+/// [[phases]]
+/// name = "spike"
+/// duration = "30s"
+/// batch = "200,10s"
+/// snakes = "big"                          # a snake_sets entry
+///
+/// [thresholds]
+/// min_success_rate = 99.0
+/// max_stuck_running = 0
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: Option<String>,
+    /// Default snake set; falls back to --snakes
+    #[serde(default)]
+    snakes: Vec<Uuid>,
+    #[serde(default)]
+    snake_sets: std::collections::HashMap<String, Vec<Uuid>>,
+    /// Default board/type mix; falls back to --mix, then --board and --type
+    #[serde(default)]
+    mix: Vec<GameVariant>,
+    phases: Vec<Phase>,
+    #[serde(default)]
+    pub thresholds: Thresholds,
+}
+
+/// `deny_unknown_fields` doesn't work through `#[serde(flatten)]`, so the
+/// pattern keys are repeated here to catch typos like `stedy`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    name: String,
+    duration: String,
+    /// Name of a `snake_sets` entry
+    snakes: Option<String>,
+    mix: Option<Vec<GameVariant>>,
+    steady: Option<String>,
+    batch: Option<String>,
+    ramp: Option<String>,
+    poisson: Option<String>,
+    sine: Option<String>,
+}
+
+impl Phase {
+    fn patterns(&self) -> PatternArgs {
+        PatternArgs {
+            steady: self.steady.clone(),
+            batch: self.batch.clone(),
+            ramp: self.ramp.clone(),
+            poisson: self.poisson.clone(),
+            sine: self.sine.clone(),
+        }
+    }
+}
+
+/// One phase, validated and ready to run
+pub struct PhasePlan {
+    pub name: String,
+    pub duration: Duration,
+    pub patterns: Vec<Box<dyn LoadPattern>>,
+    pub pattern_desc: String,
+    pub snakes: Vec<Uuid>,
+    pub variants: Vec<GameVariant>,
+}
+
+impl Scenario {
+    pub fn load(path: &str) -> color_eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read scenario {}", path))?;
+        toml::from_str(&contents).wrap_err_with(|| format!("Invalid scenario {}", path))
+    }
+
+    /// Resolve every phase against the scenario defaults and the CLI
+    /// fallbacks, failing before any load is generated
+    pub fn plan(
+        &self,
+        default_snakes: &[Uuid],
+        default_mix: &[GameVariant],
+    ) -> Result<Vec<PhasePlan>, String> {
+        if self.phases.is_empty() {
+            return Err("Scenario has no phases".to_string());
+        }
+        let snakes = if self.snakes.is_empty() {
+            default_snakes
+        } else {
+            &self.snakes
+        };
+        let mix = if self.mix.is_empty() {
+            default_mix
+        } else {
+            &self.mix
+        };
+
+        self.phases
+            .iter()
+            .map(|phase| {
+                let context = |e: String| format!("Phase '{}': {}", phase.name, e);
+                let duration = parse_duration(&phase.duration).map_err(context)?;
+                let (patterns, pattern_desc) = phase.patterns().build().map_err(context)?;
+                let snakes = match &phase.snakes {
+                    Some(set) => self
+                        .snake_sets
+                        .get(set)
+                        .ok_or_else(|| context(format!("unknown snake set '{}'", set)))?
+                        .clone(),
+                    None => snakes.to_vec(),
+                };
+                if snakes.is_empty() {
+                    return Err(context("no snakes (set --snakes or `snakes`)".to_string()));
+                }
+                let variants = phase.mix.clone().unwrap_or_else(|| mix.to_vec());
+                if variants.is_empty() || variants.iter().any(|v| v.weight == 0) {
+                    return Err(context("mix weights must be positive".to_string()));
+                }
+                Ok(PhasePlan {
+                    name: phase.name.clone(),
+                    duration,
+                    patterns,
+                    pattern_desc,
+                    snakes,
+                    variants,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Pass/fail criteria checked after the completion report. Unset ones are
+/// skipped.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Thresholds {
+    /// Percent of create requests that must succeed
+    min_success_rate: Option<f64>,
+    max_create_p99_ms: Option<f64>,
+    max_completion_p99_ms: Option<f64>,
+    max_stuck_running: Option<u64>,
+    max_not_started: Option<u64>,
+    min_throughput_per_min: Option<f64>,
+}
+
+pub struct ThresholdResult {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl Thresholds {
+    pub fn check(&self, creation: &StatsSnapshot, report: Option<&Report>) -> Vec<ThresholdResult> {
+        fn fmt_actual(actual: Option<f64>) -> String {
+            actual.map_or_else(|| "n/a".to_string(), |a| format!("{:.1}", a))
+        }
+        fn min(name: &'static str, actual: Option<f64>, limit: f64) -> ThresholdResult {
+            ThresholdResult {
+                name,
+                passed: actual.is_some_and(|a| a >= limit),
+                detail: format!("{} (min {})", fmt_actual(actual), limit),
+            }
+        }
+        fn max(name: &'static str, actual: Option<f64>, limit: f64) -> ThresholdResult {
+            ThresholdResult {
+                name,
+                passed: actual.is_some_and(|a| a <= limit),
+                detail: format!("{} (max {})", fmt_actual(actual), limit),
+            }
+        }
+
+        let mut results = Vec::new();
+        if let Some(limit) = self.min_success_rate {
+            results.push(min("success rate %", Some(creation.success_rate), limit));
+        }
+        if let Some(limit) = self.max_create_p99_ms {
+            results.push(max("create p99 ms", Some(creation.p99_latency_ms), limit));
+        }
+        if let Some(limit) = self.max_completion_p99_ms {
+            let actual = report.and_then(|r| r.p99_completion_ms);
+            results.push(max("completion p99 ms", actual, limit));
+        }
+        if let Some(limit) = self.max_stuck_running {
+            let actual = report.map(|r| r.stuck_running as f64);
+            results.push(max("stuck running", actual, limit as f64));
+        }
+        if let Some(limit) = self.max_not_started {
+            let actual = report.map(|r| r.not_started as f64);
+            results.push(max("not started", actual, limit as f64));
+        }
+        if let Some(limit) = self.min_throughput_per_min {
+            let actual = report.and_then(|r| r.throughput_per_min);
+            results.push(min("throughput/min", actual, limit));
+        }
+        results
+    }
+}
+
+/// A number `--assert` can check
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AssertMetric {
+    SuccessRate,
+    Failed,
+    CreateP50Ms,
+    CreateP95Ms,
+    CreateP99Ms,
+    Finished,
+    Stuck,
+    NotStarted,
+    P50CompletionMs,
+    P95CompletionMs,
+    P99CompletionMs,
+    ThroughputPerMin,
+}
+
+impl AssertMetric {
+    const ALL: [AssertMetric; 12] = [
+        AssertMetric::SuccessRate,
+        AssertMetric::Failed,
+        AssertMetric::CreateP50Ms,
+        AssertMetric::CreateP95Ms,
+        AssertMetric::CreateP99Ms,
+        AssertMetric::Finished,
+        AssertMetric::Stuck,
+        AssertMetric::NotStarted,
+        AssertMetric::P50CompletionMs,
+        AssertMetric::P95CompletionMs,
+        AssertMetric::P99CompletionMs,
+        AssertMetric::ThroughputPerMin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssertMetric::SuccessRate => "success_rate",
+            AssertMetric::Failed => "failed",
+            AssertMetric::CreateP50Ms => "create_p50_ms",
+            AssertMetric::CreateP95Ms => "create_p95_ms",
+            AssertMetric::CreateP99Ms => "create_p99_ms",
+            AssertMetric::Finished => "finished",
+            AssertMetric::Stuck => "stuck",
+            AssertMetric::NotStarted => "not_started",
+            AssertMetric::P50CompletionMs => "p50_completion_ms",
+            AssertMetric::P95CompletionMs => "p95_completion_ms",
+            AssertMetric::P99CompletionMs => "p99_completion_ms",
+            AssertMetric::ThroughputPerMin => "throughput_per_min",
+        }
+    }
+
+    /// Completion metrics are `None` without a completion report
+    fn value(self, creation: &StatsSnapshot, report: Option<&Report>) -> Option<f64> {
+        match self {
+            AssertMetric::SuccessRate => Some(creation.success_rate),
+            AssertMetric::Failed => Some(creation.failed as f64),
+            AssertMetric::CreateP50Ms => Some(creation.p50_latency_ms),
+            AssertMetric::CreateP95Ms => Some(creation.p95_latency_ms),
+            AssertMetric::CreateP99Ms => Some(creation.p99_latency_ms),
+            AssertMetric::Finished => report.map(|r| r.finished as f64),
+            AssertMetric::Stuck => report.map(|r| r.stuck_running as f64),
+            AssertMetric::NotStarted => report.map(|r| r.not_started as f64),
+            AssertMetric::P50CompletionMs => report.and_then(|r| r.p50_completion_ms),
+            AssertMetric::P95CompletionMs => report.and_then(|r| r.p95_completion_ms),
+            AssertMetric::P99CompletionMs => report.and_then(|r| r.p99_completion_ms),
+            AssertMetric::ThroughputPerMin => report.and_then(|r| r.throughput_per_min),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AssertOp {
+    Ge,
+    Le,
+    Eq,
+    Gt,
+    Lt,
+}
+
+impl AssertOp {
+    /// Two-character operators first, so `>=` isn't read as `>`
+    const ALL: [AssertOp; 5] = [
+        AssertOp::Ge,
+        AssertOp::Le,
+        AssertOp::Eq,
+        AssertOp::Gt,
+        AssertOp::Lt,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssertOp::Ge => ">=",
+            AssertOp::Le => "<=",
+            AssertOp::Eq => "==",
+            AssertOp::Gt => ">",
+            AssertOp::Lt => "<",
+        }
+    }
+
+    fn holds(self, actual: f64, limit: f64) -> bool {
+        match self {
+            AssertOp::Ge => actual >= limit,
+            AssertOp::Le => actual <= limit,
+            AssertOp::Eq => actual == limit,
+            AssertOp::Gt => actual > limit,
+            AssertOp::Lt => actual < limit,
+        }
+    }
+}
+
+/// One `--assert` check, like `p95_completion_ms<=30000`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assertion {
+    metric: AssertMetric,
+    op: AssertOp,
+    limit: f64,
+}
+
+impl Assertion {
+    /// A missing value (no completion report) fails the check
+    pub fn check(&self, creation: &StatsSnapshot, report: Option<&Report>) -> ThresholdResult {
+        let actual = self.metric.value(creation, report);
+        ThresholdResult {
+            name: self.metric.as_str(),
+            passed: actual.is_some_and(|a| self.op.holds(a, self.limit)),
+            detail: format!(
+                "{} ({} {})",
+                actual.map_or_else(|| "n/a".to_string(), |a| format!("{:.1}", a)),
+                self.op.as_str(),
+                self.limit
+            ),
+        }
+    }
+}
+
+/// Parse an `--assert` like "success_rate>=99,stuck==0"
+pub fn parse_assertions(s: &str) -> Result<Vec<Assertion>, String> {
+    s.split(',')
+        .map(|entry| {
+            let entry = entry.trim();
+            let (op, at) = AssertOp::ALL
+                .iter()
+                .find_map(|op| entry.find(op.as_str()).map(|at| (*op, at)))
+                .ok_or_else(|| {
+                    format!("'{}' has no comparison (use >=, <=, ==, >, or <)", entry)
+                })?;
+            let name = entry[..at].trim();
+            let metric = AssertMetric::ALL
+                .into_iter()
+                .find(|m| m.as_str() == name)
+                .ok_or_else(|| {
+                    let known: Vec<_> = AssertMetric::ALL.iter().map(|m| m.as_str()).collect();
+                    format!("Unknown metric '{}' (one of: {})", name, known.join(", "))
+                })?;
+            let limit = entry[at + op.as_str().len()..]
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid value in '{}'", entry))?;
+            Ok(Assertion { metric, op, limit })
+        })
+        .collect()
+}
+
+/// Run one phase's patterns for its duration, then stop them
+pub async fn run_phase(
+    client: &reqwest::Client,
+    config: &LoadConfig,
+    stats: &Arc<Stats>,
+    phase: PhasePlan,
+    stop: &CancellationToken,
+) {
+    println!(
+        "--- Phase '{}': {} for {} ---",
+        phase.name,
+        phase.pattern_desc,
+        format_duration(phase.duration)
+    );
+    tracing::info!(
+        phase = %phase.name,
+        pattern = %phase.pattern_desc,
+        duration_secs = phase.duration.as_secs(),
+        "phase_started"
+    );
+
+    let config = LoadConfig {
+        snakes: phase.snakes,
+        variants: phase.variants,
+        ..config.clone()
+    };
+    let cancel = CancellationToken::new();
+    let mut handles = Vec::new();
+    for pattern in phase.patterns {
+        let client = client.clone();
+        let config = config.clone();
+        let stats = stats.clone();
+        let cancel = cancel.clone();
+
+        handles.push(tokio::spawn(async move {
+            pattern.run(&client, &config, &stats, cancel).await;
+        }));
+    }
+
+    tokio::select! {
+        _ = tokio::time::sleep(phase.duration) => {}
+        _ = stop.cancelled() => {}
+    }
+    cancel.cancel();
+    for handle in handles {
+        let _ = handle.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::db::CompletionDb;
+    use crate::db::tests::new_in_memory_db;
+    use crate::load::FailureCategory;
+    use crate::load::tests::default_variant;
+
+    const SNAKE_A: &str = "00000000-0000-0000-0000-00000000000a";
+    const SNAKE_B: &str = "00000000-0000-0000-0000-00000000000b";
+
+    #[test]
+    fn test_scenario_plan() {
+        let scenario: Scenario = toml::from_str(&format!(
+            r#"
+            name = "nightly"
+            mix = [{{ board = "19x19", type = "royale", weight = 3 }}]
+
+            [snake_sets]
+            big = ["{SNAKE_A}", "{SNAKE_B}"]
+
+            [[phases]]
+            name = "warmup"
+            duration = "1m"
+            steady = "1/s"
+
+            [[phases]]
+            name = "spike"
+            duration = "30s"
+            batch = "200,10s"
+            ramp = "1/s..5/s over 30s"
+            snakes = "big"
+            mix = [{{ board = "7x7", type = "standard" }}]
+
+            [thresholds]
+            min_success_rate = 99.0
+            "#
+        ))
+        .unwrap();
+
+        let default_snakes = vec![Uuid::parse_str(SNAKE_A).unwrap()];
+        let phases = scenario
+            .plan(&default_snakes, &[default_variant()])
+            .unwrap();
+        assert_eq!(phases.len(), 2);
+
+        assert_eq!(phases[0].name, "warmup");
+        assert_eq!(phases[0].duration, Duration::from_secs(60));
+        assert_eq!(phases[0].pattern_desc, "steady:1/s");
+        assert_eq!(phases[0].snakes, default_snakes);
+        assert_eq!(phases[0].variants[0].board, "19x19");
+        assert_eq!(phases[0].variants[0].weight, 3);
+
+        assert_eq!(phases[1].patterns.len(), 2);
+        assert_eq!(phases[1].snakes.len(), 2);
+        assert_eq!(phases[1].variants[0].board, "7x7");
+        assert_eq!(phases[1].variants[0].weight, 1);
+        assert_eq!(scenario.thresholds.min_success_rate, Some(99.0));
+    }
+
+    #[test]
+    fn test_scenario_plan_invalid() {
+        let plan = |toml: &str| {
+            let scenario: Scenario = toml::from_str(toml).unwrap();
+            scenario.plan(&[], &[default_variant()]).map(|_| ())
+        };
+        let phase = r#"
+            [[phases]]
+            name = "a"
+            duration = "1m"
+        "#;
+
+        // No snakes anywhere
+        assert!(plan(&format!("{phase}\nsteady = \"1/s\"")).is_err());
+        // No pattern
+        assert!(plan(&format!("snakes = [\"{SNAKE_A}\"]\n{phase}")).is_err());
+        // Unknown snake set
+        assert!(
+            plan(&format!(
+                "snakes = [\"{SNAKE_A}\"]\n{phase}\nsteady = \"1/s\"\nsnakes = \"nope\""
+            ))
+            .is_err()
+        );
+        // Typo'd pattern key
+        assert!(toml::from_str::<Scenario>(&format!("{phase}\nstedy = \"1/s\"")).is_err());
+    }
+
+    #[test]
+    fn test_thresholds_check() {
+        let thresholds = Thresholds {
+            min_success_rate: Some(99.0),
+            max_create_p99_ms: Some(200.0),
+            max_stuck_running: Some(0),
+            ..Default::default()
+        };
+        let creation = StatsSnapshot {
+            total_games: 100,
+            successful: 100,
+            failed: 0,
+            failures_by_category: FailureCategory::ALL.map(|c| (c, 0)),
+            in_flight: 0,
+            waiting: 0,
+            queued: 0,
+            dropped: 0,
+            retries_rate_limited: 0,
+            retries_unavailable: 0,
+            elapsed: Duration::from_secs(10),
+            rate: 10.0,
+            success_rate: 100.0,
+            avg_latency_ms: 50.0,
+            p50_latency_ms: 40.0,
+            p95_latency_ms: 150.0,
+            p99_latency_ms: 250.0,
+            spectators_active: 0,
+            spectator_connections: 0,
+            spectator_failures: 0,
+            spectator_frames: 0,
+            spectator_first_frame_p95_ms: 0.0,
+        };
+
+        let results = thresholds.check(&creation, None);
+        let passed: Vec<_> = results.iter().map(|r| (r.name, r.passed)).collect();
+        assert_eq!(
+            passed,
+            vec![
+                ("success rate %", true),
+                ("create p99 ms", false),
+                // Without a completion report there's nothing to pass with
+                ("stuck running", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_assertions() {
+        let assertions =
+            parse_assertions("success_rate>=99, p95_completion_ms<=30000,stuck==0").unwrap();
+        assert_eq!(
+            assertions,
+            vec![
+                Assertion {
+                    metric: AssertMetric::SuccessRate,
+                    op: AssertOp::Ge,
+                    limit: 99.0,
+                },
+                Assertion {
+                    metric: AssertMetric::P95CompletionMs,
+                    op: AssertOp::Le,
+                    limit: 30000.0,
+                },
+                Assertion {
+                    metric: AssertMetric::Stuck,
+                    op: AssertOp::Eq,
+                    limit: 0.0,
+                },
+            ]
+        );
+        assert_eq!(parse_assertions("failed<1").unwrap()[0].op, AssertOp::Lt);
+
+        assert!(parse_assertions("stuck=0").is_err());
+        assert!(parse_assertions("stuk==0").is_err());
+        assert!(parse_assertions("stuck==none").is_err());
+        assert!(parse_assertions("").is_err());
+    }
+
+    #[test]
+    fn test_assertion_check() {
+        let (conn, run_id) = new_in_memory_db();
+        CompletionDb::record_game_created_sync(
+            &conn,
+            run_id,
+            Uuid::new_v4(),
+            Duration::ZERO,
+            &default_variant(),
+        )
+        .unwrap();
+        let report = CompletionDb::generate_report_sync(&conn, run_id, 5).unwrap();
+        let creation = Stats::new().snapshot();
+
+        let assertions =
+            parse_assertions("stuck==0,not_started<=0,p95_completion_ms<=30000").unwrap();
+        let passed: Vec<_> = assertions
+            .iter()
+            .map(|a| (a.metric.as_str(), a.check(&creation, Some(&report)).passed))
+            .collect();
+        assert_eq!(
+            passed,
+            vec![
+                ("stuck", true),
+                // The one game never started
+                ("not_started", false),
+                // Nothing finished, so there's no completion time to compare
+                ("p95_completion_ms", false),
+            ]
+        );
+        assert!(!assertions[0].check(&creation, None).passed);
+    }
+}
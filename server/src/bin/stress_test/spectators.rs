@@ -0,0 +1,184 @@
+//! WebSocket spectators that watch created games the way the board viewer does.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::stats::{LatencyHistogram, Stats};
+
+/// How many of the most recently created games spectators pick from
+pub const RECENT_GAMES: usize = 256;
+
+#[derive(Default)]
+pub struct SpectatorStats {
+    pub active: AtomicU64,
+    pub connections: AtomicU64,
+    pub failures: AtomicU64,
+    pub frames: AtomicU64,
+    /// Connect-to-first-frame times
+    pub first_frame: Mutex<LatencyHistogram>,
+}
+
+/// Decrements the active spectator count when a connection ends, however it ends
+struct ActiveSpectator<'a>(&'a AtomicU64);
+
+impl Drop for ActiveSpectator<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The board viewer's WebSocket base for an API base URL
+fn websocket_base_url(base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        base_url.to_string()
+    }
+}
+
+/// Watch one game's event stream until it ends, counting frames
+async fn spectate_game(
+    ws_base: &str,
+    game_id: Uuid,
+    stats: &SpectatorStats,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    use futures::StreamExt as _;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let start = Instant::now();
+    let (mut socket, _) =
+        tokio_tungstenite::connect_async(format!("{}/games/{}/events", ws_base, game_id)).await?;
+    stats.connections.fetch_add(1, Ordering::Relaxed);
+    stats.active.fetch_add(1, Ordering::Relaxed);
+    let _active = ActiveSpectator(&stats.active);
+
+    let mut seen_frame = false;
+    while let Some(message) = socket.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let message_type = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|v| v["Type"].as_str().map(str::to_string));
+        match message_type.as_deref() {
+            Some("frame") => {
+                if !seen_frame {
+                    seen_frame = true;
+                    let elapsed = start.elapsed().as_micros() as u64;
+                    stats.first_frame.lock().unwrap().record(elapsed);
+                }
+                stats.frames.fetch_add(1, Ordering::Relaxed);
+            }
+            Some("game_end") => break,
+            Some("error") => {
+                tracing::warn!(game_id = %game_id, message = %text, "spectator stream error");
+                stats.failures.fetch_add(1, Ordering::Relaxed);
+                break;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// One spectator: watch a recently created game to the end, then another,
+/// until cancelled
+pub async fn spectator_task(base_url: String, stats: Arc<Stats>, cancel: CancellationToken) {
+    let ws_base = websocket_base_url(&base_url);
+    loop {
+        let Some(game_id) = stats.recent_game(rand::random()) else {
+            // Nothing created yet
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = tokio::time::sleep(Duration::from_millis(500)) => continue,
+            }
+        };
+
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            result = spectate_game(&ws_base, game_id, &stats.spectators) => {
+                if let Err(e) = result {
+                    stats.spectators.failures.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(game_id = %game_id, error = %e, "spectator connection failed");
+                    // Back off so a down server isn't hammered with reconnects
+                    tokio::select! {
+                        _ = cancel.cancelled() => break,
+                        _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_websocket_base_url() {
+        assert_eq!(
+            websocket_base_url("http://localhost:3000"),
+            "ws://localhost:3000"
+        );
+        assert_eq!(
+            websocket_base_url("https://arena.example/"),
+            "wss://arena.example"
+        );
+    }
+
+    #[test]
+    fn test_recent_games_are_capped() {
+        let stats = Stats::new();
+        assert_eq!(stats.recent_game(0), None);
+        let games: Vec<Uuid> = (0..RECENT_GAMES + 10).map(|_| Uuid::new_v4()).collect();
+        for game in &games {
+            stats.record_success(*game, Duration::from_millis(1));
+        }
+        assert_eq!(stats.recent_games.lock().unwrap().len(), RECENT_GAMES);
+        // The oldest ten fell off
+        assert_eq!(stats.recent_game(0), Some(games[10]));
+        assert_eq!(stats.recent_game(RECENT_GAMES - 1), games.last().copied());
+    }
+
+    #[tokio::test]
+    async fn test_spectator_counts_frames_until_game_end() {
+        use axum::extract::ws::{Message, WebSocketUpgrade};
+
+        let app = axum::Router::new().route(
+            "/games/{id}/events",
+            axum::routing::get(|ws: WebSocketUpgrade| async {
+                ws.on_upgrade(|mut socket| async move {
+                    for message in [
+                        r#"{"Type":"frame","Data":{}}"#,
+                        r#"{"Type":"frame","Data":{}}"#,
+                        r#"{"Type":"game_end","Data":{}}"#,
+                    ] {
+                        let _ = socket.send(Message::Text(message.into())).await;
+                    }
+                })
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let stats = SpectatorStats::default();
+        spectate_game(&format!("ws://{}", addr), Uuid::new_v4(), &stats)
+            .await
+            .unwrap();
+        assert_eq!(stats.connections.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.frames.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.active.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.first_frame.lock().unwrap().count, 1);
+    }
+}
@@ -0,0 +1,703 @@
+//! Live run statistics: counters, the latency histogram, periodic output and the
+//! Prometheus endpoint.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Context as _;
+use reqwest::StatusCode;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::load::FailureCategory;
+use crate::spectators::{RECENT_GAMES, SpectatorStats};
+
+pub struct Stats {
+    total_games: AtomicU64,
+    pub successful: AtomicU64,
+    failed: AtomicU64,
+    start_time: Instant,
+    latencies: Mutex<LatencyHistogram>,
+    /// Indexed like [`FailureCategory::ALL`]
+    failures: [AtomicU64; FailureCategory::ALL.len()],
+    /// Create Game requests currently outstanding
+    pub in_flight: AtomicU64,
+    /// Creations currently waiting for an in-flight slot
+    pub waiting: AtomicU64,
+    /// Creations that had to wait for an in-flight slot
+    pub queued: AtomicU64,
+    /// Creations skipped because the in-flight cap was full
+    pub dropped: AtomicU64,
+    pub retries_rate_limited: AtomicU64,
+    pub retries_unavailable: AtomicU64,
+    /// Newest last, capped at [`RECENT_GAMES`]
+    pub recent_games: Mutex<VecDeque<Uuid>>,
+    pub spectators: SpectatorStats,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self {
+            total_games: AtomicU64::new(0),
+            successful: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            start_time: Instant::now(),
+            latencies: Mutex::new(LatencyHistogram::default()),
+            failures: Default::default(),
+            in_flight: AtomicU64::new(0),
+            waiting: AtomicU64::new(0),
+            queued: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            retries_rate_limited: AtomicU64::new(0),
+            retries_unavailable: AtomicU64::new(0),
+            recent_games: Mutex::new(VecDeque::with_capacity(RECENT_GAMES)),
+            spectators: SpectatorStats::default(),
+        }
+    }
+
+    pub fn record_success(&self, game_id: Uuid, latency: Duration) {
+        self.total_games.fetch_add(1, Ordering::Relaxed);
+        self.successful.fetch_add(1, Ordering::Relaxed);
+        let latency_us = latency.as_micros() as u64;
+        self.latencies.lock().unwrap().record(latency_us);
+
+        let mut recent = self.recent_games.lock().unwrap();
+        if recent.len() == RECENT_GAMES {
+            recent.pop_front();
+        }
+        recent.push_back(game_id);
+    }
+
+    /// One of the recently created games, chosen by `roll`
+    pub fn recent_game(&self, roll: usize) -> Option<Uuid> {
+        let recent = self.recent_games.lock().unwrap();
+        if recent.is_empty() {
+            None
+        } else {
+            Some(recent[roll % recent.len()])
+        }
+    }
+
+    pub fn record_failure(&self, category: FailureCategory) {
+        self.total_games.fetch_add(1, Ordering::Relaxed);
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        self.failures[category as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_retry(&self, status: StatusCode) {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            self.retries_rate_limited.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.retries_unavailable.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn failures_by_category(&self) -> [(FailureCategory, u64); FailureCategory::ALL.len()] {
+        FailureCategory::ALL.map(|c| (c, self.failures[c as usize].load(Ordering::Relaxed)))
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let total = self.total_games.load(Ordering::Relaxed);
+        let successful = self.successful.load(Ordering::Relaxed);
+        let failed = self.failed.load(Ordering::Relaxed);
+        let elapsed = self.start_time.elapsed();
+
+        let (avg_latency, p50, p95, p99) = self.latencies.lock().unwrap().percentiles_ms();
+
+        StatsSnapshot {
+            total_games: total,
+            successful,
+            failed,
+            failures_by_category: self.failures_by_category(),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            waiting: self.waiting.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            retries_rate_limited: self.retries_rate_limited.load(Ordering::Relaxed),
+            retries_unavailable: self.retries_unavailable.load(Ordering::Relaxed),
+            elapsed,
+            rate: if elapsed.as_secs_f64() > 0.0 {
+                total as f64 / elapsed.as_secs_f64()
+            } else {
+                0.0
+            },
+            success_rate: if total > 0 {
+                successful as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            },
+            avg_latency_ms: avg_latency,
+            p50_latency_ms: p50,
+            p95_latency_ms: p95,
+            p99_latency_ms: p99,
+            spectators_active: self.spectators.active.load(Ordering::Relaxed),
+            spectator_connections: self.spectators.connections.load(Ordering::Relaxed),
+            spectator_failures: self.spectators.failures.load(Ordering::Relaxed),
+            spectator_frames: self.spectators.frames.load(Ordering::Relaxed),
+            spectator_first_frame_p95_ms: self
+                .spectators
+                .first_frame
+                .lock()
+                .unwrap()
+                .percentiles_ms()
+                .2,
+        }
+    }
+}
+
+pub struct StatsSnapshot {
+    pub total_games: u64,
+    pub successful: u64,
+    pub failed: u64,
+    pub failures_by_category: [(FailureCategory, u64); FailureCategory::ALL.len()],
+    pub in_flight: u64,
+    pub waiting: u64,
+    pub queued: u64,
+    pub dropped: u64,
+    pub retries_rate_limited: u64,
+    pub retries_unavailable: u64,
+    pub elapsed: Duration,
+    pub rate: f64,
+    pub success_rate: f64,
+    pub avg_latency_ms: f64,
+    pub p50_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub spectators_active: u64,
+    pub spectator_connections: u64,
+    pub spectator_failures: u64,
+    pub spectator_frames: u64,
+    pub spectator_first_frame_p95_ms: f64,
+}
+
+impl StatsSnapshot {
+    pub fn failures_of(&self, category: FailureCategory) -> u64 {
+        self.failures_by_category[category as usize].1
+    }
+
+    /// Non-zero failure counts, e.g. "timeout: 3 | 429: 12"
+    pub fn failure_breakdown(&self) -> String {
+        self.failures_by_category
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(category, count)| format!("{}: {}", category.as_str(), count))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+/// Linear buckets per power of two; values below this are counted exactly
+const SUB_BUCKETS: u64 = 128;
+const SUB_BUCKET_BITS: u32 = 7;
+
+/// Log-linear histogram of latencies in microseconds, laid out like an HDR
+/// histogram: exact below [`SUB_BUCKETS`], then [`SUB_BUCKETS`] linear
+/// buckets per power of two, so a percentile is within 1% of the true value.
+/// Buckets are stored sparsely, so memory tracks the spread of latencies
+/// rather than the number of games, and a week-long soak stays flat.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    buckets: BTreeMap<u32, u64>,
+    pub count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    fn bucket_index(value: u64) -> u32 {
+        if value < SUB_BUCKETS {
+            return value as u32;
+        }
+        let shift = 63 - value.leading_zeros() - SUB_BUCKET_BITS;
+        let sub = (value >> shift) - SUB_BUCKETS;
+        (shift + 1) * SUB_BUCKETS as u32 + sub as u32
+    }
+
+    /// Lowest and highest value that land in a bucket
+    fn bucket_range(index: u32) -> (u64, u64) {
+        if (index as u64) < SUB_BUCKETS {
+            return (index as u64, index as u64);
+        }
+        let shift = index / SUB_BUCKETS as u32 - 1;
+        let low = (index as u64 % SUB_BUCKETS + SUB_BUCKETS) << shift;
+        (low, low + ((1 << shift) - 1))
+    }
+
+    pub fn record(&mut self, value_us: u64) {
+        *self
+            .buckets
+            .entry(Self::bucket_index(value_us))
+            .or_default() += 1;
+        self.min = if self.count == 0 {
+            value_us
+        } else {
+            self.min.min(value_us)
+        };
+        self.max = self.max.max(value_us);
+        self.count += 1;
+        self.sum = self.sum.saturating_add(value_us);
+    }
+
+    /// Value at the `pct`th percentile, ranked like a sorted sample
+    /// (`sorted[len * pct / 100]`) and reported as the middle of its bucket
+    fn percentile(&self, pct: u64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let rank = (self.count * pct / 100).min(self.count - 1);
+        let mut seen = 0;
+        for (&index, &count) in &self.buckets {
+            seen += count;
+            if seen > rank {
+                let (low, high) = Self::bucket_range(index);
+                return (low + (high - low) / 2).clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+
+    /// (avg, p50, p95, p99) in milliseconds
+    fn percentiles_ms(&self) -> (f64, f64, f64, f64) {
+        if self.count == 0 {
+            return (0.0, 0.0, 0.0, 0.0);
+        }
+        let ms = |us: u64| us as f64 / 1000.0;
+        (
+            self.sum as f64 / self.count as f64 / 1000.0,
+            ms(self.percentile(50)),
+            ms(self.percentile(95)),
+            ms(self.percentile(99)),
+        )
+    }
+}
+
+// ============================================================================
+
+// Stats Output
+
+// ============================================================================
+
+/// `label` prefixes each line, to tell targets apart in multi-target runs
+pub async fn stats_output_task(
+    stats: Arc<Stats>,
+    label: String,
+    interval_secs: u64,
+    cancel: CancellationToken,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = interval.tick() => {
+                let snapshot = stats.snapshot();
+
+                // Terminal output
+                let elapsed = format_duration(snapshot.elapsed);
+                println!(
+                    "{}[{}] Games: {} | Rate: {:.1}/s | Success: {:.1}% | Avg: {:.0}ms | p50: {:.0}ms | p95: {:.0}ms | p99: {:.0}ms",
+                    label,
+                    elapsed,
+                    snapshot.total_games,
+                    snapshot.rate,
+                    snapshot.success_rate,
+                    snapshot.avg_latency_ms,
+                    snapshot.p50_latency_ms,
+                    snapshot.p95_latency_ms,
+                    snapshot.p99_latency_ms,
+                );
+                if snapshot.failed > 0 {
+                    println!("           Errors: {}", snapshot.failure_breakdown());
+                }
+                if snapshot.retries_rate_limited > 0 || snapshot.retries_unavailable > 0 {
+                    println!(
+                        "           Retries: 429: {} | 503: {}",
+                        snapshot.retries_rate_limited, snapshot.retries_unavailable,
+                    );
+                }
+                if snapshot.in_flight > 0 || snapshot.waiting > 0 || snapshot.queued > 0 || snapshot.dropped > 0 {
+                    println!(
+                        "           In flight: {} | Waiting: {} | Queued: {} | Dropped: {}",
+                        snapshot.in_flight, snapshot.waiting, snapshot.queued, snapshot.dropped,
+                    );
+                }
+                if snapshot.spectator_connections > 0 || snapshot.spectator_failures > 0 {
+                    println!(
+                        "           Spectators: {} active | Connections: {} | Failed: {} | Frames: {} | First frame p95: {:.0}ms",
+                        snapshot.spectators_active,
+                        snapshot.spectator_connections,
+                        snapshot.spectator_failures,
+                        snapshot.spectator_frames,
+                        snapshot.spectator_first_frame_p95_ms,
+                    );
+                }
+
+                // Structured tracing event for Eyes
+                tracing::info!(
+                    total_games = snapshot.total_games,
+                    successful = snapshot.successful,
+                    failed = snapshot.failed,
+                    failed_timeout = snapshot.failures_of(FailureCategory::Timeout),
+                    failed_rate_limited = snapshot.failures_of(FailureCategory::RateLimited),
+                    failed_server_error = snapshot.failures_of(FailureCategory::ServerError),
+                    failed_client_error = snapshot.failures_of(FailureCategory::ClientError),
+                    failed_connection = snapshot.failures_of(FailureCategory::Connection),
+                    failed_parse = snapshot.failures_of(FailureCategory::Parse),
+                    target = %label.trim(),
+                    in_flight = snapshot.in_flight,
+                    waiting = snapshot.waiting,
+                    queued = snapshot.queued,
+                    dropped = snapshot.dropped,
+                    retries_rate_limited = snapshot.retries_rate_limited,
+                    retries_unavailable = snapshot.retries_unavailable,
+                    rate = snapshot.rate,
+                    success_rate = snapshot.success_rate,
+                    avg_latency_ms = snapshot.avg_latency_ms,
+                    p50_latency_ms = snapshot.p50_latency_ms,
+                    p95_latency_ms = snapshot.p95_latency_ms,
+                    p99_latency_ms = snapshot.p99_latency_ms,
+                    spectators_active = snapshot.spectators_active,
+                    spectator_connections = snapshot.spectator_connections,
+                    spectator_failures = snapshot.spectator_failures,
+                    spectator_frames = snapshot.spectator_frames,
+                    "stress_test_stats"
+                );
+            }
+        }
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let total_secs = d.as_secs();
+    let hours = total_secs / 3600;
+    let mins = (total_secs % 3600) / 60;
+    let secs = total_secs % 60;
+    format!("{:02}:{:02}:{:02}", hours, mins, secs)
+}
+
+// ============================================================================
+
+// Prometheus Endpoint
+
+// ============================================================================
+
+/// Render the run's counters in the Prometheus text exposition format
+fn render_prometheus(stats: &Stats, run_id: Uuid, run_desc: &str) -> String {
+    use std::fmt::Write as _;
+
+    fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {kind}");
+    }
+
+    let mut out = String::new();
+
+    header(
+        &mut out,
+        "stress_test_run_info",
+        "gauge",
+        "The run being measured",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_run_info{{run_id=\"{}\",run=\"{}\"}} 1",
+        run_id,
+        run_desc.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+
+    header(
+        &mut out,
+        "stress_test_games_created_total",
+        "counter",
+        "Create Game API calls by outcome",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_games_created_total{{result=\"success\"}} {}",
+        stats.successful.load(Ordering::Relaxed)
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_games_created_total{{result=\"failure\"}} {}",
+        stats.failed.load(Ordering::Relaxed)
+    );
+
+    header(
+        &mut out,
+        "stress_test_create_failures_total",
+        "counter",
+        "Failed Create Game API calls by category",
+    );
+    for (category, count) in stats.failures_by_category() {
+        let _ = writeln!(
+            out,
+            "stress_test_create_failures_total{{category=\"{}\"}} {}",
+            category.as_str(),
+            count
+        );
+    }
+
+    header(
+        &mut out,
+        "stress_test_create_latency_seconds",
+        "summary",
+        "Create Game API latency of successful calls",
+    );
+    {
+        let latencies = stats.latencies.lock().unwrap();
+        for quantile in [50, 95, 99] {
+            let _ = writeln!(
+                out,
+                "stress_test_create_latency_seconds{{quantile=\"0.{}\"}} {}",
+                quantile,
+                latencies.percentile(quantile) as f64 / 1_000_000.0
+            );
+        }
+        let _ = writeln!(
+            out,
+            "stress_test_create_latency_seconds_sum {}",
+            latencies.sum as f64 / 1_000_000.0
+        );
+        let _ = writeln!(
+            out,
+            "stress_test_create_latency_seconds_count {}",
+            latencies.count
+        );
+    }
+
+    header(
+        &mut out,
+        "stress_test_create_retries_total",
+        "counter",
+        "Create Game retries by the status that triggered them",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_create_retries_total{{status=\"429\"}} {}",
+        stats.retries_rate_limited.load(Ordering::Relaxed)
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_create_retries_total{{status=\"503\"}} {}",
+        stats.retries_unavailable.load(Ordering::Relaxed)
+    );
+
+    header(
+        &mut out,
+        "stress_test_creates_in_flight",
+        "gauge",
+        "Create Game requests currently outstanding",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_creates_in_flight {}",
+        stats.in_flight.load(Ordering::Relaxed)
+    );
+    header(
+        &mut out,
+        "stress_test_creates_waiting",
+        "gauge",
+        "Creations waiting for a --max-in-flight slot",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_creates_waiting {}",
+        stats.waiting.load(Ordering::Relaxed)
+    );
+    for (name, help, counter) in [
+        (
+            "stress_test_creates_queued_total",
+            "Creations that waited for a --max-in-flight slot",
+            &stats.queued,
+        ),
+        (
+            "stress_test_creates_dropped_total",
+            "Creations skipped because --max-in-flight was reached",
+            &stats.dropped,
+        ),
+    ] {
+        header(&mut out, name, "counter", help);
+        let _ = writeln!(out, "{} {}", name, counter.load(Ordering::Relaxed));
+    }
+
+    let spectators = &stats.spectators;
+    header(
+        &mut out,
+        "stress_test_spectators_active",
+        "gauge",
+        "Spectator WebSocket connections currently open",
+    );
+    let _ = writeln!(
+        out,
+        "stress_test_spectators_active {}",
+        spectators.active.load(Ordering::Relaxed)
+    );
+    for (name, help, counter) in [
+        (
+            "stress_test_spectator_connections_total",
+            "Spectator WebSocket connections opened",
+            &spectators.connections,
+        ),
+        (
+            "stress_test_spectator_failures_total",
+            "Spectator connections that failed or reported an error",
+            &spectators.failures,
+        ),
+        (
+            "stress_test_spectator_frames_total",
+            "Frames received by spectators",
+            &spectators.frames,
+        ),
+    ] {
+        header(&mut out, name, "counter", help);
+        let _ = writeln!(out, "{} {}", name, counter.load(Ordering::Relaxed));
+    }
+
+    out
+}
+
+/// Serve `/metrics` on `bind` for the rest of the process, returning the
+/// bound address
+pub async fn start_metrics_server(
+    bind: &str,
+    stats: Arc<Stats>,
+    run_id: Uuid,
+    run_desc: String,
+) -> color_eyre::Result<std::net::SocketAddr> {
+    let run_desc = Arc::new(run_desc);
+    let app = axum::Router::new().route(
+        "/metrics",
+        axum::routing::get(move || {
+            let stats = stats.clone();
+            let run_desc = run_desc.clone();
+            async move {
+                (
+                    [(
+                        axum::http::header::CONTENT_TYPE,
+                        "text/plain; version=0.0.4",
+                    )],
+                    render_prometheus(&stats, run_id, &run_desc),
+                )
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind(bind)
+        .await
+        .wrap_err_with(|| format!("Failed to bind metrics server to {}", bind))?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::error!(error = %e, "metrics server stopped");
+        }
+    });
+    Ok(addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::load::create_http_client;
+
+    #[test]
+    fn test_latency_histogram_empty() {
+        let (avg, p50, p95, p99) = LatencyHistogram::default().percentiles_ms();
+        assert_eq!(avg, 0.0);
+        assert_eq!(p50, 0.0);
+        assert_eq!(p95, 0.0);
+        assert_eq!(p99, 0.0);
+    }
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        // 100 values from 1000 to 100000 microseconds (1ms to 100ms)
+        let mut histogram = LatencyHistogram::default();
+        for i in 1..=100 {
+            histogram.record(i * 1000);
+        }
+        let (avg, p50, p95, p99) = histogram.percentiles_ms();
+
+        // Average of 1..=100 is 50.5, so in ms: 50.5
+        assert!((avg - 50.5).abs() < 0.1);
+
+        // For 100 elements: len*50/100 = 50, sorted[50] = 51ms
+        // So p50 is around 51ms (integer division floors)
+        assert!((p50 - 51.0).abs() < 1.0);
+
+        // p95: len*95/100 = 95, sorted[95] = 96ms
+        assert!((p95 - 96.0).abs() < 1.0);
+
+        // p99: min(len*99/100, len-1) = min(99, 99) = 99, sorted[99] = 100ms
+        assert!((p99 - 100.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets_stay_within_one_percent() {
+        for value in [0, 127, 128, 255, 256, 51_000, 1_234_567, u64::MAX] {
+            let (low, high) = LatencyHistogram::bucket_range(LatencyHistogram::bucket_index(value));
+            assert!(
+                low <= value && value <= high,
+                "{value} not in {low}..={high}"
+            );
+            assert!((high - low) as f64 <= value as f64 / 100.0, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_latency_histogram_memory_is_bounded() {
+        // A million samples evenly spread over 1ms..10s
+        let mut histogram = LatencyHistogram::default();
+        for i in 0..1_000_000 {
+            histogram.record(1_000 + i * 10);
+        }
+        assert_eq!(histogram.count, 1_000_000);
+        assert!(histogram.buckets.len() < 2_000);
+        let p99 = histogram.percentile(99) as f64;
+        assert!((p99 - 9_901_000.0).abs() / 9_901_000.0 < 0.01, "{p99}");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_renders_run_counters() {
+        let stats = Arc::new(Stats::new());
+        stats.record_success(Uuid::new_v4(), Duration::from_millis(20));
+        stats.record_success(Uuid::new_v4(), Duration::from_millis(40));
+        stats.record_failure(FailureCategory::RateLimited);
+        let run_id = Uuid::new_v4();
+
+        let addr = start_metrics_server("127.0.0.1:0", stats, run_id, "steady:1/s".to_string())
+            .await
+            .unwrap();
+        let body = create_http_client()
+            .get(format!("http://{}/metrics", addr))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert!(body.contains(&format!(
+            "stress_test_run_info{{run_id=\"{}\",run=\"steady:1/s\"}} 1\n",
+            run_id
+        )));
+        assert!(body.contains("stress_test_games_created_total{result=\"success\"} 2\n"));
+        assert!(body.contains("stress_test_games_created_total{result=\"failure\"} 1\n"));
+        assert!(body.contains("stress_test_create_failures_total{category=\"429\"} 1\n"));
+        assert!(body.contains("stress_test_create_failures_total{category=\"5xx\"} 0\n"));
+        assert!(body.contains("stress_test_create_latency_seconds_count 2\n"));
+        assert!(body.contains("stress_test_create_latency_seconds_sum 0.06\n"));
+        assert!(body.contains("# TYPE stress_test_spectators_active gauge\n"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_duration(Duration::from_secs(61)), "00:01:01");
+        assert_eq!(format_duration(Duration::from_secs(3661)), "01:01:01");
+        assert_eq!(format_duration(Duration::from_secs(90)), "00:01:30");
+    }
+}
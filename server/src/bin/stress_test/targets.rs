@@ -0,0 +1,144 @@
+//! Multi-target runs: resolving `--url`/`--targets` and the side-by-side summary.
+
+use std::sync::Arc;
+
+use color_eyre::eyre::Context as _;
+
+use crate::db::CompletionDb;
+use crate::load::LoadConfig;
+use crate::report::Report;
+use crate::stats::{Stats, StatsSnapshot};
+
+/// The run's target base URLs: each `--url`, then the `--targets` file's
+pub fn resolve_targets(
+    urls: &[String],
+    targets_file: Option<&str>,
+) -> color_eyre::Result<Vec<String>> {
+    let mut targets = urls.to_vec();
+    if let Some(path) = targets_file {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read targets file {}", path))?;
+        targets.extend(parse_targets(&contents));
+    }
+    if targets.is_empty() {
+        targets.push("http://localhost:3000".to_string());
+    }
+    Ok(targets)
+}
+
+fn parse_targets(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Where a target's HTML report goes: the path as given for a single
+/// target, otherwise numbered per target (`report-1.html`, `report-2.html`)
+pub fn target_report_path(path: &str, index: usize, target_count: usize) -> String {
+    if target_count <= 1 {
+        return path.to_string();
+    }
+    let file_start = path.rfind('/').map_or(0, |i| i + 1);
+    match path[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let dot = file_start + dot;
+            format!("{}-{}{}", &path[..dot], index + 1, &path[dot..])
+        }
+        _ => format!("{}-{}", path, index + 1),
+    }
+}
+
+/// One arena environment a run drives
+pub struct Target {
+    pub url: String,
+    /// Output prefix; empty unless the run has several targets
+    pub label: String,
+    pub stats: Arc<Stats>,
+    pub completion_db: CompletionDb,
+    pub config: LoadConfig,
+}
+
+/// Side-by-side headline numbers for a multi-target run
+pub fn print_target_summary(
+    targets: &[Target],
+    snapshots: &[StatsSnapshot],
+    reports: &[Option<Report>],
+) {
+    fn ms(value: Option<f64>) -> String {
+        value.map_or_else(|| "-".to_string(), |v| format!("{:.0}ms", v))
+    }
+
+    println!();
+    println!("=== Targets ===");
+    println!();
+    println!(
+        "  {:<32} {:>8} {:>9} {:>10} {:>9} {:>6} {:>13} {:>10}",
+        "Target",
+        "Created",
+        "Success",
+        "Create p95",
+        "Finished",
+        "Stuck",
+        "Complete p95",
+        "Games/min"
+    );
+    for ((target, snapshot), report) in targets.iter().zip(snapshots).zip(reports) {
+        println!(
+            "  {:<32} {:>8} {:>8.1}% {:>10} {:>9} {:>6} {:>13} {:>10}",
+            target.url,
+            snapshot.successful,
+            snapshot.success_rate,
+            ms(Some(snapshot.p95_latency_ms)),
+            report
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |r| r.finished.to_string()),
+            report
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |r| r.stuck_running.to_string()),
+            ms(report.as_ref().and_then(|r| r.p95_completion_ms)),
+            report
+                .as_ref()
+                .and_then(|r| r.throughput_per_min)
+                .map_or_else(|| "-".to_string(), |t| format!("{:.1}", t)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let contents =
+            "# blue/green\nhttps://blue.example.com\n\n  https://green.example.com  # canary\n";
+        assert_eq!(
+            parse_targets(contents),
+            vec!["https://blue.example.com", "https://green.example.com"]
+        );
+        assert_eq!(
+            resolve_targets(&[], None).unwrap(),
+            vec!["http://localhost:3000"]
+        );
+    }
+
+    #[test]
+    fn test_target_report_path() {
+        assert_eq!(
+            target_report_path("out/report.html", 0, 1),
+            "out/report.html"
+        );
+        assert_eq!(
+            target_report_path("out/report.html", 0, 2),
+            "out/report-1.html"
+        );
+        assert_eq!(
+            target_report_path("out/report.html", 1, 2),
+            "out/report-2.html"
+        );
+        assert_eq!(target_report_path("out.d/report", 1, 2), "out.d/report-2");
+    }
+}
@@ -0,0 +1,8 @@
+//! Game worker: runs background jobs and cron against the same database as
+//! the web server, without serving HTTP.
+
+use arena::app::{self, Role};
+
+fn main() -> color_eyre::Result<()> {
+    app::main(Role::Worker)
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
@@ -39,6 +41,81 @@ impl<T> TtlCell<T> {
         }
         value
     }
+
+    /// Drop the cached value so the next `get` misses
+    pub fn clear(&self) {
+        let mut slot = self.slot.write().unwrap_or_else(|e| e.into_inner());
+        *slot = None;
+    }
+}
+
+/// Keyed TTL memo holding at most `capacity` values, for per-item reads
+/// that are expensive to repeat in quick succession (e.g. a decoded frame
+/// archive while a replay pages through it). When full, expired entries go
+/// first, then the oldest.
+pub struct TtlMap<K, V> {
+    ttl: Duration,
+    capacity: usize,
+    slots: RwLock<HashMap<K, (Instant, Arc<V>)>>,
+}
+
+impl<K: Eq + Hash + Clone, V> TtlMap<K, V> {
+    /// A zero TTL or capacity disables caching: `get` always misses.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            slots: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn disabled(&self) -> bool {
+        self.ttl.is_zero() || self.capacity == 0
+    }
+
+    pub fn get(&self, key: &K) -> Option<Arc<V>> {
+        if self.disabled() {
+            return None;
+        }
+        let slots = self.slots.read().unwrap_or_else(|e| e.into_inner());
+        match slots.get(key) {
+            Some((stored_at, value)) if stored_at.elapsed() < self.ttl => Some(Arc::clone(value)),
+            _ => None,
+        }
+    }
+
+    pub fn put(&self, key: K, value: V) -> Arc<V> {
+        let value = Arc::new(value);
+        if self.disabled() {
+            return value;
+        }
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        if !slots.contains_key(&key) && slots.len() >= self.capacity {
+            slots.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+            if slots.len() >= self.capacity
+                && let Some(oldest) = slots
+                    .iter()
+                    .min_by_key(|(_, (stored_at, _))| *stored_at)
+                    .map(|(k, _)| k.clone())
+            {
+                slots.remove(&oldest);
+            }
+        }
+        slots.insert(key, (Instant::now(), Arc::clone(&value)));
+        value
+    }
+
+    pub fn remove(&self, key: &K) {
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        slots.remove(key);
+    }
+
+    /// Drop every entry whose key matches, e.g. all pages of one
+    /// leaderboard's standings
+    pub fn remove_where(&self, matches: impl Fn(&K) -> bool) {
+        let mut slots = self.slots.write().unwrap_or_else(|e| e.into_inner());
+        slots.retain(|key, _| !matches(key));
+    }
 }
 
 #[cfg(test)]
@@ -67,4 +144,44 @@ mod tests {
         std::thread::sleep(Duration::from_millis(1));
         assert!(cell.get().is_none());
     }
+
+    #[test]
+    fn map_evicts_oldest_when_full() {
+        let map = TtlMap::new(Duration::from_secs(60), 2);
+        map.put("a", 1);
+        std::thread::sleep(Duration::from_millis(1));
+        map.put("b", 2);
+        map.put("c", 3);
+        assert!(map.get(&"a").is_none());
+        assert_eq!(map.get(&"b").as_deref(), Some(&2));
+        assert_eq!(map.get(&"c").as_deref(), Some(&3));
+
+        map.remove(&"b");
+        assert!(map.get(&"b").is_none());
+    }
+
+    #[test]
+    fn clear_and_remove_where_invalidate() {
+        let cell = TtlCell::new(Duration::from_secs(60));
+        cell.put(7);
+        cell.clear();
+        assert!(cell.get().is_none());
+
+        let map = TtlMap::new(Duration::from_secs(60), 4);
+        map.put(("a", 0), 1);
+        map.put(("a", 1), 2);
+        map.put(("b", 0), 3);
+        map.remove_where(|(name, _)| *name == "a");
+        assert!(map.get(&("a", 0)).is_none());
+        assert!(map.get(&("a", 1)).is_none());
+        assert_eq!(map.get(&("b", 0)).as_deref(), Some(&3));
+    }
+
+    #[test]
+    fn map_expired_value_misses() {
+        let map = TtlMap::new(Duration::from_nanos(1), 4);
+        map.put("a", 1);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(map.get(&"a").is_none());
+    }
 }
@@ -1,6 +1,6 @@
 use maud::{DOCTYPE, Markup, PreEscaped, Render, html};
 
-use crate::{models::user::User, static_assets::asset_url};
+use crate::{i18n::Locale, models::user::User, preview_image, static_assets::asset_url};
 
 /// Resolves the two theme axes before first paint so there is no flash of
 /// the wrong theme. Mirrors the logic in /static/theme.js: html data
@@ -13,13 +13,17 @@ const DEFAULT_DESCRIPTION: &str = "A competitive arena where your code battles o
 
 const GOOGLE_FONTS_HREF: &str = "https://fonts.googleapis.com/css2?family=Bricolage+Grotesque:opsz,wght@12..96,300;12..96,500;12..96,600;12..96,700;12..96,800&family=Instrument+Sans:ital,wght@0,400;0,500;0,600;1,400&family=IBM+Plex+Mono:wght@400;500;600&display=swap";
 
-/// Primary nav links: (label, href). Battlesnakes is only shown logged in
-/// (it lists your own snakes).
-const NAV_LINKS: [(&str, &str, bool); 4] = [
-    ("Leaderboards", "/leaderboards", false),
-    ("Tournaments", "/tournaments", false),
-    ("Battlesnakes", "/battlesnakes", true),
-    ("Customizations", "/customizations", false),
+/// Primary nav links: (label key, href). Battlesnakes and Challenges are
+/// only shown logged in (they list your own snakes and challenges); Snakes
+/// is the public directory of everyone's.
+const NAV_LINKS: [(&str, &str, bool); 7] = [
+    ("nav.leaderboards", "/leaderboards", false),
+    ("nav.games", "/games", false),
+    ("nav.snakes", "/snakes", false),
+    ("nav.tournaments", "/tournaments", false),
+    ("nav.battlesnakes", "/battlesnakes", true),
+    ("nav.challenges", "/challenges", true),
+    ("nav.customizations", "/customizations", false),
 ];
 
 pub struct Page {
@@ -36,6 +40,15 @@ pub struct Page {
     /// Page-specific description for social embeds (OpenGraph/Twitter).
     /// Falls back to a site-wide default when unset.
     pub description: Option<String>,
+    /// Absolute URL of a page-specific social-embed image, drawn at
+    /// `preview_image::WIDTH` x `HEIGHT`. Pages without one get the small
+    /// text-only card.
+    pub image: Option<String>,
+    /// Site-wide maintenance notice, shown above the flash message on every
+    /// page while maintenance mode is on.
+    pub maintenance_message: Option<String>,
+    /// Language the shell (nav, footer) renders in and `<html lang>`
+    pub locale: Locale,
 }
 
 impl Page {
@@ -49,6 +62,9 @@ impl Page {
             current_path: "/".to_string(),
             theater: false,
             description: None,
+            image: None,
+            maintenance_message: None,
+            locale: Locale::default(),
         }
     }
 
@@ -59,6 +75,12 @@ impl Page {
         self
     }
 
+    /// Set a social-embed image (builder style, like `with_description`)
+    pub fn with_image(mut self, url: impl Into<String>) -> Self {
+        self.image = Some(url.into());
+        self
+    }
+
     fn description(&self) -> &str {
         self.description.as_deref().unwrap_or(DEFAULT_DESCRIPTION)
     }
@@ -105,10 +127,10 @@ impl Page {
         html! {
             @for (label, href, authed_only) in NAV_LINKS {
                 @if !authed_only || self.user.is_some() {
-                    a class=[self.is_active(href).then_some("active")] href=(href) { (label) }
+                    a class=[self.is_active(href).then_some("active")] href=(href) { (self.locale.t(label)) }
                 }
             }
-            a href="https://docs.battlesnake.com" { "Docs" }
+            a href="https://docs.battlesnake.com" { (self.locale.t("nav.docs")) }
         }
     }
 
@@ -172,7 +194,7 @@ impl Page {
                 div class="spacer" {}
                 (self.theme_controls())
                 details class="mobile-menu" {
-                    summary aria-label="Menu" {
+                    summary aria-label=(self.locale.t("nav.menu")) {
                         svg viewBox="0 0 18 18" fill="none" stroke="currentColor" stroke-width="1.5" aria-hidden="true" {
                             path d="M2 4.5h14M2 9h14M2 13.5h14" {}
                         }
@@ -180,7 +202,7 @@ impl Page {
                     div class="sheet" {
                         (self.nav_links())
                         @if self.user.is_none() {
-                            a class="cta" href="/auth/github" { "Sign in with GitHub" }
+                            a class="cta" href="/auth/github" { (self.locale.t("nav.sign_in")) }
                         }
                     }
                 }
@@ -192,8 +214,23 @@ impl Page {
                         span { (user.display_name.as_deref().unwrap_or(&user.github_login)) }
                     }
                 } @else {
-                    a class="btn solid" href="/auth/github" { "Sign in with GitHub" }
+                    a class="btn solid" href="/auth/github" { (self.locale.t("nav.sign_in")) }
+                }
+            }
+        }
+    }
+
+    /// Sets the locale cookie and comes back to this page
+    fn locale_picker(&self) -> Markup {
+        html! {
+            form class="locale-picker" action="/locale" method="post" {
+                input type="hidden" name="return_to" value=(self.current_path);
+                select name="locale" aria-label=(self.locale.t("footer.language")) {
+                    @for locale in Locale::ALL {
+                        option value=(locale.tag()) selected[locale == self.locale] { (locale.native_name()) }
+                    }
                 }
+                button type="submit" class="btn sm" { (self.locale.t("footer.change_language")) }
             }
         }
     }
@@ -204,9 +241,10 @@ impl Page {
                 div class="inner" {
                     span { "Battlesnake Arena" }
                     div class="spacer" {}
-                    a href="/conduct" { "Code of Conduct" }
-                    a href="/privacy" { "Privacy Policy" }
-                    a href="/terms" { "Terms of Service" }
+                    a href="/conduct" { (self.locale.t("footer.conduct")) }
+                    a href="/privacy" { (self.locale.t("footer.privacy")) }
+                    a href="/terms" { (self.locale.t("footer.terms")) }
+                    (self.locale_picker())
                 }
             }
         }
@@ -217,7 +255,7 @@ impl Render for Page {
     fn render(&self) -> Markup {
         html! {
             (DOCTYPE)
-            html lang="en"
+            html lang=(self.locale.tag())
                 data-app-theme=[self.initial_theme()]
                 data-theater-page[self.theater]
                 data-authed[self.user.is_some()]
@@ -233,7 +271,14 @@ impl Render for Page {
                     meta property="og:title" content=(self.title);
                     meta property="og:type" content="website";
                     meta property="og:description" content=(self.description());
-                    meta name="twitter:card" content="summary";
+                    @if let Some(image) = &self.image {
+                        meta property="og:image" content=(image);
+                        meta property="og:image:width" content=(preview_image::WIDTH);
+                        meta property="og:image:height" content=(preview_image::HEIGHT);
+                        meta name="twitter:card" content="summary_large_image";
+                    } @else {
+                        meta name="twitter:card" content="summary";
+                    }
                     link rel="preconnect" href="https://fonts.googleapis.com";
                     link rel="preconnect" href="https://fonts.gstatic.com" crossorigin;
                     link href=(GOOGLE_FONTS_HREF) rel="stylesheet";
@@ -246,6 +291,10 @@ impl Render for Page {
                 body {
                     (self.nav())
 
+                    @if let Some(message) = &self.maintenance_message {
+                        div class="maintenance-banner" role="status" { (message) }
+                    }
+
                     @if let Some(flash_message) = &self.flash {
                         div class="flash-message" data-flash-type=[self.flash_type.as_deref()] {
                             (flash_message)
@@ -299,6 +348,21 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn maintenance_banner_renders_only_when_set() {
+        assert!(
+            !test_page()
+                .render()
+                .into_string()
+                .contains("maintenance-banner")
+        );
+
+        let mut page = test_page();
+        page.maintenance_message = Some("Back soon".to_string());
+        let html = page.render().into_string();
+        assert!(html.contains(r#"<div class="maintenance-banner" role="status">Back soon</div>"#));
+    }
+
     #[test]
     fn custom_description_overrides_default() {
         let html = test_page()
@@ -311,6 +375,23 @@ mod tests {
         assert!(!html.contains(DEFAULT_DESCRIPTION));
     }
 
+    #[test]
+    fn image_switches_to_the_large_card() {
+        let html = test_page().render().into_string();
+        assert!(!html.contains("og:image"));
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary">"#));
+
+        let html = test_page()
+            .with_image("https://arena.example/games/1/preview.png")
+            .render()
+            .into_string();
+        assert!(html.contains(
+            r#"<meta property="og:image" content="https://arena.example/games/1/preview.png">"#
+        ));
+        assert!(html.contains(r#"<meta property="og:image:width" content="1200">"#));
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+    }
+
     #[test]
     fn description_is_html_escaped() {
         let html = test_page()
@@ -320,4 +401,14 @@ mod tests {
         assert!(html.contains("&lt;script&gt;&quot;quotes&quot; &amp; snakes&lt;/script&gt;"));
         assert!(!html.contains(r#"<script>"quotes""#));
     }
+
+    #[test]
+    fn shell_renders_in_the_page_locale() {
+        let mut page = test_page();
+        page.locale = Locale::Es;
+        let html = page.render().into_string();
+        assert!(html.contains(r#"<html lang="es""#));
+        assert!(html.contains(">Clasificaciones</a>"));
+        assert!(html.contains(r#"<option value="es" selected>Español</option>"#));
+    }
 }
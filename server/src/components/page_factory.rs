@@ -3,6 +3,7 @@ use maud::Render;
 
 use crate::{
     components::{flash::Flash, page::Page},
+    i18n::Locale,
     models::user::User,
     routes::auth::OptionalUser,
     state::AppState,
@@ -20,6 +21,11 @@ pub struct PageFactory {
     pub user: Option<User>,
     /// The request path, used for nav active states
     pub path: String,
+    /// Banner text while maintenance mode is on
+    pub maintenance_message: Option<String>,
+    /// The request's negotiated locale; handlers copy it out before
+    /// `create_page` to translate their content
+    pub locale: Locale,
 }
 
 impl PageFactory {
@@ -59,6 +65,9 @@ impl PageFactory {
             current_path: self.path,
             theater,
             description: None,
+            image: None,
+            maintenance_message: self.maintenance_message,
+            locale: self.locale,
         }
     }
 }
@@ -73,6 +82,14 @@ impl FromRequestParts<AppState> for PageFactory {
         let path = parts.uri.path().to_string();
         let flash = Flash::from_request_parts(parts, state).await?;
         let OptionalUser(user) = OptionalUser::from_request_parts(parts, state).await?;
-        Ok(Self { flash, user, path })
+        let maintenance_message = state.maintenance().await.banner().map(str::to_string);
+        let locale = Locale::from_request_parts(parts, state).await?;
+        Ok(Self {
+            flash,
+            user,
+            path,
+            maintenance_message,
+            locale,
+        })
     }
 }
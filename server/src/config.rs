@@ -10,7 +10,7 @@
 //! (The one exception is the `import-play` subcommand, which is a separate
 //! one-shot entry point that exits before `AppState` is built.)
 
-use cja::jobs::worker::{DEFAULT_LOCK_TIMEOUT, DEFAULT_MAX_RETRIES};
+use cja::jobs::worker::DEFAULT_MAX_RETRIES;
 
 use crate::email::MailgunConfig;
 use crate::github::auth::GitHubOAuthConfig;
@@ -19,9 +19,15 @@ use crate::github::auth::GitHubOAuthConfig;
 #[derive(Clone, Debug)]
 pub struct JobConfig {
     pub poll_interval_ms: u64,
-    pub lock_timeout_secs: u64,
+    /// Seconds a claimed job stays locked without a heartbeat before
+    /// another worker may take it over (`ARENA_JOB_VISIBILITY_TIMEOUT_SECS`)
+    pub visibility_timeout_secs: u64,
     pub max_retries: i32,
     pub workers: usize,
+    /// Seconds a SIGTERM waits for running games to finish before
+    /// checkpointing them (`ARENA_SHUTDOWN_DRAIN_SECS`). Keep it under the
+    /// platform's kill grace period.
+    pub shutdown_drain_secs: u64,
 }
 
 /// Eyes telemetry (<https://eyes.coreyja.com>) identifiers. Present only
@@ -47,6 +53,9 @@ pub struct AppConfig {
     // Core
     pub database_url: String,
     pub pg_max_connections: u32,
+    /// Read-only replica for heavy, lag-tolerant reads (rankings, game
+    /// history, admin time windows). Those go to the primary when unset.
+    pub database_replica_url: Option<String>,
     /// Public base URL, used to build the board-viewer iframe `engine=` param.
     pub base_url: String,
 
@@ -56,6 +65,9 @@ pub struct AppConfig {
     pub github: Option<GitHubOAuthConfig>,
     pub mailgun: Option<MailgunConfig>,
     pub discord_webhook_url: Option<String>,
+    /// Slack or Discord incoming webhook for operational alerts
+    /// (`ALERT_WEBHOOK_URL`). Unset: the job-error check only logs.
+    pub alert_webhook_url: Option<String>,
 
     // Rate limiting
     /// Max games an account may create within the sliding window (shared
@@ -63,9 +75,55 @@ pub struct AppConfig {
     pub game_creation_rate_limit: i64,
     /// Length of the game-creation sliding window, in minutes.
     pub game_creation_rate_limit_window_minutes: i32,
+    /// Max API write requests (POST/PUT/PATCH/DELETE under `/api`) per
+    /// minute for one Bearer token.
+    pub api_rate_limit_per_token: i64,
+    /// Max API write requests per minute from one client IP, for requests
+    /// without a Bearer token (session cookies, anonymous).
+    pub api_rate_limit_per_ip: i64,
+    /// Date the unversioned `/api/...` aliases of `/api/v1` will be removed,
+    /// advertised in a `Sunset` header. Unset: no header.
+    pub api_unversioned_sunset: Option<chrono::NaiveDate>,
+    /// Browser origins allowed to call the `/api/v1` resources cross-origin
+    /// (`API_CORS_ALLOWED_ORIGINS`, comma-separated). Empty means any origin.
+    /// The board viewer endpoints are always open to any origin.
+    pub api_cors_allowed_origins: Vec<String>,
+    /// Seconds an HTML page may take before it is cut off with a 503
+    /// (`HTML_REQUEST_TIMEOUT_SECS`), so a stuck query fails fast and shows
+    /// up in error reporting instead of hanging the browser.
+    pub html_request_timeout_secs: u64,
     /// Consecutive failed health probes before the sweeper pulls a snake
     /// from leaderboard matchmaking (BS-3534).
     pub snake_health_failure_threshold: i32,
+    /// Minutes a `running` game may go without a new turn before the
+    /// stuck-game watchdog steps in.
+    pub stuck_game_timeout_minutes: i64,
+    /// Times the watchdog re-enqueues a stuck game's runner before giving up
+    /// and ending the game.
+    pub stuck_game_max_requeues: i32,
+    /// Turns the game runner buffers before writing them in one statement
+    /// (`FRAME_FLUSH_TURNS`). 1 writes every turn as it is played.
+    pub frame_flush_turns: usize,
+    /// Longest a buffered turn waits to be written, in milliseconds
+    /// (`FRAME_FLUSH_INTERVAL_MS`). Live viewers read turns from the
+    /// database, so this is how far behind the game they can fall.
+    pub frame_flush_interval_ms: u64,
+    /// Days to keep finished games that no leaderboard, tournament, or saved
+    /// game points at (`GAME_RETENTION_DAYS`). Unset: keep everything.
+    pub game_retention_days: Option<i64>,
+    /// Days after a game finishes before its frames are moved to
+    /// `GCS_BUCKET` (`FRAME_ARCHIVE_AFTER_DAYS`). Unset: frames stay in
+    /// Postgres.
+    pub frame_archive_after_days: Option<i64>,
+    /// Whether the hourly consistency check repairs what it finds
+    /// (`ORPHAN_REPAIR_ENABLED`). Off: it only reports counts.
+    pub orphan_repair_enabled: bool,
+    /// Jobs failing within one alert-check window before the job-error
+    /// check sends an alert.
+    pub alert_job_failures_threshold: i64,
+    /// Ready-to-run job queue depth before the job-error check sends an
+    /// alert.
+    pub alert_queue_depth_threshold: i64,
 
     /// Max transactional emails one recipient address may receive per hour,
     /// across all purposes (BS-7e38). Play's safety net against logic bugs
@@ -78,11 +136,19 @@ pub struct AppConfig {
     /// TTL for the anonymous-homepage feed cache, in seconds. 0 disables
     /// caching (e2e sets this so seeded data shows up immediately).
     pub home_feed_cache_secs: u64,
+    /// TTL for the leaderboards list, leaderboard status, and standings
+    /// caches, in seconds. Rating updates clear a leaderboard's entries in
+    /// the process that made them; other processes catch up within the TTL.
+    /// 0 disables caching.
+    pub leaderboard_cache_secs: u64,
     pub gcp_logging: bool,
     pub gcp_project_id: Option<String>,
     pub rust_log: String,
     /// Eyes telemetry, enabled when `EYES_ORG_ID` + `EYES_APP_ID` are set.
     pub eyes: Option<EyesConfig>,
+    /// OTLP collector for span export (`OTEL_EXPORTER_OTLP_ENDPOINT`, e.g.
+    /// `http://tempo:4318`), with or without GCP logging.
+    pub otlp_endpoint: Option<String>,
 
     pub job: JobConfig,
     pub features: FeatureFlags,
@@ -108,12 +174,42 @@ fn optional_env(name: &str) -> Option<String> {
     non_empty(std::env::var(name).ok())
 }
 
+/// `API_UNVERSIONED_SUNSET` as a `YYYY-MM-DD` date. A malformed value fails
+/// boot rather than quietly dropping the `Sunset` header.
+fn sunset_date(value: Option<String>) -> cja::Result<Option<chrono::NaiveDate>> {
+    value
+        .map(|date| {
+            date.parse().map_err(|e| {
+                cja::color_eyre::eyre::eyre!(
+                    "Invalid API_UNVERSIONED_SUNSET {date:?} (expected YYYY-MM-DD): {e}"
+                )
+            })
+        })
+        .transpose()
+}
+
 /// The empty-string → `None` rule, pure so it's testable without touching
 /// process env.
 fn non_empty(value: Option<String>) -> Option<String> {
     value.filter(|v| !v.is_empty())
 }
 
+/// Split a comma-separated origin list, dropping blanks and trailing
+/// slashes (browsers never send one in `Origin`). A lone `*` means "any",
+/// the same as unset.
+fn origin_list(value: Option<String>) -> Vec<String> {
+    let origins: Vec<String> = value
+        .unwrap_or_default()
+        .split(',')
+        .map(|origin| origin.trim().trim_end_matches('/').to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect();
+    if origins.iter().any(|origin| origin == "*") {
+        return Vec::new();
+    }
+    origins
+}
+
 impl AppConfig {
     /// Read all configuration from the environment. `DATABASE_URL` is the
     /// only hard requirement; everything else has a default or is optional.
@@ -124,6 +220,7 @@ impl AppConfig {
         Ok(Self {
             database_url,
             pg_max_connections: parse_env("ARENA_PG_MAX_CONNECTIONS", 5),
+            database_replica_url: optional_env("DATABASE_REPLICA_URL"),
             base_url: std::env::var("BASE_URL")
                 .unwrap_or_else(|_| "http://localhost:3000".to_string()),
 
@@ -132,6 +229,7 @@ impl AppConfig {
             github: github_config_from_env(),
             mailgun: mailgun_config_from_env(),
             discord_webhook_url: optional_env("DISCORD_WEBHOOK_URL"),
+            alert_webhook_url: optional_env("ALERT_WEBHOOK_URL"),
 
             // Limit 0 is a deliberate "block all game creation" switch; a
             // zero or negative WINDOW, though, would silently disable the
@@ -142,25 +240,45 @@ impl AppConfig {
                 10,
             )
             .max(1),
+            api_rate_limit_per_token: parse_env("API_RATE_LIMIT_PER_TOKEN", 600),
+            api_rate_limit_per_ip: parse_env("API_RATE_LIMIT_PER_IP", 120),
+            api_unversioned_sunset: sunset_date(optional_env("API_UNVERSIONED_SUNSET"))?,
+            api_cors_allowed_origins: origin_list(optional_env("API_CORS_ALLOWED_ORIGINS")),
+            html_request_timeout_secs: parse_env::<u64>("HTML_REQUEST_TIMEOUT_SECS", 15).max(1),
             snake_health_failure_threshold: parse_env("SNAKE_HEALTH_FAILURE_THRESHOLD", 3).max(1),
+            stuck_game_timeout_minutes: parse_env("STUCK_GAME_TIMEOUT_MINUTES", 30).max(1),
+            stuck_game_max_requeues: parse_env("STUCK_GAME_MAX_REQUEUES", 1).max(0),
+            frame_flush_turns: parse_env::<usize>("FRAME_FLUSH_TURNS", 10).max(1),
+            frame_flush_interval_ms: parse_env("FRAME_FLUSH_INTERVAL_MS", 1000),
+            // Zero or negative would purge games the moment they finish
+            game_retention_days: optional_env("GAME_RETENTION_DAYS")
+                .and_then(|days| days.parse().ok())
+                .filter(|days| *days > 0),
+            frame_archive_after_days: optional_env("FRAME_ARCHIVE_AFTER_DAYS")
+                .and_then(|days| days.parse().ok())
+                .filter(|days| *days >= 0),
+            orphan_repair_enabled: parse_env("ORPHAN_REPAIR_ENABLED", false),
+            alert_job_failures_threshold: parse_env("ALERT_JOB_FAILURES_THRESHOLD", 10).max(1),
+            alert_queue_depth_threshold: parse_env("ALERT_QUEUE_DEPTH_THRESHOLD", 1000).max(1),
             email_per_recipient_hourly_limit: parse_env("EMAIL_PER_RECIPIENT_HOURLY_LIMIT", 5)
                 .max(1),
 
             tokio_worker_multiplier: parse_env("ARENA_TOKIO_WORKER_MULTIPLIER", 2),
             home_feed_cache_secs: parse_env("HOME_FEED_CACHE_SECS", 30),
+            leaderboard_cache_secs: parse_env("LEADERBOARD_CACHE_SECS", 15),
             gcp_logging: std::env::var("GCP_LOGGING").is_ok(),
             gcp_project_id: optional_env("GCP_PROJECT_ID"),
             rust_log: std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
             eyes: eyes_config_from_env()?,
+            otlp_endpoint: optional_env("OTEL_EXPORTER_OTLP_ENDPOINT"),
 
             job: JobConfig {
                 poll_interval_ms: parse_env("ARENA_JOB_POLL_INTERVAL_MS", 60_000),
-                lock_timeout_secs: parse_env(
-                    "ARENA_JOB_LOCK_TIMEOUT_SECS",
-                    DEFAULT_LOCK_TIMEOUT.as_secs(),
-                ),
+                visibility_timeout_secs: parse_env::<u64>("ARENA_JOB_VISIBILITY_TIMEOUT_SECS", 60)
+                    .max(3),
                 max_retries: parse_env("ARENA_JOB_MAX_RETRIES", DEFAULT_MAX_RETRIES),
                 workers: parse_env::<usize>("ARENA_JOB_WORKERS", 1).max(1),
+                shutdown_drain_secs: parse_env("ARENA_SHUTDOWN_DRAIN_SECS", 20),
             },
             features: FeatureFlags {
                 server: feature_enabled("SERVER"),
@@ -171,34 +289,54 @@ impl AppConfig {
     }
 }
 
-#[cfg(test)]
+// Also for `AppState::test_from_pool`
+#[cfg(any(test, feature = "test-support"))]
 impl AppConfig {
     /// Inert config for tests: no external services, sensible defaults.
     pub fn test_default() -> Self {
         Self {
             database_url: String::new(),
             pg_max_connections: 5,
+            database_replica_url: None,
             base_url: "http://localhost:3000".to_string(),
             engine_database_url: None,
             gcs_bucket: None,
             github: None,
             mailgun: None,
             discord_webhook_url: None,
+            alert_webhook_url: None,
             game_creation_rate_limit: 20,
             game_creation_rate_limit_window_minutes: 10,
+            api_rate_limit_per_token: 600,
+            api_rate_limit_per_ip: 120,
+            api_unversioned_sunset: None,
+            api_cors_allowed_origins: Vec::new(),
+            html_request_timeout_secs: 15,
             snake_health_failure_threshold: 3,
+            stuck_game_timeout_minutes: 30,
+            stuck_game_max_requeues: 1,
+            frame_flush_turns: 10,
+            frame_flush_interval_ms: 1000,
+            game_retention_days: None,
+            frame_archive_after_days: None,
+            orphan_repair_enabled: false,
+            alert_job_failures_threshold: 10,
+            alert_queue_depth_threshold: 1000,
             email_per_recipient_hourly_limit: 5,
             home_feed_cache_secs: 0,
+            leaderboard_cache_secs: 0,
             tokio_worker_multiplier: 2,
             gcp_logging: false,
             gcp_project_id: None,
             rust_log: "info".to_string(),
             eyes: None,
+            otlp_endpoint: None,
             job: JobConfig {
                 poll_interval_ms: 60_000,
-                lock_timeout_secs: 7200,
+                visibility_timeout_secs: 60,
                 max_retries: 20,
                 workers: 1,
+                shutdown_drain_secs: 20,
             },
             features: FeatureFlags {
                 server: true,
@@ -292,6 +430,18 @@ mod tests {
         assert_eq!(non_empty(Some("x".to_string())), Some("x".to_string()));
     }
 
+    #[test]
+    fn origin_list_splits_and_normalizes() {
+        assert!(origin_list(None).is_empty());
+        assert!(origin_list(Some("*".to_string())).is_empty());
+        assert_eq!(
+            origin_list(Some(
+                " https://dash.example.com/ ,,https://replays.example.org".to_string()
+            )),
+            vec!["https://dash.example.com", "https://replays.example.org"]
+        );
+    }
+
     #[test]
     fn feature_flag_semantics() {
         // Only the exact string "true" disables a feature; everything else
@@ -317,15 +467,28 @@ mod tests {
         assert!(is_enabled_for(None));
     }
 
+    #[test]
+    fn test_sunset_date() {
+        assert_eq!(sunset_date(None).unwrap(), None);
+        assert_eq!(
+            sunset_date(Some("2027-01-31".to_string())).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2027, 1, 31)
+        );
+        assert!(sunset_date(Some("31/01/2027".to_string())).is_err());
+    }
+
     #[test]
     fn test_default_disables_external_services() {
         let c = AppConfig::test_default();
         assert!(c.github.is_none());
         assert!(c.mailgun.is_none());
         assert!(c.discord_webhook_url.is_none());
+        assert!(c.alert_webhook_url.is_none());
         assert!(c.engine_database_url.is_none());
+        assert!(c.database_replica_url.is_none());
         assert!(c.gcs_bucket.is_none());
         assert!(c.gcp_project_id.is_none());
+        assert!(c.otlp_endpoint.is_none());
         assert_eq!(c.job.workers, 1);
         assert!(c.features.server && c.features.jobs && c.features.cron);
     }
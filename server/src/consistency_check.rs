@@ -0,0 +1,261 @@
+//! Hourly check for records a failed follow-up step left behind.
+//!
+//! Creating a game, finishing a leaderboard game, and deleting a leaderboard
+//! entry each kick off work that can fail independently of the write that
+//! triggered it. See [`crate::models::consistency`] for what is counted. The
+//! counts are logged as a `consistency_check` event every pass and shown on
+//! the admin dashboard; with
+//! [`crate::config::AppConfig::orphan_repair_enabled`] the check also repairs
+//! them:
+//! - unrated leaderboard games get a fresh [`LeaderboardRatingUpdateJob`]
+//! - unlinked snakes are pointed back at their snake's entry, if it still has
+//!   one on that leaderboard
+//! - unenqueued games get their [`GameRunnerJob`]
+
+use color_eyre::eyre::Context as _;
+
+use crate::jobs::{GameRunnerJob, LeaderboardRatingUpdateJob};
+use crate::models::{consistency, game};
+use crate::state::AppState;
+
+/// How old a record must be before it counts as orphaned rather than in
+/// flight
+pub const GRACE_PERIOD_MINUTES: i64 = 30;
+
+/// Most records of each kind one pass repairs; the next pass picks up the
+/// rest.
+const REPAIR_BATCH_SIZE: i64 = 500;
+
+pub fn grace_cutoff() -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now() - chrono::Duration::minutes(GRACE_PERIOD_MINUTES)
+}
+
+/// Run one pass. Called from the cron-scheduled
+/// [`crate::jobs::ConsistencyCheckJob`].
+pub async fn run_consistency_check(app_state: &AppState) -> cja::Result<()> {
+    let cutoff = grace_cutoff();
+    let report = consistency::get_report(&app_state.db, cutoff).await?;
+    tracing::info!(
+        event_type = "consistency_check",
+        unrated_leaderboard_games = report.unrated_leaderboard_games,
+        unlinked_entry_snakes = report.unlinked_entry_snakes,
+        unenqueued_games = report.unenqueued_games,
+        repair = app_state.config.orphan_repair_enabled,
+        "Consistency check complete"
+    );
+
+    if !app_state.config.orphan_repair_enabled
+        || report == consistency::ConsistencyReport::default()
+    {
+        return Ok(());
+    }
+
+    for leaderboard_game_id in
+        consistency::find_unrated_leaderboard_games(&app_state.db, cutoff, REPAIR_BATCH_SIZE)
+            .await?
+    {
+        cja::jobs::Job::enqueue(
+            LeaderboardRatingUpdateJob {
+                leaderboard_game_id,
+            },
+            app_state.clone(),
+            format!("Consistency repair for leaderboard game {leaderboard_game_id}"),
+            None,
+        )
+        .await
+        .wrap_err("Failed to enqueue leaderboard rating update")?;
+    }
+
+    let relinked = consistency::relink_entry_snakes(&app_state.db).await?;
+    if relinked > 0 {
+        tracing::warn!(
+            relinked,
+            "Relinked leaderboard game snakes to their entries"
+        );
+    }
+
+    for game_id in
+        consistency::find_unenqueued_games(&app_state.db, cutoff, REPAIR_BATCH_SIZE).await?
+    {
+        game::set_game_enqueued_at(&app_state.db, game_id, chrono::Utc::now()).await?;
+        cja::jobs::Job::enqueue(
+            GameRunnerJob { game_id },
+            app_state.clone(),
+            format!("Consistency repair for game {game_id}"),
+            None,
+        )
+        .await
+        .wrap_err("Failed to enqueue game runner")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{job, leaderboard};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    /// A waiting game created an hour ago with no runner job. Inserted
+    /// directly so it never goes through the create flow's enqueue.
+    async fn insert_unenqueued_game(pool: &PgPool) -> cja::Result<Uuid> {
+        let game_id = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status, created_at)
+             VALUES ('11x11', 'Standard', 'waiting', NOW() - INTERVAL '1 hour')
+             RETURNING game_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(game_id)
+    }
+
+    /// A leaderboard game whose one snake lost its entry link but still has
+    /// an entry on the leaderboard
+    async fn insert_unlinked_snake(pool: &PgPool) -> cja::Result<Uuid> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'snake', 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Season') RETURNING leaderboard_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'running')
+             RETURNING game_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        sqlx::query("INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2)")
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .execute(pool)
+            .await?;
+        leaderboard::create_leaderboard_game(pool, leaderboard_id, game_id).await?;
+        let entry = leaderboard::get_or_create_entry(pool, leaderboard_id, battlesnake_id).await?;
+        Ok(entry.leaderboard_entry_id)
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn check_reports_and_repairs_orphans(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let game_id = insert_unenqueued_game(&pool).await?;
+        let entry_id = insert_unlinked_snake(&pool).await?;
+
+        let expected = consistency::ConsistencyReport {
+            unrated_leaderboard_games: 0,
+            unlinked_entry_snakes: 1,
+            unenqueued_games: 1,
+        };
+        assert_eq!(
+            consistency::get_report(&pool, grace_cutoff()).await?,
+            expected
+        );
+
+        // Report-only by default
+        run_consistency_check(&app_state).await?;
+        assert_eq!(
+            consistency::get_report(&pool, grace_cutoff()).await?,
+            expected
+        );
+
+        let mut config = (*app_state.config).clone();
+        config.orphan_repair_enabled = true;
+        app_state.config = std::sync::Arc::new(config);
+        run_consistency_check(&app_state).await?;
+        assert_eq!(
+            consistency::get_report(&pool, grace_cutoff()).await?,
+            consistency::ConsistencyReport::default()
+        );
+        assert_eq!(
+            job::list_runner_jobs_for_game(&pool, game_id).await?.len(),
+            1
+        );
+        let linked: Option<Uuid> = sqlx::query_scalar(
+            "SELECT leaderboard_entry_id FROM game_battlesnakes WHERE game_id <> $1",
+        )
+        .bind(game_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(linked, Some(entry_id));
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn unrated_games_need_two_entries(pool: PgPool) -> cja::Result<()> {
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Season') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status, updated_at)
+             VALUES ('11x11', 'Standard', 'finished', NOW() - INTERVAL '1 hour')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        leaderboard::create_leaderboard_game(&pool, leaderboard_id, game_id).await?;
+
+        let mut snakes = Vec::new();
+        for (github_id, placement) in [(1, 1), (2, 2)] {
+            let user_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (external_github_id, github_login, github_access_token)
+                 VALUES ($1, $2, 'test-token') RETURNING user_id",
+            )
+            .bind(github_id)
+            .bind(format!("owner{github_id}"))
+            .fetch_one(&pool)
+            .await?;
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'snake', 'http://example.com')
+                 RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id, placement) VALUES ($1, $2, $3)",
+            )
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .bind(placement)
+            .execute(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+        }
+
+        // Only one snake is on the leaderboard, so the rating update would
+        // skip the game; it isn't flagged
+        leaderboard::get_or_create_entry(&pool, leaderboard_id, snakes[0]).await?;
+        assert_eq!(
+            consistency::get_report(&pool, grace_cutoff())
+                .await?
+                .unrated_leaderboard_games,
+            0
+        );
+
+        leaderboard::get_or_create_entry(&pool, leaderboard_id, snakes[1]).await?;
+        assert_eq!(
+            consistency::get_report(&pool, grace_cutoff())
+                .await?
+                .unrated_leaderboard_games,
+            1
+        );
+
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use std::time::Duration;
 
 use cja::cron::{CronRegistry, Worker};
-use tokio_util::sync::CancellationToken;
+use color_eyre::eyre::{Context as _, eyre};
+use sqlx::{Connection as _, PgConnection, PgPool};
 
 use crate::jobs::{
-    GameBackupJob, LeaderboardMatchmakerJob, RateLimitPruneJob, SnakeHealthSweeperJob,
-    StuckMatchSweeperJob,
+    ConsistencyCheckJob, FrameArchiveDiscoveryJob, GameBackupJob, GameRetentionJob,
+    JobAlertCheckJob, LeaderboardMatchmakerJob, MetricSnapshotJob, PartitionMaintenanceJob,
+    RateLimitPruneJob, SnakeHealthSweeperJob, StuckGameWatchdogJob, StuckMatchSweeperJob,
 };
 use crate::state::AppState;
 
@@ -16,6 +18,14 @@ pub const MATCHMAKER_INTERVAL_SECS: u64 = 15 * 60;
 /// a broken snake is pulled from matchmaking after ~90 minutes.
 pub const SNAKE_HEALTH_SWEEP_INTERVAL_SECS: u64 = 30 * 60;
 
+/// Session advisory lock held by the one replica that runs the cron worker.
+/// Distinct from the migration lock in `state.rs`.
+const CRON_LEADER_LOCK_ID: i64 = 0xC0_C0_C0_C0_C0_C0_C0;
+
+/// How often a standby replica retries for cron leadership, and how often the
+/// leader checks its lock connection is still alive
+const LEADER_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 pub(crate) fn cron_registry() -> CronRegistry<AppState> {
     let mut registry = CronRegistry::new();
 
@@ -57,14 +67,141 @@ pub(crate) fn cron_registry() -> CronRegistry<AppState> {
         Duration::from_secs(SNAKE_HEALTH_SWEEP_INTERVAL_SECS),
     );
 
+    // Stuck-game watchdog: runs every 5 minutes, re-enqueues runners for
+    // games that stopped making progress and ends the ones that keep stalling
+    registry.register_job(
+        StuckGameWatchdogJob,
+        Some("Re-enqueue or end games stuck in running"),
+        Duration::from_secs(5 * 60),
+    );
+
+    // Game retention: runs every 6 hours, deletes unlinked finished games
+    // past GAME_RETENTION_DAYS (stress-test runs create a lot of them)
+    registry.register_job(
+        GameRetentionJob,
+        Some("Delete unlinked finished games past retention"),
+        Duration::from_secs(6 * 60 * 60),
+    );
+
+    // Partition maintenance: runs daily, creates the per-turn tables'
+    // monthly partitions a few months ahead
+    registry.register_job(
+        PartitionMaintenanceJob,
+        Some("Create upcoming monthly partitions"),
+        Duration::from_secs(24 * 60 * 60),
+    );
+
+    // Frame archive discovery: runs every hour, enqueues jobs that move old
+    // finished games' frames to GCS (no-op unless FRAME_ARCHIVE_AFTER_DAYS is set)
+    registry.register_job(
+        FrameArchiveDiscoveryJob,
+        Some("Enqueue frame archive jobs for old finished games"),
+        Duration::from_secs(60 * 60),
+    );
+
+    // Consistency check: runs every hour, counts orphaned records and
+    // repairs them when ORPHAN_REPAIR_ENABLED is set
+    registry.register_job(
+        ConsistencyCheckJob,
+        Some("Detect and optionally repair orphaned records"),
+        Duration::from_secs(60 * 60),
+    );
+
+    // Job alerts: runs every 15 minutes, posts to ALERT_WEBHOOK_URL when job
+    // failures or the ready queue cross their thresholds
+    registry.register_job(
+        JobAlertCheckJob,
+        Some("Alert on job failure spikes and queue backlogs"),
+        Duration::from_secs(crate::job_alerts::CHECK_INTERVAL_SECS),
+    );
+
+    // Metric snapshots: every 5 minutes, for /api/v1/admin/stats/history
+    registry.register_job(
+        MetricSnapshotJob,
+        Some("Persist a snapshot of the admin metrics"),
+        Duration::from_secs(5 * 60),
+    );
+
     registry
 }
 
+/// Try to become the cron leader. On success, returns the connection that
+/// holds the advisory lock; it's detached from the pool so the lock lives
+/// exactly as long as this connection, and is released if the process dies.
+async fn try_acquire_leadership(pool: &PgPool) -> cja::Result<Option<PgConnection>> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .wrap_err("Failed to acquire connection for cron leader lock")?
+        .detach();
+    let acquired = sqlx::query_scalar!("SELECT pg_try_advisory_lock($1)", CRON_LEADER_LOCK_ID)
+        .fetch_one(&mut conn)
+        .await
+        .wrap_err("Failed to try cron leader lock")?
+        .unwrap_or(false);
+
+    if acquired {
+        Ok(Some(conn))
+    } else {
+        let _ = conn.close().await;
+        Ok(None)
+    }
+}
+
+/// Resolve when the leader's lock connection stops answering, which means
+/// Postgres has dropped the session and with it the lock
+async fn watch_leadership(conn: &mut PgConnection) -> color_eyre::Report {
+    loop {
+        tokio::time::sleep(LEADER_CHECK_INTERVAL).await;
+        if let Err(e) = conn.ping().await {
+            return eyre!("Lost cron leader lock connection: {e}");
+        }
+    }
+}
+
+/// Run the cron worker on exactly one replica. Every replica with cron
+/// enabled calls this; all but one wait on the leader lock, so scheduled
+/// jobs (the matchmaker, the watchdogs) aren't enqueued once per replica.
 pub(crate) async fn run_cron(
     app_state: AppState,
     registry: CronRegistry<AppState>,
 ) -> cja::Result<()> {
-    Ok(Worker::new(app_state, registry)
-        .run(CancellationToken::new())
-        .await?)
+    let token = app_state.shutdown.worker_token();
+
+    let mut lock_conn = loop {
+        if let Some(conn) = try_acquire_leadership(&app_state.db).await? {
+            break conn;
+        }
+        tracing::debug!("Another replica holds the cron leader lock; standing by");
+        tokio::select! {
+            () = tokio::time::sleep(LEADER_CHECK_INTERVAL) => {}
+            () = token.cancelled() => return Ok(()),
+        }
+    };
+    tracing::info!("Acquired cron leader lock; running cron worker");
+
+    // Losing the lock connection is fatal for this task so the process
+    // restarts instead of running cron alongside a new leader
+    tokio::select! {
+        result = Worker::new(app_state, registry).run(token) => Ok(result?),
+        e = watch_leadership(&mut lock_conn) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn only_one_connection_holds_cron_leadership(pool: PgPool) -> cja::Result<()> {
+        let leader = try_acquire_leadership(&pool).await?;
+        assert!(leader.is_some());
+        assert!(try_acquire_leadership(&pool).await?.is_none());
+
+        // Closing the leader's session hands leadership to the next replica
+        leader.unwrap().close().await?;
+        assert!(try_acquire_leadership(&pool).await?.is_some());
+
+        Ok(())
+    }
 }
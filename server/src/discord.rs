@@ -6,12 +6,17 @@
 //! once the URL is set. Callers treat a send as best-effort — a failed
 //! notification must never break the operation that triggered it.
 //!
+//! Users can also set up their own webhook for news about their snakes;
+//! [`crate::jobs::UserWebhookJob`] posts [`Embed`]s there whether or not
+//! the community webhook is configured.
+//!
 //! All payloads include `allowed_mentions: {parse: []}` to suppress
 //! Discord's default mention parsing. This prevents `@everyone`/`@here`
 //! injection from user-controlled identifiers (snake names, play
 //! usernames) that appear in message content.
 
 use color_eyre::eyre::Context as _;
+use serde::{Deserialize, Serialize};
 
 /// Posts community events to a Discord webhook, or silently drops them
 /// when no webhook URL is configured. Cloneable (the client is cheap to
@@ -57,13 +62,37 @@ impl DiscordNotifier {
             return Ok(());
         };
 
+        self.post(
+            url,
+            &serde_json::json!({
+                "content": content,
+                "allowed_mentions": { "parse": [] }
+            }),
+        )
+        .await?;
+
+        tracing::info!(content = content, "Discord notification sent");
+        Ok(())
+    }
+
+    /// Post an embed to a user's own webhook. Works even when the
+    /// community webhook isn't configured. Errors on a non-2xx response.
+    pub async fn send_embed(&self, webhook_url: &str, embed: &Embed) -> cja::Result<()> {
+        self.post(
+            webhook_url,
+            &serde_json::json!({
+                "embeds": [embed.to_json()],
+                "allowed_mentions": { "parse": [] }
+            }),
+        )
+        .await
+    }
+
+    async fn post(&self, url: &str, payload: &serde_json::Value) -> cja::Result<()> {
         let response = self
             .http
             .post(url)
-            .json(&serde_json::json!({
-                "content": content,
-                "allowed_mentions": { "parse": [] }
-            }))
+            .json(payload)
             .send()
             .await
             .wrap_err("Failed to send request to Discord webhook")?;
@@ -76,7 +105,6 @@ impl DiscordNotifier {
             ));
         }
 
-        tracing::info!(content = content, "Discord notification sent");
         Ok(())
     }
 
@@ -132,6 +160,62 @@ fn truncated(name: &str) -> String {
     }
 }
 
+/// Embed accent colors
+pub const COLOR_GOOD: u32 = 0x2ECC71;
+pub const COLOR_BAD: u32 = 0xE74C3C;
+pub const COLOR_NEUTRAL: u32 = 0x95A5A6;
+
+// Discord's embed limits; anything longer fails the whole post with a 400
+const MAX_TITLE_CHARS: usize = 256;
+const MAX_DESCRIPTION_CHARS: usize = 4096;
+const MAX_FIELD_VALUE_CHARS: usize = 1024;
+
+fn clipped(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max - 1).collect();
+        format!("{cut}…")
+    }
+}
+
+/// A Discord embed: a titled card linking somewhere, with inline fields
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Embed {
+    pub title: String,
+    pub description: Option<String>,
+    /// Where clicking the title goes
+    pub url: Option<String>,
+    pub color: u32,
+    /// `(name, value)` pairs, shown inline
+    pub fields: Vec<(String, String)>,
+}
+
+impl Embed {
+    fn to_json(&self) -> serde_json::Value {
+        let mut embed = serde_json::json!({
+            "title": clipped(&self.title, MAX_TITLE_CHARS),
+            "color": self.color,
+            "fields": self
+                .fields
+                .iter()
+                .map(|(name, value)| serde_json::json!({
+                    "name": clipped(name, MAX_TITLE_CHARS),
+                    "value": clipped(value, MAX_FIELD_VALUE_CHARS),
+                    "inline": true,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        if let Some(description) = &self.description {
+            embed["description"] = clipped(description, MAX_DESCRIPTION_CHARS).into();
+        }
+        if let Some(url) = &self.url {
+            embed["url"] = url.clone().into();
+        }
+        embed
+    }
+}
+
 fn user_signup_message(github_login: &str) -> String {
     format!(
         "👋 {} just joined Battlesnake Arena",
@@ -215,6 +299,50 @@ mod tests {
         assert!(err.to_string().contains("429"));
     }
 
+    #[tokio::test]
+    async fn send_embed_posts_to_the_given_webhook_even_when_disabled() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("\"embeds\""))
+            .and(body_string_contains("Hissy finished 1st"))
+            .and(body_string_contains("\"parse\":[]"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let embed = Embed {
+            title: "Hissy finished 1st".to_string(),
+            description: None,
+            url: Some("https://arena.example.com/games/abc".to_string()),
+            color: COLOR_GOOD,
+            fields: vec![("Rank".to_string(), "#3".to_string())],
+        };
+        DiscordNotifier::disabled()
+            .send_embed(&server.uri(), &embed)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn embed_json_clips_to_discord_limits() {
+        let embed = Embed {
+            title: "t".repeat(1000),
+            description: Some("d".repeat(5000)),
+            url: None,
+            color: COLOR_BAD,
+            fields: vec![("Rank".to_string(), "v".repeat(2000))],
+        };
+        let json = embed.to_json();
+        assert_eq!(json["title"].as_str().unwrap().chars().count(), 256);
+        assert_eq!(json["description"].as_str().unwrap().chars().count(), 4096);
+        assert_eq!(
+            json["fields"][0]["value"].as_str().unwrap().chars().count(),
+            1024
+        );
+        assert!(json.get("url").is_none());
+    }
+
     #[test]
     fn user_signup_message_contains_login() {
         let msg = user_signup_message("octocat");
@@ -111,6 +111,39 @@ pub fn claim_verification(to_email: &str, play_username: &str, verify_url: &str)
     }
 }
 
+/// A notification about one of the recipient's snakes (rank milestones,
+/// timeout streaks), for owners who asked for these by email. The same
+/// sentence shows in their in-app inbox; the email adds where to look and
+/// how to turn these off.
+pub fn snake_notification(
+    to_email: &str,
+    subject: &str,
+    message: &str,
+    link_url: &str,
+    preferences_url: &str,
+) -> EmailMessage {
+    let text = format!(
+        "Hi,\n\
+         \n\
+         {message}\n\
+         \n\
+         {link_url}\n\
+         \n\
+         You're getting this because you turned on email for these \
+         notifications. Change that here:\n\
+         \n\
+         {preferences_url}\n\
+         \n\
+         — Battlesnake Arena\n"
+    );
+
+    EmailMessage {
+        to: to_email.to_string(),
+        subject: subject.to_string(),
+        text,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,6 +166,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snake_notification_renders_message_and_opt_out() {
+        let msg = snake_notification(
+            "owner@example.com",
+            "Hissy entered the Standard top 10",
+            "Hissy is now #7 on Standard.",
+            "https://arena.example.com/leaderboards/abc",
+            "https://arena.example.com/settings/notifications",
+        );
+        assert_eq!(msg.to, "owner@example.com");
+        assert_eq!(msg.subject, "Hissy entered the Standard top 10");
+        assert!(msg.text.contains("Hissy is now #7 on Standard."));
+        assert!(
+            msg.text
+                .contains("https://arena.example.com/leaderboards/abc")
+        );
+        assert!(
+            msg.text
+                .contains("https://arena.example.com/settings/notifications")
+        );
+    }
+
     #[test]
     fn claim_verification_renders_link_and_expiry_note() {
         let msg = claim_verification(
@@ -126,6 +126,26 @@ impl Mailer {
         self.spawn_limited_send(pool.clone(), hourly_limit, "claim_verification", message);
     }
 
+    /// Email a snake notification to an owner who opted in to email for
+    /// it. Same fire-and-forget contract as
+    /// [`Mailer::notify_account_claimed`]: the job that raised the
+    /// notification never waits on or fails because of an email.
+    #[allow(clippy::too_many_arguments)]
+    pub fn notify_snake_notification(
+        &self,
+        pool: &sqlx::PgPool,
+        hourly_limit: i64,
+        to_email: &str,
+        subject: &str,
+        message: &str,
+        link_url: &str,
+        preferences_url: &str,
+    ) {
+        let message =
+            messages::snake_notification(to_email, subject, message, link_url, preferences_url);
+        self.spawn_limited_send(pool.clone(), hourly_limit, "snake_notification", message);
+    }
+
     /// The shared fire-and-forget tail: run `send_limited` in a spawned
     /// task, log any failure, never propagate.
     fn spawn_limited_send(
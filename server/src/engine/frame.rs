@@ -1,10 +1,11 @@
 //! Frame data serialization for the Battlesnake board viewer
 //!
 //! This module converts the internal game state to the PascalCase JSON format
-//! expected by the board viewer.
+//! expected by the board viewer, and back again for a game resuming from its
+//! last stored frame.
 
-use rules::Point;
-use serde::Serialize;
+use rules::{EliminationCause, Point, Snake};
+use serde::{Deserialize, Serialize};
 
 use super::EngineGame;
 
@@ -22,7 +23,7 @@ pub struct DeathInfo {
 }
 
 /// Frame data in PascalCase format for the board viewer
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct EngineGameFrame {
     pub turn: i32,
@@ -31,7 +32,7 @@ pub struct EngineGameFrame {
     pub hazards: Vec<FrameCoord>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct FrameSnake {
     #[serde(rename = "ID")]
@@ -53,7 +54,7 @@ pub struct FrameSnake {
     pub eliminated_by: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct FrameCoord {
     #[serde(rename = "X")]
@@ -62,7 +63,7 @@ pub struct FrameCoord {
     pub y: i32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct FrameDeath {
     pub cause: String,
@@ -76,6 +77,80 @@ impl From<Point> for FrameCoord {
     }
 }
 
+impl From<&FrameCoord> for Point {
+    fn from(pos: &FrameCoord) -> Self {
+        Point::new(pos.x, pos.y)
+    }
+}
+
+/// Human-readable label for an elimination cause, used in frame data.
+pub fn elimination_cause_label(cause: &EliminationCause) -> String {
+    match cause {
+        EliminationCause::NotEliminated => String::new(),
+        EliminationCause::OutOfHealth => "out-of-health".to_string(),
+        EliminationCause::OutOfBounds => "wall-collision".to_string(),
+        EliminationCause::SelfCollision => "self-collision".to_string(),
+        EliminationCause::Collision => "snake-collision".to_string(),
+        EliminationCause::HeadToHeadCollision => "head-collision".to_string(),
+        EliminationCause::Hazard => "hazard".to_string(),
+    }
+}
+
+/// Inverse of [`elimination_cause_label`]
+fn elimination_cause_from_label(label: &str) -> EliminationCause {
+    match label {
+        "out-of-health" => EliminationCause::OutOfHealth,
+        "wall-collision" => EliminationCause::OutOfBounds,
+        "self-collision" => EliminationCause::SelfCollision,
+        "snake-collision" => EliminationCause::Collision,
+        "head-collision" => EliminationCause::HeadToHeadCollision,
+        "hazard" => EliminationCause::Hazard,
+        _ => EliminationCause::NotEliminated,
+    }
+}
+
+/// Put a game back in the state a stored frame recorded: the turn, food,
+/// hazards, and every snake's body, health, and death. Returns the deaths in
+/// the order the snakes were eliminated, as [`game_to_frame`] expects them.
+pub fn restore_from_frame(game: &mut EngineGame, frame: &EngineGameFrame) -> Vec<DeathInfo> {
+    game.board.turn = frame.turn;
+    game.board.food = frame.food.iter().map(Point::from).collect();
+    game.board.hazards = frame.hazards.iter().map(Point::from).collect();
+    game.board.snakes = frame
+        .snakes
+        .iter()
+        .map(|s| Snake {
+            id: s.id.clone(),
+            body: s.body.iter().map(Point::from).collect(),
+            health: s.health,
+            eliminated_cause: s
+                .death
+                .as_ref()
+                .map_or(EliminationCause::NotEliminated, |d| {
+                    elimination_cause_from_label(&d.cause)
+                }),
+            eliminated_by: s.eliminated_by.clone(),
+            eliminated_on_turn: s.death.as_ref().map_or(0, |d| d.turn),
+        })
+        .collect();
+
+    let mut deaths: Vec<DeathInfo> = frame
+        .snakes
+        .iter()
+        .filter_map(|s| {
+            s.death.as_ref().map(|d| DeathInfo {
+                snake_id: s.id.clone(),
+                turn: d.turn,
+                cause: d.cause.clone(),
+                eliminated_by: d.eliminated_by.clone(),
+            })
+        })
+        .collect();
+    // Same-turn deaths were recorded in board order, which sort_by_key keeps
+    deaths.sort_by_key(|d| d.turn);
+    deaths
+}
+
 pub struct SnakeCustomizations {
     pub color: String,
     pub head: String,
@@ -420,6 +495,51 @@ mod tests {
         assert_eq!(frame.snakes[0].eliminated_by, "");
     }
 
+    #[test]
+    fn test_restore_from_frame_round_trips() {
+        let mut game = create_test_game();
+        game.board.turn = 12;
+        game.board.food = vec![Point::new(7, 7)];
+        game.board.hazards = vec![Point::new(0, 0)];
+        game.board.snakes.push(Snake {
+            id: "snake-2".to_string(),
+            body: vec![Point::new(3, 3), Point::new(3, 2)],
+            health: 0,
+            eliminated_cause: EliminationCause::HeadToHeadCollision,
+            eliminated_by: "snake-1".to_string(),
+            eliminated_on_turn: 9,
+        });
+        let death_info = vec![DeathInfo {
+            snake_id: "snake-2".to_string(),
+            turn: 9,
+            cause: "head-collision".to_string(),
+            eliminated_by: "snake-1".to_string(),
+        }];
+        let frame = game_to_frame(&game, &death_info, &[], &std::collections::HashMap::new());
+        let stored: EngineGameFrame =
+            serde_json::from_value(serde_json::to_value(&frame).unwrap()).unwrap();
+
+        let mut restored = create_test_game();
+        let deaths = restore_from_frame(&mut restored, &stored);
+
+        assert_eq!(restored.board.turn, 12);
+        assert_eq!(restored.board.food, game.board.food);
+        assert_eq!(restored.board.hazards, game.board.hazards);
+        assert_eq!(restored.board.snakes.len(), 2);
+        assert_eq!(restored.board.snakes[0].body, game.board.snakes[0].body);
+        assert_eq!(
+            restored.board.snakes[0].eliminated_cause,
+            EliminationCause::NotEliminated
+        );
+        let dead = &restored.board.snakes[1];
+        assert_eq!(dead.eliminated_cause, EliminationCause::HeadToHeadCollision);
+        assert_eq!(dead.eliminated_by, "snake-1");
+        assert_eq!(dead.eliminated_on_turn, 9);
+        assert_eq!(deaths.len(), 1);
+        assert_eq!(deaths[0].snake_id, "snake-2");
+        assert_eq!(deaths[0].turn, 9);
+    }
+
     #[test]
     fn test_frame_snake_serialization() {
         let game = create_test_game();
@@ -464,6 +584,7 @@ mod tests {
             direction: rules::Direction::Up,
             latency_ms: Some(42),
             timed_out: false,
+            error: None,
             shout: None,
         }];
 
@@ -488,6 +609,7 @@ mod tests {
             direction: rules::Direction::Up,
             latency_ms: None,
             timed_out: true,
+            error: Some(crate::models::turn::MoveError::Timeout),
             shout: None,
         }];
 
@@ -512,6 +634,7 @@ mod tests {
             direction: rules::Direction::Up,
             latency_ms: Some(100),
             timed_out: false,
+            error: None,
             shout: Some("Hello from move!".to_string()),
         }];
 
@@ -538,6 +661,7 @@ mod tests {
             direction: rules::Direction::Down,
             latency_ms: Some(50),
             timed_out: false,
+            error: None,
             shout: None,
         }];
 
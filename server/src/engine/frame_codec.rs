@@ -0,0 +1,520 @@
+//! Compact binary encoding for stored frames (`turns.frame_bin`).
+//!
+//! A frame as board-viewer JSON spells out every coordinate as
+//! `{"X":3,"Y":7}`, so a long game on a large board stores megabytes of
+//! it. This encoding keeps exactly the same [`EngineGameFrame`] in a
+//! fraction of the space:
+//!
+//! - food and hazards are a bitboard over the smallest grid holding them,
+//!   plus a list for stacked hazards and anything off the grid;
+//! - a snake body is its head followed by one nibble per segment (same
+//!   cell, or one step in a direction), with an escape to an absolute
+//!   point for jumps such as wrapping around the board;
+//! - strings that are UUIDs or `#rrggbb` colors are stored as raw bytes.
+//!
+//! Everything else is LEB128 varints (zigzagged when signed). Food and
+//! hazards decode in row-major order rather than the order they were
+//! stored in; nothing reads meaning into that order. JSON is produced only
+//! at the API boundary, via [`crate::models::turn::Turn::frame_json`].
+//!
+//! The first byte is a format version so the layout can change later.
+
+use color_eyre::eyre::{bail, eyre};
+
+use super::frame::{EngineGameFrame, FrameCoord, FrameDeath, FrameSnake};
+
+const FORMAT_VERSION: u8 = 1;
+
+/// Bitboards cover at most this many cells per side; points past it (or
+/// negative) go in the extras list instead of blowing the bitboard up
+const MAX_BITBOARD_SIDE: i32 = 64;
+
+/// Body segment codes, one nibble each
+const SEGMENT_SAME: u8 = 0;
+const SEGMENT_UP: u8 = 1;
+const SEGMENT_DOWN: u8 = 2;
+const SEGMENT_LEFT: u8 = 3;
+const SEGMENT_RIGHT: u8 = 4;
+/// The segment's absolute point follows the nibbles
+const SEGMENT_JUMP: u8 = 5;
+
+/// String tags
+const STR_RAW: u8 = 0;
+const STR_UUID: u8 = 1;
+const STR_COLOR: u8 = 2;
+
+pub fn encode(frame: &EngineGameFrame) -> Vec<u8> {
+    let mut out = Vec::with_capacity(64 + frame.snakes.len() * 128);
+    out.push(FORMAT_VERSION);
+    put_i64(&mut out, frame.turn.into());
+    put_cells(&mut out, &frame.food);
+    put_cells(&mut out, &frame.hazards);
+    put_u64(&mut out, frame.snakes.len() as u64);
+    for snake in &frame.snakes {
+        put_snake(&mut out, snake);
+    }
+    out
+}
+
+pub fn decode(bytes: &[u8]) -> cja::Result<EngineGameFrame> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let version = reader.byte()?;
+    if version != FORMAT_VERSION {
+        bail!("Unknown frame encoding version {version}");
+    }
+    let turn = reader.i32()?;
+    let food = reader.cells()?;
+    let hazards = reader.cells()?;
+    let snake_count = reader.len()?;
+    let snakes = (0..snake_count)
+        .map(|_| reader.snake())
+        .collect::<cja::Result<_>>()?;
+    if reader.pos != bytes.len() {
+        bail!("Trailing bytes after encoded frame");
+    }
+    Ok(EngineGameFrame {
+        turn,
+        snakes,
+        food,
+        hazards,
+    })
+}
+
+fn put_u64(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_i64(out: &mut Vec<u8>, value: i64) {
+    put_u64(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn put_point(out: &mut Vec<u8>, point: &FrameCoord) {
+    put_i64(out, point.x.into());
+    put_i64(out, point.y.into());
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) {
+    if value.len() == 36
+        && let Ok(uuid) = uuid::Uuid::parse_str(value)
+        && uuid.hyphenated().to_string() == value
+    {
+        out.push(STR_UUID);
+        out.extend_from_slice(uuid.as_bytes());
+    } else if let Some(hex) = value.strip_prefix('#')
+        && hex.len() == 6
+        && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        && let Ok(rgb) = u32::from_str_radix(hex, 16)
+    {
+        out.push(STR_COLOR);
+        out.extend_from_slice(&rgb.to_be_bytes()[1..]);
+    } else {
+        out.push(STR_RAW);
+        put_u64(out, value.len() as u64);
+        out.extend_from_slice(value.as_bytes());
+    }
+}
+
+/// Bitboard of the points inside `[0, MAX_BITBOARD_SIDE)` on both axes,
+/// then the rest (repeats, and points outside) as a list
+fn put_cells(out: &mut Vec<u8>, cells: &[FrameCoord]) {
+    let on_grid = |c: &FrameCoord| {
+        (0..MAX_BITBOARD_SIDE).contains(&c.x) && (0..MAX_BITBOARD_SIDE).contains(&c.y)
+    };
+    let width = cells.iter().filter(|c| on_grid(c)).map(|c| c.x + 1).max();
+    let height = cells.iter().filter(|c| on_grid(c)).map(|c| c.y + 1).max();
+    let (width, height) = (width.unwrap_or(0) as usize, height.unwrap_or(0) as usize);
+
+    let mut bits = vec![0u8; (width * height).div_ceil(8)];
+    let mut extras = Vec::new();
+    for cell in cells {
+        if on_grid(cell) {
+            let index = cell.y as usize * width + cell.x as usize;
+            let (byte, bit) = (index / 8, 1u8 << (index % 8));
+            if bits[byte] & bit == 0 {
+                bits[byte] |= bit;
+                continue;
+            }
+        }
+        extras.push(cell);
+    }
+
+    put_u64(out, width as u64);
+    put_u64(out, height as u64);
+    out.extend_from_slice(&bits);
+    put_u64(out, extras.len() as u64);
+    for cell in extras {
+        put_point(out, cell);
+    }
+}
+
+fn segment_code(from: &FrameCoord, to: &FrameCoord) -> u8 {
+    let dx = i64::from(to.x) - i64::from(from.x);
+    let dy = i64::from(to.y) - i64::from(from.y);
+    match (dx, dy) {
+        (0, 0) => SEGMENT_SAME,
+        (0, 1) => SEGMENT_UP,
+        (0, -1) => SEGMENT_DOWN,
+        (-1, 0) => SEGMENT_LEFT,
+        (1, 0) => SEGMENT_RIGHT,
+        _ => SEGMENT_JUMP,
+    }
+}
+
+fn put_body(out: &mut Vec<u8>, body: &[FrameCoord]) {
+    put_u64(out, body.len() as u64);
+    let Some(head) = body.first() else {
+        return;
+    };
+    put_point(out, head);
+
+    let codes: Vec<u8> = body
+        .windows(2)
+        .map(|pair| segment_code(&pair[0], &pair[1]))
+        .collect();
+    for pair in codes.chunks(2) {
+        out.push(pair[0] | pair.get(1).map_or(0, |code| code << 4));
+    }
+    for (segment, code) in body[1..].iter().zip(&codes) {
+        if *code == SEGMENT_JUMP {
+            put_point(out, segment);
+        }
+    }
+}
+
+fn put_snake(out: &mut Vec<u8>, snake: &FrameSnake) {
+    put_str(out, &snake.id);
+    put_str(out, &snake.name);
+    put_body(out, &snake.body);
+    put_i64(out, snake.health.into());
+    for value in [
+        &snake.color,
+        &snake.head_type,
+        &snake.tail_type,
+        &snake.latency,
+        &snake.shout,
+        &snake.squad,
+        &snake.api_version,
+        &snake.author,
+        &snake.eliminated_cause,
+        &snake.eliminated_by,
+    ] {
+        put_str(out, value);
+    }
+    match &snake.death {
+        Some(death) => {
+            out.push(1);
+            put_str(out, &death.cause);
+            put_i64(out, death.turn.into());
+            put_str(out, &death.eliminated_by);
+        }
+        None => out.push(0),
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> cja::Result<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| eyre!("Truncated encoded frame"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> cja::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> cja::Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        bail!("Varint too long in encoded frame")
+    }
+
+    /// A count of things that follow, bounded by the bytes left so a
+    /// corrupt length can't trigger a huge allocation
+    fn len(&mut self) -> cja::Result<usize> {
+        let len = self.u64()?;
+        if len > (self.bytes.len() - self.pos) as u64 * 2 + 1 {
+            bail!("Implausible length {len} in encoded frame");
+        }
+        Ok(len as usize)
+    }
+
+    fn i32(&mut self) -> cja::Result<i32> {
+        let raw = self.u64()?;
+        let value = ((raw >> 1) as i64) ^ -((raw & 1) as i64);
+        i32::try_from(value).map_err(|_| eyre!("Value {value} out of range in encoded frame"))
+    }
+
+    fn point(&mut self) -> cja::Result<FrameCoord> {
+        Ok(FrameCoord {
+            x: self.i32()?,
+            y: self.i32()?,
+        })
+    }
+
+    fn str(&mut self) -> cja::Result<String> {
+        match self.byte()? {
+            STR_RAW => {
+                let len = self.len()?;
+                String::from_utf8(self.take(len)?.to_vec())
+                    .map_err(|_| eyre!("Invalid UTF-8 in encoded frame"))
+            }
+            STR_UUID => {
+                let bytes: [u8; 16] = self.take(16)?.try_into()?;
+                Ok(uuid::Uuid::from_bytes(bytes).to_string())
+            }
+            STR_COLOR => {
+                let rgb = self.take(3)?;
+                Ok(format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]))
+            }
+            tag => bail!("Unknown string tag {tag} in encoded frame"),
+        }
+    }
+
+    fn cells(&mut self) -> cja::Result<Vec<FrameCoord>> {
+        let width = self.u64()?;
+        let height = self.u64()?;
+        if width > MAX_BITBOARD_SIDE as u64 || height > MAX_BITBOARD_SIDE as u64 {
+            bail!("Bitboard of {width}x{height} in encoded frame is too large");
+        }
+        let (width, height) = (width as usize, height as usize);
+        let bits = self.take((width * height).div_ceil(8))?;
+
+        let mut cells = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let index = y * width + x;
+                if bits[index / 8] & (1 << (index % 8)) != 0 {
+                    cells.push(FrameCoord {
+                        x: x as i32,
+                        y: y as i32,
+                    });
+                }
+            }
+        }
+        let extras = self.len()?;
+        for _ in 0..extras {
+            cells.push(self.point()?);
+        }
+        Ok(cells)
+    }
+
+    fn body(&mut self) -> cja::Result<Vec<FrameCoord>> {
+        let len = self.len()?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        let head = self.point()?;
+        let codes: Vec<u8> = self
+            .take((len - 1).div_ceil(2))?
+            .iter()
+            .flat_map(|byte| [byte & 0x0f, byte >> 4])
+            .take(len - 1)
+            .collect();
+
+        // The point each segment code steps from: the head, then whatever
+        // was decoded last
+        let mut prev = (head.x, head.y);
+        let mut body = Vec::with_capacity(len);
+        body.push(head);
+        for code in codes {
+            let (dx, dy) = match code {
+                SEGMENT_SAME => (0, 0),
+                SEGMENT_UP => (0, 1),
+                SEGMENT_DOWN => (0, -1),
+                SEGMENT_LEFT => (-1, 0),
+                SEGMENT_RIGHT => (1, 0),
+                // Jump points follow the nibbles, in segment order
+                SEGMENT_JUMP => {
+                    let point = self.point()?;
+                    prev = (point.x, point.y);
+                    body.push(point);
+                    continue;
+                }
+                code => bail!("Unknown body segment code {code} in encoded frame"),
+            };
+            prev = prev
+                .0
+                .checked_add(dx)
+                .zip(prev.1.checked_add(dy))
+                .ok_or_else(|| eyre!("Body segment out of range in encoded frame"))?;
+            body.push(FrameCoord {
+                x: prev.0,
+                y: prev.1,
+            });
+        }
+        Ok(body)
+    }
+
+    fn snake(&mut self) -> cja::Result<FrameSnake> {
+        let id = self.str()?;
+        let name = self.str()?;
+        let body = self.body()?;
+        let health = self.i32()?;
+        Ok(FrameSnake {
+            id,
+            name,
+            body,
+            health,
+            color: self.str()?,
+            head_type: self.str()?,
+            tail_type: self.str()?,
+            latency: self.str()?,
+            shout: self.str()?,
+            squad: self.str()?,
+            api_version: self.str()?,
+            author: self.str()?,
+            eliminated_cause: self.str()?,
+            eliminated_by: self.str()?,
+            death: match self.byte()? {
+                0 => None,
+                1 => Some(FrameDeath {
+                    cause: self.str()?,
+                    turn: self.i32()?,
+                    eliminated_by: self.str()?,
+                }),
+                flag => bail!("Invalid death flag {flag} in encoded frame"),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(x: i32, y: i32) -> FrameCoord {
+        FrameCoord { x, y }
+    }
+
+    fn snake(id: &str, body: Vec<FrameCoord>) -> FrameSnake {
+        FrameSnake {
+            id: id.to_string(),
+            name: "Snek".to_string(),
+            body,
+            health: 87,
+            color: "#ff00aa".to_string(),
+            head_type: "default".to_string(),
+            tail_type: "default".to_string(),
+            latency: "123".to_string(),
+            shout: "hiss".to_string(),
+            squad: String::new(),
+            api_version: "1".to_string(),
+            author: String::new(),
+            death: None,
+            eliminated_cause: String::new(),
+            eliminated_by: String::new(),
+        }
+    }
+
+    /// Food and hazards come back in row-major order
+    fn sorted(mut frame: EngineGameFrame) -> EngineGameFrame {
+        let key = |c: &FrameCoord| (c.y, c.x);
+        frame.food.sort_by_key(key);
+        frame.hazards.sort_by_key(key);
+        frame
+    }
+
+    #[test]
+    fn round_trips_every_field() {
+        let mut dead = snake(
+            "not-a-uuid",
+            // Off the board, as after a wall collision
+            vec![coord(-1, 4), coord(0, 4), coord(0, 3)],
+        );
+        dead.color = "#ABCDEF".to_string();
+        dead.health = 0;
+        dead.eliminated_cause = "wall-collision".to_string();
+        dead.death = Some(FrameDeath {
+            cause: "wall-collision".to_string(),
+            turn: 12,
+            eliminated_by: String::new(),
+        });
+
+        let frame = EngineGameFrame {
+            turn: 12,
+            snakes: vec![
+                snake(
+                    "0b4a1c52-5f0e-4c43-9a8e-2f1d6c7b8a90",
+                    // Stacked tail and a wrap from x=0 to x=10
+                    vec![
+                        coord(0, 5),
+                        coord(10, 5),
+                        coord(10, 6),
+                        coord(9, 6),
+                        coord(9, 6),
+                    ],
+                ),
+                dead,
+            ],
+            food: vec![coord(7, 2), coord(0, 0), coord(10, 10)],
+            // A stacked hazard
+            hazards: vec![coord(3, 3), coord(3, 3), coord(4, 3)],
+        };
+
+        let decoded = decode(&encode(&frame)).unwrap();
+        assert_eq!(sorted(decoded), sorted(frame));
+    }
+
+    #[test]
+    fn is_much_smaller_than_json_on_large_boards() {
+        let body: Vec<FrameCoord> = (0..60).map(|i| coord(i % 25, i / 25)).collect();
+        let frame = EngineGameFrame {
+            turn: 480,
+            snakes: (0..8)
+                .map(|_| snake(&uuid::Uuid::new_v4().to_string(), body.clone()))
+                .collect(),
+            food: (0..20).map(|i| coord(i, 24 - i)).collect(),
+            hazards: (0..25)
+                .flat_map(|x| (0..6).map(move |y| coord(x, y)))
+                .collect(),
+        };
+
+        let json = serde_json::to_vec(&frame).unwrap();
+        let binary = encode(&frame);
+        assert!(
+            binary.len() * 10 < json.len(),
+            "{} bytes binary vs {} JSON",
+            binary.len(),
+            json.len()
+        );
+        assert_eq!(sorted(decode(&binary).unwrap()), sorted(frame));
+    }
+
+    #[test]
+    fn rejects_corrupt_input() {
+        let frame = EngineGameFrame {
+            turn: 1,
+            snakes: vec![snake("s1", vec![coord(1, 1), coord(1, 2)])],
+            food: vec![coord(2, 2)],
+            hazards: vec![],
+        };
+        let bytes = encode(&frame);
+
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[FORMAT_VERSION + 1]).is_err());
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode(&trailing).is_err());
+    }
+}
@@ -6,6 +6,7 @@
 //! `snake_client.rs`).
 
 pub mod frame;
+pub mod frame_codec;
 
 use rules::{BoardState, Direction, Point, RoyaleSettings, SnakeMove, StandardSettings};
 use uuid::Uuid;
@@ -16,9 +16,29 @@ impl<R: IntoResponse> Display for ServerError<R> {
 
 impl<R: IntoResponse + Debug> IntoResponse for ServerError<R> {
     fn into_response(self) -> axum::response::Response {
-        tracing::error!(error = ?self, "Request Error");
+        let mut response = self.1.into_response();
+        let status = response.status();
+        if status.is_server_error() {
+            crate::telemetry::report_error(&self.0, status);
+        } else {
+            tracing::error!(error = ?self.0, status = %status, "Request Error");
+        }
 
-        self.1.into_response()
+        // A bare status has no body; give the user something to quote
+        if status.is_server_error()
+            && axum::body::HttpBody::size_hint(response.body()).exact() == Some(0)
+            && let Some(request_id) = crate::request_id::current()
+        {
+            *response.body_mut() = axum::body::Body::from(format!(
+                "{}\n\nRequest ID: {request_id}\n",
+                status.canonical_reason().unwrap_or("Error")
+            ));
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_TYPE,
+                axum::http::HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+        }
+        response
     }
 }
 
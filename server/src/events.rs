@@ -0,0 +1,277 @@
+//! Canonical game lifecycle events.
+//!
+//! Each is an `info` log whose `event_type` names the event, emitted only
+//! through the functions below so field names can't drift between call
+//! sites. Dashboards and log-based metrics filter on these, so treat names
+//! and fields as a stable contract: add fields freely, but don't rename or
+//! remove one without updating the dashboards that read it.
+//!
+//! | `event_type`       | Fields |
+//! |--------------------|--------|
+//! | `game_created`     | `game_id`, `board_size`, `game_type`, `snake_count`, `source`, `user_id`? |
+//! | `game_started`     | `game_id`, `board_size`, `game_type`, `snake_count`, `resumed_from_turn`? |
+//! | `turn_completed`   | `game_id`, `turn`, `alive_snakes`, `max_latency_ms`?, `timeouts`, `duration_ms` |
+//! | `snake_eliminated` | `game_id`, `turn`, `snake_id`, `cause`, `eliminated_by`? |
+//! | `game_finished`    | `game_id`, `final_turn`, `snake_count`, `duration_ms`, `winner_snake_id`? |
+//!
+//! Fields marked `?` are left out when there's no value. `snake_id` and
+//! `winner_snake_id` are game battlesnake ids (one per snake per game), and
+//! `source` is one of `web_ui`, `api`, `leaderboard`, `tournament`, or
+//! `challenge`.
+
+use uuid::Uuid;
+
+use crate::models::game::{GameBoardSize, GameType};
+
+/// Where a game came from, the `source` of a `game_created` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameSource {
+    WebUi,
+    Api,
+    Leaderboard,
+    Tournament,
+    Challenge,
+    /// "Play as a snake", steered from the browser
+    Play,
+}
+
+impl GameSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameSource::WebUi => "web_ui",
+            GameSource::Api => "api",
+            GameSource::Leaderboard => "leaderboard",
+            GameSource::Tournament => "tournament",
+            GameSource::Challenge => "challenge",
+            GameSource::Play => "play",
+        }
+    }
+}
+
+/// A game was created and its runner job enqueued
+pub fn game_created(
+    game_id: Uuid,
+    board_size: &GameBoardSize,
+    game_type: &GameType,
+    snake_count: usize,
+    source: GameSource,
+    user_id: Option<Uuid>,
+) {
+    tracing::info!(
+        event_type = "game_created",
+        game_id = %game_id,
+        board_size = board_size.as_str(),
+        game_type = game_type.as_str(),
+        snake_count,
+        source = source.as_str(),
+        user_id = user_id.map(tracing::field::display),
+        "game created"
+    );
+}
+
+/// A runner picked the game up. `resumed_from_turn` is set when it carries
+/// on from a checkpoint rather than starting at turn 0.
+pub fn game_started(
+    game_id: Uuid,
+    board_size: &GameBoardSize,
+    game_type: &GameType,
+    snake_count: usize,
+    resumed_from_turn: Option<i32>,
+) {
+    tracing::info!(
+        event_type = "game_started",
+        game_id = %game_id,
+        board_size = board_size.as_str(),
+        game_type = game_type.as_str(),
+        snake_count,
+        resumed_from_turn,
+        "game started"
+    );
+}
+
+/// A turn was played and stored. `duration_ms` covers the whole turn: the
+/// /move calls, applying the moves, and persisting the frame.
+pub fn turn_completed(
+    game_id: Uuid,
+    turn: i32,
+    alive_snakes: usize,
+    max_latency_ms: Option<i64>,
+    timeouts: usize,
+    duration_ms: u64,
+) {
+    tracing::info!(
+        event_type = "turn_completed",
+        game_id = %game_id,
+        turn,
+        alive_snakes,
+        max_latency_ms,
+        timeouts,
+        duration_ms,
+        "turn completed"
+    );
+}
+
+/// A snake was eliminated on `turn`
+pub fn snake_eliminated(
+    game_id: Uuid,
+    turn: i32,
+    snake_id: &str,
+    cause: &str,
+    eliminated_by: &str,
+) {
+    tracing::info!(
+        event_type = "snake_eliminated",
+        game_id = %game_id,
+        turn,
+        snake_id,
+        cause,
+        eliminated_by = (!eliminated_by.is_empty()).then_some(eliminated_by),
+        "snake eliminated"
+    );
+}
+
+/// The game's result committed
+pub fn game_finished(
+    game_id: Uuid,
+    final_turn: i32,
+    snake_count: usize,
+    duration_ms: u64,
+    winner_snake_id: Option<&str>,
+) {
+    tracing::info!(
+        event_type = "game_finished",
+        game_id = %game_id,
+        final_turn,
+        snake_count,
+        duration_ms,
+        winner_snake_id,
+        "game finished"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    type Captured = Arc<Mutex<Vec<BTreeMap<String, String>>>>;
+
+    /// Records each event's fields by name
+    struct Capture(Captured);
+
+    struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+    impl tracing::field::Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(
+                field.name().to_string(),
+                format!("{value:?}").replace('"', ""),
+            );
+        }
+
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Capture {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut fields = BTreeMap::new();
+            event.record(&mut Fields(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+
+    fn capture(emit: impl FnOnce()) -> Vec<BTreeMap<String, String>> {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry().with(Capture(captured.clone()));
+        tracing::subscriber::with_default(subscriber, emit);
+        Arc::try_unwrap(captured).unwrap().into_inner().unwrap()
+    }
+
+    fn names(fields: &BTreeMap<String, String>) -> Vec<&str> {
+        fields
+            .keys()
+            .map(String::as_str)
+            .filter(|name| *name != "message")
+            .collect()
+    }
+
+    #[test]
+    fn events_carry_their_documented_fields() {
+        let game_id = Uuid::nil();
+        let events = capture(|| {
+            game_created(
+                game_id,
+                &GameBoardSize::Medium,
+                &GameType::Standard,
+                2,
+                GameSource::Api,
+                Some(game_id),
+            );
+            game_started(
+                game_id,
+                &GameBoardSize::Medium,
+                &GameType::Standard,
+                2,
+                None,
+            );
+            turn_completed(game_id, 3, 2, Some(120), 1, 180);
+            snake_eliminated(game_id, 4, "snake-a", "wall-collision", "");
+            game_finished(game_id, 4, 2, 900, Some("snake-b"));
+        });
+
+        assert_eq!(
+            events.iter().map(names).collect::<Vec<_>>(),
+            vec![
+                vec![
+                    "board_size",
+                    "event_type",
+                    "game_id",
+                    "game_type",
+                    "snake_count",
+                    "source",
+                    "user_id"
+                ],
+                vec![
+                    "board_size",
+                    "event_type",
+                    "game_id",
+                    "game_type",
+                    "snake_count"
+                ],
+                vec![
+                    "alive_snakes",
+                    "duration_ms",
+                    "event_type",
+                    "game_id",
+                    "max_latency_ms",
+                    "timeouts",
+                    "turn"
+                ],
+                vec!["cause", "event_type", "game_id", "snake_id", "turn"],
+                vec![
+                    "duration_ms",
+                    "event_type",
+                    "final_turn",
+                    "game_id",
+                    "snake_count",
+                    "winner_snake_id"
+                ],
+            ]
+        );
+        assert_eq!(events[0]["event_type"], "game_created");
+        assert_eq!(events[0]["source"], "api");
+        assert_eq!(events[1]["event_type"], "game_started");
+        assert_eq!(events[2]["event_type"], "turn_completed");
+        assert_eq!(events[3]["event_type"], "snake_eliminated");
+        assert_eq!(events[4]["event_type"], "game_finished");
+        assert_eq!(events[4]["winner_snake_id"], "snake-b");
+    }
+}
@@ -0,0 +1,344 @@
+//! Offloading finished games' frames from Postgres to GCS.
+//!
+//! Frames are most of the database's bulk, and stress-test runs pile them up
+//! fast. Once a finished game is `FRAME_ARCHIVE_AFTER_DAYS` old, its frames
+//! are uploaded to the backup bucket as one zstd-compressed JSON file, a
+//! pointer is written to `game_frame_archives`, and the turns' frames are
+//! cleared. Archives hold board-viewer JSON whichever way a frame was
+//! stored. The turn rows themselves stay, so latency stats keep working.
+//!
+//! Readers go through [`load_turns`], [`load_frames_page`], and
+//! [`load_last_frame`], which fetch the frames back from GCS when a game has
+//! been archived, so replays look the same either way. A downloaded archive
+//! is kept briefly in [`AppState::frame_archive_cache`] so paging through a
+//! replay downloads it once. Deleting an archived game leaves its object
+//! behind for the caller to remove with [`delete_archives`].
+
+use std::io::Write;
+use std::sync::Arc;
+
+use color_eyre::eyre::{Context as _, eyre};
+use google_cloud_storage::{
+    client::Client as GcsClient,
+    http::objects::{
+        delete::DeleteObjectRequest,
+        download::Range,
+        get::GetObjectRequest,
+        upload::{Media, UploadObjectRequest, UploadType},
+    },
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::jobs::ArchiveGameFramesJob;
+use crate::models::game::{self, GameStatus};
+use crate::models::game_frame_archive::{self, ArchivedGame};
+use crate::models::turn::{self, Turn};
+use crate::state::AppState;
+
+/// Most games one discovery run enqueues; the next run picks up the rest.
+const DISCOVERY_BATCH_SIZE: i64 = 500;
+
+/// One frame in an archive file, keyed by the turn it belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ArchivedFrame {
+    turn_number: i32,
+    frame: serde_json::Value,
+}
+
+/// A game's decoded archive, in turn order
+pub struct FrameArchive(Vec<ArchivedFrame>);
+
+/// GCS path for a game's frames, bucketed by creation date like the engine
+/// backups.
+fn gcs_path(game_id: Uuid, created_at: chrono::DateTime<chrono::Utc>) -> String {
+    format!(
+        "frames/{}/{}.json.zst",
+        created_at.format("%Y/%m/%d"),
+        game_id
+    )
+}
+
+fn encode(frames: &[ArchivedFrame]) -> cja::Result<Vec<u8>> {
+    let json = serde_json::to_vec(frames).wrap_err("Failed to serialize frames")?;
+    let mut encoder =
+        zstd::Encoder::new(Vec::new(), 3).wrap_err("Failed to create zstd encoder")?;
+    encoder
+        .write_all(&json)
+        .wrap_err("Failed to write to zstd encoder")?;
+    encoder
+        .finish()
+        .wrap_err("Failed to finish zstd compression")
+}
+
+fn decode(bytes: &[u8]) -> cja::Result<Vec<ArchivedFrame>> {
+    let json = zstd::decode_all(bytes).wrap_err("Failed to decompress frame archive")?;
+    serde_json::from_slice(&json).wrap_err("Failed to parse frame archive")
+}
+
+/// The shared GCS client and the bucket archives live in
+fn gcs(app_state: &AppState) -> cja::Result<(&GcsClient, &str)> {
+    match (&app_state.gcs, app_state.config.gcs_bucket.as_deref()) {
+        (Some(client), Some(bucket)) => Ok((client, bucket)),
+        _ => Err(eyre!("GCS bucket not configured")),
+    }
+}
+
+/// Enqueue an [`ArchiveGameFramesJob`] for every finished game past the
+/// grace period whose frames are still in Postgres. A no-op unless both
+/// `FRAME_ARCHIVE_AFTER_DAYS` and `GCS_BUCKET` are set.
+pub async fn run_archive_discovery(app_state: &AppState) -> cja::Result<()> {
+    let Some(days) = app_state.config.frame_archive_after_days else {
+        return Ok(());
+    };
+    if app_state.config.gcs_bucket.is_none() {
+        tracing::warn!("GCS bucket not configured, skipping frame archive discovery");
+        return Ok(());
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+    let game_ids =
+        game_frame_archive::find_archivable_games(&app_state.db, cutoff, DISCOVERY_BATCH_SIZE)
+            .await?;
+
+    for game_id in &game_ids {
+        cja::jobs::Job::enqueue(
+            ArchiveGameFramesJob { game_id: *game_id },
+            app_state.clone(),
+            format!("archive frames for game {game_id}"),
+            None,
+        )
+        .await
+        .wrap_err_with(|| format!("Failed to enqueue frame archive job for game {game_id}"))?;
+    }
+
+    tracing::info!(
+        enqueued = game_ids.len(),
+        "Frame archive discovery complete"
+    );
+    Ok(())
+}
+
+/// Upload one game's frames to GCS, then swap the Postgres copy for a
+/// pointer. Safe to retry: an already-archived game is skipped, and the
+/// upload overwrites the same object.
+pub async fn archive_game_frames(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
+    if game_frame_archive::get_gcs_path(&app_state.db, game_id)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+    let (client, bucket) = gcs(app_state)?;
+
+    let game = game::get_game_by_id(&app_state.db, game_id)
+        .await?
+        .ok_or_else(|| eyre!("Game {game_id} not found"))?;
+    if game.status != GameStatus::Finished {
+        return Err(eyre!("Game {game_id} isn't finished; refusing to archive"));
+    }
+
+    let mut frames = Vec::new();
+    for t in turn::get_turns_by_game_id(&app_state.db, game_id).await? {
+        if let Some(frame) = t.frame_json()? {
+            frames.push(ArchivedFrame {
+                turn_number: t.turn_number,
+                frame,
+            });
+        }
+    }
+    let compressed = encode(&frames)?;
+    let path = gcs_path(game_id, game.created_at);
+
+    let upload_type = UploadType::Simple(Media::new(path.clone()));
+    client
+        .upload_object(
+            &UploadObjectRequest {
+                bucket: bucket.to_string(),
+                ..Default::default()
+            },
+            compressed,
+            &upload_type,
+        )
+        .await
+        .wrap_err("Failed to upload frames to GCS")?;
+
+    game_frame_archive::record_archive(&app_state.db, game_id, &path, frames.len() as i32).await?;
+    tracing::info!(game_id = %game_id, path = %path, frames = frames.len(), "Archived game frames");
+
+    Ok(())
+}
+
+/// The archived frames for a game, or `None` if its frames are still in
+/// Postgres
+async fn fetch_archived_frames(
+    app_state: &AppState,
+    game_id: Uuid,
+) -> cja::Result<Option<Arc<FrameArchive>>> {
+    if let Some(archive) = app_state.frame_archive_cache.get(&game_id) {
+        return Ok(Some(archive));
+    }
+    let Some(path) = game_frame_archive::get_gcs_path(&app_state.db, game_id).await? else {
+        return Ok(None);
+    };
+
+    let (client, bucket) = gcs(app_state)?;
+    let bytes = client
+        .download_object(
+            &GetObjectRequest {
+                bucket: bucket.to_string(),
+                object: path.clone(),
+                ..Default::default()
+            },
+            &Range::default(),
+        )
+        .await
+        .wrap_err_with(|| format!("Failed to download frame archive {path}"))?;
+
+    let archive = FrameArchive(decode(&bytes)?);
+    Ok(Some(app_state.frame_archive_cache.put(game_id, archive)))
+}
+
+/// Delete the GCS objects of archived games that have been deleted from
+/// Postgres. Failures are logged rather than returned: the games are
+/// already gone, and a leftover object costs storage, not correctness.
+pub async fn delete_archives(app_state: &AppState, archived: &[ArchivedGame]) {
+    if archived.is_empty() {
+        return;
+    }
+    let (client, bucket) = match gcs(app_state) {
+        Ok(gcs) => gcs,
+        Err(e) => {
+            tracing::error!(
+                error = ?e,
+                orphaned = archived.len(),
+                "Can't delete frame archives of deleted games"
+            );
+            return;
+        }
+    };
+
+    for ArchivedGame { game_id, gcs_path } in archived {
+        app_state.frame_archive_cache.remove(game_id);
+        let request = DeleteObjectRequest {
+            bucket: bucket.to_string(),
+            object: gcs_path.clone(),
+            ..Default::default()
+        };
+        if let Err(e) = client.delete_object(&request).await {
+            tracing::error!(
+                error = ?e,
+                game_id = %game_id,
+                path = %gcs_path,
+                "Failed to delete frame archive of deleted game"
+            );
+        }
+    }
+}
+
+/// Like [`turn::get_turns_by_game_id`], with archived frames filled back in
+pub async fn load_turns(app_state: &AppState, game_id: Uuid) -> cja::Result<Vec<Turn>> {
+    let mut turns = turn::get_turns_by_game_id(&app_state.db, game_id).await?;
+    if let Some(archive) = fetch_archived_frames(app_state, game_id).await? {
+        restore_frames(&mut turns, &archive.0);
+    }
+    Ok(turns)
+}
+
+fn restore_frames(turns: &mut [Turn], archived: &[ArchivedFrame]) {
+    let frames: std::collections::HashMap<i32, &serde_json::Value> =
+        archived.iter().map(|a| (a.turn_number, &a.frame)).collect();
+    for turn in turns {
+        if !turn.has_frame() {
+            turn.frame_data = frames.get(&turn.turn_number).map(|&frame| frame.clone());
+        }
+    }
+}
+
+/// Like [`turn::get_turn_frames_page`], reading from the archive once the
+/// game has been archived
+pub async fn load_frames_page(
+    app_state: &AppState,
+    game_id: Uuid,
+    offset: i64,
+    limit: i64,
+) -> cja::Result<Vec<serde_json::Value>> {
+    if let Some(archive) = fetch_archived_frames(app_state, game_id).await? {
+        return Ok(archive
+            .0
+            .iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .map(|a| a.frame.clone())
+            .collect());
+    }
+
+    let mut frames = Vec::new();
+    for t in turn::get_turn_frames_page(&app_state.db, game_id, offset, limit).await? {
+        frames.extend(t.frame_json()?);
+    }
+    Ok(frames)
+}
+
+/// The game's final frame and its turn number, from Postgres or the archive
+pub async fn load_last_frame(
+    app_state: &AppState,
+    game_id: Uuid,
+) -> cja::Result<Option<(i32, serde_json::Value)>> {
+    if let Some(archive) = fetch_archived_frames(app_state, game_id).await? {
+        return Ok(archive.0.last().map(|a| (a.turn_number, a.frame.clone())));
+    }
+
+    match turn::get_last_turn(&app_state.db, game_id).await? {
+        Some(t) => Ok(t.frame_json()?.map(|frame| (t.turn_number, frame))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(turn_number: i32) -> ArchivedFrame {
+        ArchivedFrame {
+            turn_number,
+            frame: serde_json::json!({ "Turn": turn_number, "Snakes": [] }),
+        }
+    }
+
+    #[test]
+    fn archive_round_trips() {
+        let frames = vec![frame(0), frame(1), frame(2)];
+        assert_eq!(decode(&encode(&frames).unwrap()).unwrap(), frames);
+    }
+
+    #[test]
+    fn restore_frames_fills_only_cleared_turns() {
+        let now = chrono::Utc::now();
+        let turn = |turn_number, frame_data| Turn {
+            turn_id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            turn_number,
+            frame_data,
+            frame_bin: None,
+            created_at: now,
+        };
+        let mut turns = vec![turn(0, None), turn(1, None), turn(2, None)];
+        restore_frames(&mut turns, &[frame(0), frame(2)]);
+
+        assert_eq!(turns[0].frame_data, Some(frame(0).frame));
+        assert_eq!(turns[1].frame_data, None);
+        assert_eq!(turns[2].frame_data, Some(frame(2).frame));
+    }
+
+    #[test]
+    fn gcs_path_is_bucketed_by_creation_date() {
+        let created_at = chrono::DateTime::parse_from_rfc3339("2026-03-04T05:06:07Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            gcs_path(Uuid::nil(), created_at),
+            "frames/2026/03/04/00000000-0000-0000-0000-000000000000.json.zst"
+        );
+    }
+}
@@ -1,8 +1,20 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
 use tokio::sync::{RwLock, broadcast};
 use uuid::Uuid;
 
+/// Postgres channel the `turns` insert trigger announces each stored turn
+/// on, as `<game_id>:<turn_number>`
+pub const TURNS_CHANNEL: &str = "game_turns";
+
+/// How often the listener drops channels nobody is watching any more
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Notification sent when a turn completes
 #[derive(Debug, Clone)]
 pub struct TurnNotification {
@@ -10,6 +22,16 @@ pub struct TurnNotification {
     pub turn_number: i32,
 }
 
+impl TurnNotification {
+    fn from_payload(payload: &str) -> Option<Self> {
+        let (game_id, turn_number) = payload.split_once(':')?;
+        Some(Self {
+            game_id: game_id.parse().ok()?,
+            turn_number: turn_number.parse().ok()?,
+        })
+    }
+}
+
 /// Manages broadcast channels for live game updates
 /// One broadcast channel per active game, subscribers receive turn notifications
 #[derive(Debug, Clone)]
@@ -76,6 +98,45 @@ impl GameChannels {
         channels.remove(&game_id);
         tracing::debug!(game_id = %game_id, "Removed game channel");
     }
+
+    /// Drop every channel with no receivers left. Games run by another
+    /// process never reach this one's `cleanup`, so the listener sweeps.
+    pub async fn prune_unwatched(&self) {
+        let mut channels = self.channels.write().await;
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+    }
+}
+
+/// Forward turns stored by any process - this one or a separate worker -
+/// to this process's subscribers. Runs alongside the web server.
+///
+/// `PgListener` reconnects on its own if the connection drops; turns stored
+/// while it was down are picked up by each viewer with the next one.
+pub async fn forward_turn_notifications(pool: PgPool, channels: GameChannels) -> cja::Result<()> {
+    let mut listener = PgListener::connect_with(&pool)
+        .await
+        .wrap_err("Failed to connect turn listener")?;
+    listener
+        .listen(TURNS_CHANNEL)
+        .await
+        .wrap_err("Failed to listen for turn notifications")?;
+
+    let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            notification = listener.recv() => {
+                let notification = notification.wrap_err("Turn listener failed")?;
+                match TurnNotification::from_payload(notification.payload()) {
+                    Some(turn) => channels.notify(turn).await,
+                    None => tracing::warn!(
+                        payload = notification.payload(),
+                        "Ignoring malformed turn notification"
+                    ),
+                }
+            }
+            _ = prune.tick() => channels.prune_unwatched().await,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -112,6 +173,33 @@ mod tests {
         assert_eq!(notification.turn_number, 5);
     }
 
+    #[test]
+    fn test_turn_notification_payload() {
+        let game_id = Uuid::new_v4();
+        let turn = TurnNotification::from_payload(&format!("{game_id}:42")).unwrap();
+        assert_eq!(turn.game_id, game_id);
+        assert_eq!(turn.turn_number, 42);
+
+        assert!(TurnNotification::from_payload("not-a-uuid:1").is_none());
+        assert!(TurnNotification::from_payload(&game_id.to_string()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_unwatched_keeps_watched_channels() {
+        let channels = GameChannels::new();
+        let watched = Uuid::new_v4();
+        let unwatched = Uuid::new_v4();
+
+        let _receiver = channels.subscribe(watched).await;
+        drop(channels.subscribe(unwatched).await);
+
+        channels.prune_unwatched().await;
+
+        let map = channels.channels.read().await;
+        assert!(map.contains_key(&watched));
+        assert!(!map.contains_key(&unwatched));
+    }
+
     #[tokio::test]
     async fn test_cleanup_removes_empty_channels() {
         let channels = GameChannels::new();
@@ -1,21 +1,78 @@
 use color_eyre::eyre::Context as _;
-use rules::{Direction, EliminationCause};
+use rules::Direction;
 use std::collections::HashMap;
+use tracing::{Instrument as _, field};
 use uuid::Uuid;
 
+use crate::config::AppConfig;
 use crate::customizations;
 use crate::engine::MAX_TURNS;
-use crate::engine::frame::{DeathInfo, SnakeCustomizations, game_to_frame};
+use crate::engine::frame::{
+    DeathInfo, EngineGameFrame, SnakeCustomizations, elimination_cause_label, game_to_frame,
+    restore_from_frame,
+};
 use crate::models::game::{GameStatus, get_game_by_id, update_game_status};
-use crate::snake_client::{request_end_parallel, request_moves_parallel, request_start_parallel};
+use crate::models::moderation::{self, FlagKind, WordList};
+use crate::models::turn::{PendingSnakeTurn, PendingTurn};
+use crate::snake_client::{
+    MoveResult, request_end_parallel, request_moves_parallel, request_start_parallel,
+};
 use crate::state::AppState;
 use crate::wire;
 
+/// Allowance per turn on top of the move timeout, for applying and writing it
+const TURN_OVERHEAD: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Wall-clock budget for one run of a game: every turn up to [`MAX_TURNS`]
+/// taking the full move timeout plus [`TURN_OVERHEAD`]. A run past it isn't
+/// slow, it's hung.
+pub fn game_budget(move_timeout_ms: i64) -> std::time::Duration {
+    let per_turn = std::time::Duration::from_millis(move_timeout_ms.max(0) as u64) + TURN_OVERHEAD;
+    per_turn * MAX_TURNS as u32
+}
+
+/// End a game whose run went past its [`game_budget`]. It is finished
+/// without placements, like a game the stuck-game watchdog gives up on:
+/// failing the job would only retry it, replaying the game into the same
+/// wall.
+pub async fn end_over_budget_game(
+    app_state: &AppState,
+    game_id: Uuid,
+    budget: std::time::Duration,
+) -> cja::Result<()> {
+    tracing::error!(
+        event_type = "game_budget_exceeded",
+        game_id = %game_id,
+        budget_secs = budget.as_secs(),
+        "Game run went past its time budget; ending the game"
+    );
+    update_game_status(&app_state.db, game_id, GameStatus::Finished).await?;
+    app_state.game_channels.cleanup(game_id).await;
+    Ok(())
+}
+
 /// Run a game with turn-by-turn DB persistence and WebSocket notifications
 ///
 /// This function calls the actual snake APIs to get moves, with timeout handling.
 /// On timeout, snakes continue in the same direction as their last move.
+///
+/// Traced as a `game` span with a `turn` child per turn, which in turn holds
+/// a `snake_move` span per /move call, so a slow game can be read turn by
+/// turn in the tracing backend.
 pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
+    let span = tracing::info_span!(
+        "game",
+        game_id = %game_id,
+        board_size = field::Empty,
+        game_type = field::Empty,
+        snake_count = field::Empty,
+        resumed_from_turn = field::Empty,
+        final_turn = field::Empty,
+    );
+    play_game(app_state, game_id).instrument(span).await
+}
+
+async fn play_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
     let pool = &app_state.db;
     let game_channels = &app_state.game_channels;
     let http_client = &app_state.http_client;
@@ -27,9 +84,11 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         .await?
         .ok_or_else(|| cja::color_eyre::eyre::eyre!("Game not found"))?;
 
-    // Re-entrancy for retries and crash recovery: run_game always plays a
-    // game from turn 0, so a retry must never blindly re-run on top of a
-    // previous attempt's state.
+    // Re-entrancy for retries and crash recovery: a crashed run is replayed
+    // from turn 0, so a retry must never blindly re-run on top of a previous
+    // attempt's state. A game checkpointed at shutdown instead goes back to
+    // `waiting` with its turns intact and carries on from the last one.
+    let mut checkpoint = None;
     match game.status {
         GameStatus::Finished => {
             // A previous attempt finished this game but may have died before
@@ -47,15 +106,21 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         GameStatus::Running => {
             // A previous attempt crashed mid-game or died before the atomic
             // finish committed. Wipe its partial state (turns, placements)
-            // so the re-run starts from a clean turn 0 instead of dying on
-            // the (game_id, turn_number) unique constraint.
+            // so the re-run starts from a clean turn 0. Partitioned `turns`
+            // has no (game_id, turn_number) unique constraint, so this reset
+            // is the only thing keeping turns unique per game: skipping it
+            // would silently write duplicate turns rather than fail.
             tracing::warn!(
                 game_id = %game_id,
                 "Game was already running; resetting partial state for a clean re-run"
             );
             crate::models::game::reset_game_state_for_retry(pool, game_id).await?;
         }
-        GameStatus::Waiting => {}
+        GameStatus::Waiting => {
+            if let Some(turn) = crate::models::turn::get_last_turn(pool, game_id).await? {
+                checkpoint = turn.frame().wrap_err("Failed to read checkpointed frame")?;
+            }
+        }
     }
 
     // Emit queue_wait metric if enqueued_at is available
@@ -77,28 +142,47 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         .await
         .wrap_err("Failed to get battlesnakes for game")?;
 
-    tracing::info!(
-        event_type = "game_started",
-        game_id = %game_id,
-        board_size = game.board_size.as_str(),
-        game_type = game.game_type.as_str(),
-        snake_count = battlesnakes.len(),
-        "game started"
+    crate::events::game_started(
+        game_id,
+        &game.board_size,
+        &game.game_type,
+        battlesnakes.len(),
+        checkpoint.as_ref().map(|frame| frame.turn),
     );
+    let game_span = tracing::Span::current();
+    game_span.record("board_size", game.board_size.as_str());
+    game_span.record("game_type", game.game_type.as_str());
+    game_span.record("snake_count", battlesnakes.len());
 
     if battlesnakes.is_empty() {
         return Err(cja::color_eyre::eyre::eyre!("No battlesnakes in the game"));
     }
 
+    // A seat steered from the browser gets no HTTP calls; see `human_play`
+    let human_seat = crate::models::human_player::get_for_game(pool, game_id)
+        .await?
+        .map(|human| human.game_battlesnake_id);
+
     // Build snake_id -> url mapping using game_battlesnake_id as the key
     // This ensures uniqueness when the same battlesnake appears multiple times
     let snake_urls: Vec<(String, String)> = battlesnakes
         .iter()
+        .filter(|bs| Some(bs.game_battlesnake_id) != human_seat)
         .map(|bs| (bs.game_battlesnake_id.to_string(), bs.url.clone()))
         .collect();
 
-    // Fetch snake customizations from all root endpoints in parallel (1s timeout)
-    let info_timeout = std::time::Duration::from_millis(1000);
+    let settings = app_state.settings().await;
+
+    // Shouts are screened against the word list as they come in; see
+    // `screen_shouts`
+    let words = moderation::load_word_list(pool).await?;
+    let shout_owners: HashMap<String, Uuid> = battlesnakes
+        .iter()
+        .map(|bs| (bs.game_battlesnake_id.to_string(), bs.battlesnake_id))
+        .collect();
+
+    // Fetch snake customizations from all root endpoints in parallel
+    let info_timeout = settings.info_timeout();
     let info_results =
         crate::snake_client::request_info_parallel(http_client, &snake_urls, info_timeout).await;
 
@@ -148,13 +232,39 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
                 );
             }
 
+            if let Err(e) = crate::models::battlesnake_version::record_observed(
+                pool,
+                bs.battlesnake_id,
+                &bs.url,
+                info.version.as_deref(),
+            )
+            .await
+            {
+                tracing::warn!(
+                    battlesnake_id = %bs.battlesnake_id,
+                    error = %e,
+                    "Failed to record battlesnake version"
+                );
+            }
+
             customizations.insert(snake_id, SnakeCustomizations { color, head, tail });
+        } else if Some(bs.game_battlesnake_id) == human_seat {
+            // Nothing to ask; the snake looks the way it last declared
+            customizations.insert(
+                snake_id,
+                SnakeCustomizations {
+                    color: bs.color.clone(),
+                    head: bs.head.clone(),
+                    tail: bs.tail.clone(),
+                },
+            );
         }
     }
 
     // Create the initial game state
     let mut engine_game =
         crate::engine::create_initial_game(game_id, game.board_size, game.game_type, &battlesnakes);
+    engine_game.meta.timeout = settings.move_timeout_ms();
 
     // Get timeout from game settings
     let timeout = std::time::Duration::from_millis(engine_game.meta.timeout as u64);
@@ -163,27 +273,43 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
     let mut elimination_order: Vec<String> = Vec::new();
     let mut last_moves: HashMap<String, Direction> = HashMap::new();
     let mut snake_contexts: HashMap<String, wire::SnakeContext> = HashMap::new();
+    let mut turns = TurnBuffer::new(game_id, &app_state.config);
+
+    if let Some(frame) = &checkpoint {
+        // The snakes already had /start; pick up where the last process
+        // stopped, with the state it kept between turns
+        death_info = restore_from_frame(&mut engine_game, frame);
+        elimination_order = death_info.iter().map(|d| d.snake_id.clone()).collect();
+        last_moves = last_moves_from_frame(frame);
+        snake_contexts = snake_contexts_from_frame(frame);
+        game_span.record("resumed_from_turn", frame.turn);
+        tracing::info!(game_id = %game_id, turn = frame.turn, "Resuming checkpointed game");
+    } else {
+        // Call /start for all snakes in parallel (fire and forget)
+        tracing::info!(game_id = %game_id, "Calling /start for all snakes");
+        request_start_parallel(
+            http_client,
+            &engine_game,
+            &snake_urls,
+            timeout,
+            &snake_contexts,
+            &customizations,
+        )
+        .await;
 
-    // Call /start for all snakes in parallel (fire and forget)
-    tracing::info!(game_id = %game_id, "Calling /start for all snakes");
-    request_start_parallel(
-        http_client,
-        &engine_game,
-        &snake_urls,
-        timeout,
-        &snake_contexts,
-        &customizations,
-    )
-    .await;
-
-    // Store turn 0 (initial state, no moves yet)
-    let frame_0 = game_to_frame(&engine_game, &death_info, &[], &customizations);
-    let frame_0_json =
-        serde_json::to_value(&frame_0).wrap_err("Failed to serialize initial frame")?;
-
-    tracing::info!(game_id = %game_id, "Storing turn 0");
-    crate::models::turn::create_turn(pool, game_channels, game_id, 0, Some(frame_0_json)).await?;
-    tracing::info!(game_id = %game_id, "Turn 0 stored successfully");
+        // Store turn 0 (initial state, no moves yet)
+        let frame_0 = game_to_frame(&engine_game, &death_info, &[], &customizations);
+
+        tracing::info!(game_id = %game_id, "Storing turn 0");
+        turns.push(PendingTurn {
+            turn_number: 0,
+            frame: frame_0,
+            moves: Vec::new(),
+        });
+        // Written straight away so viewers get the starting board
+        turns.flush(pool).await?;
+        tracing::info!(game_id = %game_id, "Turn 0 stored successfully");
+    }
 
     // Track timing for processing_overhead metric
     let game_start = std::time::Instant::now();
@@ -191,8 +317,30 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
 
     // Run the game turn by turn
     while !crate::engine::is_game_over(&engine_game) && engine_game.board.turn < MAX_TURNS {
+        // Out of drain time: hand the game to the next process rather than
+        // being killed mid-turn and stranding it in `running`
+        if app_state.shutdown.deadline_passed() {
+            // The next process resumes from the last turn written
+            turns.flush(pool).await?;
+            crate::shutdown::checkpoint_game(app_state, game_id).await?;
+            return Ok(());
+        }
+
+        let turn_start = std::time::Instant::now();
+        // Filled in below once the moves are in and applied
+        let turn_span = tracing::info_span!(
+            "turn",
+            turn = engine_game.board.turn + 1,
+            snake_ids = field::Empty,
+            max_latency_ms = field::Empty,
+            slowest_snake_id = field::Empty,
+            timeouts = field::Empty,
+            timed_out_snake_ids = field::Empty,
+            eliminated_snake_ids = field::Empty,
+        );
+
         // Request moves from all alive snakes in parallel
-        let move_results = request_moves_parallel(
+        let mut move_results = request_moves_parallel(
             http_client,
             &engine_game,
             &snake_urls,
@@ -201,12 +349,24 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
             &snake_contexts,
             &customizations,
         )
+        .instrument(turn_span.clone())
         .await;
+        if let Some(seat) = human_seat
+            && let Some(human) =
+                crate::human_play::human_move(pool, &engine_game, seat, &last_moves).await?
+        {
+            move_results.push(human);
+        }
+        record_moves(&turn_span, &move_results);
+        screen_shouts(pool, game_id, &words, &shout_owners, &mut move_results).await;
 
         // Accumulate snake wait time from latency measurements
         for result in &move_results {
             if let Some(latency) = result.latency_ms {
                 total_snake_wait_ms += latency;
+                crate::metrics::METRICS
+                    .snake_move_latency
+                    .observe(std::time::Duration::from_millis(latency.max(0) as u64));
             }
         }
 
@@ -241,60 +401,81 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         crate::engine::spawn_food(&mut engine_game);
 
         // Track newly eliminated snakes
+        let eliminated_before = elimination_order.len();
         for snake in &engine_game.board.snakes {
             if snake.eliminated_cause.is_eliminated() && !elimination_order.contains(&snake.id) {
-                elimination_order.push(snake.id.clone());
-                death_info.push(DeathInfo {
+                let death = DeathInfo {
                     snake_id: snake.id.clone(),
                     turn: engine_game.board.turn,
                     cause: elimination_cause_label(&snake.eliminated_cause),
                     eliminated_by: snake.eliminated_by.clone(),
-                });
+                };
+                crate::events::snake_eliminated(
+                    game_id,
+                    death.turn,
+                    &death.snake_id,
+                    &death.cause,
+                    &death.eliminated_by,
+                );
+                elimination_order.push(snake.id.clone());
+                death_info.push(death);
             }
         }
+        if elimination_order.len() > eliminated_before {
+            turn_span.record(
+                "eliminated_snake_ids",
+                elimination_order[eliminated_before..].join(","),
+            );
+        }
 
-        // Store the turn frame with latency info and notify subscribers
+        // Queue the turn frame with each snake's move and latency for the
+        // next flush
         let frame = game_to_frame(&engine_game, &death_info, &move_results, &customizations);
-        let frame_json = serde_json::to_value(&frame)
-            .wrap_err_with(|| format!("Failed to serialize frame {}", engine_game.board.turn))?;
-
-        // Measure DB write latency
-        let db_write_start = std::time::Instant::now();
+        let moves = move_results
+            .iter()
+            .filter_map(|result| {
+                let game_battlesnake_id = Uuid::parse_str(&result.snake_id).ok()?;
+                Some(PendingSnakeTurn {
+                    game_battlesnake_id,
+                    direction: result.direction.to_string(),
+                    latency_ms: result.latency_ms,
+                    timed_out: result.timed_out,
+                    error: result.error,
+                })
+            })
+            .collect();
+        turns.push(PendingTurn {
+            turn_number: engine_game.board.turn,
+            frame,
+            moves,
+        });
+        // A human game is written every turn so the player sees it at once
+        if turns.is_due() || human_seat.is_some() {
+            turns.flush(pool).instrument(turn_span.clone()).await?;
+        }
 
-        tracing::debug!(game_id = %game_id, turn = engine_game.board.turn, "Storing turn");
-        let turn = crate::models::turn::create_turn(
-            pool,
-            game_channels,
+        crate::events::turn_completed(
             game_id,
             engine_game.board.turn,
-            Some(frame_json),
-        )
-        .await?;
+            engine_game
+                .board
+                .snakes
+                .iter()
+                .filter(|s| !s.eliminated_cause.is_eliminated())
+                .count(),
+            move_results.iter().filter_map(|r| r.latency_ms).max(),
+            move_results.iter().filter(|r| r.timed_out).count(),
+            turn_start.elapsed().as_millis() as u64,
+        );
 
-        // Store individual snake moves with latency
-        for result in &move_results {
-            if let Ok(game_battlesnake_id) = Uuid::parse_str(&result.snake_id) {
-                crate::models::turn::create_snake_turn(
-                    pool,
-                    turn.turn_id,
-                    game_battlesnake_id,
-                    &result.direction.to_string(),
-                    result.latency_ms,
-                    result.timed_out,
-                )
-                .await?;
+        // Relaxed clock for a human game: the rest of the turn's interval
+        if human_seat.is_some() {
+            let elapsed = turn_start.elapsed();
+            if elapsed < crate::human_play::TURN_INTERVAL {
+                tokio::time::sleep(crate::human_play::TURN_INTERVAL - elapsed).await;
             }
         }
 
-        let db_write_duration = db_write_start.elapsed();
-        tracing::info!(
-            metric_type = "db_write_latency",
-            game_id = %game_id,
-            turn = engine_game.board.turn,
-            duration_ms = db_write_duration.as_millis() as u64,
-            "turn persistence latency"
-        );
-
         // Measure async scheduler jitter
         let before_yield = std::time::Instant::now();
         tokio::task::yield_now().await;
@@ -308,6 +489,9 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         );
     }
 
+    // Everything is written before the game can be finished
+    turns.flush(pool).await?;
+
     // Emit processing_overhead metric
     let total_time = game_start.elapsed();
     let total_time_ms = total_time.as_millis() as i64;
@@ -338,6 +522,7 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
         final_turn = engine_game.board.turn,
         "Game completed with persistence"
     );
+    game_span.record("final_turn", engine_game.board.turn);
 
     // Build placements: last eliminated = winner (placement 1)
     // Snakes still alive at the end go first
@@ -432,14 +617,14 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
     tx.commit()
         .await
         .wrap_err("Failed to commit game finish transaction")?;
+    crate::metrics::METRICS.games_finished.inc();
 
-    tracing::info!(
-        event_type = "game_completed",
-        game_id = %game_id,
-        final_turn = engine_game.board.turn,
-        total_ms = total_time_ms,
-        winner_battlesnake_id = ?placements.first(),
-        "game completed"
+    crate::events::game_finished(
+        game_id,
+        engine_game.board.turn,
+        placements.len(),
+        total_time_ms as u64,
+        placements.first().map(String::as_str),
     );
 
     enqueue_post_completion_jobs(app_state, game_id).await?;
@@ -450,16 +635,206 @@ pub async fn run_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
     Ok(())
 }
 
-/// Enqueue the follow-up jobs for a finished game: the leaderboard rating
-/// update and the tournament match evaluation, as applicable.
+/// What a turn's span records about its /move calls
+#[derive(Debug, PartialEq)]
+struct MoveSummary {
+    snake_ids: String,
+    /// The slowest answer and who gave it; timeouts have no latency
+    slowest: Option<(i64, String)>,
+    timed_out_snake_ids: Vec<String>,
+}
+
+fn summarize_moves(move_results: &[MoveResult]) -> MoveSummary {
+    let snake_ids = move_results
+        .iter()
+        .map(|r| r.snake_id.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    let slowest = move_results
+        .iter()
+        .filter_map(|r| r.latency_ms.map(|latency| (latency, r.snake_id.clone())))
+        .max_by_key(|(latency, _)| *latency);
+    let timed_out_snake_ids = move_results
+        .iter()
+        .filter(|r| r.timed_out)
+        .map(|r| r.snake_id.clone())
+        .collect();
+    MoveSummary {
+        snake_ids,
+        slowest,
+        timed_out_snake_ids,
+    }
+}
+
+/// Turns played but not yet written. The runner flushes them in one
+/// statement once [`AppConfig::frame_flush_turns`] have piled up or the
+/// oldest has waited [`AppConfig::frame_flush_interval_ms`], rather than
+/// paying a round trip per turn and per snake move.
+struct TurnBuffer {
+    game_id: Uuid,
+    pending: Vec<PendingTurn>,
+    oldest: Option<std::time::Instant>,
+    max_turns: usize,
+    max_delay: std::time::Duration,
+}
+
+impl TurnBuffer {
+    fn new(game_id: Uuid, config: &AppConfig) -> Self {
+        Self {
+            game_id,
+            pending: Vec::new(),
+            oldest: None,
+            max_turns: config.frame_flush_turns,
+            max_delay: std::time::Duration::from_millis(config.frame_flush_interval_ms),
+        }
+    }
+
+    fn push(&mut self, turn: PendingTurn) {
+        self.oldest.get_or_insert_with(std::time::Instant::now);
+        self.pending.push(turn);
+    }
+
+    fn is_due(&self) -> bool {
+        self.pending.len() >= self.max_turns
+            || self
+                .oldest
+                .is_some_and(|oldest| oldest.elapsed() >= self.max_delay)
+    }
+
+    async fn flush(&mut self, pool: &sqlx::PgPool) -> cja::Result<()> {
+        let Some(last) = self.pending.last() else {
+            return Ok(());
+        };
+        let last_turn = last.turn_number;
+
+        let db_write_start = std::time::Instant::now();
+        crate::models::turn::insert_turns(pool, self.game_id, &self.pending).await?;
+        tracing::info!(
+            metric_type = "db_write_latency",
+            game_id = %self.game_id,
+            turn = last_turn,
+            turns = self.pending.len(),
+            duration_ms = db_write_start.elapsed().as_millis() as u64,
+            "turn persistence latency"
+        );
+
+        self.pending.clear();
+        self.oldest = None;
+        Ok(())
+    }
+}
+
+fn record_moves(turn_span: &tracing::Span, move_results: &[MoveResult]) {
+    let summary = summarize_moves(move_results);
+    turn_span.record("snake_ids", summary.snake_ids);
+    if let Some((latency, snake_id)) = summary.slowest {
+        turn_span.record("max_latency_ms", latency);
+        turn_span.record("slowest_snake_id", snake_id);
+    }
+    turn_span.record("timeouts", summary.timed_out_snake_ids.len());
+    if !summary.timed_out_snake_ids.is_empty() {
+        turn_span.record("timed_out_snake_ids", summary.timed_out_snake_ids.join(","));
+    }
+}
+
+/// Each live snake's last move, read off its head and neck in a stored
+/// frame, for the timeout fallback on the first resumed turn
+fn last_moves_from_frame(frame: &EngineGameFrame) -> HashMap<String, Direction> {
+    frame
+        .snakes
+        .iter()
+        .filter(|s| s.death.is_none())
+        .filter_map(|s| {
+            let (head, neck) = (s.body.first()?, s.body.get(1)?);
+            let direction = match (head.x - neck.x, head.y - neck.y) {
+                (0, 1) => Direction::Up,
+                (0, -1) => Direction::Down,
+                (-1, 0) => Direction::Left,
+                (1, 0) => Direction::Right,
+                _ => return None,
+            };
+            Some((s.id.clone(), direction))
+        })
+        .collect()
+}
+
+/// Blank any shout that hits the moderation word list, before it reaches the
+/// stored frame or the other snakes, and queue it for admin review. A failed
+/// flag is logged rather than failing the game; the shout is blanked either
+/// way.
+async fn screen_shouts(
+    pool: &sqlx::PgPool,
+    game_id: Uuid,
+    words: &WordList,
+    shout_owners: &HashMap<String, Uuid>,
+    move_results: &mut [MoveResult],
+) {
+    for result in move_results.iter_mut() {
+        let Some(shout) = &result.shout else {
+            continue;
+        };
+        let Some(term) = words.find(shout) else {
+            continue;
+        };
+        if let Some(&battlesnake_id) = shout_owners.get(&result.snake_id)
+            && let Err(e) = moderation::flag(
+                pool,
+                battlesnake_id,
+                FlagKind::Shout,
+                shout,
+                term,
+                Some(game_id),
+            )
+            .await
+        {
+            tracing::warn!(
+                game_id = %game_id,
+                battlesnake_id = %battlesnake_id,
+                error = %e,
+                "Failed to flag shout for moderation"
+            );
+        }
+        result.shout = None;
+    }
+}
+
+/// The latency and shout each snake is told about on its next turn, as a
+/// stored frame recorded them
+fn snake_contexts_from_frame(frame: &EngineGameFrame) -> HashMap<String, wire::SnakeContext> {
+    frame
+        .snakes
+        .iter()
+        .map(|s| {
+            let context = wire::SnakeContext {
+                latency_ms: s.latency.parse().ok(),
+                shout: Some(s.shout.clone()).filter(|shout| !shout.is_empty()),
+            };
+            (s.id.clone(), context)
+        })
+        .collect()
+}
+
+/// Enqueue the follow-up jobs for a finished game: owner notifications,
+/// plus the leaderboard rating update and the tournament match evaluation,
+/// as applicable.
 ///
 /// Called after the finish transaction commits, and again by retries that
-/// find the game already finished. Both targets are idempotent (the rating
-/// job checks for already-applied results; match evaluation is re-entrant),
-/// so duplicate enqueues are harmless.
+/// find the game already finished. All targets are idempotent (the rating
+/// job checks for already-applied results; match evaluation is re-entrant;
+/// notifications are recorded once per event), so duplicate enqueues are
+/// harmless.
 async fn enqueue_post_completion_jobs(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
     let pool = &app_state.db;
 
+    cja::jobs::Job::enqueue(
+        crate::jobs::GameNotificationsJob { game_id },
+        app_state.clone(),
+        format!("Notifications for game {game_id}"),
+        None,
+    )
+    .await
+    .wrap_err("Failed to enqueue game notifications job")?;
+
     // Check if this is a leaderboard game and enqueue rating update
     if let Some(lb_game) =
         crate::models::leaderboard::find_leaderboard_game_by_game_id(pool, game_id).await?
@@ -510,19 +885,6 @@ async fn enqueue_post_completion_jobs(app_state: &AppState, game_id: Uuid) -> cj
     Ok(())
 }
 
-/// Human-readable label for an elimination cause, used in frame data.
-fn elimination_cause_label(cause: &EliminationCause) -> String {
-    match cause {
-        EliminationCause::NotEliminated => String::new(),
-        EliminationCause::OutOfHealth => "out-of-health".to_string(),
-        EliminationCause::OutOfBounds => "wall-collision".to_string(),
-        EliminationCause::SelfCollision => "self-collision".to_string(),
-        EliminationCause::Collision => "snake-collision".to_string(),
-        EliminationCause::HeadToHeadCollision => "head-collision".to_string(),
-        EliminationCause::Hazard => "hazard".to_string(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -675,4 +1037,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn summarize_moves_finds_slowest_and_timeouts() {
+        let result = |snake_id: &str, latency_ms: Option<i64>| MoveResult {
+            snake_id: snake_id.to_string(),
+            direction: Direction::Up,
+            latency_ms,
+            timed_out: latency_ms.is_none(),
+            error: latency_ms
+                .is_none()
+                .then_some(crate::models::turn::MoveError::Timeout),
+            shout: None,
+        };
+        let summary = summarize_moves(&[
+            result("a", Some(40)),
+            result("b", None),
+            result("c", Some(310)),
+        ]);
+        assert_eq!(
+            summary,
+            MoveSummary {
+                snake_ids: "a,b,c".to_string(),
+                slowest: Some((310, "c".to_string())),
+                timed_out_snake_ids: vec!["b".to_string()],
+            }
+        );
+
+        assert_eq!(summarize_moves(&[]).slowest, None);
+    }
+
+    #[test]
+    fn game_budget_covers_every_turn_timing_out() {
+        // 5000 turns at 500ms + 200ms overhead
+        assert_eq!(game_budget(500), std::time::Duration::from_secs(3500));
+        assert_eq!(game_budget(-1), TURN_OVERHEAD * MAX_TURNS as u32);
+    }
 }
@@ -0,0 +1,85 @@
+//! The runner's side of "play as a snake" (see
+//! [`crate::models::human_player`]).
+//!
+//! The steered seat gets no HTTP calls. Each turn its move is the heading the
+//! player last chose in the browser, and turns are paced to
+//! [`TURN_INTERVAL`] so a person can keep up; the other snakes still answer
+//! within the usual move timeout.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rules::Direction;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::engine::EngineGame;
+use crate::models::human_player;
+use crate::snake_client::MoveResult;
+
+/// Shortest a turn of a human game takes
+pub const TURN_INTERVAL: Duration = Duration::from_millis(500);
+
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+
+/// The steered seat's move, or `None` once it's been eliminated. A heading
+/// straight back into the snake's own neck is ignored, as are turns before
+/// the first key press: the snake keeps going the way it was.
+pub async fn human_move(
+    pool: &PgPool,
+    game: &EngineGame,
+    game_battlesnake_id: Uuid,
+    last_moves: &HashMap<String, Direction>,
+) -> cja::Result<Option<MoveResult>> {
+    let snake_id = game_battlesnake_id.to_string();
+    let alive = game
+        .board
+        .snakes
+        .iter()
+        .any(|s| s.id == snake_id && !s.eliminated_cause.is_eliminated());
+    if !alive {
+        return Ok(None);
+    }
+
+    let last = last_moves.get(&snake_id).copied();
+    let heading = human_player::get_heading(pool, game_battlesnake_id).await?;
+    let direction = match (heading, last) {
+        (Some(heading), Some(last)) if heading == opposite(last) => last,
+        (Some(heading), _) => heading,
+        (None, last) => last.unwrap_or(Direction::Up),
+    };
+
+    Ok(Some(MoveResult {
+        snake_id,
+        direction,
+        latency_ms: None,
+        timed_out: false,
+        error: None,
+        shout: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opposite_undoes_itself() {
+        for d in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            assert_ne!(opposite(d), d);
+            assert_eq!(opposite(opposite(d)), d);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Translations for the public pages.
+//!
+//! Each locale has a message catalog in `server/locales/<tag>.toml`, embedded
+//! at build time and flattened to dotted keys (`[leaderboard] title = ...`
+//! becomes `leaderboard.title`). English is the source catalog: a key missing
+//! from another locale falls back to it, so a page is never worse than
+//! untranslated. Placeholders are written `{name}` and filled by
+//! [`Locale::t_with`].
+//!
+//! The request's locale is a [`Locale`] extractor: the visitor's explicit
+//! choice (the [`LOCALE_COOKIE_NAME`] cookie, set from the footer picker)
+//! wins over the browser's `Accept-Language`, and everything else gets
+//! English.
+
+use std::{collections::HashMap, fmt::Display, sync::LazyLock};
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+    response::Response,
+};
+use cja::server::cookies::CookieJar;
+
+use crate::state::AppState;
+
+/// Cookie holding the visitor's chosen locale tag
+pub const LOCALE_COOKIE_NAME: &str = "arena_locale";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    PtBr,
+}
+
+static CATALOGS: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let source = match locale {
+                Locale::En => include_str!("../locales/en.toml"),
+                Locale::Es => include_str!("../locales/es.toml"),
+                Locale::PtBr => include_str!("../locales/pt-BR.toml"),
+            };
+            let table: toml::Table = toml::from_str(source)
+                .unwrap_or_else(|e| panic!("locales/{}.toml is invalid: {e}", locale.tag()));
+            let mut messages = HashMap::new();
+            flatten("", table, &mut messages);
+            (locale, messages)
+        })
+        .collect()
+});
+
+fn flatten(prefix: &str, table: toml::Table, out: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, out),
+            toml::Value::String(message) => {
+                out.insert(key, message);
+            }
+            other => panic!("locale message {key} must be a string, got {other}"),
+        }
+    }
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::PtBr];
+
+    /// BCP 47 tag, as used for `<html lang>`, the cookie and the catalog file
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::PtBr => "pt-BR",
+        }
+    }
+
+    /// The language's name in itself, for the picker
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+            Locale::PtBr => "Português (Brasil)",
+        }
+    }
+
+    /// Match a language tag by its primary subtag, so `es-MX` is Spanish and
+    /// any Portuguese gets the Brazilian catalog.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            "pt" => Some(Locale::PtBr),
+            _ => None,
+        }
+    }
+
+    /// The best supported locale for an `Accept-Language` header: highest
+    /// q-value first, then header order. English when nothing matches.
+    pub fn negotiate(accept_language: &str) -> Self {
+        let mut ranges: Vec<(f32, Locale)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let locale = Locale::from_tag(parts.next()?)?;
+                let q = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+                (q > 0.0).then_some((q, locale))
+            })
+            .collect();
+        // Stable, so equal q-values keep the browser's order
+        ranges.sort_by(|a, b| b.0.total_cmp(&a.0));
+        ranges.first().map_or(Locale::En, |(_, locale)| *locale)
+    }
+
+    /// The message for `key`, falling back to English and then to the key
+    /// itself so a typo shows up on the page rather than as an error.
+    pub fn t(self, key: &'static str) -> &'static str {
+        let lookup = |locale: Locale| CATALOGS.get(&locale)?.get(key).map(String::as_str);
+        lookup(self)
+            .or_else(|| lookup(Locale::En))
+            .unwrap_or_else(|| {
+                tracing::warn!(key, "Missing translation key");
+                key
+            })
+    }
+
+    /// A calendar date the way the locale writes it
+    pub fn format_date(self, date: chrono::DateTime<chrono::Utc>) -> String {
+        match self {
+            Locale::En => date.format("%B %-d, %Y").to_string(),
+            Locale::Es | Locale::PtBr => date.format("%d/%m/%Y").to_string(),
+        }
+    }
+
+    /// [`Locale::t`] with `{name}` placeholders filled from `args`
+    pub fn t_with(self, key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter()
+            .fold(self.t(key).to_string(), |message, (name, value)| {
+                message.replace(&format!("{{{name}}}"), &value.to_string())
+            })
+    }
+}
+
+impl FromRequestParts<AppState> for Locale {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let chosen = CookieJar::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|jar| jar.get(LOCALE_COOKIE_NAME))
+            .and_then(|cookie| Locale::from_tag(cookie.value()));
+        if let Some(locale) = chosen {
+            return Ok(locale);
+        }
+
+        Ok(parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(Locale::negotiate)
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{name}` placeholders in a message, sorted
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        let english = &CATALOGS[&Locale::En];
+        for locale in Locale::ALL {
+            for (key, message) in &CATALOGS[&locale] {
+                let source = english
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{} has {key}, which English lacks", locale.tag()));
+                assert_eq!(
+                    placeholders(message),
+                    placeholders(source),
+                    "{} {key} placeholders differ from English",
+                    locale.tag()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_falls_back_to_english() {
+        assert_eq!(Locale::Es.t("nav.leaderboards"), "Clasificaciones");
+        assert_eq!(Locale::En.t("nav.leaderboards"), "Leaderboards");
+        assert_eq!(Locale::Es.t("no.such.key"), "no.such.key");
+        assert_eq!(
+            Locale::PtBr.t_with("common.page_of", &[("page", &2), ("total", &5)]),
+            "Página 2 de 5"
+        );
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Locale::negotiate("es-MX,es;q=0.9,en;q=0.8"), Locale::Es);
+        assert_eq!(Locale::negotiate("en;q=0.5, pt-PT;q=0.9"), Locale::PtBr);
+        assert_eq!(Locale::negotiate("fr-FR,fr;q=0.9"), Locale::En);
+        assert_eq!(Locale::negotiate("de, es;q=0"), Locale::En);
+        assert_eq!(Locale::negotiate("es, en"), Locale::Es);
+        assert_eq!(Locale::negotiate(""), Locale::En);
+    }
+}
@@ -0,0 +1,170 @@
+//! Alerting on job failure spikes and queue backlogs.
+//!
+//! Failures otherwise only show up on the admin dashboard. Every
+//! [`CHECK_INTERVAL_SECS`] this check counts jobs that failed within the
+//! window and the ready-to-run queue depth, and when either crosses its
+//! threshold ([`crate::config::AppConfig::alert_job_failures_threshold`],
+//! [`crate::config::AppConfig::alert_queue_depth_threshold`]) posts the top
+//! failing job names to `ALERT_WEBHOOK_URL`. The window matches the
+//! interval, so a spike alerts once per interval for as long as it lasts.
+
+use color_eyre::eyre::{Context as _, eyre};
+
+use crate::models::job;
+use crate::state::AppState;
+
+/// Cron interval, and the failure-counting window
+pub const CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Failing job names listed in an alert
+const TOP_FAILING_NAMES: usize = 5;
+
+/// What one check found
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobHealth {
+    /// (job name, jobs failing within the window), most first
+    pub failures_by_name: Vec<(String, i64)>,
+    pub ready_jobs: i64,
+}
+
+impl JobHealth {
+    pub fn total_failures(&self) -> i64 {
+        self.failures_by_name.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// The alert text, or `None` if nothing crossed its threshold
+fn alert_message(
+    health: &JobHealth,
+    failures_threshold: i64,
+    queue_threshold: i64,
+) -> Option<String> {
+    let failures = health.total_failures();
+    let mut problems = Vec::new();
+    if failures >= failures_threshold {
+        problems.push(format!(
+            "{failures} jobs failed in the last {} minutes (threshold {failures_threshold})",
+            CHECK_INTERVAL_SECS / 60
+        ));
+    }
+    if health.ready_jobs >= queue_threshold {
+        problems.push(format!(
+            "{} jobs waiting for a worker (threshold {queue_threshold})",
+            health.ready_jobs
+        ));
+    }
+    if problems.is_empty() {
+        return None;
+    }
+
+    let mut message = format!("🚨 Arena job alert: {}", problems.join("; "));
+    if !health.failures_by_name.is_empty() {
+        message.push_str("\nTop failing jobs:");
+        for (name, count) in health.failures_by_name.iter().take(TOP_FAILING_NAMES) {
+            message.push_str(&format!("\n• {name}: {count}"));
+        }
+    }
+    Some(message)
+}
+
+/// POST to a Slack or Discord incoming webhook. Each reads its own field
+/// (`text` / `content`) and ignores the other's.
+async fn send_alert(app_state: &AppState, url: &str, message: &str) -> cja::Result<()> {
+    let response = app_state
+        .http_client
+        .post(url)
+        .json(&serde_json::json!({
+            "text": message,
+            "content": message,
+            "allowed_mentions": { "parse": [] }
+        }))
+        .send()
+        .await
+        .wrap_err("Failed to send alert webhook request")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(eyre!("Alert webhook returned {status}: {body}"));
+    }
+    Ok(())
+}
+
+/// Run one check. Called from the cron-scheduled
+/// [`crate::jobs::JobAlertCheckJob`].
+pub async fn run_job_alert_check(app_state: &AppState) -> cja::Result<()> {
+    let since = chrono::Utc::now() - chrono::Duration::seconds(CHECK_INTERVAL_SECS as i64);
+    let health = JobHealth {
+        failures_by_name: job::count_recent_failures_by_name(&app_state.db, since).await?,
+        ready_jobs: job::count_ready_jobs(&app_state.db).await?,
+    };
+
+    let Some(message) = alert_message(
+        &health,
+        app_state.config.alert_job_failures_threshold,
+        app_state.config.alert_queue_depth_threshold,
+    ) else {
+        return Ok(());
+    };
+
+    tracing::warn!(
+        event_type = "job_alert",
+        failures = health.total_failures(),
+        ready_jobs = health.ready_jobs,
+        "{message}"
+    );
+    match &app_state.config.alert_webhook_url {
+        Some(url) => send_alert(app_state, url, &message).await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(failures: &[(&str, i64)], ready_jobs: i64) -> JobHealth {
+        JobHealth {
+            failures_by_name: failures
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect(),
+            ready_jobs,
+        }
+    }
+
+    #[test]
+    fn no_alert_below_thresholds() {
+        assert_eq!(
+            alert_message(&health(&[("GameRunnerJob", 3)], 20), 10, 100),
+            None
+        );
+    }
+
+    #[test]
+    fn failure_spike_lists_top_job_names() {
+        let message = alert_message(
+            &health(
+                &[
+                    ("GameRunnerJob", 8),
+                    ("LeaderboardRatingUpdateJob", 3),
+                    ("GameBackupJob", 1),
+                ],
+                20,
+            ),
+            10,
+            100,
+        )
+        .unwrap();
+        assert!(message.contains("12 jobs failed in the last 15 minutes"));
+        assert!(!message.contains("waiting for a worker"));
+        assert!(message.contains("\n• GameRunnerJob: 8\n• LeaderboardRatingUpdateJob: 3"));
+    }
+
+    #[test]
+    fn queue_backlog_alerts_without_failures() {
+        let message = alert_message(&health(&[], 500), 10, 100).unwrap();
+        assert!(message.contains("500 jobs waiting for a worker"));
+        assert!(!message.contains("Top failing jobs"));
+    }
+}
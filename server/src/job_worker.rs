@@ -0,0 +1,195 @@
+//! Job worker: claims due rows from the `jobs` table and runs them.
+//!
+//! Any number of workers, in any number of processes, can drain the queue
+//! together. A claim locks the row with `FOR UPDATE SKIP LOCKED`, so two
+//! workers never take the same job, and only until a deadline
+//! ([`crate::config::JobConfig::visibility_timeout_secs`] out). While the
+//! job runs the worker heartbeats, pushing the deadline along. A worker that
+//! dies stops heartbeating, and its job becomes claimable again once the
+//! deadline passes - a minute or so, rather than the hours a fixed lock
+//! timeout had to allow for the longest game.
+//!
+//! A worker that finds at a heartbeat that another has taken its job over
+//! (it stalled past its own deadline) drops its run, so a [`GameRunnerJob`]
+//! is never played by two workers for longer than one heartbeat.
+//!
+//! [`GameRunnerJob`]: crate::jobs::GameRunnerJob
+
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+use futures::FutureExt as _;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument as _;
+
+use crate::jobs;
+use crate::models::job::{self, ClaimedJob};
+use crate::state::AppState;
+
+/// Heartbeats per visibility timeout, so a couple of slow or failed ones in
+/// a row don't cost a live worker its job
+const HEARTBEATS_PER_TIMEOUT: u32 = 3;
+
+/// Claim and run jobs until `token` is cancelled. A job already running
+/// when it is cancelled is finished first, which is what the shutdown drain
+/// waits on.
+pub(crate) async fn run_worker(
+    app_state: AppState,
+    worker_id: String,
+    token: CancellationToken,
+) -> cja::Result<()> {
+    let poll_interval = Duration::from_millis(app_state.config.job.poll_interval_ms);
+    let visibility = Duration::from_secs(app_state.config.job.visibility_timeout_secs);
+
+    while !token.is_cancelled() {
+        match job::claim_next_job(&app_state.db, &worker_id, visibility).await {
+            Ok(Some(claimed)) => {
+                let span = tracing::info_span!(
+                    "job",
+                    job_id = %claimed.job_id,
+                    job_name = %claimed.name,
+                    worker_id = %worker_id,
+                );
+                run_claimed(&app_state, &worker_id, claimed, visibility)
+                    .instrument(span)
+                    .await;
+                // Go straight back for the next job while there is work
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!(error = ?e, worker_id = %worker_id, "Failed to claim a job"),
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(poll_interval) => {}
+            () = token.cancelled() => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one claimed job, heartbeating until it finishes, then delete it or
+/// record the failure
+async fn run_claimed(
+    app_state: &AppState,
+    worker_id: &str,
+    claimed: ClaimedJob,
+    visibility: Duration,
+) {
+    let pool = &app_state.db;
+    let started = std::time::Instant::now();
+
+    // A panicking job fails like any other rather than taking the worker
+    // down with it
+    let run = AssertUnwindSafe(jobs::run_job(
+        &claimed.name,
+        claimed.payload.clone(),
+        app_state.clone(),
+    ))
+    .catch_unwind();
+    tokio::pin!(run);
+
+    let mut heartbeat = tokio::time::interval(visibility / HEARTBEATS_PER_TIMEOUT);
+    // The claim itself set the first deadline
+    heartbeat.tick().await;
+
+    let result = loop {
+        tokio::select! {
+            result = &mut run => break result,
+            _ = heartbeat.tick() => {
+                match job::extend_job_lock(pool, claimed.job_id, worker_id, visibility).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::warn!(
+                            event_type = "job_lock_lost",
+                            "Another worker took this job over; abandoning the run"
+                        );
+                        return;
+                    }
+                    // Try again next beat; the deadline leaves room for it
+                    Err(e) => tracing::warn!(error = ?e, "Job heartbeat failed"),
+                }
+            }
+        }
+    };
+
+    let error = match result {
+        Ok(Ok(())) => {
+            tracing::info!(
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "Job finished"
+            );
+            if let Err(e) = job::complete_job(pool, claimed.job_id, worker_id).await {
+                tracing::error!(error = ?e, "Failed to delete finished job");
+            }
+            return;
+        }
+        Ok(Err(e)) => format!("{e:?}"),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            format!("Job panicked: {message}")
+        }
+    };
+
+    let max_retries = app_state.config.job.max_retries;
+    match job::fail_job(pool, claimed.job_id, worker_id, &error, max_retries).await {
+        Ok(will_retry) => tracing::error!(
+            error = %error,
+            error_count = claimed.error_count + 1,
+            will_retry,
+            "Job failed"
+        ),
+        Err(e) => tracing::error!(error = ?e, job_error = %error, "Failed to record job failure"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn worker_runs_due_jobs_and_records_failures(pool: sqlx::PgPool) -> cja::Result<()> {
+        let state = AppState::test_from_pool(pool.clone());
+        for (name, payload) in [("NoopJob", "null"), ("NoSuchJob", "{}")] {
+            sqlx::query(
+                "INSERT INTO jobs (job_id, name, payload, priority, run_at, context)
+                 VALUES ($1, $2, $3::jsonb, 0, NOW(), 'test')",
+            )
+            .bind(uuid::Uuid::new_v4())
+            .bind(name)
+            .bind(payload)
+            .execute(&pool)
+            .await?;
+        }
+
+        let token = CancellationToken::new();
+        let worker = tokio::spawn(run_worker(state, "test-worker".to_string(), token.clone()));
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        token.cancel();
+        worker.await??;
+
+        // The noop ran and was deleted; the unknown job failed and waits
+        // for its retry, unlocked
+        let failing = job::list_failing_jobs(&pool, None, 50).await?;
+        assert_eq!(failing.len(), 1);
+        assert_eq!(failing[0].name, "NoSuchJob");
+        assert_eq!(failing[0].locked_at, None);
+        assert!(
+            failing[0]
+                .last_error_message
+                .as_deref()
+                .is_some_and(|m| m.contains("Unknown job type"))
+        );
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(remaining, 1);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::state::AppState;
 
 use cja::jobs::Job;
+use color_eyre::eyre::{Context as _, eyre};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -26,9 +27,34 @@ impl Job<AppState> for GameRunnerJob {
     const NAME: &'static str = "GameRunnerJob";
 
     async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        // Picked up after shutdown began: leave the game for the next process
+        if app_state.shutdown.is_requested() {
+            crate::shutdown::checkpoint_game(&app_state, self.game_id).await?;
+            return Ok(());
+        }
         // Run the game with HTTP calls to snake APIs, turn-by-turn persistence, and WebSocket notifications
-        crate::game_runner::run_game(&app_state, self.game_id).await?;
-        Ok(())
+        let mut move_timeout_ms = app_state.settings().await.move_timeout_ms();
+        // Human games pace each turn to at least `human_play::TURN_INTERVAL`,
+        // so only they get it as a floor; bot games keep the move timeout
+        if crate::models::human_player::get_for_game(&app_state.db, self.game_id)
+            .await?
+            .is_some()
+        {
+            move_timeout_ms =
+                move_timeout_ms.max(crate::human_play::TURN_INTERVAL.as_millis() as i64);
+        }
+        let budget = crate::game_runner::game_budget(move_timeout_ms);
+        match tokio::time::timeout(
+            budget,
+            crate::game_runner::run_game(&app_state, self.game_id),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(_) => {
+                crate::game_runner::end_over_budget_game(&app_state, self.game_id, budget).await
+            }
+        }
     }
 }
 
@@ -124,8 +150,44 @@ impl Job<AppState> for LeaderboardRatingUpdateJob {
     }
 }
 
+/// Job to apply the rating updates a leaderboard freeze deferred.
+/// Enqueued when an admin unfreezes the leaderboard.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ApplyDeferredRatingsJob {
+    pub leaderboard_id: Uuid,
+}
+
+#[async_trait::async_trait]
+impl Job<AppState> for ApplyDeferredRatingsJob {
+    const NAME: &'static str = "ApplyDeferredRatingsJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::leaderboard_ratings::apply_deferred_ratings(&app_state, self.leaderboard_id).await?;
+        Ok(())
+    }
+}
+
+/// Job to raise notifications about how the snakes in a finished game are
+/// doing (timeout streaks). Enqueued for every game once it finishes;
+/// notifications are recorded once per event, so a rerun is harmless.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GameNotificationsJob {
+    pub game_id: Uuid,
+}
+
+#[async_trait::async_trait]
+impl Job<AppState> for GameNotificationsJob {
+    const NAME: &'static str = "GameNotificationsJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::notifications::notify_timeout_streaks(&app_state, self.game_id).await?;
+        Ok(())
+    }
+}
+
 /// Job to kick off every ready match in a tournament's current round.
-/// Enqueued when the owner clicks "Run Round".
+/// Enqueued when the owner clicks "Run Round", or by round progression when
+/// the tournament has auto-advance on.
 ///
 /// `round` pins the job to the round the owner saw when they clicked: if the
 /// tournament has moved on (or was reset and restarted) by the time the job
@@ -202,7 +264,7 @@ impl Job<AppState> for StuckMatchSweeperJob {
 }
 
 /// Cron job that prunes rate-limit bookkeeping (game_creation_attempts,
-/// claim_attempts) past its retention window. The limits record every
+/// claim_attempts, api_rate_limit_buckets) past its retention window. The limits record every
 /// attempt — including rejected ones — so without this the tables grow
 /// without bound.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -235,8 +297,209 @@ impl Job<AppState> for SnakeHealthSweeperJob {
     }
 }
 
-cja::impl_job_registry!(
-    AppState,
+/// Cron job that re-enqueues (or, past the retry budget, ends) games stuck
+/// in `running`; see [`crate::stuck_game_watchdog`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StuckGameWatchdogJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for StuckGameWatchdogJob {
+    const NAME: &'static str = "StuckGameWatchdogJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::stuck_game_watchdog::run_watchdog(&app_state).await?;
+        Ok(())
+    }
+}
+
+/// Cron job that deletes finished games past `GAME_RETENTION_DAYS` that no
+/// leaderboard, tournament, or saved game needs. A no-op when retention is
+/// unset; see [`crate::models::game::purge_expired_games`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GameRetentionJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for GameRetentionJob {
+    const NAME: &'static str = "GameRetentionJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        let Some(days) = app_state.config.game_retention_days else {
+            return Ok(());
+        };
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+        let purged = crate::models::game::purge_expired_games(&app_state.db, cutoff).await?;
+        crate::frame_archive::delete_archives(&app_state, &purged.archived).await;
+        tracing::info!(
+            deleted = purged.count,
+            archives_deleted = purged.archived.len(),
+            retention_days = days,
+            "Purged expired games"
+        );
+        Ok(())
+    }
+}
+
+/// Cron job that keeps the monthly partitions of the per-turn tables
+/// created ahead of time; see [`crate::models::partition`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PartitionMaintenanceJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for PartitionMaintenanceJob {
+    const NAME: &'static str = "PartitionMaintenanceJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        let created = crate::models::partition::ensure_monthly_partitions(&app_state.db).await?;
+        if created > 0 {
+            tracing::info!(created, "Created monthly partitions");
+        }
+        Ok(())
+    }
+}
+
+/// Cron job that enqueues an [`ArchiveGameFramesJob`] for each finished game
+/// past `FRAME_ARCHIVE_AFTER_DAYS`; see [`crate::frame_archive`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FrameArchiveDiscoveryJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for FrameArchiveDiscoveryJob {
+    const NAME: &'static str = "FrameArchiveDiscoveryJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::frame_archive::run_archive_discovery(&app_state).await?;
+        Ok(())
+    }
+}
+
+/// Cron job that counts orphaned records and, with `ORPHAN_REPAIR_ENABLED`,
+/// repairs them; see [`crate::consistency_check`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ConsistencyCheckJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for ConsistencyCheckJob {
+    const NAME: &'static str = "ConsistencyCheckJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::consistency_check::run_consistency_check(&app_state).await?;
+        Ok(())
+    }
+}
+
+/// Cron job that alerts `ALERT_WEBHOOK_URL` when job failures or the queue
+/// backlog cross their thresholds; see [`crate::job_alerts`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct JobAlertCheckJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for JobAlertCheckJob {
+    const NAME: &'static str = "JobAlertCheckJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::job_alerts::run_job_alert_check(&app_state).await?;
+        Ok(())
+    }
+}
+
+/// Cron job to persist a snapshot of the admin metrics for the stats history
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MetricSnapshotJob;
+
+#[async_trait::async_trait]
+impl Job<AppState> for MetricSnapshotJob {
+    const NAME: &'static str = "MetricSnapshotJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::routes::admin::capture_metric_snapshot(&app_state.db, &app_state.read_db).await?;
+        Ok(())
+    }
+}
+
+/// Job that deletes a user's account once they've confirmed it in settings;
+/// see [`crate::models::account_deletion`]. A no-op for accounts already
+/// deleted, so retries are safe.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DeleteAccountJob {
+    pub user_id: Uuid,
+}
+
+#[async_trait::async_trait]
+impl Job<AppState> for DeleteAccountJob {
+    const NAME: &'static str = "DeleteAccountJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        if crate::models::account_deletion::delete_account(&app_state.db, self.user_id).await? {
+            tracing::info!(user_id = %self.user_id, "Deleted account");
+        }
+        Ok(())
+    }
+}
+
+/// Job that posts one embed to a user's own Discord webhook. Enqueued by
+/// [`crate::notifications`] for each game result, rank move, and health
+/// pause the owner asked to hear about.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UserWebhookJob {
+    pub webhook_url: String,
+    pub embed: crate::discord::Embed,
+}
+
+#[async_trait::async_trait]
+impl Job<AppState> for UserWebhookJob {
+    const NAME: &'static str = "UserWebhookJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        app_state
+            .discord
+            .send_embed(&self.webhook_url, &self.embed)
+            .await
+    }
+}
+
+/// Job to move one finished game's frames from Postgres to GCS.
+/// Enqueued by FrameArchiveDiscoveryJob.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ArchiveGameFramesJob {
+    pub game_id: Uuid,
+}
+
+#[async_trait::async_trait]
+impl Job<AppState> for ArchiveGameFramesJob {
+    const NAME: &'static str = "ArchiveGameFramesJob";
+
+    async fn run(&self, app_state: AppState) -> cja::Result<()> {
+        crate::frame_archive::archive_game_frames(&app_state, self.game_id).await?;
+        Ok(())
+    }
+}
+
+/// Registers every job with cja (for the Eyes boot manifest) and generates
+/// [`run_job`], which [`crate::job_worker`] uses to run a claimed row, from
+/// the one list.
+macro_rules! job_registry {
+    ($($job:ty),* $(,)?) => {
+        cja::impl_job_registry!(AppState, $($job),*);
+
+        /// Run the job named `name` with its stored payload
+        pub(crate) async fn run_job(
+            name: &str,
+            payload: serde_json::Value,
+            app_state: AppState,
+        ) -> cja::Result<()> {
+            $(
+                if name == <$job as Job<AppState>>::NAME {
+                    let job: $job = serde_json::from_value(payload)
+                        .wrap_err_with(|| format!("Invalid payload for {name}"))?;
+                    return job.run(app_state).await;
+                }
+            )*
+            Err(eyre!("Unknown job type: {name}"))
+        }
+    };
+}
+
+job_registry!(
     NoopJob,
     GameRunnerJob,
     GameBackupJob,
@@ -244,10 +507,22 @@ cja::impl_job_registry!(
     HistoricalBackupDiscoveryJob,
     LeaderboardMatchmakerJob,
     LeaderboardRatingUpdateJob,
+    ApplyDeferredRatingsJob,
+    GameNotificationsJob,
     RunTournamentRoundJob,
     RunMatchJob,
     UpdateTournamentStatusJob,
     StuckMatchSweeperJob,
     RateLimitPruneJob,
-    SnakeHealthSweeperJob
+    SnakeHealthSweeperJob,
+    StuckGameWatchdogJob,
+    GameRetentionJob,
+    PartitionMaintenanceJob,
+    FrameArchiveDiscoveryJob,
+    ArchiveGameFramesJob,
+    ConsistencyCheckJob,
+    JobAlertCheckJob,
+    MetricSnapshotJob,
+    DeleteAccountJob,
+    UserWebhookJob
 );
@@ -11,8 +11,14 @@ use crate::{
     state::AppState,
 };
 
-/// Run the matchmaker for all active leaderboards
+/// Run the matchmaker for all active leaderboards. Skipped entirely while
+/// maintenance mode is on.
 pub async fn run_matchmaker(app_state: &AppState) -> cja::Result<()> {
+    if app_state.maintenance().await.enabled {
+        tracing::info!("Maintenance mode is on, skipping matchmaker run");
+        return Ok(());
+    }
+
     let pool = &app_state.db;
 
     let leaderboards = leaderboard::get_active_leaderboards(pool)
@@ -20,6 +26,10 @@ pub async fn run_matchmaker(app_state: &AppState) -> cja::Result<()> {
         .wrap_err("Failed to fetch active leaderboards")?;
 
     for lb in &leaderboards {
+        if lb.frozen_at.is_some() {
+            tracing::debug!(leaderboard_id = %lb.leaderboard_id, "Leaderboard is frozen, skipping");
+            continue;
+        }
         if let Err(e) = run_matchmaker_for_leaderboard(app_state, lb.leaderboard_id).await {
             tracing::error!(
                 leaderboard_id = %lb.leaderboard_id,
@@ -56,7 +66,23 @@ async fn run_matchmaker_for_leaderboard(
     // Calculate how many games to create this run
     // Derived from shared cron interval constant to avoid manual sync bugs
     let runs_per_day = (24 * 60 * 60 / MATCHMAKER_INTERVAL_SECS) as i32;
-    let games_per_run = ((GAMES_PER_DAY + runs_per_day - 1) / runs_per_day).max(1);
+    let base_games_per_run = ((GAMES_PER_DAY + runs_per_day - 1) / runs_per_day).max(1);
+
+    // Scaled and capped by the admin runtime settings
+    let settings = app_state.settings().await;
+    let mut games_per_run =
+        (base_games_per_run as f64 * settings.matchmaker_multiplier()).ceil() as i32;
+    if let Some(max_active) = settings.max_active_games() {
+        let active = game::count_active_games(pool).await?;
+        games_per_run = games_per_run.min((max_active - active).max(0) as i32);
+    }
+    if games_per_run == 0 {
+        tracing::info!(
+            leaderboard_id = %leaderboard_id,
+            "Runtime settings allow no new games this run, skipping"
+        );
+        return Ok(());
+    }
 
     tracing::info!(
         leaderboard_id = %leaderboard_id,
@@ -83,6 +109,7 @@ async fn run_matchmaker_for_leaderboard(
             CreateGame {
                 board_size: GameBoardSize::Medium, // 11x11
                 game_type: GameType::Standard,
+                run_tag: None,
             },
         )
         .await
@@ -127,6 +154,14 @@ async fn run_matchmaker_for_leaderboard(
         .await
         .wrap_err("Failed to enqueue game runner job")?;
 
+        crate::events::game_created(
+            game.game_id,
+            &game.board_size,
+            &game.game_type,
+            selected.len(),
+            crate::events::GameSource::Leaderboard,
+            None,
+        );
         tracing::info!(
             leaderboard_id = %leaderboard_id,
             game_id = %game.game_id,
@@ -143,7 +178,7 @@ async fn run_matchmaker_for_leaderboard(
 ///
 /// TODO: Add recently-matched deprioritization to prevent the same group of snakes
 /// from being matched repeatedly in low-volume periods.
-fn select_match(
+pub fn select_match(
     rng: &mut impl rand::Rng,
     entries: &[LeaderboardEntry],
     match_size: usize,
@@ -6,7 +6,7 @@ use crate::{
         game_battlesnake,
         leaderboard::{self, LeaderboardGame},
     },
-    scoring::{GameResultEntry, GameResultEvent},
+    scoring::{GameResultEntry, GameResultEvent, ScoringRegistry},
     state::AppState,
 };
 
@@ -35,15 +35,23 @@ pub async fn update_ratings(app_state: &AppState, leaderboard_game_id: Uuid) ->
     }
 
     // Fetch the leaderboard game (outside transaction — immutable data)
-    let lb_game = sqlx::query_as::<_, LeaderboardGame>(
+    let Some(lb_game) = sqlx::query_as::<_, LeaderboardGame>(
         "SELECT leaderboard_game_id, leaderboard_id, game_id, created_at
          FROM leaderboard_games
          WHERE leaderboard_game_id = $1",
     )
     .bind(leaderboard_game_id)
-    .fetch_one(pool)
+    .fetch_optional(pool)
     .await
-    .wrap_err("Failed to fetch leaderboard game")?;
+    .wrap_err("Failed to fetch leaderboard game")?
+    else {
+        // An admin deleted the game before its ratings job ran
+        tracing::info!(
+            leaderboard_game_id = %leaderboard_game_id,
+            "Leaderboard game no longer exists, skipping"
+        );
+        return Ok(());
+    };
 
     // Fetch all game_battlesnakes with their placements (outside transaction — immutable after game finishes)
     let game_snakes = game_battlesnake::get_battlesnakes_by_game_id(pool, lb_game.game_id).await?;
@@ -81,6 +89,30 @@ pub async fn update_ratings(app_state: &AppState, leaderboard_game_id: Uuid) ->
         return Ok(());
     }
 
+    // A frozen leaderboard defers the update, marking the game so unfreezing
+    // applies it via `apply_deferred_ratings`. FOR SHARE makes a concurrent
+    // freeze wait for this transaction, so an update either lands before the
+    // freeze or is deferred by it.
+    let frozen_at = sqlx::query_scalar!(
+        "SELECT frozen_at FROM leaderboards WHERE leaderboard_id = $1 FOR SHARE",
+        lb_game.leaderboard_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .wrap_err("Failed to check leaderboard freeze")?;
+    if frozen_at.is_some() {
+        tracing::info!(
+            leaderboard_game_id = %leaderboard_game_id,
+            leaderboard_id = %lb_game.leaderboard_id,
+            "Leaderboard is frozen, deferring rating update"
+        );
+        leaderboard::mark_rating_deferred(&mut tx, leaderboard_game_id).await?;
+        tx.commit()
+            .await
+            .wrap_err("Failed to commit deferred rating update")?;
+        return Ok(());
+    }
+
     // Look up each snake's leaderboard entry with FOR UPDATE to lock the rows
     let mut entries_with_placements: Vec<(leaderboard::LeaderboardEntry, i32, Uuid)> = Vec::new();
 
@@ -143,15 +175,76 @@ pub async fn update_ratings(app_state: &AppState, leaderboard_game_id: Uuid) ->
             .collect(),
     };
 
+    // Snapshot the top of the leaderboard and the game's entries either
+    // side of the update, so owners can hear how the game moved them
+    let entry_ids: Vec<Uuid> = event
+        .results
+        .iter()
+        .map(|r| r.leaderboard_entry_id)
+        .collect();
+    let top_before = leaderboard::get_entry_ids_within_rank(
+        &mut *tx,
+        lb_game.leaderboard_id,
+        crate::notifications::MILESTONE_RANK,
+    )
+    .await?;
+    let standings_before = leaderboard::get_entry_standings(&mut *tx, &entry_ids).await?;
+
     // Run all scoring algorithms
     for algo in app_state.scoring.algorithms() {
         algo.process_game_result(&mut tx, &event).await?;
     }
 
+    let top_after = leaderboard::get_entry_ids_within_rank(
+        &mut *tx,
+        lb_game.leaderboard_id,
+        crate::notifications::MILESTONE_RANK,
+    )
+    .await?;
+    let standings_after = leaderboard::get_entry_standings(&mut *tx, &entry_ids).await?;
+
     // Commit the transaction — all rating updates are atomic
     tx.commit()
         .await
         .wrap_err("Failed to commit rating update transaction")?;
+    app_state.invalidate_leaderboard(lb_game.leaderboard_id);
+
+    // The ratings are in; a failed notification mustn't fail (and retry)
+    // the update
+    if let Err(e) = crate::notifications::notify_rank_milestones(
+        app_state,
+        leaderboard_game_id,
+        &top_before,
+        &top_after,
+    )
+    .await
+    {
+        tracing::warn!(
+            leaderboard_game_id = %leaderboard_game_id,
+            error = ?e,
+            "Failed to send rank milestone notifications"
+        );
+    }
+    let placements: Vec<(Uuid, i32)> = event
+        .results
+        .iter()
+        .map(|r| (r.leaderboard_entry_id, r.placement))
+        .collect();
+    if let Err(e) = crate::notifications::notify_discord_game_results(
+        app_state,
+        lb_game.game_id,
+        &placements,
+        &standings_before,
+        &standings_after,
+    )
+    .await
+    {
+        tracing::warn!(
+            leaderboard_game_id = %leaderboard_game_id,
+            error = ?e,
+            "Failed to send Discord game result notifications"
+        );
+    }
 
     tracing::info!(
         leaderboard_game_id = %leaderboard_game_id,
@@ -162,3 +255,330 @@ pub async fn update_ratings(app_state: &AppState, leaderboard_game_id: Uuid) ->
 
     Ok(())
 }
+
+/// What [`reverse_ratings`] did
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reversal {
+    /// How many entries were adjusted; zero if the game was never rated
+    Reversed(usize),
+    /// An entry in the game has been rated in a later game, so subtracting
+    /// this game's deltas wouldn't restore its rating. The game's results
+    /// have already been deleted in the transaction: roll it back.
+    LaterGamesRated,
+}
+
+/// Undo a leaderboard game's rating updates across every scoring algorithm
+/// and delete its results, within the caller's transaction. Only exact while
+/// the game is still the latest rated game for every entry in it, so it's
+/// refused otherwise.
+pub async fn reverse_ratings(
+    conn: &mut sqlx::PgConnection,
+    scoring: &ScoringRegistry,
+    leaderboard_game_id: Uuid,
+) -> cja::Result<Reversal> {
+    // Locks the entries, so no later game can be rated while we check
+    let results = leaderboard::take_results_for_leaderboard_game(conn, leaderboard_game_id).await?;
+    let Some(rated_at) = results.iter().map(|r| r.created_at).min() else {
+        return Ok(Reversal::Reversed(0));
+    };
+    let entry_ids: Vec<Uuid> = results.iter().map(|r| r.leaderboard_entry_id).collect();
+    if leaderboard::has_results_since(conn, &entry_ids, rated_at).await? {
+        return Ok(Reversal::LaterGamesRated);
+    }
+
+    for algo in scoring.algorithms() {
+        algo.reverse_game_result(conn, &results).await?;
+    }
+
+    Ok(Reversal::Reversed(results.len()))
+}
+
+/// Apply every rating update a freeze deferred, one game at a time in the
+/// order the games finished. Enqueued on unfreeze. A game that fails is
+/// logged and skipped rather than holding up the rest; it stays unrated, so
+/// the consistency check picks it up.
+pub async fn apply_deferred_ratings(app_state: &AppState, leaderboard_id: Uuid) -> cja::Result<()> {
+    let pending = leaderboard::get_deferred_games(&app_state.db, leaderboard_id).await?;
+    let mut failed = 0;
+    for leaderboard_game_id in &pending {
+        if let Err(e) = update_ratings(app_state, *leaderboard_game_id).await {
+            failed += 1;
+            tracing::error!(
+                leaderboard_id = %leaderboard_id,
+                leaderboard_game_id = %leaderboard_game_id,
+                error = ?e,
+                "Failed to apply deferred rating update"
+            );
+        }
+    }
+
+    tracing::info!(
+        leaderboard_id = %leaderboard_id,
+        applied = pending.len() - failed,
+        failed,
+        "Applied deferred rating updates"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    /// A finished two-snake game on a fresh leaderboard; returns
+    /// (leaderboard_id, leaderboard_game_id)
+    async fn finished_leaderboard_game(pool: &PgPool) -> cja::Result<(Uuid, Uuid)> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Season') RETURNING leaderboard_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'finished')
+             RETURNING game_id",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        for placement in 1..=2 {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, $2, 'http://example.com')
+                 RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(format!("snake-{placement}"))
+            .fetch_one(pool)
+            .await?;
+            let entry =
+                leaderboard::get_or_create_entry(pool, leaderboard_id, battlesnake_id).await?;
+            sqlx::query(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id, placement, leaderboard_entry_id)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .bind(placement)
+            .bind(entry.leaderboard_entry_id)
+            .execute(pool)
+            .await?;
+        }
+
+        let lb_game = leaderboard::create_leaderboard_game(pool, leaderboard_id, game_id).await?;
+        Ok((leaderboard_id, lb_game.leaderboard_game_id))
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn frozen_leaderboard_defers_ratings_until_unfrozen(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let mut registry = crate::scoring::ScoringRegistry::new();
+        registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
+        app_state.scoring = std::sync::Arc::new(registry);
+
+        let (leaderboard_id, leaderboard_game_id) = finished_leaderboard_game(&pool).await?;
+        leaderboard::set_frozen(&pool, leaderboard_id, true).await?;
+
+        update_ratings(&app_state, leaderboard_game_id).await?;
+        assert_eq!(
+            leaderboard::get_deferred_games(&pool, leaderboard_id).await?,
+            vec![leaderboard_game_id]
+        );
+
+        leaderboard::set_frozen(&pool, leaderboard_id, false).await?;
+        apply_deferred_ratings(&app_state, leaderboard_id).await?;
+        assert!(
+            leaderboard::get_deferred_games(&pool, leaderboard_id)
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn rating_update_invalidates_cached_standings(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let mut registry = crate::scoring::ScoringRegistry::new();
+        registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
+        app_state.scoring = std::sync::Arc::new(registry);
+        app_state.standings_cache = std::sync::Arc::new(crate::cache::TtlMap::new(
+            std::time::Duration::from_secs(60),
+            8,
+        ));
+
+        let (leaderboard_id, leaderboard_game_id) = finished_leaderboard_game(&pool).await?;
+        // Both snakes already ranked, so they show in the standings
+        sqlx::query("UPDATE leaderboard_entries SET games_played = $1")
+            .bind(leaderboard::MIN_GAMES_FOR_RANKING)
+            .execute(&pool)
+            .await?;
+        let games_played = |entries: &[leaderboard::RankedEntry]| {
+            entries.iter().map(|e| e.games_played).collect::<Vec<_>>()
+        };
+        let sort = leaderboard::LeaderboardSort::Rating;
+        let before = app_state.standings(leaderboard_id, sort, None).await?;
+        let min = leaderboard::MIN_GAMES_FOR_RANKING;
+        assert_eq!(games_played(&before), vec![min, min]);
+
+        update_ratings(&app_state, leaderboard_game_id).await?;
+
+        let after = app_state.standings(leaderboard_id, sort, None).await?;
+        assert_eq!(games_played(&after), vec![min + 1, min + 1]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn unfreeze_applies_only_deferred_games(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let mut registry = crate::scoring::ScoringRegistry::new();
+        registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
+        app_state.scoring = std::sync::Arc::new(registry);
+
+        // A game whose rating job never ran, from before any freeze
+        let (leaderboard_id, stale_game_id) = finished_leaderboard_game(&pool).await?;
+
+        // The same snakes play again while the leaderboard is frozen
+        leaderboard::set_frozen(&pool, leaderboard_id, true).await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'finished')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO game_battlesnakes (game_id, battlesnake_id, placement, leaderboard_entry_id)
+             SELECT $1, gb.battlesnake_id, gb.placement, gb.leaderboard_entry_id
+             FROM game_battlesnakes gb
+             JOIN leaderboard_games lg ON lg.game_id = gb.game_id
+             WHERE lg.leaderboard_game_id = $2",
+        )
+        .bind(game_id)
+        .bind(stale_game_id)
+        .execute(&pool)
+        .await?;
+        let deferred_game_id = leaderboard::create_leaderboard_game(&pool, leaderboard_id, game_id)
+            .await?
+            .leaderboard_game_id;
+        update_ratings(&app_state, deferred_game_id).await?;
+
+        leaderboard::set_frozen(&pool, leaderboard_id, false).await?;
+        apply_deferred_ratings(&app_state, leaderboard_id).await?;
+
+        let rated: Vec<Uuid> =
+            sqlx::query_scalar("SELECT DISTINCT leaderboard_game_id FROM leaderboard_game_results")
+                .fetch_all(&pool)
+                .await?;
+        assert_eq!(rated, vec![deferred_game_id]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn reverse_ratings_refuses_games_with_later_rated_games(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let mut registry = crate::scoring::ScoringRegistry::new();
+        registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
+        app_state.scoring = std::sync::Arc::new(registry);
+
+        let (leaderboard_id, first_game_id) = finished_leaderboard_game(&pool).await?;
+        update_ratings(&app_state, first_game_id).await?;
+
+        // The same snakes play and are rated again
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'finished')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO game_battlesnakes (game_id, battlesnake_id, placement, leaderboard_entry_id)
+             SELECT $1, gb.battlesnake_id, 3 - gb.placement, gb.leaderboard_entry_id
+             FROM game_battlesnakes gb
+             JOIN leaderboard_games lg ON lg.game_id = gb.game_id
+             WHERE lg.leaderboard_game_id = $2",
+        )
+        .bind(game_id)
+        .bind(first_game_id)
+        .execute(&pool)
+        .await?;
+        let second_game_id = leaderboard::create_leaderboard_game(&pool, leaderboard_id, game_id)
+            .await?
+            .leaderboard_game_id;
+        update_ratings(&app_state, second_game_id).await?;
+        let rated = leaderboard::get_active_entries(&pool, leaderboard_id).await?;
+
+        // The first game's deltas were computed from ratings the second game
+        // has since moved, so it can't be reversed
+        let mut tx = pool.begin().await?;
+        assert_eq!(
+            reverse_ratings(&mut tx, &app_state.scoring, first_game_id).await?,
+            Reversal::LaterGamesRated
+        );
+        drop(tx);
+        let after = leaderboard::get_active_entries(&pool, leaderboard_id).await?;
+        for (entry, original) in after.iter().zip(&rated) {
+            assert_eq!(entry.leaderboard_entry_id, original.leaderboard_entry_id);
+            assert_eq!(entry.mu, original.mu);
+            assert_eq!(entry.games_played, 2);
+        }
+
+        // The latest game still can be
+        let mut tx = pool.begin().await?;
+        assert_eq!(
+            reverse_ratings(&mut tx, &app_state.scoring, second_game_id).await?,
+            Reversal::Reversed(2)
+        );
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn reverse_ratings_restores_sole_game_entries(pool: PgPool) -> cja::Result<()> {
+        let mut app_state = AppState::test_from_pool(pool.clone());
+        let mut registry = crate::scoring::ScoringRegistry::new();
+        registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
+        registry.register(Box::new(crate::scoring::win_rate::WinRateScoring));
+        app_state.scoring = std::sync::Arc::new(registry);
+
+        let (leaderboard_id, leaderboard_game_id) = finished_leaderboard_game(&pool).await?;
+        let before = leaderboard::get_active_entries(&pool, leaderboard_id).await?;
+        update_ratings(&app_state, leaderboard_game_id).await?;
+
+        let mut tx = pool.begin().await?;
+        let reversed = reverse_ratings(&mut tx, &app_state.scoring, leaderboard_game_id).await?;
+        tx.commit().await?;
+        assert_eq!(reversed, Reversal::Reversed(2));
+
+        // It was each entry's only game, so reversal is exact
+        let after = leaderboard::get_active_entries(&pool, leaderboard_id).await?;
+        for entry in &after {
+            let original = before
+                .iter()
+                .find(|e| e.leaderboard_entry_id == entry.leaderboard_entry_id)
+                .unwrap();
+            assert!((entry.mu - original.mu).abs() < 1e-9);
+            assert!((entry.sigma - original.sigma).abs() < 1e-9);
+            assert_eq!(entry.games_played, 0);
+            assert_eq!(entry.first_place_finishes, 0);
+        }
+        let win_rate_games: i64 =
+            sqlx::query_scalar("SELECT COALESCE(SUM(games_played), 0)::BIGINT FROM win_rate_stats")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(win_rate_games, 0);
+
+        // Results are gone, so the game reads as unrated again
+        let results: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM leaderboard_game_results WHERE leaderboard_game_id = $1",
+        )
+        .bind(leaderboard_game_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(results, 0);
+        Ok(())
+    }
+}
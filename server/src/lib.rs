@@ -1,5 +1,173 @@
 //! Arena library crate
 //!
-//! This exposes modules needed by the CLI binary.
+//! Holds the application itself, started through [`app`] by both the
+//! `arena` web binary and the `arena-worker` game worker, and the modules
+//! the CLI binary needs.
+#![allow(dead_code)]
 
+pub mod app;
 pub mod cli;
+
+mod backup;
+mod cache;
+mod config;
+mod consistency_check;
+mod cron;
+mod customizations;
+mod discord;
+mod django_password;
+mod email;
+mod engine;
+mod engine_models;
+mod errors;
+mod events;
+mod flasher;
+mod frame_archive;
+mod game_channels;
+mod game_runner;
+mod github;
+mod human_play;
+mod i18n;
+mod job_alerts;
+mod job_worker;
+mod jobs;
+mod leaderboard_matchmaker;
+mod leaderboard_ratings;
+mod metrics;
+mod models;
+mod notifications;
+mod play_import;
+mod preview_image;
+mod request_id;
+mod routes;
+mod scoring;
+mod shutdown;
+mod snake_client;
+mod snake_failure_log;
+mod snake_health;
+mod snake_health_sweeper;
+mod state;
+mod static_assets;
+mod stuck_game_watchdog;
+mod telemetry;
+mod tournament_bracket;
+mod tournament_match;
+mod wire;
+
+/// Per-game hot paths, exposed for the criterion benchmarks in `benches/`.
+/// Not an API.
+#[doc(hidden)]
+pub mod bench {
+    pub use crate::engine::frame::{EngineGameFrame, FrameCoord, FrameSnake};
+    pub use crate::engine::frame_codec::{decode as decode_frame, encode as encode_frame};
+    pub use crate::leaderboard_matchmaker::select_match;
+    pub use crate::models::leaderboard::LeaderboardEntry;
+    pub use crate::scoring::weng_lin::calculate_rating_updates;
+}
+
+/// Hooks for the end-to-end tests in `tests/`, which only see the crate's
+/// public items. Not an API; behind the `test-support` feature.
+#[doc(hidden)]
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing {
+    use color_eyre::eyre::{Context as _, eyre};
+    use uuid::Uuid;
+
+    use crate::models::api_token;
+    pub use crate::state::AppState;
+
+    /// More jobs than one test's pipeline enqueues; hitting it means a job
+    /// keeps re-enqueueing itself
+    const MAX_INLINE_JOBS: usize = 100;
+
+    /// An [`AppState`] on `pool` with external services off and the
+    /// production scoring algorithms registered
+    pub fn app_state(pool: sqlx::PgPool) -> AppState {
+        AppState {
+            scoring: std::sync::Arc::new(crate::scoring::ScoringRegistry::standard()),
+            ..AppState::test_from_pool(pool)
+        }
+    }
+
+    /// The full web + API router, as served
+    pub fn router(state: AppState) -> axum::Router {
+        crate::routes::routes(state)
+    }
+
+    /// A bearer token for `user_id` with every non-admin scope
+    pub async fn api_token(state: &AppState, user_id: Uuid) -> cja::Result<String> {
+        let token = api_token::create_api_token(
+            &state.db,
+            user_id,
+            "e2e",
+            &api_token::grantable_scopes(false),
+            None,
+        )
+        .await?;
+        Ok(token.secret)
+    }
+
+    /// A user logged in as `login` who owns one private snake named
+    /// `{login}-snake`. Returns `(user_id, battlesnake_id)`.
+    pub async fn fixture_user_with_snake(
+        pool: &sqlx::PgPool,
+        login: &str,
+    ) -> cja::Result<(Uuid, Uuid)> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES ($1, $2, 'token') RETURNING user_id",
+        )
+        .bind(i64::from(Uuid::new_v4().as_fields().0))
+        .bind(login)
+        .fetch_one(pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url, visibility)
+             VALUES ($1, $2, 'http://example.com', 'private') RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .bind(format!("{login}-snake"))
+        .fetch_one(pool)
+        .await?;
+        Ok((user_id, battlesnake_id))
+    }
+
+    /// Run queued jobs inline, oldest first, the way the worker would claim
+    /// them, until the queue is empty — including jobs the ones run enqueue.
+    /// Returns the names of the jobs run, in order.
+    pub async fn run_queued_jobs(state: &AppState) -> cja::Result<Vec<String>> {
+        let mut ran = Vec::new();
+        while ran.len() < MAX_INLINE_JOBS {
+            let Some(job) = sqlx::query!(
+                r#"
+                DELETE FROM jobs
+                WHERE job_id = (
+                    SELECT job_id FROM jobs ORDER BY created_at, job_id LIMIT 1
+                )
+                RETURNING name, payload
+                "#
+            )
+            .fetch_optional(&state.db)
+            .await
+            .wrap_err("Failed to claim a queued job")?
+            else {
+                return Ok(ran);
+            };
+
+            crate::jobs::run_job(&job.name, job.payload, state.clone())
+                .await
+                .wrap_err_with(|| format!("{} failed", job.name))?;
+            ran.push(job.name);
+        }
+        Err(eyre!(
+            "Job queue still not empty after {MAX_INLINE_JOBS} jobs"
+        ))
+    }
+}
+
+/// Frontend UI components only - do not place backend logic here
+mod components {
+    pub mod flash;
+    pub mod page;
+    pub mod page_factory;
+}
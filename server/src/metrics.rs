@@ -0,0 +1,561 @@
+//! In-process Prometheus metrics.
+//!
+//! Counters and histograms live in the process-wide [`METRICS`] registry and
+//! are bumped where the events happen (game creation, the runner, the HTTP
+//! middleware). HTTP requests are one histogram per method, route template,
+//! and status class, so its `_count` is the request count; the same series
+//! are summarized per route for `/api/v1/admin/stats` by
+//! [`Metrics::route_traffic`]. Hot database queries are timed through
+//! [`TimedQuery::timed`], which also gives each one a span and warns when it
+//! crosses [`SLOW_QUERY_THRESHOLD`]; the slowest show on the admin
+//! dashboard via [`Metrics::slowest_queries`]. Everything is rendered in the Prometheus text exposition
+//! format by [`Metrics::render`]; point-in-time gauges that come from the
+//! database (job queue depth, games by status) are appended by the
+//! `/metrics` handler at scrape time.
+//!
+//! Counters reset when the process restarts, which Prometheus' `rate()` and
+//! `increase()` already handle.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::Instrument as _;
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::default);
+
+/// Snake /move round trips: the default 500ms timeout sits between buckets
+const MOVE_LATENCY_BUCKETS: &[f64] = &[0.025, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.75, 1.0, 2.5];
+
+/// HTTP handler durations
+const HTTP_LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Timed database queries
+const DB_QUERY_LATENCY_BUCKETS: &[f64] =
+    &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0];
+
+/// A timed query taking longer than this logs a warning
+pub const SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cumulative-bucket histogram of seconds
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    /// Per-bucket (non-cumulative) counts; the extra slot is `+Inf`
+    buckets: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, value: Duration) {
+        let secs = value.as_secs_f64();
+        let slot = self
+            .bounds
+            .iter()
+            .position(|bound| secs <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.buckets[slot].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(value.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Per-bucket (non-cumulative) counts, `+Inf` last
+    fn bucket_counts(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = self
+                .bounds
+                .get(i)
+                .map_or_else(|| "+Inf".to_string(), |b| b.to_string());
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{labels}{sep}le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let braces = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{labels}}}")
+        };
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{name}_sum{braces} {sum}");
+        let _ = writeln!(
+            out,
+            "{name}_count{braces} {}",
+            self.count.load(Ordering::Relaxed)
+        );
+    }
+}
+
+/// HTTP latency series key: method, matched route template, status class
+type RouteKey = (String, String, &'static str);
+
+/// One route's requests since the process started, every status class
+/// together
+#[derive(Debug, Clone)]
+pub struct RouteTraffic {
+    pub method: String,
+    pub route: String,
+    pub requests: u64,
+    /// Requests by status class (`"2xx"`, `"5xx"`, ...); classes the route
+    /// never answered with are left out
+    pub by_status: BTreeMap<&'static str, u64>,
+    /// Handler time summed over every request
+    pub total: Duration,
+    bounds: &'static [f64],
+    /// Per-bucket counts merged across status classes, `+Inf` last
+    buckets: Vec<u64>,
+}
+
+impl RouteTraffic {
+    pub fn mean(&self) -> Option<Duration> {
+        (self.requests > 0).then(|| self.total / self.requests as u32)
+    }
+
+    /// Upper bound, in seconds, of the bucket holding quantile `q`. `None`
+    /// when there were no requests or it falls past the last bucket.
+    pub fn quantile_upper_bound(&self, q: f64) -> Option<f64> {
+        if self.requests == 0 {
+            return None;
+        }
+        let rank = (q * self.requests as f64).ceil().max(1.0) as u64;
+        let mut cumulative = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= rank {
+                return self.bounds.get(i).copied();
+            }
+        }
+        None
+    }
+}
+
+/// One timed query's durations since the process started
+#[derive(Debug)]
+struct QueryStats {
+    latency: Histogram,
+    slow: Counter,
+    max_micros: AtomicU64,
+}
+
+/// A timed query's summary, for the admin dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTiming {
+    pub query: &'static str,
+    pub calls: u64,
+    /// Calls slower than [`SLOW_QUERY_THRESHOLD`]
+    pub slow_calls: u64,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug)]
+pub struct Metrics {
+    pub games_created: Counter,
+    pub games_finished: Counter,
+    pub snake_move_latency: Histogram,
+    http_latency: Mutex<BTreeMap<RouteKey, Histogram>>,
+    db_query_latency: Mutex<BTreeMap<&'static str, QueryStats>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            games_created: Counter::default(),
+            games_finished: Counter::default(),
+            snake_move_latency: Histogram::new(MOVE_LATENCY_BUCKETS),
+            http_latency: Mutex::new(BTreeMap::new()),
+            db_query_latency: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+/// Time a query future with `.timed("leaderboard::ranked_entries")`: it
+/// runs in a `db_query` span, lands in `arena_db_query_duration_seconds`,
+/// and warns past [`SLOW_QUERY_THRESHOLD`]. Name queries `module::what`,
+/// and never build the name from data: each name is its own series.
+pub trait TimedQuery: std::future::Future + Sized {
+    fn timed(self, query: &'static str) -> impl std::future::Future<Output = Self::Output> {
+        let span = tracing::info_span!("db_query", query, elapsed_ms = tracing::field::Empty);
+        async move {
+            let started = Instant::now();
+            let output = self.await;
+            let elapsed = started.elapsed();
+
+            tracing::Span::current().record("elapsed_ms", elapsed.as_millis() as u64);
+            METRICS.observe_query(query, elapsed);
+            if elapsed > SLOW_QUERY_THRESHOLD {
+                tracing::warn!(
+                    query,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    threshold_ms = SLOW_QUERY_THRESHOLD.as_millis() as u64,
+                    "slow query"
+                );
+            }
+            output
+        }
+        .instrument(span)
+    }
+}
+
+impl<F: std::future::Future> TimedQuery for F {}
+
+fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// This process's resident set size, from `/proc/self/status`. `None` off
+/// Linux.
+pub fn process_rss_bytes() -> Option<u64> {
+    parse_vm_rss(&std::fs::read_to_string("/proc/self/status").ok()?)
+}
+
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let kib: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Escape a label value per the exposition format
+pub fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Append a `# HELP` / `# TYPE` header
+pub fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+impl Metrics {
+    /// Record one handled request. `route` must be the matched route
+    /// template (`/games/{id}`), never the raw path, or every game id
+    /// becomes its own series.
+    pub fn observe_http(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let key = (method.to_string(), route.to_string(), status_class(status));
+        let mut series = self.http_latency.lock().unwrap_or_else(|e| e.into_inner());
+        series
+            .entry(key)
+            .or_insert_with(|| Histogram::new(HTTP_LATENCY_BUCKETS))
+            .observe(elapsed);
+    }
+
+    /// Record one run of a timed query; see [`TimedQuery`]
+    pub fn observe_query(&self, query: &'static str, elapsed: Duration) {
+        let mut queries = self
+            .db_query_latency
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let stats = queries.entry(query).or_insert_with(|| QueryStats {
+            latency: Histogram::new(DB_QUERY_LATENCY_BUCKETS),
+            slow: Counter::default(),
+            max_micros: AtomicU64::new(0),
+        });
+        stats.latency.observe(elapsed);
+        stats
+            .max_micros
+            .fetch_max(elapsed.as_micros() as u64, Ordering::Relaxed);
+        if elapsed > SLOW_QUERY_THRESHOLD {
+            stats.slow.inc();
+        }
+    }
+
+    /// The `limit` timed queries with the highest mean duration
+    pub fn slowest_queries(&self, limit: usize) -> Vec<QueryTiming> {
+        let queries = self
+            .db_query_latency
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut timings: Vec<QueryTiming> = queries
+            .iter()
+            .filter_map(|(query, stats)| {
+                let calls = stats.latency.count.load(Ordering::Relaxed);
+                let total = stats.latency.sum_micros.load(Ordering::Relaxed);
+                (calls > 0).then(|| QueryTiming {
+                    query,
+                    calls,
+                    slow_calls: stats.slow.get(),
+                    mean: Duration::from_micros(total / calls),
+                    max: Duration::from_micros(stats.max_micros.load(Ordering::Relaxed)),
+                })
+            })
+            .collect();
+        timings.sort_by_key(|timing| std::cmp::Reverse(timing.mean));
+        timings.truncate(limit);
+        timings
+    }
+
+    /// HTTP traffic per method and route, the routes with the most total
+    /// handler time first
+    pub fn route_traffic(&self) -> Vec<RouteTraffic> {
+        let series = self.http_latency.lock().unwrap_or_else(|e| e.into_inner());
+        let mut routes: BTreeMap<(&str, &str), RouteTraffic> = BTreeMap::new();
+        for ((method, route, status), histogram) in series.iter() {
+            let traffic = routes
+                .entry((method, route))
+                .or_insert_with(|| RouteTraffic {
+                    method: method.clone(),
+                    route: route.clone(),
+                    requests: 0,
+                    by_status: BTreeMap::new(),
+                    total: Duration::ZERO,
+                    bounds: histogram.bounds,
+                    buckets: vec![0; histogram.buckets.len()],
+                });
+            let count = histogram.count.load(Ordering::Relaxed);
+            traffic.requests += count;
+            *traffic.by_status.entry(status).or_default() += count;
+            traffic.total += Duration::from_micros(histogram.sum_micros.load(Ordering::Relaxed));
+            for (merged, count) in traffic.buckets.iter_mut().zip(histogram.bucket_counts()) {
+                *merged += count;
+            }
+        }
+
+        let mut routes: Vec<_> = routes.into_values().collect();
+        routes.sort_by_key(|route| std::cmp::Reverse(route.total));
+        routes
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        write_header(
+            &mut out,
+            "arena_games_created_total",
+            "counter",
+            "Games created by this process",
+        );
+        let _ = writeln!(
+            out,
+            "arena_games_created_total {}",
+            self.games_created.get()
+        );
+
+        write_header(
+            &mut out,
+            "arena_games_finished_total",
+            "counter",
+            "Games this process finished running",
+        );
+        let _ = writeln!(
+            out,
+            "arena_games_finished_total {}",
+            self.games_finished.get()
+        );
+
+        write_header(
+            &mut out,
+            "arena_snake_move_latency_seconds",
+            "histogram",
+            "Snake /move round-trip time",
+        );
+        self.snake_move_latency
+            .render(&mut out, "arena_snake_move_latency_seconds", "");
+
+        write_header(
+            &mut out,
+            "arena_http_request_duration_seconds",
+            "histogram",
+            "HTTP handler duration by method, route, and status class",
+        );
+        let series = self.http_latency.lock().unwrap_or_else(|e| e.into_inner());
+        for ((method, route, status), histogram) in series.iter() {
+            let labels = format!(
+                "method=\"{}\",route=\"{}\",status=\"{status}\"",
+                escape_label(method),
+                escape_label(route)
+            );
+            histogram.render(&mut out, "arena_http_request_duration_seconds", &labels);
+        }
+        drop(series);
+
+        write_header(
+            &mut out,
+            "arena_db_query_duration_seconds",
+            "histogram",
+            "Timed database query duration by query name",
+        );
+        let queries = self
+            .db_query_latency
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for (query, stats) in queries.iter() {
+            let labels = format!("query=\"{}\"", escape_label(query));
+            stats
+                .latency
+                .render(&mut out, "arena_db_query_duration_seconds", &labels);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let histogram = Histogram::new(&[0.1, 0.5]);
+        histogram.observe(Duration::from_millis(50));
+        histogram.observe(Duration::from_millis(300));
+        histogram.observe(Duration::from_secs(2));
+
+        let mut out = String::new();
+        histogram.render(&mut out, "h", "");
+        assert_eq!(
+            out,
+            "h_bucket{le=\"0.1\"} 1\n\
+             h_bucket{le=\"0.5\"} 2\n\
+             h_bucket{le=\"+Inf\"} 3\n\
+             h_sum 2.35\n\
+             h_count 3\n"
+        );
+    }
+
+    #[test]
+    fn http_series_are_labelled_by_route_template() {
+        let metrics = Metrics::default();
+        metrics.observe_http("GET", "/games/{id}", 200, Duration::from_millis(3));
+        metrics.observe_http("GET", "/games/{id}", 404, Duration::from_millis(3));
+        metrics.games_created.inc();
+
+        let out = metrics.render();
+        assert!(out.contains("arena_games_created_total 1\n"));
+        assert!(out.contains(
+            "arena_http_request_duration_seconds_count{method=\"GET\",route=\"/games/{id}\",status=\"2xx\"} 1\n"
+        ));
+        assert!(out.contains("status=\"4xx\",le=\"+Inf\"} 1\n"));
+    }
+
+    #[test]
+    fn route_traffic_merges_status_classes() {
+        let metrics = Metrics::default();
+        metrics.observe_http("GET", "/games", 200, Duration::from_millis(20));
+        metrics.observe_http("GET", "/games", 200, Duration::from_millis(40));
+        metrics.observe_http("GET", "/games", 500, Duration::from_secs(3));
+        metrics.observe_http("GET", "/", 200, Duration::from_millis(2));
+
+        let routes = metrics.route_traffic();
+        assert_eq!(
+            routes.iter().map(|r| r.route.as_str()).collect::<Vec<_>>(),
+            vec!["/games", "/"]
+        );
+        let games = &routes[0];
+        assert_eq!(games.requests, 3);
+        assert_eq!(games.by_status, BTreeMap::from([("2xx", 2), ("5xx", 1)]));
+        assert_eq!(games.mean(), Some(Duration::from_millis(1020)));
+        assert_eq!(games.quantile_upper_bound(0.5), Some(0.05));
+        assert_eq!(games.quantile_upper_bound(0.95), Some(5.0));
+        assert_eq!(routes[1].quantile_upper_bound(0.95), Some(0.005));
+    }
+
+    #[test]
+    fn slowest_queries_rank_by_mean() {
+        let metrics = Metrics::default();
+        metrics.observe_query("leaderboard::ranked_entries", Duration::from_millis(10));
+        metrics.observe_query("leaderboard::ranked_entries", Duration::from_millis(400));
+        metrics.observe_query("admin::job_queue", Duration::from_millis(5));
+        metrics.observe_query("games::changed_status", Duration::from_millis(30));
+
+        let slowest = metrics.slowest_queries(2);
+        assert_eq!(
+            slowest,
+            vec![
+                QueryTiming {
+                    query: "leaderboard::ranked_entries",
+                    calls: 2,
+                    slow_calls: 1,
+                    mean: Duration::from_millis(205),
+                    max: Duration::from_millis(400),
+                },
+                QueryTiming {
+                    query: "games::changed_status",
+                    calls: 1,
+                    slow_calls: 0,
+                    mean: Duration::from_millis(30),
+                    max: Duration::from_millis(30),
+                },
+            ]
+        );
+        assert!(
+            metrics
+                .render()
+                .contains("arena_db_query_duration_seconds_count{query=\"admin::job_queue\"} 1\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn timed_queries_are_recorded() {
+        let value = async { 7 }.timed("test::timed_queries_are_recorded").await;
+        assert_eq!(value, 7);
+        assert!(
+            METRICS
+                .slowest_queries(usize::MAX)
+                .iter()
+                .any(|timing| timing.query == "test::timed_queries_are_recorded")
+        );
+    }
+
+    #[test]
+    fn vm_rss_is_parsed_from_proc_status() {
+        let status = "Name:\tserver\nVmPeak:\t  204800 kB\nVmRSS:\t   51200 kB\nThreads:\t8\n";
+        assert_eq!(parse_vm_rss(status), Some(51200 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tserver\n"), None);
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
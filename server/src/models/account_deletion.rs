@@ -0,0 +1,365 @@
+//! Deleting a user's account at their request.
+//!
+//! The user row and their snakes stay behind, scrubbed, so the games and
+//! ratings they took part in keep making sense for everyone else. Everything
+//! happens in one transaction, so a failed run leaves the account untouched
+//! and the job can simply retry.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+
+/// `disabled_reason` written on the leaderboard entries of a deleted account.
+/// Nothing resumes these.
+pub const DISABLED_REASON_ACCOUNT_DELETED: &str = "account_deleted";
+
+/// The login a deleted account is left with
+fn scrubbed_login(user_id: Uuid) -> String {
+    format!("deleted-{}", &user_id.simple().to_string()[..8])
+}
+
+/// Delete `user_id`'s account: withdraw their snakes from leaderboards,
+/// call off their challenges and snake transfers, anonymize the snakes,
+/// revoke their API tokens, end their sessions, drop their personal data,
+/// and note it in the audit log. Returns `false` when the user doesn't exist
+/// or was already deleted.
+pub async fn delete_account(pool: &PgPool, user_id: Uuid) -> cja::Result<bool> {
+    let mut tx = pool.begin().await.wrap_err("Failed to begin transaction")?;
+
+    let deleted_at = sqlx::query_scalar!(
+        "SELECT deleted_at FROM users WHERE user_id = $1 FOR UPDATE",
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .wrap_err("Failed to lock user")?;
+    if !matches!(deleted_at, Some(None)) {
+        return Ok(false);
+    }
+
+    let entries_withdrawn = sqlx::query!(
+        r#"UPDATE leaderboard_entries le
+         SET disabled_at = COALESCE(le.disabled_at, NOW()), disabled_reason = $2, updated_at = NOW()
+         FROM battlesnakes b
+         WHERE b.battlesnake_id = le.battlesnake_id
+           AND b.user_id = $1"#,
+        user_id,
+        DISABLED_REASON_ACCOUNT_DELETED
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to withdraw leaderboard entries")?
+    .rows_affected();
+
+    let challenges_canceled = sqlx::query!(
+        r#"UPDATE challenges
+         SET status = 'canceled', responded_at = NOW()
+         WHERE status = 'pending'
+           AND (challenger_user_id = $1 OR opponent_user_id = $1)"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to cancel pending challenges")?
+    .rows_affected();
+
+    sqlx::query!(
+        r#"UPDATE battlesnake_transfers
+         SET status = 'canceled', responded_at = NOW()
+         WHERE status = 'pending'
+           AND (from_user_id = $1 OR to_user_id = $1)"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to cancel pending transfers")?;
+
+    // Old URLs point at the owner's servers, so they go along with the name
+    sqlx::query!(
+        r#"DELETE FROM snake_url_verifications
+         WHERE battlesnake_id IN (SELECT battlesnake_id FROM battlesnakes WHERE user_id = $1)"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete snake URL verifications")?;
+    sqlx::query!(
+        r#"DELETE FROM battlesnake_versions
+         WHERE battlesnake_id IN (SELECT battlesnake_id FROM battlesnakes WHERE user_id = $1)"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete snake versions")?;
+    // Flags hold copies of the snakes' old names and shouts
+    sqlx::query!(
+        r#"DELETE FROM moderation_flags
+         WHERE battlesnake_id IN (SELECT battlesnake_id FROM battlesnakes WHERE user_id = $1)"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete moderation flags")?;
+    let snakes_anonymized = sqlx::query!(
+        r#"UPDATE battlesnakes
+         SET name = 'Deleted snake ' || left(battlesnake_id::text, 8),
+             url = '',
+             visibility = 'private',
+             color = '',
+             head = '',
+             tail = ''
+         WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to anonymize snakes")?
+    .rows_affected();
+
+    let tokens_revoked = sqlx::query!(
+        "UPDATE api_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to revoke API tokens")?
+    .rows_affected();
+
+    sqlx::query!("DELETE FROM sessions WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to end sessions")?;
+    sqlx::query!("DELETE FROM notifications WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete notifications")?;
+    sqlx::query!(
+        "DELETE FROM notification_preferences WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete notification preferences")?;
+    sqlx::query!("DELETE FROM discord_webhooks WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete Discord webhook")?;
+    sqlx::query!("DELETE FROM claim_attempts WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete claim attempts")?;
+    sqlx::query!(
+        "DELETE FROM claim_email_tokens WHERE requested_by_user_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete claim email tokens")?;
+    sqlx::query!("DELETE FROM snake_follows WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete snake follows")?;
+    sqlx::query!(
+        "DELETE FROM leaderboard_follows WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to delete leaderboard follows")?;
+    sqlx::query!("DELETE FROM saved_games WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete saved games")?;
+    sqlx::query!("DELETE FROM game_flows WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to delete game flows")?;
+
+    // The GitHub id moves out of the way (to a negative number derived from
+    // the user id, which real ids never are) so signing in with the same
+    // GitHub account later starts a fresh one
+    let login = scrubbed_login(user_id);
+    sqlx::query!(
+        r#"UPDATE users
+         SET external_github_id = -(('x' || left(md5(user_id::text), 15))::bit(60)::bigint) - 1,
+             github_login = $2,
+             github_avatar_url = NULL,
+             github_name = NULL,
+             github_email = NULL,
+             github_access_token = '',
+             github_refresh_token = NULL,
+             github_token_expires_at = NULL,
+             display_name = NULL,
+             pronouns = '',
+             country = '',
+             backstory = '',
+             is_admin = false,
+             deleted_at = NOW()
+         WHERE user_id = $1"#,
+        user_id,
+        login
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to scrub user")?;
+
+    // Counts only; the point is that nothing personal is left to log
+    admin_audit::record_as(
+        &mut *tx,
+        user_id,
+        &login,
+        AuditAction::AccountDeleted,
+        AuditTarget::User(user_id),
+        Some(serde_json::json!({
+            "snakes": snakes_anonymized,
+            "leaderboard_entries": entries_withdrawn,
+            "api_tokens": tokens_revoked,
+            "challenges": challenges_canceled,
+        })),
+        Some(serde_json::json!({ "github_login": login })),
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .wrap_err("Failed to commit account deletion")?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_delete_account(pool: PgPool) -> cja::Result<()> {
+        let mut users = Vec::new();
+        for (github_id, login) in [(1_i64, "leaving"), (2, "staying")] {
+            let user_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (external_github_id, github_login, github_access_token,
+                                    github_email, display_name, backstory)
+                 VALUES ($1, $2, 'test-token', 'me@example.com', 'Me', 'hello')
+                 RETURNING user_id",
+            )
+            .bind(github_id)
+            .bind(login)
+            .fetch_one(&pool)
+            .await?;
+            users.push(user_id);
+        }
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut snakes = Vec::new();
+        for user_id in &users {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, 'snake', 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id) VALUES ($1, $2)",
+            )
+            .bind(leaderboard_id)
+            .bind(battlesnake_id)
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO sessions (user_id) VALUES ($1)")
+                .bind(user_id)
+                .execute(&pool)
+                .await?;
+            sqlx::query(
+                "INSERT INTO api_tokens (user_id, token_hash, name) VALUES ($1, $2, 'cli')",
+            )
+            .bind(user_id)
+            .bind(user_id.to_string())
+            .execute(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+        }
+        sqlx::query(
+            "INSERT INTO challenges (challenger_user_id, challenger_battlesnake_id,
+                                     opponent_user_id, opponent_battlesnake_id,
+                                     board_size, game_type)
+             VALUES ($1, $2, $3, $4, '11x11', 'Standard')",
+        )
+        .bind(users[1])
+        .bind(snakes[1])
+        .bind(users[0])
+        .bind(snakes[0])
+        .execute(&pool)
+        .await?;
+
+        assert!(delete_account(&pool, users[0]).await?);
+        // Running it again, as a retried job would, changes nothing
+        assert!(!delete_account(&pool, users[0]).await?);
+        assert!(!delete_account(&pool, Uuid::new_v4()).await?);
+
+        let user = crate::models::user::get_user_by_id(&pool, users[0])
+            .await?
+            .unwrap();
+        assert_eq!(user.github_login, scrubbed_login(users[0]));
+        assert!(user.external_github_id < 0);
+        assert_eq!(user.github_email, None);
+        assert_eq!(user.display_name, None);
+        assert_eq!(user.backstory, "");
+
+        let snake: (String, String, String) = sqlx::query_as(
+            "SELECT name, url, visibility FROM battlesnakes WHERE battlesnake_id = $1",
+        )
+        .bind(snakes[0])
+        .fetch_one(&pool)
+        .await?;
+        assert!(snake.0.starts_with("Deleted snake "));
+        assert_eq!((snake.1.as_str(), snake.2.as_str()), ("", "private"));
+
+        let reasons: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT le.disabled_reason FROM leaderboard_entries le
+             JOIN battlesnakes b USING (battlesnake_id)
+             ORDER BY b.user_id = $1 DESC",
+        )
+        .bind(users[0])
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(
+            reasons,
+            vec![Some(DISABLED_REASON_ACCOUNT_DELETED.to_string()), None]
+        );
+
+        let live_tokens: Vec<Uuid> =
+            sqlx::query_scalar("SELECT user_id FROM api_tokens WHERE revoked_at IS NULL")
+                .fetch_all(&pool)
+                .await?;
+        assert_eq!(live_tokens, vec![users[1]]);
+        let sessions: Vec<Option<Uuid>> = sqlx::query_scalar("SELECT user_id FROM sessions")
+            .fetch_all(&pool)
+            .await?;
+        assert_eq!(sessions, vec![Some(users[1])]);
+        let challenge_status: String = sqlx::query_scalar("SELECT status FROM challenges")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(challenge_status, "canceled");
+
+        let audit = admin_audit::list_recent(&pool, 10).await?;
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, "account_deleted");
+        assert_eq!(audit[0].actor_login, scrubbed_login(users[0]));
+        assert!(!serde_json::to_string(&audit[0].before)?.contains("leaving"));
+
+        // The same GitHub account can sign up again
+        sqlx::query(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'leaving', 'test-token')",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(())
+    }
+}
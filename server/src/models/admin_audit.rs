@@ -0,0 +1,264 @@
+//! Audit log of privileged admin mutations.
+//!
+//! Handlers call [`record`] after an admin action succeeds, with the state
+//! the action changed before and after, so the dashboard can show who did
+//! what and undo it by hand if needed.
+
+use color_eyre::eyre::Context as _;
+use serde_json::Value;
+use sqlx::{Executor, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::models::user::User;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    GameRerun,
+    GameCancelled,
+    GameDeleted,
+    AdminChanged,
+    SuspensionChanged,
+    MaintenanceChanged,
+    SettingChanged,
+    EntriesPaused,
+    LeaderboardFreezeChanged,
+    JobRetried,
+    JobsRetried,
+    JobDeleted,
+    TournamentCreated,
+    /// A user deleted their own account
+    AccountDeleted,
+    ModerationTermAdded,
+    ModerationTermRemoved,
+    ModerationFlagResolved,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::GameRerun => "game_rerun",
+            AuditAction::GameCancelled => "game_cancelled",
+            AuditAction::GameDeleted => "game_deleted",
+            AuditAction::AdminChanged => "admin_changed",
+            AuditAction::SuspensionChanged => "suspension_changed",
+            AuditAction::MaintenanceChanged => "maintenance_changed",
+            AuditAction::SettingChanged => "setting_changed",
+            AuditAction::EntriesPaused => "entries_paused",
+            AuditAction::LeaderboardFreezeChanged => "leaderboard_freeze_changed",
+            AuditAction::JobRetried => "job_retried",
+            AuditAction::JobsRetried => "jobs_retried",
+            AuditAction::JobDeleted => "job_deleted",
+            AuditAction::TournamentCreated => "tournament_created",
+            AuditAction::AccountDeleted => "account_deleted",
+            AuditAction::ModerationTermAdded => "moderation_term_added",
+            AuditAction::ModerationTermRemoved => "moderation_term_removed",
+            AuditAction::ModerationFlagResolved => "moderation_flag_resolved",
+        }
+    }
+}
+
+/// What an admin action was applied to
+#[derive(Debug, Clone)]
+pub enum AuditTarget {
+    Game(Uuid),
+    User(Uuid),
+    Battlesnake(Uuid),
+    Leaderboard(Uuid),
+    Tournament(Uuid),
+    Job(Uuid),
+    /// Every failing job with this name
+    JobName(String),
+    /// The site-wide maintenance flag
+    Maintenance,
+    /// A runtime setting, by key
+    Setting(String),
+    /// A moderation word list entry, by term
+    ModerationTerm(String),
+}
+
+impl AuditTarget {
+    fn target_type(&self) -> &'static str {
+        match self {
+            AuditTarget::Game(_) => "game",
+            AuditTarget::User(_) => "user",
+            AuditTarget::Battlesnake(_) => "battlesnake",
+            AuditTarget::Leaderboard(_) => "leaderboard",
+            AuditTarget::Tournament(_) => "tournament",
+            AuditTarget::Job(_) | AuditTarget::JobName(_) => "job",
+            AuditTarget::Maintenance => "maintenance",
+            AuditTarget::Setting(_) => "setting",
+            AuditTarget::ModerationTerm(_) => "moderation_term",
+        }
+    }
+
+    fn target_id(&self) -> Option<String> {
+        match self {
+            AuditTarget::Game(id)
+            | AuditTarget::User(id)
+            | AuditTarget::Battlesnake(id)
+            | AuditTarget::Leaderboard(id)
+            | AuditTarget::Tournament(id)
+            | AuditTarget::Job(id) => Some(id.to_string()),
+            AuditTarget::JobName(name)
+            | AuditTarget::Setting(name)
+            | AuditTarget::ModerationTerm(name) => Some(name.clone()),
+            AuditTarget::Maintenance => None,
+        }
+    }
+}
+
+/// A row of the audit log
+#[derive(Debug, Clone)]
+pub struct AuditLogEntry {
+    pub admin_audit_log_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_login: String,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditLogEntry {
+    /// Admin page for the target, where there is one
+    pub fn target_href(&self) -> Option<String> {
+        let id = self.target_id.as_deref()?;
+        match self.target_type.as_str() {
+            "game" => Some(format!("/admin/games/{id}")),
+            "user" => Some(format!("/admin/users/{id}")),
+            "battlesnake" => Some(format!("/admin/snakes/{id}/errors")),
+            "leaderboard" => Some(format!("/leaderboards/{id}")),
+            "tournament" => Some(format!("/tournaments/{id}")),
+            "setting" => Some("/admin/settings".to_string()),
+            "moderation_term" => Some("/admin/moderation".to_string()),
+            _ => None,
+        }
+    }
+}
+
+pub async fn record(
+    pool: &PgPool,
+    actor: &User,
+    action: AuditAction,
+    target: AuditTarget,
+    before: Option<Value>,
+    after: Option<Value>,
+) -> cja::Result<()> {
+    record_as(
+        pool,
+        actor.user_id,
+        &actor.github_login,
+        action,
+        target,
+        before,
+        after,
+    )
+    .await
+}
+
+/// [`record`] for callers that need the entry in their own transaction, or
+/// whose actor is no longer a loadable [`User`]
+pub async fn record_as<'e, E>(
+    executor: E,
+    actor_id: Uuid,
+    actor_login: &str,
+    action: AuditAction,
+    target: AuditTarget,
+    before: Option<Value>,
+    after: Option<Value>,
+) -> cja::Result<()>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query!(
+        r#"
+        INSERT INTO admin_audit_log
+            (actor_id, actor_login, action, target_type, target_id, before, after)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        actor_id,
+        actor_login,
+        action.as_str(),
+        target.target_type(),
+        target.target_id(),
+        before,
+        after,
+    )
+    .execute(executor)
+    .await
+    .wrap_err("Failed to record admin audit log entry")?;
+
+    Ok(())
+}
+
+/// Most recent entries first
+pub async fn list_recent(pool: &PgPool, limit: i64) -> cja::Result<Vec<AuditLogEntry>> {
+    sqlx::query_as!(
+        AuditLogEntry,
+        r#"
+        SELECT admin_audit_log_id, actor_id, actor_login, action, target_type, target_id,
+               before, after, created_at
+        FROM admin_audit_log
+        ORDER BY created_at DESC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list admin audit log")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_record_and_list(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'admin', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let admin = crate::models::user::get_user_by_id(&pool, user_id)
+            .await?
+            .unwrap();
+        let game_id = Uuid::new_v4();
+
+        record(
+            &pool,
+            &admin,
+            AuditAction::GameCancelled,
+            AuditTarget::Game(game_id),
+            Some(serde_json::json!({ "status": "running" })),
+            Some(serde_json::json!({ "status": "finished" })),
+        )
+        .await?;
+        record(
+            &pool,
+            &admin,
+            AuditAction::MaintenanceChanged,
+            AuditTarget::Maintenance,
+            None,
+            None,
+        )
+        .await?;
+
+        let entries = list_recent(&pool, 10).await?;
+        assert_eq!(entries.len(), 2);
+        let cancel = entries
+            .iter()
+            .find(|e| e.action == "game_cancelled")
+            .unwrap();
+        assert_eq!(cancel.actor_login, "admin");
+        assert_eq!(
+            cancel.target_href(),
+            Some(format!("/admin/games/{game_id}"))
+        );
+        assert_eq!(cancel.before.as_ref().unwrap()["status"], "running");
+        Ok(())
+    }
+}
@@ -1,12 +1,65 @@
+use std::str::FromStr;
+
 use color_eyre::eyre::Context as _;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use sqlx::{FromRow, PgPool};
+use sqlx::PgPool;
 use uuid::Uuid;
 
+/// Permission a bearer token must carry for privileged API actions.
+///
+/// Reading data and managing the owner's own snakes only needs a valid
+/// token; anything that spends server resources or touches shared state
+/// needs a scope. Session (cookie) auth is treated as having every scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum TokenScope {
+    #[serde(rename = "games:create")]
+    GamesCreate,
+    #[serde(rename = "leaderboards:write")]
+    LeaderboardsWrite,
+    /// Admin endpoints. Only effective for users with `is_admin`.
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 3] = [
+        TokenScope::GamesCreate,
+        TokenScope::LeaderboardsWrite,
+        TokenScope::Admin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::GamesCreate => "games:create",
+            TokenScope::LeaderboardsWrite => "leaderboards:write",
+            TokenScope::Admin => "admin",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            TokenScope::GamesCreate => "Create games",
+            TokenScope::LeaderboardsWrite => "Add and remove your snakes from leaderboards",
+            TokenScope::Admin => "Admin endpoints (admins only)",
+        }
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TokenScope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Invalid token scope: {}", s))
+    }
+}
+
 /// API token stored in the database (hashed)
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ApiToken {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -15,6 +68,47 @@ pub struct ApiToken {
     pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the token stops working on its own; `None` never expires
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Raw scope strings; use [`ApiToken::scopes`] for the parsed form
+    pub scope_names: Vec<String>,
+}
+
+impl ApiToken {
+    /// Parsed scopes. Unknown strings are ignored (the column has a CHECK
+    /// constraint, so this only matters mid-deploy when adding a scope).
+    pub fn scopes(&self) -> Vec<TokenScope> {
+        self.scope_names
+            .iter()
+            .filter_map(|s| s.parse().ok())
+            .collect()
+    }
+
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        self.scope_names.iter().any(|s| s == scope.as_str())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    }
+}
+
+/// Scopes a user may put on a token: `admin` only if they're an admin
+pub fn grantable_scopes(is_admin: bool) -> Vec<TokenScope> {
+    TokenScope::ALL
+        .into_iter()
+        .filter(|scope| is_admin || *scope != TokenScope::Admin)
+        .collect()
+}
+
+/// Canonical column value for a set of scopes: deduplicated, in `ALL` order
+fn scopes_to_names(scopes: &[TokenScope]) -> Vec<String> {
+    TokenScope::ALL
+        .into_iter()
+        .filter(|scope| scopes.contains(scope))
+        .map(|scope| scope.as_str().to_string())
+        .collect()
 }
 
 /// Result of creating a new token - includes the raw secret (only shown once)
@@ -32,31 +126,38 @@ fn generate_token_secret() -> String {
 }
 
 /// Hash a token secret using SHA-256, returning the hex-encoded hash
-fn hash_token(secret: &str) -> String {
+pub(crate) fn hash_token(secret: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(secret.as_bytes());
     hex::encode(hasher.finalize())
 }
 
-/// Create a new API token for a user
+/// Create a new API token for a user with the given scopes, expiring at
+/// `expires_at` (or never)
 pub async fn create_api_token(
     pool: &PgPool,
     user_id: Uuid,
     name: &str,
+    scopes: &[TokenScope],
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> cja::Result<NewApiToken> {
     let secret = generate_token_secret();
     let token_hash = hash_token(&secret);
 
-    let token: ApiToken = sqlx::query_as(
+    let token = sqlx::query_as!(
+        ApiToken,
         r#"
-        INSERT INTO api_tokens (user_id, token_hash, name)
-        VALUES ($1, $2, $3)
-        RETURNING id, user_id, token_hash, name, last_used_at, created_at, revoked_at
+        INSERT INTO api_tokens (user_id, token_hash, name, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, user_id, token_hash, name, last_used_at, created_at, revoked_at, expires_at,
+                  scopes AS scope_names
         "#,
+        user_id,
+        token_hash,
+        name,
+        &scopes_to_names(scopes),
+        expires_at,
     )
-    .bind(user_id)
-    .bind(&token_hash)
-    .bind(name)
     .fetch_one(pool)
     .await
     .wrap_err("Failed to create API token")?;
@@ -64,17 +165,20 @@ pub async fn create_api_token(
     Ok(NewApiToken { token, secret })
 }
 
-/// Get all active (non-revoked) tokens for a user
+/// Get all non-revoked tokens for a user, expired ones included so they can
+/// be seen and cleaned up
 pub async fn list_user_tokens(pool: &PgPool, user_id: Uuid) -> cja::Result<Vec<ApiToken>> {
-    let tokens: Vec<ApiToken> = sqlx::query_as(
+    let tokens = sqlx::query_as!(
+        ApiToken,
         r#"
-        SELECT id, user_id, token_hash, name, last_used_at, created_at, revoked_at
+        SELECT id, user_id, token_hash, name, last_used_at, created_at, revoked_at, expires_at,
+               scopes AS scope_names
         FROM api_tokens
         WHERE user_id = $1 AND revoked_at IS NULL
         ORDER BY created_at DESC
         "#,
+        user_id,
     )
-    .bind(user_id)
     .fetch_all(pool)
     .await
     .wrap_err("Failed to list API tokens")?;
@@ -82,21 +186,50 @@ pub async fn list_user_tokens(pool: &PgPool, user_id: Uuid) -> cja::Result<Vec<A
     Ok(tokens)
 }
 
-/// Validate a raw token secret and return the associated user_id if valid (not revoked)
+/// Get one active (non-revoked) token, scoped to its owner
+pub async fn get_user_token(
+    pool: &PgPool,
+    token_id: Uuid,
+    user_id: Uuid,
+) -> cja::Result<Option<ApiToken>> {
+    let token = sqlx::query_as!(
+        ApiToken,
+        r#"
+        SELECT id, user_id, token_hash, name, last_used_at, created_at, revoked_at, expires_at,
+               scopes AS scope_names
+        FROM api_tokens
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+        token_id,
+        user_id,
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to get API token")?;
+
+    Ok(token)
+}
+
+/// Validate a raw token secret and return the token if valid (not revoked
+/// or expired)
 ///
 /// This function hashes the token internally to prevent accidentally passing unhashed tokens.
-pub async fn validate_token(pool: &PgPool, token_secret: &str) -> cja::Result<Option<Uuid>> {
+pub async fn validate_token(pool: &PgPool, token_secret: &str) -> cja::Result<Option<ApiToken>> {
     let token_hash = hash_token(token_secret);
 
-    let result: Option<Uuid> = sqlx::query_scalar(
+    let result = sqlx::query_as!(
+        ApiToken,
         r#"
         UPDATE api_tokens
         SET last_used_at = NOW()
-        WHERE token_hash = $1 AND revoked_at IS NULL
-        RETURNING user_id
+        WHERE token_hash = $1
+          AND revoked_at IS NULL
+          AND (expires_at IS NULL OR expires_at > NOW())
+        RETURNING id, user_id, token_hash, name, last_used_at, created_at, revoked_at, expires_at,
+                  scopes AS scope_names
         "#,
+        token_hash,
     )
-    .bind(token_hash)
     .fetch_optional(pool)
     .await
     .wrap_err("Failed to validate API token")?;
@@ -104,6 +237,60 @@ pub async fn validate_token(pool: &PgPool, token_secret: &str) -> cja::Result<Op
     Ok(result)
 }
 
+/// Whether a token hash belongs to a live (unrevoked, unexpired) token. Read-only,
+/// unlike [`validate_token`], so the rate limiter can check it without
+/// touching `last_used_at`.
+pub async fn is_active_token_hash(pool: &PgPool, token_hash: &str) -> cja::Result<bool> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM api_tokens
+            WHERE token_hash = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+        ) AS "exists!"
+        "#,
+        token_hash
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to look up API token")
+}
+
+/// Replace a token's secret, keeping its name and scopes. The old secret
+/// stops working immediately, and the expiry stays as it was. Returns None
+/// if the token isn't the user's, is already revoked, or has expired.
+pub async fn rotate_token(
+    pool: &PgPool,
+    token_id: Uuid,
+    user_id: Uuid,
+) -> cja::Result<Option<NewApiToken>> {
+    let secret = generate_token_secret();
+    let token_hash = hash_token(&secret);
+
+    let token = sqlx::query_as!(
+        ApiToken,
+        r#"
+        UPDATE api_tokens
+        SET token_hash = $3, last_used_at = NULL
+        WHERE id = $1
+          AND user_id = $2
+          AND revoked_at IS NULL
+          AND (expires_at IS NULL OR expires_at > NOW())
+        RETURNING id, user_id, token_hash, name, last_used_at, created_at, revoked_at, expires_at,
+                  scopes AS scope_names
+        "#,
+        token_id,
+        user_id,
+        token_hash,
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to rotate API token")?;
+
+    Ok(token.map(|token| NewApiToken { token, secret }))
+}
+
 /// Revoke a token by ID (must belong to the user)
 pub async fn revoke_token(pool: &PgPool, token_id: Uuid, user_id: Uuid) -> cja::Result<bool> {
     let result = sqlx::query(
@@ -152,4 +339,143 @@ mod tests {
         let hash = hash_token("test");
         assert_eq!(hash.len(), 64); // SHA-256 = 64 hex chars
     }
+
+    #[test]
+    fn test_token_scope_round_trip() {
+        for scope in TokenScope::ALL {
+            assert_eq!(scope.as_str().parse::<TokenScope>().unwrap(), scope);
+            let json = serde_json::to_string(&scope).unwrap();
+            assert_eq!(json, format!("\"{}\"", scope.as_str()));
+        }
+        assert!("games".parse::<TokenScope>().is_err());
+    }
+
+    #[test]
+    fn test_scope_names_are_canonical() {
+        let names = scopes_to_names(&[
+            TokenScope::Admin,
+            TokenScope::GamesCreate,
+            TokenScope::Admin,
+        ]);
+        assert_eq!(names, vec!["games:create", "admin"]);
+    }
+
+    #[test]
+    fn test_grantable_scopes_excludes_admin_for_non_admins() {
+        assert_eq!(grantable_scopes(true), TokenScope::ALL.to_vec());
+        assert_eq!(
+            grantable_scopes(false),
+            vec![TokenScope::GamesCreate, TokenScope::LeaderboardsWrite]
+        );
+    }
+
+    async fn create_user(pool: &PgPool) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (4242, 'token-owner', 'gh-token') RETURNING user_id",
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_validate_token_returns_scopes(pool: PgPool) {
+        let user_id = create_user(&pool).await;
+        let new = create_api_token(&pool, user_id, "stress", &[TokenScope::GamesCreate], None)
+            .await
+            .unwrap();
+
+        let token = validate_token(&pool, &new.secret).await.unwrap().unwrap();
+        assert_eq!(token.user_id, user_id);
+        assert!(token.has_scope(TokenScope::GamesCreate));
+        assert!(!token.has_scope(TokenScope::LeaderboardsWrite));
+        assert!(!token.has_scope(TokenScope::Admin));
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_rotate_token_invalidates_old_secret(pool: PgPool) {
+        let user_id = create_user(&pool).await;
+        let original = create_api_token(&pool, user_id, "ci", &TokenScope::ALL, None)
+            .await
+            .unwrap();
+
+        let rotated = rotate_token(&pool, original.token.id, user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rotated.token.id, original.token.id);
+        assert_eq!(rotated.token.scopes(), TokenScope::ALL.to_vec());
+        assert_ne!(rotated.secret, original.secret);
+
+        assert!(
+            validate_token(&pool, &original.secret)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            validate_token(&pool, &rotated.secret)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Someone else's token can't be rotated
+        assert!(
+            rotate_token(&pool, original.token.id, Uuid::new_v4())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_expired_tokens_stop_working(pool: PgPool) {
+        let user_id = create_user(&pool).await;
+        let expiring = create_api_token(
+            &pool,
+            user_id,
+            "short-lived",
+            &[],
+            Some(chrono::Utc::now() + chrono::Duration::days(1)),
+        )
+        .await
+        .unwrap();
+        assert!(!expiring.token.is_expired());
+        assert!(
+            validate_token(&pool, &expiring.secret)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        sqlx::query("UPDATE api_tokens SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+            .bind(expiring.token.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(
+            validate_token(&pool, &expiring.secret)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            !is_active_token_hash(&pool, &hash_token(&expiring.secret))
+                .await
+                .unwrap()
+        );
+        assert!(
+            rotate_token(&pool, expiring.token.id, user_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // Still listed, so the owner can see what happened to it
+        let listed = list_user_tokens(&pool, user_id).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].is_expired());
+    }
 }
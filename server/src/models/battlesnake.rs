@@ -5,7 +5,7 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 // Visibility enum for battlesnakes
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type, utoipa::ToSchema)]
 #[sqlx(type_name = "text", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 #[derive(Default)]
@@ -69,6 +69,31 @@ pub struct UpdateBattlesnake {
     pub visibility: Visibility,
 }
 
+/// Why a name on the moderation word list was refused
+pub const NAME_NOT_ALLOWED: &str =
+    "That name contains a word that isn't allowed. Please choose a different name.";
+
+/// Whether `err` from [`create_battlesnake`] or [`update_battlesnake`] is a
+/// refusal of the name, to show the user, rather than a failure
+pub fn is_name_rejection(err: &cja::color_eyre::Report) -> bool {
+    let msg = err.to_string();
+    msg == NAME_NOT_ALLOWED || msg.contains("already have a battlesnake named")
+}
+
+/// Refuse `name` if it hits the moderation word list
+async fn screen_name(pool: &PgPool, name: &str) -> cja::Result<()> {
+    let words = super::moderation::load_word_list(pool).await?;
+    if let Some(term) = words.find(name) {
+        tracing::info!(
+            name,
+            term,
+            "Refused battlesnake name on the moderation word list"
+        );
+        return Err(cja::color_eyre::eyre::eyre!(NAME_NOT_ALLOWED));
+    }
+    Ok(())
+}
+
 // Database functions for battlesnake management
 
 // Get all battlesnakes for a user
@@ -140,6 +165,7 @@ pub async fn create_battlesnake(
     user_id: Uuid,
     data: CreateBattlesnake,
 ) -> cja::Result<Battlesnake> {
+    screen_name(pool, &data.name).await?;
     let visibility_str = data.visibility.as_str();
 
     let result = sqlx::query_as!(
@@ -173,7 +199,16 @@ pub async fn create_battlesnake(
     .await;
 
     match result {
-        Ok(battlesnake) => Ok(battlesnake),
+        Ok(battlesnake) => {
+            super::battlesnake_version::record_observed(
+                pool,
+                battlesnake.battlesnake_id,
+                &battlesnake.url,
+                None,
+            )
+            .await?;
+            Ok(battlesnake)
+        }
         Err(err) => {
             // Check if this is a unique violation error
             if let Some(db_err) = err.as_database_error()
@@ -199,6 +234,18 @@ pub async fn update_battlesnake(
     user_id: Uuid,
     data: UpdateBattlesnake,
 ) -> cja::Result<Battlesnake> {
+    // Only a new name is screened, so one an admin approved can be kept
+    let current_name = sqlx::query_scalar!(
+        "SELECT name FROM battlesnakes WHERE battlesnake_id = $1",
+        battlesnake_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch current battlesnake name")?;
+    if current_name.as_deref() != Some(data.name.as_str()) {
+        screen_name(pool, &data.name).await?;
+    }
+
     let visibility_str = data.visibility.as_str();
 
     let result = sqlx::query_as!(
@@ -234,7 +281,20 @@ pub async fn update_battlesnake(
     .await;
 
     match result {
-        Ok(battlesnake) => Ok(battlesnake),
+        Ok(battlesnake) => {
+            // A new URL needs verifying again before the snake plays on
+            // leaderboards
+            super::snake_url_verification::pause_entries_if_unverified(pool, battlesnake_id)
+                .await?;
+            super::battlesnake_version::record_observed(
+                pool,
+                battlesnake_id,
+                &battlesnake.url,
+                None,
+            )
+            .await?;
+            Ok(battlesnake)
+        }
         Err(err) => {
             // Check if this is a unique violation error
             if let Some(db_err) = err.as_database_error()
@@ -385,3 +445,212 @@ pub async fn update_battlesnake_customizations(
     .wrap_err("Failed to update battlesnake customizations")?;
     Ok(())
 }
+
+/// Narrows the public snake directory. `None` fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryFilter {
+    /// Substring of the snake's name or its owner's login or display name
+    pub query: Option<String>,
+    /// Only snakes entered on this leaderboard
+    pub leaderboard_id: Option<Uuid>,
+    /// Only snakes the health sweeper has (or hasn't) paused
+    pub paused: Option<bool>,
+}
+
+/// A public snake as listed in the directory
+#[derive(Debug, Clone)]
+pub struct DirectorySnake {
+    pub battlesnake_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub head: String,
+    pub tail: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub owner_login: String,
+    pub owner_display_name: Option<String>,
+    /// Failed probes in a row; `None` until the sweeper first checks it
+    pub consecutive_failures: Option<i32>,
+    /// Set while the sweeper has the snake paused
+    pub deactivated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A page of public snakes, newest first, starting after `before`.
+///
+/// Suspended owners' snakes are left out, as they are from matchmaking.
+pub async fn list_directory_page(
+    pool: &PgPool,
+    filter: &DirectoryFilter,
+    before: Option<(chrono::DateTime<chrono::Utc>, Uuid)>,
+    limit: i64,
+) -> cja::Result<Vec<DirectorySnake>> {
+    let (before_created_at, before_battlesnake_id) = before.unzip();
+    let pattern = filter.query.as_deref().map(|query| {
+        format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        )
+    });
+    let snakes = sqlx::query_as!(
+        DirectorySnake,
+        r#"
+        SELECT
+            b.battlesnake_id,
+            b.name,
+            b.color,
+            b.head,
+            b.tail,
+            b.created_at,
+            u.github_login as owner_login,
+            u.display_name as owner_display_name,
+            h.consecutive_failures as "consecutive_failures?",
+            h.deactivated_at as "deactivated_at?"
+        FROM battlesnakes b
+        JOIN users u ON u.user_id = b.user_id
+        LEFT JOIN snake_health_status h ON h.battlesnake_id = b.battlesnake_id
+        WHERE b.visibility = 'public'
+          AND u.suspended_at IS NULL
+          AND ($1::text IS NULL
+               OR b.name ILIKE $1
+               OR b.user_id IN (
+                    SELECT user_id FROM users WHERE github_login ILIKE $1 OR display_name ILIKE $1
+               ))
+          AND ($2::uuid IS NULL OR EXISTS (
+                SELECT 1 FROM leaderboard_entries le
+                WHERE le.battlesnake_id = b.battlesnake_id AND le.leaderboard_id = $2
+              ))
+          AND ($3::bool IS NULL OR (h.deactivated_at IS NOT NULL) = $3)
+          AND (b.created_at, b.battlesnake_id)
+              < (COALESCE($4::timestamptz, 'infinity'),
+                 COALESCE($5::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+        ORDER BY b.created_at DESC, b.battlesnake_id DESC
+        LIMIT $6
+        "#,
+        pattern,
+        filter.leaderboard_id,
+        filter.paused,
+        before_created_at,
+        before_battlesnake_id,
+        limit,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list public battlesnakes")?;
+
+    Ok(snakes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn list_directory_page_filters_and_pages(pool: PgPool) -> cja::Result<()> {
+        let owner_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'snake_charmer', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        async fn insert_snake(
+            pool: &PgPool,
+            user_id: Uuid,
+            name: &str,
+            visibility: &str,
+            age_hours: i32,
+        ) -> cja::Result<Uuid> {
+            let battlesnake_id = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url, visibility, created_at)
+                 VALUES ($1, $2, 'http://example.com', $3, NOW() - make_interval(hours => $4))
+                 RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .bind(visibility)
+            .bind(age_hours)
+            .fetch_one(pool)
+            .await?;
+            Ok(battlesnake_id)
+        }
+        let newest = insert_snake(&pool, owner_id, "Noodle 100%", "public", 1).await?;
+        let paused = insert_snake(&pool, owner_id, "Slowpoke", "public", 2).await?;
+        let oldest = insert_snake(&pool, owner_id, "Noodle Classic", "public", 3).await?;
+        insert_snake(&pool, owner_id, "Noodle Secret", "private", 4).await?;
+        sqlx::query(
+            "INSERT INTO snake_health_status (battlesnake_id, consecutive_failures, deactivated_at)
+             VALUES ($1, 3, NOW())",
+        )
+        .bind(paused)
+        .execute(&pool)
+        .await?;
+
+        let ids = |snakes: Vec<DirectorySnake>| -> Vec<Uuid> {
+            snakes.into_iter().map(|s| s.battlesnake_id).collect()
+        };
+
+        let all = DirectoryFilter::default();
+        let first = list_directory_page(&pool, &all, None, 2).await?;
+        assert_eq!(first[0].owner_login, "snake_charmer");
+        let last = first.last().unwrap();
+        let after = Some((last.created_at, last.battlesnake_id));
+        assert_eq!(ids(first), vec![newest, paused]);
+        assert_eq!(
+            ids(list_directory_page(&pool, &all, after, 2).await?),
+            vec![oldest]
+        );
+
+        let search = |query: &str| DirectoryFilter {
+            query: Some(query.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(list_directory_page(&pool, &search("noodle"), None, 10).await?),
+            vec![newest, oldest]
+        );
+        // `%` is a literal, not a wildcard
+        assert_eq!(
+            ids(list_directory_page(&pool, &search("100%"), None, 10).await?),
+            vec![newest]
+        );
+        assert_eq!(
+            list_directory_page(&pool, &search("charmer"), None, 10)
+                .await?
+                .len(),
+            3
+        );
+
+        let paused_only = DirectoryFilter {
+            paused: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(list_directory_page(&pool, &paused_only, None, 10).await?),
+            vec![paused]
+        );
+
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Standard') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id) VALUES ($1, $2)",
+        )
+        .bind(leaderboard_id)
+        .bind(oldest)
+        .execute(&pool)
+        .await?;
+        let on_leaderboard = DirectoryFilter {
+            leaderboard_id: Some(leaderboard_id),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(list_directory_page(&pool, &on_leaderboard, None, 10).await?),
+            vec![oldest]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+//! Handing a snake to another user without re-registering it.
+//!
+//! The owner offers the snake to someone by login; it only changes hands
+//! once that user accepts. Until then either side can back out.
+
+use color_eyre::eyre::Context as _;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, PgPool, Postgres, Type};
+use uuid::Uuid;
+
+use crate::models::{battlesnake, tournament};
+
+/// Where a transfer stands. Only a pending transfer can change.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Canceled,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "pending",
+            TransferStatus::Accepted => "accepted",
+            TransferStatus::Declined => "declined",
+            TransferStatus::Canceled => "canceled",
+        }
+    }
+}
+
+/// A transfer with the names needed to list it
+#[derive(Debug, Clone)]
+pub struct TransferListing {
+    pub battlesnake_transfer_id: Uuid,
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    pub from_user_id: Uuid,
+    pub from_login: String,
+    pub to_user_id: Uuid,
+    pub to_login: String,
+    /// Whether the snake's leaderboard entries go with it
+    pub include_history: bool,
+    pub status: TransferStatus,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Offer `battlesnake_id` to the user with `to_login`. Returns
+/// `Ok(Err(message))` for user-facing refusals.
+pub async fn offer_transfer(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+    from_user_id: Uuid,
+    to_login: &str,
+    include_history: bool,
+) -> cja::Result<Result<Uuid, String>> {
+    let to_user_id = sqlx::query_scalar!(
+        "SELECT user_id FROM users WHERE lower(github_login) = lower($1) AND deleted_at IS NULL",
+        to_login.trim()
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to look up transfer recipient")?;
+    let Some(to_user_id) = to_user_id else {
+        return Ok(Err(format!("No user named {}", to_login.trim())));
+    };
+    if to_user_id == from_user_id {
+        return Ok(Err("That snake is already yours".to_string()));
+    }
+
+    if !battlesnake::belongs_to_user(pool, battlesnake_id, from_user_id).await? {
+        return Ok(Err("Battlesnake not found".to_string()));
+    }
+
+    let transfer_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO battlesnake_transfers (battlesnake_id, from_user_id, to_user_id, include_history)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (battlesnake_id) WHERE status = 'pending' DO NOTHING
+        RETURNING battlesnake_transfer_id
+        "#,
+        battlesnake_id,
+        from_user_id,
+        to_user_id,
+        include_history,
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to create battlesnake transfer")?;
+
+    Ok(transfer_id
+        .ok_or_else(|| "This snake already has a transfer waiting; cancel it first".to_string()))
+}
+
+/// Accept a transfer: only its recipient can, only while it's pending, and
+/// only while the sender still owns the snake. The snake's pending
+/// challenges are canceled, and without `include_history` its leaderboard
+/// entries are dropped so the new owner starts fresh. Returns
+/// `Ok(Err(message))` for user-facing refusals.
+pub async fn accept_transfer(
+    pool: &PgPool,
+    battlesnake_transfer_id: Uuid,
+    user_id: Uuid,
+) -> cja::Result<Result<(), String>> {
+    let mut tx = pool.begin().await.wrap_err("Failed to begin transaction")?;
+
+    let transfer = sqlx::query!(
+        r#"
+        SELECT battlesnake_id, from_user_id, to_user_id, include_history,
+               status as "status: TransferStatus"
+        FROM battlesnake_transfers
+        WHERE battlesnake_transfer_id = $1
+        FOR UPDATE
+        "#,
+        battlesnake_transfer_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .wrap_err("Failed to fetch battlesnake transfer")?;
+    let Some(transfer) = transfer.filter(|t| t.to_user_id == user_id) else {
+        return Ok(Err("Transfer not found".to_string()));
+    };
+    if transfer.status != TransferStatus::Pending {
+        return Ok(Err(format!(
+            "This transfer was already {}",
+            transfer.status.as_str()
+        )));
+    }
+
+    let snake = sqlx::query!(
+        "SELECT user_id, name FROM battlesnakes WHERE battlesnake_id = $1 FOR UPDATE",
+        transfer.battlesnake_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .wrap_err("Failed to lock battlesnake")?;
+    if snake.user_id != transfer.from_user_id {
+        return Ok(Err("The sender no longer owns this snake".to_string()));
+    }
+    // Tournament entries count against their owner's per-user cap, so a
+    // snake can't change hands mid-tournament
+    if tournament::count_active_tournament_registrations(&mut *tx, transfer.battlesnake_id).await?
+        > 0
+    {
+        return Ok(Err(
+            "This snake is in an active tournament; accept once the tournament is over".to_string(),
+        ));
+    }
+    let name_taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM battlesnakes WHERE user_id = $1 AND name = $2) as "exists!""#,
+        user_id,
+        snake.name
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .wrap_err("Failed to check battlesnake name")?;
+    if name_taken {
+        return Ok(Err(format!(
+            "You already have a battlesnake named '{}'; rename one of them first",
+            snake.name
+        )));
+    }
+
+    sqlx::query!(
+        "UPDATE battlesnakes SET user_id = $2 WHERE battlesnake_id = $1",
+        transfer.battlesnake_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to move battlesnake")?;
+    sqlx::query!(
+        r#"UPDATE challenges
+         SET status = 'canceled', responded_at = NOW()
+         WHERE status = 'pending'
+           AND (challenger_battlesnake_id = $1 OR opponent_battlesnake_id = $1)"#,
+        transfer.battlesnake_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to cancel pending challenges")?;
+    if !transfer.include_history {
+        sqlx::query!(
+            "DELETE FROM leaderboard_entries WHERE battlesnake_id = $1",
+            transfer.battlesnake_id
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to reset leaderboard entries")?;
+    }
+    resolve_transfer(&mut *tx, battlesnake_transfer_id, TransferStatus::Accepted).await?;
+
+    tx.commit().await.wrap_err("Failed to commit transfer")?;
+    Ok(Ok(()))
+}
+
+/// Move a pending transfer to `status`. Returns false if it was no longer
+/// pending.
+pub async fn resolve_transfer<'e, E>(
+    executor: E,
+    battlesnake_transfer_id: Uuid,
+    status: TransferStatus,
+) -> cja::Result<bool>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let result = sqlx::query!(
+        r#"
+        UPDATE battlesnake_transfers
+        SET status = $2, responded_at = NOW()
+        WHERE battlesnake_transfer_id = $1 AND status = 'pending'
+        "#,
+        battlesnake_transfer_id,
+        status.as_str(),
+    )
+    .execute(executor)
+    .await
+    .wrap_err("Failed to resolve battlesnake transfer")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_transfer_by_id(
+    pool: &PgPool,
+    battlesnake_transfer_id: Uuid,
+) -> cja::Result<Option<TransferListing>> {
+    sqlx::query_as!(
+        TransferListing,
+        r#"
+        SELECT
+            t.battlesnake_transfer_id,
+            t.battlesnake_id,
+            b.name as snake_name,
+            t.from_user_id,
+            fu.github_login as from_login,
+            t.to_user_id,
+            tu.github_login as to_login,
+            t.include_history,
+            t.status as "status: TransferStatus",
+            t.created_at
+        FROM battlesnake_transfers t
+        JOIN battlesnakes b ON b.battlesnake_id = t.battlesnake_id
+        JOIN users fu ON fu.user_id = t.from_user_id
+        JOIN users tu ON tu.user_id = t.to_user_id
+        WHERE t.battlesnake_transfer_id = $1
+        "#,
+        battlesnake_transfer_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch battlesnake transfer")
+}
+
+/// Pending transfers the user offered or was offered, oldest first
+pub async fn list_pending_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<TransferListing>> {
+    sqlx::query_as!(
+        TransferListing,
+        r#"
+        SELECT
+            t.battlesnake_transfer_id,
+            t.battlesnake_id,
+            b.name as snake_name,
+            t.from_user_id,
+            fu.github_login as from_login,
+            t.to_user_id,
+            tu.github_login as to_login,
+            t.include_history,
+            t.status as "status: TransferStatus",
+            t.created_at
+        FROM battlesnake_transfers t
+        JOIN battlesnakes b ON b.battlesnake_id = t.battlesnake_id
+        JOIN users fu ON fu.user_id = t.from_user_id
+        JOIN users tu ON tu.user_id = t.to_user_id
+        WHERE (t.from_user_id = $1 OR t.to_user_id = $1)
+          AND t.status = 'pending'
+        ORDER BY t.created_at
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list pending battlesnake transfers")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixture_user_with_snake;
+
+    async fn owner_of(pool: &PgPool, battlesnake_id: Uuid) -> cja::Result<Uuid> {
+        Ok(
+            sqlx::query_scalar("SELECT user_id FROM battlesnakes WHERE battlesnake_id = $1")
+                .bind(battlesnake_id)
+                .fetch_one(pool)
+                .await?,
+        )
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_transfer_needs_both_sides(pool: PgPool) -> cja::Result<()> {
+        let (owner, snake) = fixture_user_with_snake(&pool, "owner").await?;
+        let (recipient, recipients_snake) = fixture_user_with_snake(&pool, "recipient").await?;
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id) VALUES ($1, $2)",
+        )
+        .bind(leaderboard_id)
+        .bind(snake)
+        .execute(&pool)
+        .await?;
+
+        assert!(
+            offer_transfer(&pool, snake, owner, "nobody", true)
+                .await?
+                .is_err()
+        );
+        assert!(
+            offer_transfer(&pool, snake, owner, "owner", true)
+                .await?
+                .is_err()
+        );
+        // Only the owner can offer a snake
+        assert!(
+            offer_transfer(&pool, snake, recipient, "owner", true)
+                .await?
+                .is_err()
+        );
+
+        let transfer_id = offer_transfer(&pool, snake, owner, "Recipient", true)
+            .await?
+            .unwrap();
+        // One offer at a time
+        assert!(
+            offer_transfer(&pool, snake, owner, "recipient", true)
+                .await?
+                .is_err()
+        );
+        for user_id in [owner, recipient] {
+            assert_eq!(list_pending_for_user(&pool, user_id).await?.len(), 1);
+        }
+
+        // Nothing moves until the recipient accepts, and only they can
+        assert_eq!(owner_of(&pool, snake).await?, owner);
+        assert!(accept_transfer(&pool, transfer_id, owner).await?.is_err());
+
+        // The recipient's own snake has the same name
+        sqlx::query("UPDATE battlesnakes SET name = 'owner-snake' WHERE battlesnake_id = $1")
+            .bind(recipients_snake)
+            .execute(&pool)
+            .await?;
+        assert!(
+            accept_transfer(&pool, transfer_id, recipient)
+                .await?
+                .is_err()
+        );
+        sqlx::query("UPDATE battlesnakes SET name = 'other' WHERE battlesnake_id = $1")
+            .bind(recipients_snake)
+            .execute(&pool)
+            .await?;
+
+        assert!(
+            accept_transfer(&pool, transfer_id, recipient)
+                .await?
+                .is_ok()
+        );
+        assert_eq!(owner_of(&pool, snake).await?, recipient);
+        let entries: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM leaderboard_entries WHERE battlesnake_id = $1",
+        )
+        .bind(snake)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(entries, 1);
+        assert!(
+            accept_transfer(&pool, transfer_id, recipient)
+                .await?
+                .is_err()
+        );
+        assert!(list_pending_for_user(&pool, owner).await?.is_empty());
+
+        // Passing it back without history leaves the leaderboards behind
+        let transfer_id = offer_transfer(&pool, snake, recipient, "owner", false)
+            .await?
+            .unwrap();
+        assert!(accept_transfer(&pool, transfer_id, owner).await?.is_ok());
+        let entries: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM leaderboard_entries WHERE battlesnake_id = $1",
+        )
+        .bind(snake)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(entries, 0);
+
+        // A declined offer can't be accepted afterwards
+        let transfer_id = offer_transfer(&pool, snake, owner, "recipient", true)
+            .await?
+            .unwrap();
+        assert!(resolve_transfer(&pool, transfer_id, TransferStatus::Declined).await?);
+        assert!(!resolve_transfer(&pool, transfer_id, TransferStatus::Canceled).await?);
+        assert!(
+            accept_transfer(&pool, transfer_id, recipient)
+                .await?
+                .is_err()
+        );
+        assert_eq!(owner_of(&pool, snake).await?, owner);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! Snake version history.
+//!
+//! A snake gets a new version whenever its owner points it at a new URL or
+//! its `GET /` reports a different `version`. Rating charts and game
+//! histories mark the changes so authors can see whether a deploy helped.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Longest reported version we keep; anything past it is cut off
+const MAX_VERSION_CHARS: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct SnakeVersion {
+    pub battlesnake_version_id: Uuid,
+    /// What the snake reported, if it reports anything
+    pub version: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// 1 for the snake's first version, counting up
+    pub number: i64,
+    /// Whether this version came from a URL change rather than a new
+    /// reported version
+    pub url_changed: bool,
+}
+
+impl SnakeVersion {
+    /// The reported version, or the version's number for snakes that don't
+    /// report one
+    pub fn label(&self) -> String {
+        self.version
+            .clone()
+            .unwrap_or_else(|| format!("#{}", self.number))
+    }
+}
+
+/// The version that was live at `at`, from a list ordered oldest first
+pub fn version_at(
+    versions: &[SnakeVersion],
+    at: chrono::DateTime<chrono::Utc>,
+) -> Option<&SnakeVersion> {
+    versions.iter().rev().find(|v| v.created_at <= at)
+}
+
+/// Note what a snake is running now. Records a new version when the URL or
+/// the reported version differs from the latest one; a first report for a
+/// version with none just fills it in. A snake that stops reporting keeps
+/// its last version. Returns whether a new version was recorded.
+pub async fn record_observed(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+    url: &str,
+    version: Option<&str>,
+) -> cja::Result<bool> {
+    let version = version
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.chars().take(MAX_VERSION_CHARS).collect::<String>());
+
+    let mut tx = pool.begin().await.wrap_err("Failed to start transaction")?;
+
+    // Lock the snake so concurrent game starts can't both record the same
+    // new version
+    let snake = sqlx::query_scalar!(
+        "SELECT battlesnake_id FROM battlesnakes WHERE battlesnake_id = $1 FOR UPDATE",
+        battlesnake_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .wrap_err("Failed to lock battlesnake")?;
+    if snake.is_none() {
+        return Ok(false);
+    }
+
+    let latest = sqlx::query!(
+        r#"
+        SELECT battlesnake_version_id, url, version
+        FROM battlesnake_versions
+        WHERE battlesnake_id = $1
+        ORDER BY created_at DESC, battlesnake_version_id DESC
+        LIMIT 1
+        "#,
+        battlesnake_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .wrap_err("Failed to fetch latest battlesnake version")?;
+
+    let recorded = match latest {
+        Some(latest) if latest.url == url => match (&version, &latest.version) {
+            (Some(version), None) => {
+                sqlx::query!(
+                    "UPDATE battlesnake_versions SET version = $2 WHERE battlesnake_version_id = $1",
+                    latest.battlesnake_version_id,
+                    version
+                )
+                .execute(&mut *tx)
+                .await
+                .wrap_err("Failed to fill in battlesnake version")?;
+                false
+            }
+            (Some(version), Some(latest_version)) if version != latest_version => true,
+            _ => false,
+        },
+        _ => true,
+    };
+
+    if recorded {
+        sqlx::query!(
+            "INSERT INTO battlesnake_versions (battlesnake_id, url, version) VALUES ($1, $2, $3)",
+            battlesnake_id,
+            url,
+            version
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to record battlesnake version")?;
+    }
+
+    tx.commit()
+        .await
+        .wrap_err("Failed to commit battlesnake version")?;
+    Ok(recorded)
+}
+
+/// A snake's versions, oldest first
+pub async fn list_for_battlesnake(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+) -> cja::Result<Vec<SnakeVersion>> {
+    let versions = sqlx::query_as!(
+        SnakeVersion,
+        r#"
+        SELECT
+            battlesnake_version_id,
+            version,
+            created_at,
+            ROW_NUMBER() OVER w as "number!",
+            (LAG(url) OVER w IS DISTINCT FROM url) as "url_changed!"
+        FROM battlesnake_versions
+        WHERE battlesnake_id = $1
+        WINDOW w AS (ORDER BY created_at, battlesnake_version_id)
+        ORDER BY created_at, battlesnake_version_id
+        "#,
+        battlesnake_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch battlesnake versions")?;
+
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_record_observed(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url)
+             VALUES ($1, 'snake', 'http://a.example') RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+
+        // The first sighting is the first version; a first report fills it in
+        assert!(record_observed(&pool, battlesnake_id, "http://a.example", None).await?);
+        assert!(!record_observed(&pool, battlesnake_id, "http://a.example", Some("1.0")).await?);
+        assert!(!record_observed(&pool, battlesnake_id, "http://a.example", Some(" 1.0 ")).await?);
+        // Going quiet keeps the last version
+        assert!(!record_observed(&pool, battlesnake_id, "http://a.example", None).await?);
+        assert!(record_observed(&pool, battlesnake_id, "http://a.example", Some("1.1")).await?);
+        assert!(record_observed(&pool, battlesnake_id, "http://b.example", None).await?);
+
+        let versions = list_for_battlesnake(&pool, battlesnake_id).await?;
+        let labels: Vec<String> = versions.iter().map(SnakeVersion::label).collect();
+        assert_eq!(labels, vec!["1.0", "1.1", "#3"]);
+        let url_changes: Vec<bool> = versions.iter().map(|v| v.url_changed).collect();
+        assert_eq!(url_changes, vec![true, false, true]);
+
+        assert!(
+            version_at(
+                &versions,
+                versions[0].created_at - chrono::Duration::seconds(1)
+            )
+            .is_none()
+        );
+        assert_eq!(
+            version_at(&versions, chrono::Utc::now()).map(|v| v.number),
+            Some(3)
+        );
+
+        // Unknown snakes are ignored
+        assert!(!record_observed(&pool, Uuid::new_v4(), "http://a.example", None).await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,425 @@
+use color_eyre::eyre::Context as _;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, PgPool, Postgres, Transaction, Type};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::game::{GameBoardSize, GameType};
+
+/// Where a challenge stands. Challenges to public snakes start (and stay)
+/// `accepted`; challenges to private snakes wait as `pending` for the
+/// opponent, and only a pending challenge can change.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Canceled,
+}
+
+impl ChallengeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChallengeStatus::Pending => "pending",
+            ChallengeStatus::Accepted => "accepted",
+            ChallengeStatus::Declined => "declined",
+            ChallengeStatus::Canceled => "canceled",
+        }
+    }
+}
+
+/// One snake challenging another to an unranked game.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub challenge_id: Uuid,
+    pub challenger_user_id: Uuid,
+    pub challenger_battlesnake_id: Uuid,
+    pub opponent_user_id: Uuid,
+    pub opponent_battlesnake_id: Uuid,
+    pub board_size: GameBoardSize,
+    pub game_type: GameType,
+    pub status: ChallengeStatus,
+    /// The game played, once accepted. Cleared if the game is deleted.
+    pub game_id: Option<Uuid>,
+    pub responded_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+struct ChallengeRow {
+    challenge_id: Uuid,
+    challenger_user_id: Uuid,
+    challenger_battlesnake_id: Uuid,
+    opponent_user_id: Uuid,
+    opponent_battlesnake_id: Uuid,
+    board_size: String,
+    game_type: String,
+    status: ChallengeStatus,
+    game_id: Option<Uuid>,
+    responded_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<ChallengeRow> for Challenge {
+    type Error = color_eyre::eyre::Report;
+
+    fn try_from(row: ChallengeRow) -> Result<Self, Self::Error> {
+        Ok(Challenge {
+            challenge_id: row.challenge_id,
+            challenger_user_id: row.challenger_user_id,
+            challenger_battlesnake_id: row.challenger_battlesnake_id,
+            opponent_user_id: row.opponent_user_id,
+            opponent_battlesnake_id: row.opponent_battlesnake_id,
+            board_size: GameBoardSize::from_str(&row.board_size)
+                .wrap_err_with(|| format!("Invalid board size: {}", row.board_size))?,
+            game_type: GameType::from_str(&row.game_type)
+                .wrap_err_with(|| format!("Invalid game type: {}", row.game_type))?,
+            status: row.status,
+            game_id: row.game_id,
+            responded_at: row.responded_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+/// A challenge with the names needed to list it: who challenged whom, with
+/// which snakes, and on what board.
+#[derive(Debug, Clone)]
+pub struct ChallengeListing {
+    pub challenge_id: Uuid,
+    pub challenger_user_id: Uuid,
+    pub challenger_login: String,
+    pub challenger_battlesnake_id: Uuid,
+    pub challenger_snake_name: String,
+    pub opponent_user_id: Uuid,
+    pub opponent_login: String,
+    pub opponent_battlesnake_id: Uuid,
+    pub opponent_snake_name: String,
+    pub board_size: String,
+    pub game_type: String,
+    pub status: ChallengeStatus,
+    pub game_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateChallenge {
+    pub challenger_user_id: Uuid,
+    pub challenger_battlesnake_id: Uuid,
+    pub opponent_user_id: Uuid,
+    pub opponent_battlesnake_id: Uuid,
+    pub board_size: GameBoardSize,
+    pub game_type: GameType,
+    /// `Accepted` with its `game_id` for a challenge played right away,
+    /// `Pending` (and no game) for one waiting on the opponent.
+    pub status: ChallengeStatus,
+    pub game_id: Option<Uuid>,
+}
+
+pub async fn create_challenge<'e, E>(executor: E, data: CreateChallenge) -> cja::Result<Challenge>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let row = sqlx::query_as!(
+        ChallengeRow,
+        r#"
+        INSERT INTO challenges (
+            challenger_user_id,
+            challenger_battlesnake_id,
+            opponent_user_id,
+            opponent_battlesnake_id,
+            board_size,
+            game_type,
+            status,
+            game_id,
+            responded_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $7 = 'pending' THEN NULL ELSE NOW() END)
+        RETURNING
+            challenge_id,
+            challenger_user_id,
+            challenger_battlesnake_id,
+            opponent_user_id,
+            opponent_battlesnake_id,
+            board_size,
+            game_type,
+            status as "status: ChallengeStatus",
+            game_id,
+            responded_at,
+            created_at,
+            updated_at
+        "#,
+        data.challenger_user_id,
+        data.challenger_battlesnake_id,
+        data.opponent_user_id,
+        data.opponent_battlesnake_id,
+        data.board_size.as_str(),
+        data.game_type.as_str(),
+        data.status.as_str(),
+        data.game_id,
+    )
+    .fetch_one(executor)
+    .await
+    .wrap_err("Failed to create challenge")?;
+
+    row.try_into()
+}
+
+pub async fn get_challenge_by_id(
+    pool: &PgPool,
+    challenge_id: Uuid,
+) -> cja::Result<Option<Challenge>> {
+    let row = sqlx::query_as!(
+        ChallengeRow,
+        r#"
+        SELECT
+            challenge_id,
+            challenger_user_id,
+            challenger_battlesnake_id,
+            opponent_user_id,
+            opponent_battlesnake_id,
+            board_size,
+            game_type,
+            status as "status: ChallengeStatus",
+            game_id,
+            responded_at,
+            created_at,
+            updated_at
+        FROM challenges
+        WHERE challenge_id = $1
+        "#,
+        challenge_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch challenge")?;
+
+    row.map(Challenge::try_from).transpose()
+}
+
+/// Fetch a challenge with its row locked, so accepting it and creating its
+/// game can't race a decline or a second accept.
+pub async fn get_challenge_for_update(
+    tx: &mut Transaction<'_, Postgres>,
+    challenge_id: Uuid,
+) -> cja::Result<Option<Challenge>> {
+    let row = sqlx::query_as!(
+        ChallengeRow,
+        r#"
+        SELECT
+            challenge_id,
+            challenger_user_id,
+            challenger_battlesnake_id,
+            opponent_user_id,
+            opponent_battlesnake_id,
+            board_size,
+            game_type,
+            status as "status: ChallengeStatus",
+            game_id,
+            responded_at,
+            created_at,
+            updated_at
+        FROM challenges
+        WHERE challenge_id = $1
+        FOR UPDATE
+        "#,
+        challenge_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .wrap_err("Failed to fetch challenge for update")?;
+
+    row.map(Challenge::try_from).transpose()
+}
+
+/// Move a pending challenge to `status`, recording the game it produced.
+/// Returns false if the challenge was no longer pending.
+pub async fn resolve_challenge<'e, E>(
+    executor: E,
+    challenge_id: Uuid,
+    status: ChallengeStatus,
+    game_id: Option<Uuid>,
+) -> cja::Result<bool>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let result = sqlx::query!(
+        r#"
+        UPDATE challenges
+        SET status = $2, game_id = $3, responded_at = NOW()
+        WHERE challenge_id = $1 AND status = 'pending'
+        "#,
+        challenge_id,
+        status.as_str(),
+        game_id,
+    )
+    .execute(executor)
+    .await
+    .wrap_err("Failed to resolve challenge")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Challenges the user sent or received, newest first.
+pub async fn list_challenges_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<ChallengeListing>> {
+    let listings = sqlx::query_as!(
+        ChallengeListing,
+        r#"
+        SELECT
+            c.challenge_id,
+            c.challenger_user_id,
+            cu.github_login as challenger_login,
+            c.challenger_battlesnake_id,
+            cs.name as challenger_snake_name,
+            c.opponent_user_id,
+            ou.github_login as opponent_login,
+            c.opponent_battlesnake_id,
+            os.name as opponent_snake_name,
+            c.board_size,
+            c.game_type,
+            c.status as "status: ChallengeStatus",
+            c.game_id,
+            c.created_at
+        FROM challenges c
+        JOIN users cu ON cu.user_id = c.challenger_user_id
+        JOIN battlesnakes cs ON cs.battlesnake_id = c.challenger_battlesnake_id
+        JOIN users ou ON ou.user_id = c.opponent_user_id
+        JOIN battlesnakes os ON os.battlesnake_id = c.opponent_battlesnake_id
+        WHERE c.challenger_user_id = $1 OR c.opponent_user_id = $1
+        ORDER BY c.created_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list challenges")?;
+
+    Ok(listings)
+}
+
+/// Pending challenges the user sent or received, oldest first.
+pub async fn list_pending_challenges_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<ChallengeListing>> {
+    let listings = sqlx::query_as!(
+        ChallengeListing,
+        r#"
+        SELECT
+            c.challenge_id,
+            c.challenger_user_id,
+            cu.github_login as challenger_login,
+            c.challenger_battlesnake_id,
+            cs.name as challenger_snake_name,
+            c.opponent_user_id,
+            ou.github_login as opponent_login,
+            c.opponent_battlesnake_id,
+            os.name as opponent_snake_name,
+            c.board_size,
+            c.game_type,
+            c.status as "status: ChallengeStatus",
+            c.game_id,
+            c.created_at
+        FROM challenges c
+        JOIN users cu ON cu.user_id = c.challenger_user_id
+        JOIN battlesnakes cs ON cs.battlesnake_id = c.challenger_battlesnake_id
+        JOIN users ou ON ou.user_id = c.opponent_user_id
+        JOIN battlesnakes os ON os.battlesnake_id = c.opponent_battlesnake_id
+        WHERE (c.challenger_user_id = $1 OR c.opponent_user_id = $1)
+          AND c.status = 'pending'
+        ORDER BY c.created_at
+        "#,
+        user_id,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list pending challenges")?;
+
+    Ok(listings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixture_user_with_snake;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_pending_challenge_resolves_once(pool: PgPool) -> cja::Result<()> {
+        let (challenger, challenger_snake) = fixture_user_with_snake(&pool, "challenger").await?;
+        let (opponent, opponent_snake) = fixture_user_with_snake(&pool, "opponent").await?;
+
+        let challenge = create_challenge(
+            &pool,
+            CreateChallenge {
+                challenger_user_id: challenger,
+                challenger_battlesnake_id: challenger_snake,
+                opponent_user_id: opponent,
+                opponent_battlesnake_id: opponent_snake,
+                board_size: GameBoardSize::Medium,
+                game_type: GameType::Standard,
+                status: ChallengeStatus::Pending,
+                game_id: None,
+            },
+        )
+        .await?;
+        assert_eq!(challenge.status, ChallengeStatus::Pending);
+        assert!(challenge.responded_at.is_none());
+
+        // Both sides see it, with names filled in
+        for user_id in [challenger, opponent] {
+            let listed = list_challenges_for_user(&pool, user_id, 10).await?;
+            assert_eq!(listed.len(), 1);
+            assert_eq!(listed[0].challenger_login, "challenger");
+            assert_eq!(listed[0].opponent_snake_name, "opponent-snake");
+            assert_eq!(
+                list_pending_challenges_for_user(&pool, user_id)
+                    .await?
+                    .len(),
+                1
+            );
+        }
+
+        assert!(
+            resolve_challenge(
+                &pool,
+                challenge.challenge_id,
+                ChallengeStatus::Declined,
+                None
+            )
+            .await?
+        );
+        // Only a pending challenge can change
+        assert!(
+            !resolve_challenge(
+                &pool,
+                challenge.challenge_id,
+                ChallengeStatus::Canceled,
+                None
+            )
+            .await?
+        );
+
+        let reloaded = get_challenge_by_id(&pool, challenge.challenge_id)
+            .await?
+            .unwrap();
+        assert_eq!(reloaded.status, ChallengeStatus::Declined);
+        assert!(reloaded.responded_at.is_some());
+        assert!(
+            list_pending_challenges_for_user(&pool, opponent)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+//! Queries behind the orphaned-record consistency check.
+//!
+//! Foreign keys already make some inconsistencies impossible: a
+//! `leaderboard_games` row cascades away with its game, so it can't outlive
+//! it. What can happen is a follow-up step that never ran, so that's what is
+//! checked here:
+//! - finished leaderboard games whose ratings were never applied (the rating
+//!   job died or was never enqueued), among those the rating update would
+//!   actually rate
+//! - leaderboard game snakes whose `leaderboard_entry_id` was nulled when the
+//!   entry was deleted
+//! - waiting games with no runner job (the enqueue after commit failed)
+
+use color_eyre::eyre::Context as _;
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::jobs::{GameRunnerJob, LeaderboardRatingUpdateJob};
+use crate::state::AppState;
+
+/// Counts of records the consistency check would flag right now
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct ConsistencyReport {
+    /// Finished leaderboard games with no ratings applied and no rating job
+    /// queued, on leaderboards that aren't frozen
+    pub unrated_leaderboard_games: i64,
+    /// Snakes in leaderboard games that lost their leaderboard entry link
+    pub unlinked_entry_snakes: i64,
+    /// Waiting games with no runner job
+    pub unenqueued_games: i64,
+}
+
+/// `cutoff` is the grace period: only records older than it are counted, so
+/// work that is merely in flight isn't flagged.
+pub async fn get_report(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<ConsistencyReport> {
+    let unrated_leaderboard_games = find_unrated_leaderboard_games(pool, cutoff, i64::MAX)
+        .await?
+        .len() as i64;
+    let unenqueued_games = find_unenqueued_games(pool, cutoff, i64::MAX).await?.len() as i64;
+    let unlinked_entry_snakes = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!"
+         FROM game_battlesnakes gb
+         JOIN leaderboard_games lg ON lg.game_id = gb.game_id
+         WHERE gb.leaderboard_entry_id IS NULL"#
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to count unlinked leaderboard game snakes")?;
+
+    Ok(ConsistencyReport {
+        unrated_leaderboard_games,
+        unlinked_entry_snakes,
+        unenqueued_games,
+    })
+}
+
+/// Leaderboard games that finished before `cutoff` with placements but no
+/// results, no queued rating job, and an unfrozen leaderboard. Games with
+/// fewer than two snakes that have a leaderboard entry are left out: the
+/// rating update skips them without writing results, so they'd be flagged
+/// again every pass.
+pub async fn find_unrated_leaderboard_games(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> cja::Result<Vec<Uuid>> {
+    sqlx::query_scalar!(
+        r#"SELECT lg.leaderboard_game_id
+         FROM leaderboard_games lg
+         JOIN games g ON g.game_id = lg.game_id
+         JOIN leaderboards l ON l.leaderboard_id = lg.leaderboard_id
+         WHERE g.status = 'finished'
+           AND g.updated_at < $1
+           AND l.frozen_at IS NULL
+           AND EXISTS (SELECT 1 FROM game_battlesnakes gb
+                       WHERE gb.game_id = g.game_id AND gb.placement IS NOT NULL)
+           AND (SELECT COUNT(*) FROM game_battlesnakes gb
+                WHERE gb.game_id = g.game_id
+                  AND (gb.leaderboard_entry_id IS NOT NULL
+                       OR EXISTS (SELECT 1 FROM leaderboard_entries le
+                                  WHERE le.leaderboard_id = lg.leaderboard_id
+                                    AND le.battlesnake_id = gb.battlesnake_id))) >= 2
+           AND NOT EXISTS (SELECT 1 FROM leaderboard_game_results lgr
+                           WHERE lgr.leaderboard_game_id = lg.leaderboard_game_id)
+           AND NOT EXISTS (SELECT 1 FROM jobs j
+                           WHERE j.name = $2
+                             AND j.payload->>'leaderboard_game_id' = lg.leaderboard_game_id::text)
+         ORDER BY g.updated_at ASC
+         LIMIT $3"#,
+        cutoff,
+        <LeaderboardRatingUpdateJob as cja::jobs::Job<AppState>>::NAME,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to find unrated leaderboard games")
+}
+
+/// Waiting games created before `cutoff` that have no runner job. Tournament
+/// games are left to the stuck-match sweeper.
+pub async fn find_unenqueued_games(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> cja::Result<Vec<Uuid>> {
+    sqlx::query_scalar!(
+        r#"SELECT g.game_id
+         FROM games g
+         WHERE g.status = 'waiting'
+           AND g.created_at < $1
+           AND NOT EXISTS (SELECT 1 FROM jobs j
+                           WHERE j.name = $2 AND j.payload->>'game_id' = g.game_id::text)
+           AND NOT EXISTS (SELECT 1 FROM match_games mg WHERE mg.game_id = g.game_id)
+         ORDER BY g.created_at ASC
+         LIMIT $3"#,
+        cutoff,
+        <GameRunnerJob as cja::jobs::Job<AppState>>::NAME,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to find unenqueued games")
+}
+
+/// Point leaderboard game snakes that lost their entry link back at their
+/// snake's current entry on that leaderboard, where one exists. Returns how
+/// many were relinked.
+pub async fn relink_entry_snakes(pool: &PgPool) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        r#"UPDATE game_battlesnakes gb
+         SET leaderboard_entry_id = le.leaderboard_entry_id
+         FROM leaderboard_games lg, leaderboard_entries le
+         WHERE lg.game_id = gb.game_id
+           AND le.leaderboard_id = lg.leaderboard_id
+           AND le.battlesnake_id = gb.battlesnake_id
+           AND gb.leaderboard_entry_id IS NULL"#
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to relink leaderboard game snakes")?;
+
+    Ok(result.rows_affected())
+}
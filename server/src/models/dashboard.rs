@@ -0,0 +1,354 @@
+//! Aggregates for the signed-in home page: where a user's snakes stand,
+//! how their latest games went, and which snakes need a look.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{leaderboard::MIN_GAMES_FOR_RANKING, snake_error::FAILING_ERROR_RATE};
+
+/// One of a user's snakes on one active leaderboard. Snakes that aren't
+/// entered anywhere get a single row with the leaderboard fields empty.
+#[derive(Debug, Clone)]
+pub struct SnakeStanding {
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    pub snake_color: String,
+    pub leaderboard_entry_id: Option<Uuid>,
+    pub leaderboard_id: Option<Uuid>,
+    pub leaderboard_name: Option<String>,
+    pub display_score: Option<f64>,
+    pub games_played: Option<i32>,
+    pub paused: bool,
+    /// Position on the rating-sorted leaderboard; `None` while paused or
+    /// short of `MIN_GAMES_FOR_RANKING`
+    pub rank: Option<i64>,
+    /// Rating gained or lost over the last day; `None` without games
+    pub day_change: Option<f64>,
+}
+
+/// Every snake a user owns, with its standing on each active leaderboard
+pub async fn get_snake_standings(pool: &PgPool, user_id: Uuid) -> cja::Result<Vec<SnakeStanding>> {
+    let standings = sqlx::query_as!(
+        SnakeStanding,
+        r#"
+        SELECT
+            b.battlesnake_id,
+            b.name as snake_name,
+            b.color as snake_color,
+            le.leaderboard_entry_id as "leaderboard_entry_id?",
+            l.leaderboard_id as "leaderboard_id?",
+            l.name as "leaderboard_name?",
+            le.display_score as "display_score?",
+            le.games_played as "games_played?",
+            (le.disabled_at IS NOT NULL) as "paused!",
+            CASE WHEN le.disabled_at IS NULL AND le.games_played >= $2 THEN (
+                SELECT COUNT(*) + 1
+                FROM leaderboard_entries other
+                WHERE other.leaderboard_id = le.leaderboard_id
+                  AND other.disabled_at IS NULL
+                  AND other.games_played >= $2
+                  AND other.display_score > le.display_score
+            ) END as rank,
+            (
+                SELECT SUM(r.display_score_change)
+                FROM leaderboard_game_results r
+                WHERE r.leaderboard_entry_id = le.leaderboard_entry_id
+                  AND r.game_created_at >= NOW() - INTERVAL '1 day'
+            ) as day_change
+        FROM battlesnakes b
+        LEFT JOIN (
+            leaderboard_entries le
+            JOIN leaderboards l ON l.leaderboard_id = le.leaderboard_id AND l.disabled_at IS NULL
+        ) ON le.battlesnake_id = b.battlesnake_id
+        WHERE b.user_id = $1
+        ORDER BY b.name, b.battlesnake_id, l.name
+        "#,
+        user_id,
+        MIN_GAMES_FOR_RANKING
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch snake standings")?;
+
+    Ok(standings)
+}
+
+/// How one of a user's snakes did in a recent game
+#[derive(Debug, Clone)]
+pub struct RecentPlacement {
+    pub game_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub status: String,
+    pub snake_name: String,
+    pub snake_color: String,
+    /// `None` until the game finishes
+    pub placement: Option<i32>,
+    pub snake_count: i64,
+    /// The leaderboard the game was played for, if any
+    pub leaderboard_name: Option<String>,
+}
+
+/// The user's snakes' latest games, newest first. A game two of the user's
+/// snakes played in shows up once per snake.
+pub async fn get_recent_placements(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<RecentPlacement>> {
+    let placements = sqlx::query_as!(
+        RecentPlacement,
+        r#"
+        SELECT
+            g.game_id,
+            g.created_at,
+            g.status,
+            b.name as snake_name,
+            b.color as snake_color,
+            gb.placement,
+            (SELECT COUNT(*) FROM game_battlesnakes o WHERE o.game_id = g.game_id) as "snake_count!",
+            l.name as "leaderboard_name?"
+        FROM battlesnakes b
+        JOIN game_battlesnakes gb ON gb.battlesnake_id = b.battlesnake_id
+        JOIN games g ON g.game_id = gb.game_id
+        LEFT JOIN leaderboard_entries le ON le.leaderboard_entry_id = gb.leaderboard_entry_id
+        LEFT JOIN leaderboards l ON l.leaderboard_id = le.leaderboard_id
+        WHERE b.user_id = $1
+        ORDER BY g.created_at DESC, g.game_id, b.name
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch recent placements")?;
+
+    Ok(placements)
+}
+
+/// One of a user's snakes that is failing health probes or /move calls
+#[derive(Debug, Clone)]
+pub struct SnakeHealthWarning {
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    /// Failed health probes in a row
+    pub consecutive_failures: i32,
+    pub last_failure: Option<String>,
+    /// Set while the health sweeper has the snake out of matchmaking
+    pub deactivated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub total_moves: i64,
+    pub failed_moves: i64,
+}
+
+impl SnakeHealthWarning {
+    pub fn error_rate(&self) -> f64 {
+        if self.total_moves == 0 {
+            0.0
+        } else {
+            self.failed_moves as f64 / self.total_moves as f64
+        }
+    }
+
+    fn needs_attention(&self) -> bool {
+        self.deactivated_at.is_some()
+            || self.consecutive_failures > 0
+            || self.error_rate() >= FAILING_ERROR_RATE
+    }
+}
+
+/// The user's snakes that were deactivated, are failing health probes, or
+/// failed too many /move calls since `since`, most serious first
+pub async fn get_health_warnings(
+    pool: &PgPool,
+    user_id: Uuid,
+    since: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<Vec<SnakeHealthWarning>> {
+    let snakes = sqlx::query_as!(
+        SnakeHealthWarning,
+        r#"
+        SELECT
+            b.battlesnake_id,
+            b.name as snake_name,
+            COALESCE(h.consecutive_failures, 0) as "consecutive_failures!",
+            h.last_failure as "last_failure?",
+            h.deactivated_at as "deactivated_at?",
+            moves.total_moves as "total_moves!",
+            moves.failed_moves as "failed_moves!"
+        FROM battlesnakes b
+        LEFT JOIN snake_health_status h ON h.battlesnake_id = b.battlesnake_id
+        CROSS JOIN LATERAL (
+            SELECT
+                COUNT(*) as total_moves,
+                COUNT(*) FILTER (WHERE st.timed_out OR st.error_kind IS NOT NULL) as failed_moves
+            FROM game_battlesnakes gb
+            JOIN snake_turns st ON st.game_battlesnake_id = gb.game_battlesnake_id
+            WHERE gb.battlesnake_id = b.battlesnake_id
+              AND st.created_at >= $2
+        ) moves
+        WHERE b.user_id = $1
+        ORDER BY
+            h.deactivated_at IS NULL,
+            COALESCE(h.consecutive_failures, 0) DESC,
+            b.name
+        "#,
+        user_id,
+        since
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch snake health warnings")?;
+
+    Ok(snakes
+        .into_iter()
+        .filter(SnakeHealthWarning::needs_attention)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_dashboard_aggregates(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut snakes = Vec::new();
+        for name in ["alpha", "beta"] {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+        }
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let entry_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id, games_played, display_score)
+             VALUES ($1, $2, $3, 30) RETURNING leaderboard_entry_id",
+        )
+        .bind(leaderboard_id)
+        .bind(snakes[0])
+        .bind(MIN_GAMES_FOR_RANKING)
+        .fetch_one(&pool)
+        .await?;
+
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status)
+             VALUES ('11x11', 'Standard', 'finished') RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let leaderboard_game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboard_games (leaderboard_id, game_id)
+             VALUES ($1, $2) RETURNING leaderboard_game_id",
+        )
+        .bind(leaderboard_id)
+        .bind(game_id)
+        .fetch_one(&pool)
+        .await?;
+        let mut game_battlesnake_ids = Vec::new();
+        for (battlesnake_id, entry, placement) in
+            [(snakes[0], Some(entry_id), 1), (snakes[1], None, 2)]
+        {
+            let game_battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id, leaderboard_entry_id, placement)
+                 VALUES ($1, $2, $3, $4) RETURNING game_battlesnake_id",
+            )
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .bind(entry)
+            .bind(placement)
+            .fetch_one(&pool)
+            .await?;
+            game_battlesnake_ids.push(game_battlesnake_id);
+        }
+        sqlx::query(
+            "INSERT INTO leaderboard_game_results (
+                leaderboard_game_id, leaderboard_entry_id, placement,
+                mu_before, mu_after, sigma_before, sigma_after, display_score_change,
+                game_created_at
+             )
+             VALUES ($1, $2, 1, 25, 26, 8, 7.5, 1.5, NOW())",
+        )
+        .bind(leaderboard_game_id)
+        .bind(entry_id)
+        .execute(&pool)
+        .await?;
+
+        let standings = get_snake_standings(&pool, user_id).await?;
+        assert_eq!(standings.len(), 2);
+        assert_eq!(standings[0].snake_name, "alpha");
+        assert_eq!(standings[0].leaderboard_name.as_deref(), Some("Duels"));
+        assert_eq!(standings[0].rank, Some(1));
+        assert_eq!(standings[0].day_change, Some(1.5));
+        assert!(!standings[0].paused);
+        assert_eq!(standings[1].snake_name, "beta");
+        assert!(standings[1].leaderboard_entry_id.is_none());
+
+        let placements = get_recent_placements(&pool, user_id, 10).await?;
+        let seen: Vec<_> = placements
+            .iter()
+            .map(|p| {
+                (
+                    p.snake_name.as_str(),
+                    p.placement,
+                    p.leaderboard_name.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            seen,
+            vec![("alpha", Some(1), Some("Duels")), ("beta", Some(2), None)]
+        );
+        assert_eq!(placements[0].snake_count, 2);
+
+        // Beta fails one move in two and is failing its health probes
+        let since = chrono::Utc::now() - chrono::Duration::hours(1);
+        assert!(get_health_warnings(&pool, user_id, since).await?.is_empty());
+        for (turn_number, timed_out) in [(0, true), (1, false)] {
+            let turn_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO turns (game_id, turn_number) VALUES ($1, $2) RETURNING turn_id",
+            )
+            .bind(game_id)
+            .bind(turn_number)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO snake_turns (turn_id, game_battlesnake_id, direction, timed_out)
+                 VALUES ($1, $2, 'up', $3)",
+            )
+            .bind(turn_id)
+            .bind(game_battlesnake_ids[1])
+            .bind(timed_out)
+            .execute(&pool)
+            .await?;
+        }
+        sqlx::query(
+            "INSERT INTO snake_health_status (battlesnake_id, consecutive_failures, last_failure)
+             VALUES ($1, 2, 'connection refused')",
+        )
+        .bind(snakes[1])
+        .execute(&pool)
+        .await?;
+
+        let warnings = get_health_warnings(&pool, user_id, since).await?;
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].snake_name, "beta");
+        assert_eq!(warnings[0].consecutive_failures, 2);
+        assert_eq!(warnings[0].error_rate(), 0.5);
+
+        Ok(())
+    }
+}
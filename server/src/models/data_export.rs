@@ -0,0 +1,247 @@
+//! Everything the arena holds about one user, as a single JSON document they
+//! can download from their profile.
+
+use color_eyre::eyre::Context as _;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{
+    battlesnake::{self, Battlesnake},
+    leaderboard::{self, UserLeaderboardEntry},
+    user::User,
+};
+
+/// Bumped whenever a field is renamed or removed, so scripts reading old
+/// exports can tell
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// One of the user's snakes in one game
+#[derive(Debug, Serialize)]
+pub struct ExportedGameResult {
+    pub game_id: Uuid,
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    /// Set for leaderboard games
+    pub leaderboard_id: Option<Uuid>,
+    pub status: String,
+    pub board_size: String,
+    pub game_type: String,
+    /// `None` until the game finishes
+    pub placement: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One rating update from a leaderboard game
+#[derive(Debug, Serialize)]
+pub struct ExportedRatingChange {
+    pub leaderboard_entry_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub battlesnake_id: Uuid,
+    pub game_id: Uuid,
+    pub placement: i32,
+    pub mu_before: f64,
+    pub mu_after: f64,
+    pub sigma_before: f64,
+    pub sigma_after: f64,
+    pub display_score_change: f64,
+    pub game_created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserDataExport {
+    pub format_version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub profile: User,
+    pub snakes: Vec<Battlesnake>,
+    pub leaderboard_entries: Vec<UserLeaderboardEntry>,
+    /// Oldest first
+    pub game_results: Vec<ExportedGameResult>,
+    /// Oldest first
+    pub rating_history: Vec<ExportedRatingChange>,
+}
+
+/// Gather the export for `user`
+pub async fn export_user_data(pool: &PgPool, user: User) -> cja::Result<UserDataExport> {
+    let snakes = battlesnake::get_battlesnakes_by_user_id(pool, user.user_id).await?;
+    let leaderboard_entries = leaderboard::get_entries_for_user(pool, user.user_id).await?;
+    let game_results = get_game_results(pool, user.user_id).await?;
+    let rating_history = get_rating_history(pool, user.user_id).await?;
+
+    Ok(UserDataExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        exported_at: chrono::Utc::now(),
+        profile: user,
+        snakes,
+        leaderboard_entries,
+        game_results,
+        rating_history,
+    })
+}
+
+/// Every game any of the user's snakes played in. Leaderboard games may
+/// only link the snake through its entry, so those are matched on that too.
+async fn get_game_results(pool: &PgPool, user_id: Uuid) -> cja::Result<Vec<ExportedGameResult>> {
+    let results = sqlx::query_as!(
+        ExportedGameResult,
+        r#"
+        SELECT
+            g.game_id,
+            b.battlesnake_id,
+            b.name as snake_name,
+            le.leaderboard_id as "leaderboard_id?",
+            g.status,
+            g.board_size,
+            g.game_type,
+            gb.placement,
+            g.created_at
+        FROM game_battlesnakes gb
+        JOIN games g ON g.game_id = gb.game_id
+        LEFT JOIN leaderboard_entries le ON le.leaderboard_entry_id = gb.leaderboard_entry_id
+        JOIN battlesnakes b ON b.battlesnake_id = COALESCE(gb.battlesnake_id, le.battlesnake_id)
+        WHERE b.user_id = $1
+        ORDER BY g.created_at, g.game_id, b.name
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch game results for export")?;
+
+    Ok(results)
+}
+
+/// Every rating update on the user's leaderboard entries
+async fn get_rating_history(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<ExportedRatingChange>> {
+    let history = sqlx::query_as!(
+        ExportedRatingChange,
+        r#"
+        SELECT
+            r.leaderboard_entry_id,
+            le.leaderboard_id,
+            le.battlesnake_id,
+            lg.game_id,
+            r.placement,
+            r.mu_before,
+            r.mu_after,
+            r.sigma_before,
+            r.sigma_after,
+            r.display_score_change,
+            r.game_created_at
+        FROM leaderboard_game_results r
+        JOIN leaderboard_entries le ON le.leaderboard_entry_id = r.leaderboard_entry_id
+        JOIN battlesnakes b ON b.battlesnake_id = le.battlesnake_id
+        JOIN leaderboard_games lg ON lg.leaderboard_game_id = r.leaderboard_game_id
+        WHERE b.user_id = $1
+        ORDER BY r.game_created_at, r.leaderboard_game_result_id
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch rating history for export")?;
+
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_export_covers_only_the_users_data(pool: PgPool) -> cja::Result<()> {
+        let mut users = Vec::new();
+        for (github_id, login) in [(1_i64, "owner"), (2, "rival")] {
+            let user_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (external_github_id, github_login, github_access_token)
+                 VALUES ($1, $2, 'secret-token') RETURNING user_id",
+            )
+            .bind(github_id)
+            .bind(login)
+            .fetch_one(&pool)
+            .await?;
+            users.push(user_id);
+        }
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status)
+             VALUES ('11x11', 'Standard', 'finished') RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let leaderboard_game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboard_games (leaderboard_id, game_id)
+             VALUES ($1, $2) RETURNING leaderboard_game_id",
+        )
+        .bind(leaderboard_id)
+        .bind(game_id)
+        .fetch_one(&pool)
+        .await?;
+        for (i, user_id) in users.iter().enumerate() {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, 'snake', 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await?;
+            let entry_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id)
+                 VALUES ($1, $2) RETURNING leaderboard_entry_id",
+            )
+            .bind(leaderboard_id)
+            .bind(battlesnake_id)
+            .fetch_one(&pool)
+            .await?;
+            // The leaderboard game links the snake through its entry only
+            sqlx::query(
+                "INSERT INTO game_battlesnakes (game_id, leaderboard_entry_id, placement)
+                 VALUES ($1, $2, $3)",
+            )
+            .bind(game_id)
+            .bind(entry_id)
+            .bind(i as i32 + 1)
+            .execute(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO leaderboard_game_results (
+                    leaderboard_game_id, leaderboard_entry_id, placement,
+                    mu_before, mu_after, sigma_before, sigma_after, display_score_change,
+                    game_created_at
+                 )
+                 VALUES ($1, $2, $3, 25, 26, 8, 7.5, 1.5, NOW())",
+            )
+            .bind(leaderboard_game_id)
+            .bind(entry_id)
+            .bind(i as i32 + 1)
+            .execute(&pool)
+            .await?;
+        }
+
+        let user = crate::models::user::get_user_by_id(&pool, users[0])
+            .await?
+            .unwrap();
+        let export = export_user_data(&pool, user).await?;
+        assert_eq!(export.profile.github_login, "owner");
+        assert_eq!(export.snakes.len(), 1);
+        assert_eq!(export.leaderboard_entries.len(), 1);
+        assert_eq!(export.game_results.len(), 1);
+        assert_eq!(export.game_results[0].placement, Some(1));
+        assert_eq!(export.game_results[0].leaderboard_id, Some(leaderboard_id));
+        assert_eq!(export.rating_history.len(), 1);
+        assert_eq!(export.rating_history[0].game_id, game_id);
+
+        // Credentials never make it into the file
+        let json = serde_json::to_string(&export)?;
+        assert!(!json.contains("secret-token"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+//! Users' own Discord webhooks for news about their leaderboard snakes.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// The only hosts a webhook may point at, so a user can't aim arena's
+/// requests at arbitrary servers
+const WEBHOOK_PREFIXES: [&str; 2] = [
+    "https://discord.com/api/webhooks/",
+    "https://discordapp.com/api/webhooks/",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscordWebhook {
+    pub user_id: Uuid,
+    pub webhook_url: String,
+    pub notify_game_results: bool,
+    pub notify_rank_changes: bool,
+    pub notify_pauses: bool,
+}
+
+/// Check a webhook URL a user entered, returning it trimmed
+pub fn validate_webhook_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let rest = WEBHOOK_PREFIXES
+        .iter()
+        .find_map(|prefix| url.strip_prefix(prefix))
+        .ok_or_else(|| {
+            "Webhook URL must start with https://discord.com/api/webhooks/".to_string()
+        })?;
+    // `{id}/{token}`: anything else isn't a webhook Discord handed out
+    let mut parts = rest.split('/');
+    let valid = matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(id), Some(token), None)
+            if !id.is_empty()
+                && id.chars().all(|c| c.is_ascii_digit())
+                && !token.is_empty()
+                && token
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    );
+    if !valid {
+        return Err("That doesn't look like a Discord webhook URL".to_string());
+    }
+    Ok(url.to_string())
+}
+
+pub async fn get_for_user(pool: &PgPool, user_id: Uuid) -> cja::Result<Option<DiscordWebhook>> {
+    let webhook = sqlx::query_as!(
+        DiscordWebhook,
+        r#"
+        SELECT user_id, webhook_url, notify_game_results, notify_rank_changes, notify_pauses
+        FROM discord_webhooks
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch Discord webhook")?;
+
+    Ok(webhook)
+}
+
+/// The webhooks of any of `user_ids` that have one
+pub async fn get_for_users(pool: &PgPool, user_ids: &[Uuid]) -> cja::Result<Vec<DiscordWebhook>> {
+    let webhooks = sqlx::query_as!(
+        DiscordWebhook,
+        r#"
+        SELECT user_id, webhook_url, notify_game_results, notify_rank_changes, notify_pauses
+        FROM discord_webhooks
+        WHERE user_id = ANY($1)
+        "#,
+        user_ids
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch Discord webhooks")?;
+
+    Ok(webhooks)
+}
+
+/// Save the user's webhook, replacing any they had
+pub async fn upsert(pool: &PgPool, webhook: &DiscordWebhook) -> cja::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO discord_webhooks (
+            user_id, webhook_url, notify_game_results, notify_rank_changes, notify_pauses
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id) DO UPDATE SET
+            webhook_url = EXCLUDED.webhook_url,
+            notify_game_results = EXCLUDED.notify_game_results,
+            notify_rank_changes = EXCLUDED.notify_rank_changes,
+            notify_pauses = EXCLUDED.notify_pauses
+        "#,
+        webhook.user_id,
+        webhook.webhook_url,
+        webhook.notify_game_results,
+        webhook.notify_rank_changes,
+        webhook.notify_pauses
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to save Discord webhook")?;
+
+    Ok(())
+}
+
+/// Remove the user's webhook. Returns whether they had one.
+pub async fn delete(pool: &PgPool, user_id: Uuid) -> cja::Result<bool> {
+    let result = sqlx::query!("DELETE FROM discord_webhooks WHERE user_id = $1", user_id)
+        .execute(pool)
+        .await
+        .wrap_err("Failed to delete Discord webhook")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert_eq!(
+            validate_webhook_url(" https://discord.com/api/webhooks/123/abc-DEF_9 ").as_deref(),
+            Ok("https://discord.com/api/webhooks/123/abc-DEF_9")
+        );
+        assert!(validate_webhook_url("https://discordapp.com/api/webhooks/123/abc").is_ok());
+
+        for bad in [
+            "",
+            "http://discord.com/api/webhooks/123/abc",
+            "https://discord.com.evil.example/api/webhooks/123/abc",
+            "https://evil.example/?https://discord.com/api/webhooks/123/abc",
+            "https://discord.com/api/webhooks/123",
+            "https://discord.com/api/webhooks/abc/token",
+            "https://discord.com/api/webhooks/123/abc/extra",
+            "https://discord.com/api/webhooks/123/abc?wait=true",
+        ] {
+            assert!(
+                validate_webhook_url(bad).is_err(),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_upsert_and_delete(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(get_for_user(&pool, user_id).await?, None);
+
+        let mut webhook = DiscordWebhook {
+            user_id,
+            webhook_url: "https://discord.com/api/webhooks/1/a".to_string(),
+            notify_game_results: true,
+            notify_rank_changes: true,
+            notify_pauses: true,
+        };
+        upsert(&pool, &webhook).await?;
+        webhook.notify_game_results = false;
+        upsert(&pool, &webhook).await?;
+        assert_eq!(get_for_user(&pool, user_id).await?, Some(webhook.clone()));
+        assert_eq!(get_for_users(&pool, &[user_id]).await?, vec![webhook]);
+
+        assert!(delete(&pool, user_id).await?);
+        assert!(!delete(&pool, user_id).await?);
+        Ok(())
+    }
+}
@@ -212,6 +212,7 @@ impl GameCreationFlow {
             board_size: self.board_size.clone(),
             game_type: self.game_type.clone(),
             battlesnake_ids: self.selected_battlesnake_ids.clone(),
+            run_tag: None,
         })
     }
 
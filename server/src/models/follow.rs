@@ -0,0 +1,344 @@
+//! Snakes and leaderboards a user follows. Following is private: it only
+//! shapes the user's own feed.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::leaderboard::MIN_GAMES_FOR_RANKING;
+
+/// Follow a snake; following twice is a no-op
+pub async fn follow_snake(pool: &PgPool, user_id: Uuid, battlesnake_id: Uuid) -> cja::Result<()> {
+    sqlx::query!(
+        "INSERT INTO snake_follows (user_id, battlesnake_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id,
+        battlesnake_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to follow snake")?;
+    Ok(())
+}
+
+pub async fn unfollow_snake(pool: &PgPool, user_id: Uuid, battlesnake_id: Uuid) -> cja::Result<()> {
+    sqlx::query!(
+        "DELETE FROM snake_follows WHERE user_id = $1 AND battlesnake_id = $2",
+        user_id,
+        battlesnake_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to unfollow snake")?;
+    Ok(())
+}
+
+pub async fn is_following_snake(
+    pool: &PgPool,
+    user_id: Uuid,
+    battlesnake_id: Uuid,
+) -> cja::Result<bool> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS(
+            SELECT 1 FROM snake_follows WHERE user_id = $1 AND battlesnake_id = $2
+        ) as "exists!""#,
+        user_id,
+        battlesnake_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to check snake follow")
+}
+
+/// Follow a leaderboard; following twice is a no-op
+pub async fn follow_leaderboard(
+    pool: &PgPool,
+    user_id: Uuid,
+    leaderboard_id: Uuid,
+) -> cja::Result<()> {
+    sqlx::query!(
+        "INSERT INTO leaderboard_follows (user_id, leaderboard_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id,
+        leaderboard_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to follow leaderboard")?;
+    Ok(())
+}
+
+pub async fn unfollow_leaderboard(
+    pool: &PgPool,
+    user_id: Uuid,
+    leaderboard_id: Uuid,
+) -> cja::Result<()> {
+    sqlx::query!(
+        "DELETE FROM leaderboard_follows WHERE user_id = $1 AND leaderboard_id = $2",
+        user_id,
+        leaderboard_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to unfollow leaderboard")?;
+    Ok(())
+}
+
+pub async fn is_following_leaderboard(
+    pool: &PgPool,
+    user_id: Uuid,
+    leaderboard_id: Uuid,
+) -> cja::Result<bool> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS(
+            SELECT 1 FROM leaderboard_follows WHERE user_id = $1 AND leaderboard_id = $2
+        ) as "exists!""#,
+        user_id,
+        leaderboard_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to check leaderboard follow")
+}
+
+/// A followed snake on one active leaderboard. Snakes that aren't entered
+/// anywhere get a single row with the leaderboard fields empty.
+#[derive(Debug, Clone)]
+pub struct FollowedStanding {
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    pub snake_color: String,
+    pub owner_login: String,
+    pub leaderboard_entry_id: Option<Uuid>,
+    pub leaderboard_id: Option<Uuid>,
+    pub leaderboard_name: Option<String>,
+    pub display_score: Option<f64>,
+    /// Position on the rating-sorted leaderboard; `None` while paused or
+    /// short of `MIN_GAMES_FOR_RANKING`
+    pub rank: Option<i64>,
+    /// Places gained over the last day, positive for climbing. Ratings a day
+    /// ago are rebuilt from the day's results, so entries that have joined
+    /// since count as if they'd been there.
+    pub day_rank_change: Option<i64>,
+}
+
+/// The user's followed snakes with their standings, ordered by name
+pub async fn get_followed_standings(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<FollowedStanding>> {
+    let standings = sqlx::query_as!(
+        FollowedStanding,
+        r#"
+        WITH day_ago AS (
+            SELECT
+                le.leaderboard_entry_id,
+                le.leaderboard_id,
+                le.display_score,
+                le.display_score - COALESCE((
+                    SELECT SUM(r.display_score_change)
+                    FROM leaderboard_game_results r
+                    WHERE r.leaderboard_entry_id = le.leaderboard_entry_id
+                      AND r.game_created_at >= NOW() - INTERVAL '1 day'
+                ), 0) as score_day_ago
+            FROM leaderboard_entries le
+            WHERE le.disabled_at IS NULL
+              AND le.games_played >= $2
+              AND le.leaderboard_id IN (
+                  SELECT le2.leaderboard_id
+                  FROM snake_follows f
+                  JOIN leaderboard_entries le2 ON le2.battlesnake_id = f.battlesnake_id
+                  WHERE f.user_id = $1
+              )
+        ),
+        ranked AS (
+            SELECT
+                leaderboard_entry_id,
+                RANK() OVER (PARTITION BY leaderboard_id ORDER BY display_score DESC) as rank_now,
+                RANK() OVER (PARTITION BY leaderboard_id ORDER BY score_day_ago DESC) as rank_day_ago
+            FROM day_ago
+        )
+        SELECT
+            b.battlesnake_id,
+            b.name as snake_name,
+            b.color as snake_color,
+            u.github_login as owner_login,
+            le.leaderboard_entry_id as "leaderboard_entry_id?",
+            l.leaderboard_id as "leaderboard_id?",
+            l.name as "leaderboard_name?",
+            le.display_score as "display_score?",
+            ranked.rank_now as rank,
+            ranked.rank_day_ago - ranked.rank_now as day_rank_change
+        FROM snake_follows f
+        JOIN battlesnakes b ON b.battlesnake_id = f.battlesnake_id
+        JOIN users u ON u.user_id = b.user_id
+        LEFT JOIN (
+            leaderboard_entries le
+            JOIN leaderboards l ON l.leaderboard_id = le.leaderboard_id AND l.disabled_at IS NULL
+        ) ON le.battlesnake_id = b.battlesnake_id
+        LEFT JOIN ranked ON ranked.leaderboard_entry_id = le.leaderboard_entry_id
+        WHERE f.user_id = $1
+        ORDER BY b.name, b.battlesnake_id, l.name
+        "#,
+        user_id,
+        MIN_GAMES_FOR_RANKING
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch followed snake standings")?;
+
+    Ok(standings)
+}
+
+/// A followed leaderboard
+#[derive(Debug, Clone)]
+pub struct FollowedLeaderboard {
+    pub leaderboard_id: Uuid,
+    pub name: String,
+}
+
+pub async fn get_followed_leaderboards(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<FollowedLeaderboard>> {
+    sqlx::query_as!(
+        FollowedLeaderboard,
+        r#"
+        SELECT l.leaderboard_id, l.name
+        FROM leaderboard_follows f
+        JOIN leaderboards l ON l.leaderboard_id = f.leaderboard_id
+        WHERE f.user_id = $1
+        ORDER BY l.name
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch followed leaderboards")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::leaderboard::get_followed_activity_feed;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_follows_shape_the_feed(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'fan', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut leaderboards = Vec::new();
+        for name in ["Duels", "Royale"] {
+            let leaderboard_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO leaderboards (name) VALUES ($1) RETURNING leaderboard_id",
+            )
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            leaderboards.push(leaderboard_id);
+        }
+
+        // Three snakes on each leaderboard; each game has them finish in
+        // order, and the day's results moved the last one from first to third
+        let mut snakes = Vec::new();
+        for (i, name) in ["alpha", "beta", "gamma"].iter().enumerate() {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+            for leaderboard_id in &leaderboards {
+                let score = 30.0 - i as f64;
+                let day_change = if i == 2 { -5.0 } else { 0.0 };
+                let entry_id: Uuid = sqlx::query_scalar(
+                    "INSERT INTO leaderboard_entries
+                        (leaderboard_id, battlesnake_id, display_score, games_played)
+                     VALUES ($1, $2, $3, $4) RETURNING leaderboard_entry_id",
+                )
+                .bind(leaderboard_id)
+                .bind(battlesnake_id)
+                .bind(score)
+                .bind(MIN_GAMES_FOR_RANKING)
+                .fetch_one(&pool)
+                .await?;
+                let game_id: Uuid = sqlx::query_scalar(
+                    "INSERT INTO games (board_size, game_type, status)
+                     VALUES ('11x11', 'Standard', 'finished') RETURNING game_id",
+                )
+                .fetch_one(&pool)
+                .await?;
+                let leaderboard_game_id: Uuid = sqlx::query_scalar(
+                    "INSERT INTO leaderboard_games (leaderboard_id, game_id)
+                     VALUES ($1, $2) RETURNING leaderboard_game_id",
+                )
+                .bind(leaderboard_id)
+                .bind(game_id)
+                .fetch_one(&pool)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO leaderboard_game_results (
+                        leaderboard_game_id, leaderboard_entry_id, placement,
+                        mu_before, mu_after, sigma_before, sigma_after, display_score_change,
+                        game_created_at
+                     )
+                     VALUES ($1, $2, $3, 25, 25, 8, 8, $4, NOW())",
+                )
+                .bind(leaderboard_game_id)
+                .bind(entry_id)
+                .bind(i as i32 + 1)
+                .bind(day_change)
+                .execute(&pool)
+                .await?;
+            }
+        }
+
+        assert!(
+            get_followed_activity_feed(&pool, user_id, 10)
+                .await?
+                .is_empty()
+        );
+        assert!(get_followed_standings(&pool, user_id).await?.is_empty());
+
+        follow_snake(&pool, user_id, snakes[2]).await?;
+        follow_snake(&pool, user_id, snakes[2]).await?;
+        assert!(is_following_snake(&pool, user_id, snakes[2]).await?);
+        let standings = get_followed_standings(&pool, user_id).await?;
+        assert_eq!(standings.len(), 2);
+        for standing in &standings {
+            assert_eq!(standing.snake_name, "gamma");
+            assert_eq!(standing.rank, Some(3));
+            assert_eq!(standing.day_rank_change, Some(-2));
+        }
+        // Each of the snake's results, on both leaderboards
+        let feed = get_followed_activity_feed(&pool, user_id, 10).await?;
+        assert_eq!(feed.len(), 2);
+        assert!(feed.iter().all(|e| e.snake_name == "gamma"));
+
+        // A followed leaderboard adds its winners
+        follow_leaderboard(&pool, user_id, leaderboards[0]).await?;
+        assert!(is_following_leaderboard(&pool, user_id, leaderboards[0]).await?);
+        assert_eq!(get_followed_leaderboards(&pool, user_id).await?.len(), 1);
+        let feed = get_followed_activity_feed(&pool, user_id, 10).await?;
+        assert_eq!(feed.len(), 3);
+        assert!(
+            feed.iter()
+                .any(|e| e.snake_name == "alpha" && e.leaderboard_id == leaderboards[0])
+        );
+
+        unfollow_snake(&pool, user_id, snakes[2]).await?;
+        unfollow_leaderboard(&pool, user_id, leaderboards[0]).await?;
+        assert!(!is_following_snake(&pool, user_id, snakes[2]).await?);
+        assert!(
+            get_followed_activity_feed(&pool, user_id, 10)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}
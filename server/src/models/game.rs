@@ -5,6 +5,7 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 use super::game_battlesnake::AddBattlesnakeToGame;
+use super::game_frame_archive::ArchivedGame;
 
 // Game board size enum
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -141,6 +142,8 @@ pub struct Game {
 pub struct CreateGame {
     pub board_size: GameBoardSize,
     pub game_type: GameType,
+    /// Client-supplied label (see GET /api/v1/games/status)
+    pub run_tag: Option<String>,
 }
 
 // Create a game with battlesnakes in a single transaction
@@ -149,6 +152,7 @@ pub struct CreateGameWithSnakes {
     pub board_size: GameBoardSize,
     pub game_type: GameType,
     pub battlesnake_ids: Vec<Uuid>,
+    pub run_tag: Option<String>,
 }
 
 // Database functions for game management
@@ -259,6 +263,7 @@ pub async fn create_game_with_snakes_tx(
         CreateGame {
             board_size: data.board_size,
             game_type: data.game_type,
+            run_tag: data.run_tag,
         },
     )
     .await
@@ -292,9 +297,10 @@ where
         INSERT INTO games (
             board_size,
             game_type,
-            status
+            status,
+            run_tag
         )
-        VALUES ($1, $2, $3)
+        VALUES ($1, $2, $3, $4)
         RETURNING
             game_id,
             board_size,
@@ -306,11 +312,15 @@ where
         "#,
         board_size_str,
         game_type_str,
-        status_str
+        status_str,
+        data.run_tag
     )
     .fetch_one(executor)
     .await
     .wrap_err("Failed to create game in database")?;
+    // Counted at insert; the rare caller transaction that rolls back after
+    // this still counts, which is fine for a rate
+    crate::metrics::METRICS.games_created.inc();
 
     Ok(Game {
         game_id: row.game_id,
@@ -488,15 +498,393 @@ pub async fn get_game_last_activity(
     Ok(last_activity)
 }
 
+/// A `running` game with no sign of life since the watchdog's cutoff
+#[derive(Debug, Clone)]
+pub struct StuckGame {
+    pub game_id: Uuid,
+    pub last_activity: chrono::DateTime<chrono::Utc>,
+    pub watchdog_requeues: i32,
+}
+
+/// Games in `running` whose last sign of life (see [`get_game_last_activity`])
+/// is older than `cutoff`, oldest first. Tournament games are left out: the
+/// stuck-match sweeper already re-drives those through `run_match`, which
+/// also keeps their match results consistent.
+pub async fn find_stuck_running_games(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<Vec<StuckGame>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            g.game_id,
+            g.watchdog_requeues,
+            GREATEST(
+                g.updated_at,
+                (SELECT MAX(t.created_at) FROM turns t WHERE t.game_id = g.game_id)
+            ) as "last_activity!"
+        FROM games g
+        WHERE g.status = 'running'
+          AND g.updated_at < $1
+          AND NOT EXISTS (
+              SELECT 1 FROM turns t WHERE t.game_id = g.game_id AND t.created_at >= $1
+          )
+          AND NOT EXISTS (SELECT 1 FROM match_games mg WHERE mg.game_id = g.game_id)
+        ORDER BY g.updated_at ASC
+        "#,
+        cutoff,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to find stuck running games")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| StuckGame {
+            game_id: row.game_id,
+            last_activity: row.last_activity,
+            watchdog_requeues: row.watchdog_requeues,
+        })
+        .collect())
+}
+
+/// Count a watchdog re-enqueue and restart the game's queue clock. The
+/// update also bumps `updated_at`, so the game isn't stuck again until it
+/// has been quiet for another full timeout.
+pub async fn record_watchdog_requeue(pool: &PgPool, game_id: Uuid) -> cja::Result<()> {
+    sqlx::query!(
+        "UPDATE games
+         SET watchdog_requeues = watchdog_requeues + 1, enqueued_at = NOW()
+         WHERE game_id = $1",
+        game_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err_with(|| format!("Failed to record watchdog requeue for game {game_id}"))?;
+
+    Ok(())
+}
+
+/// Games deleted per statement by [`purge_expired_games`], so one purge
+/// never holds locks on a huge batch of rows
+const PURGE_BATCH_SIZE: i64 = 1000;
+
+/// What [`purge_expired_games`] removed
+#[derive(Debug, Default)]
+pub struct PurgedGames {
+    pub count: u64,
+    /// Purged games whose frames were archived. Their archive pointers
+    /// cascaded away, so the caller deletes the GCS objects.
+    pub archived: Vec<ArchivedGame>,
+}
+
+/// Delete finished games created before `cutoff` that nothing else needs:
+/// not leaderboard or tournament games, not saved to anyone's profile, and
+/// not imported from the legacy engine (those rows index the GCS archive).
+/// Turns, snake turns, battlesnake links, and frame archive pointers go with
+/// them via `ON DELETE CASCADE`. Deletes in batches.
+pub async fn purge_expired_games(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<PurgedGames> {
+    let mut purged = PurgedGames::default();
+    loop {
+        // Every part of the statement sees the same snapshot, so the archive
+        // pointers are still there to read while their games are deleted
+        let deleted = sqlx::query!(
+            r#"
+            WITH gone AS (
+                DELETE FROM games
+                WHERE game_id IN (
+                    SELECT g.game_id
+                    FROM games g
+                    WHERE g.status = 'finished'
+                      AND g.created_at < $1
+                      AND g.engine_game_id IS NULL
+                      AND NOT EXISTS (SELECT 1 FROM leaderboard_games lg WHERE lg.game_id = g.game_id)
+                      AND NOT EXISTS (SELECT 1 FROM match_games mg WHERE mg.game_id = g.game_id)
+                      AND NOT EXISTS (SELECT 1 FROM saved_games sg WHERE sg.game_id = g.game_id)
+                    ORDER BY g.created_at
+                    LIMIT $2
+                )
+                RETURNING game_id
+            )
+            SELECT gone.game_id AS "game_id!", a.gcs_path AS "gcs_path?"
+            FROM gone
+            LEFT JOIN game_frame_archives a ON a.game_id = gone.game_id
+            "#,
+            cutoff,
+            PURGE_BATCH_SIZE,
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("Failed to purge expired games")?;
+
+        purged.count += deleted.len() as u64;
+        let batch_full = deleted.len() as i64 >= PURGE_BATCH_SIZE;
+        purged
+            .archived
+            .extend(deleted.into_iter().filter_map(|row| {
+                row.gcs_path.map(|gcs_path| ArchivedGame {
+                    game_id: row.game_id,
+                    gcs_path,
+                })
+            }));
+        if !batch_full {
+            return Ok(purged);
+        }
+    }
+}
+
+/// Games waiting for or in the middle of a run
+pub async fn count_active_games(pool: &PgPool) -> cja::Result<i64> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM games WHERE status IN ('waiting', 'running')"#
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to count active games")
+}
+
+/// Which games the `/games` list shows. `since` always bounds the window:
+/// the list is for browsing recent games, and an unbounded scan is what
+/// made the old page time out.
+#[derive(Debug, Clone)]
+pub struct GameListFilter {
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub status: Option<GameStatus>,
+    /// Games the snake played, directly or through a leaderboard entry
+    pub battlesnake_id: Option<Uuid>,
+    /// Games played for this leaderboard
+    pub leaderboard_id: Option<Uuid>,
+}
+
+struct GameListRow {
+    game_id: Uuid,
+    board_size: String,
+    game_type: String,
+    status: String,
+    enqueued_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<GameListRow> for Game {
+    type Error = color_eyre::Report;
+
+    fn try_from(row: GameListRow) -> cja::Result<Self> {
+        Ok(Game {
+            game_id: row.game_id,
+            board_size: GameBoardSize::from_str(&row.board_size)
+                .wrap_err_with(|| format!("Invalid board size: {}", row.board_size))?,
+            game_type: GameType::from_str(&row.game_type)
+                .wrap_err_with(|| format!("Invalid game type: {}", row.game_type))?,
+            status: GameStatus::from_str(&row.status)
+                .wrap_err_with(|| format!("Invalid game status: {}", row.status))?,
+            enqueued_at: row.enqueued_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+/// A page of games matching `filter`, newest first, starting after the
+/// `(created_at, game_id)` position of the last game a client has seen.
+///
+/// Each filter shape gets its own statement so every one can walk an index
+/// in order and stop after `limit` rows: `idx_games_created_at_game_id`,
+/// `idx_games_status_created_at_game_id`, for a snake its
+/// `game_battlesnakes` rows from `since` on, or for a leaderboard its
+/// `leaderboard_games` rows from `since` on. Snakes and leaderboards are
+/// linked to a game in the transaction that creates it (or later, for
+/// ratings backfills), so a link is never older than its game and
+/// `created_at >= since` on the link loses nothing.
+pub async fn list_games_page(
+    pool: &PgPool,
+    filter: &GameListFilter,
+    before: Option<(chrono::DateTime<chrono::Utc>, Uuid)>,
+    limit: i64,
+) -> cja::Result<Vec<Game>> {
+    let (before_created_at, before_game_id) = before.unzip();
+    let status = filter.status.as_ref().map(GameStatus::as_str);
+    // A missing cursor compares as past the newest game rather than with
+    // `$n IS NULL OR`, so a generic plan still seeks the index
+    let rows = match (filter.battlesnake_id, filter.leaderboard_id, status) {
+        (Some(battlesnake_id), leaderboard_id, status) => {
+            sqlx::query_as!(
+                GameListRow,
+                r#"
+                SELECT g.game_id, g.board_size, g.game_type, g.status, g.enqueued_at, g.created_at, g.updated_at
+                FROM games g
+                WHERE g.game_id IN (
+                    SELECT gb.game_id FROM game_battlesnakes gb
+                    WHERE gb.battlesnake_id = $5 AND gb.created_at >= $1
+                    UNION ALL
+                    SELECT gb.game_id FROM game_battlesnakes gb
+                    JOIN leaderboard_entries le ON gb.leaderboard_entry_id = le.leaderboard_entry_id
+                    WHERE le.battlesnake_id = $5 AND gb.created_at >= $1
+                )
+                  AND g.created_at >= $1
+                  AND ($6::text IS NULL OR g.status = $6)
+                  AND ($7::uuid IS NULL OR EXISTS (
+                        SELECT 1 FROM leaderboard_games lg
+                        WHERE lg.game_id = g.game_id AND lg.leaderboard_id = $7
+                      ))
+                  AND (g.created_at, g.game_id)
+                      < (COALESCE($2::timestamptz, 'infinity'),
+                         COALESCE($3::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+                ORDER BY g.created_at DESC, g.game_id DESC
+                LIMIT $4
+                "#,
+                filter.since,
+                before_created_at,
+                before_game_id,
+                limit,
+                battlesnake_id,
+                status,
+                leaderboard_id,
+            )
+            .fetch_all(pool)
+            .await
+        }
+        (None, Some(leaderboard_id), status) => {
+            sqlx::query_as!(
+                GameListRow,
+                r#"
+                SELECT g.game_id, g.board_size, g.game_type, g.status, g.enqueued_at, g.created_at, g.updated_at
+                FROM leaderboard_games lg
+                JOIN games g ON g.game_id = lg.game_id
+                WHERE lg.leaderboard_id = $5
+                  AND lg.created_at >= $1
+                  AND g.created_at >= $1
+                  AND ($6::text IS NULL OR g.status = $6)
+                  AND (g.created_at, g.game_id)
+                      < (COALESCE($2::timestamptz, 'infinity'),
+                         COALESCE($3::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+                ORDER BY g.created_at DESC, g.game_id DESC
+                LIMIT $4
+                "#,
+                filter.since,
+                before_created_at,
+                before_game_id,
+                limit,
+                leaderboard_id,
+                status,
+            )
+            .fetch_all(pool)
+            .await
+        }
+        (None, None, Some(status)) => {
+            sqlx::query_as!(
+                GameListRow,
+                r#"
+                SELECT game_id, board_size, game_type, status, enqueued_at, created_at, updated_at
+                FROM games
+                WHERE status = $5
+                  AND created_at >= $1
+                  AND (created_at, game_id)
+                      < (COALESCE($2::timestamptz, 'infinity'),
+                         COALESCE($3::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+                ORDER BY created_at DESC, game_id DESC
+                LIMIT $4
+                "#,
+                filter.since,
+                before_created_at,
+                before_game_id,
+                limit,
+                status,
+            )
+            .fetch_all(pool)
+            .await
+        }
+        (None, None, None) => {
+            sqlx::query_as!(
+                GameListRow,
+                r#"
+                SELECT game_id, board_size, game_type, status, enqueued_at, created_at, updated_at
+                FROM games
+                WHERE created_at >= $1
+                  AND (created_at, game_id)
+                      < (COALESCE($2::timestamptz, 'infinity'),
+                         COALESCE($3::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+                ORDER BY created_at DESC, game_id DESC
+                LIMIT $4
+                "#,
+                filter.since,
+                before_created_at,
+                before_game_id,
+                limit,
+            )
+            .fetch_all(pool)
+            .await
+        }
+    }
+    .wrap_err("Failed to list games")?;
+
+    rows.into_iter().map(Game::try_from).collect()
+}
+
+/// What [`delete_game_tx`] removed
+#[derive(Debug)]
+pub struct DeletedGame {
+    /// Set when the game's frames were archived. The pointer cascaded away,
+    /// so the caller deletes the GCS object once the transaction commits.
+    pub archived: Option<ArchivedGame>,
+}
+
+/// Delete one game. Turns, placements, leaderboard links, tournament links,
+/// and archive pointers cascade with it; saved-game bookmarks don't, so
+/// they go first. Returns `None` if the game didn't exist.
+pub async fn delete_game_tx(
+    conn: &mut sqlx::PgConnection,
+    game_id: Uuid,
+) -> cja::Result<Option<DeletedGame>> {
+    sqlx::query!("DELETE FROM saved_games WHERE game_id = $1", game_id)
+        .execute(&mut *conn)
+        .await
+        .wrap_err("Failed to delete saved games for game")?;
+
+    let deleted = sqlx::query!(
+        r#"
+        WITH gone AS (DELETE FROM games WHERE game_id = $1 RETURNING game_id)
+        SELECT a.gcs_path AS "gcs_path?"
+        FROM gone
+        LEFT JOIN game_frame_archives a ON a.game_id = gone.game_id
+        "#,
+        game_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .wrap_err("Failed to delete game")?;
+
+    Ok(deleted.map(|row| DeletedGame {
+        archived: row
+            .gcs_path
+            .map(|gcs_path| ArchivedGame { game_id, gcs_path }),
+    }))
+}
+
 /// Wipe the per-game state a previous (crashed) run left behind so `run_game`
-/// can restart cleanly from turn 0: turns (snake_turns cascade with them) and
-/// any partially written placements. Runs in a single transaction.
+/// can restart cleanly from turn 0: turns, their snake_turns, and any
+/// partially written placements. Runs in a single transaction.
 pub async fn reset_game_state_for_retry(pool: &PgPool, game_id: Uuid) -> cja::Result<()> {
     let mut tx = pool
         .begin()
         .await
         .wrap_err("Failed to start game reset transaction")?;
 
+    // snake_turns no longer reference turns (see the partitioning
+    // migration), so they don't go with them
+    sqlx::query!(
+        "DELETE FROM snake_turns st
+         USING game_battlesnakes gb
+         WHERE st.game_battlesnake_id = gb.game_battlesnake_id AND gb.game_id = $1",
+        game_id
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err_with(|| format!("Failed to delete snake turns for game {game_id} reset"))?;
+
     sqlx::query!("DELETE FROM turns WHERE game_id = $1", game_id)
         .execute(&mut *tx)
         .await
@@ -565,6 +953,220 @@ pub async fn set_game_enqueued_at_tx(
 mod tests {
     use super::*;
 
+    #[sqlx::test(migrations = "../migrations")]
+    async fn purge_expired_games_keeps_linked_and_recent_games(pool: PgPool) -> cja::Result<()> {
+        async fn insert_game(pool: &PgPool, status: &str, age_days: i32) -> cja::Result<Uuid> {
+            let game_id = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type, status, created_at)
+                 VALUES ('11x11', 'Standard', $1, NOW() - make_interval(days => $2))
+                 RETURNING game_id",
+            )
+            .bind(status)
+            .bind(age_days)
+            .fetch_one(pool)
+            .await?;
+            Ok(game_id)
+        }
+
+        let expired = insert_game(&pool, "finished", 40).await?;
+        let recent = insert_game(&pool, "finished", 1).await?;
+        let unfinished = insert_game(&pool, "running", 40).await?;
+        let on_leaderboard = insert_game(&pool, "finished", 40).await?;
+        let saved = insert_game(&pool, "finished", 40).await?;
+
+        sqlx::query(
+            "INSERT INTO leaderboard_games (leaderboard_id, game_id)
+             SELECT leaderboard_id, $1 FROM leaderboards LIMIT 1",
+        )
+        .bind(on_leaderboard)
+        .execute(&pool)
+        .await?;
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'saver', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query("INSERT INTO saved_games (user_id, game_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(saved)
+            .execute(&pool)
+            .await?;
+        sqlx::query("INSERT INTO turns (game_id, turn_number, frame_data) VALUES ($1, 0, '{}')")
+            .bind(expired)
+            .execute(&pool)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO game_frame_archives (game_id, gcs_path, frame_count)
+             VALUES ($1, 'frames/expired.json.zst', 1)",
+        )
+        .bind(expired)
+        .execute(&pool)
+        .await?;
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+        let purged = purge_expired_games(&pool, cutoff).await?;
+        assert_eq!(purged.count, 1);
+        assert_eq!(
+            purged.archived,
+            vec![ArchivedGame {
+                game_id: expired,
+                gcs_path: "frames/expired.json.zst".to_string(),
+            }]
+        );
+
+        assert!(get_game_by_id(&pool, expired).await?.is_none());
+        for kept in [recent, unfinished, on_leaderboard, saved] {
+            assert!(get_game_by_id(&pool, kept).await?.is_some());
+        }
+        let turns: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM turns WHERE game_id = $1")
+            .bind(expired)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(turns, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn list_games_page_filters_and_pages(pool: PgPool) -> cja::Result<()> {
+        async fn insert_game(pool: &PgPool, status: &str, age_hours: i32) -> cja::Result<Uuid> {
+            let game_id = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type, status, created_at)
+                 VALUES ('11x11', 'Standard', $1, NOW() - make_interval(hours => $2))
+                 RETURNING game_id",
+            )
+            .bind(status)
+            .bind(age_hours)
+            .fetch_one(pool)
+            .await?;
+            Ok(game_id)
+        }
+
+        let newest = insert_game(&pool, "running", 1).await?;
+        let middle = insert_game(&pool, "finished", 2).await?;
+        let oldest = insert_game(&pool, "finished", 3).await?;
+        let outside_window = insert_game(&pool, "finished", 48).await?;
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'lister', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let snake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url)
+             VALUES ($1, 'snake', 'http://example.com') RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        // One game played directly, one through a leaderboard entry, and one
+        // outside the window; links are as old as their games
+        for game_id in [newest, outside_window] {
+            sqlx::query(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id, created_at)
+                 SELECT $1, $2, created_at FROM games WHERE game_id = $1",
+            )
+            .bind(game_id)
+            .bind(snake_id)
+            .execute(&pool)
+            .await?;
+        }
+        sqlx::query(
+            "WITH entry AS (
+                 INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id)
+                 SELECT leaderboard_id, $2 FROM leaderboards LIMIT 1
+                 RETURNING leaderboard_entry_id
+             )
+             INSERT INTO game_battlesnakes (game_id, leaderboard_entry_id, created_at)
+             SELECT $1, leaderboard_entry_id, (SELECT created_at FROM games WHERE game_id = $1)
+             FROM entry",
+        )
+        .bind(oldest)
+        .bind(snake_id)
+        .execute(&pool)
+        .await?;
+
+        let day = GameListFilter {
+            since: chrono::Utc::now() - chrono::Duration::hours(24),
+            status: None,
+            battlesnake_id: None,
+            leaderboard_id: None,
+        };
+        let ids = |games: Vec<Game>| games.into_iter().map(|g| g.game_id).collect::<Vec<_>>();
+
+        let first = list_games_page(&pool, &day, None, 2).await?;
+        let last = first.last().map(|g| (g.created_at, g.game_id));
+        assert_eq!(ids(first), vec![newest, middle]);
+        assert_eq!(
+            ids(list_games_page(&pool, &day, last, 2).await?),
+            vec![oldest]
+        );
+
+        let finished = GameListFilter {
+            status: Some(GameStatus::Finished),
+            ..day.clone()
+        };
+        assert_eq!(
+            ids(list_games_page(&pool, &finished, None, 10).await?),
+            vec![middle, oldest]
+        );
+
+        let snake = GameListFilter {
+            battlesnake_id: Some(snake_id),
+            ..day.clone()
+        };
+        assert_eq!(
+            ids(list_games_page(&pool, &snake, None, 10).await?),
+            vec![newest, oldest]
+        );
+        let snake_finished = GameListFilter {
+            status: Some(GameStatus::Finished),
+            ..snake
+        };
+        assert_eq!(
+            ids(list_games_page(&pool, &snake_finished, None, 10).await?),
+            vec![oldest]
+        );
+
+        // `middle` and `oldest` were played for the leaderboard, and the
+        // snake only played `oldest` there
+        let leaderboard_id: Uuid =
+            sqlx::query_scalar("SELECT leaderboard_id FROM leaderboards LIMIT 1")
+                .fetch_one(&pool)
+                .await?;
+        for game_id in [middle, oldest, outside_window] {
+            sqlx::query("INSERT INTO leaderboard_games (leaderboard_id, game_id) VALUES ($1, $2)")
+                .bind(leaderboard_id)
+                .bind(game_id)
+                .execute(&pool)
+                .await?;
+        }
+        let leaderboard = GameListFilter {
+            leaderboard_id: Some(leaderboard_id),
+            ..day.clone()
+        };
+        let first = list_games_page(&pool, &leaderboard, None, 1).await?;
+        let last = first.last().map(|g| (g.created_at, g.game_id));
+        assert_eq!(ids(first), vec![middle]);
+        assert_eq!(
+            ids(list_games_page(&pool, &leaderboard, last, 10).await?),
+            vec![oldest]
+        );
+        let leaderboard_snake = GameListFilter {
+            battlesnake_id: Some(snake_id),
+            ..leaderboard
+        };
+        assert_eq!(
+            ids(list_games_page(&pool, &leaderboard_snake, None, 10).await?),
+            vec![oldest]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn game_type_from_str_case_insensitive() {
         assert_eq!(GameType::from_str("Standard").unwrap(), GameType::Standard);
@@ -93,6 +93,47 @@ pub async fn get_battlesnakes_by_game_id(
     Ok(game_battlesnakes)
 }
 
+/// A snake's line in a games list
+#[derive(Debug)]
+pub struct GameSnakeSummary {
+    pub game_id: Uuid,
+    pub battlesnake_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub placement: Option<i32>,
+    pub is_public: bool,
+}
+
+/// The snakes in each of `game_ids`, in one query, ordered by placement
+/// within each game
+pub async fn get_snake_summaries_for_games(
+    pool: &PgPool,
+    game_ids: &[Uuid],
+) -> cja::Result<Vec<GameSnakeSummary>> {
+    sqlx::query_as!(
+        GameSnakeSummary,
+        r#"
+        SELECT
+            gb.game_id,
+            b.battlesnake_id,
+            b.name,
+            b.color,
+            gb.placement,
+            b.visibility = 'public' AS "is_public!"
+        FROM game_battlesnakes gb
+        LEFT JOIN leaderboard_entries le ON gb.leaderboard_entry_id = le.leaderboard_entry_id
+        JOIN battlesnakes b
+            ON COALESCE(gb.battlesnake_id, le.battlesnake_id) = b.battlesnake_id
+        WHERE gb.game_id = ANY($1)
+        ORDER BY gb.game_id, gb.placement NULLS LAST, gb.created_at ASC
+        "#,
+        game_ids
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch snakes for games")
+}
+
 // Get all games for a battlesnake
 pub async fn get_games_by_battlesnake_id(
     pool: &PgPool,
@@ -144,6 +185,64 @@ pub async fn get_games_by_battlesnake_id(
     Ok(games)
 }
 
+// Get the most recent games any of a user's battlesnakes played in
+pub async fn get_recent_games_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    status: Option<GameStatus>,
+    limit: i64,
+) -> cja::Result<Vec<Game>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT
+            g.game_id,
+            g.board_size,
+            g.game_type,
+            g.status,
+            g.enqueued_at,
+            g.created_at,
+            g.updated_at
+        FROM games g
+        JOIN game_battlesnakes gb ON g.game_id = gb.game_id
+        JOIN battlesnakes b ON gb.battlesnake_id = b.battlesnake_id
+        WHERE b.user_id = $1
+          AND ($3::text IS NULL OR g.status = $3)
+        ORDER BY g.created_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit,
+        status.as_ref().map(GameStatus::as_str)
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch recent games for user from database")?;
+
+    let games = rows
+        .into_iter()
+        .map(|row| {
+            let board_size = GameBoardSize::from_str(&row.board_size)
+                .wrap_err_with(|| format!("Invalid board size: {}", row.board_size))?;
+            let game_type = GameType::from_str(&row.game_type)
+                .wrap_err_with(|| format!("Invalid game type: {}", row.game_type))?;
+            let status = GameStatus::from_str(&row.status)
+                .wrap_err_with(|| format!("Invalid game status: {}", row.status))?;
+
+            Ok(Game {
+                game_id: row.game_id,
+                board_size,
+                game_type,
+                status,
+                enqueued_at: row.enqueued_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect::<cja::Result<Vec<_>>>()?;
+
+    Ok(games)
+}
+
 // Add a battlesnake to a game
 pub async fn add_battlesnake_to_game(
     pool: &PgPool,
@@ -309,10 +408,11 @@ pub struct GameHistoryEntry {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-// Get game history for a battlesnake (for profile page)
+// Get a battlesnake's most recent games (for profile page)
 pub async fn get_game_history_for_battlesnake(
     pool: &PgPool,
     battlesnake_id: Uuid,
+    limit: i64,
 ) -> cja::Result<Vec<GameHistoryEntry>> {
     let rows = sqlx::query!(
         r#"
@@ -330,8 +430,10 @@ pub async fn get_game_history_for_battlesnake(
         LEFT JOIN game_battlesnakes gb_winner ON g.game_id = gb_winner.game_id AND gb_winner.placement = 1
         LEFT JOIN battlesnakes winner_b ON gb_winner.battlesnake_id = winner_b.battlesnake_id
         ORDER BY g.created_at DESC
+        LIMIT $2
         "#,
-        battlesnake_id
+        battlesnake_id,
+        limit
     )
     .fetch_all(pool)
     .await
@@ -363,6 +465,62 @@ pub async fn get_game_history_for_battlesnake(
     Ok(entries)
 }
 
+/// A snake's record across every game it has played, for its profile.
+/// Placements only count once a game is finished.
+#[derive(Debug, Default)]
+pub struct BattlesnakeGameStats {
+    pub total_games: i64,
+    pub finished_games: i64,
+    pub wins: i64,
+    pub second_places: i64,
+    pub third_places: i64,
+    pub fourth_places: i64,
+    pub average_placement: Option<f64>,
+}
+
+impl BattlesnakeGameStats {
+    /// Percentage of finished games won, `None` before any have finished
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.finished_games > 0).then(|| self.wins as f64 / self.finished_games as f64 * 100.0)
+    }
+}
+
+// Aggregate a battlesnake's whole game history (for profile page)
+pub async fn get_game_stats_for_battlesnake(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+) -> cja::Result<BattlesnakeGameStats> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) AS "total_games!",
+            COUNT(*) FILTER (WHERE g.status = 'finished') AS "finished_games!",
+            COUNT(*) FILTER (WHERE g.status = 'finished' AND gb.placement = 1) AS "wins!",
+            COUNT(*) FILTER (WHERE g.status = 'finished' AND gb.placement = 2) AS "second_places!",
+            COUNT(*) FILTER (WHERE g.status = 'finished' AND gb.placement = 3) AS "third_places!",
+            COUNT(*) FILTER (WHERE g.status = 'finished' AND gb.placement = 4) AS "fourth_places!",
+            AVG(gb.placement) FILTER (WHERE g.status = 'finished')::float8 AS average_placement
+        FROM game_battlesnakes gb
+        JOIN games g ON g.game_id = gb.game_id
+        WHERE gb.battlesnake_id = $1
+        "#,
+        battlesnake_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to fetch game stats for battlesnake")?;
+
+    Ok(BattlesnakeGameStats {
+        total_games: row.total_games,
+        finished_games: row.finished_games,
+        wins: row.wins,
+        second_places: row.second_places,
+        third_places: row.third_places,
+        fourth_places: row.fourth_places,
+        average_placement: row.average_placement,
+    })
+}
+
 // Get a game with all its battlesnakes
 pub async fn get_game_with_battlesnakes(
     pool: &PgPool,
@@ -378,3 +536,67 @@ pub async fn get_game_with_battlesnakes(
 
     Ok((game, battlesnakes))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_game_stats_count_only_finished_placements(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'snake', 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+
+        assert_eq!(
+            get_game_stats_for_battlesnake(&pool, battlesnake_id)
+                .await?
+                .win_rate(),
+            None
+        );
+
+        // Two finished games (1st and 3rd) and one still running
+        for (status, placement) in [
+            ("finished", Some(1)),
+            ("finished", Some(3)),
+            ("running", None),
+        ] {
+            let game_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', $1)
+                 RETURNING game_id",
+            )
+            .bind(status)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id, placement) VALUES ($1, $2, $3)",
+            )
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .bind(placement)
+            .execute(&pool)
+            .await?;
+        }
+
+        let stats = get_game_stats_for_battlesnake(&pool, battlesnake_id).await?;
+        assert_eq!(stats.total_games, 3);
+        assert_eq!(stats.finished_games, 2);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.third_places, 1);
+        assert_eq!(stats.win_rate(), Some(50.0));
+        assert_eq!(stats.average_placement, Some(2.0));
+
+        let history = get_game_history_for_battlesnake(&pool, battlesnake_id, 2).await?;
+        assert_eq!(history.len(), 2);
+        Ok(())
+    }
+}
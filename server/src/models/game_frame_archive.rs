@@ -0,0 +1,150 @@
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A game whose frames live in GCS, and the object holding them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedGame {
+    pub game_id: Uuid,
+    pub gcs_path: String,
+}
+
+/// Where a game's frames were archived, or `None` if they're still in
+/// `turns` (or the game doesn't exist)
+pub async fn get_gcs_path(pool: &PgPool, game_id: Uuid) -> cja::Result<Option<String>> {
+    let path = sqlx::query_scalar!(
+        "SELECT gcs_path FROM game_frame_archives WHERE game_id = $1",
+        game_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch game frame archive")?;
+
+    Ok(path)
+}
+
+/// Finished games last touched before `cutoff` whose frames are still in
+/// Postgres, oldest first. Games imported from the legacy engine are
+/// skipped: their replays already live in GCS.
+pub async fn find_archivable_games(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> cja::Result<Vec<Uuid>> {
+    let game_ids = sqlx::query_scalar!(
+        r#"
+        SELECT g.game_id
+        FROM games g
+        WHERE g.status = 'finished'
+          AND g.updated_at < $1
+          AND g.engine_game_id IS NULL
+          AND NOT EXISTS (SELECT 1 FROM game_frame_archives a WHERE a.game_id = g.game_id)
+          AND EXISTS (
+              SELECT 1 FROM turns t
+              WHERE t.game_id = g.game_id AND (t.frame_bin IS NOT NULL OR t.frame_data IS NOT NULL)
+          )
+        ORDER BY g.updated_at ASC
+        LIMIT $2
+        "#,
+        cutoff,
+        limit,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to find games to archive frames for")?;
+
+    Ok(game_ids)
+}
+
+/// Record that a game's frames are safely in GCS and clear them from
+/// `turns`, in one transaction so readers always find the frames in exactly
+/// one place. Returns false if the game was already archived (nothing is
+/// cleared then).
+pub async fn record_archive(
+    pool: &PgPool,
+    game_id: Uuid,
+    gcs_path: &str,
+    frame_count: i32,
+) -> cja::Result<bool> {
+    let mut tx = pool
+        .begin()
+        .await
+        .wrap_err("Failed to start frame archive transaction")?;
+
+    let inserted = sqlx::query!(
+        "INSERT INTO game_frame_archives (game_id, gcs_path, frame_count)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (game_id) DO NOTHING",
+        game_id,
+        gcs_path,
+        frame_count,
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to record game frame archive")?
+    .rows_affected()
+        > 0;
+
+    if inserted {
+        sqlx::query!(
+            "UPDATE turns SET frame_data = NULL, frame_bin = NULL WHERE game_id = $1",
+            game_id
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err_with(|| format!("Failed to clear archived frames for game {game_id}"))?;
+    }
+
+    tx.commit()
+        .await
+        .wrap_err("Failed to commit frame archive transaction")?;
+
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn record_archive_clears_frames_once(pool: PgPool) -> cja::Result<()> {
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status, updated_at)
+             VALUES ('11x11', 'Standard', 'finished', NOW() - INTERVAL '30 days')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO turns (game_id, turn_number, frame_data)
+             VALUES ($1, 0, '{\"Turn\": 0}'), ($1, 1, '{\"Turn\": 1}')",
+        )
+        .bind(game_id)
+        .execute(&pool)
+        .await?;
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(7);
+        assert_eq!(
+            find_archivable_games(&pool, cutoff, 10).await?,
+            vec![game_id]
+        );
+
+        assert!(record_archive(&pool, game_id, "frames/g.json.zst", 2).await?);
+        assert!(!record_archive(&pool, game_id, "frames/other.json.zst", 2).await?);
+        assert_eq!(
+            get_gcs_path(&pool, game_id).await?.as_deref(),
+            Some("frames/g.json.zst")
+        );
+
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM turns WHERE game_id = $1 AND frame_data IS NOT NULL",
+        )
+        .bind(game_id)
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(remaining, 0);
+        assert!(find_archivable_games(&pool, cutoff, 10).await?.is_empty());
+
+        Ok(())
+    }
+}
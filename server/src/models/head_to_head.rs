@@ -0,0 +1,239 @@
+//! Head-to-head records between two snakes, from the leaderboard games they
+//! both played in. "A" and "B" are the snakes in the order they were asked
+//! for.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How two snakes fared against each other on one leaderboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadToHeadSummary {
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    pub games: i64,
+    /// Games A placed ahead of B, and the reverse; the rest were ties
+    pub a_ahead: i64,
+    pub b_ahead: i64,
+    /// Games each snake won outright or shared first place in
+    pub a_wins: i64,
+    pub b_wins: i64,
+}
+
+/// One leaderboard game both snakes played in
+#[derive(Debug, Clone)]
+pub struct SharedGame {
+    pub game_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub game_created_at: chrono::DateTime<chrono::Utc>,
+    pub placement_a: i32,
+    pub placement_b: i32,
+    /// Each snake's ID within the game, which is how frames name it
+    pub game_battlesnake_a: Option<Uuid>,
+    pub game_battlesnake_b: Option<Uuid>,
+}
+
+/// Per-leaderboard records for A against B, busiest leaderboard first
+pub async fn get_summaries(
+    pool: &PgPool,
+    battlesnake_a: Uuid,
+    battlesnake_b: Uuid,
+) -> cja::Result<Vec<HeadToHeadSummary>> {
+    let summaries = sqlx::query_as!(
+        HeadToHeadSummary,
+        r#"
+        SELECT
+            lg.leaderboard_id,
+            l.name as leaderboard_name,
+            COUNT(*) as "games!",
+            COUNT(*) FILTER (WHERE ra.placement < rb.placement) as "a_ahead!",
+            COUNT(*) FILTER (WHERE rb.placement < ra.placement) as "b_ahead!",
+            COUNT(*) FILTER (WHERE ra.placement = 1) as "a_wins!",
+            COUNT(*) FILTER (WHERE rb.placement = 1) as "b_wins!"
+        FROM leaderboard_entries ea
+        JOIN leaderboard_game_results ra ON ra.leaderboard_entry_id = ea.leaderboard_entry_id
+        JOIN leaderboard_game_results rb ON rb.leaderboard_game_id = ra.leaderboard_game_id
+        JOIN leaderboard_entries eb ON eb.leaderboard_entry_id = rb.leaderboard_entry_id
+        JOIN leaderboard_games lg ON lg.leaderboard_game_id = ra.leaderboard_game_id
+        JOIN leaderboards l ON l.leaderboard_id = lg.leaderboard_id
+        WHERE ea.battlesnake_id = $1 AND eb.battlesnake_id = $2
+        GROUP BY lg.leaderboard_id, l.name
+        ORDER BY COUNT(*) DESC, l.name
+        "#,
+        battlesnake_a,
+        battlesnake_b
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch head-to-head summaries")?;
+
+    Ok(summaries)
+}
+
+/// The most recent leaderboard games A and B both played in, newest first
+pub async fn get_recent_shared_games(
+    pool: &PgPool,
+    battlesnake_a: Uuid,
+    battlesnake_b: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<SharedGame>> {
+    let games = sqlx::query_as!(
+        SharedGame,
+        r#"
+        SELECT
+            lg.game_id,
+            lg.leaderboard_id,
+            ra.game_created_at,
+            ra.placement as placement_a,
+            rb.placement as placement_b,
+            (SELECT gb.game_battlesnake_id FROM game_battlesnakes gb
+             WHERE gb.game_id = lg.game_id AND gb.leaderboard_entry_id = ra.leaderboard_entry_id
+             LIMIT 1) as game_battlesnake_a,
+            (SELECT gb.game_battlesnake_id FROM game_battlesnakes gb
+             WHERE gb.game_id = lg.game_id AND gb.leaderboard_entry_id = rb.leaderboard_entry_id
+             LIMIT 1) as game_battlesnake_b
+        FROM leaderboard_entries ea
+        JOIN leaderboard_game_results ra ON ra.leaderboard_entry_id = ea.leaderboard_entry_id
+        JOIN leaderboard_game_results rb ON rb.leaderboard_game_id = ra.leaderboard_game_id
+        JOIN leaderboard_entries eb ON eb.leaderboard_entry_id = rb.leaderboard_entry_id
+        JOIN leaderboard_games lg ON lg.leaderboard_game_id = ra.leaderboard_game_id
+        WHERE ea.battlesnake_id = $1 AND eb.battlesnake_id = $2
+        ORDER BY ra.game_created_at DESC, lg.game_id DESC
+        LIMIT $3
+        "#,
+        battlesnake_a,
+        battlesnake_b,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch shared games")?;
+
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_summaries_and_shared_games(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut snakes = Vec::new();
+        for name in ["a", "b", "c"] {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+        }
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut entries = Vec::new();
+        for battlesnake_id in &snakes {
+            let entry_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id)
+                 VALUES ($1, $2) RETURNING leaderboard_entry_id",
+            )
+            .bind(leaderboard_id)
+            .bind(battlesnake_id)
+            .fetch_one(&pool)
+            .await?;
+            entries.push(entry_id);
+        }
+
+        // Games as (age in hours, placement per entry); C sits out the first
+        let games = [
+            (3, vec![Some(1), Some(2), None]),
+            (2, vec![Some(2), Some(1), Some(3)]),
+            (1, vec![Some(1), Some(3), Some(2)]),
+        ];
+        let mut game_ids = Vec::new();
+        for (age_hours, placements) in games {
+            let game_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type, status, created_at)
+                 VALUES ('11x11', 'Standard', 'finished', NOW() - make_interval(hours => $1))
+                 RETURNING game_id",
+            )
+            .bind(age_hours)
+            .fetch_one(&pool)
+            .await?;
+            let leaderboard_game_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO leaderboard_games (leaderboard_id, game_id)
+                 VALUES ($1, $2) RETURNING leaderboard_game_id",
+            )
+            .bind(leaderboard_id)
+            .bind(game_id)
+            .fetch_one(&pool)
+            .await?;
+            for (entry_id, placement) in entries.iter().zip(placements) {
+                let Some(placement) = placement else { continue };
+                sqlx::query(
+                    "INSERT INTO game_battlesnakes (game_id, leaderboard_entry_id, placement)
+                     VALUES ($1, $2, $3)",
+                )
+                .bind(game_id)
+                .bind(entry_id)
+                .bind(placement)
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO leaderboard_game_results (
+                        leaderboard_game_id, leaderboard_entry_id, placement,
+                        mu_before, mu_after, sigma_before, sigma_after, display_score_change,
+                        game_created_at
+                     )
+                     SELECT $1, $2, $3, 25, 25, 8, 8, 0, created_at FROM games WHERE game_id = $4",
+                )
+                .bind(leaderboard_game_id)
+                .bind(entry_id)
+                .bind(placement)
+                .bind(game_id)
+                .execute(&pool)
+                .await?;
+            }
+            game_ids.push(game_id);
+        }
+
+        let summaries = get_summaries(&pool, snakes[0], snakes[1]).await?;
+        assert_eq!(
+            summaries,
+            vec![HeadToHeadSummary {
+                leaderboard_id,
+                leaderboard_name: "Duels".to_string(),
+                games: 3,
+                a_ahead: 2,
+                b_ahead: 1,
+                a_wins: 2,
+                b_wins: 1,
+            }]
+        );
+        let reversed = get_summaries(&pool, snakes[1], snakes[0]).await?;
+        assert_eq!((reversed[0].a_ahead, reversed[0].b_ahead), (1, 2));
+        assert_eq!(
+            get_summaries(&pool, snakes[0], snakes[2]).await?[0].games,
+            2
+        );
+
+        let shared = get_recent_shared_games(&pool, snakes[1], snakes[2], 10).await?;
+        let shared_ids: Vec<Uuid> = shared.iter().map(|g| g.game_id).collect();
+        assert_eq!(shared_ids, vec![game_ids[2], game_ids[1]]);
+        assert_eq!((shared[0].placement_a, shared[0].placement_b), (3, 2));
+        assert!(shared[0].game_battlesnake_a.is_some());
+        assert_ne!(shared[0].game_battlesnake_a, shared[0].game_battlesnake_b);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+//! "Play as a snake": a game where one seat is steered from the browser.
+//!
+//! The player picks one of their own snakes and the arena fills the other
+//! seats with public snakes that are currently in matchmaking. The game is an
+//! ordinary unranked game except for that seat, whose move the runner reads
+//! from the player's last heading instead of the snake's URL (see
+//! [`crate::human_play`]).
+
+use color_eyre::eyre::Context as _;
+use rules::Direction;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::game::{self, CreateGame, Game, GameBoardSize, GameType};
+use crate::models::game_battlesnake::AddBattlesnakeToGame;
+
+/// Most opponents a human game can have; four snakes in all, like any game
+pub const MAX_OPPONENTS: i64 = 3;
+
+/// The steered seat of a game
+#[derive(Debug, Clone)]
+pub struct HumanPlayer {
+    pub game_battlesnake_id: Uuid,
+    pub game_id: Uuid,
+    pub user_id: Uuid,
+    pub heading: Option<String>,
+}
+
+impl HumanPlayer {
+    pub fn direction(&self) -> Option<Direction> {
+        self.heading.as_deref()?.parse().ok()
+    }
+}
+
+/// Create a game with `battlesnake_id` steered by `user_id` against up to
+/// `opponents` random public snakes in matchmaking. Returns `Ok(Err(message))`
+/// for user-facing refusals. The caller enqueues the game.
+pub async fn create_human_game(
+    pool: &PgPool,
+    user_id: Uuid,
+    battlesnake_id: Uuid,
+    board_size: GameBoardSize,
+    opponents: i64,
+) -> cja::Result<Result<Game, String>> {
+    if !crate::models::battlesnake::belongs_to_user(pool, battlesnake_id, user_id).await? {
+        return Ok(Err("Pick one of your own snakes to play as".to_string()));
+    }
+
+    let opponent_ids = sqlx::query_scalar!(
+        r#"
+        SELECT b.battlesnake_id
+        FROM battlesnakes b
+        WHERE b.visibility = 'public'
+          AND b.user_id <> $1
+          AND EXISTS (
+              SELECT 1 FROM leaderboard_entries le
+              WHERE le.battlesnake_id = b.battlesnake_id AND le.disabled_at IS NULL
+          )
+        ORDER BY random()
+        LIMIT $2
+        "#,
+        user_id,
+        opponents.clamp(1, MAX_OPPONENTS),
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to pick opponents for a human game")?;
+    if opponent_ids.is_empty() {
+        return Ok(Err(
+            "No public snakes are in matchmaking right now, so there's nobody to play against"
+                .to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await.wrap_err("Failed to begin transaction")?;
+    let game = game::create_game(
+        &mut *tx,
+        CreateGame {
+            board_size,
+            game_type: GameType::Standard,
+            run_tag: None,
+        },
+    )
+    .await?;
+
+    let game_battlesnake_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO game_battlesnakes (game_id, battlesnake_id)
+        VALUES ($1, $2)
+        RETURNING game_battlesnake_id
+        "#,
+        game.game_id,
+        battlesnake_id,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .wrap_err("Failed to add the player's snake to the game")?;
+    sqlx::query!(
+        r#"
+        INSERT INTO human_players (game_battlesnake_id, game_id, user_id)
+        VALUES ($1, $2, $3)
+        "#,
+        game_battlesnake_id,
+        game.game_id,
+        user_id,
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to record the human player")?;
+
+    for battlesnake_id in opponent_ids {
+        game::add_battlesnake_to_game(
+            &mut *tx,
+            game.game_id,
+            AddBattlesnakeToGame { battlesnake_id },
+        )
+        .await?;
+    }
+
+    tx.commit().await.wrap_err("Failed to commit transaction")?;
+    Ok(Ok(game))
+}
+
+pub async fn get_for_game(pool: &PgPool, game_id: Uuid) -> cja::Result<Option<HumanPlayer>> {
+    sqlx::query_as!(
+        HumanPlayer,
+        r#"
+        SELECT game_battlesnake_id, game_id, user_id, heading
+        FROM human_players
+        WHERE game_id = $1
+        "#,
+        game_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch human player")
+}
+
+/// The player's latest heading, as the runner reads it each turn
+pub async fn get_heading(
+    pool: &PgPool,
+    game_battlesnake_id: Uuid,
+) -> cja::Result<Option<Direction>> {
+    let heading = sqlx::query_scalar!(
+        "SELECT heading FROM human_players WHERE game_battlesnake_id = $1",
+        game_battlesnake_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch human heading")?
+    .flatten();
+
+    Ok(heading.and_then(|h| h.parse().ok()))
+}
+
+/// Steer `user_id`'s seat in `game_id`. Returns false if they aren't the
+/// game's player.
+pub async fn set_heading(
+    pool: &PgPool,
+    game_id: Uuid,
+    user_id: Uuid,
+    direction: Direction,
+) -> cja::Result<bool> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE human_players
+        SET heading = $3
+        WHERE game_id = $1 AND user_id = $2
+        "#,
+        game_id,
+        user_id,
+        direction.to_string(),
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to set human heading")?;
+
+    Ok(updated.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixture_user_with_snake;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_human_game_seats_the_player(pool: PgPool) -> cja::Result<()> {
+        let (player, snake) = fixture_user_with_snake(&pool, "player").await?;
+        let (_, opponent) = fixture_user_with_snake(&pool, "opponent").await?;
+        // Only public snakes are picked as opponents
+        sqlx::query("UPDATE battlesnakes SET visibility = 'public' WHERE battlesnake_id = $1")
+            .bind(opponent)
+            .execute(&pool)
+            .await?;
+
+        // Nobody in matchmaking yet
+        let refused =
+            create_human_game(&pool, player, snake, GameBoardSize::Medium, MAX_OPPONENTS).await?;
+        assert!(refused.is_err());
+
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id) VALUES ($1, $2)",
+        )
+        .bind(leaderboard_id)
+        .bind(opponent)
+        .execute(&pool)
+        .await?;
+
+        // Someone else's snake can't be steered
+        assert!(
+            create_human_game(&pool, player, opponent, GameBoardSize::Medium, 1)
+                .await?
+                .is_err()
+        );
+
+        let game = create_human_game(&pool, player, snake, GameBoardSize::Medium, MAX_OPPONENTS)
+            .await?
+            .expect("game is created");
+        let seats: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM game_battlesnakes WHERE game_id = $1")
+                .bind(game.game_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(seats, 2);
+
+        let human = get_for_game(&pool, game.game_id).await?.unwrap();
+        assert_eq!(human.user_id, player);
+        assert_eq!(human.direction(), None);
+
+        assert!(set_heading(&pool, game.game_id, player, Direction::Left).await?);
+        assert!(!set_heading(&pool, game.game_id, Uuid::new_v4(), Direction::Up).await?);
+        assert_eq!(
+            get_heading(&pool, human.game_battlesnake_id).await?,
+            Some(Direction::Left)
+        );
+
+        Ok(())
+    }
+}
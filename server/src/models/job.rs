@@ -0,0 +1,518 @@
+//! The cja `jobs` table: pickup and bookkeeping for [`crate::job_worker`],
+//! and admin access to failing jobs.
+//!
+//! A worker claims a job by locking it until a deadline (`locked_until`)
+//! and keeps pushing that deadline out while the job runs. A failed job
+//! gets `error_count` bumped and `run_at` pushed out with backoff, and is
+//! deleted once it exhausts its retries. The admin queries act on the
+//! failing rows without psql: pull a job's next attempt forward to now
+//! (with a fresh retry budget), or drop a poisoned job outright. Locked rows
+//! belong to a worker mid-run and are never touched.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{jobs::GameRunnerJob, state::AppState};
+
+/// A row of the cja `jobs` table
+#[derive(Debug, Clone)]
+pub struct JobRecord {
+    pub job_id: Uuid,
+    pub name: String,
+    pub payload: serde_json::Value,
+    pub error_count: i32,
+    pub last_error_message: Option<String>,
+    pub last_failed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub run_at: chrono::DateTime<chrono::Utc>,
+    pub locked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Outcome of acting on a single job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobAction {
+    Done,
+    NotFound,
+    /// A worker holds the job right now
+    Locked,
+}
+
+/// Jobs whose latest failure was at or after `since`, counted by name, most
+/// first
+pub async fn count_recent_failures_by_name(
+    pool: &PgPool,
+    since: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<Vec<(String, i64)>> {
+    let rows = sqlx::query!(
+        r#"SELECT name, COUNT(*) AS "count!"
+         FROM jobs
+         WHERE error_count > 0 AND last_failed_at >= $1
+         GROUP BY name
+         ORDER BY COUNT(*) DESC, name"#,
+        since
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to count recent job failures")?;
+
+    Ok(rows.into_iter().map(|r| (r.name, r.count)).collect())
+}
+
+/// Jobs due to run that no live worker holds
+pub async fn count_ready_jobs(pool: &PgPool) -> cja::Result<i64> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM jobs
+         WHERE (locked_at IS NULL OR locked_until < NOW()) AND run_at <= NOW()"#
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to count ready jobs")
+}
+
+/// Jobs that have failed at least once, most recent failure first,
+/// optionally only those with the given name.
+pub async fn list_failing_jobs(
+    pool: &PgPool,
+    name: Option<&str>,
+    limit: i64,
+) -> cja::Result<Vec<JobRecord>> {
+    sqlx::query_as!(
+        JobRecord,
+        r#"SELECT job_id, name, payload, error_count, last_error_message, last_failed_at,
+                run_at, locked_at, created_at
+         FROM jobs
+         WHERE error_count > 0 AND ($1::text IS NULL OR name = $1)
+         ORDER BY last_failed_at DESC NULLS LAST, job_id
+         LIMIT $2"#,
+        name,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list failing jobs")
+}
+
+async fn job_is_locked(pool: &PgPool, job_id: Uuid) -> cja::Result<Option<bool>> {
+    sqlx::query_scalar!(
+        r#"SELECT locked_at IS NOT NULL as "locked!" FROM jobs WHERE job_id = $1"#,
+        job_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to look up job")
+}
+
+/// Make a job runnable now with a fresh retry budget. The last error is
+/// kept so the admin can still see why it was failing.
+pub async fn retry_job(pool: &PgPool, job_id: Uuid) -> cja::Result<JobAction> {
+    let result = sqlx::query!(
+        r#"UPDATE jobs SET run_at = NOW(), error_count = 0
+         WHERE job_id = $1 AND locked_at IS NULL"#,
+        job_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to retry job")?;
+
+    if result.rows_affected() > 0 {
+        return Ok(JobAction::Done);
+    }
+    Ok(match job_is_locked(pool, job_id).await? {
+        Some(true) => JobAction::Locked,
+        _ => JobAction::NotFound,
+    })
+}
+
+/// Retry every unlocked failing job with the given name. Returns how many
+/// were rescheduled.
+pub async fn retry_failing_jobs_by_name(pool: &PgPool, name: &str) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        r#"UPDATE jobs SET run_at = NOW(), error_count = 0
+         WHERE name = $1 AND error_count > 0 AND locked_at IS NULL"#,
+        name
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to retry jobs")?;
+
+    Ok(result.rows_affected())
+}
+
+/// Delete a job that will never succeed.
+pub async fn delete_job(pool: &PgPool, job_id: Uuid) -> cja::Result<JobAction> {
+    let result = sqlx::query!(
+        "DELETE FROM jobs WHERE job_id = $1 AND locked_at IS NULL",
+        job_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to delete job")?;
+
+    if result.rows_affected() > 0 {
+        return Ok(JobAction::Done);
+    }
+    Ok(match job_is_locked(pool, job_id).await? {
+        Some(true) => JobAction::Locked,
+        _ => JobAction::NotFound,
+    })
+}
+
+/// A game's `GameRunnerJob` rows, oldest first. cja deletes a job once it
+/// succeeds, so these are only runs still queued, in flight, or failing.
+pub async fn list_runner_jobs_for_game(
+    pool: &PgPool,
+    game_id: Uuid,
+) -> cja::Result<Vec<JobRecord>> {
+    sqlx::query_as!(
+        JobRecord,
+        r#"SELECT job_id, name, payload, error_count, last_error_message, last_failed_at,
+                run_at, locked_at, created_at
+         FROM jobs
+         WHERE name = $1 AND payload->>'game_id' = $2::uuid::text
+         ORDER BY created_at, job_id"#,
+        <GameRunnerJob as cja::jobs::Job<AppState>>::NAME,
+        game_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list runner jobs for game")
+}
+
+/// Delete a game's queued `GameRunnerJob`s, leaving any a worker holds.
+/// Returns how many were deleted.
+pub async fn delete_queued_runner_jobs_for_game(pool: &PgPool, game_id: Uuid) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        r#"DELETE FROM jobs
+         WHERE name = $1 AND payload->>'game_id' = $2::uuid::text AND locked_at IS NULL"#,
+        <GameRunnerJob as cja::jobs::Job<AppState>>::NAME,
+        game_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to delete queued runner jobs for game")?;
+
+    Ok(result.rows_affected())
+}
+
+/// A job a worker has claimed and must run
+#[derive(Debug, Clone)]
+pub struct ClaimedJob {
+    pub job_id: Uuid,
+    pub name: String,
+    pub payload: serde_json::Value,
+    pub error_count: i32,
+}
+
+/// Claim the next due job for `worker_id`, locked for `visibility`.
+///
+/// `SKIP LOCKED` lets any number of workers poll at once: each skips rows
+/// another is claiming instead of waiting on them, so no two claim the same
+/// job. A job whose lock deadline has passed is claimable again; its worker
+/// stopped heartbeating and is presumed dead.
+pub async fn claim_next_job(
+    pool: &PgPool,
+    worker_id: &str,
+    visibility: std::time::Duration,
+) -> cja::Result<Option<ClaimedJob>> {
+    sqlx::query_as!(
+        ClaimedJob,
+        r#"UPDATE jobs
+         SET locked_at = NOW(), locked_by = $1, locked_until = NOW() + make_interval(secs => $2)
+         WHERE job_id = (
+             SELECT job_id FROM jobs
+             WHERE run_at <= NOW() AND (locked_at IS NULL OR locked_until < NOW())
+             ORDER BY priority DESC, run_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING job_id, name, payload, error_count"#,
+        worker_id,
+        visibility.as_secs_f64()
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to claim next job")
+}
+
+/// Heartbeat: push the lock deadline of a job `worker_id` holds out to
+/// `visibility` from now. `false` means the worker no longer holds it - the
+/// deadline passed and another worker claimed it.
+pub async fn extend_job_lock(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    visibility: std::time::Duration,
+) -> cja::Result<bool> {
+    let result = sqlx::query!(
+        r#"UPDATE jobs SET locked_until = NOW() + make_interval(secs => $3)
+         WHERE job_id = $1 AND locked_by = $2"#,
+        job_id,
+        worker_id,
+        visibility.as_secs_f64()
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to extend job lock")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove a job `worker_id` ran to completion
+pub async fn complete_job(pool: &PgPool, job_id: Uuid, worker_id: &str) -> cja::Result<()> {
+    sqlx::query!(
+        "DELETE FROM jobs WHERE job_id = $1 AND locked_by = $2",
+        job_id,
+        worker_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to delete completed job")?;
+
+    Ok(())
+}
+
+/// Record a failed run and unlock the job for a retry after exponential
+/// backoff, or delete it once it has failed `max_retries` times. Returns
+/// whether it will be retried.
+pub async fn fail_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    error: &str,
+    max_retries: i32,
+) -> cja::Result<bool> {
+    let error_count = sqlx::query_scalar!(
+        r#"UPDATE jobs
+         SET error_count = error_count + 1,
+             last_error_message = $3,
+             last_failed_at = NOW(),
+             run_at = NOW() + make_interval(secs => power(2, LEAST(error_count, 12))),
+             locked_at = NULL, locked_by = NULL, locked_until = NULL
+         WHERE job_id = $1 AND locked_by = $2
+         RETURNING error_count"#,
+        job_id,
+        worker_id,
+        error
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to record job failure")?;
+
+    match error_count {
+        Some(count) if count >= max_retries => {
+            sqlx::query!("DELETE FROM jobs WHERE job_id = $1", job_id)
+                .execute(pool)
+                .await
+                .wrap_err("Failed to delete exhausted job")?;
+            Ok(false)
+        }
+        Some(_) => Ok(true),
+        // Another worker took the job over; its run decides
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_job(
+        pool: &PgPool,
+        name: &str,
+        error_count: i32,
+        locked: bool,
+    ) -> cja::Result<Uuid> {
+        let job_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO jobs (job_id, name, payload, priority, run_at, context, error_count,
+                               last_error_message, last_failed_at, locked_at, locked_by)
+             VALUES ($1, $2, '{}', 0, NOW() + INTERVAL '1 hour', 'test', $3,
+                     CASE WHEN $3 > 0 THEN 'boom' END, CASE WHEN $3 > 0 THEN NOW() END,
+                     CASE WHEN $4 THEN NOW() END, CASE WHEN $4 THEN 'worker' END)",
+        )
+        .bind(job_id)
+        .bind(name)
+        .bind(error_count)
+        .bind(locked)
+        .execute(pool)
+        .await?;
+        Ok(job_id)
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_list_failing_jobs_filters_by_name(pool: PgPool) -> cja::Result<()> {
+        let failing = insert_job(&pool, "GameRunnerJob", 3, false).await?;
+        insert_job(&pool, "GameRunnerJob", 0, false).await?;
+        let other = insert_job(&pool, "BackupJob", 1, false).await?;
+
+        let all: Vec<Uuid> = list_failing_jobs(&pool, None, 50)
+            .await?
+            .iter()
+            .map(|j| j.job_id)
+            .collect();
+        assert_eq!(all.len(), 2);
+        assert!(all.contains(&failing) && all.contains(&other));
+
+        let runner = list_failing_jobs(&pool, Some("GameRunnerJob"), 50).await?;
+        assert_eq!(runner.len(), 1);
+        assert_eq!(runner[0].job_id, failing);
+        assert_eq!(runner[0].last_error_message.as_deref(), Some("boom"));
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_retry_and_delete_skip_locked_jobs(pool: PgPool) -> cja::Result<()> {
+        let idle = insert_job(&pool, "GameRunnerJob", 2, false).await?;
+        let locked = insert_job(&pool, "GameRunnerJob", 2, true).await?;
+
+        assert_eq!(retry_job(&pool, idle).await?, JobAction::Done);
+        assert_eq!(retry_job(&pool, locked).await?, JobAction::Locked);
+        assert_eq!(retry_job(&pool, Uuid::new_v4()).await?, JobAction::NotFound);
+        assert_eq!(list_failing_jobs(&pool, None, 50).await?.len(), 1);
+
+        assert_eq!(delete_job(&pool, locked).await?, JobAction::Locked);
+        assert_eq!(delete_job(&pool, idle).await?, JobAction::Done);
+        assert_eq!(delete_job(&pool, idle).await?, JobAction::NotFound);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_retry_by_name_only_touches_failing_unlocked_jobs(
+        pool: PgPool,
+    ) -> cja::Result<()> {
+        insert_job(&pool, "BackupJob", 1, false).await?;
+        insert_job(&pool, "BackupJob", 4, false).await?;
+        insert_job(&pool, "BackupJob", 1, true).await?;
+        insert_job(&pool, "BackupJob", 0, false).await?;
+        insert_job(&pool, "GameRunnerJob", 1, false).await?;
+
+        assert_eq!(retry_failing_jobs_by_name(&pool, "BackupJob").await?, 2);
+
+        let due: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM jobs WHERE name = 'BackupJob' AND run_at <= NOW()",
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(due, 2);
+
+        Ok(())
+    }
+
+    async fn insert_due_job(pool: &PgPool, priority: i32) -> cja::Result<Uuid> {
+        let job_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO jobs (job_id, name, payload, priority, run_at, context)
+             VALUES ($1, 'NoopJob', '{}', $2, NOW() - INTERVAL '1 second', 'test')",
+        )
+        .bind(job_id)
+        .bind(priority)
+        .execute(pool)
+        .await?;
+        Ok(job_id)
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_claim_skips_held_jobs_and_reclaims_expired_ones(pool: PgPool) -> cja::Result<()> {
+        let visibility = std::time::Duration::from_secs(60);
+        let low = insert_due_job(&pool, 0).await?;
+        let high = insert_due_job(&pool, 5).await?;
+
+        let first = claim_next_job(&pool, "worker-a", visibility)
+            .await?
+            .unwrap();
+        let second = claim_next_job(&pool, "worker-b", visibility)
+            .await?
+            .unwrap();
+        assert_eq!((first.job_id, second.job_id), (high, low));
+        assert!(
+            claim_next_job(&pool, "worker-c", visibility)
+                .await?
+                .is_none()
+        );
+
+        // worker-a stops heartbeating; its job is up for grabs again
+        sqlx::query("UPDATE jobs SET locked_until = NOW() - INTERVAL '1 second' WHERE job_id = $1")
+            .bind(high)
+            .execute(&pool)
+            .await?;
+        assert_eq!(count_ready_jobs(&pool).await?, 1);
+        let reclaimed = claim_next_job(&pool, "worker-c", visibility)
+            .await?
+            .unwrap();
+        assert_eq!(reclaimed.job_id, high);
+
+        // The old holder finds out at its next heartbeat, and can no longer
+        // finish or fail the job
+        assert!(!extend_job_lock(&pool, high, "worker-a", visibility).await?);
+        assert!(extend_job_lock(&pool, high, "worker-c", visibility).await?);
+        assert!(!fail_job(&pool, high, "worker-a", "boom", 3).await?);
+        complete_job(&pool, high, "worker-a").await?;
+        assert_eq!(list_failing_jobs(&pool, None, 50).await?.len(), 0);
+
+        complete_job(&pool, high, "worker-c").await?;
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(remaining, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_fail_job_backs_off_then_gives_up(pool: PgPool) -> cja::Result<()> {
+        let visibility = std::time::Duration::from_secs(60);
+        let job_id = insert_due_job(&pool, 0).await?;
+
+        claim_next_job(&pool, "worker", visibility).await?.unwrap();
+        assert!(fail_job(&pool, job_id, "worker", "boom", 2).await?);
+        // Backed off, so not due again yet
+        assert!(claim_next_job(&pool, "worker", visibility).await?.is_none());
+        let failing = list_failing_jobs(&pool, None, 50).await?;
+        assert_eq!(failing[0].error_count, 1);
+        assert_eq!(failing[0].locked_at, None);
+
+        sqlx::query("UPDATE jobs SET run_at = NOW() WHERE job_id = $1")
+            .bind(job_id)
+            .execute(&pool)
+            .await?;
+        let claimed = claim_next_job(&pool, "worker", visibility).await?.unwrap();
+        assert_eq!(claimed.error_count, 1);
+        assert!(!fail_job(&pool, job_id, "worker", "boom", 2).await?);
+        assert_eq!(job_is_locked(&pool, job_id).await?, None);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_runner_jobs_for_game(pool: PgPool) -> cja::Result<()> {
+        let game_id = Uuid::new_v4();
+        let queued = insert_job(&pool, "GameRunnerJob", 1, false).await?;
+        let running = insert_job(&pool, "GameRunnerJob", 0, true).await?;
+        let other_game = insert_job(&pool, "GameRunnerJob", 0, false).await?;
+        sqlx::query("UPDATE jobs SET payload = jsonb_build_object('game_id', $1::uuid) WHERE job_id = ANY($2)")
+            .bind(game_id)
+            .bind(vec![queued, running])
+            .execute(&pool)
+            .await?;
+        sqlx::query(
+            "UPDATE jobs SET payload = jsonb_build_object('game_id', $1::uuid) WHERE job_id = $2",
+        )
+        .bind(Uuid::new_v4())
+        .bind(other_game)
+        .execute(&pool)
+        .await?;
+
+        let jobs = list_runner_jobs_for_game(&pool, game_id).await?;
+        assert_eq!(jobs.len(), 2);
+
+        assert_eq!(delete_queued_runner_jobs_for_game(&pool, game_id).await?, 1);
+        let jobs = list_runner_jobs_for_game(&pool, game_id).await?;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].job_id, running);
+
+        Ok(())
+    }
+}
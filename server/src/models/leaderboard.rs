@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool, Postgres};
 use uuid::Uuid;
 
+use crate::metrics::TimedQuery as _;
+
 /// Application constants for leaderboard configuration
 pub const MATCH_SIZE: usize = 4;
 pub const MIN_GAMES_FOR_RANKING: i32 = 10;
@@ -14,6 +16,8 @@ pub struct Leaderboard {
     pub leaderboard_id: Uuid,
     pub name: String,
     pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set while an admin has frozen the standings (end of season)
+    pub frozen_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -82,7 +86,9 @@ pub struct RankedEntry {
 }
 
 /// Sort order for ranked leaderboard entries.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Deserialize, utoipa::ToSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum LeaderboardSort {
     #[default]
@@ -104,7 +110,7 @@ impl LeaderboardSort {
 pub async fn get_all_leaderboards(pool: &PgPool) -> cja::Result<Vec<Leaderboard>> {
     let rows = sqlx::query_as!(
         Leaderboard,
-        r#"SELECT leaderboard_id, name, disabled_at, created_at, updated_at
+        r#"SELECT leaderboard_id, name, disabled_at, frozen_at, created_at, updated_at
          FROM leaderboards
          ORDER BY created_at ASC"#
     )
@@ -118,7 +124,7 @@ pub async fn get_all_leaderboards(pool: &PgPool) -> cja::Result<Vec<Leaderboard>
 pub async fn get_active_leaderboards(pool: &PgPool) -> cja::Result<Vec<Leaderboard>> {
     let rows = sqlx::query_as!(
         Leaderboard,
-        r#"SELECT leaderboard_id, name, disabled_at, created_at, updated_at
+        r#"SELECT leaderboard_id, name, disabled_at, frozen_at, created_at, updated_at
          FROM leaderboards
          WHERE disabled_at IS NULL
          ORDER BY created_at ASC"#
@@ -136,7 +142,7 @@ pub async fn get_leaderboard_by_id(
 ) -> cja::Result<Option<Leaderboard>> {
     let row = sqlx::query_as!(
         Leaderboard,
-        r#"SELECT leaderboard_id, name, disabled_at, created_at, updated_at
+        r#"SELECT leaderboard_id, name, disabled_at, frozen_at, created_at, updated_at
          FROM leaderboards
          WHERE leaderboard_id = $1"#,
         leaderboard_id
@@ -148,6 +154,103 @@ pub async fn get_leaderboard_by_id(
     Ok(row)
 }
 
+/// Freeze or unfreeze a leaderboard. Re-freezing keeps the original time.
+/// Returns `false` if the leaderboard doesn't exist.
+pub async fn set_frozen(pool: &PgPool, leaderboard_id: Uuid, frozen: bool) -> cja::Result<bool> {
+    let result = sqlx::query!(
+        r#"UPDATE leaderboards
+         SET frozen_at = CASE WHEN $2 THEN COALESCE(frozen_at, NOW()) END, updated_at = NOW()
+         WHERE leaderboard_id = $1"#,
+        leaderboard_id,
+        frozen
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to update leaderboard freeze")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Games on a leaderboard whose rating update a freeze deferred and that are
+/// still unrated, in the order they finished. Older unrated games are the
+/// consistency check's to find, not the unfreeze's.
+pub async fn get_deferred_games(pool: &PgPool, leaderboard_id: Uuid) -> cja::Result<Vec<Uuid>> {
+    let rows = sqlx::query_scalar!(
+        r#"SELECT lg.leaderboard_game_id
+         FROM leaderboard_games lg
+         JOIN games g ON g.game_id = lg.game_id
+         WHERE lg.leaderboard_id = $1
+           AND lg.rating_deferred_at IS NOT NULL
+           AND g.status = 'finished'
+           AND NOT EXISTS (SELECT 1 FROM leaderboard_game_results lgr
+                           WHERE lgr.leaderboard_game_id = lg.leaderboard_game_id)
+         ORDER BY g.finished_at ASC, lg.leaderboard_game_id ASC"#,
+        leaderboard_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch deferred leaderboard games")?;
+
+    Ok(rows)
+}
+
+/// Mark a leaderboard game's rating update as deferred by a freeze, within
+/// the rating update's transaction
+pub async fn mark_rating_deferred(
+    conn: &mut sqlx::PgConnection,
+    leaderboard_game_id: Uuid,
+) -> cja::Result<()> {
+    sqlx::query!(
+        "UPDATE leaderboard_games SET rating_deferred_at = COALESCE(rating_deferred_at, NOW())
+         WHERE leaderboard_game_id = $1",
+        leaderboard_game_id
+    )
+    .execute(conn)
+    .await
+    .wrap_err("Failed to mark leaderboard game rating as deferred")?;
+
+    Ok(())
+}
+
+/// Everything the rankings response depends on that can change between
+/// requests. Rating updates bump `leaderboard_entries.updated_at` in the same
+/// transaction as the per-algorithm stats, so the entries' max covers scores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingsVersion {
+    pub leaderboard_updated_at: chrono::DateTime<chrono::Utc>,
+    pub entry_count: i64,
+    /// Latest change to an entry, its snake, or the snake's owner
+    pub entries_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Cheap fingerprint of a leaderboard's rankings, for ETags. `None` if the
+/// leaderboard doesn't exist.
+pub async fn get_rankings_version(
+    pool: &PgPool,
+    leaderboard_id: Uuid,
+) -> cja::Result<Option<RankingsVersion>> {
+    let version = sqlx::query_as!(
+        RankingsVersion,
+        r#"SELECT
+            l.updated_at as leaderboard_updated_at,
+            (SELECT COUNT(*) FROM leaderboard_entries le
+             WHERE le.leaderboard_id = l.leaderboard_id) as "entry_count!",
+            (SELECT MAX(GREATEST(le.updated_at, b.updated_at, u.updated_at))
+             FROM leaderboard_entries le
+             JOIN battlesnakes b ON b.battlesnake_id = le.battlesnake_id
+             JOIN users u ON u.user_id = b.user_id
+             WHERE le.leaderboard_id = l.leaderboard_id) as entries_updated_at
+         FROM leaderboards l
+         WHERE l.leaderboard_id = $1"#,
+        leaderboard_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch rankings version")?;
+
+    Ok(version)
+}
+
 // --- Leaderboard entry queries ---
 
 /// Opt-in a snake to a leaderboard. Returns the existing entry if one already exists.
@@ -234,6 +337,7 @@ pub async fn get_ranked_entries(
                 MIN_GAMES_FOR_RANKING
             )
             .fetch_all(pool)
+            .timed("leaderboard::ranked_entries")
             .await
         }
         LeaderboardSort::FoodEaten => {
@@ -265,6 +369,7 @@ pub async fn get_ranked_entries(
                 MIN_GAMES_FOR_RANKING
             )
             .fetch_all(pool)
+            .timed("leaderboard::ranked_entries")
             .await
         }
     }
@@ -305,6 +410,7 @@ pub async fn get_placement_entries(
         MIN_GAMES_FOR_RANKING
     )
     .fetch_all(pool)
+    .timed("leaderboard::placement_entries")
     .await
     .wrap_err("Failed to fetch placement leaderboard entries")?;
 
@@ -448,6 +554,38 @@ where
     Ok(())
 }
 
+/// Undo one game's [`update_rating`]: subtract its mu/sigma changes and
+/// finish counts. Used when an admin deletes a rated game.
+pub async fn reverse_rating<'e, E>(
+    executor: E,
+    entry_id: Uuid,
+    mu_change: f64,
+    sigma_change: f64,
+    was_first_place: bool,
+) -> cja::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query!(
+        r#"UPDATE leaderboard_entries
+         SET mu = mu - $2, sigma = sigma - $3,
+             display_score = (mu - $2) - 3.0 * (sigma - $3),
+             games_played = GREATEST(games_played - 1, 0),
+             first_place_finishes = GREATEST(first_place_finishes - CASE WHEN $4 THEN 1 ELSE 0 END, 0),
+             non_first_finishes = GREATEST(non_first_finishes - CASE WHEN $4 THEN 0 ELSE 1 END, 0)
+         WHERE leaderboard_entry_id = $1"#,
+        entry_id,
+        mu_change,
+        sigma_change,
+        was_first_place
+    )
+    .execute(executor)
+    .await
+    .wrap_err("Failed to reverse rating")?;
+
+    Ok(())
+}
+
 /// Pause or resume a leaderboard entry (owner/admin action).
 ///
 /// Always clears `disabled_reason`: a manual pause is reason-less, and a
@@ -472,6 +610,26 @@ pub async fn set_disabled(
     Ok(())
 }
 
+/// Pause every active leaderboard entry for a snake (admin action from the
+/// snake error report). A reason-less manual pause, so the owner can resume
+/// once the snake is fixed. Returns how many entries were paused.
+pub async fn pause_entries_for_battlesnake(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        r#"UPDATE leaderboard_entries
+         SET disabled_at = NOW(), disabled_reason = NULL, updated_at = NOW()
+         WHERE battlesnake_id = $1 AND disabled_at IS NULL"#,
+        battlesnake_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to pause leaderboard entries")?;
+
+    Ok(result.rows_affected())
+}
+
 /// Get entries for a specific user across a leaderboard
 pub async fn get_user_entries(
     pool: &PgPool,
@@ -544,6 +702,86 @@ pub async fn find_leaderboard_game_by_game_id(
 
 // --- Leaderboard game result queries ---
 
+/// Get the per-snake rating changes recorded for a leaderboard game
+pub async fn get_results_for_leaderboard_game(
+    pool: &PgPool,
+    leaderboard_game_id: Uuid,
+) -> cja::Result<Vec<LeaderboardGameResult>> {
+    let results = sqlx::query_as!(
+        LeaderboardGameResult,
+        r#"SELECT leaderboard_game_result_id, leaderboard_game_id, leaderboard_entry_id,
+                placement, mu_before, mu_after, sigma_before, sigma_after,
+                display_score_change, food_eaten, created_at
+         FROM leaderboard_game_results
+         WHERE leaderboard_game_id = $1
+         ORDER BY placement ASC"#,
+        leaderboard_game_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch leaderboard game results")?;
+
+    Ok(results)
+}
+
+/// Delete a leaderboard game's results, returning them, after locking the
+/// entries they belong to. For reversing the game's ratings in the same
+/// transaction.
+pub async fn take_results_for_leaderboard_game(
+    conn: &mut sqlx::PgConnection,
+    leaderboard_game_id: Uuid,
+) -> cja::Result<Vec<LeaderboardGameResult>> {
+    sqlx::query!(
+        r#"SELECT le.leaderboard_entry_id
+         FROM leaderboard_entries le
+         JOIN leaderboard_game_results lgr ON lgr.leaderboard_entry_id = le.leaderboard_entry_id
+         WHERE lgr.leaderboard_game_id = $1
+         ORDER BY le.leaderboard_entry_id
+         FOR UPDATE OF le"#,
+        leaderboard_game_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .wrap_err("Failed to lock leaderboard entries for reversal")?;
+
+    let results = sqlx::query_as!(
+        LeaderboardGameResult,
+        r#"DELETE FROM leaderboard_game_results
+         WHERE leaderboard_game_id = $1
+         RETURNING leaderboard_game_result_id, leaderboard_game_id, leaderboard_entry_id,
+                placement, mu_before, mu_after, sigma_before, sigma_after,
+                display_score_change, food_eaten, created_at"#,
+        leaderboard_game_id
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .wrap_err("Failed to delete leaderboard game results")?;
+
+    Ok(results)
+}
+
+/// Whether any of these entries has a result recorded at or after `since`,
+/// i.e. was rated in a later game
+pub async fn has_results_since(
+    conn: &mut sqlx::PgConnection,
+    entry_ids: &[Uuid],
+    since: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<bool> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM leaderboard_game_results
+            WHERE leaderboard_entry_id = ANY($1) AND created_at >= $2
+         ) AS "exists!""#,
+        entry_ids,
+        since
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .wrap_err("Failed to check for later leaderboard game results")?;
+
+    Ok(exists)
+}
+
 pub struct CreateGameResult {
     pub leaderboard_game_id: Uuid,
     pub leaderboard_entry_id: Uuid,
@@ -556,7 +794,8 @@ pub struct CreateGameResult {
 }
 
 /// Record a game result for a snake. Accepts any sqlx executor (pool or transaction).
-/// Uses ON CONFLICT DO NOTHING as a DB-level idempotency guard.
+/// Uses ON CONFLICT DO NOTHING as a DB-level idempotency guard. The game's
+/// `created_at` is copied onto the result for the entry history index.
 pub async fn create_game_result<'e, E>(
     executor: E,
     data: CreateGameResult,
@@ -568,9 +807,12 @@ where
         LeaderboardGameResult,
         r#"INSERT INTO leaderboard_game_results (
             leaderboard_game_id, leaderboard_entry_id, placement,
-            mu_before, mu_after, sigma_before, sigma_after, display_score_change
+            mu_before, mu_after, sigma_before, sigma_after, display_score_change,
+            game_created_at
          )
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         SELECT $1, $2, $3, $4, $5, $6, $7, $8, lg.created_at
+         FROM leaderboard_games lg
+         WHERE lg.leaderboard_game_id = $1
          ON CONFLICT (leaderboard_game_id, leaderboard_entry_id) DO NOTHING
          RETURNING
             leaderboard_game_result_id, leaderboard_game_id, leaderboard_entry_id,
@@ -649,6 +891,7 @@ pub async fn get_ranked_entries_paginated(
                 offset
             )
             .fetch_all(pool)
+            .timed("leaderboard::ranked_entries_page")
             .await
         }
         LeaderboardSort::FoodEaten => {
@@ -682,6 +925,7 @@ pub async fn get_ranked_entries_paginated(
                 offset
             )
             .fetch_all(pool)
+            .timed("leaderboard::ranked_entries_page")
             .await
         }
     }
@@ -690,22 +934,6 @@ pub async fn get_ranked_entries_paginated(
     Ok(entries)
 }
 
-/// Count total ranked entries for pagination
-pub async fn count_ranked_entries(pool: &PgPool, leaderboard_id: Uuid) -> cja::Result<i64> {
-    let count = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!"
-         FROM leaderboard_entries
-         WHERE leaderboard_id = $1 AND disabled_at IS NULL AND games_played >= $2"#,
-        leaderboard_id,
-        MIN_GAMES_FOR_RANKING
-    )
-    .fetch_one(pool)
-    .await
-    .wrap_err("Failed to count ranked entries")?;
-
-    Ok(count)
-}
-
 /// Game history entry for a leaderboard entry
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct LeaderboardGameHistoryEntry {
@@ -741,11 +969,11 @@ pub async fn get_game_history_for_entry(
             lgr.sigma_before,
             lgr.sigma_after,
             lgr.food_eaten,
-            lg.created_at as game_created_at
+            lgr.game_created_at
          FROM leaderboard_game_results lgr
          JOIN leaderboard_games lg ON lgr.leaderboard_game_id = lg.leaderboard_game_id
          WHERE lgr.leaderboard_entry_id = $1
-         ORDER BY lg.created_at DESC
+         ORDER BY lgr.game_created_at DESC, lgr.leaderboard_game_id DESC
          LIMIT $2 OFFSET $3"#,
         leaderboard_entry_id,
         per_page,
@@ -758,6 +986,53 @@ pub async fn get_game_history_for_entry(
     Ok(entries)
 }
 
+/// Get game history for a leaderboard entry, newest first, starting after
+/// the `(created_at, leaderboard_game_id)` position of the last game a client
+/// has seen. Keyset pagination keeps deep pages as cheap as the first one;
+/// it walks `idx_leaderboard_game_results_entry_history` via the result's
+/// copy of the game's `created_at`.
+pub async fn get_game_history_for_entry_before(
+    pool: &PgPool,
+    leaderboard_entry_id: Uuid,
+    before: Option<(chrono::DateTime<chrono::Utc>, Uuid)>,
+    limit: i64,
+) -> cja::Result<Vec<LeaderboardGameHistoryEntry>> {
+    let (before_created_at, before_game_id) = before.unzip();
+    let entries = sqlx::query_as!(
+        LeaderboardGameHistoryEntry,
+        r#"SELECT
+            lgr.leaderboard_game_id,
+            lg.game_id,
+            lgr.placement,
+            lgr.display_score_change,
+            lgr.mu_before,
+            lgr.mu_after,
+            lgr.sigma_before,
+            lgr.sigma_after,
+            lgr.food_eaten,
+            lgr.game_created_at
+         FROM leaderboard_game_results lgr
+         JOIN leaderboard_games lg ON lgr.leaderboard_game_id = lg.leaderboard_game_id
+         WHERE lgr.leaderboard_entry_id = $1
+           -- A missing cursor compares as past the newest game rather than
+           -- with `$2 IS NULL OR`, so a generic plan still seeks the index
+           AND (lgr.game_created_at, lgr.leaderboard_game_id)
+               < (COALESCE($2::timestamptz, 'infinity'),
+                  COALESCE($3::uuid, 'ffffffff-ffff-ffff-ffff-ffffffffffff'))
+         ORDER BY lgr.game_created_at DESC, lgr.leaderboard_game_id DESC
+         LIMIT $4"#,
+        leaderboard_entry_id,
+        before_created_at,
+        before_game_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch game history for entry")?;
+
+    Ok(entries)
+}
+
 /// Count total game results for a leaderboard entry
 pub async fn count_game_results_for_entry(
     pool: &PgPool,
@@ -860,44 +1135,96 @@ pub struct LeaderboardStatus {
     pub last_game_created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub games_in_progress: i64,
     pub total_games: i64,
+    /// Entries with enough games to be ranked, for pagination
+    pub ranked_entries: i64,
 }
 
-/// Get leaderboard status (last game, in-progress count, total games)
+/// Get leaderboard status (last game, in-progress and total game counts,
+/// ranked entry count) in one round trip
 pub async fn get_leaderboard_status(
     pool: &PgPool,
     leaderboard_id: Uuid,
 ) -> cja::Result<LeaderboardStatus> {
-    let last_game_created_at = sqlx::query_scalar!(
-        r#"SELECT MAX(created_at) FROM leaderboard_games WHERE leaderboard_id = $1"#,
-        leaderboard_id
+    let row = sqlx::query!(
+        r#"WITH game_counts AS (
+            SELECT
+                MAX(lg.created_at) as last_game_created_at,
+                COUNT(*) FILTER (WHERE g.status != 'finished') as games_in_progress,
+                COUNT(*) as total_games
+            FROM leaderboard_games lg
+            JOIN games g ON lg.game_id = g.game_id
+            WHERE lg.leaderboard_id = $1
+         ),
+         ranked AS (
+            SELECT COUNT(*) as ranked_entries
+            FROM leaderboard_entries
+            WHERE leaderboard_id = $1 AND disabled_at IS NULL AND games_played >= $2
+         )
+         SELECT
+            game_counts.last_game_created_at,
+            game_counts.games_in_progress as "games_in_progress!",
+            game_counts.total_games as "total_games!",
+            ranked.ranked_entries as "ranked_entries!"
+         FROM game_counts, ranked"#,
+        leaderboard_id,
+        MIN_GAMES_FOR_RANKING
     )
     .fetch_one(pool)
+    .timed("leaderboard::status")
     .await
-    .wrap_err("Failed to fetch last game created_at")?;
+    .wrap_err("Failed to fetch leaderboard status")?;
 
-    let games_in_progress = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!" FROM leaderboard_games lg
-         JOIN games g ON lg.game_id = g.game_id
-         WHERE lg.leaderboard_id = $1 AND g.status != 'finished'"#,
-        leaderboard_id
-    )
-    .fetch_one(pool)
-    .await
-    .wrap_err("Failed to count games in progress")?;
+    Ok(LeaderboardStatus {
+        last_game_created_at: row.last_game_created_at,
+        games_in_progress: row.games_in_progress,
+        total_games: row.total_games,
+        ranked_entries: row.ranked_entries,
+    })
+}
 
-    let total_games = sqlx::query_scalar!(
-        r#"SELECT COUNT(*) as "count!" FROM leaderboard_games WHERE leaderboard_id = $1"#,
-        leaderboard_id
+/// A leaderboard game that hasn't finished, for the live games tab
+#[derive(Debug, FromRow)]
+pub struct LiveLeaderboardGame {
+    pub game_id: Uuid,
+    pub status: String,
+    pub board_size: String,
+    pub game_type: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Latest turn played; `None` before the first
+    pub turn: Option<i32>,
+}
+
+/// The leaderboard's unfinished games, the same ones counted as in
+/// progress by [`get_leaderboard_status`]. Running games come first, newest
+/// first within each status.
+pub async fn get_live_games(
+    pool: &PgPool,
+    leaderboard_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<LiveLeaderboardGame>> {
+    let games = sqlx::query_as!(
+        LiveLeaderboardGame,
+        r#"SELECT
+            g.game_id,
+            g.status,
+            g.board_size,
+            g.game_type,
+            g.created_at,
+            (SELECT MAX(t.turn_number) FROM turns t WHERE t.game_id = g.game_id) as turn
+         FROM leaderboard_games lg
+         JOIN games g ON lg.game_id = g.game_id
+         WHERE lg.leaderboard_id = $1
+           AND g.status != 'finished'
+         ORDER BY g.status = 'running' DESC, g.created_at DESC, g.game_id
+         LIMIT $2"#,
+        leaderboard_id,
+        limit
     )
-    .fetch_one(pool)
+    .fetch_all(pool)
     .await
-    .wrap_err("Failed to count total games")?;
+    .wrap_err("Failed to fetch live leaderboard games")?;
 
-    Ok(LeaderboardStatus {
-        last_game_created_at,
-        games_in_progress,
-        total_games,
-    })
+    Ok(games)
 }
 
 /// Activity feed entry for recent leaderboard events
@@ -907,6 +1234,9 @@ pub struct ActivityFeedEntry {
     pub snake_color: String,
     pub owner_login: String,
     pub leaderboard_entry_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    pub game_id: Uuid,
     pub placement: i32,
     pub display_score_change: f64,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -925,6 +1255,9 @@ pub async fn get_activity_feed(
             b.color as snake_color,
             u.github_login as owner_login,
             lgr.leaderboard_entry_id,
+            lg.leaderboard_id,
+            l.name as leaderboard_name,
+            lg.game_id,
             lgr.placement,
             lgr.display_score_change,
             lgr.created_at
@@ -933,6 +1266,7 @@ pub async fn get_activity_feed(
          JOIN battlesnakes b ON le.battlesnake_id = b.battlesnake_id
          JOIN users u ON b.user_id = u.user_id
          JOIN leaderboard_games lg ON lgr.leaderboard_game_id = lg.leaderboard_game_id
+         JOIN leaderboards l ON lg.leaderboard_id = l.leaderboard_id
          WHERE lg.leaderboard_id = $1
          ORDER BY lgr.created_at DESC
          LIMIT $2"#,
@@ -940,12 +1274,57 @@ pub async fn get_activity_feed(
         limit
     )
     .fetch_all(pool)
+    .timed("leaderboard::activity_feed")
     .await
     .wrap_err("Failed to fetch activity feed")?;
 
     Ok(entries)
 }
 
+/// The activity feed for what a user follows: every result of a followed
+/// snake, plus the winners on followed leaderboards
+pub async fn get_followed_activity_feed(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<ActivityFeedEntry>> {
+    let entries = sqlx::query_as!(
+        ActivityFeedEntry,
+        r#"SELECT
+            b.name as snake_name,
+            b.color as snake_color,
+            u.github_login as owner_login,
+            lgr.leaderboard_entry_id,
+            lg.leaderboard_id,
+            l.name as leaderboard_name,
+            lg.game_id,
+            lgr.placement,
+            lgr.display_score_change,
+            lgr.created_at
+         FROM leaderboard_game_results lgr
+         JOIN leaderboard_entries le ON lgr.leaderboard_entry_id = le.leaderboard_entry_id
+         JOIN battlesnakes b ON le.battlesnake_id = b.battlesnake_id
+         JOIN users u ON b.user_id = u.user_id
+         JOIN leaderboard_games lg ON lgr.leaderboard_game_id = lg.leaderboard_game_id
+         JOIN leaderboards l ON lg.leaderboard_id = l.leaderboard_id
+         WHERE le.battlesnake_id IN (SELECT battlesnake_id FROM snake_follows WHERE user_id = $1)
+            OR (
+                lgr.placement = 1
+                AND lg.leaderboard_id IN (SELECT leaderboard_id FROM leaderboard_follows WHERE user_id = $1)
+            )
+         ORDER BY lgr.created_at DESC
+         LIMIT $2"#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .timed("leaderboard::followed_activity_feed")
+    .await
+    .wrap_err("Failed to fetch followed activity feed")?;
+
+    Ok(entries)
+}
+
 /// Summary of a battlesnake's leaderboard participation
 #[derive(Debug, FromRow)]
 pub struct BattlesnakeLeaderboardSummary {
@@ -957,6 +1336,9 @@ pub struct BattlesnakeLeaderboardSummary {
     pub first_place_finishes: i32,
     pub non_first_finishes: i32,
     pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Position on the rating-sorted leaderboard; `None` while paused or
+    /// short of `MIN_GAMES_FOR_RANKING`
+    pub rank: Option<i64>,
 }
 
 /// Get all leaderboard entries for a battlesnake
@@ -974,12 +1356,21 @@ pub async fn get_entries_for_battlesnake(
             le.games_played,
             le.first_place_finishes,
             le.non_first_finishes,
-            le.disabled_at
+            le.disabled_at,
+            CASE WHEN le.disabled_at IS NULL AND le.games_played >= $2 THEN (
+                SELECT COUNT(*) + 1
+                FROM leaderboard_entries other
+                WHERE other.leaderboard_id = le.leaderboard_id
+                  AND other.disabled_at IS NULL
+                  AND other.games_played >= $2
+                  AND other.display_score > le.display_score
+            ) END as rank
          FROM leaderboard_entries le
          JOIN leaderboards l ON le.leaderboard_id = l.leaderboard_id
          WHERE le.battlesnake_id = $1
          ORDER BY le.display_score DESC"#,
-        battlesnake_id
+        battlesnake_id,
+        MIN_GAMES_FOR_RANKING
     )
     .fetch_all(pool)
     .await
@@ -988,6 +1379,97 @@ pub async fn get_entries_for_battlesnake(
     Ok(entries)
 }
 
+/// A snake's place on one leaderboard, for listing many snakes at once
+#[derive(Debug, Clone, FromRow)]
+pub struct SnakeMembership {
+    pub battlesnake_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    /// As in [`BattlesnakeLeaderboardSummary::rank`]
+    pub rank: Option<i64>,
+}
+
+/// The active leaderboards each of `battlesnake_ids` is entered on
+pub async fn get_memberships_for_battlesnakes(
+    pool: &PgPool,
+    battlesnake_ids: &[Uuid],
+) -> cja::Result<Vec<SnakeMembership>> {
+    let memberships = sqlx::query_as!(
+        SnakeMembership,
+        r#"SELECT
+            le.battlesnake_id,
+            le.leaderboard_id,
+            l.name as leaderboard_name,
+            CASE WHEN le.disabled_at IS NULL AND le.games_played >= $2 THEN (
+                SELECT COUNT(*) + 1
+                FROM leaderboard_entries other
+                WHERE other.leaderboard_id = le.leaderboard_id
+                  AND other.disabled_at IS NULL
+                  AND other.games_played >= $2
+                  AND other.display_score > le.display_score
+            ) END as rank
+         FROM leaderboard_entries le
+         JOIN leaderboards l ON le.leaderboard_id = l.leaderboard_id
+         WHERE le.battlesnake_id = ANY($1)
+           AND l.disabled_at IS NULL
+         ORDER BY l.name, le.leaderboard_id"#,
+        battlesnake_ids,
+        MIN_GAMES_FOR_RANKING
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch leaderboard memberships")?;
+
+    Ok(memberships)
+}
+
+/// A user's leaderboard entry, with the snake and leaderboard it belongs to
+#[derive(Debug, FromRow, Serialize)]
+pub struct UserLeaderboardEntry {
+    pub leaderboard_entry_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    pub battlesnake_id: Uuid,
+    pub battlesnake_name: String,
+    pub display_score: f64,
+    pub games_played: i32,
+    pub first_place_finishes: i32,
+    pub non_first_finishes: i32,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Get every leaderboard entry for a user's battlesnakes, across all leaderboards
+pub async fn get_entries_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> cja::Result<Vec<UserLeaderboardEntry>> {
+    let entries = sqlx::query_as!(
+        UserLeaderboardEntry,
+        r#"SELECT
+            le.leaderboard_entry_id,
+            le.leaderboard_id,
+            l.name as leaderboard_name,
+            le.battlesnake_id,
+            b.name as battlesnake_name,
+            le.display_score,
+            le.games_played,
+            le.first_place_finishes,
+            le.non_first_finishes,
+            le.disabled_at
+         FROM leaderboard_entries le
+         JOIN leaderboards l ON le.leaderboard_id = l.leaderboard_id
+         JOIN battlesnakes b ON le.battlesnake_id = b.battlesnake_id
+         WHERE b.user_id = $1
+         ORDER BY l.name ASC, le.display_score DESC"#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch leaderboard entries for user")?;
+
+    Ok(entries)
+}
+
 /// Get competition rank for a specific entry (count of entries with higher score + 1)
 pub async fn get_rank_for_entry(
     pool: &PgPool,
@@ -1011,12 +1493,128 @@ pub async fn get_rank_for_entry(
         display_score
     )
     .fetch_one(pool)
+    .timed("leaderboard::rank_for_entry")
     .await
     .wrap_err("Failed to get rank for entry")?;
 
     Ok(Some(rank))
 }
 
+/// IDs of the ranked entries placed `max_rank` or better (ties share a
+/// rank, so there can be more than `max_rank` of them)
+pub async fn get_entry_ids_within_rank<'e, E>(
+    executor: E,
+    leaderboard_id: Uuid,
+    max_rank: i64,
+) -> cja::Result<Vec<Uuid>>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    let ids = sqlx::query_scalar!(
+        r#"SELECT le.leaderboard_entry_id
+         FROM leaderboard_entries le
+         WHERE le.leaderboard_id = $1
+           AND le.disabled_at IS NULL
+           AND le.games_played >= $2
+           AND (
+               SELECT COUNT(*) + 1
+               FROM leaderboard_entries other
+               WHERE other.leaderboard_id = le.leaderboard_id
+                 AND other.disabled_at IS NULL
+                 AND other.games_played >= $2
+                 AND other.display_score > le.display_score
+           ) <= $3"#,
+        leaderboard_id,
+        MIN_GAMES_FOR_RANKING,
+        max_rank
+    )
+    .fetch_all(executor)
+    .await
+    .wrap_err("Failed to fetch top ranked entries")?;
+
+    Ok(ids)
+}
+
+/// Where an entry stands on its leaderboard at a moment
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct EntryStanding {
+    pub leaderboard_entry_id: Uuid,
+    pub display_score: f64,
+    /// `None` while paused or short of `MIN_GAMES_FOR_RANKING`
+    pub rank: Option<i64>,
+}
+
+/// Current rating and rank of each of the given entries
+pub async fn get_entry_standings<'e, E>(
+    executor: E,
+    leaderboard_entry_ids: &[Uuid],
+) -> cja::Result<Vec<EntryStanding>>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    let standings = sqlx::query_as!(
+        EntryStanding,
+        r#"SELECT
+            le.leaderboard_entry_id,
+            le.display_score,
+            CASE WHEN le.disabled_at IS NULL AND le.games_played >= $2 THEN (
+                SELECT COUNT(*) + 1
+                FROM leaderboard_entries other
+                WHERE other.leaderboard_id = le.leaderboard_id
+                  AND other.disabled_at IS NULL
+                  AND other.games_played >= $2
+                  AND other.display_score > le.display_score
+            ) END as rank
+         FROM leaderboard_entries le
+         WHERE le.leaderboard_entry_id = ANY($1)"#,
+        leaderboard_entry_ids,
+        MIN_GAMES_FOR_RANKING
+    )
+    .fetch_all(executor)
+    .await
+    .wrap_err("Failed to fetch leaderboard entry standings")?;
+
+    Ok(standings)
+}
+
+/// An entry's snake, owner and leaderboard, for telling the owner about it
+#[derive(Debug, FromRow)]
+pub struct EntryOwner {
+    pub leaderboard_entry_id: Uuid,
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    pub battlesnake_id: Uuid,
+    pub battlesnake_name: String,
+    pub user_id: Uuid,
+}
+
+/// Look up the owners of the given entries
+pub async fn get_entry_owners(
+    pool: &PgPool,
+    leaderboard_entry_ids: &[Uuid],
+) -> cja::Result<Vec<EntryOwner>> {
+    let owners = sqlx::query_as!(
+        EntryOwner,
+        r#"SELECT
+            le.leaderboard_entry_id,
+            le.leaderboard_id,
+            l.name as leaderboard_name,
+            b.battlesnake_id,
+            b.name as battlesnake_name,
+            b.user_id
+         FROM leaderboard_entries le
+         JOIN leaderboards l ON le.leaderboard_id = l.leaderboard_id
+         JOIN battlesnakes b ON le.battlesnake_id = b.battlesnake_id
+         WHERE le.leaderboard_entry_id = ANY($1)"#,
+        leaderboard_entry_ids
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch leaderboard entry owners")?;
+
+    Ok(owners)
+}
+
 /// Top food-eater on a leaderboard (display row)
 #[derive(Debug, FromRow)]
 pub struct TopEater {
@@ -1054,6 +1652,7 @@ pub async fn get_top_eaters(
         limit
     )
     .fetch_all(pool)
+    .timed("leaderboard::top_eaters")
     .await
     .wrap_err("Failed to fetch top eaters")?;
 
@@ -1096,3 +1695,86 @@ pub async fn load_home_feed(pool: &sqlx::PgPool) -> cja::Result<HomeFeed> {
         top_entries,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_leaderboard_status_counts_games_and_ranked_entries(
+        pool: PgPool,
+    ) -> cja::Result<()> {
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Season') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let empty = get_leaderboard_status(&pool, leaderboard_id).await?;
+        assert_eq!(empty.last_game_created_at, None);
+        assert_eq!(
+            (
+                empty.games_in_progress,
+                empty.total_games,
+                empty.ranked_entries
+            ),
+            (0, 0, 0)
+        );
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (4242, 'status-user', 'token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        // One ranked entry, one short of the game minimum, one disabled
+        for (name, games_played, disabled) in [
+            ("ranked", MIN_GAMES_FOR_RANKING, false),
+            ("placement", MIN_GAMES_FOR_RANKING - 1, false),
+            ("disabled", MIN_GAMES_FOR_RANKING, true),
+        ] {
+            sqlx::query(
+                "WITH snake AS (
+                     INSERT INTO battlesnakes (user_id, name, url)
+                     VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id
+                 )
+                 INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id, games_played, disabled_at)
+                 SELECT $3, battlesnake_id, $4, CASE WHEN $5 THEN NOW() END FROM snake",
+            )
+            .bind(user_id)
+            .bind(name)
+            .bind(leaderboard_id)
+            .bind(games_played)
+            .bind(disabled)
+            .execute(&pool)
+            .await?;
+        }
+        for status in ["finished", "finished", "running"] {
+            sqlx::query(
+                "WITH game AS (
+                     INSERT INTO games (board_size, game_type, status)
+                     VALUES ('11x11', 'Standard', $2) RETURNING game_id
+                 )
+                 INSERT INTO leaderboard_games (leaderboard_id, game_id)
+                 SELECT $1, game_id FROM game",
+            )
+            .bind(leaderboard_id)
+            .bind(status)
+            .execute(&pool)
+            .await?;
+        }
+
+        let status = get_leaderboard_status(&pool, leaderboard_id).await?;
+        assert!(status.last_game_created_at.is_some());
+        assert_eq!(status.games_in_progress, 1);
+        assert_eq!(status.total_games, 3);
+        assert_eq!(status.ranked_entries, 1);
+
+        let live = get_live_games(&pool, leaderboard_id, 10).await?;
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].status, "running");
+        assert_eq!(live[0].turn, None);
+
+        Ok(())
+    }
+}
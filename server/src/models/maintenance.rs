@@ -0,0 +1,101 @@
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Shown in the banner when an admin enables maintenance without a message
+pub const DEFAULT_MESSAGE: &str =
+    "Arena is down for maintenance. New games are paused; games in progress will finish.";
+
+/// The site-wide maintenance flag
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    pub message: String,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MaintenanceState {
+    /// The banner text while maintenance is on, `None` otherwise
+    pub fn banner(&self) -> Option<&str> {
+        if !self.enabled {
+            None
+        } else if self.message.trim().is_empty() {
+            Some(DEFAULT_MESSAGE)
+        } else {
+            Some(&self.message)
+        }
+    }
+}
+
+pub async fn get(pool: &PgPool) -> cja::Result<MaintenanceState> {
+    let row = sqlx::query!("SELECT enabled, message, updated_at FROM maintenance_mode")
+        .fetch_optional(pool)
+        .await
+        .wrap_err("Failed to fetch maintenance mode")?;
+
+    Ok(row
+        .map(|row| MaintenanceState {
+            enabled: row.enabled,
+            message: row.message,
+            updated_at: Some(row.updated_at),
+        })
+        .unwrap_or_default())
+}
+
+pub async fn set(
+    pool: &PgPool,
+    enabled: bool,
+    message: &str,
+    updated_by: Uuid,
+) -> cja::Result<MaintenanceState> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO maintenance_mode (id, enabled, message, updated_by, updated_at)
+        VALUES (TRUE, $1, $2, $3, NOW())
+        ON CONFLICT (id) DO UPDATE SET
+            enabled = EXCLUDED.enabled,
+            message = EXCLUDED.message,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = EXCLUDED.updated_at
+        RETURNING enabled, message, updated_at
+        "#,
+        enabled,
+        message,
+        updated_by,
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to update maintenance mode")?;
+
+    Ok(MaintenanceState {
+        enabled: row.enabled,
+        message: row.message,
+        updated_at: Some(row.updated_at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_set_and_get_maintenance(pool: PgPool) -> cja::Result<()> {
+        assert!(!get(&pool).await?.enabled);
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'admin', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        set(&pool, true, "", user_id).await?;
+
+        let state = get(&pool).await?;
+        assert!(state.enabled);
+        assert_eq!(state.banner(), Some(DEFAULT_MESSAGE));
+
+        set(&pool, false, "", user_id).await?;
+        assert_eq!(get(&pool).await?.banner(), None);
+        Ok(())
+    }
+}
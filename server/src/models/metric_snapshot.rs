@@ -0,0 +1,205 @@
+//! Persisted admin metric snapshots, captured every few minutes by
+//! [`crate::jobs::MetricSnapshotJob`] and exported through
+//! `/api/v1/admin/stats/history`.
+
+use color_eyre::eyre::Context as _;
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+/// Days of snapshots kept; older ones are purged as new ones are captured
+pub const RETENTION_DAYS: i64 = 90;
+
+/// One point-in-time copy of the dashboard's key numbers
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MetricSnapshot {
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+    pub jobs_ready: i64,
+    pub jobs_running: i64,
+    pub jobs_scheduled: i64,
+    pub games_waiting: i64,
+    pub games_running: i64,
+    pub games_created_last_hour: i64,
+    pub games_finished_last_hour: i64,
+    pub moves_last_hour: i64,
+    pub move_timeout_rate: f64,
+    pub move_p50_ms: Option<f64>,
+    pub move_p99_ms: Option<f64>,
+    pub db_pool_utilization: f64,
+    pub db_acquire_wait_ms: f64,
+    pub tokio_alive_tasks: i64,
+    pub rss_bytes: Option<i64>,
+}
+
+impl MetricSnapshot {
+    pub const CSV_HEADER: &'static str = "captured_at,jobs_ready,jobs_running,jobs_scheduled,\
+        games_waiting,games_running,games_created_last_hour,games_finished_last_hour,\
+        moves_last_hour,move_timeout_rate,move_p50_ms,move_p99_ms,db_pool_utilization,\
+        db_acquire_wait_ms,tokio_alive_tasks,rss_bytes";
+
+    /// One CSV row matching [`Self::CSV_HEADER`]; missing values are empty.
+    /// Every field is a number or timestamp, so nothing needs quoting.
+    pub fn csv_row(&self) -> String {
+        fn opt<T: ToString>(value: Option<T>) -> String {
+            value.map(|v| v.to_string()).unwrap_or_default()
+        }
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.captured_at.to_rfc3339(),
+            self.jobs_ready,
+            self.jobs_running,
+            self.jobs_scheduled,
+            self.games_waiting,
+            self.games_running,
+            self.games_created_last_hour,
+            self.games_finished_last_hour,
+            self.moves_last_hour,
+            self.move_timeout_rate,
+            opt(self.move_p50_ms),
+            opt(self.move_p99_ms),
+            self.db_pool_utilization,
+            self.db_acquire_wait_ms,
+            self.tokio_alive_tasks,
+            opt(self.rss_bytes),
+        )
+    }
+}
+
+pub async fn record(pool: &PgPool, snapshot: &MetricSnapshot) -> cja::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO admin_metric_snapshots (
+            captured_at, jobs_ready, jobs_running, jobs_scheduled, games_waiting, games_running,
+            games_created_last_hour, games_finished_last_hour, moves_last_hour,
+            move_timeout_rate, move_p50_ms, move_p99_ms, db_pool_utilization,
+            db_acquire_wait_ms, tokio_alive_tasks, rss_bytes
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        "#,
+        snapshot.captured_at,
+        snapshot.jobs_ready,
+        snapshot.jobs_running,
+        snapshot.jobs_scheduled,
+        snapshot.games_waiting,
+        snapshot.games_running,
+        snapshot.games_created_last_hour,
+        snapshot.games_finished_last_hour,
+        snapshot.moves_last_hour,
+        snapshot.move_timeout_rate,
+        snapshot.move_p50_ms,
+        snapshot.move_p99_ms,
+        snapshot.db_pool_utilization,
+        snapshot.db_acquire_wait_ms,
+        snapshot.tokio_alive_tasks,
+        snapshot.rss_bytes,
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to record metric snapshot")?;
+
+    Ok(())
+}
+
+/// Snapshots captured in `[from, to)`, oldest first
+pub async fn list_range(
+    pool: &PgPool,
+    from: chrono::DateTime<chrono::Utc>,
+    to: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> cja::Result<Vec<MetricSnapshot>> {
+    sqlx::query_as!(
+        MetricSnapshot,
+        r#"SELECT captured_at, jobs_ready, jobs_running, jobs_scheduled, games_waiting,
+                games_running, games_created_last_hour, games_finished_last_hour,
+                moves_last_hour, move_timeout_rate, move_p50_ms, move_p99_ms,
+                db_pool_utilization, db_acquire_wait_ms, tokio_alive_tasks, rss_bytes
+         FROM admin_metric_snapshots
+         WHERE captured_at >= $1 AND captured_at < $2
+         ORDER BY captured_at ASC
+         LIMIT $3"#,
+        from,
+        to,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list metric snapshots")
+}
+
+/// Delete snapshots older than `cutoff`, returning how many went
+pub async fn purge_before(
+    pool: &PgPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        "DELETE FROM admin_metric_snapshots WHERE captured_at < $1",
+        cutoff
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to purge metric snapshots")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::SubsecRound as _;
+
+    fn snapshot(captured_at: chrono::DateTime<chrono::Utc>) -> MetricSnapshot {
+        MetricSnapshot {
+            captured_at,
+            jobs_ready: 3,
+            jobs_running: 1,
+            jobs_scheduled: 0,
+            games_waiting: 2,
+            games_running: 4,
+            games_created_last_hour: 10,
+            games_finished_last_hour: 8,
+            moves_last_hour: 500,
+            move_timeout_rate: 0.02,
+            move_p50_ms: Some(40.0),
+            move_p99_ms: None,
+            db_pool_utilization: 0.5,
+            db_acquire_wait_ms: 1.5,
+            tokio_alive_tasks: 12,
+            rss_bytes: None,
+        }
+    }
+
+    #[test]
+    fn csv_row_matches_header() {
+        let row = snapshot(chrono::Utc::now()).csv_row();
+        assert_eq!(
+            row.split(',').count(),
+            MetricSnapshot::CSV_HEADER.split(',').count()
+        );
+        assert!(row.ends_with(",12,"));
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn snapshots_are_listed_by_range_and_purged(pool: PgPool) -> cja::Result<()> {
+        let now = chrono::Utc::now().trunc_subsecs(0);
+        for hours_ago in [0, 2, 48] {
+            record(&pool, &snapshot(now - chrono::Duration::hours(hours_ago))).await?;
+        }
+
+        let recent = list_range(&pool, now - chrono::Duration::hours(24), now, 100).await?;
+        assert_eq!(recent, vec![snapshot(now - chrono::Duration::hours(2))]);
+
+        let purged = purge_before(&pool, now - chrono::Duration::hours(24)).await?;
+        assert_eq!(purged, 1);
+        let all = list_range(
+            &pool,
+            now - chrono::Duration::days(7),
+            now + chrono::Duration::hours(1),
+            100,
+        )
+        .await?;
+        assert_eq!(all.len(), 2);
+        assert!(all[0].captured_at < all[1].captured_at);
+
+        Ok(())
+    }
+}
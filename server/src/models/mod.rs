@@ -1,16 +1,38 @@
+pub mod account_deletion;
+pub mod admin_audit;
 pub mod api_token;
 pub mod battlesnake;
+pub mod battlesnake_transfer;
+pub mod battlesnake_version;
+pub mod challenge;
 pub mod claim_email_token;
+pub mod consistency;
+pub mod dashboard;
+pub mod data_export;
+pub mod discord_webhook;
 pub mod email_log;
 pub mod flow;
+pub mod follow;
 pub mod game;
 pub mod game_battlesnake;
+pub mod game_frame_archive;
+pub mod head_to_head;
+pub mod human_player;
 pub mod imported_account;
+pub mod job;
 pub mod leaderboard;
+pub mod maintenance;
+pub mod metric_snapshot;
+pub mod moderation;
+pub mod notification;
+pub mod partition;
 pub mod rate_limit;
 pub mod saved_game;
 pub mod session;
+pub mod setting;
+pub mod snake_error;
 pub mod snake_health_status;
+pub mod snake_url_verification;
 pub mod tag;
 pub mod tournament;
 pub mod turn;
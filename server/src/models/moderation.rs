@@ -0,0 +1,467 @@
+//! Screening for the strings players choose that everyone else sees: snake
+//! names and in-game shouts.
+//!
+//! Admins keep a word list. A new or renamed snake whose name hits it is
+//! refused; a shout that hits it is blanked before the frame is stored and
+//! queued for review. Adding a term also queues the existing names it
+//! matches, so an admin can rename them.
+
+use color_eyre::eyre::Context as _;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, PgPool, Postgres, Type};
+use uuid::Uuid;
+
+/// Pending flags shown on the review page
+pub const QUEUE_LIMIT: i64 = 200;
+
+/// What a flag is about
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FlagKind {
+    Name,
+    Shout,
+}
+
+impl FlagKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlagKind::Name => "name",
+            FlagKind::Shout => "shout",
+        }
+    }
+}
+
+/// Where a flag stands. `Approved` means the match was a false positive;
+/// `Removed` means the content is gone (a name was reset, or a shout stays
+/// blanked).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FlagStatus {
+    Pending,
+    Approved,
+    Removed,
+}
+
+impl FlagStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlagStatus::Pending => "pending",
+            FlagStatus::Approved => "approved",
+            FlagStatus::Removed => "removed",
+        }
+    }
+}
+
+/// Lowercase `text`, undo common digit/symbol substitutions, and reduce
+/// everything else that isn't a letter or digit to single spaces, so
+/// `"B4d_W0rd!!"` and `"bad word"` compare equal.
+pub fn normalize(text: &str) -> String {
+    let mapped: String = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            '0' => 'o',
+            '1' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    mapped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The word list, loaded once and checked many times
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Normalized terms
+    terms: Vec<String>,
+}
+
+impl WordList {
+    pub fn new(terms: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            terms: terms
+                .into_iter()
+                .map(|t| normalize(&t))
+                .filter(|t| !t.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The first term `text` contains as whole words, if any. Matching whole
+    /// words keeps a term from flagging every longer word it's part of.
+    pub fn find(&self, text: &str) -> Option<&str> {
+        if self.terms.is_empty() {
+            return None;
+        }
+        let padded = format!(" {} ", normalize(text));
+        self.terms
+            .iter()
+            .find(|term| padded.contains(&format!(" {term} ")))
+            .map(String::as_str)
+    }
+}
+
+pub async fn load_word_list(pool: &PgPool) -> cja::Result<WordList> {
+    let terms = sqlx::query_scalar!("SELECT term FROM moderation_terms")
+        .fetch_all(pool)
+        .await
+        .wrap_err("Failed to fetch moderation terms")?;
+    Ok(WordList::new(terms))
+}
+
+/// A word list entry, with who added it
+#[derive(Debug, Clone)]
+pub struct ModerationTerm {
+    pub moderation_term_id: Uuid,
+    pub term: String,
+    pub created_by_login: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub async fn list_terms(pool: &PgPool) -> cja::Result<Vec<ModerationTerm>> {
+    sqlx::query_as!(
+        ModerationTerm,
+        r#"
+        SELECT
+            t.moderation_term_id,
+            t.term,
+            u.github_login as "created_by_login?",
+            t.created_at
+        FROM moderation_terms t
+        LEFT JOIN users u ON u.user_id = t.created_by_user_id
+        ORDER BY t.term
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list moderation terms")
+}
+
+/// Add `raw` to the word list, normalized. Returns the stored term, or
+/// `Ok(Err(message))` for user-facing refusals.
+pub async fn add_term(
+    pool: &PgPool,
+    raw: &str,
+    created_by_user_id: Uuid,
+) -> cja::Result<Result<String, String>> {
+    let term = normalize(raw);
+    if term.is_empty() {
+        return Ok(Err("Enter a word or phrase with letters in it".to_string()));
+    }
+
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO moderation_terms (term, created_by_user_id)
+        VALUES ($1, $2)
+        ON CONFLICT (term) DO NOTHING
+        RETURNING term
+        "#,
+        term,
+        created_by_user_id,
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to add moderation term")?;
+
+    Ok(inserted.ok_or_else(|| format!("\"{term}\" is already on the list")))
+}
+
+/// Take a term off the list. Returns the term, or `None` if it was already
+/// gone. Flags it raised stay in the queue.
+pub async fn remove_term(pool: &PgPool, moderation_term_id: Uuid) -> cja::Result<Option<String>> {
+    sqlx::query_scalar!(
+        "DELETE FROM moderation_terms WHERE moderation_term_id = $1 RETURNING term",
+        moderation_term_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to remove moderation term")
+}
+
+/// Queue `content` for review. A pending flag for the same snake and content
+/// absorbs the new one; returns whether a flag was added.
+pub async fn flag<'e, E>(
+    executor: E,
+    battlesnake_id: Uuid,
+    kind: FlagKind,
+    content: &str,
+    matched_term: &str,
+    game_id: Option<Uuid>,
+) -> cja::Result<bool>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO moderation_flags (battlesnake_id, kind, content, matched_term, game_id)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (battlesnake_id, kind, content) WHERE status = 'pending' DO NOTHING
+        "#,
+        battlesnake_id,
+        kind.as_str(),
+        content,
+        matched_term,
+        game_id,
+    )
+    .execute(executor)
+    .await
+    .wrap_err("Failed to flag content for moderation")?;
+
+    Ok(inserted.rows_affected() > 0)
+}
+
+/// Queue every existing snake name that `words` matches, skipping names an
+/// admin already approved. Returns how many were queued.
+pub async fn flag_existing_names(pool: &PgPool, words: &WordList) -> cja::Result<usize> {
+    if words.is_empty() {
+        return Ok(0);
+    }
+
+    let snakes = sqlx::query!(
+        r#"
+        SELECT b.battlesnake_id, b.name
+        FROM battlesnakes b
+        WHERE NOT EXISTS (
+            SELECT 1 FROM moderation_flags f
+            WHERE f.battlesnake_id = b.battlesnake_id
+              AND f.kind = 'name'
+              AND f.content = b.name
+              AND f.status = 'approved'
+        )
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch snake names to screen")?;
+
+    let mut queued = 0;
+    for snake in snakes {
+        if let Some(term) = words.find(&snake.name)
+            && flag(
+                pool,
+                snake.battlesnake_id,
+                FlagKind::Name,
+                &snake.name,
+                term,
+                None,
+            )
+            .await?
+        {
+            queued += 1;
+        }
+    }
+    Ok(queued)
+}
+
+/// A flag with the names needed to review it
+#[derive(Debug, Clone)]
+pub struct FlagListing {
+    pub moderation_flag_id: Uuid,
+    pub battlesnake_id: Uuid,
+    pub snake_name: String,
+    pub owner_id: Uuid,
+    pub owner_login: String,
+    pub kind: FlagKind,
+    pub content: String,
+    pub matched_term: String,
+    pub game_id: Option<Uuid>,
+    pub status: FlagStatus,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The review queue, oldest first
+pub async fn list_pending_flags(pool: &PgPool, limit: i64) -> cja::Result<Vec<FlagListing>> {
+    sqlx::query_as!(
+        FlagListing,
+        r#"
+        SELECT
+            f.moderation_flag_id,
+            f.battlesnake_id,
+            b.name as snake_name,
+            u.user_id as owner_id,
+            u.github_login as owner_login,
+            f.kind as "kind: FlagKind",
+            f.content,
+            f.matched_term,
+            f.game_id,
+            f.status as "status: FlagStatus",
+            f.created_at
+        FROM moderation_flags f
+        JOIN battlesnakes b ON b.battlesnake_id = f.battlesnake_id
+        JOIN users u ON u.user_id = b.user_id
+        WHERE f.status = 'pending'
+        ORDER BY f.created_at
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list moderation flags")
+}
+
+pub async fn get_flag_by_id(
+    pool: &PgPool,
+    moderation_flag_id: Uuid,
+) -> cja::Result<Option<FlagListing>> {
+    sqlx::query_as!(
+        FlagListing,
+        r#"
+        SELECT
+            f.moderation_flag_id,
+            f.battlesnake_id,
+            b.name as snake_name,
+            u.user_id as owner_id,
+            u.github_login as owner_login,
+            f.kind as "kind: FlagKind",
+            f.content,
+            f.matched_term,
+            f.game_id,
+            f.status as "status: FlagStatus",
+            f.created_at
+        FROM moderation_flags f
+        JOIN battlesnakes b ON b.battlesnake_id = f.battlesnake_id
+        JOIN users u ON u.user_id = b.user_id
+        WHERE f.moderation_flag_id = $1
+        "#,
+        moderation_flag_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch moderation flag")
+}
+
+/// Close a pending flag. Removing a name flag also resets the snake's name,
+/// if it still has the flagged one. Returns false if the flag was already
+/// reviewed.
+pub async fn resolve_flag(
+    pool: &PgPool,
+    moderation_flag_id: Uuid,
+    status: FlagStatus,
+    reviewed_by_user_id: Uuid,
+) -> cja::Result<bool> {
+    let mut tx = pool.begin().await.wrap_err("Failed to begin transaction")?;
+
+    let flag = sqlx::query!(
+        r#"
+        UPDATE moderation_flags
+        SET status = $2, reviewed_by_user_id = $3, reviewed_at = NOW()
+        WHERE moderation_flag_id = $1 AND status = 'pending'
+        RETURNING battlesnake_id, kind as "kind: FlagKind", content
+        "#,
+        moderation_flag_id,
+        status.as_str(),
+        reviewed_by_user_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .wrap_err("Failed to resolve moderation flag")?;
+    let Some(flag) = flag else {
+        return Ok(false);
+    };
+
+    if status == FlagStatus::Removed && flag.kind == FlagKind::Name {
+        sqlx::query!(
+            r#"
+            UPDATE battlesnakes
+            SET name = 'Snake ' || left(battlesnake_id::text, 8)
+            WHERE battlesnake_id = $1 AND name = $2
+            "#,
+            flag.battlesnake_id,
+            flag.content,
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to reset flagged snake name")?;
+    }
+
+    tx.commit().await.wrap_err("Failed to commit transaction")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::battlesnake::{self, CreateBattlesnake, Visibility};
+
+    #[test]
+    fn test_word_list_matches_whole_normalized_words() {
+        let words = WordList::new(["Bad Word".to_string(), "rude".to_string()]);
+
+        assert_eq!(words.find("B4d_W0rd Slither"), Some("bad word"));
+        assert_eq!(words.find("so RUDE!!"), Some("rude"));
+        assert_eq!(words.find("Prudence"), None);
+        assert_eq!(WordList::default().find("rude"), None);
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_flagged_names_can_be_reset(pool: PgPool) -> cja::Result<()> {
+        let owner: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (9501, 'mod-owner', 'token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let snake = battlesnake::create_battlesnake(
+            &pool,
+            owner,
+            CreateBattlesnake {
+                name: "Rude Snake".to_string(),
+                url: "https://example.com/snake".to_string(),
+                visibility: Visibility::Public,
+            },
+        )
+        .await?;
+
+        assert_eq!(
+            add_term(&pool, "RUDE", owner).await?,
+            Ok("rude".to_string())
+        );
+        assert!(add_term(&pool, "rude", owner).await?.is_err());
+
+        // New names that hit the list are refused
+        let refused = battlesnake::create_battlesnake(
+            &pool,
+            owner,
+            CreateBattlesnake {
+                name: "Very rude".to_string(),
+                url: "https://example.com/other".to_string(),
+                visibility: Visibility::Public,
+            },
+        )
+        .await;
+        assert!(refused.is_err_and(|e| e.to_string() == battlesnake::NAME_NOT_ALLOWED));
+
+        // The name from before the term was added is queued, once
+        let words = load_word_list(&pool).await?;
+        assert_eq!(flag_existing_names(&pool, &words).await?, 1);
+        assert_eq!(flag_existing_names(&pool, &words).await?, 0);
+
+        let queue = list_pending_flags(&pool, QUEUE_LIMIT).await?;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].kind, FlagKind::Name);
+        assert_eq!(queue[0].matched_term, "rude");
+
+        let flag_id = queue[0].moderation_flag_id;
+        assert!(resolve_flag(&pool, flag_id, FlagStatus::Removed, owner).await?);
+        assert!(!resolve_flag(&pool, flag_id, FlagStatus::Approved, owner).await?);
+
+        let renamed = battlesnake::get_battlesnake_by_id(&pool, snake.battlesnake_id)
+            .await?
+            .unwrap();
+        assert!(renamed.name.starts_with("Snake "));
+        assert!(list_pending_flags(&pool, QUEUE_LIMIT).await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+//! Notifications about a user's snakes (rank milestones, timeout streaks)
+//! and each user's choice of how to receive them.
+
+use color_eyre::eyre::Context as _;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Type};
+use uuid::Uuid;
+
+/// What a notification is about
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A snake moved into a leaderboard's top ten
+    TopTenEntered,
+    /// A snake fell out of a leaderboard's top ten
+    TopTenLeft,
+    /// A snake timed out in several finished games in a row
+    TimeoutStreak,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::TopTenEntered => "top_ten_entered",
+            NotificationKind::TopTenLeft => "top_ten_left",
+            NotificationKind::TimeoutStreak => "timeout_streak",
+        }
+    }
+}
+
+/// How a user wants to hear about each group of notifications. Users who
+/// never saved preferences get [`NotificationPreferences::default`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationPreferences {
+    pub rank_milestones_in_app: bool,
+    pub rank_milestones_email: bool,
+    pub timeout_streaks_in_app: bool,
+    pub timeout_streaks_email: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            rank_milestones_in_app: true,
+            rank_milestones_email: false,
+            timeout_streaks_in_app: true,
+            timeout_streaks_email: false,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// `(in_app, email)` for a kind of notification
+    pub fn channels(&self, kind: NotificationKind) -> (bool, bool) {
+        match kind {
+            NotificationKind::TopTenEntered | NotificationKind::TopTenLeft => {
+                (self.rank_milestones_in_app, self.rank_milestones_email)
+            }
+            NotificationKind::TimeoutStreak => {
+                (self.timeout_streaks_in_app, self.timeout_streaks_email)
+            }
+        }
+    }
+}
+
+/// A notification shown in the user's inbox
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub notification_id: Uuid,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub link: Option<String>,
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The user's saved preferences, or the defaults if they never saved any
+pub async fn get_preferences(pool: &PgPool, user_id: Uuid) -> cja::Result<NotificationPreferences> {
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        r#"
+        SELECT rank_milestones_in_app, rank_milestones_email,
+               timeout_streaks_in_app, timeout_streaks_email
+        FROM notification_preferences
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch notification preferences")?;
+
+    Ok(preferences.unwrap_or_default())
+}
+
+pub async fn update_preferences(
+    pool: &PgPool,
+    user_id: Uuid,
+    preferences: &NotificationPreferences,
+) -> cja::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_preferences (
+            user_id, rank_milestones_in_app, rank_milestones_email,
+            timeout_streaks_in_app, timeout_streaks_email
+        )
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id) DO UPDATE SET
+            rank_milestones_in_app = EXCLUDED.rank_milestones_in_app,
+            rank_milestones_email = EXCLUDED.rank_milestones_email,
+            timeout_streaks_in_app = EXCLUDED.timeout_streaks_in_app,
+            timeout_streaks_email = EXCLUDED.timeout_streaks_email
+        "#,
+        user_id,
+        preferences.rank_milestones_in_app,
+        preferences.rank_milestones_email,
+        preferences.timeout_streaks_in_app,
+        preferences.timeout_streaks_email
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to update notification preferences")?;
+
+    Ok(())
+}
+
+/// A notification to record for a user
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub link: Option<String>,
+    /// Identifies the event, so recording it again is a no-op
+    pub dedup_key: String,
+}
+
+/// Record a notification, showing it in the inbox if `in_app`. Returns
+/// `false` if the same event was already recorded for the user.
+pub async fn record(
+    pool: &PgPool,
+    notification: &NewNotification,
+    in_app: bool,
+) -> cja::Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO notifications (user_id, kind, message, link, in_app, dedup_key)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (user_id, dedup_key) DO NOTHING
+        "#,
+        notification.user_id,
+        notification.kind.as_str(),
+        notification.message,
+        notification.link,
+        in_app,
+        notification.dedup_key
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to record notification")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The user's most recent in-app notifications, newest first
+pub async fn list_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<Notification>> {
+    let notifications = sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT notification_id, kind as "kind: NotificationKind", message, link, read_at, created_at
+        FROM notifications
+        WHERE user_id = $1 AND in_app
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list notifications")?;
+
+    Ok(notifications)
+}
+
+/// Mark all of the user's inbox notifications read. Returns how many were
+/// unread.
+pub async fn mark_all_read(pool: &PgPool, user_id: Uuid) -> cja::Result<u64> {
+    let result = sqlx::query!(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND in_app AND read_at IS NULL",
+        user_id
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to mark notifications read")?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_record_dedups_and_hides_email_only(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        assert_eq!(
+            get_preferences(&pool, user_id).await?,
+            NotificationPreferences::default()
+        );
+        let preferences = NotificationPreferences {
+            timeout_streaks_in_app: false,
+            timeout_streaks_email: true,
+            ..Default::default()
+        };
+        update_preferences(&pool, user_id, &preferences).await?;
+        assert_eq!(get_preferences(&pool, user_id).await?, preferences);
+
+        let entered = NewNotification {
+            user_id,
+            kind: NotificationKind::TopTenEntered,
+            message: "snake entered the top 10".to_string(),
+            link: None,
+            dedup_key: "top_ten_entered:1".to_string(),
+        };
+        assert!(record(&pool, &entered, true).await?);
+        assert!(!record(&pool, &entered, true).await?);
+
+        let streak = NewNotification {
+            kind: NotificationKind::TimeoutStreak,
+            dedup_key: "timeout_streak:1".to_string(),
+            ..entered.clone()
+        };
+        assert!(record(&pool, &streak, false).await?);
+
+        let inbox = list_for_user(&pool, user_id, 10).await?;
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].kind, NotificationKind::TopTenEntered);
+        assert!(inbox[0].read_at.is_none());
+
+        assert_eq!(mark_all_read(&pool, user_id).await?, 1);
+        assert!(
+            list_for_user(&pool, user_id, 10).await?[0]
+                .read_at
+                .is_some()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! Monthly partitions of the per-turn tables, kept created ahead of time by
+//! [`crate::jobs::PartitionMaintenanceJob`].
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+
+/// Tables range-partitioned by month of `created_at`
+pub const PARTITIONED_TABLES: [&str; 2] = ["turns", "snake_turns"];
+
+/// Months past the current one that always have a partition. Rows that
+/// would land past the last one go to the default partition, which blocks
+/// creating that month's partition later, so this leaves the cron plenty of
+/// runs to catch up after an outage.
+pub const MONTHS_AHEAD: i32 = 3;
+
+/// Create any missing monthly partitions from this month through
+/// [`MONTHS_AHEAD`] for every partitioned table. Returns how many were
+/// created.
+pub async fn ensure_monthly_partitions(pool: &PgPool) -> cja::Result<i32> {
+    let mut created = 0;
+    for table in PARTITIONED_TABLES {
+        let count = sqlx::query_scalar!(
+            "SELECT ensure_monthly_partitions($1::text::regclass, $2)",
+            table,
+            MONTHS_AHEAD,
+        )
+        .fetch_one(pool)
+        .await
+        .wrap_err_with(|| format!("Failed to create partitions for {table}"))?;
+        created += count.unwrap_or(0);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_partitions_are_created_ahead_and_idempotent(pool: PgPool) -> cja::Result<()> {
+        // The migration already covers the months ahead
+        assert_eq!(ensure_monthly_partitions(&pool).await?, 0);
+
+        // A month whose partition went missing is recreated, and only once
+        let month = (chrono::Utc::now() + chrono::Duration::days(62)).format("y%Ym%m");
+        sqlx::query(&format!("DROP TABLE turns_{month}"))
+            .execute(&pool)
+            .await?;
+        assert_eq!(ensure_monthly_partitions(&pool).await?, 1);
+        assert_eq!(ensure_monthly_partitions(&pool).await?, 0);
+
+        // A turn played now lands in a monthly partition, not the default
+        let game_id = uuid::Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO games (game_id, board_size, game_type, status)
+             VALUES ($1, '11x11', 'Standard', 'running')",
+        )
+        .bind(game_id)
+        .execute(&pool)
+        .await?;
+        sqlx::query("INSERT INTO turns (game_id, turn_number) VALUES ($1, 0)")
+            .bind(game_id)
+            .execute(&pool)
+            .await?;
+        let partition: String =
+            sqlx::query_scalar("SELECT tableoid::regclass::text FROM turns WHERE game_id = $1")
+                .bind(game_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_ne!(partition, "turns_default");
+
+        Ok(())
+    }
+}
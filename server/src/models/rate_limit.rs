@@ -1,6 +1,6 @@
-//! Per-account rate-limit bookkeeping. Home for feature-level sliding
-//! window limits; game creation lives here today, future per-feature
-//! limits can sit alongside it.
+//! Rate-limit bookkeeping. Home for feature-level sliding window limits
+//! (game creation) and the per-minute buckets behind the API write rate
+//! limiter (`routes::api::rate_limit`).
 
 use color_eyre::eyre::Context as _;
 use sqlx::PgPool;
@@ -77,6 +77,49 @@ pub async fn record_and_count_game_creation_attempts(
     count_recent_game_creation_attempts(pool, user_id, window_minutes).await
 }
 
+/// Outcome of counting one request against an API rate-limit bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiBucketHit {
+    /// Requests in the current window, including this one
+    pub count: i64,
+    /// Seconds until the current window ends (>= 1)
+    pub reset_secs: i64,
+}
+
+/// Count one API request against `bucket_key` in the current one-minute
+/// window and return the running total. Like the game-creation limit, the
+/// request is recorded before the caller compares against the limit, so
+/// concurrent requests see each other and rejected ones still count.
+///
+/// Windows are fixed calendar minutes, so a client can burst up to twice
+/// the limit across a boundary; that's an accepted trade for one upsert per
+/// request.
+pub async fn hit_api_bucket(pool: &PgPool, bucket_key: &str) -> cja::Result<ApiBucketHit> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO api_rate_limit_buckets (bucket_key, window_start, request_count)
+        VALUES ($1, date_trunc('minute', NOW()), 1)
+        ON CONFLICT (bucket_key, window_start)
+        DO UPDATE SET request_count = api_rate_limit_buckets.request_count + 1
+        RETURNING
+            request_count as "count!",
+            GREATEST(
+                CEIL(EXTRACT(EPOCH FROM (window_start + INTERVAL '1 minute' - NOW())))::BIGINT,
+                1
+            ) as "reset_secs!"
+        "#,
+        bucket_key,
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to record API request")?;
+
+    Ok(ApiBucketHit {
+        count: i64::from(row.count),
+        reset_secs: row.reset_secs,
+    })
+}
+
 /// Retention for attempt rows, comfortably past any plausible window
 /// setting so the sliding-window counts are never affected.
 const PRUNE_RETENTION_HOURS: i32 = 24;
@@ -104,9 +147,19 @@ pub async fn prune_old_attempts(pool: &PgPool) -> cja::Result<()> {
     .await
     .wrap_err("Failed to prune claim attempts")?;
 
+    let api_buckets = sqlx::query!(
+        "DELETE FROM api_rate_limit_buckets
+         WHERE window_start < NOW() - make_interval(hours => $1)",
+        PRUNE_RETENTION_HOURS,
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to prune API rate-limit buckets")?;
+
     tracing::info!(
         game_creation_attempts = games.rows_affected(),
         claim_attempts = claims.rows_affected(),
+        api_rate_limit_buckets = api_buckets.rows_affected(),
         "Pruned rate-limit bookkeeping"
     );
 
@@ -193,6 +246,20 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrations = "../migrations")]
+    async fn api_bucket_counts_per_key(pool: PgPool) -> cja::Result<()> {
+        let first = hit_api_bucket(&pool, "ip:203.0.113.7").await?;
+        assert_eq!(first.count, 1);
+        assert!((1..=60).contains(&first.reset_secs));
+
+        assert_eq!(hit_api_bucket(&pool, "ip:203.0.113.7").await?.count, 2);
+
+        // Different key, different bucket
+        assert_eq!(hit_api_bucket(&pool, "token:abc").await?.count, 1);
+
+        Ok(())
+    }
+
     #[sqlx::test(migrations = "../migrations")]
     async fn prune_deletes_only_rows_past_retention(pool: PgPool) -> cja::Result<()> {
         let user = create_user(&pool, 9005).await?;
@@ -213,6 +280,12 @@ mod tests {
         )
         .execute(&pool)
         .await?;
+        sqlx::query!(
+            "INSERT INTO api_rate_limit_buckets (bucket_key, window_start, request_count)
+             VALUES ('ip:old', NOW() - INTERVAL '25 hours', 3), ('ip:new', NOW(), 1)",
+        )
+        .execute(&pool)
+        .await?;
 
         prune_old_attempts(&pool).await?;
 
@@ -223,7 +296,11 @@ mod tests {
         let claims: i64 = sqlx::query_scalar!(r#"SELECT COUNT(*) as "c!" FROM claim_attempts"#)
             .fetch_one(&pool)
             .await?;
-        assert_eq!((games, claims), (1, 1));
+        let buckets: i64 =
+            sqlx::query_scalar!(r#"SELECT COUNT(*) as "c!" FROM api_rate_limit_buckets"#)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!((games, claims, buckets), (1, 1, 1));
 
         Ok(())
     }
@@ -202,6 +202,7 @@ mod tests {
             CreateGame {
                 board_size: GameBoardSize::Medium,
                 game_type: GameType::Standard,
+                run_tag: None,
             },
         )
         .await?;
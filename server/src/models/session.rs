@@ -203,6 +203,7 @@ pub async fn get_session_with_user(
             u.is_admin as "is_admin?",
             u.site_theme as "site_theme?",
             u.theater_theme as "theater_theme?",
+            u.suspended_at,
             u.created_at as "user_created_at?",
             u.updated_at as "user_updated_at?"
         FROM sessions s
@@ -260,6 +261,7 @@ pub async fn get_session_with_user(
                     is_admin: row.is_admin.unwrap_or(false),
                     site_theme: row.site_theme.unwrap_or_else(|| "system".to_string()),
                     theater_theme: row.theater_theme.unwrap_or_else(|| "dark".to_string()),
+                    suspended_at: row.suspended_at,
                     created_at: user_created_at,
                     updated_at: user_updated_at,
                 })
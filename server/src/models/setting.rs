@@ -0,0 +1,229 @@
+//! Runtime tunables stored in the `settings` table, editable from the admin
+//! settings page without a redeploy. Each [`Setting`] has a compiled-in
+//! default and range; a stored value outside the range (or of the wrong
+//! type) is ignored in favor of the default, so a bad row can't wedge the
+//! matchmaker or runner.
+
+use std::collections::HashMap;
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Setting {
+    MatchmakerMultiplier,
+    MaxActiveGames,
+    MoveTimeoutMs,
+    InfoTimeoutMs,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 4] = [
+        Setting::MatchmakerMultiplier,
+        Setting::MaxActiveGames,
+        Setting::MoveTimeoutMs,
+        Setting::InfoTimeoutMs,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Setting::MatchmakerMultiplier => "matchmaker_multiplier",
+            Setting::MaxActiveGames => "max_active_games",
+            Setting::MoveTimeoutMs => "move_timeout_ms",
+            Setting::InfoTimeoutMs => "info_timeout_ms",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Setting> {
+        Setting::ALL.into_iter().find(|s| s.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Setting::MatchmakerMultiplier => "Matchmaker multiplier",
+            Setting::MaxActiveGames => "Max active games",
+            Setting::MoveTimeoutMs => "Move timeout (ms)",
+            Setting::InfoTimeoutMs => "Info timeout (ms)",
+        }
+    }
+
+    pub fn help(&self) -> &'static str {
+        match self {
+            Setting::MatchmakerMultiplier => {
+                "Scales the leaderboard games each matchmaker run creates. 0 pauses matchmaking."
+            }
+            Setting::MaxActiveGames => {
+                "The matchmaker stops creating games while this many are waiting or running. 0 means no cap."
+            }
+            Setting::MoveTimeoutMs => "How long snakes get to answer /move in new games",
+            Setting::InfoTimeoutMs => "How long snakes get to answer GET / for customizations",
+        }
+    }
+
+    pub fn default_value(&self) -> f64 {
+        match self {
+            Setting::MatchmakerMultiplier => 1.0,
+            Setting::MaxActiveGames => 0.0,
+            Setting::MoveTimeoutMs => 500.0,
+            Setting::InfoTimeoutMs => 1000.0,
+        }
+    }
+
+    /// Inclusive bounds for a valid value
+    pub fn range(&self) -> (f64, f64) {
+        match self {
+            Setting::MatchmakerMultiplier => (0.0, 10.0),
+            Setting::MaxActiveGames => (0.0, 100_000.0),
+            Setting::MoveTimeoutMs => (100.0, 5000.0),
+            Setting::InfoTimeoutMs => (100.0, 10_000.0),
+        }
+    }
+
+    /// Whether the value must be a whole number
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, Setting::MatchmakerMultiplier)
+    }
+
+    /// Check a proposed value, returning why it's rejected
+    pub fn validate(&self, value: f64) -> Result<f64, String> {
+        let (min, max) = self.range();
+        if !value.is_finite() || value < min || value > max {
+            return Err(format!("{} must be between {min} and {max}", self.label()));
+        }
+        if self.is_integer() && value.fract() != 0.0 {
+            return Err(format!("{} must be a whole number", self.label()));
+        }
+        Ok(value)
+    }
+}
+
+/// The effective value of every setting: stored overrides on top of the
+/// defaults
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    values: HashMap<Setting, f64>,
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        Self {
+            values: Setting::ALL
+                .into_iter()
+                .map(|s| (s, s.default_value()))
+                .collect(),
+        }
+    }
+}
+
+impl RuntimeSettings {
+    pub fn get(&self, setting: Setting) -> f64 {
+        self.values
+            .get(&setting)
+            .copied()
+            .unwrap_or_else(|| setting.default_value())
+    }
+
+    pub fn matchmaker_multiplier(&self) -> f64 {
+        self.get(Setting::MatchmakerMultiplier)
+    }
+
+    /// `None` when uncapped
+    pub fn max_active_games(&self) -> Option<i64> {
+        Some(self.get(Setting::MaxActiveGames) as i64).filter(|max| *max > 0)
+    }
+
+    pub fn move_timeout_ms(&self) -> i64 {
+        self.get(Setting::MoveTimeoutMs) as i64
+    }
+
+    pub fn info_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.get(Setting::InfoTimeoutMs) as u64)
+    }
+}
+
+pub async fn get_all(pool: &PgPool) -> cja::Result<RuntimeSettings> {
+    let rows = sqlx::query!("SELECT key, value FROM settings")
+        .fetch_all(pool)
+        .await
+        .wrap_err("Failed to fetch settings")?;
+
+    let mut settings = RuntimeSettings::default();
+    for row in rows {
+        let Some(setting) = Setting::from_key(&row.key) else {
+            continue;
+        };
+        match row.value.as_f64().map(|v| setting.validate(v)) {
+            Some(Ok(value)) => {
+                settings.values.insert(setting, value);
+            }
+            _ => {
+                tracing::warn!(key = %row.key, value = %row.value, "Ignoring invalid stored setting")
+            }
+        }
+    }
+    Ok(settings)
+}
+
+/// Store a validated value for one setting
+pub async fn set(pool: &PgPool, setting: Setting, value: f64, updated_by: Uuid) -> cja::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO settings (key, value, updated_by, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (key) DO UPDATE SET
+            value = EXCLUDED.value,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = EXCLUDED.updated_at
+        "#,
+        setting.key(),
+        serde_json::json!(value),
+        updated_by,
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to update setting")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(Setting::MatchmakerMultiplier.validate(1.5), Ok(1.5));
+        assert!(Setting::MatchmakerMultiplier.validate(11.0).is_err());
+        assert!(Setting::MoveTimeoutMs.validate(250.5).is_err());
+        assert!(Setting::MoveTimeoutMs.validate(f64::NAN).is_err());
+        assert_eq!(RuntimeSettings::default().max_active_games(), None);
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_set_and_get_settings(pool: PgPool) -> cja::Result<()> {
+        assert_eq!(get_all(&pool).await?, RuntimeSettings::default());
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'admin', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        set(&pool, Setting::MoveTimeoutMs, 750.0, user_id).await?;
+        set(&pool, Setting::MaxActiveGames, 40.0, user_id).await?;
+        // Written around `set`, e.g. by hand in psql
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('info_timeout_ms', '\"fast\"')")
+            .execute(&pool)
+            .await?;
+
+        let settings = get_all(&pool).await?;
+        assert_eq!(settings.move_timeout_ms(), 750);
+        assert_eq!(settings.max_active_games(), Some(40));
+        assert_eq!(
+            settings.info_timeout(),
+            std::time::Duration::from_millis(1000)
+        );
+        Ok(())
+    }
+}
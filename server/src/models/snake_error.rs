@@ -0,0 +1,375 @@
+//! Per-snake /move failure and latency stats, for the admin error report
+//! and the health card on snake profiles.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Share of failed moves at which a snake counts as failing
+pub const FAILING_ERROR_RATE: f64 = 0.05;
+
+/// One snake's /move failures over the report window
+#[derive(Debug, Clone)]
+pub struct SnakeErrorStats {
+    pub battlesnake_id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub owner_login: String,
+    pub total_moves: i64,
+    /// Timeouts and network errors (both leave `timed_out` set)
+    pub timeouts: i64,
+    pub http_errors: i64,
+    pub parse_failures: i64,
+    /// Moves with any kind of failure
+    pub failed_moves: i64,
+}
+
+impl SnakeErrorStats {
+    fn rate(&self, count: i64) -> f64 {
+        if self.total_moves == 0 {
+            0.0
+        } else {
+            count as f64 / self.total_moves as f64
+        }
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        self.rate(self.failed_moves)
+    }
+
+    pub fn timeout_rate(&self) -> f64 {
+        self.rate(self.timeouts)
+    }
+
+    pub fn http_error_rate(&self) -> f64 {
+        self.rate(self.http_errors)
+    }
+
+    pub fn parse_failure_rate(&self) -> f64 {
+        self.rate(self.parse_failures)
+    }
+}
+
+/// Snakes with at least one failed move since `since`, worst error rate
+/// first. Snakes with fewer than `min_moves` moves are left out so one
+/// unlucky game doesn't top the list.
+pub async fn get_error_report(
+    pool: &PgPool,
+    since: chrono::DateTime<chrono::Utc>,
+    min_moves: i64,
+    limit: i64,
+) -> cja::Result<Vec<SnakeErrorStats>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            b.battlesnake_id,
+            b.name,
+            u.user_id AS owner_id,
+            u.github_login AS owner_login,
+            COUNT(*) AS "total_moves!",
+            COUNT(*) FILTER (WHERE st.timed_out) AS "timeouts!",
+            COUNT(*) FILTER (WHERE st.error_kind = 'http_status') AS "http_errors!",
+            COUNT(*) FILTER (WHERE st.error_kind = 'parse') AS "parse_failures!",
+            COUNT(*) FILTER (WHERE st.timed_out OR st.error_kind IS NOT NULL) AS "failed_moves!"
+        FROM snake_turns st
+        JOIN game_battlesnakes gb ON gb.game_battlesnake_id = st.game_battlesnake_id
+        JOIN battlesnakes b ON b.battlesnake_id = gb.battlesnake_id
+        JOIN users u ON u.user_id = b.user_id
+        WHERE st.created_at >= $1
+        GROUP BY b.battlesnake_id, u.user_id
+        HAVING COUNT(*) >= $2
+           AND COUNT(*) FILTER (WHERE st.timed_out OR st.error_kind IS NOT NULL) > 0
+        ORDER BY
+            COUNT(*) FILTER (WHERE st.timed_out OR st.error_kind IS NOT NULL)::float8 / COUNT(*) DESC,
+            COUNT(*) DESC
+        LIMIT $3
+        "#,
+        since,
+        min_moves,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to build snake error report")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SnakeErrorStats {
+            battlesnake_id: row.battlesnake_id,
+            name: row.name,
+            owner_id: row.owner_id,
+            owner_login: row.owner_login,
+            total_moves: row.total_moves,
+            timeouts: row.timeouts,
+            http_errors: row.http_errors,
+            parse_failures: row.parse_failures,
+            failed_moves: row.failed_moves,
+        })
+        .collect())
+}
+
+/// One failed /move call, for drilling into a snake's report row
+#[derive(Debug, Clone)]
+pub struct FailedMove {
+    pub game_id: Uuid,
+    pub turn_number: i32,
+    /// `None` on rows recorded before failures were classified
+    pub error_kind: Option<String>,
+    pub http_status: Option<i16>,
+    pub latency_ms: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl FailedMove {
+    pub fn describe(&self) -> String {
+        match (self.error_kind.as_deref(), self.http_status) {
+            (Some("http_status"), Some(status)) => format!("HTTP {status}"),
+            (Some("timeout"), _) => "Timeout".to_string(),
+            (Some("network"), _) => "Network error".to_string(),
+            (Some("parse"), _) => "Unparseable response".to_string(),
+            _ => "Timeout or network error".to_string(),
+        }
+    }
+}
+
+/// A snake's most recent failed moves since `since`
+pub async fn get_recent_failed_moves(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+    since: chrono::DateTime<chrono::Utc>,
+    limit: i64,
+) -> cja::Result<Vec<FailedMove>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.game_id, t.turn_number, st.error_kind, st.http_status, st.latency_ms, st.created_at
+        FROM snake_turns st
+        JOIN game_battlesnakes gb ON gb.game_battlesnake_id = st.game_battlesnake_id
+        JOIN turns t ON t.turn_id = st.turn_id
+        WHERE gb.battlesnake_id = $1
+          AND st.created_at >= $2
+          AND (st.timed_out OR st.error_kind IS NOT NULL)
+        ORDER BY st.created_at DESC
+        LIMIT $3
+        "#,
+        battlesnake_id,
+        since,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch failed moves")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| FailedMove {
+            game_id: row.game_id,
+            turn_number: row.turn_number,
+            error_kind: row.error_kind,
+            http_status: row.http_status,
+            latency_ms: row.latency_ms,
+            created_at: row.created_at,
+        })
+        .collect())
+}
+
+/// How one snake has been answering /move over a window
+#[derive(Debug, Clone, Default)]
+pub struct SnakeLatencyStats {
+    pub total_moves: i64,
+    /// Moves with any kind of failure
+    pub failed_moves: i64,
+    /// Over moves that got a response; `None` if none did
+    pub avg_latency_ms: Option<f64>,
+    pub p95_latency_ms: Option<f64>,
+}
+
+impl SnakeLatencyStats {
+    pub fn error_rate(&self) -> f64 {
+        if self.total_moves == 0 {
+            0.0
+        } else {
+            self.failed_moves as f64 / self.total_moves as f64
+        }
+    }
+}
+
+/// A snake's /move latency and failure counts since `since`
+pub async fn get_latency_stats(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+    since: chrono::DateTime<chrono::Utc>,
+) -> cja::Result<SnakeLatencyStats> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) AS "total_moves!",
+            COUNT(*) FILTER (WHERE st.timed_out OR st.error_kind IS NOT NULL) AS "failed_moves!",
+            AVG(st.latency_ms)::float8 AS avg_latency_ms,
+            percentile_cont(0.95) WITHIN GROUP (ORDER BY st.latency_ms) AS p95_latency_ms
+        FROM snake_turns st
+        JOIN game_battlesnakes gb ON gb.game_battlesnake_id = st.game_battlesnake_id
+        WHERE gb.battlesnake_id = $1
+          AND st.created_at >= $2
+        "#,
+        battlesnake_id,
+        since
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to fetch snake latency stats")?;
+
+    Ok(SnakeLatencyStats {
+        total_moves: row.total_moves,
+        failed_moves: row.failed_moves,
+        avg_latency_ms: row.avg_latency_ms,
+        p95_latency_ms: row.p95_latency_ms,
+    })
+}
+
+/// A snake's most recent finished games, newest first, each paired with
+/// whether any of its moves in that game timed out. Leaderboard games only
+/// link the snake through its entry, so those are matched on that too.
+pub async fn get_recent_game_timeouts(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+    limit: i64,
+) -> cja::Result<Vec<(Uuid, bool)>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            g.game_id,
+            EXISTS (
+                SELECT 1 FROM snake_turns st
+                WHERE st.game_battlesnake_id = gb.game_battlesnake_id
+                  AND st.timed_out
+            ) AS "timed_out!"
+        FROM game_battlesnakes gb
+        JOIN games g ON g.game_id = gb.game_id
+        WHERE (
+            gb.battlesnake_id = $1
+            OR gb.leaderboard_entry_id IN (
+                SELECT leaderboard_entry_id FROM leaderboard_entries WHERE battlesnake_id = $1
+            )
+        )
+          AND g.status = 'finished'
+        ORDER BY g.created_at DESC
+        LIMIT $2
+        "#,
+        battlesnake_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch recent game timeouts")?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.game_id, row.timed_out))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_error_report_ranks_by_error_rate(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'finished')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut turn_ids = Vec::new();
+        for turn_number in 0..4 {
+            let turn_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO turns (game_id, turn_number) VALUES ($1, $2) RETURNING turn_id",
+            )
+            .bind(game_id)
+            .bind(turn_number)
+            .fetch_one(&pool)
+            .await?;
+            turn_ids.push(turn_id);
+        }
+
+        // (error_kind, http_status, timed_out) for each failed move; the
+        // rest of a snake's 4 moves succeed
+        type Failure = (Option<&'static str>, Option<i16>, bool);
+        let snakes: [(&str, &[Failure]); 3] = [
+            ("healthy", &[]),
+            ("flaky", &[(Some("http_status"), Some(500), false)]),
+            (
+                "broken",
+                &[
+                    (Some("timeout"), None, true),
+                    (Some("parse"), None, false),
+                    (None, None, true),
+                ],
+            ),
+        ];
+        let mut ids = std::collections::HashMap::new();
+        for (name, failures) in snakes {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, $2, 'http://example.com')
+                 RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            ids.insert(name, battlesnake_id);
+            let gb_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2)
+                 RETURNING game_battlesnake_id",
+            )
+            .bind(game_id)
+            .bind(battlesnake_id)
+            .fetch_one(&pool)
+            .await?;
+
+            for (i, turn_id) in turn_ids.iter().enumerate() {
+                let (error_kind, http_status, timed_out) =
+                    failures.get(i).copied().unwrap_or((None, None, false));
+                sqlx::query(
+                    "INSERT INTO snake_turns
+                        (turn_id, game_battlesnake_id, direction, timed_out, error_kind, http_status)
+                     VALUES ($1, $2, 'up', $3, $4, $5)",
+                )
+                .bind(turn_id)
+                .bind(gb_id)
+                .bind(timed_out)
+                .bind(error_kind)
+                .bind(http_status)
+                .execute(&pool)
+                .await?;
+            }
+        }
+
+        let since = chrono::Utc::now() - chrono::Duration::hours(1);
+        let report = get_error_report(&pool, since, 4, 10).await?;
+        let names: Vec<_> = report.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["broken", "flaky"]);
+        assert_eq!(report[0].timeouts, 2);
+        assert_eq!(report[0].parse_failures, 1);
+        assert_eq!(report[0].error_rate(), 0.75);
+        assert_eq!(report[1].http_errors, 1);
+
+        assert!(get_error_report(&pool, since, 5, 10).await?.is_empty());
+
+        let failed = get_recent_failed_moves(&pool, ids["flaky"], since, 10).await?;
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].describe(), "HTTP 500");
+
+        let latency = get_latency_stats(&pool, ids["broken"], since).await?;
+        assert_eq!(latency.total_moves, 4);
+        assert_eq!(latency.failed_moves, 3);
+        assert_eq!(latency.avg_latency_ms, None);
+        Ok(())
+    }
+}
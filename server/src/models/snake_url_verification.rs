@@ -0,0 +1,156 @@
+//! Snake URL ownership verification.
+//!
+//! Before a snake can join a leaderboard its owner proves they control its
+//! URL: the arena fetches `GET /` and looks for the snake's token anywhere
+//! in the response. Verification belongs to the URL it was done against, so
+//! pointing a snake somewhere else un-verifies it.
+
+use color_eyre::eyre::Context as _;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct SnakeUrlVerification {
+    pub battlesnake_id: Uuid,
+    pub token: String,
+    /// The URL that was verified, which may no longer be the snake's URL
+    pub verified_url: Option<String>,
+    pub verified_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl SnakeUrlVerification {
+    /// Whether `url` (the snake's current URL) is the one that was verified
+    pub fn is_verified_for(&self, url: &str) -> bool {
+        self.verified_at.is_some() && self.verified_url.as_deref() == Some(url)
+    }
+}
+
+/// The snake's verification row, creating it (with a fresh token) the first
+/// time it's asked for.
+pub async fn get_or_create(
+    pool: &PgPool,
+    battlesnake_id: Uuid,
+) -> cja::Result<SnakeUrlVerification> {
+    let verification = sqlx::query_as!(
+        SnakeUrlVerification,
+        r#"
+        INSERT INTO snake_url_verifications (battlesnake_id)
+        VALUES ($1)
+        ON CONFLICT (battlesnake_id) DO UPDATE SET battlesnake_id = EXCLUDED.battlesnake_id
+        RETURNING battlesnake_id, token, verified_url, verified_at
+        "#,
+        battlesnake_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to get or create snake URL verification")?;
+
+    Ok(verification)
+}
+
+/// Whether the snake's current URL has been verified
+pub async fn is_verified(pool: &PgPool, battlesnake_id: Uuid) -> cja::Result<bool> {
+    let verified = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM snake_url_verifications v
+            JOIN battlesnakes b ON b.battlesnake_id = v.battlesnake_id
+            WHERE v.battlesnake_id = $1
+              AND v.verified_at IS NOT NULL
+              AND v.verified_url = b.url
+        ) AS "verified!"
+        "#,
+        battlesnake_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to check snake URL verification")?;
+
+    Ok(verified)
+}
+
+/// Record that `url` served the snake's token
+pub async fn mark_verified(pool: &PgPool, battlesnake_id: Uuid, url: &str) -> cja::Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE snake_url_verifications
+        SET verified_url = $2, verified_at = NOW()
+        WHERE battlesnake_id = $1
+        "#,
+        battlesnake_id,
+        url
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to mark snake URL verified")?;
+
+    Ok(())
+}
+
+/// Pause the snake's active leaderboard entries if its current URL isn't
+/// verified — called after a snake is edited, so a verified snake can't be
+/// re-pointed at someone else's server and keep playing. The owner rejoins
+/// once the new URL is verified. Returns how many entries were paused.
+pub async fn pause_entries_if_unverified(pool: &PgPool, battlesnake_id: Uuid) -> cja::Result<u64> {
+    if is_verified(pool, battlesnake_id).await? {
+        return Ok(0);
+    }
+    crate::models::leaderboard::pause_entries_for_battlesnake(pool, battlesnake_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_verification_follows_the_url(pool: PgPool) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'snake', 'http://a.example')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('lb') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let first = get_or_create(&pool, battlesnake_id).await?;
+        assert!(first.token.starts_with("arena-verify-"));
+        assert!(!is_verified(&pool, battlesnake_id).await?);
+        // Asking again keeps the same token
+        assert_eq!(
+            get_or_create(&pool, battlesnake_id).await?.token,
+            first.token
+        );
+
+        mark_verified(&pool, battlesnake_id, "http://a.example").await?;
+        assert!(is_verified(&pool, battlesnake_id).await?);
+        crate::models::leaderboard::get_or_create_entry(&pool, leaderboard_id, battlesnake_id)
+            .await?;
+        assert_eq!(pause_entries_if_unverified(&pool, battlesnake_id).await?, 0);
+
+        // Moving the snake to a new URL drops verification and pauses it
+        sqlx::query("UPDATE battlesnakes SET url = 'http://b.example' WHERE battlesnake_id = $1")
+            .bind(battlesnake_id)
+            .execute(&pool)
+            .await?;
+        assert!(!is_verified(&pool, battlesnake_id).await?);
+        assert!(
+            !get_or_create(&pool, battlesnake_id)
+                .await?
+                .is_verified_for("http://b.example")
+        );
+        assert_eq!(pause_entries_if_unverified(&pool, battlesnake_id).await?, 1);
+        Ok(())
+    }
+}
@@ -133,6 +133,50 @@ impl FromStr for TournamentVisibility {
     }
 }
 
+// How the field is paired: a knockout bracket (one or two losses) or a full
+// round robin scored by standings.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentFormat {
+    #[default]
+    SingleElimination,
+    DoubleElimination,
+    RoundRobin,
+}
+
+impl TournamentFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TournamentFormat::SingleElimination => "single_elimination",
+            TournamentFormat::DoubleElimination => "double_elimination",
+            TournamentFormat::RoundRobin => "round_robin",
+        }
+    }
+
+    /// Human-readable name for pages and forms.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TournamentFormat::SingleElimination => "Single elimination",
+            TournamentFormat::DoubleElimination => "Double elimination",
+            TournamentFormat::RoundRobin => "Round robin",
+        }
+    }
+}
+
+impl FromStr for TournamentFormat {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "single_elimination" => Ok(TournamentFormat::SingleElimination),
+            "double_elimination" => Ok(TournamentFormat::DoubleElimination),
+            "round_robin" => Ok(TournamentFormat::RoundRobin),
+            _ => Err(color_eyre::eyre::eyre!("Invalid tournament format: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type, Default)]
 #[sqlx(type_name = "text")]
 pub enum MatchStyle {
@@ -225,6 +269,44 @@ impl FromStr for MatchStatus {
     }
 }
 
+// Which part of the tournament a match belongs to. Single elimination only
+// uses `Winners`; round robin matches are all `Pool`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MatchBracket {
+    #[default]
+    Winners,
+    Losers,
+    GrandFinal,
+    Pool,
+}
+
+impl MatchBracket {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchBracket::Winners => "winners",
+            MatchBracket::Losers => "losers",
+            MatchBracket::GrandFinal => "grand_final",
+            MatchBracket::Pool => "pool",
+        }
+    }
+}
+
+impl FromStr for MatchBracket {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "winners" => Ok(MatchBracket::Winners),
+            "losers" => Ok(MatchBracket::Losers),
+            "grand_final" => Ok(MatchBracket::GrandFinal),
+            "pool" => Ok(MatchBracket::Pool),
+            _ => Err(color_eyre::eyre::eyre!("Invalid match bracket: {}", s)),
+        }
+    }
+}
+
 // How a participant slot gets filled: seeded in round 1, advanced as a
 // winner/loser of a feeder match, or placed manually as a wildcard.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type, Default)]
@@ -275,6 +357,9 @@ pub struct Tournament {
     pub visibility: TournamentVisibility,
     pub status: TournamentStatus,
     pub match_style: MatchStyle,
+    pub format: TournamentFormat,
+    /// Run each round as soon as the previous one finishes
+    pub auto_advance: bool,
     pub max_snakes_per_user: i32,
     pub required_participants: i32,
     pub current_round: i32,
@@ -299,7 +384,10 @@ pub struct TournamentMatch {
     pub round: i32,
     pub position: i32,
     pub status: MatchStatus,
+    pub bracket: MatchBracket,
     pub next_match_id: Option<Uuid>,
+    /// Double elimination: where this match's loser drops to
+    pub loser_next_match_id: Option<Uuid>,
     pub winner_id: Option<Uuid>,
     pub visual_column: i32,
     pub visual_row: i32,
@@ -341,6 +429,8 @@ pub struct CreateTournament {
     pub registration_status: RegistrationStatus,
     pub visibility: TournamentVisibility,
     pub match_style: MatchStyle,
+    pub format: TournamentFormat,
+    pub auto_advance: bool,
     pub max_snakes_per_user: i32,
     pub required_participants: i32,
 }
@@ -361,6 +451,8 @@ struct TournamentRow {
     visibility: TournamentVisibility,
     status: TournamentStatus,
     match_style: MatchStyle,
+    format: TournamentFormat,
+    auto_advance: bool,
     max_snakes_per_user: i32,
     required_participants: i32,
     current_round: i32,
@@ -388,6 +480,8 @@ impl TryFrom<TournamentRow> for Tournament {
             visibility: row.visibility,
             status: row.status,
             match_style: row.match_style,
+            format: row.format,
+            auto_advance: row.auto_advance,
             max_snakes_per_user: row.max_snakes_per_user,
             required_participants: row.required_participants,
             current_round: row.current_round,
@@ -410,10 +504,10 @@ where
         r#"
         INSERT INTO tournaments (
             name, description, user_id, game_type, board_size,
-            registration_status, visibility, match_style,
+            registration_status, visibility, match_style, format, auto_advance,
             max_snakes_per_user, required_participants
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         RETURNING
             tournament_id,
             name,
@@ -425,6 +519,8 @@ where
             visibility as "visibility: TournamentVisibility",
             status as "status: TournamentStatus",
             match_style as "match_style: MatchStyle",
+            format as "format: TournamentFormat",
+            auto_advance,
             max_snakes_per_user,
             required_participants,
             current_round,
@@ -439,6 +535,8 @@ where
         data.registration_status.as_str(),
         data.visibility.as_str(),
         data.match_style.as_str(),
+        data.format.as_str(),
+        data.auto_advance,
         data.max_snakes_per_user,
         data.required_participants,
     )
@@ -470,6 +568,8 @@ where
             visibility as "visibility: TournamentVisibility",
             status as "status: TournamentStatus",
             match_style as "match_style: MatchStyle",
+            format as "format: TournamentFormat",
+            auto_advance,
             max_snakes_per_user,
             required_participants,
             current_round,
@@ -511,6 +611,8 @@ pub async fn get_tournament_for_update(
             visibility as "visibility: TournamentVisibility",
             status as "status: TournamentStatus",
             match_style as "match_style: MatchStyle",
+            format as "format: TournamentFormat",
+            auto_advance,
             max_snakes_per_user,
             required_participants,
             current_round,
@@ -661,35 +763,57 @@ pub struct TournamentListItem {
     pub name: String,
     pub owner_login: String,
     pub status: TournamentStatus,
+    pub format: TournamentFormat,
     pub game_type: GameType,
     pub registration_count: i64,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+struct TournamentListRow {
+    tournament_id: Uuid,
+    name: String,
+    owner_login: String,
+    status: TournamentStatus,
+    format: TournamentFormat,
+    game_type: String,
+    registration_count: i64,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<TournamentListRow> for TournamentListItem {
+    type Error = color_eyre::eyre::Report;
+
+    fn try_from(row: TournamentListRow) -> Result<Self, Self::Error> {
+        let game_type = GameType::from_str(&row.game_type)
+            .wrap_err_with(|| format!("Invalid game type: {}", row.game_type))?;
+        Ok(TournamentListItem {
+            tournament_id: row.tournament_id,
+            name: row.name,
+            owner_login: row.owner_login,
+            status: row.status,
+            format: row.format,
+            game_type,
+            registration_count: row.registration_count,
+            created_at: row.created_at,
+        })
+    }
+}
+
 /// List tournaments visible to a viewer: all public tournaments, plus the
 /// viewer's own non-public ones when logged in.
 pub async fn list_visible_tournaments(
     pool: &PgPool,
     viewer_user_id: Option<Uuid>,
 ) -> cja::Result<Vec<TournamentListItem>> {
-    struct Row {
-        tournament_id: Uuid,
-        name: String,
-        owner_login: String,
-        status: TournamentStatus,
-        game_type: String,
-        registration_count: i64,
-        created_at: chrono::DateTime<chrono::Utc>,
-    }
-
     let rows = sqlx::query_as!(
-        Row,
+        TournamentListRow,
         r#"
         SELECT
             t.tournament_id,
             t.name,
             u.github_login as owner_login,
             t.status as "status: TournamentStatus",
+            t.format as "format: TournamentFormat",
             t.game_type,
             COUNT(r.registration_id) as "registration_count!",
             t.created_at
@@ -706,21 +830,35 @@ pub async fn list_visible_tournaments(
     .await
     .wrap_err("Failed to list visible tournaments")?;
 
-    rows.into_iter()
-        .map(|row| {
-            let game_type = GameType::from_str(&row.game_type)
-                .wrap_err_with(|| format!("Invalid game type: {}", row.game_type))?;
-            Ok(TournamentListItem {
-                tournament_id: row.tournament_id,
-                name: row.name,
-                owner_login: row.owner_login,
-                status: row.status,
-                game_type,
-                registration_count: row.registration_count,
-                created_at: row.created_at,
-            })
-        })
-        .collect()
+    rows.into_iter().map(TournamentListItem::try_from).collect()
+}
+
+/// List every tournament regardless of visibility, for the admin page.
+pub async fn list_all_tournaments(pool: &PgPool) -> cja::Result<Vec<TournamentListItem>> {
+    let rows = sqlx::query_as!(
+        TournamentListRow,
+        r#"
+        SELECT
+            t.tournament_id,
+            t.name,
+            u.github_login as owner_login,
+            t.status as "status: TournamentStatus",
+            t.format as "format: TournamentFormat",
+            t.game_type,
+            COUNT(r.registration_id) as "registration_count!",
+            t.created_at
+        FROM tournaments t
+        JOIN users u ON t.user_id = u.user_id
+        LEFT JOIN tournament_registrations r ON r.tournament_id = t.tournament_id
+        GROUP BY t.tournament_id, u.github_login
+        ORDER BY t.created_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to list tournaments")?;
+
+    rows.into_iter().map(TournamentListItem::try_from).collect()
 }
 
 /// A registration enriched with snake name and owner login for display.
@@ -1062,6 +1200,8 @@ pub struct UpdateTournamentSettings {
     pub game_type: GameType,
     pub board_size: GameBoardSize,
     pub match_style: MatchStyle,
+    pub format: TournamentFormat,
+    pub auto_advance: bool,
     pub registration_status: RegistrationStatus,
     pub visibility: TournamentVisibility,
     pub max_snakes_per_user: i32,
@@ -1087,7 +1227,9 @@ where
             registration_status = $7,
             visibility = $8,
             max_snakes_per_user = $9,
-            required_participants = $10
+            required_participants = $10,
+            format = $11,
+            auto_advance = $12
         WHERE tournament_id = $1
         "#,
         tournament_id,
@@ -1100,6 +1242,8 @@ where
         data.visibility.as_str(),
         data.max_snakes_per_user,
         data.required_participants,
+        data.format.as_str(),
+        data.auto_advance,
     )
     .execute(executor)
     .await
@@ -1141,7 +1285,9 @@ pub struct CreateTournamentMatch {
     pub tournament_id: Uuid,
     pub round: i32,
     pub position: i32,
+    pub bracket: MatchBracket,
     pub next_match_id: Option<Uuid>,
+    pub loser_next_match_id: Option<Uuid>,
     pub visual_column: i32,
     pub visual_row: i32,
 }
@@ -1157,16 +1303,19 @@ where
         TournamentMatch,
         r#"
         INSERT INTO tournament_matches (
-            tournament_id, round, position, next_match_id, visual_column, visual_row
+            tournament_id, round, position, bracket, next_match_id, loser_next_match_id,
+            visual_column, visual_row
         )
-        VALUES ($1, $2, $3, $4, $5, $6)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING
             match_id,
             tournament_id,
             round,
             position,
             status as "status: MatchStatus",
+            bracket as "bracket: MatchBracket",
             next_match_id,
+            loser_next_match_id,
             winner_id,
             visual_column,
             visual_row,
@@ -1176,7 +1325,9 @@ where
         data.tournament_id,
         data.round,
         data.position,
+        data.bracket.as_str(),
         data.next_match_id,
+        data.loser_next_match_id,
         data.visual_column,
         data.visual_row,
     )
@@ -1200,7 +1351,9 @@ pub async fn get_matches_for_tournament(
             round,
             position,
             status as "status: MatchStatus",
+            bracket as "bracket: MatchBracket",
             next_match_id,
+            loser_next_match_id,
             winner_id,
             visual_column,
             visual_row,
@@ -1373,7 +1526,9 @@ where
             round,
             position,
             status as "status: MatchStatus",
+            bracket as "bracket: MatchBracket",
             next_match_id,
+            loser_next_match_id,
             winner_id,
             visual_column,
             visual_row,
@@ -1405,7 +1560,9 @@ pub async fn get_matches_for_round(
             round,
             position,
             status as "status: MatchStatus",
+            bracket as "bracket: MatchBracket",
             next_match_id,
+            loser_next_match_id,
             winner_id,
             visual_column,
             visual_row,
@@ -1789,6 +1946,31 @@ mod tests {
         assert!(MatchStatus::from_str("bogus").is_err());
     }
 
+    #[test]
+    fn tournament_format_round_trips_through_strings() {
+        for format in [
+            TournamentFormat::SingleElimination,
+            TournamentFormat::DoubleElimination,
+            TournamentFormat::RoundRobin,
+        ] {
+            assert_eq!(TournamentFormat::from_str(format.as_str()).unwrap(), format);
+        }
+        assert!(TournamentFormat::from_str("bogus").is_err());
+    }
+
+    #[test]
+    fn match_bracket_round_trips_through_strings() {
+        for bracket in [
+            MatchBracket::Winners,
+            MatchBracket::Losers,
+            MatchBracket::GrandFinal,
+            MatchBracket::Pool,
+        ] {
+            assert_eq!(MatchBracket::from_str(bracket.as_str()).unwrap(), bracket);
+        }
+        assert!(MatchBracket::from_str("bogus").is_err());
+    }
+
     #[test]
     fn participant_type_round_trips_through_strings() {
         for participant_type in [
@@ -1840,6 +2022,8 @@ mod tests {
             registration_status: RegistrationStatus::Open,
             visibility: TournamentVisibility::Public,
             match_style: MatchStyle::SingleGame,
+            format: TournamentFormat::SingleElimination,
+            auto_advance: false,
             max_snakes_per_user: 1,
             required_participants: 2,
         }
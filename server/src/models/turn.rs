@@ -3,18 +3,83 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
 
-use crate::game_channels::{GameChannels, TurnNotification};
+use crate::engine::frame::EngineGameFrame;
+use crate::engine::frame_codec;
 
 /// A turn in a game with its frame data
+///
+/// A turn's frame is in one of two columns: `frame_bin`, in the compact
+/// encoding of [`frame_codec`], for turns stored since it was added, or
+/// `frame_data` as board-viewer JSON for older turns. Read it through
+/// [`Turn::frame_json`] at the API boundary, or [`Turn::frame`] when the
+/// typed frame is wanted.
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Turn {
     pub turn_id: Uuid,
     pub game_id: Uuid,
     pub turn_number: i32,
     pub frame_data: Option<serde_json::Value>,
+    #[serde(skip)]
+    pub frame_bin: Option<Vec<u8>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl Turn {
+    pub fn has_frame(&self) -> bool {
+        self.frame_bin.is_some() || self.frame_data.is_some()
+    }
+
+    /// The frame as board-viewer JSON
+    pub fn frame_json(&self) -> cja::Result<Option<serde_json::Value>> {
+        frame_json(self.frame_bin.as_deref(), self.frame_data.as_ref())
+            .wrap_err_with(|| format!("Bad frame on turn {}", self.turn_id))
+    }
+
+    /// The frame as an [`EngineGameFrame`]; binary frames skip JSON entirely
+    pub fn frame(&self) -> cja::Result<Option<EngineGameFrame>> {
+        match (&self.frame_bin, &self.frame_data) {
+            (Some(bytes), _) => frame_codec::decode(bytes).map(Some),
+            (None, Some(json)) => serde_json::from_value(json.clone())
+                .map(Some)
+                .wrap_err("Failed to parse frame JSON"),
+            (None, None) => Ok(None),
+        }
+        .wrap_err_with(|| format!("Bad frame on turn {}", self.turn_id))
+    }
+}
+
+/// Just a turn's frame columns, for queries that need nothing else
+#[derive(Debug, FromRow)]
+pub struct StoredFrame {
+    pub frame_data: Option<serde_json::Value>,
+    pub frame_bin: Option<Vec<u8>>,
+}
+
+impl StoredFrame {
+    /// The frame as board-viewer JSON
+    pub fn into_json(self) -> cja::Result<Option<serde_json::Value>> {
+        match self.frame_bin {
+            Some(_) => frame_json(self.frame_bin.as_deref(), None),
+            None => Ok(self.frame_data),
+        }
+    }
+}
+
+fn frame_json(
+    frame_bin: Option<&[u8]>,
+    frame_data: Option<&serde_json::Value>,
+) -> cja::Result<Option<serde_json::Value>> {
+    match frame_bin {
+        Some(bytes) => {
+            let frame = frame_codec::decode(bytes)?;
+            Ok(Some(
+                serde_json::to_value(&frame).wrap_err("Failed to serialize frame")?,
+            ))
+        }
+        None => Ok(frame_data.cloned()),
+    }
+}
+
 /// Get all turns for a game, ordered by turn number
 pub async fn get_turns_by_game_id(pool: &PgPool, game_id: Uuid) -> cja::Result<Vec<Turn>> {
     let turns = sqlx::query_as::<_, Turn>(
@@ -24,6 +89,7 @@ pub async fn get_turns_by_game_id(pool: &PgPool, game_id: Uuid) -> cja::Result<V
             game_id,
             turn_number,
             frame_data,
+            frame_bin,
             created_at
         FROM turns
         WHERE game_id = $1
@@ -58,9 +124,10 @@ pub async fn get_turn_frames_page(
             game_id,
             turn_number,
             frame_data,
+            frame_bin,
             created_at
         FROM turns
-        WHERE game_id = $1 AND frame_data IS NOT NULL
+        WHERE game_id = $1 AND (frame_bin IS NOT NULL OR frame_data IS NOT NULL)
         ORDER BY turn_number ASC
         OFFSET $2
         LIMIT $3
@@ -76,6 +143,31 @@ pub async fn get_turn_frames_page(
     Ok(turns)
 }
 
+/// Get the last turn of a game that has frame data, i.e. the final board
+pub async fn get_last_turn(pool: &PgPool, game_id: Uuid) -> cja::Result<Option<Turn>> {
+    let turn = sqlx::query_as::<_, Turn>(
+        r#"
+        SELECT
+            turn_id,
+            game_id,
+            turn_number,
+            frame_data,
+            frame_bin,
+            created_at
+        FROM turns
+        WHERE game_id = $1 AND (frame_bin IS NOT NULL OR frame_data IS NOT NULL)
+        ORDER BY turn_number DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(game_id)
+    .fetch_optional(pool)
+    .await
+    .wrap_err("Failed to fetch last turn from database")?;
+
+    Ok(turn)
+}
+
 /// Get turns for a game starting from a specific turn number
 /// Used for reconnection catch-up
 pub async fn get_turns_from(
@@ -90,6 +182,7 @@ pub async fn get_turns_from(
             game_id,
             turn_number,
             frame_data,
+            frame_bin,
             created_at
         FROM turns
         WHERE game_id = $1 AND turn_number >= $2
@@ -105,35 +198,29 @@ pub async fn get_turns_from(
     Ok(turns)
 }
 
-/// Create a new turn for a game and notify WebSocket subscribers
+/// Create a new turn for a game, its frame in the compact encoding. The
+/// `turns` insert trigger tells WebSocket subscribers, in whichever
+/// process serves them.
 pub async fn create_turn(
     pool: &PgPool,
-    game_channels: &GameChannels,
     game_id: Uuid,
     turn_number: i32,
-    frame_data: Option<serde_json::Value>,
+    frame: Option<&EngineGameFrame>,
 ) -> cja::Result<Turn> {
     let turn = sqlx::query_as::<_, Turn>(
         r#"
-        INSERT INTO turns (game_id, turn_number, frame_data)
+        INSERT INTO turns (game_id, turn_number, frame_bin)
         VALUES ($1, $2, $3)
-        RETURNING turn_id, game_id, turn_number, frame_data, created_at
+        RETURNING turn_id, game_id, turn_number, frame_data, frame_bin, created_at
         "#,
     )
     .bind(game_id)
     .bind(turn_number)
-    .bind(frame_data)
+    .bind(frame.map(frame_codec::encode))
     .fetch_one(pool)
     .await
     .wrap_err("Failed to create turn")?;
 
-    game_channels
-        .notify(TurnNotification {
-            game_id,
-            turn_number,
-        })
-        .await;
-
     Ok(turn)
 }
 
@@ -141,16 +228,16 @@ pub async fn create_turn(
 pub async fn update_turn_frame_data(
     pool: &PgPool,
     turn_id: Uuid,
-    frame_data: serde_json::Value,
+    frame: &EngineGameFrame,
 ) -> cja::Result<()> {
     sqlx::query!(
         r#"
         UPDATE turns
-        SET frame_data = $2
+        SET frame_bin = $2, frame_data = NULL
         WHERE turn_id = $1
         "#,
         turn_id,
-        frame_data
+        frame_codec::encode(frame)
     )
     .execute(pool)
     .await
@@ -159,6 +246,37 @@ pub async fn update_turn_frame_data(
     Ok(())
 }
 
+/// Why a snake's /move call failed, stored on its snake turn as
+/// `error_kind` (plus `http_status` for non-2xx answers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    Timeout,
+    /// Connection refused, DNS failure, TLS error, ...
+    Network,
+    /// The snake answered with a non-2xx status
+    HttpStatus(u16),
+    /// The body wasn't a move: bad JSON or an unknown direction
+    Parse,
+}
+
+impl MoveError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MoveError::Timeout => "timeout",
+            MoveError::Network => "network",
+            MoveError::HttpStatus(_) => "http_status",
+            MoveError::Parse => "parse",
+        }
+    }
+
+    pub fn http_status(&self) -> Option<i16> {
+        match self {
+            MoveError::HttpStatus(status) => Some(*status as i16),
+            _ => None,
+        }
+    }
+}
+
 /// A snake's move for a specific turn
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SnakeTurn {
@@ -179,19 +297,23 @@ pub async fn create_snake_turn(
     direction: &str,
     latency_ms: Option<i64>,
     timed_out: bool,
+    error: Option<MoveError>,
 ) -> cja::Result<SnakeTurn> {
     let latency_i32 = latency_ms.map(|ms| ms as i32);
     let row = sqlx::query!(
         r#"
-        INSERT INTO snake_turns (turn_id, game_battlesnake_id, direction, latency_ms, timed_out)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO snake_turns
+            (turn_id, game_battlesnake_id, direction, latency_ms, timed_out, error_kind, http_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING snake_turn_id, turn_id, game_battlesnake_id, direction, latency_ms, timed_out, created_at
         "#,
         turn_id,
         game_battlesnake_id,
         direction,
         latency_i32,
-        timed_out
+        timed_out,
+        error.map(|e| e.kind()),
+        error.and_then(|e| e.http_status()),
     )
     .fetch_one(pool)
     .await
@@ -208,6 +330,94 @@ pub async fn create_snake_turn(
     })
 }
 
+/// A played turn the game runner hasn't written yet
+#[derive(Debug)]
+pub struct PendingTurn {
+    pub turn_number: i32,
+    pub frame: EngineGameFrame,
+    pub moves: Vec<PendingSnakeTurn>,
+}
+
+/// One snake's move in a [`PendingTurn`]
+#[derive(Debug)]
+pub struct PendingSnakeTurn {
+    pub game_battlesnake_id: Uuid,
+    pub direction: String,
+    pub latency_ms: Option<i64>,
+    pub timed_out: bool,
+    pub error: Option<MoveError>,
+}
+
+/// Write a batch of turns and their snake turns in a single statement.
+///
+/// Turn ids are generated here rather than by the database so the snake
+/// turns can point at them in the same round trip. Each turn still fires
+/// its own insert notification, so live viewers see every frame.
+pub async fn insert_turns(pool: &PgPool, game_id: Uuid, turns: &[PendingTurn]) -> cja::Result<()> {
+    if turns.is_empty() {
+        return Ok(());
+    }
+
+    let mut turn_ids = Vec::with_capacity(turns.len());
+    let mut turn_numbers = Vec::with_capacity(turns.len());
+    let mut frame_bins = Vec::with_capacity(turns.len());
+    let mut move_turn_ids = Vec::new();
+    let mut game_battlesnake_ids = Vec::new();
+    let mut directions = Vec::new();
+    let mut latencies = Vec::new();
+    let mut timed_outs = Vec::new();
+    let mut error_kinds = Vec::new();
+    let mut http_statuses = Vec::new();
+
+    for turn in turns {
+        let turn_id = Uuid::new_v4();
+        turn_ids.push(turn_id);
+        turn_numbers.push(turn.turn_number);
+        frame_bins.push(frame_codec::encode(&turn.frame));
+        for snake_turn in &turn.moves {
+            move_turn_ids.push(turn_id);
+            game_battlesnake_ids.push(snake_turn.game_battlesnake_id);
+            directions.push(snake_turn.direction.as_str());
+            latencies.push(snake_turn.latency_ms.map(|ms| ms as i32));
+            timed_outs.push(snake_turn.timed_out);
+            error_kinds.push(snake_turn.error.map(|e| e.kind()));
+            http_statuses.push(snake_turn.error.and_then(|e| e.http_status()));
+        }
+    }
+
+    // Bound with runtime checks: the query macros can't type arrays with
+    // NULL elements
+    sqlx::query(
+        r#"
+        WITH new_turns AS (
+            INSERT INTO turns (turn_id, game_id, turn_number, frame_bin)
+            SELECT turn_id, $1, turn_number, frame_bin
+            FROM UNNEST($2::uuid[], $3::int4[], $4::bytea[]) AS t(turn_id, turn_number, frame_bin)
+        )
+        INSERT INTO snake_turns
+            (turn_id, game_battlesnake_id, direction, latency_ms, timed_out, error_kind, http_status)
+        SELECT *
+        FROM UNNEST($5::uuid[], $6::uuid[], $7::text[], $8::int4[], $9::bool[], $10::text[], $11::int2[])
+        "#,
+    )
+    .bind(game_id)
+    .bind(&turn_ids)
+    .bind(&turn_numbers)
+    .bind(&frame_bins)
+    .bind(&move_turn_ids)
+    .bind(&game_battlesnake_ids)
+    .bind(&directions)
+    .bind(&latencies)
+    .bind(&timed_outs)
+    .bind(&error_kinds)
+    .bind(&http_statuses)
+    .execute(pool)
+    .await
+    .wrap_err_with(|| format!("Failed to write {} turns", turns.len()))?;
+
+    Ok(())
+}
+
 /// Get all snake turns for a specific turn
 pub async fn get_snake_turns_by_turn_id(
     pool: &PgPool,
@@ -248,6 +458,74 @@ pub async fn get_snake_turns_by_turn_id(
     Ok(turns)
 }
 
+/// When a game's first and last turns were persisted
+#[derive(Debug)]
+pub struct TurnSpan {
+    pub turns: i64,
+    pub first_turn_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_turn_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Turn count and first/last turn timestamps for a game. The first turn is
+/// the closest thing to a "started at" the games table doesn't record.
+pub async fn get_turn_span(pool: &PgPool, game_id: Uuid) -> cja::Result<TurnSpan> {
+    sqlx::query_as!(
+        TurnSpan,
+        r#"
+        SELECT
+            COUNT(*) as "turns!",
+            MIN(created_at) as first_turn_at,
+            MAX(created_at) as last_turn_at
+        FROM turns
+        WHERE game_id = $1
+        "#,
+        game_id
+    )
+    .fetch_one(pool)
+    .await
+    .wrap_err("Failed to fetch turn span")
+}
+
+/// Response-time aggregates for one snake across a whole game
+#[derive(Debug, Serialize)]
+pub struct SnakeLatencyStats {
+    pub game_battlesnake_id: Uuid,
+    pub moves: i64,
+    pub timeouts: i64,
+    pub avg_latency_ms: Option<f64>,
+    pub p95_latency_ms: Option<f64>,
+    pub max_latency_ms: Option<i32>,
+}
+
+/// Aggregate move latency per snake for a game
+pub async fn get_latency_stats_by_game_id(
+    pool: &PgPool,
+    game_id: Uuid,
+) -> cja::Result<Vec<SnakeLatencyStats>> {
+    let stats = sqlx::query_as!(
+        SnakeLatencyStats,
+        r#"
+        SELECT
+            st.game_battlesnake_id,
+            COUNT(*) as "moves!",
+            COUNT(*) FILTER (WHERE st.timed_out) as "timeouts!",
+            AVG(st.latency_ms)::FLOAT8 as avg_latency_ms,
+            percentile_cont(0.95) WITHIN GROUP (ORDER BY st.latency_ms) as p95_latency_ms,
+            MAX(st.latency_ms) as max_latency_ms
+        FROM snake_turns st
+        JOIN turns t ON t.turn_id = st.turn_id
+        WHERE t.game_id = $1
+        GROUP BY st.game_battlesnake_id
+        "#,
+        game_id
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to fetch snake latency stats")?;
+
+    Ok(stats)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +537,7 @@ mod tests {
             game_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap(),
             turn_number: 42,
             frame_data: Some(serde_json::json!({"test": "data"})),
+            frame_bin: None,
             created_at: chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
                 .unwrap()
                 .with_timezone(&chrono::Utc),
@@ -300,6 +579,7 @@ mod tests {
             game_id: Uuid::new_v4(),
             turn_number: 5,
             frame_data: Some(frame_data.clone()),
+            frame_bin: None,
             created_at: chrono::Utc::now(),
         };
 
@@ -307,6 +587,37 @@ mod tests {
         assert!(turn.frame_data.as_ref().unwrap()["Snakes"].is_array());
     }
 
+    #[test]
+    fn test_binary_and_json_frames_read_the_same() {
+        let frame = EngineGameFrame {
+            turn: 3,
+            snakes: vec![],
+            food: vec![crate::engine::frame::FrameCoord { x: 1, y: 2 }],
+            hazards: vec![],
+        };
+        let json = serde_json::to_value(&frame).unwrap();
+        let mut turn = Turn {
+            turn_id: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            turn_number: 3,
+            frame_data: None,
+            frame_bin: Some(frame_codec::encode(&frame)),
+            created_at: chrono::Utc::now(),
+        };
+        assert!(turn.has_frame());
+        assert_eq!(turn.frame_json().unwrap(), Some(json.clone()));
+        assert_eq!(turn.frame().unwrap(), Some(frame.clone()));
+
+        turn.frame_bin = None;
+        turn.frame_data = Some(json.clone());
+        assert_eq!(turn.frame_json().unwrap(), Some(json));
+        assert_eq!(turn.frame().unwrap(), Some(frame));
+
+        turn.frame_data = None;
+        assert!(!turn.has_frame());
+        assert_eq!(turn.frame_json().unwrap(), None);
+    }
+
     #[test]
     fn test_snake_turn_struct_serialization() {
         let snake_turn = SnakeTurn {
@@ -355,4 +666,95 @@ mod tests {
         assert!(snake_turn.timed_out);
         assert!(snake_turn.latency_ms.is_none());
     }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_insert_turns_writes_turns_and_moves_in_one_batch(
+        pool: PgPool,
+    ) -> cja::Result<()> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'snake', 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'running')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let game_battlesnake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2)
+             RETURNING game_battlesnake_id",
+        )
+        .bind(game_id)
+        .bind(battlesnake_id)
+        .fetch_one(&pool)
+        .await?;
+
+        let frame = |turn| EngineGameFrame {
+            turn,
+            snakes: Vec::new(),
+            food: Vec::new(),
+            hazards: Vec::new(),
+        };
+        let batch = vec![
+            PendingTurn {
+                turn_number: 0,
+                frame: frame(0),
+                moves: Vec::new(),
+            },
+            PendingTurn {
+                turn_number: 1,
+                frame: frame(1),
+                moves: vec![PendingSnakeTurn {
+                    game_battlesnake_id,
+                    direction: "up".to_string(),
+                    latency_ms: Some(42),
+                    timed_out: false,
+                    error: None,
+                }],
+            },
+            PendingTurn {
+                turn_number: 2,
+                frame: frame(2),
+                moves: vec![PendingSnakeTurn {
+                    game_battlesnake_id,
+                    direction: "up".to_string(),
+                    latency_ms: None,
+                    timed_out: false,
+                    error: Some(MoveError::HttpStatus(502)),
+                }],
+            },
+        ];
+        insert_turns(&pool, game_id, &batch).await?;
+        insert_turns(&pool, game_id, &[]).await?;
+
+        let turns = get_turns_from(&pool, game_id, 0).await?;
+        assert_eq!(
+            turns.iter().map(|t| t.turn_number).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(turns[2].frame()?, Some(frame(2)));
+
+        // Each move is attached to its own turn
+        let first = get_snake_turns_by_turn_id(&pool, turns[1].turn_id).await?;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].latency_ms, Some(42));
+        let errored: (Option<String>, Option<i16>) =
+            sqlx::query_as("SELECT error_kind, http_status FROM snake_turns WHERE turn_id = $1")
+                .bind(turns[2].turn_id)
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(errored, (Some("http_status".to_string()), Some(502)));
+
+        Ok(())
+    }
 }
@@ -21,10 +21,22 @@ pub struct User {
     pub is_admin: bool,
     pub site_theme: String,
     pub theater_theme: String,
+    /// Set while an admin has suspended the account
+    pub suspended_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl User {
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+}
+
+/// `disabled_reason` written on leaderboard entries paused by an account
+/// suspension, so lifting it resumes exactly those.
+pub const DISABLED_REASON_SUSPENDED: &str = "suspended";
+
 /// Valid values for `users.site_theme` (mirrors the DB CHECK constraint).
 pub const SITE_THEMES: [&str; 3] = ["system", "light", "dark"];
 /// Valid values for `users.theater_theme` (mirrors the DB CHECK constraint).
@@ -49,6 +61,7 @@ pub async fn get_user_by_id(pool: &PgPool, user_id: Uuid) -> cja::Result<Option<
             is_admin,
             site_theme,
             theater_theme,
+            suspended_at,
             created_at,
             updated_at
         FROM users
@@ -83,6 +96,7 @@ pub async fn get_user_by_github_login(pool: &PgPool, login: &str) -> cja::Result
             is_admin,
             site_theme,
             theater_theme,
+            suspended_at,
             created_at,
             updated_at
         FROM users
@@ -142,6 +156,7 @@ pub async fn create_or_update_user(
             is_admin,
             site_theme,
             theater_theme,
+            suspended_at,
             created_at,
             updated_at
         "#,
@@ -258,6 +273,127 @@ pub async fn update_theme_preferences(
     Ok(())
 }
 
+/// A row in the admin user search
+#[derive(Debug)]
+pub struct UserSearchResult {
+    pub user_id: Uuid,
+    pub github_login: String,
+    pub display_name: Option<String>,
+    pub is_admin: bool,
+    pub suspended_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub snake_count: i64,
+}
+
+/// Users whose GitHub login or display name contains `query`
+/// (case-insensitive), newest first. An empty query lists the newest users.
+pub async fn search_users(
+    pool: &PgPool,
+    query: &str,
+    limit: i64,
+) -> cja::Result<Vec<UserSearchResult>> {
+    let pattern = format!(
+        "%{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    sqlx::query_as!(
+        UserSearchResult,
+        r#"
+        SELECT
+            u.user_id,
+            u.github_login,
+            u.display_name,
+            u.is_admin,
+            u.suspended_at,
+            u.created_at,
+            (SELECT COUNT(*) FROM battlesnakes b WHERE b.user_id = u.user_id) as "snake_count!"
+        FROM users u
+        WHERE u.github_login ILIKE $1 OR u.display_name ILIKE $1
+        ORDER BY u.created_at DESC, u.user_id
+        LIMIT $2
+        "#,
+        pattern,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .wrap_err("Failed to search users")
+}
+
+/// Grant or revoke admin. Returns `false` if the user doesn't exist.
+pub async fn set_admin(pool: &PgPool, user_id: Uuid, is_admin: bool) -> cja::Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE users SET is_admin = $2 WHERE user_id = $1",
+        user_id,
+        is_admin
+    )
+    .execute(pool)
+    .await
+    .wrap_err("Failed to update admin flag")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Suspend or reinstate an account. Suspending pauses the user's active
+/// leaderboard entries (tagged `'suspended'`); reinstating resumes exactly
+/// those, so entries the owner paused themselves stay paused. Returns
+/// `false` if the user doesn't exist.
+pub async fn set_suspended(pool: &PgPool, user_id: Uuid, suspended: bool) -> cja::Result<bool> {
+    let mut tx = pool.begin().await.wrap_err("Failed to begin transaction")?;
+
+    // Only stamp on the transition so re-suspending keeps the original time
+    let result = sqlx::query!(
+        r#"UPDATE users
+         SET suspended_at = CASE WHEN $2 THEN COALESCE(suspended_at, NOW()) END
+         WHERE user_id = $1"#,
+        user_id,
+        suspended
+    )
+    .execute(&mut *tx)
+    .await
+    .wrap_err("Failed to update suspension")?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    if suspended {
+        sqlx::query!(
+            r#"UPDATE leaderboard_entries le
+             SET disabled_at = NOW(), disabled_reason = $2, updated_at = NOW()
+             FROM battlesnakes b
+             WHERE b.battlesnake_id = le.battlesnake_id
+               AND b.user_id = $1
+               AND le.disabled_at IS NULL"#,
+            user_id,
+            DISABLED_REASON_SUSPENDED
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to pause leaderboard entries")?;
+    } else {
+        sqlx::query!(
+            r#"UPDATE leaderboard_entries le
+             SET disabled_at = NULL, disabled_reason = NULL, updated_at = NOW()
+             FROM battlesnakes b
+             WHERE b.battlesnake_id = le.battlesnake_id
+               AND b.user_id = $1
+               AND le.disabled_reason = $2"#,
+            user_id,
+            DISABLED_REASON_SUSPENDED
+        )
+        .execute(&mut *tx)
+        .await
+        .wrap_err("Failed to resume leaderboard entries")?;
+    }
+
+    tx.commit().await.wrap_err("Failed to commit suspension")?;
+
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,4 +507,86 @@ mod tests {
                 .is_ok()
         );
     }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn search_users_matches_login_and_display_name(pool: PgPool) -> cja::Result<()> {
+        let first = create_user(&pool, 1).await?;
+        create_user(&pool, 2).await?;
+        update_profile_fields(&pool, first, "Coiled_Cobra", "", "", "").await?;
+
+        let by_login = search_users(&pool, "GH-USER-2", 10).await?;
+        assert_eq!(by_login.len(), 1);
+        assert_eq!(by_login[0].github_login, "gh-user-2");
+
+        let by_name = search_users(&pool, "cobra", 10).await?;
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].user_id, first);
+
+        // LIKE wildcards in the query are literal
+        assert!(search_users(&pool, "user%1", 10).await?.is_empty());
+        assert_eq!(search_users(&pool, "", 10).await?.len(), 2);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn suspension_pauses_and_resumes_only_its_own_entries(pool: PgPool) -> cja::Result<()> {
+        let user_id = create_user(&pool, 7).await?;
+        let mut entry_ids = Vec::new();
+        for name in ["active", "paused"] {
+            let snake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, $2, 'http://example.com')
+                 RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            let leaderboard_id: Uuid = sqlx::query_scalar(
+                "SELECT leaderboard_id FROM leaderboards WHERE name = 'Standard 11x11'",
+            )
+            .fetch_one(&pool)
+            .await?;
+            let entry =
+                crate::models::leaderboard::get_or_create_entry(&pool, leaderboard_id, snake_id)
+                    .await?;
+            entry_ids.push(entry.leaderboard_entry_id);
+        }
+        crate::models::leaderboard::set_disabled(&pool, entry_ids[1], Some(chrono::Utc::now()))
+            .await?;
+
+        assert!(set_suspended(&pool, user_id, true).await?);
+        let user = get_user_by_id(&pool, user_id).await?.unwrap();
+        assert!(user.suspended_at.is_some());
+        let reasons: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT disabled_reason FROM leaderboard_entries WHERE leaderboard_entry_id = ANY($1)
+             ORDER BY leaderboard_entry_id = $2 DESC",
+        )
+        .bind(&entry_ids)
+        .bind(entry_ids[0])
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(reasons, vec![Some("suspended".to_string()), None]);
+
+        assert!(set_suspended(&pool, user_id, false).await?);
+        let still_disabled: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT leaderboard_entry_id FROM leaderboard_entries
+             WHERE leaderboard_entry_id = ANY($1) AND disabled_at IS NOT NULL",
+        )
+        .bind(&entry_ids)
+        .fetch_all(&pool)
+        .await?;
+        assert_eq!(still_disabled, vec![entry_ids[1]]);
+        assert!(
+            get_user_by_id(&pool, user_id)
+                .await?
+                .unwrap()
+                .suspended_at
+                .is_none()
+        );
+
+        assert!(!set_suspended(&pool, Uuid::new_v4(), true).await?);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,510 @@
+//! Raising notifications about users' snakes.
+//!
+//! Each event is recorded once per owner (see
+//! [`notification::NewNotification::dedup_key`]) and delivered the ways the
+//! owner asked for: in their `/notifications` inbox, by email, or both.
+//! Rank milestones come out of the rating update; timeout streaks are
+//! checked by [`crate::jobs::GameNotificationsJob`] after every game.
+//!
+//! Owners with their own Discord webhook also get an embed there for each
+//! leaderboard game their snakes finish, big rank moves, and health pauses.
+
+use cja::jobs::Job;
+use color_eyre::eyre::Context as _;
+use uuid::Uuid;
+
+use crate::discord::{COLOR_BAD, COLOR_GOOD, COLOR_NEUTRAL, Embed};
+use crate::jobs::UserWebhookJob;
+use crate::models::{
+    battlesnake::Battlesnake,
+    discord_webhook, game_battlesnake,
+    leaderboard::{self, EntryOwner, EntryStanding},
+    notification::{self, NewNotification, NotificationKind},
+    snake_error, snake_health_status,
+};
+use crate::state::AppState;
+
+/// Moving into (or out of) this rank or better is a rank milestone
+pub const MILESTONE_RANK: i64 = 10;
+
+/// Finished games in a row with a timed-out move before the owner hears
+/// about it
+pub const TIMEOUT_STREAK_GAMES: usize = 5;
+
+/// Places a snake has to move in one game before its owner's Discord
+/// webhook hears about it
+pub const DISCORD_RANK_CHANGE: i64 = 5;
+
+/// Record a notification about `battlesnake_id` for its owner and send it
+/// the ways they asked for. A no-op for events already recorded.
+async fn deliver(
+    app_state: &AppState,
+    battlesnake_id: Uuid,
+    subject: &str,
+    notification: NewNotification,
+) -> cja::Result<()> {
+    let preferences = notification::get_preferences(&app_state.db, notification.user_id).await?;
+    let (in_app, email) = preferences.channels(notification.kind);
+    if !in_app && !email {
+        return Ok(());
+    }
+
+    if !notification::record(&app_state.db, &notification, in_app).await? || !email {
+        return Ok(());
+    }
+
+    match snake_health_status::owner_notification_email(&app_state.db, battlesnake_id).await? {
+        Some(to) => {
+            let base_url = &app_state.config.base_url;
+            let link = notification.link.as_deref().unwrap_or("/notifications");
+            app_state.mailer.notify_snake_notification(
+                &app_state.db,
+                app_state.config.email_per_recipient_hourly_limit,
+                &to,
+                subject,
+                &notification.message,
+                &format!("{base_url}{link}"),
+                &format!("{base_url}/settings/notifications"),
+            );
+        }
+        None => {
+            tracing::warn!(
+                battlesnake_id = %battlesnake_id,
+                kind = notification.kind.as_str(),
+                "Owner wants notification email but has no known address; skipping"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Tell owners whose snakes moved into or out of the top
+/// [`MILESTONE_RANK`] when a leaderboard game was rated. `before` and
+/// `after` are the entries placed within it either side of the update.
+pub async fn notify_rank_milestones(
+    app_state: &AppState,
+    leaderboard_game_id: Uuid,
+    before: &[Uuid],
+    after: &[Uuid],
+) -> cja::Result<()> {
+    let entered: Vec<Uuid> = after
+        .iter()
+        .filter(|id| !before.contains(id))
+        .copied()
+        .collect();
+    let left: Vec<Uuid> = before
+        .iter()
+        .filter(|id| !after.contains(id))
+        .copied()
+        .collect();
+    if entered.is_empty() && left.is_empty() {
+        return Ok(());
+    }
+
+    let owners =
+        leaderboard::get_entry_owners(&app_state.db, &[entered.clone(), left].concat()).await?;
+    for owner in owners {
+        let kind = if entered.contains(&owner.leaderboard_entry_id) {
+            NotificationKind::TopTenEntered
+        } else {
+            NotificationKind::TopTenLeft
+        };
+        let (subject, message) = rank_milestone_copy(kind, &owner);
+        let notification = NewNotification {
+            user_id: owner.user_id,
+            kind,
+            message,
+            link: Some(format!("/leaderboards/{}", owner.leaderboard_id)),
+            dedup_key: format!(
+                "{}:{leaderboard_game_id}:{}",
+                kind.as_str(),
+                owner.leaderboard_entry_id
+            ),
+        };
+        deliver(app_state, owner.battlesnake_id, &subject, notification).await?;
+    }
+
+    Ok(())
+}
+
+fn rank_milestone_copy(kind: NotificationKind, owner: &EntryOwner) -> (String, String) {
+    let (snake, board) = (&owner.battlesnake_name, &owner.leaderboard_name);
+    if kind == NotificationKind::TopTenEntered {
+        (
+            format!("{snake} entered the {board} top {MILESTONE_RANK}"),
+            format!("{snake} moved into the top {MILESTONE_RANK} on {board}."),
+        )
+    } else {
+        (
+            format!("{snake} dropped out of the {board} top {MILESTONE_RANK}"),
+            format!("{snake} dropped out of the top {MILESTONE_RANK} on {board}."),
+        )
+    }
+}
+
+/// The game that completed a streak of exactly [`TIMEOUT_STREAK_GAMES`]
+/// games with timeouts, given a snake's recent games newest first. `None`
+/// when the streak is shorter, or longer (the owner already heard about it).
+fn completed_timeout_streak(recent: &[(Uuid, bool)]) -> Option<Uuid> {
+    let streak = recent
+        .iter()
+        .take_while(|(_, timed_out)| *timed_out)
+        .count();
+    (streak == TIMEOUT_STREAK_GAMES).then(|| recent[0].0)
+}
+
+/// Tell the owners of snakes in a finished game if it made
+/// [`TIMEOUT_STREAK_GAMES`] straight games their snake timed out in
+pub async fn notify_timeout_streaks(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
+    let snakes = game_battlesnake::get_battlesnakes_by_game_id(&app_state.db, game_id).await?;
+
+    for snake in snakes {
+        let recent = snake_error::get_recent_game_timeouts(
+            &app_state.db,
+            snake.battlesnake_id,
+            TIMEOUT_STREAK_GAMES as i64 + 1,
+        )
+        .await?;
+        let Some(last_game_id) = completed_timeout_streak(&recent) else {
+            continue;
+        };
+
+        let notification = NewNotification {
+            user_id: snake.user_id,
+            kind: NotificationKind::TimeoutStreak,
+            message: format!(
+                "{} timed out in {TIMEOUT_STREAK_GAMES} games in a row. Check its server, \
+                 or use the test console on its profile to see how it's responding.",
+                snake.name
+            ),
+            link: Some(format!("/battlesnakes/{}/profile", snake.battlesnake_id)),
+            dedup_key: format!("timeout_streak:{last_game_id}:{}", snake.battlesnake_id),
+        };
+        let subject = format!(
+            "{} timed out in {TIMEOUT_STREAK_GAMES} straight games",
+            snake.name
+        );
+        deliver(app_state, snake.battlesnake_id, &subject, notification).await?;
+    }
+
+    Ok(())
+}
+
+fn ordinal(n: i32) -> String {
+    match n {
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        _ => format!("{n}th"),
+    }
+}
+
+fn rank_text(rank: Option<i64>) -> String {
+    rank.map_or_else(|| "Unranked".to_string(), |rank| format!("#{rank}"))
+}
+
+fn game_result_embed(
+    owner: &EntryOwner,
+    placement: i32,
+    snake_count: usize,
+    before: &EntryStanding,
+    after: &EntryStanding,
+    replay_url: &str,
+) -> Embed {
+    let delta = after.display_score - before.display_score;
+    let rank = if before.rank == after.rank {
+        rank_text(after.rank)
+    } else {
+        format!("{} → {}", rank_text(before.rank), rank_text(after.rank))
+    };
+    Embed {
+        title: format!(
+            "{} finished {} of {snake_count} on {}",
+            owner.battlesnake_name,
+            ordinal(placement),
+            owner.leaderboard_name
+        ),
+        description: Some("Watch the replay".to_string()),
+        url: Some(replay_url.to_string()),
+        color: if delta > 0.0 {
+            COLOR_GOOD
+        } else if delta < 0.0 {
+            COLOR_BAD
+        } else {
+            COLOR_NEUTRAL
+        },
+        fields: vec![
+            (
+                "Rating".to_string(),
+                format!(
+                    "{:.1} → {:.1} ({delta:+.1})",
+                    before.display_score, after.display_score
+                ),
+            ),
+            ("Rank".to_string(), rank),
+        ],
+    }
+}
+
+/// An embed for a move of at least [`DISCORD_RANK_CHANGE`] places between
+/// two ranked standings, or `None` for a smaller move
+fn rank_change_embed(
+    owner: &EntryOwner,
+    before: &EntryStanding,
+    after: &EntryStanding,
+    leaderboard_url: &str,
+) -> Option<Embed> {
+    let (Some(from), Some(to)) = (before.rank, after.rank) else {
+        return None;
+    };
+    let places = from - to;
+    if places.abs() < DISCORD_RANK_CHANGE {
+        return None;
+    }
+
+    let (snake, board) = (&owner.battlesnake_name, &owner.leaderboard_name);
+    let (title, description, color) = if places > 0 {
+        (
+            format!("{snake} climbed to #{to} on {board}"),
+            format!("Up {places} places from #{from}."),
+            COLOR_GOOD,
+        )
+    } else {
+        (
+            format!("{snake} fell to #{to} on {board}"),
+            format!("Down {} places from #{from}.", -places),
+            COLOR_BAD,
+        )
+    };
+    Some(Embed {
+        title,
+        description: Some(description),
+        url: Some(leaderboard_url.to_string()),
+        color,
+        fields: Vec::new(),
+    })
+}
+
+/// Post a rated leaderboard game to the Discord webhooks of the owners of
+/// the snakes in it: the result for owners who want game results, and big
+/// rank moves for owners who want those. `placements` pairs each entry in
+/// the game with where it finished; `before` and `after` are the entries'
+/// standings either side of the rating update.
+pub async fn notify_discord_game_results(
+    app_state: &AppState,
+    game_id: Uuid,
+    placements: &[(Uuid, i32)],
+    before: &[EntryStanding],
+    after: &[EntryStanding],
+) -> cja::Result<()> {
+    let entry_ids: Vec<Uuid> = placements.iter().map(|(id, _)| *id).collect();
+    let owners = leaderboard::get_entry_owners(&app_state.db, &entry_ids).await?;
+    let user_ids: Vec<Uuid> = owners.iter().map(|o| o.user_id).collect();
+    let webhooks = discord_webhook::get_for_users(&app_state.db, &user_ids).await?;
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let base_url = &app_state.config.base_url;
+    let standing = |standings: &[EntryStanding], entry_id: Uuid| {
+        standings
+            .iter()
+            .find(|s| s.leaderboard_entry_id == entry_id)
+            .cloned()
+    };
+
+    for owner in &owners {
+        let Some(webhook) = webhooks.iter().find(|w| w.user_id == owner.user_id) else {
+            continue;
+        };
+        let entry_id = owner.leaderboard_entry_id;
+        let (Some((_, placement)), Some(before), Some(after)) = (
+            placements.iter().find(|(id, _)| *id == entry_id),
+            standing(before, entry_id),
+            standing(after, entry_id),
+        ) else {
+            continue;
+        };
+
+        if webhook.notify_game_results {
+            let embed = game_result_embed(
+                owner,
+                *placement,
+                placements.len(),
+                &before,
+                &after,
+                &format!("{base_url}/games/{game_id}"),
+            );
+            enqueue_user_webhook(app_state, webhook.webhook_url.clone(), embed).await?;
+        }
+        if webhook.notify_rank_changes
+            && let Some(embed) = rank_change_embed(
+                owner,
+                &before,
+                &after,
+                &format!("{base_url}/leaderboards/{}", owner.leaderboard_id),
+            )
+        {
+            enqueue_user_webhook(app_state, webhook.webhook_url.clone(), embed).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tell a snake's owner on their Discord webhook that the health sweeper
+/// paused it from leaderboard matchmaking
+pub async fn notify_discord_snake_paused(
+    app_state: &AppState,
+    snake: &Battlesnake,
+    failure_summary: &str,
+    profile_url: &str,
+) -> cja::Result<()> {
+    let Some(webhook) = discord_webhook::get_for_user(&app_state.db, snake.user_id).await? else {
+        return Ok(());
+    };
+    if !webhook.notify_pauses {
+        return Ok(());
+    }
+
+    let embed = Embed {
+        title: format!("{} was paused from leaderboard matchmaking", snake.name),
+        description: Some(
+            "Its server kept failing health checks. Its ratings are safe; \
+             resume matchmaking from its profile once it's fixed."
+                .to_string(),
+        ),
+        url: Some(profile_url.to_string()),
+        color: COLOR_BAD,
+        fields: vec![(
+            "Most recent problem".to_string(),
+            failure_summary.to_string(),
+        )],
+    };
+    enqueue_user_webhook(app_state, webhook.webhook_url, embed).await
+}
+
+/// Queue an embed for a user's own Discord webhook. It's delivered by a
+/// [`UserWebhookJob`], so a slow or failing webhook gets the job queue's
+/// retries instead of a detached task.
+async fn enqueue_user_webhook(
+    app_state: &AppState,
+    webhook_url: String,
+    embed: Embed,
+) -> cja::Result<()> {
+    let context = format!("discord webhook: {}", embed.title);
+    UserWebhookJob { webhook_url, embed }
+        .enqueue(app_state.clone(), context, None)
+        .await
+        .wrap_err("Failed to enqueue Discord webhook notification")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner() -> EntryOwner {
+        EntryOwner {
+            leaderboard_entry_id: Uuid::new_v4(),
+            leaderboard_id: Uuid::new_v4(),
+            leaderboard_name: "Standard".to_string(),
+            battlesnake_id: Uuid::new_v4(),
+            battlesnake_name: "Hissy".to_string(),
+            user_id: Uuid::new_v4(),
+        }
+    }
+
+    fn standing(owner: &EntryOwner, display_score: f64, rank: Option<i64>) -> EntryStanding {
+        EntryStanding {
+            leaderboard_entry_id: owner.leaderboard_entry_id,
+            display_score,
+            rank,
+        }
+    }
+
+    #[test]
+    fn test_game_result_embed() {
+        let owner = owner();
+        let embed = game_result_embed(
+            &owner,
+            2,
+            4,
+            &standing(&owner, 20.0, Some(12)),
+            &standing(&owner, 21.5, Some(9)),
+            "https://arena.example.com/games/abc",
+        );
+        assert_eq!(embed.title, "Hissy finished 2nd of 4 on Standard");
+        assert_eq!(
+            embed.url.as_deref(),
+            Some("https://arena.example.com/games/abc")
+        );
+        assert_eq!(embed.color, COLOR_GOOD);
+        assert_eq!(
+            embed.fields,
+            vec![
+                ("Rating".to_string(), "20.0 → 21.5 (+1.5)".to_string()),
+                ("Rank".to_string(), "#12 → #9".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_change_embed_needs_a_big_move_between_ranks() {
+        let owner = owner();
+        let url = "https://arena.example.com/leaderboards/abc";
+        let embed = |from, to| {
+            rank_change_embed(
+                &owner,
+                &standing(&owner, 0.0, from),
+                &standing(&owner, 0.0, to),
+                url,
+            )
+        };
+
+        assert_eq!(embed(Some(10), Some(6)), None);
+        assert_eq!(embed(None, Some(3)), None);
+        let climbed = embed(Some(10), Some(5)).unwrap();
+        assert_eq!(climbed.title, "Hissy climbed to #5 on Standard");
+        assert_eq!(
+            climbed.description.as_deref(),
+            Some("Up 5 places from #10.")
+        );
+        let fell = embed(Some(3), Some(20)).unwrap();
+        assert_eq!(fell.title, "Hissy fell to #20 on Standard");
+        assert_eq!(fell.color, COLOR_BAD);
+    }
+
+    #[test]
+    fn test_completed_timeout_streak_fires_once() {
+        let games: Vec<Uuid> = (0..=TIMEOUT_STREAK_GAMES).map(|_| Uuid::new_v4()).collect();
+        let with = |timeouts: usize| -> Vec<(Uuid, bool)> {
+            games
+                .iter()
+                .enumerate()
+                .map(|(i, id)| (*id, i < timeouts))
+                .collect()
+        };
+
+        assert_eq!(completed_timeout_streak(&with(0)), None);
+        assert_eq!(
+            completed_timeout_streak(&with(TIMEOUT_STREAK_GAMES - 1)),
+            None
+        );
+        assert_eq!(
+            completed_timeout_streak(&with(TIMEOUT_STREAK_GAMES)),
+            Some(games[0])
+        );
+        // Still timing out a game later: already notified
+        assert_eq!(
+            completed_timeout_streak(&with(TIMEOUT_STREAK_GAMES + 1)),
+            None
+        );
+        // A new snake whose only games all timed out
+        assert_eq!(
+            completed_timeout_streak(&with(TIMEOUT_STREAK_GAMES)[..TIMEOUT_STREAK_GAMES]),
+            Some(games[0])
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Open Graph preview images: a game's board and a leaderboard's podium,
+//! drawn as PNGs at the 1200x630 size link unfurlers (Discord, Slack)
+//! expect. Everything is flat rectangles, so there's no font or image
+//! library involved; titles and names come from the page's meta tags.
+
+use std::io::Write as _;
+
+use color_eyre::eyre::Context as _;
+use flate2::{Compression, write::ZlibEncoder};
+use uuid::Uuid;
+
+use crate::{
+    customizations::chip_color,
+    engine_models::{EngineGameFrame, Point},
+};
+
+pub const WIDTH: u32 = 1200;
+pub const HEIGHT: u32 = 630;
+
+/// Space kept clear around the board
+const MARGIN: i64 = 40;
+
+const BACKGROUND: Rgb = Rgb(0x16, 0x1b, 0x22);
+const CELL: Rgb = Rgb(0x24, 0x2b, 0x35);
+const HAZARD: Rgb = Rgb(0x4a, 0x3a, 0x5c);
+const FOOD: Rgb = Rgb(0xff, 0x5a, 0x6e);
+const STEP: Rgb = Rgb(0x2e, 0x37, 0x43);
+const PIP: Rgb = Rgb(0xe8, 0xea, 0xed);
+
+/// A cached preview, by what it shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreviewKey {
+    /// `spoilers` previews show the final board, the rest the opening one
+    Game {
+        game_id: Uuid,
+        spoilers: bool,
+    },
+    Leaderboard(Uuid),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rgb(u8, u8, u8);
+
+impl Rgb {
+    /// A snake's declared color, with the same gray fallback as its chip
+    fn snake(declared: Option<&str>) -> Self {
+        let hex = chip_color(declared.unwrap_or_default());
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|c| u8::from_str_radix(c, 16).ok())
+                .unwrap_or(0x88)
+        };
+        Self(channel(1), channel(3), channel(5))
+    }
+
+    /// Mix `amount` (0 to 1) of `other` into this color
+    fn blend(self, other: Self, amount: f32) -> Self {
+        let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * amount) as u8;
+        Self(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    /// Packed RGB, row by row from the top
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, fill: Rgb) -> Self {
+        let pixels = [fill.0, fill.1, fill.2].repeat((width * height) as usize);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Fill a rectangle, clipped to the canvas
+    fn fill_rect(&mut self, x: i64, y: i64, w: i64, h: i64, color: Rgb) {
+        let (x0, x1) = (x.max(0), (x + w).min(i64::from(self.width)));
+        let (y0, y1) = (y.max(0), (y + h).min(i64::from(self.height)));
+        for row in y0..y1 {
+            for col in x0..x1 {
+                let i = ((row * i64::from(self.width) + col) * 3) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn pixel(&self, x: u32, y: u32) -> Rgb {
+        let i = ((y * self.width + x) * 3) as usize;
+        Rgb(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2])
+    }
+
+    /// An 8-bit RGB PNG with one zlib stream and no scanline filtering
+    fn encode_png(&self) -> cja::Result<Vec<u8>> {
+        let mut raw = Vec::with_capacity(((self.width * 3 + 1) * self.height) as usize);
+        for row in self.pixels.chunks_exact(self.width as usize * 3) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&raw)
+            .wrap_err("Failed to compress preview image")?;
+        let data = encoder
+            .finish()
+            .wrap_err("Failed to compress preview image")?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // Bit depth 8, truecolor, deflate, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &data);
+        write_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(data);
+    png.extend_from_slice(&crc.finalize().to_be_bytes());
+}
+
+/// A `width` x `height` board as of `frame` (empty without one), with
+/// food, hazards, and the snakes still alive in their colors
+pub fn render_board(
+    width: u32,
+    height: u32,
+    frame: Option<&EngineGameFrame>,
+) -> cja::Result<Vec<u8>> {
+    board_canvas(width, height, frame).encode_png()
+}
+
+fn board_canvas(width: u32, height: u32, frame: Option<&EngineGameFrame>) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT, BACKGROUND);
+    let (cols, rows) = (i64::from(width.max(1)), i64::from(height.max(1)));
+    let cell = ((i64::from(WIDTH) - 2 * MARGIN) / cols)
+        .min((i64::from(HEIGHT) - 2 * MARGIN) / rows)
+        .max(1);
+    let left = (i64::from(WIDTH) - cell * cols) / 2;
+    let top = (i64::from(HEIGHT) - cell * rows) / 2;
+    let gap = (cell / 10).max(1);
+    let inset = cell / 3;
+
+    // Top-left pixel of a board point. Battlesnake's (0, 0) is the bottom
+    // left; points off the board aren't drawn.
+    let origin = |p: &Point| {
+        let (x, y) = (i64::from(p.x), i64::from(p.y));
+        ((0..cols).contains(&x) && (0..rows).contains(&y))
+            .then(|| (left + x * cell, top + (rows - 1 - y) * cell))
+    };
+
+    for x in 0..cols {
+        for y in 0..rows {
+            canvas.fill_rect(
+                left + x * cell + gap,
+                top + y * cell + gap,
+                cell - 2 * gap,
+                cell - 2 * gap,
+                CELL,
+            );
+        }
+    }
+    let Some(frame) = frame else {
+        return canvas;
+    };
+
+    for (x, y) in frame.hazards.iter().filter_map(origin) {
+        canvas.fill_rect(x + gap, y + gap, cell - 2 * gap, cell - 2 * gap, HAZARD);
+    }
+    for (x, y) in frame.food.iter().filter_map(origin) {
+        canvas.fill_rect(
+            x + inset,
+            y + inset,
+            cell - 2 * inset,
+            cell - 2 * inset,
+            FOOD,
+        );
+    }
+    for snake in frame.snakes.iter().filter(|s| s.death.is_none()) {
+        let color = Rgb::snake(snake.color.as_deref());
+        let body: Vec<Option<(i64, i64)>> = snake.body.iter().map(origin).collect();
+        for (i, &segment) in body.iter().enumerate() {
+            let Some((x, y)) = segment else { continue };
+            canvas.fill_rect(x + gap, y + gap, cell - 2 * gap, cell - 2 * gap, color);
+            // Bridge the gap to the next segment so the body reads as one
+            // snake rather than a row of tiles
+            if let Some(&Some((nx, ny))) = body.get(i + 1)
+                && (nx - x).abs() + (ny - y).abs() == cell
+            {
+                canvas.fill_rect(
+                    x.min(nx) + gap,
+                    y.min(ny) + gap,
+                    (nx - x).abs() + cell - 2 * gap,
+                    (ny - y).abs() + cell - 2 * gap,
+                    color,
+                );
+            }
+        }
+        if let Some(&Some((x, y))) = body.first() {
+            canvas.fill_rect(
+                x + inset,
+                y + inset,
+                cell - 2 * inset,
+                cell - 2 * inset,
+                color.blend(BACKGROUND, 0.6),
+            );
+        }
+    }
+    canvas
+}
+
+/// A podium for a leaderboard's top three snakes, given by declared color
+/// in rank order: first on the tall middle step, second to its left, third
+/// to its right. Steps without a snake stand empty.
+pub fn render_podium(colors: [Option<&str>; 3]) -> cja::Result<Vec<u8>> {
+    podium_canvas(colors).encode_png()
+}
+
+/// Each step as (rank, left edge, height)
+const PODIUM_STEPS: [(usize, i64, i64); 3] = [(2, 180, 220), (1, 460, 320), (3, 740, 150)];
+const STEP_WIDTH: i64 = 280;
+const PODIUM_FLOOR: i64 = 570;
+const CHIP: i64 = 140;
+const PIP_SIZE: i64 = 24;
+
+fn podium_canvas(colors: [Option<&str>; 3]) -> Canvas {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT, BACKGROUND);
+    for (rank, left, height) in PODIUM_STEPS {
+        let top = PODIUM_FLOOR - height;
+        canvas.fill_rect(left, top, STEP_WIDTH, height, STEP);
+
+        // One pip per place, since there's no text to number the steps
+        let pips = rank as i64;
+        let pips_width = pips * PIP_SIZE + (pips - 1) * PIP_SIZE / 2;
+        let mut pip_left = left + (STEP_WIDTH - pips_width) / 2;
+        for _ in 0..pips {
+            canvas.fill_rect(pip_left, top + 40, PIP_SIZE, PIP_SIZE, PIP);
+            pip_left += PIP_SIZE * 3 / 2;
+        }
+
+        if let Some(declared) = colors[rank - 1] {
+            let color = Rgb::snake(Some(declared));
+            canvas.fill_rect(
+                left + (STEP_WIDTH - CHIP) / 2,
+                top - CHIP - 20,
+                CHIP,
+                CHIP,
+                color,
+            );
+        }
+    }
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(json: serde_json::Value) -> EngineGameFrame {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn snake_colors_fall_back_like_chips() {
+        assert_eq!(Rgb::snake(Some("#FF8800")), Rgb(0xff, 0x88, 0x00));
+        assert_eq!(
+            Rgb::snake(Some("red;position:fixed")),
+            Rgb(0x88, 0x88, 0x88)
+        );
+        assert_eq!(Rgb::snake(None), Rgb(0x88, 0x88, 0x88));
+    }
+
+    #[test]
+    fn png_has_signature_and_dimensions() {
+        let png = render_board(11, 11, None).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), WIDTH);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), HEIGHT);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn board_draws_live_snakes_and_food_bottom_up() {
+        let frame = frame(serde_json::json!({
+            "Turn": 10,
+            "Food": [{ "X": 0, "Y": 0 }],
+            "Snakes": [
+                {
+                    "ID": "alive",
+                    "Name": "Alive",
+                    "Color": "#00ff00",
+                    "Body": [{ "X": 2, "Y": 0 }, { "X": 1, "Y": 0 }],
+                },
+                {
+                    "ID": "dead",
+                    "Name": "Dead",
+                    "Color": "#0000ff",
+                    "Body": [{ "X": 4, "Y": 4 }],
+                    "Death": { "Cause": "wall-collision", "Turn": 9 },
+                },
+            ],
+        }));
+        let canvas = board_canvas(5, 5, Some(&frame));
+        // 5x5 cells of 110px, centered
+        let center = |x: u32, y: u32| canvas.pixel(325 + x * 110 + 55, 40 + (4 - y) * 110 + 55);
+
+        assert_eq!(center(0, 0), FOOD);
+        assert_eq!(center(1, 0), Rgb(0x00, 0xff, 0x00));
+        assert_eq!(
+            center(2, 0),
+            Rgb(0x00, 0xff, 0x00).blend(BACKGROUND, 0.6),
+            "the head is marked"
+        );
+        // The body is bridged between segments
+        assert_eq!(
+            canvas.pixel(325 + 2 * 110, 40 + 4 * 110 + 55),
+            Rgb(0x00, 0xff, 0x00)
+        );
+        assert_eq!(center(4, 4), CELL, "dead snakes are left off");
+    }
+
+    #[test]
+    fn podium_places_snakes_by_rank() {
+        let canvas = podium_canvas([Some("#ff0000"), Some("#00ff00"), None]);
+        let chip_center = |left: i64, height: i64| {
+            canvas.pixel(
+                (left + STEP_WIDTH / 2) as u32,
+                (PODIUM_FLOOR - height - 20 - CHIP / 2) as u32,
+            )
+        };
+        assert_eq!(chip_center(460, 320), Rgb(0xff, 0x00, 0x00));
+        assert_eq!(chip_center(180, 220), Rgb(0x00, 0xff, 0x00));
+        assert_eq!(chip_center(740, 150), BACKGROUND);
+    }
+}
@@ -0,0 +1,147 @@
+//! Per-request ids. Every request gets one — the caller's `X-Request-Id`
+//! if it sent a sane one, a fresh UUID otherwise — which is echoed back in
+//! the `X-Request-Id` response header, carried by a `request` span (so
+//! every log line and exported span for the request has it), and shown on
+//! error pages and in API error bodies so a user's report can be matched
+//! to the logs.
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument as _;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied id we'll reuse
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST: RequestContext;
+}
+
+/// The request being handled, as seen by this middleware
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub id: String,
+    pub method: String,
+    /// The path without its query string, which may carry secrets
+    pub path: String,
+}
+
+/// The id of the request being handled, if any
+pub fn current() -> Option<String> {
+    REQUEST.try_with(|request| request.id.clone()).ok()
+}
+
+/// The request being handled, if any
+pub fn context() -> Option<RequestContext> {
+    REQUEST.try_with(Clone::clone).ok()
+}
+
+/// The caller's id when it's short and made of safe characters (it ends up
+/// in logs and HTML), otherwise a new one
+fn request_id(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Outermost middleware: assign the id, run the request in its span, and
+/// return the id in the response.
+pub async fn assign_request_id(request: Request, next: Next) -> Response {
+    let context = RequestContext {
+        id: request_id(request.headers()),
+        method: request.method().to_string(),
+        path: request.uri().path().to_string(),
+    };
+    let id = context.id.clone();
+    let span = tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %context.method,
+        path = %context.path,
+    );
+    crate::telemetry::insert_request_id_into_span(&span, id.clone());
+
+    let mut response = REQUEST
+        .scope(context, next.run(request))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn honors_safe_caller_ids_only() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("lb-1234:abc"));
+        assert_eq!(request_id(&headers), "lb-1234:abc");
+
+        headers.insert(
+            REQUEST_ID_HEADER,
+            HeaderValue::from_static("<script>alert(1)</script>"),
+        );
+        let generated = request_id(&headers);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+
+        let long = "a".repeat(MAX_REQUEST_ID_LEN + 1);
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_str(&long).unwrap());
+        assert_ne!(request_id(&headers), long);
+
+        assert!(uuid::Uuid::parse_str(&request_id(&HeaderMap::new())).is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn echoes_id_in_header_and_error_bodies(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let app = crate::routes::routes(crate::state::AppState::test_from_pool(pool));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/api/v1/no-such-thing")
+                    .header(REQUEST_ID_HEADER, "req-42")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-42");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["request_id"], "req-42");
+
+        let response = app
+            .oneshot(
+                Request::get("/robots.txt")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+        assert_eq!(current(), None);
+    }
+}
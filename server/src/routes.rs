@@ -3,20 +3,23 @@ use axum::{
     extract::State,
     http::StatusCode,
     response::{IntoResponse, Redirect},
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
 };
 use color_eyre::eyre::Context as _;
 use maud::{Markup, html};
 use serde::Deserialize;
-use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
     components::page_factory::PageFactory,
     customizations::chip_color,
     errors::ServerResult,
     models::{
-        battlesnake as battlesnake_model,
-        leaderboard::{self as leaderboard_model, ActivityFeedEntry, Leaderboard, RankedEntry},
+        challenge::{self as challenge_model, ChallengeListing},
+        dashboard::{self, RecentPlacement, SnakeHealthWarning, SnakeStanding},
+        leaderboard::{
+            self as leaderboard_model, ActivityFeedEntry, Leaderboard, MIN_GAMES_FOR_RANKING,
+            RankedEntry,
+        },
     },
     state::AppState,
 };
@@ -34,61 +37,65 @@ pub mod admin;
 pub mod api;
 pub mod auth;
 pub mod battlesnake;
+pub mod battlesnake_transfer;
+pub mod challenge;
 pub mod claim;
+pub mod compare;
+pub mod cursor;
 pub mod customizations;
+pub mod feed;
 pub mod game;
 pub mod github_auth;
 pub mod leaderboard;
+pub mod locale;
+pub mod metrics;
+pub mod notifications;
+pub mod play;
 pub mod policy;
+pub mod preview;
 pub mod redirects;
 pub mod saved_games;
 pub mod settings;
+pub mod snakes;
+pub mod timeout;
 pub mod tournament;
 pub mod users;
 
 pub fn routes(app_state: AppState) -> axum::Router {
-    // CORS layer for API routes - allows board.battlesnake.com to access our API
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    // API routes with CORS enabled (for board viewer and CLI/programmatic access)
-    let api_routes = axum::Router::new()
+    // Public, engine-compatible endpoints: open to any origin so
+    // board.battlesnake.com and the GIF exporter can fetch them
+    let public_api_routes = axum::Router::new()
+        // OpenAPI spec + Swagger UI for client authors
+        .route("/openapi.json", get(api::openapi::openapi_json))
+        .route("/docs", get(api::openapi::swagger_ui))
+        // Board viewer endpoints. Unversioned on purpose: the board viewer
+        // and GIF exporter hardcode these paths.
         .route("/games/{id}", get(game::get_game_info))
         .route("/games/{id}/events", get(game::game_events_websocket))
         // Engine-compatible frame history (public, used by the GIF exporter)
         .route("/games/{id}/frames", get(game::get_game_frames))
-        .route("/tokens", post(api::tokens::create_token))
-        .route("/tokens", get(api::tokens::list_tokens))
-        .route("/tokens/{id}", delete(api::tokens::revoke_token))
-        // Snake management endpoints
-        .route("/snakes", get(api::snakes::list_snakes))
-        .route("/snakes", post(api::snakes::create_snake))
-        .route("/snakes/{id}", get(api::snakes::get_snake))
-        .route("/snakes/{id}", put(api::snakes::update_snake))
-        .route("/snakes/{id}", delete(api::snakes::delete_snake))
-        // Games API endpoints (list, create, details)
-        .route("/games", post(api::games::create_game))
-        .route("/games", get(api::games::list_games))
-        .route("/games/{id}/details", get(api::games::show_game))
-        .route("/games/status", post(api::games::batch_game_status))
-        .route("/admin/stats", get(admin::stats_json))
-        // Leaderboard API endpoints
-        .route("/leaderboards", get(api::leaderboards::list_leaderboards))
-        .route(
-            "/leaderboards/{id}/rankings",
-            get(api::leaderboards::get_rankings),
-        )
-        .route(
-            "/leaderboards/{id}/entries",
-            post(api::leaderboards::create_entry),
-        )
-        .route(
-            "/leaderboards/{id}/entries/{battlesnake_id}",
-            delete(api::leaderboards::delete_entry),
+        .layer(api::cors::public_cors());
+
+    // Resource API, CORS-restricted to API_CORS_ALLOWED_ORIGINS
+    let api_cors = api::cors::api_cors(&app_state.config.api_cors_allowed_origins);
+    let api_routes = axum::Router::new()
+        .merge(public_api_routes)
+        .nest("/v1", api_v1_routes().layer(api_cors.clone()))
+        // Deprecated unversioned aliases of /api/v1, kept for existing clients
+        .merge(
+            api_v1_routes()
+                .layer(axum::middleware::from_fn_with_state(
+                    app_state.clone(),
+                    api::versioning::deprecate_unversioned,
+                ))
+                .layer(api_cors),
         )
-        .layer(cors);
+        // Unknown API paths get the JSON error envelope, not the HTML 404
+        .fallback(api_not_found)
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            api::rate_limit::limit_api_writes,
+        ));
 
     let router = axum::Router::new()
         // Public pages
@@ -98,12 +105,38 @@ pub fn routes(app_state: AppState) -> axum::Router {
         .route("/conduct", get(policy::conduct_page))
         .route("/privacy", get(policy::privacy_page))
         .route("/terms", get(policy::terms_page))
+        // Footer language picker
+        .route("/locale", axum::routing::post(locale::set_locale))
         // Public user profiles
         .route("/users/{login}", get(users::show_user_profile))
         // Profile page - requires authentication
         .route("/me", get(profile_page).post(update_profile))
         // Appearance (theme) preference - requires authentication
         .route("/settings/appearance", post(settings::update_appearance))
+        .route("/settings/export", get(settings::export_data))
+        .route("/settings/account", get(settings::account_page))
+        .route("/settings/account/delete", post(settings::delete_account))
+        .route(
+            "/settings/tokens",
+            get(settings::tokens_page).post(settings::create_token),
+        )
+        .route("/settings/tokens/{id}/rotate", post(settings::rotate_token))
+        .route("/settings/tokens/{id}/revoke", post(settings::revoke_token))
+        .route(
+            "/settings/notifications",
+            get(settings::notifications_page).post(settings::update_notifications),
+        )
+        .route(
+            "/settings/discord",
+            get(settings::discord_page).post(settings::update_discord),
+        )
+        .route("/settings/discord/test", post(settings::test_discord))
+        .route("/settings/discord/delete", post(settings::delete_discord))
+        // Notifications inbox - requires authentication
+        .route("/notifications", get(notifications::inbox))
+        .route("/notifications/read", post(notifications::mark_all_read))
+        // Feed of followed snakes and leaderboards - requires authentication
+        .route("/feed", get(feed::show_feed))
         // GitHub OAuth routes
         .route("/auth/github", get(github_auth::github_auth))
         .route(
@@ -122,6 +155,8 @@ pub fn routes(app_state: AppState) -> axum::Router {
             "/claim/email/verify",
             get(claim::email_claim_verify_page).post(claim::complete_email_claim),
         )
+        .route("/snakes", get(snakes::list_snakes))
+        .route("/compare", get(compare::compare_snakes))
         .route("/battlesnakes", get(battlesnake::list_battlesnakes))
         .route("/battlesnakes/new", get(battlesnake::new_battlesnake))
         .route(
@@ -148,13 +183,47 @@ pub fn routes(app_state: AppState) -> axum::Router {
             "/battlesnakes/{id}/test",
             axum::routing::post(battlesnake::test_battlesnake),
         )
+        .route(
+            "/battlesnakes/{id}/console",
+            get(battlesnake::test_console).post(battlesnake::run_test_console),
+        )
         .route(
             "/battlesnakes/{id}/reactivate",
             axum::routing::post(battlesnake::reactivate_battlesnake),
         )
+        .route(
+            "/battlesnakes/{id}/verify",
+            axum::routing::post(battlesnake::verify_battlesnake_url),
+        )
+        .route(
+            "/battlesnakes/{id}/follow",
+            axum::routing::post(feed::follow_snake),
+        )
+        .route(
+            "/battlesnakes/{id}/unfollow",
+            axum::routing::post(feed::unfollow_snake),
+        )
+        .route(
+            "/battlesnakes/{id}/transfer",
+            axum::routing::post(battlesnake_transfer::offer_transfer),
+        )
+        .route(
+            "/transfers/{id}/accept",
+            axum::routing::post(battlesnake_transfer::accept_transfer),
+        )
+        .route(
+            "/transfers/{id}/decline",
+            axum::routing::post(battlesnake_transfer::decline_transfer),
+        )
+        .route(
+            "/transfers/{id}/cancel",
+            axum::routing::post(battlesnake_transfer::cancel_transfer),
+        )
         // Game routes
+        .route("/games", get(game::list_games))
         .route("/games/new", get(game::new_game))
         .route("/games/{id}", get(game::view_game))
+        .route("/games/{id}/preview.png", get(preview::game_preview))
         .route(
             "/games/{id}/rematch",
             axum::routing::post(game::rematch_game),
@@ -188,6 +257,14 @@ pub fn routes(app_state: AppState) -> axum::Router {
         // Leaderboard routes
         .route("/leaderboards", get(leaderboard::list_leaderboards))
         .route("/leaderboards/{id}", get(leaderboard::show_leaderboard))
+        .route(
+            "/leaderboards/{id}/live",
+            get(leaderboard::show_leaderboard_live),
+        )
+        .route(
+            "/leaderboards/{id}/preview.png",
+            get(preview::leaderboard_preview),
+        )
         .route(
             "/leaderboards/{id}/join",
             axum::routing::post(leaderboard::join_leaderboard),
@@ -196,6 +273,14 @@ pub fn routes(app_state: AppState) -> axum::Router {
             "/leaderboards/{id}/leave",
             axum::routing::post(leaderboard::leave_leaderboard),
         )
+        .route(
+            "/leaderboards/{id}/follow",
+            axum::routing::post(feed::follow_leaderboard),
+        )
+        .route(
+            "/leaderboards/{id}/unfollow",
+            axum::routing::post(feed::unfollow_leaderboard),
+        )
         .route(
             "/leaderboards/{id}/entries/{entry_id}",
             get(leaderboard::show_leaderboard_entry),
@@ -245,10 +330,97 @@ pub fn routes(app_state: AppState) -> axum::Router {
             "/tournaments/{id}/import-leaderboard",
             axum::routing::post(tournament::import_leaderboard),
         )
+        // Play-as-a-snake routes
+        .route("/play", get(play::new_play).post(play::create_play))
+        .route("/play/{id}", get(play::show_play))
+        .route("/play/{id}/input", get(play::play_input_websocket))
+        // Challenge routes
+        .route(
+            "/challenges",
+            get(challenge::inbox).post(challenge::create_challenge),
+        )
+        .route("/challenges/new", get(challenge::new_challenge))
+        .route(
+            "/challenges/{id}/accept",
+            axum::routing::post(challenge::accept_challenge),
+        )
+        .route(
+            "/challenges/{id}/decline",
+            axum::routing::post(challenge::decline_challenge),
+        )
+        .route(
+            "/challenges/{id}/cancel",
+            axum::routing::post(challenge::cancel_challenge),
+        )
         // Admin routes
         .route("/admin", get(admin::dashboard))
-        // Game API routes for board viewer (with CORS)
-        .nest("/api", api_routes)
+        .route("/admin/live", get(admin::live))
+        .route("/admin/audit", get(admin::audit::audit_log))
+        .route("/admin/games/{id}", get(admin::games::show_game))
+        .route(
+            "/admin/games/{id}/rerun",
+            axum::routing::post(admin::games::rerun_game),
+        )
+        .route(
+            "/admin/games/{id}/cancel",
+            axum::routing::post(admin::games::cancel_game),
+        )
+        .route(
+            "/admin/games/{id}/delete",
+            axum::routing::post(admin::games::delete_game),
+        )
+        .route(
+            "/admin/settings",
+            get(admin::settings::show_settings).post(admin::settings::update_settings),
+        )
+        .route(
+            "/admin/maintenance",
+            axum::routing::post(admin::maintenance::set_maintenance),
+        )
+        .route(
+            "/admin/leaderboards/{id}/freeze",
+            axum::routing::post(admin::leaderboards::set_frozen),
+        )
+        .route("/admin/moderation", get(admin::moderation::show_moderation))
+        .route(
+            "/admin/moderation/terms",
+            axum::routing::post(admin::moderation::add_term),
+        )
+        .route(
+            "/admin/moderation/terms/{id}/delete",
+            axum::routing::post(admin::moderation::remove_term),
+        )
+        .route(
+            "/admin/moderation/flags/{id}/approve",
+            axum::routing::post(admin::moderation::approve_flag),
+        )
+        .route(
+            "/admin/moderation/flags/{id}/remove",
+            axum::routing::post(admin::moderation::remove_flag),
+        )
+        .route("/admin/snakes/errors", get(admin::snakes::error_report))
+        .route(
+            "/admin/snakes/{id}/errors",
+            get(admin::snakes::snake_errors),
+        )
+        .route(
+            "/admin/snakes/{id}/pause",
+            axum::routing::post(admin::snakes::pause_snake),
+        )
+        .route(
+            "/admin/tournaments",
+            get(admin::tournaments::list_tournaments).post(admin::tournaments::create_tournament),
+        )
+        .route("/admin/users", get(admin::users::list_users))
+        .route("/admin/users/{id}", get(admin::users::show_user))
+        .route(
+            "/admin/users/{id}/admin",
+            axum::routing::post(admin::users::set_admin),
+        )
+        .route(
+            "/admin/users/{id}/suspend",
+            axum::routing::post(admin::users::set_suspended),
+        )
         // Static files
         .route(
             "/static/{*path}",
@@ -256,18 +428,100 @@ pub fn routes(app_state: AppState) -> axum::Router {
         )
         // Internal routes
         .route("/_/version", get(version_page))
+        // Prometheus scrape endpoint (admin auth)
+        .route("/metrics", get(metrics::metrics))
         // Unknown routes get a branded 404 instead of an empty response
-        .fallback(not_found_page);
+        .fallback(not_found_page)
+        // Pages fail fast rather than hang; added before the API so it only
+        // covers the routes above
+        .layer(axum::middleware::from_fn_with_state(
+            std::time::Duration::from_secs(app_state.config.html_request_timeout_secs),
+            timeout::enforce_budget,
+        ))
+        // JSON API (board viewer endpoints + /api/v1)
+        .nest("/api", api_routes);
 
     // Community short links (/docs, /discord, ...) carried over from play
     redirects::register(router)
+        .route_layer(axum::middleware::from_fn(metrics::track_http_latency))
         .layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             inject_trace_context,
         ))
+        // Outermost, so everything above runs inside the request's span
+        .layer(axum::middleware::from_fn(
+            crate::request_id::assign_request_id,
+        ))
         .with_state(app_state)
 }
 
+/// The versioned JSON API, mounted at `/api/v1` (and, deprecated, at `/api`)
+fn api_v1_routes() -> axum::Router<AppState> {
+    axum::Router::new()
+        .route("/tokens", post(api::tokens::create_token))
+        .route("/tokens", get(api::tokens::list_tokens))
+        .route("/tokens/{id}", delete(api::tokens::revoke_token))
+        .route("/tokens/{id}/rotate", post(api::tokens::rotate_token))
+        // Snake management endpoints
+        .route("/snakes", get(api::snakes::list_snakes))
+        .route("/snakes", post(api::snakes::create_snake))
+        .route("/snakes/{id}", get(api::snakes::get_snake))
+        .route("/snakes/{id}", put(api::snakes::update_snake))
+        .route("/snakes/{id}", delete(api::snakes::delete_snake))
+        .route(
+            "/battlesnakes",
+            post(api::battlesnakes::register_battlesnake),
+        )
+        .route(
+            "/battlesnakes/{id}",
+            patch(api::battlesnakes::patch_battlesnake)
+                .delete(api::battlesnakes::delete_battlesnake),
+        )
+        // Games API endpoints (list, create, details)
+        .route("/games", post(api::games::create_game))
+        .route("/games", get(api::games::list_games))
+        .route("/games/{id}/details", get(api::games::show_game))
+        .route("/games/{id}/result", get(api::games::game_result))
+        .route(
+            "/games/status",
+            get(api::games::changed_game_status).post(api::games::batch_game_status),
+        )
+        .route("/admin/stats", get(admin::stats_json))
+        .route("/admin/stats/history", get(admin::stats_history))
+        .route("/admin/jobs", get(api::jobs::list_failing_jobs))
+        .route("/admin/jobs/retry", post(api::jobs::retry_jobs_by_name))
+        .route("/admin/jobs/{id}", delete(api::jobs::delete_job))
+        .route("/admin/jobs/{id}/retry", post(api::jobs::retry_job))
+        .route("/admin/users", get(api::admin_users::search_users))
+        .route(
+            "/admin/users/{id}",
+            get(api::admin_users::show_user).patch(api::admin_users::update_user),
+        )
+        // Leaderboard API endpoints
+        .route("/leaderboards", get(api::leaderboards::list_leaderboards))
+        .route(
+            "/leaderboards/{id}/rankings",
+            get(api::leaderboards::get_rankings),
+        )
+        .route(
+            "/leaderboards/{id}/entries",
+            post(api::leaderboards::create_entry),
+        )
+        .route(
+            "/leaderboards/{id}/entries/{battlesnake_id}",
+            delete(api::leaderboards::delete_entry),
+        )
+        .route(
+            "/leaderboards/{id}/entries/{entry_id}/games",
+            get(api::leaderboards::list_entry_games),
+        )
+        .route("/users/me", get(api::users::get_me))
+}
+
+async fn api_not_found() -> api::error::ApiError {
+    api::error::ApiError::not_found("No such API endpoint")
+}
+
 async fn not_found_page(page_factory: PageFactory) -> impl IntoResponse {
     (
         StatusCode::NOT_FOUND,
@@ -285,6 +539,9 @@ async fn not_found_page(page_factory: PageFactory) -> impl IntoResponse {
                             a class="btn solid" href="/" { "Back to Home" }
                             a class="btn" href="/leaderboards" { "View Leaderboards" }
                         }
+                        @if let Some(request_id) = crate::request_id::current() {
+                            p class="empty" { "Request ID: " code { (request_id) } }
+                        }
                     }
                 }
             }),
@@ -335,64 +592,50 @@ async fn root_page(
     let featured = feed.featured.as_ref();
     let (activity, top_entries) = (&feed.activity, &feed.top_entries);
 
-    let user_snakes = if let Some(user) = &user {
-        battlesnake_model::get_battlesnakes_by_user_id(&state.db, user.user_id)
-            .await
-            .wrap_err("Failed to fetch user's battlesnakes")?
+    let dashboard = if let Some(user) = &user {
+        Some(
+            HomeDashboard::load(&state, user.user_id)
+                .await
+                .wrap_err("Failed to load home dashboard")?,
+        )
     } else {
-        Vec::new()
+        None
     };
 
     Ok(page_factory.create_page(
         "Home".to_string(),
         Box::new(html! {
             div class="home" {
-                @if let Some(user) = &user {
+                @if let (Some(user), Some(dashboard)) = (&user, &dashboard) {
                     section class="welcome" {
                         img src=(user.github_avatar_url.clone().unwrap_or_default()) alt="" width="64" height="64";
                         div class="who" {
                             h1 { "Welcome, " (user.github_login) "!" }
                             p class="sub" {
-                                @if user_snakes.is_empty() {
-                                    "No snakes in your stable yet — deploy a server and claim a spot on the ladder."
-                                } @else if user_snakes.len() == 1 {
-                                    "One snake in your stable, playing around the clock."
-                                } @else {
-                                    (user_snakes.len()) " snakes in your stable, playing around the clock."
+                                @match dashboard.snake_count() {
+                                    0 => "No snakes in your stable yet — deploy a server and claim a spot on the ladder.",
+                                    1 => "One snake in your stable, playing around the clock.",
+                                    n => { (n) " snakes in your stable, playing around the clock." }
                                 }
                             }
                         }
                         div class="cta-row" {
                             a class="btn" href="/me" { "Profile" }
                             a class="btn" href="/battlesnakes" { "My snakes" }
+                            a class="btn" href="/feed" { "Feed" }
+                            a class="btn" href="/play" { "Play" }
                             a class="btn" href="/auth/logout" { "Logout" }
                         }
                     }
 
-                    @if user_snakes.is_empty() {
+                    @if dashboard.standings.is_empty() {
                         section class="section" {
                             h2 { "Get on the board" }
                             p class="empty" { "Register your first snake to start playing ranked games." }
                             a class="btn solid" href="/battlesnakes/new" { "Register a snake" }
                         }
                     } @else {
-                        section class="section snakes" {
-                            h2 { "Your snakes" }
-                            div class="rows" {
-                                @for snake in &user_snakes {
-                                    a class="srow" href={"/battlesnakes/"(snake.battlesnake_id)"/profile"} {
-                                        span class="chip" style={"background:"(chip_color(&snake.color))} {}
-                                        span class="sname" { (snake.name) }
-                                        span class="surl hide-sm" { (snake.url) }
-                                        @if snake.visibility == battlesnake_model::Visibility::Public {
-                                            span class="badge ok" { "Public" }
-                                        } @else {
-                                            span class="badge" { "Private" }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                        (home_dashboard(dashboard, user.user_id))
                     }
                 } @else {
                     section class="hero" {
@@ -490,6 +733,230 @@ async fn root_page(
     ))
 }
 
+/// Recent games listed on the signed-in home page
+const DASHBOARD_RECENT_GAMES: i64 = 8;
+
+/// How far back the home page looks for failing /move calls
+const DASHBOARD_HEALTH_WINDOW_HOURS: i64 = 24;
+
+/// What the signed-in home page shows about the user's own snakes
+struct HomeDashboard {
+    standings: Vec<SnakeStanding>,
+    recent_games: Vec<RecentPlacement>,
+    pending_challenges: Vec<ChallengeListing>,
+    health_warnings: Vec<SnakeHealthWarning>,
+}
+
+impl HomeDashboard {
+    async fn load(state: &AppState, user_id: uuid::Uuid) -> cja::Result<Self> {
+        let standings = dashboard::get_snake_standings(&state.db, user_id).await?;
+        // Nothing else can be on the dashboard without a snake
+        if standings.is_empty() {
+            return Ok(Self {
+                standings,
+                recent_games: Vec::new(),
+                pending_challenges: Vec::new(),
+                health_warnings: Vec::new(),
+            });
+        }
+
+        let since = chrono::Utc::now() - chrono::Duration::hours(DASHBOARD_HEALTH_WINDOW_HOURS);
+        Ok(Self {
+            standings,
+            recent_games: dashboard::get_recent_placements(
+                &state.db,
+                user_id,
+                DASHBOARD_RECENT_GAMES,
+            )
+            .await?,
+            pending_challenges: challenge_model::list_pending_challenges_for_user(
+                &state.db, user_id,
+            )
+            .await?,
+            health_warnings: dashboard::get_health_warnings(&state.db, user_id, since).await?,
+        })
+    }
+
+    /// Standings are sorted by snake, one row per leaderboard entry
+    fn snake_count(&self) -> usize {
+        let mut ids: Vec<_> = self.standings.iter().map(|s| s.battlesnake_id).collect();
+        ids.dedup();
+        ids.len()
+    }
+}
+
+/// The signed-in home page's own-snake sections: anything that needs the
+/// owner's attention, where each snake stands, and how its games went.
+fn home_dashboard(dashboard: &HomeDashboard, user_id: uuid::Uuid) -> Markup {
+    let (incoming, outgoing): (Vec<&ChallengeListing>, Vec<&ChallengeListing>) = dashboard
+        .pending_challenges
+        .iter()
+        .partition(|c| c.opponent_user_id == user_id);
+
+    html! {
+        @if !dashboard.health_warnings.is_empty() || !incoming.is_empty() {
+            section class="section snakes" {
+                h2 { "Needs your attention" }
+                div class="rows" {
+                    @for warning in &dashboard.health_warnings {
+                        a class="srow" href={"/battlesnakes/"(warning.battlesnake_id)"/profile"} {
+                            span class="sname" { (warning.snake_name) }
+                            span class="surl" { (health_warning_text(warning)) }
+                            span class="badge warn" {
+                                @if warning.deactivated_at.is_some() { "Paused" } @else { "Failing" }
+                            }
+                        }
+                    }
+                    @for c in &incoming {
+                        a class="srow" href="/challenges" {
+                            span class="sname" { (c.challenger_snake_name) }
+                            span class="surl" {
+                                (c.challenger_login) " challenged " (c.opponent_snake_name)
+                                " to " (c.game_type) " · " (c.board_size)
+                            }
+                            span class="badge" { "Challenge" }
+                        }
+                    }
+                }
+            }
+        }
+
+        section class="section" {
+            h2 { "Your snakes" }
+            table class="data" {
+                thead {
+                    tr {
+                        th { "Snake" }
+                        th { "Leaderboard" }
+                        th { "Rank" }
+                        th class="r" { "Rating" }
+                        th class="r hide-sm" { "24h" }
+                    }
+                }
+                tbody {
+                    @for standing in &dashboard.standings {
+                        tr {
+                            td {
+                                div class="snake-cell" {
+                                    span class="chip" style={"background:"(chip_color(&standing.snake_color))} {}
+                                    a class="name" href={"/battlesnakes/"(standing.battlesnake_id)"/profile"} { (standing.snake_name) }
+                                }
+                            }
+                            @if let (Some(leaderboard_id), Some(entry_id)) = (standing.leaderboard_id, standing.leaderboard_entry_id) {
+                                td {
+                                    a href={"/leaderboards/"(leaderboard_id)"/entries/"(entry_id)} {
+                                        (standing.leaderboard_name.as_deref().unwrap_or_default())
+                                    }
+                                }
+                                td class="rank" {
+                                    @if standing.paused {
+                                        span class="badge warn" { "Paused" }
+                                    } @else if let Some(rank) = standing.rank {
+                                        "#" (rank)
+                                    } @else {
+                                        "Placement " (standing.games_played.unwrap_or_default()) "/" (MIN_GAMES_FOR_RANKING)
+                                    }
+                                }
+                                td class="r rating" { (format!("{:.1}", standing.display_score.unwrap_or_default())) }
+                                td class="r hide-sm" {
+                                    @match standing.day_change {
+                                        Some(change) if change >= 0.0 => span class="delta up" { (format!("{change:+.1}")) },
+                                        Some(change) => span class="delta down" { (format!("{change:+.1}")) },
+                                        None => "—",
+                                    }
+                                }
+                            } @else {
+                                td colspan="4" class="empty" {
+                                    "Not on a leaderboard. "
+                                    a href="/leaderboards" { "Join one" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !outgoing.is_empty() {
+                a class="more" href="/challenges" {
+                    (outgoing.len())
+                    @if outgoing.len() == 1 { " challenge" } @else { " challenges" }
+                    " waiting on opponents →"
+                }
+            }
+        }
+
+        section class="section" {
+            h2 { "Your recent games" }
+            @if dashboard.recent_games.is_empty() {
+                p class="empty" { "No games yet. Join a leaderboard and your snakes will start playing." }
+            } @else {
+                table class="data" {
+                    thead {
+                        tr {
+                            th { "Snake" }
+                            th { "Result" }
+                            th class="hide-sm" { "Played for" }
+                            th class="r" { "When" }
+                        }
+                    }
+                    tbody {
+                        @for game in &dashboard.recent_games {
+                            tr {
+                                td {
+                                    div class="snake-cell" {
+                                        span class="chip" style={"background:"(chip_color(&game.snake_color))} {}
+                                        span class="name" { (game.snake_name) }
+                                    }
+                                }
+                                td {
+                                    a href={"/games/"(game.game_id)} {
+                                        @if let Some(placement) = game.placement {
+                                            (home_ordinal(placement)) " of " (game.snake_count)
+                                        } @else {
+                                            (game.status)
+                                        }
+                                    }
+                                }
+                                td class="hide-sm" { (game.leaderboard_name.as_deref().unwrap_or("Unranked")) }
+                                td class="r num" { (home_fmt_ago(game.created_at)) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Why a snake is on the "needs your attention" list, worst problem first
+fn health_warning_text(warning: &SnakeHealthWarning) -> String {
+    if let Some(deactivated_at) = warning.deactivated_at {
+        format!(
+            "Taken out of matchmaking {} after failing health checks",
+            home_fmt_ago(deactivated_at)
+        )
+    } else if warning.consecutive_failures > 0 {
+        let mut text = format!(
+            "Failed its last {} health {}",
+            warning.consecutive_failures,
+            if warning.consecutive_failures == 1 {
+                "check"
+            } else {
+                "checks"
+            }
+        );
+        if let Some(failure) = &warning.last_failure {
+            text.push_str(": ");
+            text.push_str(failure);
+        }
+        text
+    } else {
+        format!(
+            "{:.0}% of moves failed in the last day",
+            warning.error_rate() * 100.0
+        )
+    }
+}
+
 /// Decorative 11×11 board for the logged-out hero. Intentionally dark-framed
 /// in both themes (hardcoded colors match the mockup's board panel).
 fn home_board() -> Markup {
@@ -706,6 +1173,12 @@ async fn profile_page(
             div class="profile-actions" {
                 a href={"/users/"(user.github_login)} class="btn" { "View Public Profile" }
                 a href="/battlesnakes" class="btn" { "Manage Battlesnakes" }
+                a href="/notifications" class="btn" { "Notifications" }
+                a href="/feed" class="btn" { "Your Feed" }
+                a href="/settings/discord" class="btn" { "Discord Webhook" }
+                a href="/settings/tokens" class="btn" { "API Tokens" }
+                a href="/settings/export" class="btn" download { "Download my data" }
+                a href="/settings/account" class="btn danger" { "Delete account" }
                 a href="/games/new" class="btn" { "Create New Game" }
                 a href="/" class="btn" { "Back to Home" }
                 a href="/auth/logout" class="btn" { "Logout" }
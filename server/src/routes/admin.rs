@@ -1,17 +1,46 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use maud::html;
-use serde::Serialize;
+use maud::{Markup, html};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use utoipa::ToSchema;
 
 use crate::components::page_factory::PageFactory;
-use crate::errors::ServerResult;
+use crate::errors::{ServerResult, WithStatus};
+use crate::metrics::TimedQuery as _;
+use crate::models::consistency::{self, ConsistencyReport};
+use crate::models::metric_snapshot::{self, MetricSnapshot};
+use crate::routes::api::{
+    error::{ApiError, ApiErrorBody, ApiResult},
+    extract::ApiQuery,
+};
 use crate::routes::auth::{AdminApiUser, AdminUser};
 use crate::state::AppState;
+use crate::static_assets::asset_url;
 
-#[derive(Serialize)]
+pub mod audit;
+pub mod games;
+pub mod leaderboards;
+pub mod maintenance;
+pub mod moderation;
+pub mod settings;
+pub mod snakes;
+pub mod tournaments;
+pub mod users;
+
+/// For admin form posts, which need the session (for flash messages) that
+/// `AdminUser` doesn't carry
+fn require_admin(user: &crate::models::user::User) -> ServerResult<(), StatusCode> {
+    if user.is_admin {
+        Ok(())
+    } else {
+        Err("Admin access required".to_string()).with_status(StatusCode::FORBIDDEN)
+    }
+}
+
+#[derive(Serialize, ToSchema)]
 pub struct AdminMetrics {
     pub job_queue: JobQueueMetrics,
     pub jobs_by_name: Vec<JobNameCount>,
@@ -20,9 +49,152 @@ pub struct AdminMetrics {
     pub games_finished: TimeWindowMetrics,
     pub avg_game_duration_secs: Option<f64>,
     pub recent_errors: Vec<JobError>,
+    pub leaderboards: Vec<LeaderboardMetrics>,
+    pub move_latency: MoveLatencyMetrics,
+    /// Orphaned records as of this request; see [`crate::consistency_check`]
+    pub orphans: ConsistencyReport,
+    pub db_pool: PoolMetrics,
+    pub runtime: RuntimeMetrics,
+    /// Routes the serving process has handled since it started, the most
+    /// total handler time first
+    pub http_routes: Vec<HttpRouteMetrics>,
+    /// The serving process's timed queries with the highest mean duration
+    pub slow_queries: Vec<QueryMetrics>,
+}
+
+/// Slow queries shown on the dashboard and in `/api/v1/admin/stats`
+const SLOW_QUERY_LIMIT: usize = 10;
+
+/// The serving process's connection pool, so stress-run slowdowns can be
+/// told apart: pool exhaustion shows as high utilization and acquire wait,
+/// CPU saturation as a deep runtime queue.
+#[derive(Serialize, ToSchema)]
+pub struct PoolMetrics {
+    /// Open connections, idle or in use
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+    /// In-use connections over `max_connections`
+    pub utilization: f64,
+    /// How long this request waited to check out a connection
+    pub acquire_wait_ms: f64,
+}
+
+/// Tokio runtime and process stats for the serving process
+#[derive(Serialize, ToSchema)]
+pub struct RuntimeMetrics {
+    pub workers: usize,
+    /// Tasks spawned and not yet finished
+    pub alive_tasks: usize,
+    /// Tasks waiting in the shared run queue for a free worker
+    pub global_queue_depth: usize,
+    /// Resident set size; `None` where the platform doesn't report it
+    pub rss_bytes: Option<u64>,
+}
+
+/// One route's requests in the serving process, from the same series as
+/// `arena_http_request_duration_seconds` on `/metrics`
+#[derive(Serialize, ToSchema)]
+pub struct HttpRouteMetrics {
+    pub method: String,
+    /// Route template, e.g. `/games/{id}`
+    pub route: String,
+    pub requests: u64,
+    /// Requests by status class (`2xx`, `4xx`, ...)
+    pub status_counts: std::collections::BTreeMap<String, u64>,
+    pub total_secs: f64,
+    pub mean_ms: Option<f64>,
+    /// Upper bound of the latency bucket holding the 95th percentile; null
+    /// past the last bucket (5s)
+    pub p95_ms: Option<f64>,
+}
+
+/// One timed query since the serving process started; see
+/// [`crate::metrics::TimedQuery`]
+#[derive(Serialize, ToSchema)]
+pub struct QueryMetrics {
+    pub query: String,
+    pub calls: u64,
+    /// Calls slower than the 250ms slow-query threshold
+    pub slow_calls: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+}
+
+impl QueryMetrics {
+    fn sample() -> Vec<Self> {
+        crate::metrics::METRICS
+            .slowest_queries(SLOW_QUERY_LIMIT)
+            .into_iter()
+            .map(|timing| QueryMetrics {
+                query: timing.query.to_string(),
+                calls: timing.calls,
+                slow_calls: timing.slow_calls,
+                mean_ms: timing.mean.as_secs_f64() * 1000.0,
+                max_ms: timing.max.as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
 }
 
-#[derive(Serialize)]
+impl HttpRouteMetrics {
+    fn sample() -> Vec<Self> {
+        crate::metrics::METRICS
+            .route_traffic()
+            .into_iter()
+            .map(|traffic| HttpRouteMetrics {
+                mean_ms: traffic.mean().map(|mean| mean.as_secs_f64() * 1000.0),
+                p95_ms: traffic.quantile_upper_bound(0.95).map(|secs| secs * 1000.0),
+                total_secs: traffic.total.as_secs_f64(),
+                status_counts: traffic
+                    .by_status
+                    .iter()
+                    .map(|(class, count)| (class.to_string(), *count))
+                    .collect(),
+                requests: traffic.requests,
+                route: traffic.route,
+                method: traffic.method,
+            })
+            .collect()
+    }
+}
+
+impl PoolMetrics {
+    async fn sample(db: &PgPool) -> cja::Result<Self> {
+        let started = std::time::Instant::now();
+        drop(db.acquire().await?);
+        let acquire_wait_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        let size = db.size();
+        let idle = db.num_idle() as u32;
+        let max_connections = db.options().get_max_connections();
+        Ok(PoolMetrics {
+            size,
+            idle,
+            max_connections,
+            utilization: if max_connections > 0 {
+                size.saturating_sub(idle) as f64 / max_connections as f64
+            } else {
+                0.0
+            },
+            acquire_wait_ms,
+        })
+    }
+}
+
+impl RuntimeMetrics {
+    fn sample() -> Self {
+        let runtime = tokio::runtime::Handle::current().metrics();
+        RuntimeMetrics {
+            workers: runtime.num_workers(),
+            alive_tasks: runtime.num_alive_tasks(),
+            global_queue_depth: runtime.global_queue_depth(),
+            rss_bytes: crate::metrics::process_rss_bytes(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, ToSchema)]
 pub struct JobQueueMetrics {
     pub ready: i64,
     pub running: i64,
@@ -30,13 +202,13 @@ pub struct JobQueueMetrics {
     pub total: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize, ToSchema)]
 pub struct JobNameCount {
     pub name: String,
     pub count: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize, ToSchema)]
 pub struct GameCountMetrics {
     pub waiting: i64,
     pub running: i64,
@@ -44,14 +216,14 @@ pub struct GameCountMetrics {
     pub total: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct TimeWindowMetrics {
     pub last_hour: i64,
     pub last_24h: i64,
     pub last_7d: i64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct JobError {
     pub name: String,
     pub error_count: i32,
@@ -59,19 +231,57 @@ pub struct JobError {
     pub last_failed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-impl AdminMetrics {
-    async fn fetch(db: &PgPool) -> cja::Result<Self> {
+/// Snake `/move` response times over the last hour. Timed-out moves count
+/// toward the percentiles at whatever latency was recorded for them.
+#[derive(Serialize, ToSchema)]
+pub struct MoveLatencyMetrics {
+    pub moves: i64,
+    pub timeouts: i64,
+    /// Fraction of moves that timed out, 0.0 when there were no moves
+    pub timeout_rate: f64,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LeaderboardMetrics {
+    pub leaderboard_id: uuid::Uuid,
+    pub name: String,
+    /// When the leaderboard was frozen, or null if it isn't
+    pub frozen_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub active_entries: i64,
+    /// Waiting or running leaderboard games
+    pub games_in_progress: i64,
+    pub games_created_last_hour: i64,
+    /// Average time from a game finishing to its ratings being written, over
+    /// the last 24 hours
+    pub avg_rating_lag_secs: Option<f64>,
+}
+
+/// The job queue and game counts, which the dashboard re-polls every few
+/// seconds without reloading the page
+pub struct LiveMetrics {
+    pub job_queue: JobQueueMetrics,
+    pub jobs_by_name: Vec<JobNameCount>,
+    pub game_counts: GameCountMetrics,
+}
+
+impl LiveMetrics {
+    pub(crate) async fn fetch(db: &PgPool) -> cja::Result<Self> {
         let job_queue = sqlx::query!(
             r#"
             SELECT
-                COUNT(*) FILTER (WHERE locked_at IS NULL AND run_at <= NOW()) as "ready!: i64",
-                COUNT(*) FILTER (WHERE locked_at IS NOT NULL) as "running!: i64",
+                COUNT(*) FILTER (WHERE (locked_at IS NULL OR locked_until < NOW()) AND run_at <= NOW()) as "ready!: i64",
+                COUNT(*) FILTER (WHERE locked_until >= NOW()) as "running!: i64",
                 COUNT(*) FILTER (WHERE locked_at IS NULL AND run_at > NOW()) as "scheduled!: i64",
                 COUNT(*) as "total!: i64"
             FROM jobs
             "#
         )
         .fetch_one(db)
+        .timed("admin::job_queue")
         .await?;
 
         let job_queue = JobQueueMetrics {
@@ -88,6 +298,7 @@ impl AdminMetrics {
             "#
         )
         .fetch_all(db)
+        .timed("admin::jobs_by_name")
         .await?;
 
         let jobs_by_name = jobs_by_name_rows
@@ -109,6 +320,7 @@ impl AdminMetrics {
             "#
         )
         .fetch_one(db)
+        .timed("admin::game_counts")
         .await?;
 
         let game_counts = GameCountMetrics {
@@ -118,6 +330,27 @@ impl AdminMetrics {
             total: game_counts.total,
         };
 
+        Ok(LiveMetrics {
+            job_queue,
+            jobs_by_name,
+            game_counts,
+        })
+    }
+}
+
+impl AdminMetrics {
+    /// Live state (pool, job queue) comes from the primary; the time-window
+    /// aggregates scan a lot of rows and come from `read_db`
+    async fn fetch(db: &PgPool, read_db: &PgPool) -> cja::Result<Self> {
+        // Sampled first, before this request's own queries hold connections
+        let db_pool = PoolMetrics::sample(db).await?;
+
+        let LiveMetrics {
+            job_queue,
+            jobs_by_name,
+            game_counts,
+        } = LiveMetrics::fetch(db).await?;
+
         let games_created = sqlx::query!(
             r#"
             SELECT
@@ -127,7 +360,8 @@ impl AdminMetrics {
             FROM games
             "#
         )
-        .fetch_one(db)
+        .fetch_one(read_db)
+        .timed("admin::games_created")
         .await?;
 
         let games_created = TimeWindowMetrics {
@@ -145,7 +379,8 @@ impl AdminMetrics {
             FROM games
             "#
         )
-        .fetch_one(db)
+        .fetch_one(read_db)
+        .timed("admin::games_finished")
         .await?;
 
         let games_finished = TimeWindowMetrics {
@@ -160,7 +395,8 @@ impl AdminMetrics {
             FROM games WHERE status = 'finished' AND updated_at > NOW() - INTERVAL '24 hours'
             "#
         )
-        .fetch_one(db)
+        .fetch_one(read_db)
+        .timed("admin::avg_game_duration")
         .await?;
 
         let recent_errors_rows = sqlx::query!(
@@ -171,6 +407,7 @@ impl AdminMetrics {
             "#
         )
         .fetch_all(db)
+        .timed("admin::recent_job_errors")
         .await?;
 
         let recent_errors = recent_errors_rows
@@ -183,6 +420,74 @@ impl AdminMetrics {
             })
             .collect();
 
+        // Rating lag is measured from the game's last update (when it was
+        // marked finished) to the first result row the ratings job wrote.
+        let leaderboards = sqlx::query_as!(
+            LeaderboardMetrics,
+            r#"
+            SELECT
+                l.leaderboard_id,
+                l.name,
+                l.frozen_at,
+                (SELECT COUNT(*) FROM leaderboard_entries le
+                 WHERE le.leaderboard_id = l.leaderboard_id AND le.disabled_at IS NULL) as "active_entries!: i64",
+                (SELECT COUNT(*) FROM leaderboard_games lg JOIN games g ON g.game_id = lg.game_id
+                 WHERE lg.leaderboard_id = l.leaderboard_id AND g.status IN ('waiting', 'running')) as "games_in_progress!: i64",
+                (SELECT COUNT(*) FROM leaderboard_games lg
+                 WHERE lg.leaderboard_id = l.leaderboard_id AND lg.created_at > NOW() - INTERVAL '1 hour') as "games_created_last_hour!: i64",
+                (SELECT AVG(GREATEST(EXTRACT(EPOCH FROM (r.rated_at - g.updated_at)), 0))
+                 FROM leaderboard_games lg
+                 JOIN games g ON g.game_id = lg.game_id
+                 JOIN LATERAL (
+                     SELECT MIN(lgr.created_at) as rated_at
+                     FROM leaderboard_game_results lgr
+                     WHERE lgr.leaderboard_game_id = lg.leaderboard_game_id
+                 ) r ON r.rated_at IS NOT NULL
+                 WHERE lg.leaderboard_id = l.leaderboard_id
+                   AND r.rated_at > NOW() - INTERVAL '24 hours') as "avg_rating_lag_secs: f64"
+            FROM leaderboards l
+            WHERE l.disabled_at IS NULL
+            ORDER BY l.name
+            "#
+        )
+        .fetch_all(read_db)
+        .timed("admin::leaderboards")
+        .await?;
+
+        let move_latency = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) as "moves!: i64",
+                COUNT(*) FILTER (WHERE timed_out) as "timeouts!: i64",
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY latency_ms) as p50_ms,
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY latency_ms) as p90_ms,
+                percentile_cont(0.99) WITHIN GROUP (ORDER BY latency_ms) as p99_ms,
+                MAX(latency_ms) as max_ms
+            FROM snake_turns
+            WHERE created_at > NOW() - INTERVAL '1 hour'
+            "#
+        )
+        .fetch_one(read_db)
+        .timed("admin::move_latency")
+        .await?;
+
+        let move_latency = MoveLatencyMetrics {
+            moves: move_latency.moves,
+            timeouts: move_latency.timeouts,
+            timeout_rate: if move_latency.moves > 0 {
+                move_latency.timeouts as f64 / move_latency.moves as f64
+            } else {
+                0.0
+            },
+            p50_ms: move_latency.p50_ms,
+            p90_ms: move_latency.p90_ms,
+            p99_ms: move_latency.p99_ms,
+            max_ms: move_latency.max_ms,
+        };
+
+        let orphans =
+            consistency::get_report(read_db, crate::consistency_check::grace_cutoff()).await?;
+
         Ok(AdminMetrics {
             job_queue,
             jobs_by_name,
@@ -191,10 +496,56 @@ impl AdminMetrics {
             games_finished,
             avg_game_duration_secs: avg_duration.avg_duration_secs,
             recent_errors,
+            leaderboards,
+            move_latency,
+            orphans,
+            db_pool,
+            runtime: RuntimeMetrics::sample(),
+            http_routes: HttpRouteMetrics::sample(),
+            slow_queries: QueryMetrics::sample(),
         })
     }
 }
 
+impl AdminMetrics {
+    /// The numbers worth keeping over time, for the snapshot history
+    fn snapshot(&self, captured_at: chrono::DateTime<chrono::Utc>) -> MetricSnapshot {
+        MetricSnapshot {
+            captured_at,
+            jobs_ready: self.job_queue.ready,
+            jobs_running: self.job_queue.running,
+            jobs_scheduled: self.job_queue.scheduled,
+            games_waiting: self.game_counts.waiting,
+            games_running: self.game_counts.running,
+            games_created_last_hour: self.games_created.last_hour,
+            games_finished_last_hour: self.games_finished.last_hour,
+            moves_last_hour: self.move_latency.moves,
+            move_timeout_rate: self.move_latency.timeout_rate,
+            move_p50_ms: self.move_latency.p50_ms,
+            move_p99_ms: self.move_latency.p99_ms,
+            db_pool_utilization: self.db_pool.utilization,
+            db_acquire_wait_ms: self.db_pool.acquire_wait_ms,
+            tokio_alive_tasks: self.runtime.alive_tasks as i64,
+            rss_bytes: self.runtime.rss_bytes.map(|b| b as i64),
+        }
+    }
+}
+
+/// Persist a snapshot of the current metrics and drop the ones past
+/// retention. Called from the cron-scheduled
+/// [`crate::jobs::MetricSnapshotJob`].
+pub(crate) async fn capture_metric_snapshot(db: &PgPool, read_db: &PgPool) -> cja::Result<()> {
+    let now = chrono::Utc::now();
+    let metrics = AdminMetrics::fetch(db, read_db).await?;
+    metric_snapshot::record(db, &metrics.snapshot(now)).await?;
+    metric_snapshot::purge_before(
+        db,
+        now - chrono::Duration::days(metric_snapshot::RETENTION_DAYS),
+    )
+    .await?;
+    Ok(())
+}
+
 fn format_duration(secs: f64) -> String {
     if secs < 60.0 {
         format!("{:.1}s", secs)
@@ -205,12 +556,136 @@ fn format_duration(secs: f64) -> String {
     }
 }
 
+/// Which way the ready-job backlog moved since the dashboard last polled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueueTrend {
+    Up(i64),
+    Down(i64),
+    Steady,
+}
+
+fn queue_trend(previous_ready: Option<i64>, ready: i64) -> Option<QueueTrend> {
+    let delta = ready - previous_ready?;
+    Some(match delta {
+        d if d > 0 => QueueTrend::Up(d),
+        d if d < 0 => QueueTrend::Down(-d),
+        _ => QueueTrend::Steady,
+    })
+}
+
+/// The auto-refreshing part of the dashboard. `data-ready` carries the
+/// backlog size back to the next poll so the server can compute the trend.
+fn live_section(live: &LiveMetrics, trend: Option<QueueTrend>) -> Markup {
+    html! {
+        div id="admin-live" data-ready=(live.job_queue.ready) {
+            h2 {
+                "Job Queue"
+                @match trend {
+                    Some(QueueTrend::Up(n)) => span style="margin-left: 12px; font-size: 0.6em; color: #c0392b;" { "▲ trending up (+" (n) " ready)" },
+                    Some(QueueTrend::Down(n)) => span style="margin-left: 12px; font-size: 0.6em; color: #27ae60;" { "▼ trending down (−" (n) " ready)" },
+                    Some(QueueTrend::Steady) => span style="margin-left: 12px; font-size: 0.6em; color: #666;" { "steady" },
+                    None => {},
+                }
+            }
+            table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                tr {
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
+                    th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Ready" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.job_queue.ready) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Running" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.job_queue.running) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Scheduled" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.job_queue.scheduled) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { "Total" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (live.job_queue.total) }
+                }
+            }
+
+            @if !live.jobs_by_name.is_empty() {
+                h3 { "Jobs by Type" }
+                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                    tr {
+                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Job Name" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
+                    }
+                    @for job in &live.jobs_by_name {
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { (job.name) }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (job.count) }
+                        }
+                    }
+                }
+            }
+
+            h2 { "Game Stats" }
+
+            h3 { "By Status" }
+            table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                tr {
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
+                    th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Waiting" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.game_counts.waiting) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Running" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.game_counts.running) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Finished" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (live.game_counts.finished) }
+                }
+                tr {
+                    td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { "Total" }
+                    td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (live.game_counts.total) }
+                }
+            }
+        }
+    }
+}
+
+/// Query for the dashboard's partial refresh
+#[derive(Debug, Deserialize)]
+pub struct LiveQuery {
+    /// Ready-job count from the previous poll
+    pub prev_ready: Option<i64>,
+}
+
+/// GET /admin/live - The job queue and game counts tables as an HTML fragment
+pub async fn live(
+    State(state): State<AppState>,
+    AdminUser(_user): AdminUser,
+    Query(query): Query<LiveQuery>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let live = LiveMetrics::fetch(&state.db).await?;
+    let trend = queue_trend(query.prev_ready, live.job_queue.ready);
+    Ok(live_section(&live, trend))
+}
+
 pub async fn dashboard(
     State(state): State<AppState>,
     AdminUser(_user): AdminUser,
     page_factory: PageFactory,
 ) -> ServerResult<impl IntoResponse, StatusCode> {
-    let metrics = AdminMetrics::fetch(&state.db).await?;
+    let metrics = AdminMetrics::fetch(&state.db, &state.read_db).await?;
+    let maintenance_state = state.maintenance().await;
+    let recent_audit = crate::models::admin_audit::list_recent(&state.db, 10).await?;
+    let live_metrics = LiveMetrics {
+        job_queue: metrics.job_queue.clone(),
+        jobs_by_name: metrics.jobs_by_name.clone(),
+        game_counts: metrics.game_counts.clone(),
+    };
 
     Ok(page_factory.create_page(
         "Admin Dashboard".to_string(),
@@ -220,73 +695,24 @@ pub async fn dashboard(
 
                 div style="margin-bottom: 20px;" {
                     a href="/admin" style="padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Refresh" }
-                }
-
-                h2 { "Job Queue" }
-                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
-                    tr {
-                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
-                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Ready" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.job_queue.ready) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Running" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.job_queue.running) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Scheduled" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.job_queue.scheduled) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { "Total" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (metrics.job_queue.total) }
+                    a href="/admin/users" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Users" }
+                    a href="/admin/snakes/errors" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Snake Errors" }
+                    a href="/admin/moderation" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Moderation" }
+                    a href="/admin/tournaments" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Tournaments" }
+                    a href="/admin/audit" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Audit Log" }
+                    a href="/admin/settings" style="margin-left: 8px; padding: 8px 16px; background: #0066cc; color: white; text-decoration: none; border-radius: 4px;" { "Settings" }
+                    span id="admin-live-status" style="margin-left: 12px; color: #666;" {
+                        "Job queue and game counts update every few seconds"
                     }
                 }
 
-                @if !metrics.jobs_by_name.is_empty() {
-                    h3 { "Jobs by Type" }
-                    table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
-                        tr {
-                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Job Name" }
-                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
-                        }
-                        @for job in &metrics.jobs_by_name {
-                            tr {
-                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (job.name) }
-                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (job.count) }
-                            }
-                        }
-                    }
-                }
+                (maintenance::maintenance_section(&maintenance_state))
 
-                h2 { "Game Stats" }
+                h2 { "Recent Admin Actions" }
+                (audit::audit_table(&recent_audit))
+                p { a href="/admin/audit" { "Full audit log" } }
 
-                h3 { "By Status" }
-                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
-                    tr {
-                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
-                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Waiting" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.game_counts.waiting) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Running" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.game_counts.running) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Finished" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.game_counts.finished) }
-                    }
-                    tr {
-                        td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { "Total" }
-                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (metrics.game_counts.total) }
-                    }
-                }
+                (live_section(&live_metrics, None))
 
                 h3 { "Games Created" }
                 table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
@@ -337,6 +763,167 @@ pub async fn dashboard(
                     }
                 }
 
+                h2 { "Snake Move Latency (last hour)" }
+                @if metrics.move_latency.moves == 0 {
+                    p { "No moves in the last hour" }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Metric" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Value" }
+                        }
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Moves" }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.move_latency.moves) }
+                        }
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Timeouts" }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                (metrics.move_latency.timeouts) " (" (format!("{:.1}%", metrics.move_latency.timeout_rate * 100.0)) ")"
+                            }
+                        }
+                        @for (label, value) in [("p50", metrics.move_latency.p50_ms), ("p90", metrics.move_latency.p90_ms), ("p99", metrics.move_latency.p99_ms), ("Max", metrics.move_latency.max_ms.map(f64::from))] {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (label) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if let Some(ms) = value { (format!("{ms:.0}ms")) } @else { "-" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Capacity" }
+                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                    tr {
+                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Metric" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Value" }
+                    }
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "DB connections (in use / open / max)" }
+                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                            (metrics.db_pool.size.saturating_sub(metrics.db_pool.idle)) " / " (metrics.db_pool.size) " / " (metrics.db_pool.max_connections)
+                            " (" (format!("{:.0}%", metrics.db_pool.utilization * 100.0)) ")"
+                        }
+                    }
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "DB acquire wait" }
+                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (format!("{:.1}ms", metrics.db_pool.acquire_wait_ms)) }
+                    }
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Tokio tasks (alive / queued)" }
+                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                            (metrics.runtime.alive_tasks) " / " (metrics.runtime.global_queue_depth)
+                        }
+                    }
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Tokio workers" }
+                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (metrics.runtime.workers) }
+                    }
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { "Process RSS" }
+                        td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                            @if let Some(bytes) = metrics.runtime.rss_bytes {
+                                (format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)))
+                            } @else {
+                                "-"
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Slowest Queries (this process)" }
+                @if metrics.slow_queries.is_empty() {
+                    p { "No timed queries have run yet" }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; max-width: 800px; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Query" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Calls" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Slow" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Mean" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Max" }
+                        }
+                        @for query in &metrics.slow_queries {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (query.query) } }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (query.calls) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (query.slow_calls) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (format!("{:.1}ms", query.mean_ms)) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (format!("{:.1}ms", query.max_ms)) }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Data Consistency" }
+                @if metrics.orphans == ConsistencyReport::default() {
+                    p { "No orphaned records" }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Check" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Count" }
+                        }
+                        @for (label, count) in [("Unrated leaderboard games", metrics.orphans.unrated_leaderboard_games), ("Snakes missing their leaderboard entry", metrics.orphans.unlinked_entry_snakes), ("Games never enqueued", metrics.orphans.unenqueued_games)] {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (label) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (count) }
+                            }
+                        }
+                    }
+                    p style="color: #666;" {
+                        @if state.config.orphan_repair_enabled {
+                            "The hourly consistency check repairs these."
+                        } @else {
+                            "Set ORPHAN_REPAIR_ENABLED to have the hourly consistency check repair these."
+                        }
+                    }
+                }
+
+                @if !metrics.leaderboards.is_empty() {
+                    h2 { "Leaderboards" }
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Leaderboard" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Active Entries" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Games In Progress" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Created Last Hour" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Avg Rating Lag (24h)" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Season" }
+                        }
+                        @for lb in &metrics.leaderboards {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (lb.name) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (lb.active_entries) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (lb.games_in_progress) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (lb.games_created_last_hour) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if let Some(secs) = lb.avg_rating_lag_secs {
+                                        (format_duration(secs))
+                                    } @else {
+                                        "N/A"
+                                    }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    form action={"/admin/leaderboards/"(lb.leaderboard_id)"/freeze"} method="post" style="margin: 0;" {
+                                        @if let Some(frozen_at) = lb.frozen_at {
+                                            "Frozen " (frozen_at.format("%Y-%m-%d %H:%M")) " "
+                                            input type="hidden" name="frozen" value="false";
+                                            button type="submit" style="padding: 4px 12px;"
+                                                onclick="return confirm('Unfreeze? Matchmaking resumes and held ratings are applied.');" { "Unfreeze" }
+                                        } @else {
+                                            input type="hidden" name="frozen" value="true";
+                                            button type="submit" style="padding: 4px 12px;"
+                                                onclick="return confirm('Freeze this leaderboard? Matchmaking stops and new ratings are held until it is unfrozen.');" { "Freeze" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 @if !metrics.recent_errors.is_empty() {
                     h2 { "Recent Job Errors" }
                     table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
@@ -372,26 +959,155 @@ pub async fn dashboard(
                 div style="margin-top: 20px;" {
                     a href="/" { "Back to Home" }
                 }
+
+                script src=(asset_url("adminDashboard.js")) defer {}
             }
         }),
     ))
 }
 
+/// GET /api/v1/admin/stats - Dashboard metrics as JSON
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Job queue, game, and error metrics", body = AdminMetrics),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn stats_json(
     State(state): State<AppState>,
     AdminApiUser(_user): AdminApiUser,
-) -> Result<impl IntoResponse, StatusCode> {
-    let metrics = AdminMetrics::fetch(&state.db).await.map_err(|e| {
-        tracing::error!("Failed to fetch admin metrics: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+) -> ApiResult<impl IntoResponse> {
+    let metrics = AdminMetrics::fetch(&state.db, &state.read_db)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch admin metrics", e))?;
     Ok(Json(metrics))
 }
 
+/// Most snapshots one history request returns: 30 days at the 5-minute
+/// capture interval
+const HISTORY_LIMIT: i64 = 30 * 24 * 12;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Query parameters for the stats history export
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatsHistoryQuery {
+    /// Start of the range, RFC 3339 (default: 24 hours before `to`)
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// End of the range, exclusive, RFC 3339 (default: now)
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    /// `json` (default) or `csv`
+    #[serde(default)]
+    #[param(inline)]
+    pub format: HistoryFormat,
+}
+
+/// GET /api/v1/admin/stats/history - Persisted metric snapshots over a time range
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats/history",
+    tag = "admin",
+    params(StatsHistoryQuery),
+    responses(
+        (status = 200, description = "Snapshots oldest first, as JSON or CSV", content(
+            (Vec<MetricSnapshot> = "application/json"),
+            (String = "text/csv"),
+        )),
+        (status = 400, description = "`from` is not before `to`", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn stats_history(
+    State(state): State<AppState>,
+    AdminApiUser(_user): AdminApiUser,
+    ApiQuery(query): ApiQuery<StatsHistoryQuery>,
+) -> ApiResult<axum::response::Response> {
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query.from.unwrap_or(to - chrono::Duration::hours(24));
+    if from >= to {
+        return Err(ApiError::bad_request("`from` must be before `to`"));
+    }
+
+    let snapshots = metric_snapshot::list_range(&state.db, from, to, HISTORY_LIMIT)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch metric snapshots", e))?;
+
+    Ok(match query.format {
+        HistoryFormat::Json => Json(snapshots).into_response(),
+        HistoryFormat::Csv => {
+            let mut csv = String::from(MetricSnapshot::CSV_HEADER);
+            csv.push('\n');
+            for snapshot in &snapshots {
+                csv.push_str(&snapshot.csv_row());
+                csv.push('\n');
+            }
+            (
+                [
+                    (axum::http::header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (
+                        axum::http::header::CONTENT_DISPOSITION,
+                        "attachment; filename=\"arena-stats.csv\"",
+                    ),
+                ],
+                csv,
+            )
+                .into_response()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_queue_trend() {
+        assert_eq!(queue_trend(None, 10), None);
+        assert_eq!(queue_trend(Some(4), 10), Some(QueueTrend::Up(6)));
+        assert_eq!(queue_trend(Some(10), 4), Some(QueueTrend::Down(6)));
+        assert_eq!(queue_trend(Some(7), 7), Some(QueueTrend::Steady));
+    }
+
+    #[test]
+    fn test_live_section_carries_ready_count_and_trend() {
+        let live = LiveMetrics {
+            job_queue: JobQueueMetrics {
+                ready: 12,
+                running: 1,
+                scheduled: 3,
+                total: 16,
+            },
+            jobs_by_name: vec![],
+            game_counts: GameCountMetrics {
+                waiting: 0,
+                running: 2,
+                finished: 5,
+                total: 7,
+            },
+        };
+
+        let html = live_section(&live, Some(QueueTrend::Up(4))).into_string();
+        assert!(html.contains(r#"id="admin-live" data-ready="12""#));
+        assert!(html.contains("trending up (+4 ready)"));
+
+        let html = live_section(&live, None).into_string();
+        assert!(!html.contains("trending"));
+    }
+
     #[test]
     fn test_format_duration_seconds() {
         assert_eq!(format_duration(0.0), "0.0s");
@@ -449,6 +1165,58 @@ mod tests {
             },
             avg_game_duration_secs: Some(12.5),
             recent_errors: vec![],
+            leaderboards: vec![LeaderboardMetrics {
+                leaderboard_id: uuid::Uuid::nil(),
+                name: "Standard 11x11".to_string(),
+                frozen_at: None,
+                active_entries: 40,
+                games_in_progress: 6,
+                games_created_last_hour: 24,
+                avg_rating_lag_secs: Some(1.5),
+            }],
+            move_latency: MoveLatencyMetrics {
+                moves: 1000,
+                timeouts: 20,
+                timeout_rate: 0.02,
+                p50_ms: Some(45.0),
+                p90_ms: Some(120.0),
+                p99_ms: Some(480.0),
+                max_ms: Some(501),
+            },
+            orphans: ConsistencyReport {
+                unrated_leaderboard_games: 2,
+                unlinked_entry_snakes: 0,
+                unenqueued_games: 1,
+            },
+            db_pool: PoolMetrics {
+                size: 5,
+                idle: 1,
+                max_connections: 5,
+                utilization: 0.8,
+                acquire_wait_ms: 12.5,
+            },
+            runtime: RuntimeMetrics {
+                workers: 4,
+                alive_tasks: 120,
+                global_queue_depth: 3,
+                rss_bytes: Some(64 * 1024 * 1024),
+            },
+            http_routes: vec![HttpRouteMetrics {
+                method: "GET".to_string(),
+                route: "/games".to_string(),
+                requests: 3,
+                status_counts: [("2xx".to_string(), 2), ("5xx".to_string(), 1)].into(),
+                total_secs: 3.06,
+                mean_ms: Some(1020.0),
+                p95_ms: Some(5000.0),
+            }],
+            slow_queries: vec![QueryMetrics {
+                query: "leaderboard::ranked_entries".to_string(),
+                calls: 2,
+                slow_calls: 1,
+                mean_ms: 205.0,
+                max_ms: 400.0,
+            }],
         };
 
         let json = serde_json::to_value(&metrics).unwrap();
@@ -478,6 +1246,29 @@ mod tests {
         assert_eq!(json["avg_game_duration_secs"], 12.5);
 
         assert!(json["recent_errors"].as_array().unwrap().is_empty());
+
+        assert_eq!(json["http_routes"][0]["route"], "/games");
+        assert_eq!(json["http_routes"][0]["status_counts"]["5xx"], 1);
+        assert_eq!(json["http_routes"][0]["p95_ms"], 5000.0);
+        assert_eq!(
+            json["slow_queries"][0]["query"],
+            "leaderboard::ranked_entries"
+        );
+        assert_eq!(json["slow_queries"][0]["slow_calls"], 1);
+
+        assert_eq!(json["leaderboards"][0]["name"], "Standard 11x11");
+        assert_eq!(json["leaderboards"][0]["active_entries"], 40);
+        assert_eq!(json["leaderboards"][0]["games_in_progress"], 6);
+        assert_eq!(json["leaderboards"][0]["games_created_last_hour"], 24);
+        assert_eq!(json["leaderboards"][0]["avg_rating_lag_secs"], 1.5);
+
+        assert_eq!(json["move_latency"]["moves"], 1000);
+        assert_eq!(json["move_latency"]["timeout_rate"], 0.02);
+        assert_eq!(json["move_latency"]["p99_ms"], 480.0);
+        assert_eq!(json["move_latency"]["max_ms"], 501);
+        assert_eq!(json["orphans"]["unrated_leaderboard_games"], 2);
+        assert_eq!(json["db_pool"]["utilization"], 0.8);
+        assert_eq!(json["runtime"]["rss_bytes"], 64 * 1024 * 1024);
     }
 
     #[test]
@@ -508,10 +1299,38 @@ mod tests {
             },
             avg_game_duration_secs: None,
             recent_errors: vec![],
+            leaderboards: vec![],
+            move_latency: MoveLatencyMetrics {
+                moves: 0,
+                timeouts: 0,
+                timeout_rate: 0.0,
+                p50_ms: None,
+                p90_ms: None,
+                p99_ms: None,
+                max_ms: None,
+            },
+            orphans: ConsistencyReport::default(),
+            db_pool: PoolMetrics {
+                size: 0,
+                idle: 0,
+                max_connections: 5,
+                utilization: 0.0,
+                acquire_wait_ms: 0.0,
+            },
+            runtime: RuntimeMetrics {
+                workers: 1,
+                alive_tasks: 0,
+                global_queue_depth: 0,
+                rss_bytes: None,
+            },
+            http_routes: vec![],
+            slow_queries: vec![],
         };
 
         let json = serde_json::to_value(&metrics).unwrap();
         assert!(json["avg_game_duration_secs"].is_null());
+        assert!(json["move_latency"]["p50_ms"].is_null());
+        assert!(json["runtime"]["rss_bytes"].is_null());
         assert!(json["jobs_by_name"].as_array().unwrap().is_empty());
     }
 
@@ -550,4 +1369,126 @@ mod tests {
         assert!(json["last_error_message"].is_null());
         assert!(json["last_failed_at"].is_null());
     }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_leaderboard_metrics(pool: PgPool) -> cja::Result<()> {
+        let leaderboard_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Arcade') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let user_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'metrics', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        for name in ["a", "b"] {
+            let snake_id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            crate::models::leaderboard::get_or_create_entry(&pool, leaderboard_id, snake_id)
+                .await?;
+        }
+        for status in ["running", "finished"] {
+            let game_id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type, status)
+                 VALUES ('11x11', 'Standard', $1) RETURNING game_id",
+            )
+            .bind(status)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query("INSERT INTO leaderboard_games (leaderboard_id, game_id) VALUES ($1, $2)")
+                .bind(leaderboard_id)
+                .bind(game_id)
+                .execute(&pool)
+                .await?;
+        }
+
+        let metrics = AdminMetrics::fetch(&pool, &pool).await?;
+        let arcade = metrics
+            .leaderboards
+            .iter()
+            .find(|lb| lb.leaderboard_id == leaderboard_id)
+            .unwrap();
+        assert_eq!(arcade.active_entries, 2);
+        assert_eq!(arcade.games_in_progress, 1);
+        assert_eq!(arcade.games_created_last_hour, 2);
+        assert!(arcade.avg_rating_lag_secs.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_move_latency_metrics_cover_last_hour(pool: PgPool) -> cja::Result<()> {
+        let user_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'latency', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let snake_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url)
+             VALUES ($1, 'slow', 'http://example.com') RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        let game_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type) VALUES ('11x11', 'Standard') RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let game_battlesnake_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2)
+             RETURNING game_battlesnake_id",
+        )
+        .bind(game_id)
+        .bind(snake_id)
+        .fetch_one(&pool)
+        .await?;
+
+        // The last move is two hours old and must be ignored
+        let moves = [
+            (100, false, "0 minutes"),
+            (200, false, "0 minutes"),
+            (300, false, "0 minutes"),
+            (500, true, "0 minutes"),
+            (900, true, "2 hours"),
+        ];
+        for (turn_number, (latency_ms, timed_out, age)) in moves.into_iter().enumerate() {
+            let turn_id: uuid::Uuid = sqlx::query_scalar(
+                "INSERT INTO turns (game_id, turn_number) VALUES ($1, $2) RETURNING turn_id",
+            )
+            .bind(game_id)
+            .bind(turn_number as i32)
+            .fetch_one(&pool)
+            .await?;
+            sqlx::query(
+                "INSERT INTO snake_turns (turn_id, game_battlesnake_id, direction, latency_ms, timed_out, created_at)
+                 VALUES ($1, $2, 'up', $3, $4, NOW() - $5::interval)",
+            )
+            .bind(turn_id)
+            .bind(game_battlesnake_id)
+            .bind(latency_ms)
+            .bind(timed_out)
+            .bind(age)
+            .execute(&pool)
+            .await?;
+        }
+
+        let latency = AdminMetrics::fetch(&pool, &pool).await?.move_latency;
+        assert_eq!(latency.moves, 4);
+        assert_eq!(latency.timeouts, 1);
+        assert_eq!(latency.timeout_rate, 0.25);
+        assert_eq!(latency.p50_ms, Some(250.0));
+        assert_eq!(latency.max_ms, Some(500));
+
+        Ok(())
+    }
 }
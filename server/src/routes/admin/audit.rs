@@ -0,0 +1,90 @@
+//! Admin audit log viewer.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use maud::{Markup, html};
+
+use crate::components::page_factory::PageFactory;
+use crate::errors::ServerResult;
+use crate::models::admin_audit::{self, AuditLogEntry};
+use crate::routes::auth::AdminUser;
+use crate::state::AppState;
+
+const PAGE_LIMIT: i64 = 200;
+
+fn snapshot(value: &Option<serde_json::Value>) -> String {
+    value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Audit entries as a table, newest first
+pub fn audit_table(entries: &[AuditLogEntry]) -> Markup {
+    html! {
+        @if entries.is_empty() {
+            p { "No admin actions recorded yet." }
+        } @else {
+            table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                tr {
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "When" }
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Admin" }
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Action" }
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Target" }
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Before" }
+                    th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "After" }
+                }
+                @for e in entries {
+                    tr {
+                        td style="padding: 8px; border-bottom: 1px solid #ddd; white-space: nowrap;" { (e.created_at.format("%Y-%m-%d %H:%M:%S")) }
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                            @if let Some(actor_id) = e.actor_id {
+                                a href={"/admin/users/"(actor_id)} { (e.actor_login) }
+                            } @else {
+                                (e.actor_login)
+                            }
+                        }
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (e.action) } }
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                            (e.target_type)
+                            @if let Some(target_id) = &e.target_id {
+                                " "
+                                @if let Some(href) = e.target_href() {
+                                    a href=(href) { code { (target_id) } }
+                                } @else {
+                                    code { (target_id) }
+                                }
+                            }
+                        }
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (snapshot(&e.before)) } }
+                        td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (snapshot(&e.after)) } }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// GET /admin/audit - Recent privileged admin actions
+pub async fn audit_log(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let entries = admin_audit::list_recent(&state.db, PAGE_LIMIT).await?;
+
+    Ok(page_factory.create_page(
+        "Audit Log".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Audit Log" }
+                p { a href="/admin" { "Back to Admin Dashboard" } }
+                (audit_table(&entries))
+                @if entries.len() as i64 == PAGE_LIMIT {
+                    p style="color: #666;" { "Showing the latest " (PAGE_LIMIT) " actions." }
+                }
+            }
+        }),
+    ))
+}
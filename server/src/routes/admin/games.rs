@@ -0,0 +1,534 @@
+//! Admin game inspector: everything about one game's trip through the
+//! runner on a single page, plus re-run and cancel for games that got stuck,
+//! and delete for corrupted or abusive ones.
+
+use axum::Form;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{format_duration, require_admin};
+use crate::components::page_factory::PageFactory;
+use crate::errors::{ServerResult, WithStatus};
+use crate::jobs::GameRunnerJob;
+use crate::leaderboard_ratings::Reversal;
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::game::{self, Game, GameStatus};
+use crate::models::{game_battlesnake, job, leaderboard, session, tournament, turn};
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::state::AppState;
+
+type Timestamp = chrono::DateTime<chrono::Utc>;
+
+fn format_timestamp(ts: Option<Timestamp>) -> String {
+    ts.map(|ts| ts.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_gap(from: Option<Timestamp>, to: Option<Timestamp>) -> String {
+    match (from, to) {
+        (Some(from), Some(to)) => format_duration((to - from).num_milliseconds() as f64 / 1000.0),
+        _ => "-".to_string(),
+    }
+}
+
+/// Why re-run and cancel are off-limits for a game right now, if they are.
+/// A finished game has already applied its results, and a locked runner job
+/// means a worker is playing the game as we speak.
+fn action_blocker(game: &Game, runner_jobs: &[job::JobRecord]) -> Option<&'static str> {
+    if game.status == GameStatus::Finished {
+        Some("Game already finished")
+    } else if runner_jobs.iter().any(|j| j.locked_at.is_some()) {
+        Some("A worker is running this game right now")
+    } else {
+        None
+    }
+}
+
+/// Why delete is off-limits for a game right now, if it is. Deleting under a
+/// live runner would have it write turns for a game that no longer exists,
+/// and a tournament game is part of a bracket's results.
+fn delete_blocker(runner_jobs: &[job::JobRecord], in_tournament: bool) -> Option<&'static str> {
+    if runner_jobs.iter().any(|j| j.locked_at.is_some()) {
+        Some("A worker is running this game right now")
+    } else if in_tournament {
+        Some("Tournament games can't be deleted")
+    } else {
+        None
+    }
+}
+
+/// GET /admin/games/{id} - Lifecycle, runner jobs, and per-snake latency for one game
+pub async fn show_game(
+    State(state): State<AppState>,
+    AdminUser(_user): AdminUser,
+    Path(game_id): Path<Uuid>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await?
+        .ok_or_else(|| "Game not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let span = turn::get_turn_span(&state.db, game_id).await?;
+    let runner_jobs = job::list_runner_jobs_for_game(&state.db, game_id).await?;
+    let snakes = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game_id).await?;
+    let latencies = turn::get_latency_stats_by_game_id(&state.db, game_id).await?;
+
+    let in_tournament = tournament::find_match_game_by_game_id(&state.db, game_id)
+        .await?
+        .is_some();
+    let lb_game = leaderboard::find_leaderboard_game_by_game_id(&state.db, game_id).await?;
+
+    let finished_at = (game.status == GameStatus::Finished).then_some(game.updated_at);
+    let blocker = action_blocker(&game, &runner_jobs);
+
+    Ok(page_factory.create_page(
+        format!("Game {game_id}"),
+        Box::new(html! {
+            div {
+                h1 { "Game " (game_id) }
+                p {
+                    a href={"/games/"(game_id)} { "Watch replay" }
+                    " · "
+                    a href="/admin" { "Back to Admin Dashboard" }
+                }
+
+                h2 { "Lifecycle" }
+                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                    @for (label, value) in [
+                        ("Status", game.status.as_str().to_string()),
+                        ("Type", format!("{} {}", game.game_type.as_str(), game.board_size.as_str())),
+                        ("Created", format_timestamp(Some(game.created_at))),
+                        ("Enqueued", format_timestamp(game.enqueued_at)),
+                        ("Started (first turn)", format_timestamp(span.first_turn_at)),
+                        ("Last turn", format_timestamp(span.last_turn_at)),
+                        ("Finished", format_timestamp(finished_at)),
+                        ("Queue wait", format_gap(game.enqueued_at, span.first_turn_at)),
+                        ("Run time", format_gap(span.first_turn_at, finished_at.or(span.last_turn_at))),
+                        ("Turns", span.turns.to_string()),
+                    ] {
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (label) }
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { (value) }
+                        }
+                    }
+                }
+
+                h2 { "Runner Jobs" }
+                @if runner_jobs.is_empty() {
+                    p { "No GameRunnerJob rows. Jobs are deleted once they succeed." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Job" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Failed Attempts" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Last Error" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Last Failed" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Next Run" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "State" }
+                        }
+                        @for j in &runner_jobs {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; font-family: monospace;" { (j.job_id) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (j.error_count) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; white-space: pre-wrap;" {
+                                    (j.last_error_message.as_deref().unwrap_or("-"))
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (format_timestamp(j.last_failed_at)) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (format_timestamp(Some(j.run_at))) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if j.locked_at.is_some() { "Running" } @else { "Queued" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Snakes" }
+                table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                    tr {
+                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Snake" }
+                        th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Owner" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Placement" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Moves" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Timeouts" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Avg" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "p95" }
+                        th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Max" }
+                    }
+                    @for snake in &snakes {
+                        @let stats = latencies.iter().find(|l| l.game_battlesnake_id == snake.game_battlesnake_id);
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { (snake.name) }
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { (snake.owner_login) }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                @if let Some(p) = snake.placement { (p) } @else { "-" }
+                            }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (stats.map_or(0, |s| s.moves)) }
+                            td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (stats.map_or(0, |s| s.timeouts)) }
+                            @for ms in [stats.and_then(|s| s.avg_latency_ms), stats.and_then(|s| s.p95_latency_ms), stats.and_then(|s| s.max_latency_ms).map(f64::from)] {
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if let Some(ms) = ms { (format!("{ms:.0}ms")) } @else { "-" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Actions" }
+                @if let Some(reason) = blocker {
+                    p style="color: #666;" { (reason) " — nothing to re-run or cancel." }
+                } @else {
+                    div style="display: flex; gap: 12px;" {
+                        form action={"/admin/games/"(game_id)"/rerun"} method="post" {
+                            button type="submit" style="padding: 8px 16px; background: #0066cc; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                                onclick="return confirm('Re-run this game from turn 0? Any partial turns are discarded.');" { "Re-run" }
+                        }
+                        form action={"/admin/games/"(game_id)"/cancel"} method="post" {
+                            button type="submit" style="padding: 8px 16px; background: #c0392b; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                                onclick="return confirm('Cancel this game? It will be marked finished with no placements.');" { "Cancel" }
+                        }
+                    }
+                }
+
+                h3 { "Delete" }
+                @if let Some(reason) = delete_blocker(&runner_jobs, in_tournament) {
+                    p style="color: #666;" { (reason) " — can't delete." }
+                } @else {
+                    form action={"/admin/games/"(game_id)"/delete"} method="post" {
+                        @if lb_game.is_some() {
+                            label style="display: block; margin-bottom: 8px;" {
+                                input type="checkbox" name="reverse_ratings" value="true";
+                                " Reverse this game's leaderboard rating changes"
+                            }
+                            p style="color: #666; margin: 0 0 8px;" {
+                                "Only possible while this is the latest rated game for every snake in it; "
+                                "once they've played on, their ratings can't be unwound exactly."
+                            }
+                        }
+                        button type="submit" style="padding: 8px 16px; background: #c0392b; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                            onclick="return confirm('Permanently delete this game, its turns, and its leaderboard record?');" { "Delete Game" }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// Load the game for an admin action, or the flash-and-redirect response
+/// explaining why the action can't run.
+async fn load_actionable_game(
+    state: &AppState,
+    session_id: Uuid,
+    game_id: Uuid,
+) -> ServerResult<Result<Game, Response>, StatusCode> {
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await?
+        .ok_or_else(|| "Game not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let runner_jobs = job::list_runner_jobs_for_game(&state.db, game_id).await?;
+
+    match action_blocker(&game, &runner_jobs) {
+        Some(reason) => {
+            session::set_flash_message(
+                &state.db,
+                session_id,
+                reason.to_string(),
+                session::FLASH_TYPE_ERROR,
+            )
+            .await
+            .wrap_err("Failed to set flash message")?;
+            Ok(Err(
+                Redirect::to(&format!("/admin/games/{game_id}")).into_response()
+            ))
+        }
+        None => Ok(Ok(game)),
+    }
+}
+
+/// POST /admin/games/{id}/rerun - Replace any queued runner with a fresh one.
+/// `run_game` wipes a crashed run's partial turns before starting over.
+pub async fn rerun_game(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(game_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&user)?;
+    let game = match load_actionable_game(&state, session.session_id, game_id).await? {
+        Ok(game) => game,
+        Err(response) => return Ok(response),
+    };
+
+    // Drop queued runners first so the game doesn't end up with two
+    job::delete_queued_runner_jobs_for_game(&state.db, game_id).await?;
+    game::set_game_enqueued_at(&state.db, game_id, chrono::Utc::now()).await?;
+    cja::jobs::Job::enqueue(
+        GameRunnerJob { game_id },
+        state.clone(),
+        format!("Admin {} re-ran game {game_id}", user.github_login),
+        None,
+    )
+    .await
+    .wrap_err("Failed to enqueue game runner job")?;
+    tracing::info!(game_id = %game_id, admin = %user.github_login, "admin re-ran game");
+    admin_audit::record(
+        &state.db,
+        &user,
+        AuditAction::GameRerun,
+        AuditTarget::Game(game_id),
+        Some(
+            serde_json::json!({ "status": game.status.as_str(), "enqueued_at": game.enqueued_at }),
+        ),
+        None,
+    )
+    .await?;
+
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        "Game re-enqueued".to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(&format!("/admin/games/{game_id}")).into_response())
+}
+
+/// POST /admin/games/{id}/cancel - Drop queued runners and mark the game
+/// finished without placements. No post-completion jobs run, so a cancelled
+/// leaderboard game never touches ratings.
+pub async fn cancel_game(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(game_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&user)?;
+    let game = match load_actionable_game(&state, session.session_id, game_id).await? {
+        Ok(game) => game,
+        Err(response) => return Ok(response),
+    };
+
+    let deleted = job::delete_queued_runner_jobs_for_game(&state.db, game_id).await?;
+    game::update_game_status(&state.db, game_id, GameStatus::Finished).await?;
+    state.game_channels.cleanup(game_id).await;
+    tracing::info!(game_id = %game_id, deleted_jobs = deleted, admin = %user.github_login, "admin cancelled game");
+    admin_audit::record(
+        &state.db,
+        &user,
+        AuditAction::GameCancelled,
+        AuditTarget::Game(game_id),
+        Some(serde_json::json!({ "status": game.status.as_str() })),
+        Some(
+            serde_json::json!({ "status": GameStatus::Finished.as_str(), "deleted_jobs": deleted }),
+        ),
+    )
+    .await?;
+
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        "Game cancelled".to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(&format!("/admin/games/{game_id}")).into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteForm {
+    #[serde(default)]
+    pub reverse_ratings: bool,
+}
+
+/// POST /admin/games/{id}/delete - Delete the game and everything that
+/// cascades from it, optionally reversing its rating changes first. Both
+/// happen in one transaction, so ratings are never reversed for a game that
+/// survives.
+pub async fn delete_game(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(game_id): Path<Uuid>,
+    Form(form): Form<DeleteForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&user)?;
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await?
+        .ok_or_else(|| "Game not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let runner_jobs = job::list_runner_jobs_for_game(&state.db, game_id).await?;
+    let in_tournament = tournament::find_match_game_by_game_id(&state.db, game_id)
+        .await?
+        .is_some();
+    if let Some(reason) = delete_blocker(&runner_jobs, in_tournament) {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            reason.to_string(),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+        return Ok(Redirect::to(&format!("/admin/games/{game_id}")).into_response());
+    }
+    let lb_game = leaderboard::find_leaderboard_game_by_game_id(&state.db, game_id).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .wrap_err("Failed to start game deletion transaction")?;
+    let reversed = match (&lb_game, form.reverse_ratings) {
+        (Some(lb_game), true) => {
+            match crate::leaderboard_ratings::reverse_ratings(
+                &mut tx,
+                &state.scoring,
+                lb_game.leaderboard_game_id,
+            )
+            .await?
+            {
+                Reversal::Reversed(entries) => entries,
+                Reversal::LaterGamesRated => {
+                    // Dropping the transaction rolls back the taken results
+                    drop(tx);
+                    session::set_flash_message(
+                        &state.db,
+                        session.session_id,
+                        "Can't reverse ratings: snakes in this game have been rated in later \
+                         games. Delete it without reversing instead."
+                            .to_string(),
+                        session::FLASH_TYPE_ERROR,
+                    )
+                    .await
+                    .wrap_err("Failed to set flash message")?;
+                    return Ok(Redirect::to(&format!("/admin/games/{game_id}")).into_response());
+                }
+            }
+        }
+        _ => 0,
+    };
+    let deleted = game::delete_game_tx(&mut tx, game_id).await?;
+    tx.commit()
+        .await
+        .wrap_err("Failed to commit game deletion")?;
+    if let Some(lb_game) = &lb_game {
+        state.invalidate_leaderboard(lb_game.leaderboard_id);
+    }
+    if let Some(archived) = deleted.and_then(|d| d.archived) {
+        crate::frame_archive::delete_archives(&state, &[archived]).await;
+    }
+
+    let deleted_jobs = job::delete_queued_runner_jobs_for_game(&state.db, game_id).await?;
+    state.game_channels.cleanup(game_id).await;
+    tracing::info!(game_id = %game_id, reversed_entries = reversed, admin = %user.github_login, "admin deleted game");
+    admin_audit::record(
+        &state.db,
+        &user,
+        AuditAction::GameDeleted,
+        AuditTarget::Game(game_id),
+        Some(serde_json::json!({
+            "status": game.status.as_str(),
+            "leaderboard_id": lb_game.as_ref().map(|g| g.leaderboard_id),
+        })),
+        Some(serde_json::json!({
+            "reversed_entries": reversed,
+            "deleted_jobs": deleted_jobs,
+        })),
+    )
+    .await?;
+
+    let message = if reversed > 0 {
+        format!("Game deleted and ratings reversed for {reversed} entries")
+    } else {
+        "Game deleted".to_string()
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job_record(locked: bool) -> job::JobRecord {
+        let now = chrono::Utc::now();
+        job::JobRecord {
+            job_id: Uuid::new_v4(),
+            name: "GameRunnerJob".to_string(),
+            payload: serde_json::json!({}),
+            error_count: 1,
+            last_error_message: Some("boom".to_string()),
+            last_failed_at: Some(now),
+            run_at: now,
+            locked_at: locked.then_some(now),
+            created_at: now,
+        }
+    }
+
+    fn game_with_status(status: GameStatus) -> Game {
+        let now = chrono::Utc::now();
+        Game {
+            game_id: Uuid::new_v4(),
+            board_size: game::GameBoardSize::Medium,
+            game_type: game::GameType::Standard,
+            status,
+            enqueued_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_action_blocker() {
+        assert_eq!(
+            action_blocker(&game_with_status(GameStatus::Running), &[]),
+            None
+        );
+        assert_eq!(
+            action_blocker(&game_with_status(GameStatus::Waiting), &[job_record(false)]),
+            None
+        );
+        assert_eq!(
+            action_blocker(&game_with_status(GameStatus::Finished), &[]),
+            Some("Game already finished")
+        );
+        assert_eq!(
+            action_blocker(&game_with_status(GameStatus::Running), &[job_record(true)]),
+            Some("A worker is running this game right now")
+        );
+    }
+
+    #[test]
+    fn test_delete_blocker() {
+        assert_eq!(delete_blocker(&[job_record(false)], false), None);
+        assert_eq!(
+            delete_blocker(&[job_record(true)], false),
+            Some("A worker is running this game right now")
+        );
+        assert_eq!(
+            delete_blocker(&[], true),
+            Some("Tournament games can't be deleted")
+        );
+    }
+
+    #[test]
+    fn test_format_gap() {
+        let start = chrono::Utc::now();
+        assert_eq!(
+            format_gap(Some(start), Some(start + chrono::Duration::seconds(90))),
+            "1.5m"
+        );
+        assert_eq!(format_gap(Some(start), None), "-");
+    }
+}
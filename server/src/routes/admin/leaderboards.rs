@@ -0,0 +1,85 @@
+//! Leaderboard freeze for end-of-season cutoffs. A frozen leaderboard gets
+//! no new matchmaker games, and ratings from games still finishing are held
+//! back until it's unfrozen.
+
+use axum::Form;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::require_admin;
+use crate::errors::{ServerResult, WithStatus};
+use crate::jobs::ApplyDeferredRatingsJob;
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::{leaderboard, session};
+use crate::routes::auth::CurrentUserWithSession;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct FreezeForm {
+    pub frozen: bool,
+}
+
+/// POST /admin/leaderboards/{id}/freeze - Freeze or unfreeze a leaderboard
+pub async fn set_frozen(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(leaderboard_id): Path<Uuid>,
+    Form(form): Form<FreezeForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    let lb = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+        .await?
+        .ok_or_else(|| "Leaderboard not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    leaderboard::set_frozen(&state.db, leaderboard_id, form.frozen).await?;
+    state.leaderboards_cache.clear();
+    if !form.frozen {
+        cja::jobs::Job::enqueue(
+            ApplyDeferredRatingsJob { leaderboard_id },
+            state.clone(),
+            format!(
+                "Admin {} unfroze leaderboard {leaderboard_id}",
+                admin.github_login
+            ),
+            None,
+        )
+        .await
+        .wrap_err("Failed to enqueue deferred ratings job")?;
+    }
+    tracing::info!(leaderboard_id = %leaderboard_id, frozen = form.frozen, admin = %admin.github_login, "admin changed leaderboard freeze");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::LeaderboardFreezeChanged,
+        AuditTarget::Leaderboard(leaderboard_id),
+        Some(serde_json::json!({ "frozen_at": lb.frozen_at })),
+        Some(serde_json::json!({ "frozen": form.frozen })),
+    )
+    .await?;
+
+    let message = if form.frozen {
+        format!(
+            "{} frozen: matchmaking stopped and new ratings are held",
+            lb.name
+        )
+    } else {
+        format!("{} unfrozen: held ratings are being applied", lb.name)
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/admin").into_response())
+}
@@ -0,0 +1,99 @@
+//! Maintenance mode toggle. While it's on, the matchmaker skips its runs,
+//! game creation (web and API) is refused, games already running finish,
+//! and every page shows a banner.
+
+use axum::Form;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::{Markup, html};
+use serde::Deserialize;
+
+use super::require_admin;
+use crate::errors::ServerResult;
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::maintenance::{self, MaintenanceState};
+use crate::models::session;
+use crate::routes::auth::CurrentUserWithSession;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceForm {
+    pub enabled: bool,
+    /// Banner text; blank uses the default notice
+    #[serde(default)]
+    pub message: String,
+}
+
+/// POST /admin/maintenance - Turn maintenance mode on or off
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Form(form): Form<MaintenanceForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+
+    let before = maintenance::get(&state.db).await?;
+    let updated =
+        maintenance::set(&state.db, form.enabled, form.message.trim(), admin.user_id).await?;
+    tracing::warn!(enabled = form.enabled, admin = %admin.github_login, "admin changed maintenance mode");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::MaintenanceChanged,
+        AuditTarget::Maintenance,
+        Some(serde_json::json!({ "enabled": before.enabled, "message": before.message })),
+        Some(serde_json::json!({ "enabled": updated.enabled, "message": updated.message })),
+    )
+    .await?;
+    // Other processes pick the change up when their cache expires
+    state.maintenance_cache.put(updated);
+
+    let message = if form.enabled {
+        "Maintenance mode on: matchmaking and game creation are paused"
+    } else {
+        "Maintenance mode off"
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message.to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/admin").into_response())
+}
+
+/// The dashboard's maintenance panel
+pub fn maintenance_section(current: &MaintenanceState) -> Markup {
+    html! {
+        h2 { "Maintenance Mode" }
+        @if current.enabled {
+            p style="color: #c0392b;" {
+                strong { "On" }
+                @if let Some(updated_at) = current.updated_at {
+                    " since " (updated_at.format("%Y-%m-%d %H:%M UTC"))
+                }
+                ": matchmaking and new games are paused."
+            }
+            form action="/admin/maintenance" method="post" style="margin-bottom: 20px;" {
+                input type="hidden" name="enabled" value="false";
+                button type="submit" style="padding: 8px 16px; background: #27ae60; color: white; border: none; border-radius: 4px; cursor: pointer;" { "Turn Off" }
+            }
+        } @else {
+            p { "Off. Turning it on pauses the matchmaker and game creation; running games finish." }
+            form action="/admin/maintenance" method="post" style="margin-bottom: 20px;" {
+                input type="hidden" name="enabled" value="true";
+                input type="text" name="message" placeholder=(maintenance::DEFAULT_MESSAGE) style="padding: 8px; width: 400px;";
+                " "
+                button type="submit" style="padding: 8px 16px; background: #c0392b; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                    onclick="return confirm('Turn on maintenance mode?');" { "Turn On" }
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Moderation of snake names and shouts: the word list they're screened
+//! against, and the queue of matches waiting on an admin.
+
+use axum::Form;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::require_admin;
+use crate::components::page_factory::PageFactory;
+use crate::errors::{ServerResult, WithStatus};
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::moderation::{self, FlagKind, FlagStatus};
+use crate::models::session;
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct TermForm {
+    pub term: String,
+}
+
+async fn flash_redirect(
+    state: &AppState,
+    session_id: Uuid,
+    message: String,
+    flash_type: &str,
+) -> ServerResult<Response, StatusCode> {
+    session::set_flash_message(&state.db, session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/admin/moderation").into_response())
+}
+
+/// GET /admin/moderation - The review queue and the word list
+pub async fn show_moderation(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let flags = moderation::list_pending_flags(&state.db, moderation::QUEUE_LIMIT).await?;
+    let terms = moderation::list_terms(&state.db).await?;
+
+    Ok(page_factory.create_page(
+        "Moderation".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Moderation" }
+                p {
+                    "Snake names and shouts are checked against the word list below, ignoring case, "
+                    "punctuation, and digits standing in for letters. Names that match are refused; "
+                    "shouts that match are blanked before anyone sees them and queued here. "
+                    a href="/admin" { "Back to Admin Dashboard" }
+                }
+
+                h2 { "Review Queue (" (flags.len()) ")" }
+                @if flags.is_empty() {
+                    p { "Nothing waiting for review." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Kind" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Content" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Matched" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Snake" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Owner" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Flagged" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" {}
+                        }
+                        @for f in &flags {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    (f.kind.as_str())
+                                    @if let Some(game_id) = f.game_id {
+                                        " (" a href={"/admin/games/"(game_id)} { "game" } ")"
+                                    }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (f.content) } }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; color: #666;" { (f.matched_term) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/snakes/"(f.battlesnake_id)"/errors"} { (f.snake_name) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/users/"(f.owner_id)} { (f.owner_login) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (f.created_at.format("%Y-%m-%d %H:%M")) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; white-space: nowrap;" {
+                                    form action={"/admin/moderation/flags/"(f.moderation_flag_id)"/approve"} method="post" style="display: inline;" {
+                                        button type="submit" style="padding: 4px 10px;" title="The match was a false positive" { "Approve" }
+                                    }
+                                    " "
+                                    form action={"/admin/moderation/flags/"(f.moderation_flag_id)"/remove"} method="post" style="display: inline;" {
+                                        @if f.kind == FlagKind::Name {
+                                            button type="submit" style="padding: 4px 10px; background: #cc3333; color: white; border: none; border-radius: 4px;" title="Rename the snake to a placeholder" { "Reset Name" }
+                                        } @else {
+                                            button type="submit" style="padding: 4px 10px; background: #cc3333; color: white; border: none; border-radius: 4px;" title="The shout stays blanked" { "Confirm" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    @if flags.len() as i64 == moderation::QUEUE_LIMIT {
+                        p style="color: #666;" { "Showing the oldest " (moderation::QUEUE_LIMIT) "." }
+                    }
+                }
+
+                h2 { "Word List (" (terms.len()) ")" }
+                form action="/admin/moderation/terms" method="post" style="margin-bottom: 20px;" {
+                    input type="text" name="term" required placeholder="Word or phrase" style="padding: 8px; width: 240px;";
+                    " "
+                    button type="submit" style="padding: 8px 16px; background: #0066cc; color: white; border: none; border-radius: 4px; cursor: pointer;" { "Add" }
+                    p style="color: #666;" { "Adding a term also queues the existing snake names it matches." }
+                }
+                @if terms.is_empty() {
+                    p { "The word list is empty, so nothing is screened." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Term" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Added By" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Added" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" {}
+                        }
+                        @for t in &terms {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { code { (t.term) } }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    (t.created_by_login.as_deref().unwrap_or("—"))
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (t.created_at.format("%Y-%m-%d")) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    form action={"/admin/moderation/terms/"(t.moderation_term_id)"/delete"} method="post" {
+                                        button type="submit" style="padding: 4px 10px;" { "Remove" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /admin/moderation/terms - Add a term and queue the names it matches
+pub async fn add_term(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Form(form): Form<TermForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+
+    let term = match moderation::add_term(&state.db, &form.term, admin.user_id).await? {
+        Ok(term) => term,
+        Err(message) => {
+            return flash_redirect(
+                &state,
+                session.session_id,
+                message,
+                session::FLASH_TYPE_ERROR,
+            )
+            .await;
+        }
+    };
+    tracing::info!(term = %term, admin = %admin.github_login, "admin added moderation term");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::ModerationTermAdded,
+        AuditTarget::ModerationTerm(term.clone()),
+        None,
+        None,
+    )
+    .await?;
+
+    let queued =
+        moderation::flag_existing_names(&state.db, &moderation::WordList::new([term.clone()]))
+            .await?;
+    let message = match queued {
+        0 => format!("Added \"{term}\""),
+        1 => format!("Added \"{term}\"; 1 existing snake name is queued for review"),
+        n => format!("Added \"{term}\"; {n} existing snake names are queued for review"),
+    };
+    flash_redirect(
+        &state,
+        session.session_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+}
+
+/// POST /admin/moderation/terms/{id}/delete - Take a term off the list
+pub async fn remove_term(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(moderation_term_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+
+    let term = moderation::remove_term(&state.db, moderation_term_id)
+        .await?
+        .ok_or_else(|| "Term not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    tracing::info!(term = %term, admin = %admin.github_login, "admin removed moderation term");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::ModerationTermRemoved,
+        AuditTarget::ModerationTerm(term.clone()),
+        None,
+        None,
+    )
+    .await?;
+
+    flash_redirect(
+        &state,
+        session.session_id,
+        format!("Removed \"{term}\""),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+}
+
+/// POST /admin/moderation/flags/{id}/approve - The match was a false
+/// positive; leave the content alone
+pub async fn approve_flag(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(moderation_flag_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    resolve(
+        &state,
+        &admin,
+        session.session_id,
+        moderation_flag_id,
+        FlagStatus::Approved,
+    )
+    .await
+}
+
+/// POST /admin/moderation/flags/{id}/remove - Reset a flagged name, or
+/// confirm a flagged shout stays blanked
+pub async fn remove_flag(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(moderation_flag_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    resolve(
+        &state,
+        &admin,
+        session.session_id,
+        moderation_flag_id,
+        FlagStatus::Removed,
+    )
+    .await
+}
+
+/// Shared tail of approve and remove: close the flag and audit it
+async fn resolve(
+    state: &AppState,
+    admin: &crate::models::user::User,
+    session_id: Uuid,
+    moderation_flag_id: Uuid,
+    status: FlagStatus,
+) -> ServerResult<Response, StatusCode> {
+    let flag = moderation::get_flag_by_id(&state.db, moderation_flag_id)
+        .await?
+        .ok_or_else(|| "Flag not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    if !moderation::resolve_flag(&state.db, moderation_flag_id, status, admin.user_id).await? {
+        return flash_redirect(
+            state,
+            session_id,
+            "That flag was already reviewed".to_string(),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await;
+    }
+    tracing::info!(
+        moderation_flag_id = %moderation_flag_id,
+        status = status.as_str(),
+        admin = %admin.github_login,
+        "admin resolved moderation flag"
+    );
+    admin_audit::record(
+        &state.db,
+        admin,
+        AuditAction::ModerationFlagResolved,
+        AuditTarget::Battlesnake(flag.battlesnake_id),
+        Some(serde_json::json!({ "kind": flag.kind.as_str(), "content": flag.content })),
+        Some(serde_json::json!({ "status": status.as_str() })),
+    )
+    .await?;
+
+    let message = match (flag.kind, status) {
+        (FlagKind::Name, FlagStatus::Removed) => format!("Reset the name of {}", flag.snake_name),
+        (_, FlagStatus::Approved) => format!("Approved {} {}", flag.snake_name, flag.kind.as_str()),
+        _ => format!("Confirmed {}'s shout stays blanked", flag.snake_name),
+    };
+    flash_redirect(state, session_id, message, session::FLASH_TYPE_SUCCESS).await
+}
@@ -0,0 +1,144 @@
+//! Runtime settings editor. Changes reach every process within the settings
+//! cache TTL, with no redeploy.
+
+use std::collections::HashMap;
+
+use axum::Form;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+
+use super::require_admin;
+use crate::components::page_factory::PageFactory;
+use crate::errors::ServerResult;
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::session;
+use crate::models::setting::{self, Setting};
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::state::AppState;
+
+/// GET /admin/settings - Current runtime settings with an edit form
+pub async fn show_settings(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let current = setting::get_all(&state.db).await?;
+
+    Ok(page_factory.create_page(
+        "Runtime Settings".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Runtime Settings" }
+                p {
+                    "Changes take effect on every server within a few seconds. "
+                    a href="/admin" { "Back to Admin Dashboard" }
+                }
+
+                form action="/admin/settings" method="post" {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Setting" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Value" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Default" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Effect" }
+                        }
+                        @for s in Setting::ALL {
+                            @let (min, max) = s.range();
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    label for=(s.key()) { (s.label()) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    input type="number" id=(s.key()) name=(s.key()) value=(current.get(s))
+                                        min=(min) max=(max) step=(if s.is_integer() { "1" } else { "0.1" })
+                                        style="padding: 6px; width: 120px;";
+                                }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (s.default_value()) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; color: #666;" { (s.help()) }
+                            }
+                        }
+                    }
+                    button type="submit" style="padding: 8px 16px; background: #0066cc; color: white; border: none; border-radius: 4px; cursor: pointer;" { "Save" }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /admin/settings - Save changed settings, one audit entry each.
+/// Nothing is saved if any value is invalid.
+pub async fn update_settings(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Form(form): Form<HashMap<String, String>>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    let current = setting::get_all(&state.db).await?;
+
+    let mut changes = Vec::new();
+    let mut errors = Vec::new();
+    for s in Setting::ALL {
+        let Some(raw) = form.get(s.key()) else {
+            continue;
+        };
+        match raw
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("{} must be a number", s.label()))
+            .and_then(|value| s.validate(value))
+        {
+            Ok(value) if value != current.get(s) => changes.push((s, value)),
+            Ok(_) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            errors.join("; "),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+        return Ok(Redirect::to("/admin/settings").into_response());
+    }
+
+    for (s, value) in &changes {
+        setting::set(&state.db, *s, *value, admin.user_id).await?;
+        tracing::warn!(key = s.key(), value, admin = %admin.github_login, "admin changed runtime setting");
+        admin_audit::record(
+            &state.db,
+            &admin,
+            AuditAction::SettingChanged,
+            AuditTarget::Setting(s.key().to_string()),
+            Some(serde_json::json!({ "value": current.get(*s) })),
+            Some(serde_json::json!({ "value": value })),
+        )
+        .await?;
+    }
+    // Other processes pick the change up when their cache expires
+    state.settings_cache.put(setting::get_all(&state.db).await?);
+
+    let message = match changes.len() {
+        0 => "No settings changed".to_string(),
+        1 => "1 setting updated".to_string(),
+        n => format!("{n} settings updated"),
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/admin/settings").into_response())
+}
@@ -0,0 +1,235 @@
+//! Admin snake error report: snakes ranked by how often their /move calls
+//! fail (timeouts, non-2xx answers, unparseable bodies), with a drill-down
+//! into recent failed turns so an operator can contact the owner or pause
+//! the snake's leaderboard entries.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::require_admin;
+use crate::components::page_factory::PageFactory;
+use crate::errors::{ServerResult, WithStatus};
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::{battlesnake, leaderboard, session, snake_error, user};
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::state::AppState;
+
+/// Snakes with fewer moves in the window are left off the report
+const MIN_MOVES: i64 = 20;
+const REPORT_LIMIT: i64 = 100;
+const FAILED_MOVES_LIMIT: i64 = 50;
+/// Longest window the report covers: a week of snake_turns is already a
+/// big scan
+const MAX_WINDOW_HOURS: i64 = 24 * 7;
+
+#[derive(Debug, Deserialize)]
+pub struct WindowQuery {
+    #[serde(default = "default_hours")]
+    pub hours: i64,
+}
+
+fn default_hours() -> i64 {
+    24
+}
+
+impl WindowQuery {
+    fn hours(&self) -> i64 {
+        self.hours.clamp(1, MAX_WINDOW_HOURS)
+    }
+
+    fn since(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() - chrono::Duration::hours(self.hours())
+    }
+}
+
+fn percent(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+/// GET /admin/snakes/errors - Snakes ranked by recent /move failure rate
+pub async fn error_report(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<WindowQuery>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let hours = query.hours();
+    let report =
+        snake_error::get_error_report(&state.read_db, query.since(), MIN_MOVES, REPORT_LIMIT)
+            .await?;
+
+    Ok(page_factory.create_page(
+        "Snake Errors".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Snake Errors" }
+                p { a href="/admin" { "Back to Admin Dashboard" } }
+
+                form action="/admin/snakes/errors" method="get" style="margin-bottom: 20px;" {
+                    "Last "
+                    input type="number" name="hours" value=(hours) min="1" max=(MAX_WINDOW_HOURS) style="padding: 8px; width: 80px;";
+                    " hours "
+                    button type="submit" style="padding: 8px 16px;" { "Update" }
+                }
+
+                @if report.is_empty() {
+                    p { "No snake with at least " (MIN_MOVES) " moves had a failed move in this window." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Snake" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Owner" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Moves" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Failed" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Timeouts" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Non-2xx" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Parse Failures" }
+                        }
+                        @for s in &report {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/snakes/"(s.battlesnake_id)"/errors?hours="(hours)} { (s.name) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/users/"(s.owner_id)} { (s.owner_login) }
+                                }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (s.total_moves) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (percent(s.error_rate())) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (percent(s.timeout_rate())) " (" (s.timeouts) ")" }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (percent(s.http_error_rate())) " (" (s.http_errors) ")" }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (percent(s.parse_failure_rate())) " (" (s.parse_failures) ")" }
+                            }
+                        }
+                    }
+                    p style="color: #666;" {
+                        "Snakes with fewer than " (MIN_MOVES) " moves in the window aren't shown. Timeouts include network errors."
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// GET /admin/snakes/{id}/errors - A snake's recent failed moves
+pub async fn snake_errors(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Path(battlesnake_id): Path<Uuid>,
+    Query(query): Query<WindowQuery>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let snake = battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let owner = user::get_user_by_id(&state.db, snake.user_id).await?;
+    let failed = snake_error::get_recent_failed_moves(
+        &state.read_db,
+        battlesnake_id,
+        query.since(),
+        FAILED_MOVES_LIMIT,
+    )
+    .await?;
+
+    Ok(page_factory.create_page(
+        format!("Errors for {}", snake.name),
+        Box::new(html! {
+            div {
+                h1 { "Errors for " (snake.name) }
+                p {
+                    @if let Some(owner) = &owner {
+                        "Owner: " a href={"/admin/users/"(owner.user_id)} { (owner.github_login) }
+                        @if let Some(email) = &owner.github_email {
+                            " · " a href={"mailto:"(email)} { (email) }
+                        }
+                        " · "
+                    }
+                    "URL: " code { (snake.url) }
+                    " · "
+                    a href={"/admin/snakes/errors?hours="(query.hours())} { "Back to Snake Errors" }
+                }
+
+                form action={"/admin/snakes/"(battlesnake_id)"/pause"} method="post" style="margin-bottom: 20px;" {
+                    button type="submit" style="padding: 8px 16px; background: #c0392b; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                        onclick="return confirm('Pause all of this snake\\'s leaderboard entries? The owner can resume them.');" { "Pause Leaderboard Entries" }
+                }
+
+                h2 { "Recent Failed Moves (last " (query.hours()) " hours)" }
+                @if failed.is_empty() {
+                    p { "No failed moves in this window." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "When" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Game" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Turn" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Failure" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Latency" }
+                        }
+                        @for f in &failed {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (f.created_at.format("%Y-%m-%d %H:%M:%S")) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/games/"(f.game_id)} { code { (f.game_id) } }
+                                }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (f.turn_number) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (f.describe()) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if let Some(ms) = f.latency_ms { (ms) "ms" } @else { "-" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /admin/snakes/{id}/pause - Pause a misbehaving snake's leaderboard entries
+pub async fn pause_snake(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(battlesnake_id): Path<Uuid>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    let paused = leaderboard::pause_entries_for_battlesnake(&state.db, battlesnake_id).await?;
+    tracing::info!(battlesnake_id = %battlesnake_id, paused, admin = %admin.github_login, "admin paused snake's leaderboard entries");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::EntriesPaused,
+        AuditTarget::Battlesnake(battlesnake_id),
+        None,
+        Some(serde_json::json!({ "paused_entries": paused })),
+    )
+    .await?;
+
+    let message = match paused {
+        0 => "This snake has no active leaderboard entries".to_string(),
+        1 => "Paused 1 leaderboard entry".to_string(),
+        n => format!("Paused {n} leaderboard entries"),
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(&format!("/admin/snakes/{battlesnake_id}/errors")).into_response())
+}
@@ -0,0 +1,127 @@
+//! Admin tournament list and creation. Tournaments an admin creates here are
+//! owned by that admin and run like any other: open registration, start, and
+//! run rounds from the tournament's own page.
+
+use axum::Form;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+
+use super::require_admin;
+use crate::components::page_factory::PageFactory;
+use crate::errors::ServerResult;
+use crate::models::admin_audit::{self, AuditAction, AuditTarget};
+use crate::models::{session, tournament};
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::routes::tournament::{TournamentSettingsForm, tournament_form_fields};
+use crate::state::AppState;
+
+/// GET /admin/tournaments - Every tournament, plus a form to create one
+pub async fn list_tournaments(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let tournaments = tournament::list_all_tournaments(&state.db).await?;
+
+    Ok(page_factory.create_page(
+        "Tournaments".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Tournaments" }
+                p { a href="/admin" { "Back to Admin Dashboard" } }
+
+                @if tournaments.is_empty() {
+                    p { "No tournaments yet." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 30px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Tournament" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Owner" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Format" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Snakes" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Created" }
+                        }
+                        @for t in &tournaments {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/tournaments/"(t.tournament_id)} { (t.name) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (t.owner_login) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (t.format.label()) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (t.status.as_str()) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (t.registration_count) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (t.created_at.format("%Y-%m-%d")) }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Create Tournament" }
+                form class="form-stack" action="/admin/tournaments" method="post" {
+                    (tournament_form_fields(None))
+                    button type="submit" style="padding: 8px 16px; background: #0066cc; color: white; border: none; border-radius: 4px; cursor: pointer;" { "Create Tournament" }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /admin/tournaments - Create a tournament owned by the admin
+pub async fn create_tournament(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Form(form): Form<TournamentSettingsForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+
+    let params = match form.to_create() {
+        Ok(params) => params,
+        Err(message) => {
+            session::set_flash_message(
+                &state.db,
+                session.session_id,
+                message,
+                session::FLASH_TYPE_ERROR,
+            )
+            .await
+            .wrap_err("Failed to set flash message")?;
+            return Ok(Redirect::to("/admin/tournaments").into_response());
+        }
+    };
+
+    let created = tournament::create_tournament(&state.db, admin.user_id, params)
+        .await
+        .wrap_err("Failed to create tournament")?;
+    tracing::info!(tournament_id = %created.tournament_id, admin = %admin.github_login, "admin created tournament");
+    admin_audit::record(
+        &state.db,
+        &admin,
+        AuditAction::TournamentCreated,
+        AuditTarget::Tournament(created.tournament_id),
+        None,
+        Some(serde_json::json!({
+            "name": created.name,
+            "format": created.format.as_str(),
+            "match_style": created.match_style.as_str(),
+            "auto_advance": created.auto_advance,
+        })),
+    )
+    .await?;
+
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        format!("{} created", created.name),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(&format!("/tournaments/{}", created.tournament_id)).into_response())
+}
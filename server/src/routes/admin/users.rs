@@ -0,0 +1,310 @@
+//! Admin user management: search users, see their snakes and API tokens,
+//! grant or revoke admin, and suspend accounts.
+
+use axum::Form;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Redirect, Response};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::require_admin;
+use crate::components::page_factory::PageFactory;
+use crate::errors::{ServerResult, WithStatus};
+use crate::models::{api_token, battlesnake, session, user};
+use crate::routes::api::admin_users::{
+    apply_admin_change, apply_suspension_change, self_change_error,
+};
+use crate::routes::auth::{AdminUser, CurrentUserWithSession};
+use crate::state::AppState;
+
+const SEARCH_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminForm {
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuspendForm {
+    pub suspended: bool,
+}
+
+/// GET /admin/users - Search users by login or display name
+pub async fn list_users(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<SearchQuery>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let q = query.q.trim();
+    let users = user::search_users(&state.db, q, SEARCH_LIMIT).await?;
+
+    Ok(page_factory.create_page(
+        "Users".to_string(),
+        Box::new(html! {
+            div {
+                h1 { "Users" }
+                p { a href="/admin" { "Back to Admin Dashboard" } }
+
+                form action="/admin/users" method="get" style="margin-bottom: 20px;" {
+                    input type="search" name="q" value=(q) placeholder="GitHub login or display name" style="padding: 8px; width: 300px;";
+                    " "
+                    button type="submit" style="padding: 8px 16px;" { "Search" }
+                }
+
+                @if users.is_empty() {
+                    p { "No users match." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Login" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Display Name" }
+                            th style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Snakes" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Status" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Joined" }
+                        }
+                        @for u in &users {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/admin/users/"(u.user_id)} { (u.github_login) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (u.display_name.as_deref().unwrap_or("-")) }
+                                td style="text-align: right; padding: 8px; border-bottom: 1px solid #ddd;" { (u.snake_count) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if u.suspended_at.is_some() { span style="color: #c0392b;" { "Suspended" } " " }
+                                    @if u.is_admin { "Admin" }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (u.created_at.format("%Y-%m-%d")) }
+                            }
+                        }
+                    }
+                    @if users.len() as i64 == SEARCH_LIMIT {
+                        p style="color: #666;" { "Showing the first " (SEARCH_LIMIT) " matches — narrow the search to see more." }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// GET /admin/users/{id} - A user with their snakes, API tokens, and admin actions
+pub async fn show_user(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<Uuid>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let u = user::get_user_by_id(&state.db, user_id)
+        .await?
+        .ok_or_else(|| "User not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user_id).await?;
+    let tokens = api_token::list_user_tokens(&state.db, user_id).await?;
+    let is_self = admin.user_id == user_id;
+
+    Ok(page_factory.create_page(
+        format!("User {}", u.github_login),
+        Box::new(html! {
+            div {
+                h1 { (u.github_login) }
+                p {
+                    a href={"/users/"(u.github_login)} { "Public profile" }
+                    " · "
+                    a href="/admin/users" { "Back to Users" }
+                }
+
+                table style="border-collapse: collapse; width: 100%; max-width: 600px; margin-bottom: 20px;" {
+                    @for (label, value) in [
+                        ("User ID", u.user_id.to_string()),
+                        ("Display name", u.display_name.clone().unwrap_or_else(|| "-".to_string())),
+                        ("Email", u.github_email.clone().unwrap_or_else(|| "-".to_string())),
+                        ("Admin", if u.is_admin { "Yes" } else { "No" }.to_string()),
+                        ("Suspended", u.suspended_at.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "No".to_string())),
+                        ("Joined", u.created_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    ] {
+                        tr {
+                            td style="padding: 8px; border-bottom: 1px solid #ddd; font-weight: bold;" { (label) }
+                            td style="padding: 8px; border-bottom: 1px solid #ddd;" { (value) }
+                        }
+                    }
+                }
+
+                h2 { "Actions" }
+                @if is_self {
+                    p style="color: #666;" { "You can't demote or suspend yourself." }
+                } @else {
+                    div style="display: flex; gap: 12px; margin-bottom: 20px;" {
+                        form action={"/admin/users/"(user_id)"/admin"} method="post" {
+                            input type="hidden" name="is_admin" value=(!u.is_admin);
+                            button type="submit" style="padding: 8px 16px; background: #0066cc; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                                onclick="return confirm('Change this user\\'s admin access?');" {
+                                @if u.is_admin { "Revoke Admin" } @else { "Grant Admin" }
+                            }
+                        }
+                        form action={"/admin/users/"(user_id)"/suspend"} method="post" {
+                            input type="hidden" name="suspended" value=(!u.is_suspended());
+                            @if u.is_suspended() {
+                                button type="submit" style="padding: 8px 16px; background: #27ae60; color: white; border: none; border-radius: 4px; cursor: pointer;" { "Lift Suspension" }
+                            } @else {
+                                button type="submit" style="padding: 8px 16px; background: #c0392b; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                                    onclick="return confirm('Suspend this account? They won\\'t be able to create games, and their leaderboard entries will be paused.');" { "Suspend" }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "Battlesnakes" }
+                @if snakes.is_empty() {
+                    p { "No battlesnakes." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Name" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "URL" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Visibility" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Created" }
+                        }
+                        @for snake in &snakes {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    a href={"/battlesnakes/"(snake.battlesnake_id)"/profile"} { (snake.name) }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd; font-family: monospace;" { (snake.url) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (snake.visibility.as_str()) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (snake.created_at.format("%Y-%m-%d")) }
+                            }
+                        }
+                    }
+                }
+
+                h2 { "API Tokens" }
+                @if tokens.is_empty() {
+                    p { "No API tokens." }
+                } @else {
+                    table style="border-collapse: collapse; width: 100%; margin-bottom: 20px;" {
+                        tr {
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Name" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Scopes" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Last Used" }
+                            th style="text-align: left; padding: 8px; border-bottom: 1px solid #ddd; background-color: #f5f5f5;" { "Created" }
+                        }
+                        @for token in &tokens {
+                            tr {
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (token.name) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (token.scope_names.join(", ")) }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" {
+                                    @if let Some(ts) = token.last_used_at { (ts.format("%Y-%m-%d %H:%M")) } @else { "Never" }
+                                }
+                                td style="padding: 8px; border-bottom: 1px solid #ddd;" { (token.created_at.format("%Y-%m-%d")) }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+async fn flash_and_return(
+    state: &AppState,
+    session_id: Uuid,
+    user_id: Uuid,
+    message: &str,
+    flash_type: &str,
+) -> ServerResult<Response, StatusCode> {
+    session::set_flash_message(&state.db, session_id, message.to_string(), flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(&format!("/admin/users/{user_id}")).into_response())
+}
+
+/// POST /admin/users/{id}/admin - Grant or revoke admin
+pub async fn set_admin(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(user_id): Path<Uuid>,
+    Form(form): Form<AdminForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    if let Some(message) = self_change_error(&admin, user_id, Some(form.is_admin), None) {
+        return flash_and_return(
+            &state,
+            session.session_id,
+            user_id,
+            message,
+            session::FLASH_TYPE_ERROR,
+        )
+        .await;
+    }
+
+    if !apply_admin_change(&state, &admin, user_id, form.is_admin).await? {
+        return Err("User not found".to_string()).with_status(StatusCode::NOT_FOUND);
+    }
+
+    let message = if form.is_admin {
+        "Admin access granted"
+    } else {
+        "Admin access revoked"
+    };
+    flash_and_return(
+        &state,
+        session.session_id,
+        user_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+}
+
+/// POST /admin/users/{id}/suspend - Suspend or reinstate an account
+pub async fn set_suspended(
+    State(state): State<AppState>,
+    CurrentUserWithSession {
+        user: admin,
+        session,
+    }: CurrentUserWithSession,
+    Path(user_id): Path<Uuid>,
+    Form(form): Form<SuspendForm>,
+) -> ServerResult<Response, StatusCode> {
+    require_admin(&admin)?;
+    if let Some(message) = self_change_error(&admin, user_id, None, Some(form.suspended)) {
+        return flash_and_return(
+            &state,
+            session.session_id,
+            user_id,
+            message,
+            session::FLASH_TYPE_ERROR,
+        )
+        .await;
+    }
+
+    if !apply_suspension_change(&state, &admin, user_id, form.suspended).await? {
+        return Err("User not found".to_string()).with_status(StatusCode::NOT_FOUND);
+    }
+
+    let message = if form.suspended {
+        "Account suspended and leaderboard entries paused"
+    } else {
+        "Suspension lifted and leaderboard entries resumed"
+    };
+    flash_and_return(
+        &state,
+        session.session_id,
+        user_id,
+        message,
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+}
@@ -0,0 +1,391 @@
+use axum::{Json, extract::State, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        admin_audit::{self, AuditAction, AuditTarget},
+        api_token, battlesnake, tag,
+        user::{self, User, UserSearchResult},
+    },
+    routes::{
+        api::{
+            battlesnakes::BattlesnakeResponse,
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath, ApiQuery},
+            tokens::TokenResponse,
+        },
+        auth::AdminApiUser,
+    },
+    state::AppState,
+};
+
+/// A user as admins see them
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserResponse {
+    pub id: Uuid,
+    pub github_login: String,
+    pub display_name: Option<String>,
+    pub is_admin: bool,
+    /// When the account was suspended, or null if it isn't
+    pub suspended_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&User> for AdminUserResponse {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.user_id,
+            github_login: user.github_login.clone(),
+            display_name: user.display_name.clone(),
+            is_admin: user.is_admin,
+            suspended_at: user.suspended_at,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// One row of an admin user search
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserSearchResponse {
+    #[serde(flatten)]
+    pub user: AdminUserResponse,
+    pub snake_count: i64,
+}
+
+impl From<UserSearchResult> for AdminUserSearchResponse {
+    fn from(result: UserSearchResult) -> Self {
+        Self {
+            user: AdminUserResponse {
+                id: result.user_id,
+                github_login: result.github_login,
+                display_name: result.display_name,
+                is_admin: result.is_admin,
+                suspended_at: result.suspended_at,
+                created_at: result.created_at,
+            },
+            snake_count: result.snake_count,
+        }
+    }
+}
+
+/// A user with their snakes and API tokens (never the token secrets)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserDetailResponse {
+    pub user: AdminUserResponse,
+    pub battlesnakes: Vec<BattlesnakeResponse>,
+    pub api_tokens: Vec<TokenResponse>,
+}
+
+/// Query parameters for searching users
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchUsersQuery {
+    /// Substring of the GitHub login or display name; omit for the newest users
+    #[serde(default)]
+    pub q: String,
+    /// Max users to return (default 50, capped at 200)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Request body for changing a user's admin flag or suspension. Omitted
+/// fields are left alone.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateAdminUserRequest {
+    pub is_admin: Option<bool>,
+    pub suspended: Option<bool>,
+}
+
+/// Admins can't demote or suspend themselves: with one admin left that
+/// would lock everyone out of this page.
+pub(crate) fn self_change_error(
+    admin: &User,
+    target_id: Uuid,
+    is_admin: Option<bool>,
+    suspended: Option<bool>,
+) -> Option<&'static str> {
+    if admin.user_id != target_id {
+        None
+    } else if is_admin == Some(false) {
+        Some("You can't revoke your own admin access")
+    } else if suspended == Some(true) {
+        Some("You can't suspend your own account")
+    } else {
+        None
+    }
+}
+
+/// Set a user's admin flag and audit it. Shared by the web and API
+/// handlers. Returns `false` if the user doesn't exist.
+pub(crate) async fn apply_admin_change(
+    state: &AppState,
+    admin: &User,
+    user_id: Uuid,
+    is_admin: bool,
+) -> cja::Result<bool> {
+    let Some(before) = user::get_user_by_id(&state.db, user_id).await? else {
+        return Ok(false);
+    };
+    if !user::set_admin(&state.db, user_id, is_admin).await? {
+        return Ok(false);
+    }
+    tracing::info!(user_id = %user_id, is_admin, admin = %admin.github_login, "admin changed admin flag");
+    admin_audit::record(
+        &state.db,
+        admin,
+        AuditAction::AdminChanged,
+        AuditTarget::User(user_id),
+        Some(serde_json::json!({ "is_admin": before.is_admin })),
+        Some(serde_json::json!({ "is_admin": is_admin })),
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Suspend or reinstate a user and audit it. Shared by the web and API
+/// handlers. Returns `false` if the user doesn't exist.
+pub(crate) async fn apply_suspension_change(
+    state: &AppState,
+    admin: &User,
+    user_id: Uuid,
+    suspended: bool,
+) -> cja::Result<bool> {
+    let Some(before) = user::get_user_by_id(&state.db, user_id).await? else {
+        return Ok(false);
+    };
+    if !user::set_suspended(&state.db, user_id, suspended).await? {
+        return Ok(false);
+    }
+    tracing::info!(user_id = %user_id, suspended, admin = %admin.github_login, "admin changed suspension");
+    admin_audit::record(
+        &state.db,
+        admin,
+        AuditAction::SuspensionChanged,
+        AuditTarget::User(user_id),
+        Some(serde_json::json!({ "suspended_at": before.suspended_at })),
+        Some(serde_json::json!({ "suspended": suspended })),
+    )
+    .await?;
+    Ok(true)
+}
+
+pub(crate) async fn load_user_detail(
+    state: &AppState,
+    user: &User,
+) -> ApiResult<AdminUserDetailResponse> {
+    let snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to list battlesnakes", e))?;
+    let mut battlesnakes = Vec::with_capacity(snakes.len());
+    for snake in snakes {
+        let tags = tag::get_tags_for_battlesnake(&state.db, snake.battlesnake_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to fetch battlesnake tags", e))?;
+        battlesnakes.push(BattlesnakeResponse::new(snake, tags));
+    }
+
+    let api_tokens = api_token::list_user_tokens(&state.db, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to list API tokens", e))?
+        .into_iter()
+        .map(TokenResponse::from)
+        .collect();
+
+    Ok(AdminUserDetailResponse {
+        user: AdminUserResponse::from(user),
+        battlesnakes,
+        api_tokens,
+    })
+}
+
+/// GET /api/v1/admin/users - Search users by login or display name
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users",
+    tag = "admin",
+    params(SearchUsersQuery),
+    responses(
+        (status = 200, description = "Matching users, newest first", body = Vec<AdminUserSearchResponse>),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn search_users(
+    State(state): State<AppState>,
+    AdminApiUser(_admin): AdminApiUser,
+    ApiQuery(query): ApiQuery<SearchUsersQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let limit = query.limit.min(200) as i64;
+    let users = user::search_users(&state.db, query.q.trim(), limit)
+        .await
+        .map_err(|e| ApiError::internal("Failed to search users", e))?;
+
+    Ok(Json(
+        users
+            .into_iter()
+            .map(AdminUserSearchResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /api/v1/admin/users/{id} - A user with their snakes and API tokens
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user, their snakes, and their API tokens", body = AdminUserDetailResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+        (status = 404, description = "User not found", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_user(
+    State(state): State<AppState>,
+    AdminApiUser(_admin): AdminApiUser,
+    ApiPath(user_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let user = user::get_user_by_id(&state.db, user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch user", e))?
+        .ok_or(ApiError::not_found("User not found"))?;
+
+    Ok(Json(load_user_detail(&state, &user).await?))
+}
+
+/// PATCH /api/v1/admin/users/{id} - Grant/revoke admin or suspend/reinstate
+///
+/// Suspension blocks game creation and leaderboard joins, and pauses the
+/// user's active leaderboard entries until it's lifted.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/admin/users/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = UpdateAdminUserRequest,
+    responses(
+        (status = 200, description = "The updated user", body = AdminUserResponse),
+        (status = 400, description = "Tried to demote or suspend yourself", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+        (status = 404, description = "User not found", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_user(
+    State(state): State<AppState>,
+    AdminApiUser(admin): AdminApiUser,
+    ApiPath(user_id): ApiPath<Uuid>,
+    ApiJson(request): ApiJson<UpdateAdminUserRequest>,
+) -> ApiResult<impl IntoResponse> {
+    if let Some(message) = self_change_error(&admin, user_id, request.is_admin, request.suspended) {
+        return Err(ApiError::bad_request(message));
+    }
+
+    let not_found = || ApiError::not_found("User not found");
+    if let Some(is_admin) = request.is_admin
+        && !apply_admin_change(&state, &admin, user_id, is_admin)
+            .await
+            .map_err(|e| ApiError::internal("Failed to update admin flag", e))?
+    {
+        return Err(not_found());
+    }
+    if let Some(suspended) = request.suspended
+        && !apply_suspension_change(&state, &admin, user_id, suspended)
+            .await
+            .map_err(|e| ApiError::internal("Failed to update suspension", e))?
+    {
+        return Err(not_found());
+    }
+
+    let user = user::get_user_by_id(&state.db, user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch user", e))?
+        .ok_or_else(not_found)?;
+    Ok(Json(AdminUserResponse::from(&user)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+
+    async fn insert_user(pool: &sqlx::PgPool, github_id: i64, login: &str) -> User {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES ($1, $2, 'test-token') RETURNING user_id",
+        )
+        .bind(github_id)
+        .bind(login)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        user::get_user_by_id(pool, user_id).await.unwrap().unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_update_user_suspends_and_refuses_self_demotion(pool: sqlx::PgPool) {
+        let admin = insert_user(&pool, 1, "admin").await;
+        let target = insert_user(&pool, 2, "target").await;
+        let state = AppState::test_from_pool(pool.clone());
+
+        let err = update_user(
+            State(state.clone()),
+            AdminApiUser(
+                user::get_user_by_id(&pool, admin.user_id)
+                    .await
+                    .unwrap()
+                    .unwrap(),
+            ),
+            ApiPath(admin.user_id),
+            ApiJson(UpdateAdminUserRequest {
+                is_admin: Some(false),
+                suspended: None,
+            }),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(err.status(), axum::http::StatusCode::BAD_REQUEST);
+
+        update_user(
+            State(state.clone()),
+            AdminApiUser(admin),
+            ApiPath(target.user_id),
+            ApiJson(UpdateAdminUserRequest {
+                is_admin: Some(true),
+                suspended: Some(true),
+            }),
+        )
+        .await
+        .unwrap();
+        let target = user::get_user_by_id(&pool, target.user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(target.is_admin);
+        assert!(target.is_suspended());
+
+        let audit = admin_audit::list_recent(&pool, 10).await.unwrap();
+        let mut actions: Vec<_> = audit.iter().map(|e| e.action.as_str()).collect();
+        actions.sort();
+        assert_eq!(actions, ["admin_changed", "suspension_changed"]);
+    }
+
+    #[test]
+    fn test_search_users_query_defaults() {
+        let uri: axum::http::Uri = "/api/v1/admin/users".parse().unwrap();
+        let Query(query) = Query::<SearchUsersQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.limit, 50);
+        assert_eq!(query.q, "");
+    }
+}
@@ -0,0 +1,328 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        battlesnake::{self, Battlesnake, CreateBattlesnake, UpdateBattlesnake, Visibility},
+        tag, tournament,
+    },
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath},
+            snakes::validate_url,
+        },
+        auth::ApiUser,
+    },
+    state::AppState,
+};
+
+/// A registered battlesnake with its tags
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BattlesnakeResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub visibility: Visibility,
+    pub tags: Vec<TagInfo>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Tag from the curated catalog
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagInfo {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl BattlesnakeResponse {
+    pub(crate) fn new(snake: Battlesnake, tags: Vec<tag::Tag>) -> Self {
+        Self {
+            id: snake.battlesnake_id,
+            name: snake.name,
+            url: snake.url,
+            visibility: snake.visibility,
+            tags: tags
+                .into_iter()
+                .map(|t| TagInfo {
+                    id: t.tag_id,
+                    name: t.name,
+                })
+                .collect(),
+            created_at: snake.created_at,
+            updated_at: snake.updated_at,
+        }
+    }
+}
+
+/// Request body for registering a battlesnake
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterBattlesnakeRequest {
+    pub name: String,
+    pub url: String,
+    pub visibility: Visibility,
+    /// Tag IDs from the catalog (at most 5)
+    #[serde(default)]
+    pub tags: Vec<Uuid>,
+}
+
+/// Request body for updating a battlesnake. Omitted fields are left as-is;
+/// `tags`, when present, replaces the snake's whole tag set.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PatchBattlesnakeRequest {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub visibility: Option<Visibility>,
+    pub tags: Option<Vec<Uuid>>,
+}
+
+/// A non-empty name, like the web form, and an HTTP(S) URL, like `/api/v1/snakes`
+fn validate_fields(name: &str, url: &str) -> ApiResult<()> {
+    if name.trim().is_empty() {
+        return Err(ApiError::bad_request("Name is required"));
+    }
+    validate_url(url).map_err(|e| ApiError::bad_request(e.to_string()))
+}
+
+/// Check tag IDs against the cap and the catalog, so bad input is a 400 rather
+/// than a foreign key failure
+async fn validate_tags(state: &AppState, tag_ids: &[Uuid]) -> ApiResult<()> {
+    if tag_ids.len() > tag::MAX_TAGS_PER_SNAKE {
+        return Err(ApiError::bad_request(format!(
+            "A battlesnake can have at most {} tags",
+            tag::MAX_TAGS_PER_SNAKE
+        )));
+    }
+    if tag_ids.is_empty() {
+        return Ok(());
+    }
+
+    let catalog = tag::get_tag_catalog(&state.db)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch tag catalog", e))?;
+    if let Some(unknown) = tag_ids.iter().find(|id| {
+        !catalog
+            .languages
+            .iter()
+            .chain(&catalog.platforms)
+            .any(|t| t.tag_id == **id)
+    }) {
+        return Err(ApiError::bad_request(format!("Unknown tag {unknown}")));
+    }
+
+    Ok(())
+}
+
+fn name_conflict_or_internal(context: &str, e: color_eyre::Report) -> ApiError {
+    let msg = e.to_string();
+    if msg == battlesnake::NAME_NOT_ALLOWED {
+        ApiError::bad_request(msg)
+    } else if msg.contains("already have a battlesnake named") {
+        ApiError::conflict(msg)
+    } else {
+        ApiError::internal(context, e)
+    }
+}
+
+async fn battlesnake_response(
+    state: &AppState,
+    snake: Battlesnake,
+) -> ApiResult<BattlesnakeResponse> {
+    let tags = tag::get_tags_for_battlesnake(&state.db, snake.battlesnake_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch battlesnake tags", e))?;
+    Ok(BattlesnakeResponse::new(snake, tags))
+}
+
+/// POST /api/v1/battlesnakes - Register a battlesnake
+#[utoipa::path(
+    post,
+    path = "/api/v1/battlesnakes",
+    tag = "snakes",
+    request_body = RegisterBattlesnakeRequest,
+    responses(
+        (status = 201, description = "Battlesnake registered", body = BattlesnakeResponse),
+        (status = 400, description = "Missing or disallowed name, invalid URL, or bad tags", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 409, description = "You already have a battlesnake with this name", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn register_battlesnake(
+    State(state): State<AppState>,
+    ApiUser(user): ApiUser,
+    ApiJson(request): ApiJson<RegisterBattlesnakeRequest>,
+) -> ApiResult<impl IntoResponse> {
+    validate_fields(&request.name, &request.url)?;
+    validate_tags(&state, &request.tags).await?;
+
+    let snake = battlesnake::create_battlesnake(
+        &state.db,
+        user.user_id,
+        CreateBattlesnake {
+            name: request.name,
+            url: request.url,
+            visibility: request.visibility,
+        },
+    )
+    .await
+    .map_err(|e| name_conflict_or_internal("Failed to register battlesnake", e))?;
+
+    tag::set_tags_for_battlesnake(&state.db, snake.battlesnake_id, &request.tags)
+        .await
+        .map_err(|e| ApiError::internal("Failed to set battlesnake tags", e))?;
+
+    if snake.visibility == Visibility::Public {
+        state
+            .discord
+            .notify_snake_registered(&snake.name, &user.github_login);
+    }
+
+    let response = battlesnake_response(&state, snake).await?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// PATCH /api/v1/battlesnakes/{id} - Update a battlesnake's name, URL, visibility, or tags
+#[utoipa::path(
+    patch,
+    path = "/api/v1/battlesnakes/{id}",
+    tag = "snakes",
+    params(("id" = Uuid, Path, description = "Battlesnake ID")),
+    request_body = PatchBattlesnakeRequest,
+    responses(
+        (status = 200, description = "Updated battlesnake", body = BattlesnakeResponse),
+        (status = 400, description = "Empty or disallowed name, invalid URL, or bad tags", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such battlesnake owned by you", body = ApiErrorBody),
+        (status = 409, description = "You already have a battlesnake with this name", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn patch_battlesnake(
+    State(state): State<AppState>,
+    ApiUser(user): ApiUser,
+    ApiPath(battlesnake_id): ApiPath<Uuid>,
+    ApiJson(request): ApiJson<PatchBattlesnakeRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let existing = battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to get battlesnake", e))?
+        .filter(|snake| snake.user_id == user.user_id)
+        .ok_or(ApiError::not_found("Battlesnake not found"))?;
+
+    let update_data = UpdateBattlesnake {
+        name: request.name.unwrap_or(existing.name),
+        url: request.url.unwrap_or(existing.url),
+        visibility: request.visibility.unwrap_or(existing.visibility),
+    };
+    validate_fields(&update_data.name, &update_data.url)?;
+    if let Some(tag_ids) = &request.tags {
+        validate_tags(&state, tag_ids).await?;
+    }
+
+    let snake =
+        battlesnake::update_battlesnake(&state.db, battlesnake_id, user.user_id, update_data)
+            .await
+            .map_err(|e| name_conflict_or_internal("Failed to update battlesnake", e))?;
+
+    if let Some(tag_ids) = &request.tags {
+        tag::set_tags_for_battlesnake(&state.db, battlesnake_id, tag_ids)
+            .await
+            .map_err(|e| ApiError::internal("Failed to set battlesnake tags", e))?;
+    }
+
+    Ok(Json(battlesnake_response(&state, snake).await?))
+}
+
+/// DELETE /api/v1/battlesnakes/{id} - Delete a battlesnake
+#[utoipa::path(
+    delete,
+    path = "/api/v1/battlesnakes/{id}",
+    tag = "snakes",
+    params(("id" = Uuid, Path, description = "Battlesnake ID")),
+    responses(
+        (status = 204, description = "Battlesnake deleted"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such battlesnake owned by you", body = ApiErrorBody),
+        (status = 409, description = "Registered in an active tournament", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_battlesnake(
+    State(state): State<AppState>,
+    ApiUser(user): ApiUser,
+    ApiPath(battlesnake_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let owned = battlesnake::belongs_to_user(&state.db, battlesnake_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to check battlesnake ownership", e))?;
+    if !owned {
+        return Err(ApiError::not_found("Battlesnake not found"));
+    }
+
+    // Same guard as the web form: the FK cascades would rip the snake out of
+    // a live bracket.
+    let active_registrations =
+        tournament::count_active_tournament_registrations(&state.db, battlesnake_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to check tournament registrations", e))?;
+    if active_registrations > 0 {
+        return Err(ApiError::conflict(
+            "This battlesnake is registered in an active tournament. Withdraw it from the tournament first.",
+        ));
+    }
+
+    battlesnake::delete_battlesnake(&state.db, battlesnake_id, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to delete battlesnake", e))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_request_requires_visibility_and_defaults_tags() {
+        let request: RegisterBattlesnakeRequest = serde_json::from_str(
+            r#"{"name": "ci-snake", "url": "https://example.com", "visibility": "private"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.visibility, Visibility::Private);
+        assert!(request.tags.is_empty());
+
+        assert!(
+            serde_json::from_str::<RegisterBattlesnakeRequest>(
+                r#"{"name": "ci-snake", "url": "https://example.com"}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        assert!(validate_fields("snake", "https://example.com").is_ok());
+        assert_eq!(
+            validate_fields("  ", "https://example.com")
+                .unwrap_err()
+                .status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            validate_fields("  ", "https://example.com")
+                .unwrap_err()
+                .message(),
+            "Name is required"
+        );
+        assert_eq!(
+            validate_fields("snake", "ftp://example.com")
+                .unwrap_err()
+                .status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! CORS for the JSON API.
+//!
+//! The board viewer endpoints are public and fetched by board.battlesnake.com
+//! and the GIF exporter, so they allow any origin. The `/api/v1` resources
+//! (and their deprecated unversioned aliases) allow the origins listed in
+//! `API_CORS_ALLOWED_ORIGINS`, or any origin when that's unset. Neither
+//! allows credentials: browser callers authenticate with a Bearer token.
+
+use axum::http::{HeaderName, HeaderValue, header};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Response headers browser clients are allowed to read
+const EXPOSED_HEADERS: [HeaderName; 7] = [
+    header::RETRY_AFTER,
+    header::LINK,
+    HeaderName::from_static("x-ratelimit-limit"),
+    HeaderName::from_static("x-ratelimit-remaining"),
+    HeaderName::from_static("x-ratelimit-reset"),
+    HeaderName::from_static("deprecation"),
+    HeaderName::from_static("sunset"),
+];
+
+/// Any origin, for the engine-compatible board viewer endpoints
+pub fn public_cors() -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
+
+/// The configured allow-list (empty means any origin), for `/api/v1`
+pub fn api_cors(allowed_origins: &[String]) -> CorsLayer {
+    let allow_origin = if allowed_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        let origins: Vec<HeaderValue> = allowed_origins
+            .iter()
+            .filter_map(|origin| match HeaderValue::from_str(origin) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    tracing::warn!(origin = %origin, "ignoring invalid CORS origin");
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(origins)
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .expose_headers(EXPOSED_HEADERS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt as _;
+
+    async fn allowed_origin(layer: CorsLayer, origin: &str) -> Option<String> {
+        let app = axum::Router::new()
+            .route("/things", axum::routing::get(|| async { "ok" }))
+            .layer(layer);
+        let response = app
+            .oneshot(
+                Request::get("/things")
+                    .header(header::ORIGIN, origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_empty_allow_list_allows_any_origin() {
+        assert_eq!(
+            allowed_origin(api_cors(&[]), "https://anywhere.example").await,
+            Some("*".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allow_list_only_echoes_listed_origins() {
+        let layer = || api_cors(&["https://dash.example.com".to_string()]);
+        assert_eq!(
+            allowed_origin(layer(), "https://dash.example.com").await,
+            Some("https://dash.example.com".to_string())
+        );
+        assert_eq!(allowed_origin(layer(), "https://evil.example").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_public_cors_ignores_allow_list() {
+        assert_eq!(
+            allowed_origin(public_cors(), "https://board.battlesnake.com").await,
+            Some("*".to_string())
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! The JSON error envelope every API endpoint responds with:
+//!
+//! ```json
+//! { "error": { "code": "not_found", "message": "Game not found", "details": null } }
+//! ```
+//!
+//! `code` is stable and meant for programs; `message` is for people and may
+//! change. `details` carries structured context for the few errors that have
+//! any (the missing scope, the rate-limit window) and is null otherwise.
+//! `request_id` matches the `X-Request-Id` response header; quote it when
+//! reporting a problem.
+
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    details: Option<serde_json::Value>,
+    retry_after_secs: Option<i64>,
+}
+
+/// Error response body for every API endpoint
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiErrorBody {
+    pub error: ApiErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiErrorDetail {
+    /// Machine-readable error code, e.g. `not_found` or `missing_scope`
+    pub code: String,
+    /// Human-readable description
+    pub message: String,
+    /// Structured context, when the error has any
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
+    /// The request's id, as in the `X-Request-Id` response header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: None,
+            retry_after_secs: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized", message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "forbidden", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, "conflict", message)
+    }
+
+    /// 403 for a suspended account trying to create games or join leaderboards
+    pub fn account_suspended() -> Self {
+        Self::new(
+            StatusCode::FORBIDDEN,
+            "account_suspended",
+            "Your account is suspended",
+        )
+    }
+
+    /// 503 while an admin has the site in maintenance mode
+    pub fn maintenance() -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "maintenance",
+            "Arena is in maintenance mode; new games are paused",
+        )
+    }
+
+    /// 429 with a `Retry-After` header, echoed in `details.retry_after_secs`
+    pub fn rate_limited(message: impl Into<String>, retry_after_secs: i64) -> Self {
+        let mut error = Self::new(StatusCode::TOO_MANY_REQUESTS, "rate_limited", message)
+            .with_details(serde_json::json!({ "retry_after_secs": retry_after_secs }));
+        error.retry_after_secs = Some(retry_after_secs);
+        error
+    }
+
+    /// Log the underlying error and hide it from the client
+    pub fn internal(context: &str, e: impl std::fmt::Display) -> Self {
+        tracing::error!("{}: {}", context, e);
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "Internal server error",
+        )
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ApiErrorBody {
+            error: ApiErrorDetail {
+                code: self.code.to_string(),
+                message: self.message,
+                details: self.details,
+                request_id: crate::request_id::current(),
+            },
+        };
+        let mut response = (self.status, Json(body)).into_response();
+        if let Some(secs) = self.retry_after_secs {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_error_envelope_shape() {
+        let response = ApiError::forbidden("Token is missing the `admin` scope")
+            .with_details(serde_json::json!({ "scope": "admin" }))
+            .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "forbidden");
+        assert_eq!(
+            json["error"]["message"],
+            "Token is missing the `admin` scope"
+        );
+        assert_eq!(json["error"]["details"]["scope"], "admin");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_sets_retry_after() {
+        let response = ApiError::rate_limited("Slow down", 42).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "42");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "rate_limited");
+        assert_eq!(json["error"]["details"]["retry_after_secs"], 42);
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_hides_cause() {
+        let response = ApiError::internal("Failed to do the thing", "db exploded").into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "internal_error");
+        assert_eq!(json["error"]["message"], "Internal server error");
+        assert!(json["error"]["details"].is_null());
+    }
+}
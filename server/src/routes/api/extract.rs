@@ -0,0 +1,156 @@
+//! `Json`, `Query` and `Path` for API handlers. They extract exactly like
+//! axum's, but a rejection (malformed body, bad UUID in the path, unknown
+//! query value) answers with the [`ApiError`] envelope instead of axum's
+//! plain-text body.
+
+use axum::{
+    extract::{
+        FromRequest, FromRequestParts, Path, Query, Request,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{StatusCode, request::Parts},
+};
+use serde::de::DeserializeOwned;
+
+use crate::routes::api::error::ApiError;
+
+pub struct ApiJson<T>(pub T);
+
+pub struct ApiQuery<T>(pub T);
+
+pub struct ApiPath<T>(pub T);
+
+/// The envelope for a rejected request, keeping axum's status and message
+fn rejection(status: StatusCode, message: String) -> ApiError {
+    match status {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => {
+            ApiError::new(status, "unsupported_media_type", message)
+        }
+        StatusCode::PAYLOAD_TOO_LARGE => ApiError::new(status, "payload_too_large", message),
+        s if s.is_server_error() => ApiError::internal("Failed to extract request", message),
+        _ => ApiError::new(status, "bad_request", message),
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(e: JsonRejection) -> Self {
+        rejection(e.status(), e.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(e: QueryRejection) -> Self {
+        rejection(e.status(), e.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(e: PathRejection) -> Self {
+        rejection(e.status(), e.body_text())
+    }
+}
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::Json(value) = axum::Json::<T>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    async fn error_body(response: axum::response::Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_body_uses_envelope() {
+        use axum::response::IntoResponse as _;
+
+        let request = Request::post("/")
+            .header("content-type", "application/json")
+            .body(Body::from("{\"snakes\": ["))
+            .unwrap();
+        let Err(e) = ApiJson::<serde_json::Value>::from_request(request, &()).await else {
+            panic!("a truncated body should be rejected");
+        };
+        let response = e.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_body(response).await["error"]["code"], "bad_request");
+
+        let request = Request::post("/").body(Body::from("{}")).unwrap();
+        let Err(e) = ApiJson::<serde_json::Value>::from_request(request, &()).await else {
+            panic!("a body without a JSON content type should be rejected");
+        };
+        let response = e.into_response();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            error_body(response).await["error"]["code"],
+            "unsupported_media_type"
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_router_rejections_use_envelope(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let app = crate::routes::routes(crate::state::AppState::test_from_pool(pool));
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        // A bad UUID in the path
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/leaderboards/not-a-uuid/rankings"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = error_body(response).await;
+        assert_eq!(body["error"]["code"], "bad_request");
+        assert!(body["error"]["message"].as_str().unwrap().contains("UUID"));
+
+        // Unknown API paths, versioned or not, don't get the HTML 404
+        for uri in ["/api/v1/no-such-thing", "/api/no-such-thing"] {
+            let response = app.clone().oneshot(get(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            assert_eq!(error_body(response).await["error"]["code"], "not_found");
+        }
+    }
+}
@@ -1,25 +1,31 @@
-use axum::{
-    Json,
-    extract::{Path, Query, State},
-    http::{StatusCode, header},
-    response::IntoResponse,
-};
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
     jobs::GameRunnerJob,
+    metrics::TimedQuery as _,
     models::{
-        game::{self, CreateGameWithSnakes, Game, GameBoardSize, GameStatus, GameType},
+        game::{
+            self, CreateGameWithSnakes, Game, GameBoardSize, GameListFilter, GameStatus, GameType,
+        },
         game_battlesnake::{self, GameBattlesnakeWithDetails},
-        rate_limit, turn,
+        leaderboard, rate_limit, turn,
+    },
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath, ApiQuery},
+        },
+        auth::{ApiUser, GamesCreateScope, ScopedApiUser},
     },
-    routes::auth::ApiUser,
     state::AppState,
 };
 
 /// Request body for creating a game
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateGameRequest {
     /// Snake IDs to include in the game (1-4 required)
     pub snakes: Vec<Uuid>,
@@ -29,8 +35,13 @@ pub struct CreateGameRequest {
     /// Game type: "standard", "royale", "constrictor", or "snail" (default: "standard")
     #[serde(default = "default_game_type")]
     pub game_type: String,
+    /// Optional label for polling this batch via `GET /api/v1/games/status` (max 100 characters)
+    pub run_tag: Option<String>,
 }
 
+/// Longest `run_tag` accepted when creating a game
+const MAX_RUN_TAG_LEN: usize = 100;
+
 fn default_board() -> String {
     "11x11".to_string()
 }
@@ -61,14 +72,14 @@ fn parse_board_size(s: &str) -> Result<GameBoardSize, &'static str> {
 }
 
 /// Response for a created game (minimal)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CreateGameResponse {
     pub id: Uuid,
     pub status: String,
 }
 
 /// Snake info in game responses
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SnakeInfo {
     pub id: Uuid,
     pub name: String,
@@ -84,7 +95,7 @@ impl From<&GameBattlesnakeWithDetails> for SnakeInfo {
 }
 
 /// Response for game list items (without frames)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GameListItem {
     pub id: Uuid,
     pub status: String,
@@ -98,12 +109,14 @@ pub struct GameListItem {
 }
 
 /// Response for full game details (with frames)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GameResponse {
     pub id: Uuid,
     pub status: String,
     pub winner: Option<Uuid>,
     pub snakes: Vec<SnakeInfo>,
+    /// Engine-format frames, one per turn (same shape as `/api/games/{id}/frames`)
+    #[schema(value_type = Vec<Object>)]
     pub frames: Vec<serde_json::Value>,
     pub board: String,
     pub game_type: String,
@@ -111,9 +124,20 @@ pub struct GameResponse {
 }
 
 /// Query parameters for listing games
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListGamesQuery {
+    /// Only games this snake played in (must be yours or public)
     pub snake_id: Option<Uuid>,
+    /// Only games played for this leaderboard
+    pub leaderboard_id: Option<Uuid>,
+    /// Only games in this status: `waiting`, `running`, or `finished`
+    pub status: Option<String>,
+    /// With `snake_id` or `leaderboard_id`, how far back to look (RFC 3339).
+    /// Filtered queries default to 30 days ago; a `snake_id` alone looks
+    /// back indefinitely.
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Max games to return (default 20, capped at 100)
     #[serde(default = "default_limit")]
     pub limit: u32,
 }
@@ -122,8 +146,15 @@ fn default_limit() -> u32 {
     20
 }
 
+/// How far back a filtered snake or leaderboard query looks without
+/// `since`, the longest window the `/games` page offers
+const DEFAULT_FILTER_WINDOW_DAYS: i64 = 30;
+
 /// Build a GameListItem from game and battlesnakes
-fn build_game_list_item(game: &Game, battlesnakes: &[GameBattlesnakeWithDetails]) -> GameListItem {
+pub(crate) fn build_game_list_item(
+    game: &Game,
+    battlesnakes: &[GameBattlesnakeWithDetails],
+) -> GameListItem {
     let winner = battlesnakes
         .iter()
         .find(|b| b.placement == Some(1))
@@ -144,12 +175,34 @@ fn build_game_list_item(game: &Game, battlesnakes: &[GameBattlesnakeWithDetails]
     }
 }
 
-/// POST /api/games - Create a new game
+/// POST /api/v1/games - Create a new game
+#[utoipa::path(
+    post,
+    path = "/api/v1/games",
+    tag = "games",
+    request_body = CreateGameRequest,
+    responses(
+        (status = 201, description = "Game created and queued to run", body = CreateGameResponse),
+        (status = 400, description = "Invalid board, game type, or snake list", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Token is missing the `games:create` scope, or your account is suspended", body = ApiErrorBody),
+        (status = 429, description = "Game creation rate limit exceeded", body = ApiErrorBody),
+        (status = 503, description = "Arena is in maintenance mode", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_game(
     State(state): State<AppState>,
-    ApiUser(user): ApiUser,
-    Json(request): Json<CreateGameRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ScopedApiUser(user, _): ScopedApiUser<GamesCreateScope>,
+    ApiJson(request): ApiJson<CreateGameRequest>,
+) -> ApiResult<impl IntoResponse> {
+    if user.is_suspended() {
+        return Err(ApiError::account_suspended());
+    }
+    if state.maintenance().await.enabled {
+        return Err(ApiError::maintenance());
+    }
+
     // Rate limit game creation per account (shared with the web flow).
     // The attempt is recorded before the check so concurrent requests see
     // each other, and the returned count includes this attempt — reject
@@ -163,13 +216,7 @@ pub async fn create_game(
         window_minutes,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to record game creation attempt: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Internal server error".to_string(),
-        )
-    })?;
+    .map_err(|e| ApiError::internal("Failed to record game creation attempt", e))?;
     if attempts > limit {
         tracing::warn!(
             event_type = "game_creation_rate_limited",
@@ -180,36 +227,38 @@ pub async fn create_game(
             "game creation rate limited"
         );
         let window_seconds = i64::from(window_minutes) * 60;
-        return Ok((
-            StatusCode::TOO_MANY_REQUESTS,
-            [(header::RETRY_AFTER, window_seconds.to_string())],
+        return Err(ApiError::rate_limited(
             format!(
                 "Rate limit exceeded: max {limit} games per {window_minutes} minutes. Try again later."
             ),
-        )
-            .into_response());
+            window_seconds,
+        ));
     }
 
     // Parse board size
     let board_size =
-        parse_board_size(&request.board).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        parse_board_size(&request.board).map_err(|e| ApiError::bad_request(e.to_string()))?;
 
     // Parse game type
-    let game_type = parse_game_type(&request.game_type)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let game_type =
+        parse_game_type(&request.game_type).map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    if request
+        .run_tag
+        .as_ref()
+        .is_some_and(|tag| tag.is_empty() || tag.len() > MAX_RUN_TAG_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "run_tag must be 1-{MAX_RUN_TAG_LEN} characters"
+        )));
+    }
 
     // Validate snake count
     if request.snakes.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "At least one snake is required".to_string(),
-        ));
+        return Err(ApiError::bad_request("At least one snake is required"));
     }
     if request.snakes.len() > 4 {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "Maximum of 4 snakes allowed".to_string(),
-        ));
+        return Err(ApiError::bad_request("Maximum of 4 snakes allowed"));
     }
 
     // Get unique snake IDs to validate (duplicates are allowed but we only need to check each once)
@@ -234,22 +283,16 @@ pub async fn create_game(
     )
     .fetch_all(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to validate snakes: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Internal server error".to_string(),
-        )
-    })?;
+    .map_err(|e| ApiError::internal("Failed to validate snakes", e))?;
 
     // Check if all requested snakes were found and accessible
     let accessible_ids: Vec<Uuid> = accessible_snakes.iter().map(|r| r.battlesnake_id).collect();
     for snake_id in &unique_snake_ids {
         if !accessible_ids.contains(snake_id) {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                format!("Snake {} not found or not accessible", snake_id),
-            ));
+            return Err(ApiError::bad_request(format!(
+                "Snake {} not found or not accessible",
+                snake_id
+            )));
         }
     }
 
@@ -260,28 +303,17 @@ pub async fn create_game(
         board_size,
         game_type,
         battlesnake_ids: request.snakes,
+        run_tag: request.run_tag,
     };
 
     let game = game::create_game_with_snakes(&state.db, create_request)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create game: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to create game".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to create game", e))?;
 
     // Set enqueued_at timestamp before enqueueing the job
     game::set_game_enqueued_at(&state.db, game.game_id, chrono::Utc::now())
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to set enqueued_at: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to prepare game".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to set enqueued_at", e))?;
 
     // Enqueue the game runner job
     let job = GameRunnerJob {
@@ -294,23 +326,15 @@ pub async fn create_game(
         None,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to enqueue game runner job: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to start game".to_string(),
-        )
-    })?;
-
-    tracing::info!(
-        event_type = "game_created",
-        game_id = %game.game_id,
-        board_size = game.board_size.as_str(),
-        game_type = game.game_type.as_str(),
-        snake_count = snake_count,
-        user_id = %user.user_id,
-        source = "api",
-        "game created via API"
+    .map_err(|e| ApiError::internal("Failed to enqueue game runner job", e))?;
+
+    crate::events::game_created(
+        game.game_id,
+        &game.board_size,
+        &game.game_type,
+        snake_count,
+        crate::events::GameSource::Api,
+        Some(user.user_id),
     );
 
     Ok((
@@ -323,12 +347,24 @@ pub async fn create_game(
         .into_response())
 }
 
-/// GET /api/games - List games
+/// GET /api/v1/games - List games
+#[utoipa::path(
+    get,
+    path = "/api/v1/games",
+    tag = "games",
+    params(ListGamesQuery),
+    responses(
+        (status = 200, description = "Most recent games first", body = Vec<GameListItem>),
+        (status = 400, description = "Snake not found or not accessible, leaderboard not found, or unknown status", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn list_games(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Query(query): Query<ListGamesQuery>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ApiQuery(query): ApiQuery<ListGamesQuery>,
+) -> ApiResult<impl IntoResponse> {
     let limit = query.limit.min(100) as i64;
 
     // If filtering by snake_id, validate access first
@@ -345,25 +381,35 @@ pub async fn list_games(
         )
         .fetch_optional(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to validate snake: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to validate snake", e))?;
 
         if accessible.is_none() {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "Snake not found or not accessible".to_string(),
-            ));
+            return Err(ApiError::bad_request("Snake not found or not accessible"));
+        }
+    }
+
+    if let Some(leaderboard_id) = query.leaderboard_id {
+        let leaderboard = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to validate leaderboard", e))?;
+        if leaderboard.is_none() {
+            return Err(ApiError::bad_request("Leaderboard not found"));
         }
     }
 
-    // Build query based on whether we're filtering by snake
-    let games: Vec<Game> = if let Some(snake_id) = query.snake_id {
-        // Filter by specific snake
+    let status = query
+        .status
+        .as_deref()
+        .map(GameStatus::from_str)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("Status must be waiting, running, or finished"))?;
+
+    let filtered = query.leaderboard_id.is_some() || status.is_some() || query.since.is_some();
+    let games: Vec<Game> = if let Some(snake_id) = query.snake_id
+        && !filtered
+    {
+        // A bare snake_id keeps its original meaning: the snake's most
+        // recent games, however far back they go
         let rows = sqlx::query!(
             r#"
             SELECT DISTINCT g.game_id, g.board_size, g.game_type, g.status, g.enqueued_at, g.created_at, g.updated_at
@@ -378,10 +424,7 @@ pub async fn list_games(
         )
         .fetch_all(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list games: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
-        })?;
+        .map_err(|e| ApiError::internal("Failed to list games", e))?;
 
         rows.into_iter()
             .filter_map(|row| {
@@ -399,44 +442,25 @@ pub async fn list_games(
                 })
             })
             .collect()
+    } else if query.snake_id.is_some() || query.leaderboard_id.is_some() {
+        // Filtered snake and leaderboard games may be anyone's, so they're
+        // read the way the /games page reads them: newest first within a window
+        let filter = GameListFilter {
+            since: query.since.unwrap_or_else(|| {
+                chrono::Utc::now() - chrono::Duration::days(DEFAULT_FILTER_WINDOW_DAYS)
+            }),
+            status,
+            battlesnake_id: query.snake_id,
+            leaderboard_id: query.leaderboard_id,
+        };
+        game::list_games_page(&state.db, &filter, None, limit)
+            .await
+            .map_err(|e| ApiError::internal("Failed to list games", e))?
     } else {
         // List games where user has a snake participating
-        let rows = sqlx::query!(
-            r#"
-            SELECT DISTINCT g.game_id, g.board_size, g.game_type, g.status, g.enqueued_at, g.created_at, g.updated_at
-            FROM games g
-            JOIN game_battlesnakes gb ON g.game_id = gb.game_id
-            JOIN battlesnakes b ON gb.battlesnake_id = b.battlesnake_id
-            WHERE b.user_id = $1
-            ORDER BY g.created_at DESC
-            LIMIT $2
-            "#,
-            user.user_id,
-            limit
-        )
-        .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to list games: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
-        })?;
-
-        rows.into_iter()
-            .filter_map(|row| {
-                let board_size = GameBoardSize::from_str(&row.board_size).ok()?;
-                let game_type = GameType::from_str(&row.game_type).ok()?;
-                let status = GameStatus::from_str(&row.status).ok()?;
-                Some(Game {
-                    game_id: row.game_id,
-                    board_size,
-                    game_type,
-                    status,
-                    enqueued_at: row.enqueued_at,
-                    created_at: row.created_at,
-                    updated_at: row.updated_at,
-                })
-            })
-            .collect()
+        game_battlesnake::get_recent_games_for_user(&state.db, user.user_id, status, limit)
+            .await
+            .map_err(|e| ApiError::internal("Failed to list games", e))?
     };
 
     // Fetch battlesnakes for each game
@@ -445,14 +469,9 @@ pub async fn list_games(
         let battlesnakes = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game.game_id)
             .await
             .map_err(|e| {
-                tracing::error!(
-                    "Failed to get battlesnakes for game {}: {}",
-                    game.game_id,
-                    e
-                );
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal server error".to_string(),
+                ApiError::internal(
+                    &format!("Failed to get battlesnakes for game {}", game.game_id),
+                    e,
                 )
             })?;
         response.push(build_game_list_item(game, &battlesnakes));
@@ -461,48 +480,50 @@ pub async fn list_games(
     Ok(Json(response))
 }
 
-/// GET /api/games/{id}/details - Show game details with frames
+/// GET /api/v1/games/{id}/details - Show game details with frames
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/{id}/details",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Game with every stored frame", body = GameResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "Game not found", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn show_game(
     State(state): State<AppState>,
     ApiUser(_user): ApiUser,
-    Path(game_id): Path<Uuid>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ApiPath(game_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
     // Fetch the game
     let game = game::get_game_by_id(&state.db, game_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get game: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Game not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to get game", e))?
+        .ok_or(ApiError::not_found("Game not found"))?;
 
     // Fetch battlesnakes
     let battlesnakes = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get battlesnakes: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to get battlesnakes", e))?;
 
     // Fetch all turns
-    let turns = turn::get_turns_by_game_id(&state.db, game_id)
+    let turns = crate::frame_archive::load_turns(&state, game_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get turns: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to get turns", e))?;
 
     // Extract frames from turns
-    let frames: Vec<serde_json::Value> = turns.into_iter().filter_map(|t| t.frame_data).collect();
+    let mut frames: Vec<serde_json::Value> = Vec::with_capacity(turns.len());
+    for turn in &turns {
+        if let Some(frame) = turn
+            .frame_json()
+            .map_err(|e| ApiError::internal("Failed to decode frames", e))?
+        {
+            frames.push(frame);
+        }
+    }
 
     // Find winner
     let winner = battlesnakes
@@ -524,32 +545,275 @@ pub async fn show_game(
     }))
 }
 
+/// How a snake died, taken from the final frame
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnakeDeath {
+    /// Engine elimination cause, e.g. "wall-collision" or "head-collision"
+    pub cause: String,
+    pub turn: i32,
+    /// The snake that eliminated this one, if another snake was involved
+    pub eliminated_by: Option<Uuid>,
+}
+
+/// Move latency aggregates for one snake
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct SnakeLatency {
+    pub moves: i64,
+    pub timeouts: i64,
+    pub avg_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub max_ms: Option<i32>,
+}
+
+/// One snake's outcome in a game
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnakeResult {
+    pub id: Uuid,
+    pub name: String,
+    pub owner: String,
+    /// 1 for the winner; null until the game finishes
+    pub placement: Option<i32>,
+    /// Null if the snake survived
+    pub death: Option<SnakeDeath>,
+    pub latency: SnakeLatency,
+    /// Leaderboard entry the snake played as, for leaderboard games
+    pub leaderboard_entry_id: Option<Uuid>,
+    /// Display score change from this game, once ratings have been applied
+    pub score_change: Option<f64>,
+}
+
+/// Leaderboard a game was played for
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GameResultLeaderboard {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Response for a game's result
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GameResultResponse {
+    pub id: Uuid,
+    pub status: String,
+    pub board: String,
+    pub game_type: String,
+    /// Number of the last turn played
+    pub turns: i32,
+    pub winner: Option<Uuid>,
+    /// Snakes ordered by placement
+    pub snakes: Vec<SnakeResult>,
+    pub leaderboard: Option<GameResultLeaderboard>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub enqueued_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Collect each dead snake's death from a board-viewer frame, keyed by frame
+/// snake ID (the game_battlesnake_id). `eliminated_by` is left as the raw
+/// frame ID for the caller to resolve.
+pub(crate) fn deaths_from_frame(
+    frame: &serde_json::Value,
+) -> HashMap<String, (String, i32, String)> {
+    let Some(snakes) = frame["Snakes"].as_array() else {
+        return HashMap::new();
+    };
+
+    snakes
+        .iter()
+        .filter_map(|snake| {
+            let id = snake["ID"].as_str()?;
+            let death = snake.get("Death").filter(|d| !d.is_null())?;
+            Some((
+                id.to_string(),
+                (
+                    death["Cause"].as_str().unwrap_or_default().to_string(),
+                    death["Turn"].as_i64().unwrap_or_default() as i32,
+                    death["EliminatedBy"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// GET /api/v1/games/{id}/result - Placements, deaths, and latency for a game
+///
+/// `/api/games/{id}` itself is the engine-compatible endpoint the board viewer
+/// reads, so the structured result lives under its own path.
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/{id}/result",
+    tag = "games",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "The game's outcome", body = GameResultResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "Game not found", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn game_result(
+    State(state): State<AppState>,
+    ApiUser(_user): ApiUser,
+    ApiPath(game_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let internal_error = |what: &str, e: color_eyre::Report| {
+        ApiError::internal(&format!("Failed to get {what} for game {game_id}"), e)
+    };
+
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await
+        .map_err(|e| internal_error("game", e))?
+        .ok_or(ApiError::not_found("Game not found"))?;
+
+    let mut battlesnakes = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game_id)
+        .await
+        .map_err(|e| internal_error("battlesnakes", e))?;
+    battlesnakes.sort_by_key(|b| b.placement.unwrap_or(i32::MAX));
+
+    let last_frame = crate::frame_archive::load_last_frame(&state, game_id)
+        .await
+        .map_err(|e| internal_error("last turn", e))?;
+
+    let mut latencies: HashMap<Uuid, SnakeLatency> =
+        turn::get_latency_stats_by_game_id(&state.db, game_id)
+            .await
+            .map_err(|e| internal_error("latency stats", e))?
+            .into_iter()
+            .map(|s| {
+                (
+                    s.game_battlesnake_id,
+                    SnakeLatency {
+                        moves: s.moves,
+                        timeouts: s.timeouts,
+                        avg_ms: s.avg_latency_ms,
+                        p95_ms: s.p95_latency_ms,
+                        max_ms: s.max_latency_ms,
+                    },
+                )
+            })
+            .collect();
+
+    let (leaderboard, score_changes) =
+        match leaderboard::find_leaderboard_game_by_game_id(&state.db, game_id)
+            .await
+            .map_err(|e| internal_error("leaderboard game", e))?
+        {
+            Some(lg) => {
+                let board = leaderboard::get_leaderboard_by_id(&state.db, lg.leaderboard_id)
+                    .await
+                    .map_err(|e| internal_error("leaderboard", e))?;
+                let changes: HashMap<Uuid, f64> = leaderboard::get_results_for_leaderboard_game(
+                    &state.db,
+                    lg.leaderboard_game_id,
+                )
+                .await
+                .map_err(|e| internal_error("leaderboard results", e))?
+                .into_iter()
+                .map(|r| (r.leaderboard_entry_id, r.display_score_change))
+                .collect();
+                (
+                    board.map(|b| GameResultLeaderboard {
+                        id: b.leaderboard_id,
+                        name: b.name,
+                    }),
+                    changes,
+                )
+            }
+            None => (None, HashMap::new()),
+        };
+
+    let deaths = last_frame
+        .as_ref()
+        .map(|(_, frame)| deaths_from_frame(frame))
+        .unwrap_or_default();
+    // Frame IDs are game_battlesnake_ids; callers only know battlesnake IDs
+    let snake_ids: HashMap<String, Uuid> = battlesnakes
+        .iter()
+        .map(|b| (b.game_battlesnake_id.to_string(), b.battlesnake_id))
+        .collect();
+
+    let snakes: Vec<SnakeResult> = battlesnakes
+        .iter()
+        .map(|b| SnakeResult {
+            id: b.battlesnake_id,
+            name: b.name.clone(),
+            owner: b.owner_login.clone(),
+            placement: b.placement,
+            death: deaths.get(&b.game_battlesnake_id.to_string()).map(
+                |(cause, turn, eliminated_by)| SnakeDeath {
+                    cause: cause.clone(),
+                    turn: *turn,
+                    eliminated_by: snake_ids.get(eliminated_by).copied(),
+                },
+            ),
+            latency: latencies.remove(&b.game_battlesnake_id).unwrap_or_default(),
+            leaderboard_entry_id: b.leaderboard_entry_id,
+            score_change: b
+                .leaderboard_entry_id
+                .and_then(|id| score_changes.get(&id).copied()),
+        })
+        .collect();
+
+    let winner = battlesnakes
+        .iter()
+        .find(|b| b.placement == Some(1))
+        .map(|b| b.battlesnake_id);
+
+    Ok(Json(GameResultResponse {
+        id: game.game_id,
+        status: game.status.as_str().to_string(),
+        board: game.board_size.as_str().to_string(),
+        game_type: game.game_type.as_str().to_string(),
+        turns: last_frame.map(|(turn_number, _)| turn_number).unwrap_or(0),
+        winner,
+        snakes,
+        leaderboard,
+        created_at: game.created_at,
+        enqueued_at: game.enqueued_at,
+        updated_at: game.updated_at,
+    }))
+}
+
 /// Request body for batch game status lookup
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BatchGameStatusRequest {
     pub game_ids: Vec<Uuid>,
 }
 
 /// Response item for batch game status
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GameStatusItem {
     pub id: Uuid,
     pub status: String,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub enqueued_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub run_tag: Option<String>,
 }
 
-/// POST /api/games/status - Batch lookup game statuses
+/// POST /api/v1/games/status - Batch lookup game statuses
+#[utoipa::path(
+    post,
+    path = "/api/v1/games/status",
+    tag = "games",
+    request_body = BatchGameStatusRequest,
+    responses(
+        (status = 200, description = "Statuses for the requested games your snakes played in", body = Vec<GameStatusItem>),
+        (status = 400, description = "More than 500 game IDs", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn batch_game_status(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Json(request): Json<BatchGameStatusRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ApiJson(request): ApiJson<BatchGameStatusRequest>,
+) -> ApiResult<impl IntoResponse> {
     if request.game_ids.len() > 500 {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "Maximum of 500 game IDs allowed per request".to_string(),
+        return Err(ApiError::bad_request(
+            "Maximum of 500 game IDs allowed per request",
         ));
     }
 
@@ -559,7 +823,7 @@ pub async fn batch_game_status(
 
     let rows = sqlx::query!(
         r#"
-        SELECT g.game_id, g.status, g.updated_at, g.enqueued_at, g.created_at
+        SELECT g.game_id, g.status, g.updated_at, g.enqueued_at, g.created_at, g.run_tag
         FROM games g
         WHERE g.game_id = ANY($1)
           AND g.game_id IN (
@@ -572,14 +836,9 @@ pub async fn batch_game_status(
         user.user_id
     )
     .fetch_all(&state.db)
+    .timed("games::batch_status")
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch game statuses: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Internal server error".to_string(),
-        )
-    })?;
+    .map_err(|e| ApiError::internal("Failed to fetch game statuses", e))?;
 
     let items: Vec<GameStatusItem> = rows
         .into_iter()
@@ -589,6 +848,106 @@ pub async fn batch_game_status(
             updated_at: row.updated_at,
             enqueued_at: row.enqueued_at,
             created_at: row.created_at,
+            run_tag: row.run_tag,
+        })
+        .collect();
+
+    Ok(Json(items))
+}
+
+/// Query parameters for polling changed games
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangedGamesQuery {
+    /// Return games updated at or after this time (RFC 3339)
+    pub since: chrono::DateTime<chrono::Utc>,
+    /// Only games created with this `run_tag`
+    pub run_tag: Option<String>,
+    /// Resume after this game: pass the last `id` of a full page, with its
+    /// `updated_at` as `since`
+    pub after_id: Option<Uuid>,
+    /// Max games to return (default and max 500)
+    pub limit: Option<i64>,
+}
+
+/// Default and maximum page size for `GET /api/v1/games/status`, matching the
+/// POST endpoint's batch cap
+const MAX_CHANGED_GAMES_LIMIT: i64 = 500;
+
+/// How old a change must be before `GET /api/v1/games/status` returns it.
+/// `updated_at` is stamped when a transaction starts, so a change can commit
+/// after later ones have already been polled; holding back recent changes
+/// keeps it from landing behind a caller's cursor.
+const CHANGED_GAMES_SETTLE_SECS: f64 = 2.0;
+
+/// GET /api/v1/games/status?since=&run_tag= - Games that changed since a timestamp
+///
+/// Results are ordered by `(updated_at, id)`. A page shorter than `limit`
+/// means the caller is caught up; otherwise request the next page with the
+/// last item's `updated_at` and `id`. Changes from the last couple of
+/// seconds are left for the next poll, so one committed late can't slip
+/// behind the cursor; a write held open longer than that still can.
+#[utoipa::path(
+    get,
+    path = "/api/v1/games/status",
+    tag = "games",
+    params(ChangedGamesQuery),
+    responses(
+        (status = 200, description = "Your snakes' games updated since `since`, oldest change first", body = Vec<GameStatusItem>),
+        (status = 400, description = "Missing or invalid `since`", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn changed_game_status(
+    State(state): State<AppState>,
+    ApiUser(user): ApiUser,
+    ApiQuery(query): ApiQuery<ChangedGamesQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let limit = query
+        .limit
+        .unwrap_or(MAX_CHANGED_GAMES_LIMIT)
+        .clamp(1, MAX_CHANGED_GAMES_LIMIT);
+    // Without a cursor id, the nil UUID makes the row comparison inclusive
+    // of games updated exactly at `since`
+    let after_id = query.after_id.unwrap_or_else(Uuid::nil);
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT g.game_id, g.status, g.updated_at, g.enqueued_at, g.created_at, g.run_tag
+        FROM games g
+        WHERE (g.updated_at, g.game_id) > ($1, $2)
+          AND g.updated_at < NOW() - make_interval(secs => $6)
+          AND ($3::text IS NULL OR g.run_tag = $3)
+          AND g.game_id IN (
+            SELECT gb.game_id FROM game_battlesnakes gb
+            JOIN battlesnakes b ON gb.battlesnake_id = b.battlesnake_id
+            WHERE b.user_id = $4
+          )
+        ORDER BY g.updated_at ASC, g.game_id ASC
+        LIMIT $5
+        "#,
+        query.since,
+        after_id,
+        query.run_tag,
+        user.user_id,
+        limit,
+        CHANGED_GAMES_SETTLE_SECS
+    )
+    .fetch_all(&state.db)
+    .timed("games::changed_status")
+    .await
+    .map_err(|e| ApiError::internal("Failed to fetch changed games", e))?;
+
+    let items: Vec<GameStatusItem> = rows
+        .into_iter()
+        .map(|row| GameStatusItem {
+            id: row.game_id,
+            status: row.status,
+            updated_at: row.updated_at,
+            enqueued_at: row.enqueued_at,
+            created_at: row.created_at,
+            run_tag: row.run_tag,
         })
         .collect();
 
@@ -601,6 +960,7 @@ use std::str::FromStr;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::extract::Query;
 
     #[test]
     fn test_parse_game_type() {
@@ -670,6 +1030,188 @@ mod tests {
         let request: CreateGameRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.board, "11x11");
         assert_eq!(request.game_type, "standard");
+        assert!(request.run_tag.is_none());
+    }
+
+    async fn fixture_user_game(
+        pool: &sqlx::PgPool,
+        run_tag: Option<&str>,
+    ) -> cja::Result<(Uuid, Uuid)> {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'poller', 'test-token')
+             ON CONFLICT (external_github_id) DO UPDATE SET github_login = EXCLUDED.github_login
+             RETURNING user_id",
+        )
+        .fetch_one(pool)
+        .await?;
+        let snake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, $2, 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .bind(Uuid::new_v4().to_string())
+        .fetch_one(pool)
+        .await?;
+        // Changed well before any poll, and all at the same moment
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status, run_tag, updated_at)
+             VALUES ('11x11', 'Standard', 'waiting', $1, '2026-01-01T00:00:00Z')
+             RETURNING game_id",
+        )
+        .bind(run_tag)
+        .fetch_one(pool)
+        .await?;
+        sqlx::query("INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2)")
+            .bind(game_id)
+            .bind(snake_id)
+            .execute(pool)
+            .await?;
+        Ok((user_id, game_id))
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn create_game_is_refused_during_maintenance(pool: sqlx::PgPool) -> cja::Result<()> {
+        let (user_id, _) = fixture_user_game(&pool, None).await?;
+        crate::models::maintenance::set(&pool, true, "", user_id).await?;
+        let user = crate::models::user::get_user_by_id(&pool, user_id)
+            .await?
+            .unwrap();
+
+        let err = create_game(
+            State(AppState::test_from_pool(pool.clone())),
+            ScopedApiUser(user, std::marker::PhantomData),
+            ApiJson(CreateGameRequest {
+                snakes: vec![],
+                board: default_board(),
+                game_type: default_game_type(),
+                run_tag: None,
+            }),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(err.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+        Ok(())
+    }
+
+    async fn changed_ids(pool: &sqlx::PgPool, user_id: Uuid, uri: &str) -> cja::Result<Vec<Uuid>> {
+        let user = crate::models::user::get_user_by_id(pool, user_id)
+            .await?
+            .unwrap();
+        let uri: axum::http::Uri = uri.parse().unwrap();
+        let response = changed_game_status(
+            State(AppState::test_from_pool(pool.clone())),
+            ApiUser(user),
+            ApiQuery(Query::try_from_uri(&uri).unwrap().0),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&bytes).unwrap();
+        Ok(items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap().parse().unwrap())
+            .collect())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn changed_game_status_filters_by_time_and_run_tag(
+        pool: sqlx::PgPool,
+    ) -> cja::Result<()> {
+        let (user_id, tagged) = fixture_user_game(&pool, Some("run-a")).await?;
+        let (_, untagged) = fixture_user_game(&pool, None).await?;
+
+        let all = changed_ids(
+            &pool,
+            user_id,
+            "/api/games/status?since=2000-01-01T00:00:00Z",
+        )
+        .await?;
+        assert_eq!(all.len(), 2);
+        assert!(all.contains(&tagged) && all.contains(&untagged));
+
+        let run_a = changed_ids(
+            &pool,
+            user_id,
+            "/api/games/status?since=2000-01-01T00:00:00Z&run_tag=run-a",
+        )
+        .await?;
+        assert_eq!(run_a, vec![tagged]);
+
+        let future = changed_ids(
+            &pool,
+            user_id,
+            "/api/games/status?since=2100-01-01T00:00:00Z",
+        )
+        .await?;
+        assert!(future.is_empty());
+
+        // A change that may not have settled waits for a later poll
+        sqlx::query("UPDATE games SET status = 'running' WHERE game_id = $1")
+            .bind(tagged)
+            .execute(&pool)
+            .await?;
+        let settled = changed_ids(
+            &pool,
+            user_id,
+            "/api/games/status?since=2000-01-01T00:00:00Z",
+        )
+        .await?;
+        assert_eq!(settled, vec![untagged]);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn changed_game_status_pages_with_after_id(pool: sqlx::PgPool) -> cja::Result<()> {
+        let (user_id, _) = fixture_user_game(&pool, Some("run-b")).await?;
+        fixture_user_game(&pool, Some("run-b")).await?;
+        fixture_user_game(&pool, Some("run-b")).await?;
+        // Same updated_at for all three, so paging has to fall back to the id
+        let updated_at: chrono::DateTime<chrono::Utc> =
+            sqlx::query_scalar("SELECT MAX(updated_at) FROM games")
+                .fetch_one(&pool)
+                .await?;
+        let since = updated_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+
+        let first = changed_ids(
+            &pool,
+            user_id,
+            &format!("/api/games/status?since={since}&limit=2"),
+        )
+        .await?;
+        assert_eq!(first.len(), 2);
+
+        let rest = changed_ids(
+            &pool,
+            user_id,
+            &format!(
+                "/api/games/status?since={since}&limit=2&after_id={}",
+                first[1]
+            ),
+        )
+        .await?;
+        assert_eq!(rest.len(), 1);
+        assert!(!first.contains(&rest[0]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_changed_games_query_parses_since() {
+        let uri: axum::http::Uri = "/api/games/status?since=2026-01-01T00:00:00Z&run_tag=stress-1"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<ChangedGamesQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.run_tag.as_deref(), Some("stress-1"));
+        assert_eq!(query.since.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let uri: axum::http::Uri = "/api/games/status?run_tag=stress-1".parse().unwrap();
+        assert!(Query::<ChangedGamesQuery>::try_from_uri(&uri).is_err());
     }
 
     #[test]
@@ -685,6 +1227,32 @@ mod tests {
         assert!(!json.contains("\"url\":"));
     }
 
+    #[test]
+    fn test_deaths_from_frame() {
+        let frame = serde_json::json!({
+            "Turn": 12,
+            "Snakes": [
+                {"ID": "gb-1", "Health": 90, "Death": null},
+                {"ID": "gb-2", "Health": 0, "Death": {"Cause": "head-collision", "Turn": 12, "EliminatedBy": "gb-1"}},
+                {"ID": "gb-3", "Health": 0, "Death": {"Cause": "wall-collision", "Turn": 4, "EliminatedBy": ""}}
+            ]
+        });
+
+        let deaths = deaths_from_frame(&frame);
+        assert_eq!(deaths.len(), 2);
+        assert!(!deaths.contains_key("gb-1"));
+        assert_eq!(
+            deaths["gb-2"],
+            ("head-collision".to_string(), 12, "gb-1".to_string())
+        );
+        assert_eq!(
+            deaths["gb-3"],
+            ("wall-collision".to_string(), 4, String::new())
+        );
+
+        assert!(deaths_from_frame(&serde_json::json!({})).is_empty());
+    }
+
     #[test]
     fn test_game_response_serialization() {
         let response = GameResponse {
@@ -0,0 +1,274 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        admin_audit::{self, AuditAction, AuditTarget},
+        job::{self, JobAction, JobRecord},
+    },
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath, ApiQuery},
+        },
+        auth::AdminApiUser,
+    },
+    state::AppState,
+};
+
+/// A job that has failed at least once
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FailingJobResponse {
+    pub id: Uuid,
+    pub name: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub error_count: i32,
+    pub last_error_message: Option<String>,
+    pub last_failed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the next attempt is due
+    pub run_at: chrono::DateTime<chrono::Utc>,
+    /// Whether a worker is running it right now
+    pub locked: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<JobRecord> for FailingJobResponse {
+    fn from(job: JobRecord) -> Self {
+        Self {
+            id: job.job_id,
+            name: job.name,
+            payload: job.payload,
+            error_count: job.error_count,
+            last_error_message: job.last_error_message,
+            last_failed_at: job.last_failed_at,
+            run_at: job.run_at,
+            locked: job.locked_at.is_some(),
+            created_at: job.created_at,
+        }
+    }
+}
+
+/// Query parameters for listing failing jobs
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListJobsQuery {
+    /// Only jobs with this name, e.g. `GameRunnerJob`
+    pub name: Option<String>,
+    /// Max jobs to return (default 50, capped at 500)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Request body for retrying every failing job of a type
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RetryJobsRequest {
+    pub name: String,
+}
+
+/// Response for a bulk retry
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetryJobsResponse {
+    /// How many jobs were rescheduled to run now
+    pub retried: u64,
+}
+
+fn job_action_result(action: JobAction) -> ApiResult<StatusCode> {
+    match action {
+        JobAction::Done => Ok(StatusCode::NO_CONTENT),
+        JobAction::NotFound => Err(ApiError::not_found("Job not found")),
+        JobAction::Locked => Err(ApiError::conflict(
+            "Job is running right now; try again once it finishes",
+        )),
+    }
+}
+
+/// GET /api/v1/admin/jobs - List failing jobs, most recent failure first
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/jobs",
+    tag = "admin",
+    params(ListJobsQuery),
+    responses(
+        (status = 200, description = "Jobs with at least one failure", body = Vec<FailingJobResponse>),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_failing_jobs(
+    State(state): State<AppState>,
+    AdminApiUser(_user): AdminApiUser,
+    ApiQuery(query): ApiQuery<ListJobsQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let limit = query.limit.min(500) as i64;
+    let jobs = job::list_failing_jobs(&state.db, query.name.as_deref(), limit)
+        .await
+        .map_err(|e| ApiError::internal("Failed to list failing jobs", e))?;
+
+    Ok(Json(
+        jobs.into_iter()
+            .map(FailingJobResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// POST /api/v1/admin/jobs/{id}/retry - Run a job now with a fresh retry budget
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/{id}/retry",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 204, description = "Job rescheduled to run now"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+        (status = 404, description = "Job not found", body = ApiErrorBody),
+        (status = 409, description = "Job is running right now", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn retry_job(
+    State(state): State<AppState>,
+    AdminApiUser(user): AdminApiUser,
+    ApiPath(job_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let action = job::retry_job(&state.db, job_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to retry job", e))?;
+    if action == JobAction::Done {
+        tracing::info!(job_id = %job_id, admin = %user.github_login, "admin retried job");
+        admin_audit::record(
+            &state.db,
+            &user,
+            AuditAction::JobRetried,
+            AuditTarget::Job(job_id),
+            None,
+            None,
+        )
+        .await
+        .map_err(|e| ApiError::internal("Failed to record audit log", e))?;
+    }
+    job_action_result(action)
+}
+
+/// POST /api/v1/admin/jobs/retry - Retry every failing job with a given name
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/retry",
+    tag = "admin",
+    request_body = RetryJobsRequest,
+    responses(
+        (status = 200, description = "Failing jobs rescheduled; running ones are skipped", body = RetryJobsResponse),
+        (status = 400, description = "Missing job name", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn retry_jobs_by_name(
+    State(state): State<AppState>,
+    AdminApiUser(user): AdminApiUser,
+    ApiJson(request): ApiJson<RetryJobsRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::bad_request("Job name is required"));
+    }
+
+    let retried = job::retry_failing_jobs_by_name(&state.db, name)
+        .await
+        .map_err(|e| ApiError::internal("Failed to retry jobs", e))?;
+    tracing::info!(job_name = %name, retried, admin = %user.github_login, "admin retried failing jobs");
+    admin_audit::record(
+        &state.db,
+        &user,
+        AuditAction::JobsRetried,
+        AuditTarget::JobName(name.to_string()),
+        None,
+        Some(serde_json::json!({ "retried": retried })),
+    )
+    .await
+    .map_err(|e| ApiError::internal("Failed to record audit log", e))?;
+
+    Ok(Json(RetryJobsResponse { retried }))
+}
+
+/// DELETE /api/v1/admin/jobs/{id} - Delete a poisoned job
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/jobs/{id}",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 204, description = "Job deleted"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Not an admin, or token is missing the `admin` scope", body = ApiErrorBody),
+        (status = 404, description = "Job not found", body = ApiErrorBody),
+        (status = 409, description = "Job is running right now", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_job(
+    State(state): State<AppState>,
+    AdminApiUser(user): AdminApiUser,
+    ApiPath(job_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let action = job::delete_job(&state.db, job_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to delete job", e))?;
+    if action == JobAction::Done {
+        tracing::info!(job_id = %job_id, admin = %user.github_login, "admin deleted job");
+        admin_audit::record(
+            &state.db,
+            &user,
+            AuditAction::JobDeleted,
+            AuditTarget::Job(job_id),
+            None,
+            None,
+        )
+        .await
+        .map_err(|e| ApiError::internal("Failed to record audit log", e))?;
+    }
+    job_action_result(action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+
+    #[test]
+    fn test_job_action_statuses() {
+        assert_eq!(
+            job_action_result(JobAction::Done).unwrap(),
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            job_action_result(JobAction::NotFound).unwrap_err().status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            job_action_result(JobAction::Locked).unwrap_err().status(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            job_action_result(JobAction::Locked).unwrap_err().code(),
+            "conflict"
+        );
+    }
+
+    #[test]
+    fn test_list_jobs_query_defaults() {
+        let uri: axum::http::Uri = "/api/v1/admin/jobs".parse().unwrap();
+        let Query(query) = Query::<ListJobsQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.limit, 50);
+        assert!(query.name.is_none());
+    }
+}
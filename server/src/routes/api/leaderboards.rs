@@ -2,31 +2,43 @@ use std::collections::HashMap;
 
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::State,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
     models::{
         battlesnake::{self, Visibility},
         leaderboard::{self, MIN_GAMES_FOR_RANKING},
+        snake_url_verification,
+    },
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath, ApiQuery},
+        },
+        auth::{LeaderboardsWriteScope, ScopedApiUser},
+        cursor,
     },
-    routes::auth::ApiUser,
     state::AppState,
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LeaderboardResponse {
     pub id: Uuid,
     pub name: String,
     pub active: bool,
+    /// Standings are frozen (end of season): no new games, ratings held
+    pub frozen: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RankingEntry {
     pub rank: usize,
     pub battlesnake_id: Uuid,
@@ -37,10 +49,11 @@ pub struct RankingEntry {
     pub first_place_finishes: i32,
     pub non_first_finishes: i32,
     pub first_place_rate: f64,
+    /// Score per scoring algorithm, keyed by algorithm key
     pub scores: HashMap<String, f64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RankingsResponse {
     pub leaderboard_id: Uuid,
     pub leaderboard_name: String,
@@ -49,18 +62,20 @@ pub struct RankingsResponse {
     pub placement: Vec<RankingEntry>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct OptInRequest {
     pub battlesnake_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RankingsQuery {
+    /// Ordering for ranked entries (default: rating)
     #[serde(default)]
     pub sort: leaderboard::LeaderboardSort,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct EntryResponse {
     pub leaderboard_entry_id: Uuid,
     pub battlesnake_id: Uuid,
@@ -71,26 +86,72 @@ pub struct EntryResponse {
     pub active: bool,
 }
 
-/// GET /api/leaderboards
-pub async fn list_leaderboards(
-    State(state): State<AppState>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let leaderboards = leaderboard::get_all_leaderboards(&state.db)
+/// Query parameters for an entry's game history
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EntryGamesQuery {
+    /// `next_cursor` from the previous page; omit for the newest games
+    pub cursor: Option<String>,
+    /// Max games to return (default 50, capped at 200)
+    #[serde(default = "default_entry_games_limit")]
+    pub limit: u32,
+}
+
+fn default_entry_games_limit() -> u32 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntryGameOpponent {
+    pub snake_name: String,
+    pub placement: Option<i32>,
+}
+
+/// One rated game from an entry's history
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntryGameResponse {
+    pub game_id: Uuid,
+    pub placement: i32,
+    pub display_score_change: f64,
+    pub mu_before: f64,
+    pub mu_after: f64,
+    pub sigma_before: f64,
+    pub sigma_after: f64,
+    pub food_eaten: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub opponents: Vec<EntryGameOpponent>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntryGamesResponse {
+    /// Newest first
+    pub games: Vec<EntryGameResponse>,
+    /// Pass as `cursor` to fetch the next page; null on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// GET /api/v1/leaderboards
+#[utoipa::path(
+    get,
+    path = "/api/v1/leaderboards",
+    tag = "leaderboards",
+    responses(
+        (status = 200, description = "All leaderboards, including inactive ones", body = Vec<LeaderboardResponse>),
+    )
+)]
+pub async fn list_leaderboards(State(state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let leaderboards = state
+        .leaderboards()
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list leaderboards: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to list leaderboards", e))?;
 
     let response: Vec<LeaderboardResponse> = leaderboards
-        .into_iter()
+        .iter()
         .map(|lb| LeaderboardResponse {
             id: lb.leaderboard_id,
-            name: lb.name,
+            name: lb.name.clone(),
             active: lb.disabled_at.is_none(),
+            frozen: lb.frozen_at.is_some(),
             created_at: lb.created_at,
         })
         .collect();
@@ -98,42 +159,93 @@ pub async fn list_leaderboards(
     Ok(Json(response))
 }
 
-/// GET /api/leaderboards/:id/rankings
+/// Weak ETag for a rankings response: the same leaderboard state and sort
+/// always serialize to the same rankings.
+fn rankings_etag(
+    version: &leaderboard::RankingsVersion,
+    sort: leaderboard::LeaderboardSort,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(
+        format!(
+            "{}|{}|{:?}|{}|{}",
+            version.leaderboard_updated_at.timestamp_micros(),
+            version.entry_count,
+            version.entries_updated_at.map(|t| t.timestamp_micros()),
+            sort.as_str(),
+            MIN_GAMES_FOR_RANKING,
+        )
+        .as_bytes(),
+    );
+    format!("W/\"{}\"", &hex::encode(hasher.finalize())[..32])
+}
+
+/// `If-None-Match` check using weak comparison, as RFC 9110 requires for GET
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == opaque(etag))
+}
+
+/// GET /api/v1/leaderboards/:id/rankings
+///
+/// Bots poll this aggressively, so it sends an ETag and answers a matching
+/// `If-None-Match` with a 304 before doing any of the ranking queries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/leaderboards/{id}/rankings",
+    tag = "leaderboards",
+    params(("id" = Uuid, Path, description = "Leaderboard ID"), RankingsQuery),
+    responses(
+        (status = 200, description = "Ranked entries plus entries still in placement", body = RankingsResponse),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 404, description = "Leaderboard not found", body = ApiErrorBody),
+    )
+)]
 pub async fn get_rankings(
     State(state): State<AppState>,
-    Path(leaderboard_id): Path<Uuid>,
-    Query(query): Query<RankingsQuery>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let lb = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+    ApiPath(leaderboard_id): ApiPath<Uuid>,
+    ApiQuery(query): ApiQuery<RankingsQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    // Rankings come from the read replica, version included, so an ETag
+    // never vouches for rankings newer than the ones it was sent with
+    let version = leaderboard::get_rankings_version(&state.read_db, leaderboard_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch leaderboard: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Leaderboard not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to fetch rankings version", e))?
+        .ok_or(ApiError::not_found("Leaderboard not found"))?;
+
+    let etag = rankings_etag(&version, query.sort);
+    let etag_value = HeaderValue::from_str(&etag)
+        .map_err(|e| ApiError::internal("Failed to build rankings ETag header", e))?;
+    let cache_headers = [
+        (header::ETAG, etag_value),
+        // Always revalidate: the ETag check is cheap, stale rankings aren't
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+    ];
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|inm| etag_matches(inm, &etag))
+    {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
 
-    let ranked = leaderboard::get_ranked_entries(&state.db, leaderboard_id, query.sort)
+    let lb = leaderboard::get_leaderboard_by_id(&state.read_db, leaderboard_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch ranked entries: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to fetch leaderboard", e))?
+        .ok_or(ApiError::not_found("Leaderboard not found"))?;
 
-    let placement = leaderboard::get_placement_entries(&state.db, leaderboard_id)
+    // Straight from the replica rather than the per-process standings cache,
+    // which can lag the version the ETag was computed from
+    let ranked = leaderboard::get_ranked_entries(&state.read_db, leaderboard_id, query.sort)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch placement entries: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to fetch ranked entries", e))?;
+
+    let placement = leaderboard::get_placement_entries(&state.read_db, leaderboard_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch placement entries", e))?;
 
     // Collect entry IDs from both ranked and placement entries for scoring lookups
     let entry_ids: Vec<Uuid> = ranked
@@ -145,13 +257,12 @@ pub async fn get_rankings(
     // Fetch per-algorithm scores for only the relevant entries
     let mut algo_maps: Vec<(String, HashMap<Uuid, f64>)> = vec![];
     for algo in state.scoring.algorithms() {
-        let scores = algo.get_scores(&state.db, &entry_ids).await.map_err(|e| {
-            tracing::error!("Failed to fetch {} scores: {}", algo.key(), e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        let scores = algo
+            .get_scores(&state.read_db, &entry_ids)
+            .await
+            .map_err(|e| {
+                ApiError::internal(&format!("Failed to fetch {} scores", algo.key()), e)
+            })?;
         let map: HashMap<Uuid, f64> = scores
             .into_iter()
             .map(|s| (s.leaderboard_entry_id, s.score))
@@ -160,12 +271,12 @@ pub async fn get_rankings(
     }
 
     fn to_ranking_entries(
-        entries: Vec<leaderboard::RankedEntry>,
+        entries: &[leaderboard::RankedEntry],
         start_rank: usize,
         algo_maps: &[(String, HashMap<Uuid, f64>)],
     ) -> Vec<RankingEntry> {
         entries
-            .into_iter()
+            .iter()
             .enumerate()
             .map(|(i, e)| {
                 let first_place_rate = if e.games_played > 0 {
@@ -182,8 +293,8 @@ pub async fn get_rankings(
                 RankingEntry {
                     rank: start_rank + i,
                     battlesnake_id: e.battlesnake_id,
-                    snake_name: e.snake_name,
-                    owner: e.owner_login,
+                    snake_name: e.snake_name.clone(),
+                    owner: e.owner_login.clone(),
                     display_score: e.display_score,
                     games_played: e.games_played,
                     first_place_finishes: e.first_place_finishes,
@@ -195,92 +306,94 @@ pub async fn get_rankings(
             .collect()
     }
 
-    let ranked_entries = to_ranking_entries(ranked, 1, &algo_maps);
-    let placement_entries = to_ranking_entries(placement, 0, &algo_maps);
+    let ranked_entries = to_ranking_entries(&ranked, 1, &algo_maps);
+    let placement_entries = to_ranking_entries(&placement, 0, &algo_maps);
 
-    Ok(Json(RankingsResponse {
-        leaderboard_id: lb.leaderboard_id,
-        leaderboard_name: lb.name,
-        min_games: MIN_GAMES_FOR_RANKING,
-        ranked: ranked_entries,
-        placement: placement_entries,
-    }))
+    Ok((
+        cache_headers,
+        Json(RankingsResponse {
+            leaderboard_id: lb.leaderboard_id,
+            leaderboard_name: lb.name,
+            min_games: MIN_GAMES_FOR_RANKING,
+            ranked: ranked_entries,
+            placement: placement_entries,
+        }),
+    )
+        .into_response())
 }
 
-/// POST /api/leaderboards/:id/entries — opt-in a snake
+/// POST /api/v1/leaderboards/:id/entries — opt-in a snake
+#[utoipa::path(
+    post,
+    path = "/api/v1/leaderboards/{id}/entries",
+    tag = "leaderboards",
+    params(("id" = Uuid, Path, description = "Leaderboard ID")),
+    request_body = OptInRequest,
+    responses(
+        (status = 201, description = "Snake entered (or re-activated)", body = EntryResponse),
+        (status = 400, description = "Leaderboard inactive, or snake not public or its URL not verified", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "You don't own this battlesnake, your account is suspended, or token is missing the `leaderboards:write` scope", body = ApiErrorBody),
+        (status = 404, description = "Leaderboard or battlesnake not found", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_entry(
     State(state): State<AppState>,
-    ApiUser(user): ApiUser,
-    Path(leaderboard_id): Path<Uuid>,
-    Json(request): Json<OptInRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ScopedApiUser(user, _): ScopedApiUser<LeaderboardsWriteScope>,
+    ApiPath(leaderboard_id): ApiPath<Uuid>,
+    ApiJson(request): ApiJson<OptInRequest>,
+) -> ApiResult<impl IntoResponse> {
     // Verify leaderboard exists and is active
     let lb = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch leaderboard: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Leaderboard not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to fetch leaderboard", e))?
+        .ok_or(ApiError::not_found("Leaderboard not found"))?;
 
     if lb.disabled_at.is_some() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "Leaderboard is not active".to_string(),
-        ));
+        return Err(ApiError::bad_request("Leaderboard is not active"));
+    }
+
+    if user.is_suspended() {
+        return Err(ApiError::account_suspended());
     }
 
     // Verify snake belongs to user and is public
     let snake = battlesnake::get_battlesnake_by_id(&state.db, request.battlesnake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch battlesnake: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Battlesnake not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to fetch battlesnake", e))?
+        .ok_or(ApiError::not_found("Battlesnake not found"))?;
 
     if snake.user_id != user.user_id {
-        return Err((
-            StatusCode::FORBIDDEN,
-            "You don't own this battlesnake".to_string(),
-        ));
+        return Err(ApiError::forbidden("You don't own this battlesnake"));
     }
 
     if snake.visibility != Visibility::Public {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "Only public snakes can join leaderboards".to_string(),
+        return Err(ApiError::bad_request(
+            "Only public snakes can join leaderboards",
+        ));
+    }
+
+    let verified = snake_url_verification::is_verified(&state.db, snake.battlesnake_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to check snake URL verification", e))?;
+    if !verified {
+        return Err(ApiError::bad_request(
+            "Verify this snake's URL from its profile page before joining leaderboards",
         ));
     }
 
     let entry = leaderboard::get_or_create_entry(&state.db, leaderboard_id, request.battlesnake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create entry: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?;
+        .map_err(|e| ApiError::internal("Failed to create entry", e))?;
 
     // Initialize scoring algorithm entries
     for algo in state.scoring.algorithms() {
         algo.initialize_entry(&state.db, entry.leaderboard_entry_id)
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to initialize scoring: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal server error".to_string(),
-                )
-            })?;
+            .map_err(|e| ApiError::internal("Failed to initialize scoring", e))?;
     }
+    state.invalidate_leaderboard(leaderboard_id);
 
     Ok((
         StatusCode::CREATED,
@@ -296,44 +409,133 @@ pub async fn create_entry(
     ))
 }
 
-/// DELETE /api/leaderboards/:id/entries/:battlesnake_id — opt-out (pause)
+/// GET /api/v1/leaderboards/:id/entries/:entry_id/games
+///
+/// Pages with a cursor rather than an offset: popular entries have tens of
+/// thousands of games, and OFFSET has to walk past all of them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/leaderboards/{id}/entries/{entry_id}/games",
+    tag = "leaderboards",
+    params(
+        ("id" = Uuid, Path, description = "Leaderboard ID"),
+        ("entry_id" = Uuid, Path, description = "Leaderboard entry ID"),
+        EntryGamesQuery,
+    ),
+    responses(
+        (status = 200, description = "A page of the entry's rated games, newest first", body = EntryGamesResponse),
+        (status = 400, description = "Invalid cursor", body = ApiErrorBody),
+        (status = 404, description = "Entry not found in this leaderboard", body = ApiErrorBody),
+    )
+)]
+pub async fn list_entry_games(
+    State(state): State<AppState>,
+    ApiPath((leaderboard_id, entry_id)): ApiPath<(Uuid, Uuid)>,
+    ApiQuery(query): ApiQuery<EntryGamesQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let before = query
+        .cursor
+        .as_deref()
+        .map(|cursor| cursor::decode(cursor).ok_or(ApiError::bad_request("Invalid cursor")))
+        .transpose()?;
+
+    let entry = leaderboard::get_entry_by_id(&state.read_db, entry_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch entry", e))?
+        .filter(|entry| entry.leaderboard_id == leaderboard_id)
+        .ok_or(ApiError::not_found("Entry not found in this leaderboard"))?;
+
+    // Fetch one extra row to learn whether there is another page
+    let limit = query.limit.clamp(1, 200) as i64;
+    let mut history = leaderboard::get_game_history_for_entry_before(
+        &state.read_db,
+        entry.leaderboard_entry_id,
+        before,
+        limit + 1,
+    )
+    .await
+    .map_err(|e| ApiError::internal("Failed to fetch game history", e))?;
+
+    let next_cursor = if history.len() as i64 > limit {
+        history.truncate(limit as usize);
+        history
+            .last()
+            .map(|last| cursor::encode(last.game_created_at, last.leaderboard_game_id))
+    } else {
+        None
+    };
+
+    let game_ids: Vec<Uuid> = history.iter().map(|h| h.game_id).collect();
+    let mut opponents: HashMap<Uuid, Vec<EntryGameOpponent>> = HashMap::new();
+    for opponent in
+        leaderboard::get_opponents_for_games(&state.read_db, &game_ids, entry.leaderboard_entry_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to fetch opponents", e))?
+    {
+        opponents
+            .entry(opponent.game_id)
+            .or_default()
+            .push(EntryGameOpponent {
+                snake_name: opponent.snake_name,
+                placement: opponent.placement,
+            });
+    }
+
+    let games = history
+        .into_iter()
+        .map(|h| EntryGameResponse {
+            game_id: h.game_id,
+            placement: h.placement,
+            display_score_change: h.display_score_change,
+            mu_before: h.mu_before,
+            mu_after: h.mu_after,
+            sigma_before: h.sigma_before,
+            sigma_after: h.sigma_after,
+            food_eaten: h.food_eaten,
+            created_at: h.game_created_at,
+            opponents: opponents.remove(&h.game_id).unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(Json(EntryGamesResponse { games, next_cursor }))
+}
+
+/// DELETE /api/v1/leaderboards/:id/entries/:battlesnake_id — opt-out (pause)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/leaderboards/{id}/entries/{battlesnake_id}",
+    tag = "leaderboards",
+    params(
+        ("id" = Uuid, Path, description = "Leaderboard ID"),
+        ("battlesnake_id" = Uuid, Path, description = "Battlesnake ID"),
+    ),
+    responses(
+        (status = 204, description = "Entry paused"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "You don't own this battlesnake, or token is missing the `leaderboards:write` scope", body = ApiErrorBody),
+        (status = 404, description = "Battlesnake not found or not in this leaderboard", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn delete_entry(
     State(state): State<AppState>,
-    ApiUser(user): ApiUser,
-    Path((leaderboard_id, battlesnake_id)): Path<(Uuid, Uuid)>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ScopedApiUser(user, _): ScopedApiUser<LeaderboardsWriteScope>,
+    ApiPath((leaderboard_id, battlesnake_id)): ApiPath<(Uuid, Uuid)>,
+) -> ApiResult<impl IntoResponse> {
     // Verify snake belongs to user
     let snake = battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch battlesnake: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Battlesnake not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to fetch battlesnake", e))?
+        .ok_or(ApiError::not_found("Battlesnake not found"))?;
 
     if snake.user_id != user.user_id {
-        return Err((
-            StatusCode::FORBIDDEN,
-            "You don't own this battlesnake".to_string(),
-        ));
+        return Err(ApiError::forbidden("You don't own this battlesnake"));
     }
 
     let entry = leaderboard::get_entry(&state.db, leaderboard_id, battlesnake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch entry: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            )
-        })?
-        .ok_or((
-            StatusCode::NOT_FOUND,
-            "Snake is not in this leaderboard".to_string(),
-        ))?;
+        .map_err(|e| ApiError::internal("Failed to fetch entry", e))?
+        .ok_or(ApiError::not_found("Snake is not in this leaderboard"))?;
 
     leaderboard::set_disabled(
         &state.db,
@@ -341,13 +543,212 @@ pub async fn delete_entry(
         Some(chrono::Utc::now()),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to disable entry: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Internal server error".to_string(),
-        )
-    })?;
+    .map_err(|e| ApiError::internal("Failed to disable entry", e))?;
+    state.invalidate_leaderboard(leaderboard_id);
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_matches_uses_weak_comparison() {
+        let etag = r#"W/"abc""#;
+        assert!(etag_matches(r#"W/"abc""#, etag));
+        assert!(etag_matches(r#""abc""#, etag));
+        assert!(etag_matches(r#""zzz", W/"abc""#, etag));
+        assert!(etag_matches("*", etag));
+        assert!(!etag_matches(r#"W/"abd""#, etag));
+    }
+
+    async fn rankings(
+        pool: &sqlx::PgPool,
+        leaderboard_id: Uuid,
+        if_none_match: Option<&str>,
+    ) -> Response {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = if_none_match {
+            headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(etag).unwrap());
+        }
+        get_rankings(
+            State(AppState::test_from_pool(pool.clone())),
+            ApiPath(leaderboard_id),
+            ApiQuery(RankingsQuery {
+                sort: leaderboard::LeaderboardSort::Rating,
+            }),
+            headers,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_rankings_not_modified_until_entries_change(
+        pool: sqlx::PgPool,
+    ) -> cja::Result<()> {
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('ETag') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let response = rankings(&pool, leaderboard_id, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = rankings(&pool, leaderboard_id, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'etag', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let snake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'etag', 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        leaderboard::get_or_create_entry(&pool, leaderboard_id, snake_id).await?;
+
+        let response = rankings(&pool, leaderboard_id, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag.as_str());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_rankings_unknown_leaderboard_is_404(pool: sqlx::PgPool) {
+        let err = get_rankings(
+            State(AppState::test_from_pool(pool)),
+            ApiPath(Uuid::new_v4()),
+            ApiQuery(RankingsQuery {
+                sort: leaderboard::LeaderboardSort::Rating,
+            }),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    }
+
+    async fn entry_games_page(
+        pool: &sqlx::PgPool,
+        leaderboard_id: Uuid,
+        entry_id: Uuid,
+        cursor: Option<String>,
+    ) -> ApiResult<serde_json::Value> {
+        let response = list_entry_games(
+            State(AppState::test_from_pool(pool.clone())),
+            ApiPath((leaderboard_id, entry_id)),
+            ApiQuery(EntryGamesQuery { cursor, limit: 2 }),
+        )
+        .await?
+        .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        Ok(serde_json::from_slice(&body).unwrap())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_entry_games_pages_through_history_by_cursor(
+        pool: sqlx::PgPool,
+    ) -> cja::Result<()> {
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('History') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'history', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let snake_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO battlesnakes (user_id, name, url) VALUES ($1, 'history', 'http://example.com')
+             RETURNING battlesnake_id",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+        let entry = leaderboard::get_or_create_entry(&pool, leaderboard_id, snake_id).await?;
+
+        for _ in 0..5 {
+            let game_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO games (board_size, game_type) VALUES ('11x11', 'Standard') RETURNING game_id",
+            )
+            .fetch_one(&pool)
+            .await?;
+            let lg = leaderboard::create_leaderboard_game(&pool, leaderboard_id, game_id).await?;
+            leaderboard::create_game_result(
+                &pool,
+                leaderboard::CreateGameResult {
+                    leaderboard_game_id: lg.leaderboard_game_id,
+                    leaderboard_entry_id: entry.leaderboard_entry_id,
+                    placement: 1,
+                    mu_before: 25.0,
+                    mu_after: 26.0,
+                    sigma_before: 8.0,
+                    sigma_after: 7.5,
+                    display_score_change: 1.0,
+                },
+            )
+            .await?;
+        }
+        // Identical timestamps make the game ID tiebreak carry the cursor
+        sqlx::query("UPDATE leaderboard_games SET created_at = NOW() WHERE leaderboard_id = $1")
+            .bind(leaderboard_id)
+            .execute(&pool)
+            .await?;
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        let mut pages = 0;
+        loop {
+            let page = entry_games_page(&pool, leaderboard_id, entry.leaderboard_entry_id, cursor)
+                .await
+                .unwrap();
+            pages += 1;
+            for game in page["games"].as_array().unwrap() {
+                seen.push(game["game_id"].as_str().unwrap().to_string());
+            }
+            match page["next_cursor"].as_str() {
+                Some(next) => cursor = Some(next.to_string()),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(seen.len(), 5);
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 5);
+
+        let err = entry_games_page(&pool, Uuid::new_v4(), entry.leaderboard_entry_id, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        let err = entry_games_page(
+            &pool,
+            leaderboard_id,
+            entry.leaderboard_entry_id,
+            Some("garbage".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,14 @@
+pub mod admin_users;
+pub mod battlesnakes;
+pub mod cors;
+pub mod error;
+pub mod extract;
 pub mod games;
+pub mod jobs;
 pub mod leaderboards;
+pub mod openapi;
+pub mod rate_limit;
 pub mod snakes;
 pub mod tokens;
+pub mod users;
+pub mod versioning;
@@ -0,0 +1,186 @@
+use axum::{Json, response::IntoResponse};
+use maud::{DOCTYPE, Markup, PreEscaped, html};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::routes::{admin, api, game};
+
+/// Pinned so a new swagger-ui release can't silently change the docs page
+const SWAGGER_UI_VERSION: &str = "5.17.14";
+
+const SWAGGER_UI_INIT_JS: &str =
+    r##"window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });"##;
+
+/// OpenAPI document for the JSON API, built from the `#[utoipa::path]`
+/// annotations on the handlers themselves so it can't drift from the routes.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Battlesnake Arena API",
+        description = "Authenticate with `Authorization: Bearer <token>` using a token from `/api/v1/tokens`. Endpoints without a lock icon are public. The unversioned `/api/...` aliases of `/api/v1` still work but are deprecated and send `Deprecation` and `Link` headers. Errors are JSON: `{\"error\": {\"code\", \"message\", \"details\"}}`, where `code` is stable and `details` is null unless the error has structured context."
+    ),
+    paths(
+        game::api::get_game_info,
+        game::api::get_game_frames,
+        api::tokens::create_token,
+        api::tokens::list_tokens,
+        api::tokens::revoke_token,
+        api::tokens::rotate_token,
+        api::snakes::list_snakes,
+        api::snakes::create_snake,
+        api::snakes::get_snake,
+        api::snakes::update_snake,
+        api::snakes::delete_snake,
+        api::battlesnakes::register_battlesnake,
+        api::battlesnakes::patch_battlesnake,
+        api::battlesnakes::delete_battlesnake,
+        api::games::create_game,
+        api::games::list_games,
+        api::games::show_game,
+        api::games::game_result,
+        api::games::batch_game_status,
+        api::games::changed_game_status,
+        admin::stats_json,
+        admin::stats_history,
+        api::jobs::list_failing_jobs,
+        api::jobs::retry_job,
+        api::jobs::retry_jobs_by_name,
+        api::jobs::delete_job,
+        api::admin_users::search_users,
+        api::admin_users::show_user,
+        api::admin_users::update_user,
+        api::leaderboards::list_leaderboards,
+        api::leaderboards::get_rankings,
+        api::leaderboards::list_entry_games,
+        api::leaderboards::create_entry,
+        api::leaderboards::delete_entry,
+        api::users::get_me,
+    ),
+    components(schemas(crate::models::leaderboard::LeaderboardSort)),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "board viewer", description = "Engine-compatible endpoints used by the board viewer and GIF exporter"),
+        (name = "tokens", description = "API token management"),
+        (name = "snakes", description = "Manage your battlesnakes"),
+        (name = "games", description = "Create and inspect games"),
+        (name = "leaderboards", description = "Leaderboard rankings and entries"),
+        (name = "users", description = "The authenticated user"),
+        (name = "admin", description = "Admin-only endpoints"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `bearer` security scheme referenced by `security(("bearer" = []))`
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// GET /api/openapi.json
+pub async fn openapi_json() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /api/docs - Swagger UI for the spec above
+///
+/// A standalone page rather than a PageFactory one: swagger-ui brings its own
+/// styles and takes over the whole body.
+pub async fn swagger_ui() -> Markup {
+    let css = format!("https://unpkg.com/swagger-ui-dist@{SWAGGER_UI_VERSION}/swagger-ui.css");
+    let js = format!("https://unpkg.com/swagger-ui-dist@{SWAGGER_UI_VERSION}/swagger-ui-bundle.js");
+
+    html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                title { "Battlesnake Arena API" }
+                link rel="stylesheet" href=(css);
+            }
+            body {
+                div id="swagger-ui" {}
+                script src=(js) {}
+                script { (PreEscaped(SWAGGER_UI_INIT_JS)) }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_documents_api_routes() {
+        let spec = ApiDoc::openapi();
+        for path in [
+            "/api/v1/games",
+            "/api/games/{id}",
+            "/api/games/{id}/frames",
+            "/api/v1/games/{id}/details",
+            "/api/v1/games/{id}/result",
+            "/api/v1/games/status",
+            "/api/v1/snakes",
+            "/api/v1/snakes/{id}",
+            "/api/v1/battlesnakes",
+            "/api/v1/battlesnakes/{id}",
+            "/api/v1/tokens",
+            "/api/v1/tokens/{id}",
+            "/api/v1/tokens/{id}/rotate",
+            "/api/v1/leaderboards",
+            "/api/v1/leaderboards/{id}/rankings",
+            "/api/v1/leaderboards/{id}/entries",
+            "/api/v1/leaderboards/{id}/entries/{battlesnake_id}",
+            "/api/v1/leaderboards/{id}/entries/{entry_id}/games",
+            "/api/v1/admin/stats",
+            "/api/v1/admin/stats/history",
+            "/api/v1/admin/jobs",
+            "/api/v1/admin/jobs/retry",
+            "/api/v1/admin/jobs/{id}",
+            "/api/v1/admin/jobs/{id}/retry",
+            "/api/v1/admin/users",
+            "/api/v1/admin/users/{id}",
+            "/api/v1/users/me",
+        ] {
+            assert!(spec.paths.paths.contains_key(path), "missing {path}");
+        }
+    }
+
+    #[test]
+    fn test_spec_includes_response_schemas_and_bearer_auth() {
+        let json = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let components = &json["components"];
+
+        for schema in [
+            "SnakeResponse",
+            "GameListItem",
+            "RankingsResponse",
+            "CreateTokenResponse",
+            "GameFramesResponse",
+            "GameResultResponse",
+        ] {
+            assert!(
+                components["schemas"].get(schema).is_some(),
+                "missing schema {schema}"
+            );
+        }
+        assert_eq!(components["securitySchemes"]["bearer"]["scheme"], "bearer");
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_points_at_spec() {
+        let page = swagger_ui().await.into_string();
+        assert!(page.contains("/api/openapi.json"));
+        assert!(page.contains("swagger-ui-bundle.js"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Per-token / per-IP rate limiting for API writes.
+//!
+//! Every POST/PUT/PATCH/DELETE under `/api` is counted in a one-minute
+//! Postgres bucket (see [`rate_limit::hit_api_bucket`]) keyed by the Bearer
+//! token when it's a live token, and by client IP otherwise — so made-up
+//! tokens can't dodge the IP limit or mint a bucket row per request. Reads
+//! are never limited here. This sits in front of, not instead of, the
+//! per-account game-creation limit.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, Method, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    models::{api_token, rate_limit},
+    routes::api::error::ApiError,
+    state::AppState,
+};
+
+/// Which bucket a request is charged to, and that bucket's per-minute limit
+#[derive(Debug, PartialEq, Eq)]
+struct Bucket {
+    key: String,
+    limit: i64,
+}
+
+fn is_write(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// The client address as seen by our load balancer: the rightmost
+/// `X-Forwarded-For` entry. Earlier entries are client-supplied and can be
+/// spoofed to dodge the limit.
+fn client_ip(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-forwarded-for")?
+        .to_str()
+        .ok()?
+        .rsplit(',')
+        .map(str::trim)
+        .find(|ip| !ip.is_empty())
+        .map(str::to_string)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+}
+
+/// `live_token_hash` is the Bearer token's hash, once it's known to belong to
+/// a live token; anything else is charged to the client IP.
+fn bucket_for(
+    headers: &HeaderMap,
+    live_token_hash: Option<String>,
+    per_token: i64,
+    per_ip: i64,
+) -> Bucket {
+    match live_token_hash {
+        // Keyed by the token's hash, which is what identifies it in
+        // `api_tokens`
+        Some(token_hash) => Bucket {
+            key: format!("token:{token_hash}"),
+            limit: per_token,
+        },
+        None => Bucket {
+            key: format!(
+                "ip:{}",
+                client_ip(headers).unwrap_or_else(|| "unknown".to_string())
+            ),
+            limit: per_ip,
+        },
+    }
+}
+
+/// Middleware for the `/api` router. Fails open: if the bucket can't be
+/// updated the request goes through and the error is logged.
+pub async fn limit_api_writes(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_write(request.method()) {
+        return next.run(request).await;
+    }
+
+    let live_token_hash = match bearer_token(request.headers()) {
+        Some(secret) => {
+            let token_hash = api_token::hash_token(secret);
+            match api_token::is_active_token_hash(&state.db, &token_hash).await {
+                Ok(true) => Some(token_hash),
+                Ok(false) => None,
+                Err(e) => {
+                    tracing::error!("Failed to check API token for rate limiting: {:?}", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+    let bucket = bucket_for(
+        request.headers(),
+        live_token_hash,
+        state.config.api_rate_limit_per_token,
+        state.config.api_rate_limit_per_ip,
+    );
+
+    let hit = match rate_limit::hit_api_bucket(&state.db, &bucket.key).await {
+        Ok(hit) => hit,
+        Err(e) => {
+            tracing::error!("Failed to check API rate limit: {:?}", e);
+            return next.run(request).await;
+        }
+    };
+
+    let remaining = (bucket.limit - hit.count).max(0);
+    let mut response = if hit.count > bucket.limit {
+        tracing::warn!(
+            event_type = "api_rate_limited",
+            bucket = if bucket.key.starts_with("token:") { "token" } else { "ip" },
+            method = %request.method(),
+            path = %request.uri().path(),
+            count = hit.count,
+            limit = bucket.limit,
+            "API request rate limited"
+        );
+        ApiError::rate_limited(
+            format!(
+                "Rate limit exceeded: max {} write requests per minute. Try again later.",
+                bucket.limit
+            ),
+            hit.reset_secs,
+        )
+        .into_response()
+    } else {
+        next.run(request).await
+    };
+
+    let headers = response.headers_mut();
+    headers.insert("x-ratelimit-limit", HeaderValue::from(bucket.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(hit.reset_secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_only_writes_are_limited() {
+        assert!(is_write(&Method::POST));
+        assert!(is_write(&Method::PUT));
+        assert!(is_write(&Method::PATCH));
+        assert!(is_write(&Method::DELETE));
+        assert!(!is_write(&Method::GET));
+        assert!(!is_write(&Method::HEAD));
+        assert!(!is_write(&Method::OPTIONS));
+    }
+
+    #[test]
+    fn test_client_ip_uses_rightmost_forwarded_entry() {
+        let h = headers(&[("x-forwarded-for", "10.0.0.1, 203.0.113.9")]);
+        assert_eq!(client_ip(&h).as_deref(), Some("203.0.113.9"));
+
+        let h = headers(&[("x-forwarded-for", "198.51.100.4")]);
+        assert_eq!(client_ip(&h).as_deref(), Some("198.51.100.4"));
+
+        assert_eq!(client_ip(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_live_tokens_use_token_bucket() {
+        let h = headers(&[
+            ("authorization", "Bearer secret"),
+            ("x-forwarded-for", "203.0.113.9"),
+        ]);
+        assert_eq!(bearer_token(&h), Some("secret"));
+        let bucket = bucket_for(&h, Some(api_token::hash_token("secret")), 600, 120);
+        assert_eq!(
+            bucket,
+            Bucket {
+                key: format!("token:{}", api_token::hash_token("secret")),
+                limit: 600,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_tokens_use_ip_bucket() {
+        let h = headers(&[
+            ("authorization", "Bearer made-up"),
+            ("x-forwarded-for", "203.0.113.9"),
+        ]);
+        assert_eq!(
+            bucket_for(&h, None, 600, 120),
+            Bucket {
+                key: "ip:203.0.113.9".to_string(),
+                limit: 120,
+            }
+        );
+    }
+
+    #[test]
+    fn test_other_requests_use_ip_bucket() {
+        let h = headers(&[("x-forwarded-for", "203.0.113.9")]);
+        assert_eq!(
+            bucket_for(&h, None, 600, 120),
+            Bucket {
+                key: "ip:203.0.113.9".to_string(),
+                limit: 120,
+            }
+        );
+        assert_eq!(
+            bucket_for(&HeaderMap::new(), None, 600, 120).key,
+            "ip:unknown"
+        );
+    }
+
+    async fn ok_handler() -> &'static str {
+        "ok"
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_rejects_over_limit_with_retry_after(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let mut state = AppState::test_from_pool(pool);
+        let mut config = (*state.config).clone();
+        config.api_rate_limit_per_ip = 2;
+        state.config = std::sync::Arc::new(config);
+
+        let app = axum::Router::new()
+            .route("/things", axum::routing::get(ok_handler).post(ok_handler))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                limit_api_writes,
+            ))
+            .with_state(state);
+
+        let post = || {
+            Request::post("/things")
+                .header("x-forwarded-for", "203.0.113.50")
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        for remaining in ["1", "0"] {
+            let response = app.clone().oneshot(post()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["x-ratelimit-remaining"], remaining);
+        }
+
+        let response = app.clone().oneshot(post()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: i64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "rate_limited");
+        assert_eq!(body["error"]["details"]["retry_after_secs"], retry_after);
+
+        // A fresh made-up token doesn't get a fresh bucket
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/things")
+                    .header("x-forwarded-for", "203.0.113.50")
+                    .header("authorization", "Bearer not-a-real-token")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Reads are never limited
+        let get = Request::get("/things")
+            .header("x-forwarded-for", "203.0.113.50")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(app.oneshot(get).await.unwrap().status(), StatusCode::OK);
+    }
+}
@@ -1,21 +1,23 @@
-use axum::{
-    Json,
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
-};
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use serde::{Deserialize, Serialize};
 use url::Url;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
     models::battlesnake::{self, Battlesnake, CreateBattlesnake, UpdateBattlesnake, Visibility},
-    routes::auth::ApiUser,
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath},
+        },
+        auth::ApiUser,
+    },
     state::AppState,
 };
 
 /// Response format for snake endpoints
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SnakeResponse {
     pub id: Uuid,
     pub name: String,
@@ -39,7 +41,7 @@ impl From<Battlesnake> for SnakeResponse {
 }
 
 /// Request body for creating a snake
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateSnakeRequest {
     pub name: String,
     pub url: String,
@@ -48,7 +50,7 @@ pub struct CreateSnakeRequest {
 }
 
 /// Request body for updating a snake
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateSnakeRequest {
     pub name: Option<String>,
     pub url: Option<String>,
@@ -56,7 +58,7 @@ pub struct UpdateSnakeRequest {
 }
 
 /// Validate that a URL is a valid HTTP or HTTPS URL
-fn validate_url(url: &str) -> Result<(), &'static str> {
+pub(crate) fn validate_url(url: &str) -> Result<(), &'static str> {
     match Url::parse(url) {
         Ok(parsed) => {
             if parsed.scheme() == "http" || parsed.scheme() == "https" {
@@ -69,31 +71,51 @@ fn validate_url(url: &str) -> Result<(), &'static str> {
     }
 }
 
-/// GET /api/snakes - List user's snakes
+/// GET /api/v1/snakes - List user's snakes
+#[utoipa::path(
+    get,
+    path = "/api/v1/snakes",
+    tag = "snakes",
+    responses(
+        (status = 200, description = "Your snakes, by name", body = Vec<SnakeResponse>),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn list_snakes(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-) -> Result<impl IntoResponse, StatusCode> {
+) -> ApiResult<impl IntoResponse> {
     let snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list snakes: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| ApiError::internal("Failed to list snakes", e))?;
 
     let response: Vec<SnakeResponse> = snakes.into_iter().map(SnakeResponse::from).collect();
     Ok(Json(response))
 }
 
-/// POST /api/snakes - Create snake
+/// POST /api/v1/snakes - Create snake
+#[utoipa::path(
+    post,
+    path = "/api/v1/snakes",
+    tag = "snakes",
+    request_body = CreateSnakeRequest,
+    responses(
+        (status = 201, description = "Snake created", body = SnakeResponse),
+        (status = 400, description = "Invalid URL or disallowed name", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 409, description = "You already have a snake with this name", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_snake(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Json(request): Json<CreateSnakeRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ApiJson(request): ApiJson<CreateSnakeRequest>,
+) -> ApiResult<impl IntoResponse> {
     // Validate URL
     if let Err(e) = validate_url(&request.url) {
-        return Err((StatusCode::BAD_REQUEST, e.to_string()));
+        return Err(ApiError::bad_request(e.to_string()));
     }
 
     let create_data = CreateBattlesnake {
@@ -109,16 +131,14 @@ pub async fn create_snake(
     let snake = battlesnake::create_battlesnake(&state.db, user.user_id, create_data)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to create snake: {}", e);
             // Return the error message for unique constraint violations
             let msg = e.to_string();
-            if msg.contains("already have a battlesnake named") {
-                (StatusCode::CONFLICT, msg)
+            if msg == battlesnake::NAME_NOT_ALLOWED {
+                ApiError::bad_request(msg)
+            } else if msg.contains("already have a battlesnake named") {
+                ApiError::conflict(msg)
             } else {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Failed to create snake".to_string(),
-                )
+                ApiError::internal("Failed to create snake", e)
             }
         })?;
 
@@ -131,50 +151,68 @@ pub async fn create_snake(
     Ok((StatusCode::CREATED, Json(SnakeResponse::from(snake))))
 }
 
-/// GET /api/snakes/{id} - Get snake details
+/// GET /api/v1/snakes/{id} - Get snake details
+#[utoipa::path(
+    get,
+    path = "/api/v1/snakes/{id}",
+    tag = "snakes",
+    params(("id" = Uuid, Path, description = "Battlesnake ID")),
+    responses(
+        (status = 200, description = "The snake", body = SnakeResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such snake owned by you"),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn get_snake(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Path(snake_id): Path<Uuid>,
-) -> Result<impl IntoResponse, StatusCode> {
+    ApiPath(snake_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
     let snake = battlesnake::get_battlesnake_by_id(&state.db, snake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get snake: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .map_err(|e| ApiError::internal("Failed to get snake", e))?
+        .ok_or(ApiError::not_found("Snake not found"))?;
 
     // Enforce ownership - users can only view their own snakes via this endpoint
     if snake.user_id != user.user_id {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Snake not found"));
     }
 
     Ok(Json(SnakeResponse::from(snake)))
 }
 
-/// PUT /api/snakes/{id} - Update snake
+/// PUT /api/v1/snakes/{id} - Update snake
+#[utoipa::path(
+    put,
+    path = "/api/v1/snakes/{id}",
+    tag = "snakes",
+    params(("id" = Uuid, Path, description = "Battlesnake ID")),
+    request_body = UpdateSnakeRequest,
+    responses(
+        (status = 200, description = "Updated snake", body = SnakeResponse),
+        (status = 400, description = "Invalid URL or disallowed name", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such snake owned by you", body = ApiErrorBody),
+        (status = 409, description = "You already have a snake with this name", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn update_snake(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Path(snake_id): Path<Uuid>,
-    Json(request): Json<UpdateSnakeRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    ApiPath(snake_id): ApiPath<Uuid>,
+    ApiJson(request): ApiJson<UpdateSnakeRequest>,
+) -> ApiResult<impl IntoResponse> {
     // Get the existing snake first
     let existing = battlesnake::get_battlesnake_by_id(&state.db, snake_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get snake: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to get snake".to_string(),
-            )
-        })?
-        .ok_or((StatusCode::NOT_FOUND, "Snake not found".to_string()))?;
+        .map_err(|e| ApiError::internal("Failed to get snake", e))?
+        .ok_or(ApiError::not_found("Snake not found"))?;
 
     // Enforce ownership
     if existing.user_id != user.user_id {
-        return Err((StatusCode::NOT_FOUND, "Snake not found".to_string()));
+        return Err(ApiError::not_found("Snake not found"));
     }
 
     // Build update with existing values as defaults
@@ -182,7 +220,7 @@ pub async fn update_snake(
 
     // Validate URL if it changed
     if let Err(e) = validate_url(&new_url) {
-        return Err((StatusCode::BAD_REQUEST, e.to_string()));
+        return Err(ApiError::bad_request(e.to_string()));
     }
 
     let update_data = UpdateBattlesnake {
@@ -198,45 +236,49 @@ pub async fn update_snake(
     let snake = battlesnake::update_battlesnake(&state.db, snake_id, user.user_id, update_data)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to update snake: {}", e);
             let msg = e.to_string();
-            if msg.contains("already have a battlesnake named") {
-                (StatusCode::CONFLICT, msg)
+            if msg == battlesnake::NAME_NOT_ALLOWED {
+                ApiError::bad_request(msg)
+            } else if msg.contains("already have a battlesnake named") {
+                ApiError::conflict(msg)
             } else {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Failed to update snake".to_string(),
-                )
+                ApiError::internal("Failed to update snake", e)
             }
         })?;
 
     Ok(Json(SnakeResponse::from(snake)))
 }
 
-/// DELETE /api/snakes/{id} - Delete snake
+/// DELETE /api/v1/snakes/{id} - Delete snake
+#[utoipa::path(
+    delete,
+    path = "/api/v1/snakes/{id}",
+    tag = "snakes",
+    params(("id" = Uuid, Path, description = "Battlesnake ID")),
+    responses(
+        (status = 204, description = "Snake deleted"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such snake owned by you"),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn delete_snake(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Path(snake_id): Path<Uuid>,
-) -> Result<impl IntoResponse, StatusCode> {
+    ApiPath(snake_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
     // Check ownership first
     let exists = battlesnake::belongs_to_user(&state.db, snake_id, user.user_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to check snake ownership: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| ApiError::internal("Failed to check snake ownership", e))?;
 
     if !exists {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Snake not found"));
     }
 
     battlesnake::delete_battlesnake(&state.db, snake_id, user.user_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete snake: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| ApiError::internal("Failed to delete snake", e))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,109 +1,235 @@
-use axum::{
-    Json,
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
-};
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
-    models::api_token::{self, ApiToken},
-    routes::auth::ApiUser,
+    models::api_token::{self, ApiToken, NewApiToken, TokenScope},
+    routes::{
+        api::{
+            error::{ApiError, ApiErrorBody, ApiResult},
+            extract::{ApiJson, ApiPath},
+        },
+        auth::{ApiCaller, ApiUser},
+    },
     state::AppState,
 };
 
 /// Request body for creating a new token
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTokenRequest {
     pub name: String,
+    /// Scopes to grant. Defaults to every scope the caller can grant; a
+    /// Bearer-authenticated caller can only grant scopes its own token has.
+    pub scopes: Option<Vec<TokenScope>>,
+    /// When the token stops working (must be in the future). Omit for a
+    /// token that never expires.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-/// Response for a newly created token (includes the secret)
-#[derive(Debug, Serialize)]
+/// Response for a newly created or rotated token (includes the secret)
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CreateTokenResponse {
     pub id: Uuid,
     pub name: String,
     pub secret: String,
+    pub scopes: Vec<TokenScope>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<NewApiToken> for CreateTokenResponse {
+    fn from(new_token: NewApiToken) -> Self {
+        Self {
+            id: new_token.token.id,
+            scopes: new_token.token.scopes(),
+            name: new_token.token.name,
+            secret: new_token.secret,
+            created_at: new_token.token.created_at,
+            expires_at: new_token.token.expires_at,
+        }
+    }
 }
 
 /// Response for listing tokens (no secrets)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TokenResponse {
     pub id: Uuid,
     pub name: String,
+    pub scopes: Vec<TokenScope>,
     pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Past for a token that has expired; `null` never expires
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl From<ApiToken> for TokenResponse {
     fn from(token: ApiToken) -> Self {
         Self {
             id: token.id,
+            scopes: token.scopes(),
             name: token.name,
             last_used_at: token.last_used_at,
             created_at: token.created_at,
+            expires_at: token.expires_at,
         }
     }
 }
 
+/// Scopes `caller` may put on a token: what their account allows, narrowed to
+/// their own token's scopes when they authenticated with one. This stops a
+/// limited token from minting (or rotating into) a more powerful one.
+fn caller_grantable_scopes(caller: &ApiCaller) -> Vec<TokenScope> {
+    api_token::grantable_scopes(caller.user.is_admin)
+        .into_iter()
+        .filter(|scope| caller.has_scope(*scope))
+        .collect()
+}
+
 /// POST /api/v1/tokens - Create a new API token
+#[utoipa::path(
+    post,
+    path = "/api/v1/tokens",
+    tag = "tokens",
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, description = "Token created; the secret is only shown here", body = CreateTokenResponse),
+        (status = 400, description = "Expiry is in the past", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Requested a scope the caller can't grant", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn create_token(
     State(state): State<AppState>,
-    ApiUser(user): ApiUser,
-    Json(request): Json<CreateTokenRequest>,
-) -> Result<impl IntoResponse, StatusCode> {
-    let new_token = api_token::create_api_token(&state.db, user.user_id, &request.name)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to create API token: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    caller: ApiCaller,
+    ApiJson(request): ApiJson<CreateTokenRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let grantable = caller_grantable_scopes(&caller);
+    let scopes = request.scopes.unwrap_or_else(|| grantable.clone());
+    if let Some(scope) = scopes.iter().find(|s| !grantable.contains(s)) {
+        return Err(ApiError::forbidden(format!(
+            "You can't grant the `{}` scope",
+            scope.as_str()
+        )));
+    }
+
+    if request
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    {
+        return Err(ApiError::bad_request("expires_at must be in the future"));
+    }
+
+    let new_token = api_token::create_api_token(
+        &state.db,
+        caller.user.user_id,
+        &request.name,
+        &scopes,
+        request.expires_at,
+    )
+    .await
+    .map_err(|e| ApiError::internal("Failed to create API token", e))?;
 
     Ok((
         StatusCode::CREATED,
-        Json(CreateTokenResponse {
-            id: new_token.token.id,
-            name: new_token.token.name,
-            secret: new_token.secret,
-            created_at: new_token.token.created_at,
-        }),
+        Json(CreateTokenResponse::from(new_token)),
     ))
 }
 
-/// GET /api/v1/tokens - List all active tokens for the current user
+/// GET /api/v1/tokens - List the current user's unrevoked tokens
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens",
+    tag = "tokens",
+    responses(
+        (status = 200, description = "Unrevoked tokens, expired ones included, newest first", body = Vec<TokenResponse>),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn list_tokens(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-) -> Result<impl IntoResponse, StatusCode> {
+) -> ApiResult<impl IntoResponse> {
     let tokens = api_token::list_user_tokens(&state.db, user.user_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list API tokens: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| ApiError::internal("Failed to list API tokens", e))?;
 
     let response: Vec<TokenResponse> = tokens.into_iter().map(TokenResponse::from).collect();
     Ok(Json(response))
 }
 
 /// DELETE /api/v1/tokens/:id - Revoke a token
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tokens/{id}",
+    tag = "tokens",
+    params(("id" = Uuid, Path, description = "Token ID")),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 404, description = "No such active token"),
+    ),
+    security(("bearer" = []))
+)]
 pub async fn revoke_token(
     State(state): State<AppState>,
     ApiUser(user): ApiUser,
-    Path(token_id): Path<Uuid>,
-) -> Result<impl IntoResponse, StatusCode> {
+    ApiPath(token_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
     let revoked = api_token::revoke_token(&state.db, token_id, user.user_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to revoke API token: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| ApiError::internal("Failed to revoke API token", e))?;
 
     if revoked {
         Ok(StatusCode::NO_CONTENT)
     } else {
-        Err(StatusCode::NOT_FOUND)
+        Err(ApiError::not_found("Token not found"))
+    }
+}
+
+/// POST /api/v1/tokens/:id/rotate - Replace a token's secret
+#[utoipa::path(
+    post,
+    path = "/api/v1/tokens/{id}/rotate",
+    tag = "tokens",
+    params(("id" = Uuid, Path, description = "Token ID")),
+    responses(
+        (status = 200, description = "New secret for the same token; the old secret stops working", body = CreateTokenResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Token has scopes the caller can't grant", body = ApiErrorBody),
+        (status = 404, description = "No such active token", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn rotate_token(
+    State(state): State<AppState>,
+    caller: ApiCaller,
+    ApiPath(token_id): ApiPath<Uuid>,
+) -> ApiResult<impl IntoResponse> {
+    let internal_error =
+        |e: color_eyre::Report| ApiError::internal("Failed to rotate API token", e);
+    let not_found = || ApiError::not_found("Token not found");
+
+    let existing = api_token::get_user_token(&state.db, token_id, caller.user.user_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(not_found)?;
+
+    // Rotating hands back a working secret, so it's as powerful as creating
+    let grantable = caller_grantable_scopes(&caller);
+    if let Some(scope) = existing.scopes().iter().find(|s| !grantable.contains(s)) {
+        return Err(ApiError::forbidden(format!(
+            "You can't rotate a token with the `{}` scope",
+            scope.as_str()
+        )));
     }
+
+    let rotated = api_token::rotate_token(&state.db, token_id, caller.user.user_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(not_found)?;
+
+    Ok(Json(CreateTokenResponse::from(rotated)))
 }
@@ -0,0 +1,240 @@
+use axum::{Json, extract::State, response::IntoResponse};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::{battlesnake, game_battlesnake, leaderboard, tag, user::User},
+    routes::{
+        api::{
+            battlesnakes::BattlesnakeResponse,
+            error::{ApiError, ApiErrorBody, ApiResult},
+            games::{GameListItem, build_game_list_item},
+        },
+        auth::ApiUser,
+    },
+    state::AppState,
+};
+
+/// How many recent games `/api/v1/users/me` includes
+const RECENT_GAMES_LIMIT: i64 = 10;
+
+/// The authenticated user's public profile fields
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub github_login: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub pronouns: String,
+    pub country: String,
+    pub backstory: String,
+    pub is_admin: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&User> for UserProfile {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.user_id,
+            github_login: user.github_login.clone(),
+            display_name: user.display_name.clone(),
+            avatar_url: user.github_avatar_url.clone(),
+            pronouns: user.pronouns.clone(),
+            country: user.country.clone(),
+            backstory: user.backstory.clone(),
+            is_admin: user.is_admin,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// One of the user's snakes on a leaderboard
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserLeaderboardEntryResponse {
+    pub leaderboard_id: Uuid,
+    pub leaderboard_name: String,
+    pub battlesnake_id: Uuid,
+    pub battlesnake_name: String,
+    /// Competition rank, or null until the snake has played enough games
+    pub rank: Option<i64>,
+    pub display_score: f64,
+    pub games_played: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub disabled: bool,
+}
+
+/// Everything a client tool needs to bootstrap for the authenticated user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MeResponse {
+    pub user: UserProfile,
+    /// Your battlesnakes, by name
+    pub battlesnakes: Vec<BattlesnakeResponse>,
+    pub leaderboard_entries: Vec<UserLeaderboardEntryResponse>,
+    /// Your snakes' most recent games, newest first
+    pub recent_games: Vec<GameListItem>,
+}
+
+/// GET /api/v1/users/me - The authenticated user's profile, snakes, leaderboard entries, and recent games
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me",
+    tag = "users",
+    responses(
+        (status = 200, description = "Your profile and resources", body = MeResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn get_me(
+    State(state): State<AppState>,
+    ApiUser(user): ApiUser,
+) -> ApiResult<impl IntoResponse> {
+    let snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to list battlesnakes", e))?;
+    let mut battlesnakes = Vec::with_capacity(snakes.len());
+    for snake in snakes {
+        let tags = tag::get_tags_for_battlesnake(&state.db, snake.battlesnake_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to fetch battlesnake tags", e))?;
+        battlesnakes.push(BattlesnakeResponse::new(snake, tags));
+    }
+
+    let entries = leaderboard::get_entries_for_user(&state.db, user.user_id)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch leaderboard entries", e))?;
+    let mut leaderboard_entries = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let rank = if entry.disabled_at.is_some() {
+            None
+        } else {
+            leaderboard::get_rank_for_entry(
+                &state.db,
+                entry.leaderboard_id,
+                entry.display_score,
+                entry.games_played,
+            )
+            .await
+            .map_err(|e| ApiError::internal("Failed to compute leaderboard rank", e))?
+        };
+        leaderboard_entries.push(UserLeaderboardEntryResponse {
+            leaderboard_id: entry.leaderboard_id,
+            leaderboard_name: entry.leaderboard_name,
+            battlesnake_id: entry.battlesnake_id,
+            battlesnake_name: entry.battlesnake_name,
+            rank,
+            display_score: entry.display_score,
+            games_played: entry.games_played,
+            wins: entry.first_place_finishes,
+            losses: entry.non_first_finishes,
+            disabled: entry.disabled_at.is_some(),
+        });
+    }
+
+    let games = game_battlesnake::get_recent_games_for_user(
+        &state.db,
+        user.user_id,
+        None,
+        RECENT_GAMES_LIMIT,
+    )
+    .await
+    .map_err(|e| ApiError::internal("Failed to list recent games", e))?;
+    let mut recent_games = Vec::with_capacity(games.len());
+    for game in &games {
+        let snakes = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game.game_id)
+            .await
+            .map_err(|e| ApiError::internal("Failed to get battlesnakes for game", e))?;
+        recent_games.push(build_game_list_item(game, &snakes));
+    }
+
+    Ok(Json(MeResponse {
+        user: UserProfile::from(&user),
+        battlesnakes,
+        leaderboard_entries,
+        recent_games,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_me_includes_only_own_snakes_entries_and_games(
+        pool: sqlx::PgPool,
+    ) -> cja::Result<()> {
+        let mut user_ids = Vec::new();
+        for (github_id, login) in [(1_i64, "me"), (2, "someone-else")] {
+            let user_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (external_github_id, github_login, github_access_token)
+                 VALUES ($1, $2, 'test-token') RETURNING user_id",
+            )
+            .bind(github_id)
+            .bind(login)
+            .fetch_one(&pool)
+            .await?;
+            user_ids.push(user_id);
+        }
+
+        let mut snake_ids = Vec::new();
+        for (user_id, name) in [(user_ids[0], "mine"), (user_ids[1], "theirs")] {
+            let snake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            snake_ids.push(snake_id);
+        }
+
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Standard') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        for snake_id in &snake_ids {
+            leaderboard::get_or_create_entry(&pool, leaderboard_id, *snake_id).await?;
+        }
+
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type) VALUES ('11x11', 'Standard') RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO game_battlesnakes (game_id, battlesnake_id) VALUES ($1, $2), ($1, $3)",
+        )
+        .bind(game_id)
+        .bind(snake_ids[0])
+        .bind(snake_ids[1])
+        .execute(&pool)
+        .await?;
+
+        let user = crate::models::user::get_user_by_id(&pool, user_ids[0])
+            .await?
+            .unwrap();
+        let response = get_me(State(AppState::test_from_pool(pool)), ApiUser(user))
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let me: serde_json::Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(me["user"]["github_login"], "me");
+        assert_eq!(me["battlesnakes"].as_array().unwrap().len(), 1);
+        assert_eq!(me["battlesnakes"][0]["name"], "mine");
+        assert_eq!(me["leaderboard_entries"].as_array().unwrap().len(), 1);
+        assert_eq!(me["leaderboard_entries"][0]["battlesnake_name"], "mine");
+        assert!(me["leaderboard_entries"][0]["rank"].is_null());
+        assert_eq!(me["recent_games"].as_array().unwrap().len(), 1);
+        assert_eq!(me["recent_games"][0]["id"], game_id.to_string());
+        assert_eq!(me["recent_games"][0]["snakes"].as_array().unwrap().len(), 2);
+
+        Ok(())
+    }
+}
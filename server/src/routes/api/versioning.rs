@@ -0,0 +1,113 @@
+//! Deprecation headers for the unversioned JSON API.
+//!
+//! Every resource under `/api/v1` is also mounted at its old unversioned
+//! path so existing scripts keep working. Responses on the old paths carry
+//! `Deprecation` (RFC 9745), a `Link` to the `/api/v1` successor, and —
+//! once `API_UNVERSIONED_SUNSET` is configured — a `Sunset` date (RFC 8594).
+//! The engine-compatible board viewer endpoints are not versioned and never
+//! deprecated: the board viewer and GIF exporter hardcode them.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::state::AppState;
+
+/// When the unversioned paths were deprecated (2026-10-16), as the
+/// `Deprecation` header's `@<unix timestamp>` value
+const UNVERSIONED_DEPRECATION: &str = "@1792108800";
+
+fn deprecation_headers(path: &str, sunset: Option<NaiveDate>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "deprecation",
+        HeaderValue::from_static(UNVERSIONED_DEPRECATION),
+    );
+    if let Ok(link) = HeaderValue::from_str(&format!("</api/v1{path}>; rel=\"successor-version\""))
+    {
+        headers.insert("link", link);
+    }
+    if let Some(date) = sunset {
+        let http_date = date
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        if let Ok(sunset) = HeaderValue::from_str(&http_date) {
+            headers.insert("sunset", sunset);
+        }
+    }
+    headers
+}
+
+/// Middleware for the unversioned mount of the `/api/v1` routes. Runs inside
+/// the `/api` nest, so the request path is already relative to it.
+pub async fn deprecate_unversioned(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+    response.headers_mut().extend(deprecation_headers(
+        &path,
+        state.config.api_unversioned_sunset,
+    ));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecation_headers_without_sunset() {
+        let headers = deprecation_headers("/snakes/abc", None);
+        assert_eq!(headers["deprecation"], "@1792108800");
+        assert_eq!(
+            headers["link"],
+            "</api/v1/snakes/abc>; rel=\"successor-version\""
+        );
+        assert!(headers.get("sunset").is_none());
+    }
+
+    #[test]
+    fn test_sunset_is_an_http_date() {
+        let headers = deprecation_headers("/games", NaiveDate::from_ymd_opt(2027, 1, 31));
+        assert_eq!(headers["sunset"], "Sun, 31 Jan 2027 00:00:00 GMT");
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_only_unversioned_aliases_are_deprecated(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let app = crate::routes::routes(AppState::test_from_pool(pool));
+        let get = |uri: &str| Request::get(uri).body(axum::body::Body::empty()).unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/v1/leaderboards"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert!(response.headers().get("deprecation").is_none());
+
+        let response = app.clone().oneshot(get("/api/leaderboards")).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(response.headers()["deprecation"], "@1792108800");
+        assert_eq!(
+            response.headers()["link"],
+            "</api/v1/leaderboards>; rel=\"successor-version\""
+        );
+
+        // Board viewer endpoints stay unversioned and undeprecated
+        let frames = format!("/api/games/{}/frames", uuid::Uuid::new_v4());
+        let response = app.oneshot(get(&frames)).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+        assert!(response.headers().get("deprecation").is_none());
+    }
+}
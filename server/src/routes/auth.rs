@@ -5,18 +5,20 @@ use axum::{
 };
 use cja::server::cookies::{Cookie, CookieJar};
 use color_eyre::eyre::eyre;
+use std::marker::PhantomData;
 use uuid::Uuid;
 
 use crate::{
     errors::ServerError,
     models::{
-        api_token::validate_token,
+        api_token::{ApiToken, TokenScope, validate_token},
         session::{
             SESSION_COOKIE_NAME, SESSION_EXPIRATION_SECONDS, Session, create_session,
             get_session_with_user,
         },
         user::{User, get_user_by_id},
     },
+    routes::api::error::ApiError,
     state::AppState,
 };
 
@@ -224,10 +226,27 @@ impl FromRequestParts<AppState> for CurrentUserWithSession {
 /// ```
 pub struct ApiUser(pub User);
 
+/// Extractor like [`ApiUser`] that also exposes the Bearer token used, if any
+///
+/// Use this when a handler needs to look at token scopes itself (e.g. token
+/// management, where a token must not mint a more powerful one).
+pub struct ApiCaller {
+    pub user: User,
+    /// `None` when authenticated by session cookie
+    pub token: Option<ApiToken>,
+}
+
+impl ApiCaller {
+    /// Session auth carries every scope; Bearer auth only the token's own.
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        self.token.as_ref().is_none_or(|t| t.has_scope(scope))
+    }
+}
+
 /// Result of attempting Bearer token authentication
 enum BearerAuthResult {
     /// Successfully authenticated user
-    Authenticated(Box<User>),
+    Authenticated(Box<User>, Box<ApiToken>),
     /// Authorization header present but token invalid/revoked
     InvalidToken,
     /// No Authorization header present
@@ -249,13 +268,13 @@ async fn try_bearer_auth(parts: &Parts, state: &AppState) -> BearerAuthResult {
     };
 
     // validate_token hashes the token internally
-    let user_id = match validate_token(&state.db, token).await {
-        Ok(Some(id)) => id,
+    let api_token = match validate_token(&state.db, token).await {
+        Ok(Some(api_token)) => api_token,
         _ => return BearerAuthResult::InvalidToken,
     };
 
-    match get_user_by_id(&state.db, user_id).await {
-        Ok(Some(user)) => BearerAuthResult::Authenticated(Box::new(user)),
+    match get_user_by_id(&state.db, api_token.user_id).await {
+        Ok(Some(user)) => BearerAuthResult::Authenticated(Box::new(user), Box::new(api_token)),
         _ => BearerAuthResult::InvalidToken,
     }
 }
@@ -282,7 +301,8 @@ impl FromRequestParts<AppState> for AdminUser {
 
 /// Extractor for requiring an authenticated admin user (Bearer token OR session auth)
 ///
-/// Returns 401 if not authenticated, 403 if authenticated but not admin.
+/// Returns 401 if not authenticated, 403 if authenticated but not admin or
+/// if the Bearer token lacks the `admin` scope.
 pub struct AdminApiUser(pub User);
 
 impl FromRequestParts<AppState> for AdminApiUser {
@@ -292,15 +312,18 @@ impl FromRequestParts<AppState> for AdminApiUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let ApiUser(user) = ApiUser::from_request_parts(parts, state).await?;
-        if !user.is_admin {
-            return Err((StatusCode::FORBIDDEN, "Admin access required").into_response());
+        let caller = ApiCaller::from_request_parts(parts, state).await?;
+        if !caller.user.is_admin {
+            return Err(ApiError::forbidden("Admin access required").into_response());
+        }
+        if !caller.has_scope(TokenScope::Admin) {
+            return Err(missing_scope_response(TokenScope::Admin));
         }
-        Ok(AdminApiUser(user))
+        Ok(AdminApiUser(caller.user))
     }
 }
 
-impl FromRequestParts<AppState> for ApiUser {
+impl FromRequestParts<AppState> for ApiCaller {
     type Rejection = axum::response::Response;
 
     async fn from_request_parts(
@@ -308,9 +331,14 @@ impl FromRequestParts<AppState> for ApiUser {
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
         match try_bearer_auth(parts, state).await {
-            BearerAuthResult::Authenticated(user) => return Ok(ApiUser(*user)),
+            BearerAuthResult::Authenticated(user, token) => {
+                return Ok(ApiCaller {
+                    user: *user,
+                    token: Some(*token),
+                });
+            }
             BearerAuthResult::InvalidToken => {
-                return Err((StatusCode::UNAUTHORIZED, "Invalid or revoked token").into_response());
+                return Err(ApiError::unauthorized("Invalid or revoked token").into_response());
             }
             BearerAuthResult::NoHeader => {
                 // Fall through to session auth
@@ -322,7 +350,78 @@ impl FromRequestParts<AppState> for ApiUser {
 
         session
             .user
-            .map(ApiUser)
-            .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Authentication required").into_response())
+            .map(|user| ApiCaller { user, token: None })
+            .ok_or_else(|| ApiError::unauthorized("Authentication required").into_response())
+    }
+}
+
+impl FromRequestParts<AppState> for ApiUser {
+    type Rejection = axum::response::Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let caller = ApiCaller::from_request_parts(parts, state).await?;
+        Ok(ApiUser(caller.user))
+    }
+}
+
+fn missing_scope_response(scope: TokenScope) -> Response {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        "missing_scope",
+        format!("Token is missing the `{}` scope", scope.as_str()),
+    )
+    .with_details(serde_json::json!({ "scope": scope.as_str() }))
+    .into_response()
+}
+
+/// Marker for the scope a [`ScopedApiUser`] requires
+pub trait RequiredScope {
+    const SCOPE: TokenScope;
+}
+
+/// `games:create` — see [`ScopedApiUser`]
+pub struct GamesCreateScope;
+
+impl RequiredScope for GamesCreateScope {
+    const SCOPE: TokenScope = TokenScope::GamesCreate;
+}
+
+/// `leaderboards:write` — see [`ScopedApiUser`]
+pub struct LeaderboardsWriteScope;
+
+impl RequiredScope for LeaderboardsWriteScope {
+    const SCOPE: TokenScope = TokenScope::LeaderboardsWrite;
+}
+
+/// Extractor for API endpoints that need a token scope
+///
+/// Same auth as [`ApiUser`], but Bearer tokens must carry `S::SCOPE`
+/// (403 otherwise). Session auth always passes.
+///
+/// Example:
+/// ```
+/// async fn api_route(
+///    ScopedApiUser(user, _): ScopedApiUser<GamesCreateScope>,
+/// ) -> impl IntoResponse {
+///    Json(user)
+/// }
+/// ```
+pub struct ScopedApiUser<S>(pub User, pub PhantomData<S>);
+
+impl<S: RequiredScope + Send> FromRequestParts<AppState> for ScopedApiUser<S> {
+    type Rejection = axum::response::Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let caller = ApiCaller::from_request_parts(parts, state).await?;
+        if !caller.has_scope(S::SCOPE) {
+            return Err(missing_scope_response(S::SCOPE));
+        }
+        Ok(ScopedApiUser(caller.user, PhantomData))
     }
 }
@@ -1,10 +1,12 @@
 use axum::{
+    Form,
     extract::{Path, RawForm, State},
     http::StatusCode,
     response::{IntoResponse, Redirect},
 };
 use color_eyre::eyre::Context as _;
 use maud::{Markup, html};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
@@ -12,14 +14,20 @@ use crate::{
     customizations::chip_color,
     errors::{ServerResult, WithStatus},
     models::battlesnake::{self, CreateBattlesnake, UpdateBattlesnake, Visibility},
+    models::battlesnake_transfer,
+    models::follow,
     models::game_battlesnake,
     models::leaderboard,
     models::session,
+    models::snake_error,
     models::snake_health_status,
+    models::snake_url_verification,
     models::tag,
     models::tournament,
     models::user::get_user_by_id,
     routes::auth::{CurrentUser, CurrentUserWithSession, OptionalUser},
+    routes::battlesnake_transfer::{pending_transfers_section, transfer_section},
+    routes::feed::follow_button,
     snake_health,
     state::AppState,
 };
@@ -117,6 +125,9 @@ pub async fn list_battlesnakes(
     let battlesnakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
         .await
         .wrap_err("Failed to get battlesnakes")?;
+    let transfers = battlesnake_transfer::list_pending_for_user(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to get pending transfers")?;
 
     // Use flash from page_factory (already extracted and cleared from DB)
     let flash = page_factory.flash.clone();
@@ -134,6 +145,8 @@ pub async fn list_battlesnakes(
                 }
             }
 
+            (pending_transfers_section(user.user_id, &transfers))
+
             @if battlesnakes.is_empty() {
                 p class="empty" { "You don't have any battlesnakes yet." }
             } @else {
@@ -312,8 +325,8 @@ pub async fn create_battlesnake(
             Ok(Redirect::to("/battlesnakes").into_response())
         }
         Err(err) => {
-            // Check if it's a name uniqueness error
-            if err.to_string().contains("already have a battlesnake named") {
+            // Check if the name was refused (taken, or on the word list)
+            if battlesnake::is_name_rejection(&err) {
                 // Set error flash message
                 session::set_flash_message(
                     &state.db,
@@ -363,6 +376,11 @@ pub async fn edit_battlesnake(
         .iter()
         .map(|t| t.tag_id)
         .collect();
+    let pending_transfer = battlesnake_transfer::list_pending_for_user(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to get pending transfers")?
+        .into_iter()
+        .find(|t| t.battlesnake_id == battlesnake_id);
 
     // Use flash from page_factory (already extracted and cleared from DB)
     let flash = page_factory.flash.clone();
@@ -403,6 +421,8 @@ pub async fn edit_battlesnake(
                     a href="/battlesnakes" class="btn" { "Cancel" }
                 }
             }
+
+            (transfer_section(battlesnake_id, pending_transfer.as_ref()))
         }),
         flash,
     ))
@@ -478,8 +498,8 @@ pub async fn update_battlesnake(
             Ok(Redirect::to("/battlesnakes").into_response())
         }
         Err(err) => {
-            // Check if it's a name uniqueness error
-            if err.to_string().contains("already have a battlesnake named") {
+            // Check if the name was refused (taken, or on the word list)
+            if battlesnake::is_name_rejection(&err) {
                 // Set error flash message
                 session::set_flash_message(
                     &state.db,
@@ -554,67 +574,30 @@ pub async fn delete_battlesnake(
     Ok(Redirect::to("/battlesnakes").into_response())
 }
 
-struct BattlesnakeStats {
-    total_games: usize,
-    finished_games: usize,
-    wins: usize,
-    second_places: usize,
-    third_places: usize,
-    fourth_places: usize,
-    win_rate: f64,
-    average_placement: f64,
-}
-
-fn compute_stats(history: &[game_battlesnake::GameHistoryEntry]) -> BattlesnakeStats {
-    use crate::models::game::GameStatus;
-
-    let total_games = history.len();
-    let mut finished_games = 0usize;
-    let mut wins = 0usize;
-    let mut second_places = 0usize;
-    let mut third_places = 0usize;
-    let mut fourth_places = 0usize;
-    let mut placement_sum = 0i64;
-    let mut placement_count = 0usize;
-
-    for entry in history {
-        if entry.status == GameStatus::Finished {
-            finished_games += 1;
-            if let Some(placement) = entry.placement {
-                match placement {
-                    1 => wins += 1,
-                    2 => second_places += 1,
-                    3 => third_places += 1,
-                    4 => fourth_places += 1,
-                    _ => {}
-                }
-                placement_sum += i64::from(placement);
-                placement_count += 1;
-            }
-        }
-    }
-
-    let win_rate = if finished_games > 0 {
-        (wins as f64 / finished_games as f64) * 100.0
+/// Recent games listed on a snake's profile; the stats above them cover
+/// every game.
+const PROFILE_RECENT_GAMES: i64 = 20;
+
+/// How far back the profile's move-health card looks
+const PROFILE_LATENCY_WINDOW_DAYS: i64 = 7;
+
+/// Headline for the move-health card: failures first (a fast snake that
+/// errors still loses games), then p95 latency against the move timeout.
+fn latency_health(
+    stats: &snake_error::SnakeLatencyStats,
+    move_timeout_ms: i64,
+) -> (&'static str, &'static str) {
+    if stats.total_moves == 0 {
+        ("No recent moves", "bg-secondary text-white")
+    } else if stats.error_rate() >= snake_error::FAILING_ERROR_RATE {
+        ("Failing", "bg-danger text-white")
+    } else if stats
+        .p95_latency_ms
+        .is_some_and(|p95| p95 > move_timeout_ms as f64 * 0.8)
+    {
+        ("Slow", "bg-warning text-dark")
     } else {
-        0.0
-    };
-
-    let average_placement = if placement_count > 0 {
-        placement_sum as f64 / placement_count as f64
-    } else {
-        0.0
-    };
-
-    BattlesnakeStats {
-        total_games,
-        finished_games,
-        wins,
-        second_places,
-        third_places,
-        fourth_places,
-        win_rate,
-        average_placement,
+        ("Healthy", "bg-success text-white")
     }
 }
 
@@ -680,7 +663,68 @@ pub async fn reactivate_battlesnake(
     Ok(Redirect::to(&format!("/battlesnakes/{battlesnake_id}/profile")).into_response())
 }
 
-// View a battlesnake's profile with game history and stats.
+/// POST /battlesnakes/{id}/verify — prove the owner controls the snake's
+/// URL by finding its verification token in the URL's `GET /` response.
+/// Required before the snake can join leaderboards.
+pub async fn verify_battlesnake_url(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(battlesnake_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let snake = battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await
+        .wrap_err("Failed to get battlesnake")?
+        .filter(|s| s.user_id == user.user_id)
+        .ok_or_else(|| {
+            "Battlesnake not found or you don't have permission to verify it".to_string()
+        })
+        .with_status(StatusCode::FORBIDDEN)?;
+
+    let verification = snake_url_verification::get_or_create(&state.db, battlesnake_id)
+        .await
+        .wrap_err("Failed to get snake URL verification")?;
+
+    let timeout = state.settings().await.info_timeout();
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .wrap_err("Failed to build HTTP client for URL verification")?;
+    let checked =
+        snake_health::check_url_token(&client, &snake.url, &verification.token, timeout).await;
+
+    let (message, flash_type) = match checked {
+        Ok(()) => {
+            snake_url_verification::mark_verified(&state.db, battlesnake_id, &snake.url)
+                .await
+                .wrap_err("Failed to mark snake URL verified")?;
+            tracing::info!(
+                battlesnake_id = %battlesnake_id,
+                user_id = %user.user_id,
+                "Owner verified snake URL"
+            );
+            (
+                "URL verified! This snake can now join leaderboards, and you can remove the token \
+                 from its response."
+                    .to_string(),
+                session::FLASH_TYPE_SUCCESS,
+            )
+        }
+        Err(problem) => (
+            format!("Couldn't verify this URL: {problem}"),
+            session::FLASH_TYPE_ERROR,
+        ),
+    };
+
+    session::set_flash_message(&state.db, session.session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to(&format!("/battlesnakes/{battlesnake_id}/profile")).into_response())
+}
+
+// View a battlesnake's profile: the canonical public page for a snake, with
+// lifetime stats, standing on every leaderboard it's entered, move health,
+// and its most recent games.
 // Public to everyone: visibility only controls whether a snake can be
 // matchmade against, not who can see it.
 #[allow(clippy::too_many_lines)]
@@ -704,10 +748,28 @@ pub async fn view_battlesnake_profile(
         .await
         .wrap_err("Failed to get owner user")?;
 
-    // Fetch game history
-    let history = game_battlesnake::get_game_history_for_battlesnake(&state.db, battlesnake_id)
+    // Lifetime stats, plus the most recent games to list
+    let stats = game_battlesnake::get_game_stats_for_battlesnake(&state.db, battlesnake_id)
         .await
-        .wrap_err("Failed to get game history")?;
+        .wrap_err("Failed to get game stats")?;
+    let history = game_battlesnake::get_game_history_for_battlesnake(
+        &state.db,
+        battlesnake_id,
+        PROFILE_RECENT_GAMES,
+    )
+    .await
+    .wrap_err("Failed to get game history")?;
+
+    // How the snake has been answering /move lately
+    let latency = snake_error::get_latency_stats(
+        &state.db,
+        battlesnake_id,
+        chrono::Utc::now() - chrono::Duration::days(PROFILE_LATENCY_WINDOW_DAYS),
+    )
+    .await
+    .wrap_err("Failed to get latency stats")?;
+    let move_timeout_ms = state.settings().await.move_timeout_ms();
+    let (health_label, health_class) = latency_health(&latency, move_timeout_ms);
 
     // Fetch leaderboard entries
     let leaderboard_entries = leaderboard::get_entries_for_battlesnake(&state.db, battlesnake_id)
@@ -719,6 +781,26 @@ pub async fn view_battlesnake_profile(
         .await
         .wrap_err("Failed to get snake health status")?;
 
+    // URL ownership verification, shown to the owner only
+    let verification = if is_owner {
+        Some(
+            snake_url_verification::get_or_create(&state.db, battlesnake_id)
+                .await
+                .wrap_err("Failed to get snake URL verification")?,
+        )
+    } else {
+        None
+    };
+
+    let following = match &user {
+        Some(user) if !is_owner => {
+            follow::is_following_snake(&state.db, user.user_id, battlesnake_id)
+                .await
+                .wrap_err("Failed to check snake follow")?
+        }
+        _ => false,
+    };
+
     // Curated language/platform tags for this snake
     let snake_tags = tag::get_tags_for_battlesnake(&state.db, battlesnake_id)
         .await
@@ -726,9 +808,6 @@ pub async fn view_battlesnake_profile(
 
     let flash = page_factory.flash.clone();
 
-    // Compute stats
-    let stats = compute_stats(&history);
-
     // Owner display info
     let owner_login = owner
         .as_ref()
@@ -777,6 +856,26 @@ pub async fn view_battlesnake_profile(
                     }
                 }
 
+                // URL verification: needed before the snake can join
+                // leaderboards, and again whenever its URL changes.
+                @if let Some(verification) = verification.as_ref().filter(|v| !v.is_verified_for(&snake.url)) {
+                    div class="alert alert-info" {
+                        p {
+                            strong { "Verify this snake's URL to join leaderboards. " }
+                            "Include this token anywhere in the JSON your snake returns from "
+                            code { "GET /" } " (for example as the " code { "version" } " field), then press Verify:"
+                        }
+                        pre style="white-space: pre-wrap; word-break: break-all;" { (verification.token) }
+                        p class="small" {
+                            "This proves the server at " (snake.url) " is yours. You can remove the token once verified; "
+                            "changing the snake's URL means verifying again."
+                        }
+                        form action={"/battlesnakes/"(battlesnake_id)"/verify"} method="post" style="display: inline;" {
+                            button type="submit" class="btn btn-sm btn-primary" { "Verify URL" }
+                        }
+                    }
+                }
+
                 // Snake Header Section
                 div class="card mb-4" {
                     div class="card-body" {
@@ -838,11 +937,23 @@ pub async fn view_battlesnake_profile(
                                     form action={"/battlesnakes/"(battlesnake_id)"/test"} method="post" class="inline" style="display: inline;" {
                                         button type="submit" class="btn btn-sm btn-info" { "Test Snake" }
                                     }
+                                    a href={"/battlesnakes/"(battlesnake_id)"/console"} class="btn btn-sm btn-info" { "Test Console" }
                                     a href={"/battlesnakes/"(battlesnake_id)"/edit"} class="btn btn-sm btn-primary" { "Edit" }
+                                    a href={"/snakes?vs="(battlesnake_id)} class="btn btn-sm" { "Compare" }
                                     form action={"/battlesnakes/"(battlesnake_id)"/delete"} method="post" class="inline" style="display: inline;" {
                                         button type="submit" class="btn btn-sm btn-danger" onclick="return confirm('Are you sure you want to delete this battlesnake?');" { "Delete" }
                                     }
                                 }
+                            } @else if user.is_some() {
+                                div {
+                                    a href={"/challenges/new?opponent="(battlesnake_id)} class="btn btn-sm btn-primary" { "Challenge" }
+                                    a href={"/snakes?vs="(battlesnake_id)} class="btn btn-sm" { "Compare" }
+                                    (follow_button(&format!("/battlesnakes/{battlesnake_id}"), following))
+                                }
+                            } @else {
+                                div {
+                                    a href={"/snakes?vs="(battlesnake_id)} class="btn btn-sm" { "Compare" }
+                                }
                             }
                         }
                     }
@@ -862,8 +973,8 @@ pub async fn view_battlesnake_profile(
                         div class="card-body" {
                             h5 { "Win Rate" }
                             p style="font-size: 2em; margin: 0;" {
-                                @if stats.finished_games > 0 {
-                                    (format!("{:.1}%", stats.win_rate))
+                                @if let Some(win_rate) = stats.win_rate() {
+                                    (format!("{win_rate:.1}%"))
                                 } @else {
                                     "N/A"
                                 }
@@ -882,14 +993,32 @@ pub async fn view_battlesnake_profile(
                         div class="card-body" {
                             h5 { "Avg. Placement" }
                             p style="font-size: 2em; margin: 0;" {
-                                @if stats.finished_games > 0 {
-                                    (format!("{:.1}", stats.average_placement))
+                                @if let Some(average) = stats.average_placement {
+                                    (format!("{average:.1}"))
                                 } @else {
                                     "N/A"
                                 }
                             }
                         }
                     }
+                    div class="card mb-4" style="flex: 1; min-width: 150px;" {
+                        div class="card-body" {
+                            h5 { "Move Health" }
+                            p style="font-size: 1.5em; margin: 0;" {
+                                span class={"badge " (health_class)} { (health_label) }
+                            }
+                            @if latency.total_moves > 0 {
+                                p class="text-muted small" style="margin: 4px 0 0;" {
+                                    @if let (Some(avg), Some(p95)) = (latency.avg_latency_ms, latency.p95_latency_ms) {
+                                        (format!("{avg:.0}ms avg · {p95:.0}ms p95 · "))
+                                    }
+                                    (format!("{:.1}% failed", latency.error_rate() * 100.0))
+                                    " over the last " (PROFILE_LATENCY_WINDOW_DAYS) " days ("
+                                    (move_timeout_ms) "ms limit)"
+                                }
+                            }
+                        }
+                    }
                 }
 
                 // Placement Distribution
@@ -914,6 +1043,7 @@ pub async fn view_battlesnake_profile(
                         thead {
                             tr {
                                 th { "Leaderboard" }
+                                th { "Rank" }
                                 th { "Rating" }
                                 th { "Games" }
                                 th { "1st Place %" }
@@ -925,6 +1055,13 @@ pub async fn view_battlesnake_profile(
                             @for entry in &leaderboard_entries {
                                 tr {
                                     td { (entry.leaderboard_name) }
+                                    td {
+                                        @if let Some(rank) = entry.rank {
+                                            "#" (rank)
+                                        } @else {
+                                            span class="text-muted" { "Unranked" }
+                                        }
+                                    }
                                     td { (format!("{:.1}", entry.display_score)) }
                                     td { (entry.games_played) }
                                     td {
@@ -950,8 +1087,13 @@ pub async fn view_battlesnake_profile(
                     }
                 }
 
-                // Game History Table
-                h2 { "Game History" }
+                // Recent Games Table
+                h2 { "Recent Games" }
+                @if stats.total_games > PROFILE_RECENT_GAMES {
+                    p class="text-muted small" {
+                        "Showing the " (PROFILE_RECENT_GAMES) " most recent of " (stats.total_games) " games."
+                    }
+                }
 
                 @if history.is_empty() {
                     div class="alert alert-info" {
@@ -1168,3 +1310,141 @@ pub async fn test_battlesnake(
         }),
     ))
 }
+
+#[derive(Deserialize)]
+pub struct ConsoleForm {
+    payload: String,
+}
+
+// Fetch a snake for the test console, refusing anyone but its owner: like
+// Test Snake, the console makes the server call the owner's infrastructure.
+async fn console_snake(
+    state: &AppState,
+    user_id: Uuid,
+    battlesnake_id: Uuid,
+) -> ServerResult<battlesnake::Battlesnake, StatusCode> {
+    let snake = battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await
+        .wrap_err("Failed to get battlesnake")?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    if snake.user_id != user_id {
+        return Err("You don't have permission to test this battlesnake".to_string())
+            .with_status(StatusCode::FORBIDDEN);
+    }
+    Ok(snake)
+}
+
+// Test console: send one hand-edited /move body to your snake and see the
+// raw response, latency and how the arena would parse it.
+pub async fn test_console(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(battlesnake_id): Path<Uuid>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let snake = console_snake(&state, user.user_id, battlesnake_id).await?;
+    let payload = snake_health::default_console_payload(&snake);
+
+    Ok(page_factory.create_page(
+        format!("Test Console: {}", snake.name),
+        Box::new(console_page(&snake, &payload, None)),
+    ))
+}
+
+// Run a console request. Renders the result directly (like Test Snake) so
+// the edited payload stays in the editor for the next try.
+pub async fn run_test_console(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(battlesnake_id): Path<Uuid>,
+    page_factory: PageFactory,
+    Form(form): Form<ConsoleForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let snake = console_snake(&state, user.user_id, battlesnake_id).await?;
+
+    let outcome = match snake_health::parse_console_payload(&form.payload) {
+        Ok(payload) => {
+            let client = reqwest::Client::builder()
+                .timeout(snake_health::HEALTH_CHECK_TIMEOUT)
+                .build()
+                .wrap_err("Failed to build HTTP client for snake test")?;
+            Ok(snake_health::send_console_move(&client, &snake.url, &payload).await)
+        }
+        Err(message) => Err(message),
+    };
+
+    Ok(page_factory.create_page(
+        format!("Test Console: {}", snake.name),
+        Box::new(console_page(&snake, &form.payload, Some(&outcome))),
+    ))
+}
+
+fn console_page(
+    snake: &battlesnake::Battlesnake,
+    payload: &str,
+    outcome: Option<&Result<snake_health::ConsoleMoveResult, String>>,
+) -> Markup {
+    let battlesnake_id = snake.battlesnake_id;
+    html! {
+        div class="container" {
+            h1 { "Test Console: " (snake.name) }
+            p {
+                "Send a single "
+                code { "POST /move" }
+                " to "
+                a href=(snake.url) target="_blank" { (snake.url) }
+                " from the arena's servers. The body starts as the turn-0 request a real game sends; "
+                "edit it to reproduce a board your snake struggles with."
+            }
+
+            @match outcome {
+                Some(Err(message)) => {
+                    div class="alert alert-danger" { p { (message) } }
+                }
+                Some(Ok(result)) => {
+                    @let call = &result.call;
+                    div class=(if call.ok { "alert alert-success" } else { "alert alert-danger" }) {
+                        p { (call.summary) }
+                    }
+                    table class="table" {
+                        tbody {
+                            tr {
+                                th { "HTTP Status" }
+                                td {
+                                    @if let Some(status) = call.http_status { (status) } @else { "—" }
+                                }
+                            }
+                            tr {
+                                th { "Latency" }
+                                td {
+                                    @if let Some(latency) = call.latency_ms { (latency) " ms" } @else { "—" }
+                                }
+                            }
+                        }
+                    }
+                    @if let Some(body) = &result.raw_body {
+                        h2 { "Raw Response" }
+                        pre style="white-space: pre-wrap; word-break: break-all; font-size: 0.85em;" {
+                            @if body.is_empty() { "(empty body)" } @else { (body) }
+                        }
+                    }
+                }
+                None => {}
+            }
+
+            form action={"/battlesnakes/"(battlesnake_id)"/console"} method="post" {
+                div class="field" {
+                    label for="payload" { "Request Body" }
+                    textarea id="payload" name="payload" rows="24" spellcheck="false"
+                        style="width: 100%; font-family: var(--mono); font-size: 0.85em;" { (payload) }
+                }
+                div class="mt-4" {
+                    button type="submit" class="btn btn-primary" { "Send /move" }
+                    a href={"/battlesnakes/"(battlesnake_id)"/console"} class="btn btn-secondary ms-2" { "Reset Body" }
+                    a href={"/battlesnakes/"(battlesnake_id)"/profile"} class="btn btn-secondary ms-2" { "Back to Profile" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Snake ownership transfers: the owner offers a snake from its edit page,
+//! and the recipient accepts or declines it from their snake list.
+
+use axum::{
+    Form,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use color_eyre::eyre::Context as _;
+use maud::{Markup, html};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{ServerResult, WithStatus},
+    models::{
+        battlesnake,
+        battlesnake_transfer::{self, TransferListing, TransferStatus},
+        session,
+    },
+    routes::auth::CurrentUserWithSession,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TransferForm {
+    pub to_login: String,
+    /// Checkbox; absent when unchecked
+    #[serde(default)]
+    pub include_history: bool,
+}
+
+async fn flash_redirect(
+    state: &AppState,
+    session_id: Uuid,
+    message: String,
+    flash_type: &str,
+    to: &str,
+) -> ServerResult<Response, StatusCode> {
+    session::set_flash_message(&state.db, session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(to).into_response())
+}
+
+/// The transfer section of a snake's edit page: the offer waiting on
+/// someone, or the form to make one.
+pub fn transfer_section(battlesnake_id: Uuid, pending: Option<&TransferListing>) -> Markup {
+    html! {
+        div class="section" {
+            h2 { "Transfer Ownership" }
+            @if let Some(t) = pending {
+                p {
+                    "Offered to " strong { (t.to_login) } " on " (t.created_at.format("%b %-d, %Y"))
+                    @if t.include_history { ", with its leaderboard history" } @else { ", without its leaderboard history" }
+                    ". It stays yours until they accept."
+                }
+                form action={"/transfers/"(t.battlesnake_transfer_id)"/cancel"} method="post" {
+                    button type="submit" class="btn danger" { "Cancel Transfer" }
+                }
+            } @else {
+                p class="help" {
+                    "Hand this snake to another user. It moves once they accept, keeping its games; "
+                    "pending challenges are canceled."
+                }
+                form class="form-stack" action={"/battlesnakes/"(battlesnake_id)"/transfer"} method="post" {
+                    div class="field" {
+                        label for="to_login" { "Recipient's GitHub username" }
+                        input type="text" id="to_login" name="to_login" required autocomplete="off";
+                    }
+                    div class="field" {
+                        label style="display:inline-flex; align-items:center; gap:6px; font-weight:normal;" {
+                            input type="checkbox" name="include_history" value="true" checked;
+                            "Keep its leaderboard ratings and history"
+                        }
+                        p class="help" { "Unchecked, the snake leaves every leaderboard and the new owner starts fresh." }
+                    }
+                    div class="form-cta" {
+                        button type="submit" class="btn" { "Offer Transfer" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pending transfers on the snake list: ones to answer, and ones waiting on
+/// someone else.
+pub fn pending_transfers_section(user_id: Uuid, transfers: &[TransferListing]) -> Markup {
+    if transfers.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        div class="section" {
+            h2 { "Pending Transfers" }
+            table class="data" {
+                thead {
+                    tr {
+                        th { "Snake" }
+                        th { "From" }
+                        th { "To" }
+                        th class="hide-sm" { "History" }
+                        th class="r" {}
+                    }
+                }
+                tbody {
+                    @for t in transfers {
+                        tr {
+                            td {
+                                a class="name" href={"/battlesnakes/"(t.battlesnake_id)"/profile"} { (t.snake_name) }
+                            }
+                            td { (t.from_login) }
+                            td { (t.to_login) }
+                            td class="hide-sm" {
+                                @if t.include_history { "Kept" } @else { "Reset" }
+                            }
+                            td class="r" {
+                                div class="reg-actions" {
+                                    @if t.to_user_id == user_id {
+                                        form action={"/transfers/"(t.battlesnake_transfer_id)"/accept"} method="post" {
+                                            button type="submit" class="btn sm solid" { "Accept" }
+                                        }
+                                        form action={"/transfers/"(t.battlesnake_transfer_id)"/decline"} method="post" {
+                                            button type="submit" class="btn sm" { "Decline" }
+                                        }
+                                    } @else {
+                                        form action={"/transfers/"(t.battlesnake_transfer_id)"/cancel"} method="post" {
+                                            button type="submit" class="btn sm danger" { "Cancel" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// POST /battlesnakes/{id}/transfer — offer the snake to another user
+pub async fn offer_transfer(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(battlesnake_id): Path<Uuid>,
+    Form(form): Form<TransferForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let owns = battlesnake::belongs_to_user(&state.db, battlesnake_id, user.user_id)
+        .await
+        .wrap_err("Failed to check battlesnake ownership")?;
+    if !owns {
+        return Err(
+            "Battlesnake not found or you don't have permission to transfer it".to_string(),
+        )
+        .with_status(StatusCode::FORBIDDEN);
+    }
+
+    let (message, flash_type) = match battlesnake_transfer::offer_transfer(
+        &state.db,
+        battlesnake_id,
+        user.user_id,
+        &form.to_login,
+        form.include_history,
+    )
+    .await?
+    {
+        Ok(_) => (
+            format!(
+                "Transfer offered to {}. The snake moves once they accept.",
+                form.to_login.trim()
+            ),
+            session::FLASH_TYPE_SUCCESS,
+        ),
+        Err(message) => (message, session::FLASH_TYPE_ERROR),
+    };
+    flash_redirect(
+        &state,
+        session.session_id,
+        message,
+        flash_type,
+        &format!("/battlesnakes/{battlesnake_id}/edit"),
+    )
+    .await
+}
+
+/// POST /transfers/{id}/accept — the recipient takes the snake
+pub async fn accept_transfer(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(transfer_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let (message, flash_type) =
+        match battlesnake_transfer::accept_transfer(&state.db, transfer_id, user.user_id).await? {
+            Ok(()) => {
+                tracing::info!(
+                    battlesnake_transfer_id = %transfer_id,
+                    user_id = %user.user_id,
+                    "Battlesnake transfer accepted"
+                );
+                (
+                    "The snake is yours now.".to_string(),
+                    session::FLASH_TYPE_SUCCESS,
+                )
+            }
+            Err(message) => (message, session::FLASH_TYPE_ERROR),
+        };
+    flash_redirect(
+        &state,
+        session.session_id,
+        message,
+        flash_type,
+        "/battlesnakes",
+    )
+    .await
+}
+
+/// POST /transfers/{id}/decline — the recipient turns the snake down
+pub async fn decline_transfer(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(transfer_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    respond(
+        &state,
+        session.session_id,
+        transfer_id,
+        |t| t.to_user_id == user.user_id,
+        TransferStatus::Declined,
+    )
+    .await
+}
+
+/// POST /transfers/{id}/cancel — the owner withdraws an offer that hasn't
+/// been answered
+pub async fn cancel_transfer(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(transfer_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    respond(
+        &state,
+        session.session_id,
+        transfer_id,
+        |t| t.from_user_id == user.user_id,
+        TransferStatus::Canceled,
+    )
+    .await
+}
+
+/// Shared tail of decline and cancel: 404 unless `allowed`, then move the
+/// transfer out of pending.
+async fn respond(
+    state: &AppState,
+    session_id: Uuid,
+    transfer_id: Uuid,
+    allowed: impl FnOnce(&TransferListing) -> bool,
+    status: TransferStatus,
+) -> ServerResult<Response, StatusCode> {
+    battlesnake_transfer::get_transfer_by_id(&state.db, transfer_id)
+        .await?
+        .filter(|t| allowed(t))
+        .ok_or_else(|| "Transfer not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    let (message, flash_type) =
+        if battlesnake_transfer::resolve_transfer(&state.db, transfer_id, status).await? {
+            (
+                format!("Transfer {}", status.as_str()),
+                session::FLASH_TYPE_SUCCESS,
+            )
+        } else {
+            (
+                "This transfer was already answered".to_string(),
+                session::FLASH_TYPE_ERROR,
+            )
+        };
+    flash_redirect(state, session_id, message, flash_type, "/battlesnakes").await
+}
@@ -0,0 +1,740 @@
+//! Challenges: pit one of your snakes against someone else's in an unranked
+//! game. A public opponent is played right away; a private one waits in its
+//! owner's inbox until they accept (which starts the game) or decline.
+
+use axum::{
+    Form,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    errors::{ServerResult, WithStatus},
+    jobs::GameRunnerJob,
+    models::{
+        battlesnake::{self, Visibility},
+        challenge::{self, ChallengeListing, ChallengeStatus, CreateChallenge},
+        game::{self, CreateGameWithSnakes, Game},
+        rate_limit, session,
+        user::User,
+    },
+    routes::{
+        auth::{CurrentUser, CurrentUserWithSession},
+        tournament::{parse_board_size, parse_game_type},
+    },
+    state::AppState,
+};
+
+/// Challenges shown on the inbox page.
+const INBOX_LIMIT: i64 = 50;
+
+/// Pending challenges one user can have waiting on others at a time, so an
+/// inbox can't be flooded.
+const MAX_PENDING_SENT: usize = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct NewChallengeQuery {
+    pub opponent: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChallengeForm {
+    pub battlesnake_id: Uuid,
+    pub opponent_battlesnake_id: Uuid,
+    pub board_size: String,
+    pub game_type: String,
+}
+
+/// Why `user` can't start a game right now, if they can't: a suspended
+/// account, maintenance mode, or the per-account game-creation limit. The
+/// attempt is recorded against that limit (shared with the web builder and
+/// API) before it's checked, under `source`.
+pub(crate) async fn game_creation_refusal(
+    state: &AppState,
+    user: &User,
+    source: &str,
+) -> cja::Result<Option<String>> {
+    if user.is_suspended() {
+        return Ok(Some(
+            "Your account is suspended, so you can't start games.".to_string(),
+        ));
+    }
+    if state.maintenance().await.enabled {
+        return Ok(Some(
+            "Arena is in maintenance mode, so new games are paused. Please try again later."
+                .to_string(),
+        ));
+    }
+
+    let limit = state.config.game_creation_rate_limit;
+    let window_minutes = state.config.game_creation_rate_limit_window_minutes;
+    let attempts = rate_limit::record_and_count_game_creation_attempts(
+        &state.db,
+        user.user_id,
+        source,
+        window_minutes,
+    )
+    .await
+    .wrap_err("Failed to record game creation attempt")?;
+    if attempts > limit {
+        tracing::warn!(
+            event_type = "game_creation_rate_limited",
+            user_id = %user.user_id,
+            attempts = attempts,
+            limit = limit,
+            source = source,
+            "game creation rate limited"
+        );
+        return Ok(Some(format!(
+            "You're creating games too fast — max {limit} games per {window_minutes} minutes."
+        )));
+    }
+    Ok(None)
+}
+
+/// Queue a challenge game that was just committed, like every other game
+/// source does once its rows exist.
+async fn enqueue_game(state: &AppState, game: &Game, user_id: Uuid) -> cja::Result<()> {
+    game::set_game_enqueued_at(&state.db, game.game_id, chrono::Utc::now())
+        .await
+        .wrap_err("Failed to set enqueued_at")?;
+    cja::jobs::Job::enqueue(
+        GameRunnerJob {
+            game_id: game.game_id,
+        },
+        state.clone(),
+        format!("Game {} created via challenge", game.game_id),
+        None,
+    )
+    .await
+    .wrap_err("Failed to enqueue game runner job")?;
+
+    crate::events::game_created(
+        game.game_id,
+        &game.board_size,
+        &game.game_type,
+        2,
+        crate::events::GameSource::Challenge,
+        Some(user_id),
+    );
+    Ok(())
+}
+
+async fn flash_redirect(
+    state: &AppState,
+    session_id: Uuid,
+    message: String,
+    flash_type: &str,
+    to: &str,
+) -> ServerResult<Response, StatusCode> {
+    session::set_flash_message(&state.db, session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to(to).into_response())
+}
+
+/// Accept a challenge against its locked row: only its opponent can, only
+/// while it's pending, and the game is created in the same transaction that
+/// marks it accepted. Returns `Ok(Err(message))` for user-facing refusals.
+async fn accept_challenge_in_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    challenge_id: Uuid,
+    user_id: Uuid,
+) -> cja::Result<Result<Game, String>> {
+    let Some(c) = challenge::get_challenge_for_update(tx, challenge_id).await? else {
+        return Ok(Err("Challenge not found".to_string()));
+    };
+    if c.opponent_user_id != user_id {
+        return Ok(Err("Challenge not found".to_string()));
+    }
+    if c.status != ChallengeStatus::Pending {
+        return Ok(Err(format!(
+            "This challenge was already {}",
+            c.status.as_str()
+        )));
+    }
+
+    let game = game::create_game_with_snakes_tx(
+        tx,
+        CreateGameWithSnakes {
+            board_size: c.board_size,
+            game_type: c.game_type,
+            battlesnake_ids: vec![c.challenger_battlesnake_id, c.opponent_battlesnake_id],
+            run_tag: None,
+        },
+    )
+    .await
+    .wrap_err("Failed to create challenge game")?;
+    challenge::resolve_challenge(
+        &mut **tx,
+        challenge_id,
+        ChallengeStatus::Accepted,
+        Some(game.game_id),
+    )
+    .await?;
+
+    Ok(Ok(game))
+}
+
+/// GET /challenges — incoming challenges to answer, outgoing ones still
+/// waiting, and recent history.
+pub async fn inbox(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let challenges = challenge::list_challenges_for_user(&state.db, user.user_id, INBOX_LIMIT)
+        .await
+        .wrap_err("Failed to list challenges")?;
+    let my_snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch user's battlesnakes")?;
+
+    let (pending, history): (Vec<&ChallengeListing>, Vec<&ChallengeListing>) = challenges
+        .iter()
+        .partition(|c| c.status == ChallengeStatus::Pending);
+    let incoming: Vec<&ChallengeListing> = pending
+        .iter()
+        .copied()
+        .filter(|c| c.opponent_user_id == user.user_id)
+        .collect();
+    let outgoing: Vec<&ChallengeListing> = pending
+        .iter()
+        .copied()
+        .filter(|c| c.challenger_user_id == user.user_id)
+        .collect();
+
+    Ok(page_factory.create_page(
+        "Challenges".to_string(),
+        Box::new(html! {
+            div class="page-head" {
+                div {
+                    h1 { "Challenges" }
+                    div class="sub" {
+                        "Unranked head-to-head games. Open any snake's profile and press "
+                        "Challenge; private snakes have to accept first."
+                    }
+                }
+            }
+
+            div class="section" {
+                h2 { "Waiting on you" }
+                @if incoming.is_empty() {
+                    p class="empty" { "No challenges to answer." }
+                } @else {
+                    table class="data" {
+                        thead {
+                            tr {
+                                th { "From" }
+                                th { "Against your snake" }
+                                th class="hide-sm" { "Game" }
+                                th class="r" { "Answer" }
+                            }
+                        }
+                        tbody {
+                            @for c in &incoming {
+                                tr {
+                                    td {
+                                        a class="name" href={"/battlesnakes/"(c.challenger_battlesnake_id)"/profile"} { (c.challenger_snake_name) }
+                                        span class="owner" { " by " (c.challenger_login) }
+                                    }
+                                    td { (c.opponent_snake_name) }
+                                    td class="hide-sm" { (c.game_type) " · " (c.board_size) }
+                                    td class="r" {
+                                        div class="reg-actions" {
+                                            form action={"/challenges/"(c.challenge_id)"/accept"} method="post" {
+                                                button type="submit" class="btn sm solid" { "Accept" }
+                                            }
+                                            form action={"/challenges/"(c.challenge_id)"/decline"} method="post" {
+                                                button type="submit" class="btn sm" { "Decline" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div class="section" {
+                h2 { "Sent" }
+                @if outgoing.is_empty() {
+                    p class="empty" {
+                        @if my_snakes.is_empty() {
+                            a href="/battlesnakes/new" { "Create a snake" } " to start challenging others."
+                        } @else {
+                            "No challenges waiting on anyone."
+                        }
+                    }
+                } @else {
+                    table class="data" {
+                        thead {
+                            tr {
+                                th { "Your snake" }
+                                th { "Against" }
+                                th class="hide-sm" { "Game" }
+                                th class="r" {}
+                            }
+                        }
+                        tbody {
+                            @for c in &outgoing {
+                                tr {
+                                    td { (c.challenger_snake_name) }
+                                    td {
+                                        a class="name" href={"/battlesnakes/"(c.opponent_battlesnake_id)"/profile"} { (c.opponent_snake_name) }
+                                        span class="owner" { " by " (c.opponent_login) }
+                                    }
+                                    td class="hide-sm" { (c.game_type) " · " (c.board_size) }
+                                    td class="r" {
+                                        form action={"/challenges/"(c.challenge_id)"/cancel"} method="post" {
+                                            button type="submit" class="btn sm danger" { "Withdraw" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            @if !history.is_empty() {
+                div class="section" {
+                    h2 { "History" }
+                    table class="data" {
+                        thead {
+                            tr {
+                                th { "Challenger" }
+                                th { "Opponent" }
+                                th { "Result" }
+                                th class="r hide-sm" { "When" }
+                            }
+                        }
+                        tbody {
+                            @for c in &history {
+                                tr {
+                                    td { (c.challenger_snake_name) span class="owner" { " by " (c.challenger_login) } }
+                                    td { (c.opponent_snake_name) span class="owner" { " by " (c.opponent_login) } }
+                                    td {
+                                        @if let Some(game_id) = c.game_id {
+                                            a href={"/games/"(game_id)} { "View game" }
+                                        } @else {
+                                            (c.status.as_str())
+                                        }
+                                    }
+                                    td class="r num hide-sm" { (c.created_at.format("%b %-d, %Y")) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// GET /challenges/new?opponent={snake_id} — pick your snake and the game
+/// settings for a challenge.
+pub async fn new_challenge(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Query(query): Query<NewChallengeQuery>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let opponent = battlesnake::get_battlesnake_by_id(&state.db, query.opponent)
+        .await
+        .wrap_err("Failed to fetch opponent snake")?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    if opponent.user_id == user.user_id {
+        return Err("You can't challenge your own snake".to_string())
+            .with_status(StatusCode::BAD_REQUEST);
+    }
+    let my_snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch user's battlesnakes")?;
+    let is_private = opponent.visibility == Visibility::Private;
+
+    Ok(page_factory.create_page(
+        format!("Challenge {}", opponent.name),
+        Box::new(html! {
+            div class="crumb" {
+                a href="/challenges" { "Challenges" }
+                " / New"
+            }
+            div class="page-head" {
+                div {
+                    h1 { "Challenge " (opponent.name) }
+                    div class="sub" {
+                        @if is_private {
+                            "This snake is private, so its owner has to accept before the game starts."
+                        } @else {
+                            "The game starts as soon as you send the challenge. It doesn't affect any rankings."
+                        }
+                    }
+                }
+            }
+
+            @if my_snakes.is_empty() {
+                p class="empty" {
+                    "You need a snake to challenge with. "
+                    a href="/battlesnakes/new" { "Create one" }
+                }
+            } @else {
+                form class="form-stack" action="/challenges" method="post" {
+                    input type="hidden" name="opponent_battlesnake_id" value=(opponent.battlesnake_id);
+
+                    div class="field" {
+                        label for="battlesnake_id" { "Your Snake" }
+                        select id="battlesnake_id" name="battlesnake_id" required {
+                            @for snake in &my_snakes {
+                                option value=(snake.battlesnake_id) { (snake.name) }
+                            }
+                        }
+                    }
+
+                    div class="field" {
+                        label for="game_type" { "Game Type" }
+                        select id="game_type" name="game_type" required {
+                            option value="Standard" selected { "Standard" }
+                            option value="Royale" { "Royale" }
+                            option value="Constrictor" { "Constrictor" }
+                            option value="Snail Mode" { "Snail Mode" }
+                        }
+                    }
+
+                    div class="field" {
+                        label for="board_size" { "Board Size" }
+                        select id="board_size" name="board_size" required {
+                            option value="7x7" { "7x7 (Small)" }
+                            option value="11x11" selected { "11x11 (Medium)" }
+                            option value="19x19" { "19x19 (Large)" }
+                        }
+                    }
+
+                    div class="form-cta" {
+                        button type="submit" class="btn solid" {
+                            @if is_private { "Send Challenge" } @else { "Start Game" }
+                        }
+                        a href={"/battlesnakes/"(opponent.battlesnake_id)"/profile"} class="btn" { "Cancel" }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /challenges — challenge another user's snake. Public snakes are
+/// played immediately; private ones get a pending challenge.
+pub async fn create_challenge(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Form(form): Form<ChallengeForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let back = format!("/challenges/new?opponent={}", form.opponent_battlesnake_id);
+
+    let mine = battlesnake::get_battlesnake_by_id(&state.db, form.battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch battlesnake")?
+        .filter(|s| s.user_id == user.user_id)
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let opponent = battlesnake::get_battlesnake_by_id(&state.db, form.opponent_battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch opponent snake")?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    if opponent.user_id == user.user_id {
+        return Err("You can't challenge your own snake".to_string())
+            .with_status(StatusCode::BAD_REQUEST);
+    }
+
+    let settings = parse_game_type(&form.game_type)
+        .and_then(|game_type| Ok((game_type, parse_board_size(&form.board_size)?)));
+    let (game_type, board_size) = match settings {
+        Ok(settings) => settings,
+        Err(message) => {
+            return flash_redirect(
+                &state,
+                session.session_id,
+                message,
+                session::FLASH_TYPE_ERROR,
+                &back,
+            )
+            .await;
+        }
+    };
+    let params = CreateChallenge {
+        challenger_user_id: user.user_id,
+        challenger_battlesnake_id: mine.battlesnake_id,
+        opponent_user_id: opponent.user_id,
+        opponent_battlesnake_id: opponent.battlesnake_id,
+        board_size,
+        game_type,
+        status: ChallengeStatus::Pending,
+        game_id: None,
+    };
+
+    if opponent.visibility == Visibility::Private {
+        let waiting = challenge::list_challenges_for_user(&state.db, user.user_id, INBOX_LIMIT)
+            .await
+            .wrap_err("Failed to list challenges")?
+            .into_iter()
+            .filter(|c| {
+                c.status == ChallengeStatus::Pending && c.challenger_user_id == user.user_id
+            })
+            .count();
+        if waiting >= MAX_PENDING_SENT {
+            return flash_redirect(
+                &state,
+                session.session_id,
+                format!(
+                    "You already have {MAX_PENDING_SENT} challenges waiting for an answer. \
+                     Withdraw one or wait for a reply."
+                ),
+                session::FLASH_TYPE_ERROR,
+                "/challenges",
+            )
+            .await;
+        }
+
+        challenge::create_challenge(&state.db, params).await?;
+        return flash_redirect(
+            &state,
+            session.session_id,
+            format!("Challenge sent to {}'s owner", opponent.name),
+            session::FLASH_TYPE_SUCCESS,
+            "/challenges",
+        )
+        .await;
+    }
+
+    if let Some(message) = game_creation_refusal(&state, &user, "challenge").await? {
+        return flash_redirect(
+            &state,
+            session.session_id,
+            message,
+            session::FLASH_TYPE_ERROR,
+            &back,
+        )
+        .await;
+    }
+
+    // The game and its (already accepted) challenge commit together, so the
+    // history never points at a game that doesn't exist or misses one that
+    // does.
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .wrap_err("Failed to begin challenge transaction")?;
+    let game = game::create_game_with_snakes_tx(
+        &mut tx,
+        CreateGameWithSnakes {
+            board_size: params.board_size.clone(),
+            game_type: params.game_type.clone(),
+            battlesnake_ids: vec![mine.battlesnake_id, opponent.battlesnake_id],
+            run_tag: None,
+        },
+    )
+    .await
+    .wrap_err("Failed to create challenge game")?;
+    challenge::create_challenge(
+        &mut *tx,
+        CreateChallenge {
+            status: ChallengeStatus::Accepted,
+            game_id: Some(game.game_id),
+            ..params
+        },
+    )
+    .await?;
+    tx.commit()
+        .await
+        .wrap_err("Failed to commit challenge transaction")?;
+
+    enqueue_game(&state, &game, user.user_id).await?;
+    Ok(Redirect::to(&format!("/games/{}", game.game_id)).into_response())
+}
+
+/// POST /challenges/{id}/accept — the opponent accepts and the game starts.
+pub async fn accept_challenge(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(challenge_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    if let Some(message) = game_creation_refusal(&state, &user, "challenge").await? {
+        return flash_redirect(
+            &state,
+            session.session_id,
+            message,
+            session::FLASH_TYPE_ERROR,
+            "/challenges",
+        )
+        .await;
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .wrap_err("Failed to begin accept transaction")?;
+    match accept_challenge_in_tx(&mut tx, challenge_id, user.user_id).await? {
+        Err(message) => {
+            drop(tx);
+            flash_redirect(
+                &state,
+                session.session_id,
+                message,
+                session::FLASH_TYPE_ERROR,
+                "/challenges",
+            )
+            .await
+        }
+        Ok(game) => {
+            tx.commit()
+                .await
+                .wrap_err("Failed to commit accept transaction")?;
+            enqueue_game(&state, &game, user.user_id).await?;
+            Ok(Redirect::to(&format!("/games/{}", game.game_id)).into_response())
+        }
+    }
+}
+
+/// POST /challenges/{id}/decline — the opponent turns a challenge down.
+pub async fn decline_challenge(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(challenge_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    respond(
+        &state,
+        session.session_id,
+        challenge_id,
+        |c| c.opponent_user_id == user.user_id,
+        ChallengeStatus::Declined,
+    )
+    .await
+}
+
+/// POST /challenges/{id}/cancel — the challenger withdraws a challenge that
+/// hasn't been answered.
+pub async fn cancel_challenge(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(challenge_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    respond(
+        &state,
+        session.session_id,
+        challenge_id,
+        |c| c.challenger_user_id == user.user_id,
+        ChallengeStatus::Canceled,
+    )
+    .await
+}
+
+/// Shared tail of decline and cancel: 404 unless `allowed`, then move the
+/// challenge out of pending.
+async fn respond(
+    state: &AppState,
+    session_id: Uuid,
+    challenge_id: Uuid,
+    allowed: impl FnOnce(&challenge::Challenge) -> bool,
+    status: ChallengeStatus,
+) -> ServerResult<Response, StatusCode> {
+    challenge::get_challenge_by_id(&state.db, challenge_id)
+        .await?
+        .filter(|c| allowed(c))
+        .ok_or_else(|| "Challenge not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    let (message, flash_type) =
+        if challenge::resolve_challenge(&state.db, challenge_id, status, None).await? {
+            (
+                format!("Challenge {}", status.as_str()),
+                session::FLASH_TYPE_SUCCESS,
+            )
+        } else {
+            (
+                "This challenge was already answered".to_string(),
+                session::FLASH_TYPE_ERROR,
+            )
+        };
+    flash_redirect(state, session_id, message, flash_type, "/challenges").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::{GameBoardSize, GameType};
+    use crate::testing::fixture_user_with_snake;
+    use sqlx::PgPool;
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn only_the_opponent_accepts_and_only_once(pool: PgPool) -> cja::Result<()> {
+        let (challenger, challenger_snake) = fixture_user_with_snake(&pool, "challenger").await?;
+        let (opponent, opponent_snake) = fixture_user_with_snake(&pool, "opponent").await?;
+        let c = challenge::create_challenge(
+            &pool,
+            CreateChallenge {
+                challenger_user_id: challenger,
+                challenger_battlesnake_id: challenger_snake,
+                opponent_user_id: opponent,
+                opponent_battlesnake_id: opponent_snake,
+                board_size: GameBoardSize::Small,
+                game_type: GameType::Constrictor,
+                status: ChallengeStatus::Pending,
+                game_id: None,
+            },
+        )
+        .await?;
+
+        // The challenger can't accept on the opponent's behalf
+        let mut tx = pool.begin().await?;
+        assert!(
+            accept_challenge_in_tx(&mut tx, c.challenge_id, challenger)
+                .await?
+                .is_err()
+        );
+        drop(tx);
+
+        let mut tx = pool.begin().await?;
+        let game = accept_challenge_in_tx(&mut tx, c.challenge_id, opponent)
+            .await?
+            .expect("opponent can accept");
+        tx.commit().await?;
+        assert_eq!(game.board_size, GameBoardSize::Small);
+        assert_eq!(game.game_type, GameType::Constrictor);
+        let snakes: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT battlesnake_id FROM game_battlesnakes WHERE game_id = $1 ORDER BY battlesnake_id",
+        )
+        .bind(game.game_id)
+        .fetch_all(&pool)
+        .await?;
+        let mut expected = vec![challenger_snake, opponent_snake];
+        expected.sort();
+        assert_eq!(snakes, expected);
+
+        let reloaded = challenge::get_challenge_by_id(&pool, c.challenge_id)
+            .await?
+            .unwrap();
+        assert_eq!(reloaded.status, ChallengeStatus::Accepted);
+        assert_eq!(reloaded.game_id, Some(game.game_id));
+
+        // A second accept is refused rather than starting another game
+        let mut tx = pool.begin().await?;
+        let message = accept_challenge_in_tx(&mut tx, c.challenge_id, opponent)
+            .await?
+            .expect_err("already accepted");
+        assert!(message.contains("already accepted"), "{message}");
+
+        Ok(())
+    }
+}
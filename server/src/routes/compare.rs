@@ -0,0 +1,564 @@
+//! Head-to-head comparison of two snakes: their record in the leaderboard
+//! games they shared, their rating trajectories on one chart, and how each
+//! tends to die when they meet.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use color_eyre::eyre::Context as _;
+use maud::{Markup, html};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    customizations::chip_color,
+    errors::{ServerResult, WithStatus},
+    models::battlesnake::{self, Battlesnake},
+    models::head_to_head::{self, SharedGame},
+    models::leaderboard::{self, RatingPoint},
+    routes::api::games::deaths_from_frame,
+    state::AppState,
+};
+
+/// Shared games listed, and read back for causes of death. Each needs its
+/// final frame, which may have to come out of the archive.
+const RECENT_SHARED_GAMES: i64 = 20;
+
+/// Line colors for A and B on the rating chart
+const SERIES_COLORS: [&str; 2] = ["#ff3d8a", "#4a90d9"];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CompareParams {
+    snake_a: Option<String>,
+    snake_b: Option<String>,
+}
+
+/// Deaths of A and B across the shared games we could read frames for
+#[derive(Debug, Default, PartialEq)]
+struct DeathTally {
+    games: u32,
+    /// Engine cause -> (A's deaths, B's deaths)
+    causes: BTreeMap<String, (u32, u32)>,
+    /// Deaths where the other snake did the eliminating
+    a_by_b: u32,
+    b_by_a: u32,
+}
+
+impl DeathTally {
+    /// Count one game's deaths, keyed by frame snake ID as
+    /// [`deaths_from_frame`] returns them
+    fn add(&mut self, game: &SharedGame, deaths: &HashMap<String, (String, i32, String)>) {
+        self.games += 1;
+        let a = game.game_battlesnake_a.map(|id| id.to_string());
+        let b = game.game_battlesnake_b.map(|id| id.to_string());
+
+        if let Some(a) = &a
+            && let Some((cause, _, by)) = deaths.get(a)
+        {
+            self.causes.entry(cause.clone()).or_default().0 += 1;
+            if b.as_ref() == Some(by) {
+                self.a_by_b += 1;
+            }
+        }
+        if let Some(b) = &b
+            && let Some((cause, _, by)) = deaths.get(b)
+        {
+            self.causes.entry(cause.clone()).or_default().1 += 1;
+            if a.as_ref() == Some(by) {
+                self.b_by_a += 1;
+            }
+        }
+    }
+
+    /// Causes with the most deaths between them first
+    fn ranked_causes(&self) -> Vec<(&str, u32, u32)> {
+        let mut causes: Vec<_> = self
+            .causes
+            .iter()
+            .map(|(cause, (a, b))| (cause.as_str(), *a, *b))
+            .collect();
+        causes.sort_by_key(|(_, a, b)| std::cmp::Reverse(a + b));
+        causes
+    }
+}
+
+fn death_cause_label(cause: &str) -> &str {
+    match cause {
+        "out-of-health" => "Starved",
+        "wall-collision" => "Hit a wall",
+        "self-collision" => "Ran into itself",
+        "snake-collision" => "Ran into a snake",
+        "head-collision" => "Lost a head-to-head",
+        "hazard" => "Hazard",
+        other => other,
+    }
+}
+
+fn percent(part: i64, whole: i64) -> String {
+    if whole == 0 {
+        "–".to_string()
+    } else {
+        format!("{:.0}%", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// SVG polyline points for each series on a shared time and score scale,
+/// in the entry page's 620x220 chart frame, with the y-axis labels. `None`
+/// unless some series has at least two points.
+fn chart_lines(series: &[&[RatingPoint]]) -> Option<(Vec<String>, Vec<(String, String)>)> {
+    if series.iter().all(|points| points.len() < 2) {
+        return None;
+    }
+    let all = || series.iter().flat_map(|points| points.iter());
+
+    let min_score = all()
+        .map(|p| p.display_score_after)
+        .fold(f64::INFINITY, f64::min);
+    let max_score = all()
+        .map(|p| p.display_score_after)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let score_range = max_score - min_score;
+    let padding = if score_range < 0.01 {
+        1.0
+    } else {
+        score_range * 0.1
+    };
+    let y_min = min_score - padding;
+    let y_max = max_score + padding;
+
+    let first_ts = all().map(|p| p.game_created_at.timestamp()).min()? as f64;
+    let last_ts = all().map(|p| p.game_created_at.timestamp()).max()? as f64;
+    let ts_range = if (last_ts - first_ts).abs() < 1.0 {
+        1.0
+    } else {
+        last_ts - first_ts
+    };
+
+    let lines = series
+        .iter()
+        .map(|points| {
+            points
+                .iter()
+                .map(|p| {
+                    let x =
+                        40.0 + (p.game_created_at.timestamp() as f64 - first_ts) / ts_range * 560.0;
+                    let y = 210.0 - (p.display_score_after - y_min) / (y_max - y_min) * 200.0;
+                    format!("{x:.0},{y:.0}")
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+
+    let grid_count = 4;
+    let labels = (0..=grid_count)
+        .map(|i| {
+            let score = y_max - (i as f64 / grid_count as f64) * (y_max - y_min);
+            let y_pos = format!("{:.0}", 10.0 + (i as f64 / grid_count as f64) * 200.0 + 4.0);
+            (format!("{score:.0}"), y_pos)
+        })
+        .collect();
+
+    Some((lines, labels))
+}
+
+fn rating_chart(series: &[&[RatingPoint]]) -> Markup {
+    let Some((lines, labels)) = chart_lines(series) else {
+        return html! { p class="empty" { "Not enough rated games for a chart yet." } };
+    };
+    html! {
+        svg width="100%" viewBox="0 0 620 220" role="img" aria-label="Rating over time" {
+            @for i in 0..=4 {
+                @let y = 10 + i * 50;
+                line x1="40" y1=(y) x2="600" y2=(y) stroke="currentColor" stroke-opacity="0.12" stroke-width="1" {}
+            }
+            @for (line, color) in lines.iter().zip(SERIES_COLORS) {
+                polyline points=(line) fill="none" stroke=(color) stroke-width="2" {}
+            }
+            @for (label, y_pos) in &labels {
+                text x="35" y=(y_pos) text-anchor="end" font-size="11" fill="currentColor" fill-opacity="0.6" { (label) }
+            }
+        }
+    }
+}
+
+fn snake_label(snake: &Battlesnake, series_color: &str) -> Markup {
+    html! {
+        span class="snake-cell" {
+            span class="chip" style={"background:"(chip_color(&snake.color))} {}
+            a class="name" href={"/battlesnakes/"(snake.battlesnake_id)"/profile"} style={"color:"(series_color)} {
+                (snake.name)
+            }
+        }
+    }
+}
+
+async fn find_snake(state: &AppState, id: &str) -> ServerResult<Battlesnake, StatusCode> {
+    let snake = match id.parse::<Uuid>() {
+        Ok(battlesnake_id) => battlesnake::get_battlesnake_by_id(&state.read_db, battlesnake_id)
+            .await
+            .wrap_err("Failed to fetch battlesnake")?,
+        Err(_) => None,
+    };
+    snake
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)
+}
+
+/// GET /compare?snake_a=&snake_b= - Head-to-head comparison of two snakes.
+///
+/// Without two different snakes there's nothing to compare, so this sends
+/// the visitor to the directory to pick the other one.
+pub async fn compare_snakes(
+    State(state): State<AppState>,
+    Query(params): Query<CompareParams>,
+    page_factory: PageFactory,
+) -> ServerResult<Response, StatusCode> {
+    let (Some(a_id), Some(b_id)) = (params.snake_a.as_deref(), params.snake_b.as_deref()) else {
+        let vs = params.snake_a.or(params.snake_b);
+        return Ok(match vs.and_then(|id| id.parse::<Uuid>().ok()) {
+            Some(id) => Redirect::to(&format!("/snakes?vs={id}")),
+            None => Redirect::to("/snakes"),
+        }
+        .into_response());
+    };
+    let a = find_snake(&state, a_id).await?;
+    let b = find_snake(&state, b_id).await?;
+    if a.battlesnake_id == b.battlesnake_id {
+        return Ok(Redirect::to(&format!("/snakes?vs={}", a.battlesnake_id)).into_response());
+    }
+
+    let summaries = head_to_head::get_summaries(&state.read_db, a.battlesnake_id, b.battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch head-to-head records")?;
+    let recent = head_to_head::get_recent_shared_games(
+        &state.read_db,
+        a.battlesnake_id,
+        b.battlesnake_id,
+        RECENT_SHARED_GAMES,
+    )
+    .await
+    .wrap_err("Failed to fetch shared games")?;
+
+    // Chart the leaderboard they've met on most, or failing that any
+    // leaderboard they're both on
+    let a_entries = leaderboard::get_entries_for_battlesnake(&state.read_db, a.battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard entries")?;
+    let b_entries = leaderboard::get_entries_for_battlesnake(&state.read_db, b.battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard entries")?;
+    let chart_entries = summaries
+        .iter()
+        .map(|s| s.leaderboard_id)
+        .chain(a_entries.iter().map(|e| e.leaderboard_id))
+        .find_map(|leaderboard_id| {
+            let a_entry = a_entries
+                .iter()
+                .find(|e| e.leaderboard_id == leaderboard_id)?;
+            let b_entry = b_entries
+                .iter()
+                .find(|e| e.leaderboard_id == leaderboard_id)?;
+            Some((a_entry, b_entry))
+        });
+    let chart = match chart_entries {
+        Some((a_entry, b_entry)) => {
+            let a_points = leaderboard::get_rating_history_for_entry(
+                &state.read_db,
+                a_entry.leaderboard_entry_id,
+            )
+            .await
+            .wrap_err("Failed to fetch rating history")?;
+            let b_points = leaderboard::get_rating_history_for_entry(
+                &state.read_db,
+                b_entry.leaderboard_entry_id,
+            )
+            .await
+            .wrap_err("Failed to fetch rating history")?;
+            Some((a_entry, b_entry, rating_chart(&[&a_points, &b_points])))
+        }
+        None => None,
+    };
+
+    // A game whose frames can't be read just drops out of the tally
+    let mut deaths = DeathTally::default();
+    for game in &recent {
+        match crate::frame_archive::load_last_frame(&state, game.game_id).await {
+            Ok(Some((_, frame))) => deaths.add(game, &deaths_from_frame(&frame)),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(game_id = %game.game_id, error = ?e, "Failed to load final frame for comparison");
+            }
+        }
+    }
+
+    let leaderboard_names: HashMap<Uuid, &str> = a_entries
+        .iter()
+        .map(|e| (e.leaderboard_id, e.leaderboard_name.as_str()))
+        .collect();
+    let [a_color, b_color] = SERIES_COLORS;
+
+    Ok(page_factory
+        .create_page(
+            format!("{} vs {}", a.name, b.name),
+            Box::new(html! {
+                div class="page-head" {
+                    h1 { (a.name) " vs " (b.name) }
+                    div class="sub" {
+                        "Head to head in the leaderboard games they've both played. "
+                        a href={"/compare?snake_a="(b.battlesnake_id)"&snake_b="(a.battlesnake_id)} { "Swap" }
+                        " · "
+                        a href={"/snakes?vs="(a.battlesnake_id)} { "Compare " (a.name) " with another snake" }
+                    }
+                }
+
+                section class="section" {
+                    h2 { "Record" }
+                    @if summaries.is_empty() {
+                        p class="empty" { "These snakes haven't met in a leaderboard game yet." }
+                    } @else {
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { "Leaderboard" }
+                                    th class="r" { "Games" }
+                                    th class="r" { (a.name) " ahead" }
+                                    th class="r" { (b.name) " ahead" }
+                                    th class="r hide-sm" { (a.name) " wins" }
+                                    th class="r hide-sm" { (b.name) " wins" }
+                                }
+                            }
+                            tbody {
+                                @for s in &summaries {
+                                    tr {
+                                        td { a href={"/leaderboards/"(s.leaderboard_id)} { (s.leaderboard_name) } }
+                                        td class="r num" { (s.games) }
+                                        td class="r num" { (s.a_ahead) " (" (percent(s.a_ahead, s.games)) ")" }
+                                        td class="r num" { (s.b_ahead) " (" (percent(s.b_ahead, s.games)) ")" }
+                                        td class="r num hide-sm" { (s.a_wins) " (" (percent(s.a_wins, s.games)) ")" }
+                                        td class="r num hide-sm" { (s.b_wins) " (" (percent(s.b_wins, s.games)) ")" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                section class="section" {
+                    h2 { "Rating Trajectory" }
+                    @if let Some((a_entry, b_entry, chart)) = &chart {
+                        p class="sub" {
+                            (snake_label(&a, a_color)) " " (format!("{:.1}", a_entry.display_score))
+                            " · "
+                            (snake_label(&b, b_color)) " " (format!("{:.1}", b_entry.display_score))
+                            " on " (a_entry.leaderboard_name)
+                        }
+                        (chart)
+                    } @else {
+                        p class="empty" { "These snakes aren't on a leaderboard together." }
+                    }
+                }
+
+                section class="section" {
+                    h2 { "Causes of Death" }
+                    @if deaths.games == 0 {
+                        p class="empty" { "No shared games to read yet." }
+                    } @else {
+                        p class="sub" { "Across their last " (deaths.games) " shared games." }
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { "Cause" }
+                                    th class="r" { (a.name) }
+                                    th class="r" { (b.name) }
+                                }
+                            }
+                            tbody {
+                                @for (cause, a_count, b_count) in deaths.ranked_causes() {
+                                    tr {
+                                        td { (death_cause_label(cause)) }
+                                        td class="r num" { (a_count) }
+                                        td class="r num" { (b_count) }
+                                    }
+                                }
+                                tr {
+                                    td { "Eliminated by the other" }
+                                    td class="r num" { (deaths.a_by_b) }
+                                    td class="r num" { (deaths.b_by_a) }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                @if !recent.is_empty() {
+                    section class="section" {
+                        h2 { "Recent Shared Games" }
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { "Game" }
+                                    th class="hide-sm" { "Leaderboard" }
+                                    th class="r" { (a.name) }
+                                    th class="r" { (b.name) }
+                                    th class="r" { "Played" }
+                                }
+                            }
+                            tbody {
+                                @for g in &recent {
+                                    tr {
+                                        td class="num" {
+                                            a href={"/games/"(g.game_id)} { (g.game_id.simple().to_string()[..8]) }
+                                        }
+                                        td class="hide-sm" {
+                                            (leaderboard_names.get(&g.leaderboard_id).copied().unwrap_or(""))
+                                        }
+                                        td class="r num" { "#" (g.placement_a) }
+                                        td class="r num" { "#" (g.placement_b) }
+                                        td class="r num" { (g.game_created_at.format("%Y-%m-%d %H:%M")) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }),
+        )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared_game(a: Uuid, b: Uuid) -> SharedGame {
+        SharedGame {
+            game_id: Uuid::new_v4(),
+            leaderboard_id: Uuid::nil(),
+            game_created_at: chrono::Utc::now(),
+            placement_a: 1,
+            placement_b: 2,
+            game_battlesnake_a: Some(a),
+            game_battlesnake_b: Some(b),
+        }
+    }
+
+    #[test]
+    fn death_tally_counts_causes_and_kills_by_the_other_snake() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let death = |cause: &str, by: Uuid| (cause.to_string(), 10, by.to_string());
+
+        let mut tally = DeathTally::default();
+        // B runs into A; A survives
+        tally.add(
+            &shared_game(a, b),
+            &HashMap::from([(b.to_string(), death("snake-collision", a))]),
+        );
+        // Both die: A to a third snake, B to the wall
+        tally.add(
+            &shared_game(a, b),
+            &HashMap::from([
+                (a.to_string(), death("head-collision", c)),
+                (
+                    b.to_string(),
+                    ("wall-collision".to_string(), 4, String::new()),
+                ),
+            ]),
+        );
+
+        assert_eq!(tally.games, 2);
+        assert_eq!((tally.a_by_b, tally.b_by_a), (0, 1));
+        assert_eq!(tally.causes["snake-collision"], (0, 1));
+        assert_eq!(tally.causes["head-collision"], (1, 0));
+        assert_eq!(tally.causes["wall-collision"], (0, 1));
+    }
+
+    #[test]
+    fn chart_lines_share_one_scale() {
+        let start = chrono::Utc::now();
+        let point = |hours: i64, score: f64| RatingPoint {
+            display_score_after: score,
+            game_created_at: start + chrono::Duration::hours(hours),
+        };
+        let a = [point(0, 10.0), point(2, 20.0)];
+        let b = [point(1, 15.0)];
+
+        let (lines, labels) = chart_lines(&[&a, &b]).unwrap();
+        // A spans the whole width and the padded score range; B's lone
+        // point sits halfway on both
+        assert_eq!(
+            lines,
+            vec!["40,193 600,27".to_string(), "320,110".to_string()]
+        );
+        assert_eq!(labels.first().unwrap().0, "21");
+
+        assert!(chart_lines(&[&b, &[]]).is_none());
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn compare_page_needs_two_snakes(pool: sqlx::PgPool) -> cja::Result<()> {
+        use tower::ServiceExt as _;
+
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES (1, 'owner', 'test-token') RETURNING user_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let mut snakes = Vec::new();
+        for name in ["a", "b"] {
+            let battlesnake_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO battlesnakes (user_id, name, url)
+                 VALUES ($1, $2, 'http://example.com') RETURNING battlesnake_id",
+            )
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&pool)
+            .await?;
+            snakes.push(battlesnake_id);
+        }
+
+        let app = crate::routes::routes(AppState::test_from_pool(pool));
+        let (a, b) = (snakes[0], snakes[1]);
+        for (uri, status) in [
+            (format!("/compare?snake_a={a}&snake_b={b}"), StatusCode::OK),
+            (format!("/compare?snake_a={a}"), StatusCode::SEE_OTHER),
+            (
+                format!("/compare?snake_a={a}&snake_b={a}"),
+                StatusCode::SEE_OTHER,
+            ),
+            (
+                format!("/compare?snake_a={a}&snake_b={}", Uuid::new_v4()),
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                format!("/compare?snake_a={a}&snake_b=not-a-uuid"),
+                StatusCode::NOT_FOUND,
+            ),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::extract::Request::get(&uri)
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status, "{uri}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn percent_handles_no_games() {
+        assert_eq!(percent(1, 3), "33%");
+        assert_eq!(percent(0, 0), "–");
+    }
+}
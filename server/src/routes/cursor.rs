@@ -0,0 +1,33 @@
+//! Opaque cursors for keyset pagination over `(created_at, id)`, newest
+//! first. A page ends with the cursor of its last row, and the next page
+//! starts strictly after it, so deep pages cost the same as the first.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use uuid::Uuid;
+
+/// Cursor for the row after which the next page starts
+pub fn encode(created_at: chrono::DateTime<chrono::Utc>, id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", created_at.timestamp_micros(), id))
+}
+
+pub fn decode(cursor: &str) -> Option<(chrono::DateTime<chrono::Utc>, Uuid)> {
+    let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+    let (micros, id) = decoded.split_once(':')?;
+    let created_at = chrono::DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+    Some((created_at, id.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let created_at = chrono::DateTime::from_timestamp_micros(1_767_225_600_123_456).unwrap();
+        let id = Uuid::new_v4();
+        assert_eq!(decode(&encode(created_at, id)), Some((created_at, id)));
+        assert_eq!(decode("not a cursor"), None);
+        assert_eq!(decode(&URL_SAFE_NO_PAD.encode("1:nope")), None);
+    }
+}
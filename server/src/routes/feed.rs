@@ -0,0 +1,262 @@
+//! Following snakes and leaderboards, and the feed of what they've been up
+//! to.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+};
+use color_eyre::eyre::Context as _;
+use maud::{Markup, html};
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    customizations::chip_color,
+    errors::{ServerResult, WithStatus},
+    models::{battlesnake, follow, leaderboard},
+    routes::{
+        auth::CurrentUser,
+        leaderboard::{fmt_ago, ordinal},
+    },
+    state::AppState,
+};
+
+/// Results shown on the feed
+const FEED_LIMIT: i64 = 50;
+
+/// Follow or unfollow toggle for the page at `path`, which takes
+/// `POST {path}/follow` and `POST {path}/unfollow`
+pub fn follow_button(path: &str, following: bool) -> Markup {
+    html! {
+        @if following {
+            form action={(path)"/unfollow"} method="post" {
+                button type="submit" class="btn" title="Stop showing this in your feed" { "Following" }
+            }
+        } @else {
+            form action={(path)"/follow"} method="post" {
+                button type="submit" class="btn" title="Show this in your feed" { "Follow" }
+            }
+        }
+    }
+}
+
+/// GET /feed — standings and recent results for what the user follows
+pub async fn show_feed(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let standings = follow::get_followed_standings(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch followed standings")?;
+    let leaderboards = follow::get_followed_leaderboards(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch followed leaderboards")?;
+    let activity = leaderboard::get_followed_activity_feed(&state.db, user.user_id, FEED_LIMIT)
+        .await
+        .wrap_err("Failed to fetch followed activity")?;
+
+    Ok(page_factory.create_page(
+        "Your Feed".to_string(),
+        Box::new(html! {
+            div class="page-head" {
+                h1 { "Your Feed" }
+                div class="sub" {
+                    "Results from the snakes you follow, and the winners on the leaderboards you follow. "
+                    "Press Follow on any snake profile or leaderboard to add it here."
+                }
+            }
+
+            @if standings.is_empty() && leaderboards.is_empty() {
+                p class="empty" {
+                    "You aren't following anything yet. Browse the "
+                    a href="/snakes" { "snake directory" } " or the "
+                    a href="/leaderboards" { "leaderboards" } " to find some."
+                }
+            } @else {
+                div class="section" {
+                    h2 { "Recent results" }
+                    @if activity.is_empty() {
+                        p class="empty" { "No ranked games yet." }
+                    } @else {
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { "Snake" }
+                                    th { "Leaderboard" }
+                                    th class="r" { "Place" }
+                                    th class="r" { "Rating" }
+                                    th class="r hide-sm" { "When" }
+                                }
+                            }
+                            tbody {
+                                @for event in &activity {
+                                    tr {
+                                        td {
+                                            div class="snake-cell" {
+                                                span class="chip" style={"background:" (chip_color(&event.snake_color))} {}
+                                                span {
+                                                    a class="name" href={"/leaderboards/"(event.leaderboard_id)"/entries/"(event.leaderboard_entry_id)} { (event.snake_name) }
+                                                    span class="owner" { "by " (event.owner_login) }
+                                                }
+                                            }
+                                        }
+                                        td { a href={"/leaderboards/"(event.leaderboard_id)} { (event.leaderboard_name) } }
+                                        td class="r" {
+                                            a href={"/games/"(event.game_id)} {
+                                                span class={"place p"(event.placement)} { (ordinal(event.placement)) }
+                                            }
+                                        }
+                                        td class="r" {
+                                            @if event.display_score_change >= 0.0 {
+                                                span class="delta up" { (format!("{:+.1}", event.display_score_change)) }
+                                            } @else {
+                                                span class="delta down" { (format!("{:+.1}", event.display_score_change)) }
+                                            }
+                                        }
+                                        td class="r num hide-sm" { (fmt_ago(event.created_at)) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                @if !standings.is_empty() {
+                    div class="section" {
+                        h2 { "Snakes you follow" }
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { "Snake" }
+                                    th { "Leaderboard" }
+                                    th class="r" { "Rank" }
+                                    th class="r" { "24h" }
+                                    th class="r" { "Rating" }
+                                }
+                            }
+                            tbody {
+                                @for s in &standings {
+                                    tr {
+                                        td {
+                                            div class="snake-cell" {
+                                                span class="chip" style={"background:" (chip_color(&s.snake_color))} {}
+                                                span {
+                                                    a class="name" href={"/battlesnakes/"(s.battlesnake_id)"/profile"} { (s.snake_name) }
+                                                    span class="owner" { "by " (s.owner_login) }
+                                                }
+                                            }
+                                        }
+                                        td {
+                                            @if let (Some(id), Some(name)) = (s.leaderboard_id, s.leaderboard_name.as_ref()) {
+                                                a href={"/leaderboards/"(id)} { (name) }
+                                            } @else {
+                                                span class="text-muted" { "Not on a leaderboard" }
+                                            }
+                                        }
+                                        td class="r num" {
+                                            @if let Some(rank) = s.rank { "#" (rank) } @else { "—" }
+                                        }
+                                        td class="r num" {
+                                            @match s.day_rank_change {
+                                                Some(change) if change > 0 => {
+                                                    span class="delta up" { "▲" (change) }
+                                                }
+                                                Some(change) if change < 0 => {
+                                                    span class="delta down" { "▼" (-change) }
+                                                }
+                                                _ => { "—" }
+                                            }
+                                        }
+                                        td class="r num" {
+                                            @if let Some(score) = s.display_score { (format!("{score:.1}")) } @else { "—" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                @if !leaderboards.is_empty() {
+                    div class="section" {
+                        h2 { "Leaderboards you follow" }
+                        nav class="modes" aria-label="Followed leaderboards" {
+                            @for lb in &leaderboards {
+                                a class="mode" href={"/leaderboards/"(lb.leaderboard_id)} { (lb.name) }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+async fn require_snake(state: &AppState, battlesnake_id: Uuid) -> ServerResult<(), StatusCode> {
+    battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+        .await
+        .wrap_err("Failed to get battlesnake")?
+        .ok_or_else(|| "Battlesnake not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+async fn require_leaderboard(
+    state: &AppState,
+    leaderboard_id: Uuid,
+) -> ServerResult<(), StatusCode> {
+    leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard")?
+        .ok_or_else(|| "Leaderboard not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+/// POST /battlesnakes/{id}/follow
+pub async fn follow_snake(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(battlesnake_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    require_snake(&state, battlesnake_id).await?;
+    follow::follow_snake(&state.db, user.user_id, battlesnake_id).await?;
+    Ok(Redirect::to(&format!(
+        "/battlesnakes/{battlesnake_id}/profile"
+    )))
+}
+
+/// POST /battlesnakes/{id}/unfollow
+pub async fn unfollow_snake(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(battlesnake_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    follow::unfollow_snake(&state.db, user.user_id, battlesnake_id).await?;
+    Ok(Redirect::to(&format!(
+        "/battlesnakes/{battlesnake_id}/profile"
+    )))
+}
+
+/// POST /leaderboards/{id}/follow
+pub async fn follow_leaderboard(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(leaderboard_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    require_leaderboard(&state, leaderboard_id).await?;
+    follow::follow_leaderboard(&state.db, user.user_id, leaderboard_id).await?;
+    Ok(Redirect::to(&format!("/leaderboards/{leaderboard_id}")))
+}
+
+/// POST /leaderboards/{id}/unfollow
+pub async fn unfollow_leaderboard(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(leaderboard_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    follow::unfollow_leaderboard(&state.db, user.user_id, leaderboard_id).await?;
+    Ok(Redirect::to(&format!("/leaderboards/{leaderboard_id}")))
+}
@@ -4,31 +4,32 @@ use axum::{
         Path, Query, State, WebSocketUpgrade,
         ws::{Message, WebSocket},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
 };
 use color_eyre::eyre::Context as _;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
     errors::ServerResult,
+    frame_archive,
     models::game::{GameStatus, get_game_by_id},
-    models::turn::{get_turn_frames_page, get_turns_by_game_id},
     state::AppState,
 };
 
 /// Response format for the board viewer's game info endpoint
 /// Uses PascalCase to match the Battlesnake board viewer expectations
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "PascalCase")]
 pub struct BoardViewerGameResponse {
     pub game: BoardViewerGame,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "PascalCase")]
 pub struct BoardViewerGame {
     /// Game ID — required by the GIF exporter, which echoes it back into
@@ -53,6 +54,16 @@ fn engine_status(status: GameStatus) -> &'static str {
 
 /// GET /api/games/{id}
 /// Returns game info for the Battlesnake board viewer and the GIF exporter
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}",
+    tag = "board viewer",
+    params(("id" = Uuid, Path, description = "Game ID")),
+    responses(
+        (status = 200, description = "Engine-compatible game info", body = BoardViewerGameResponse),
+        (status = 404, description = "Game not found"),
+    )
+)]
 pub async fn get_game_info(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
@@ -87,9 +98,12 @@ pub async fn get_game_info(
 /// this cap (applied in SQL) is what keeps the endpoint bounded.
 const MAX_FRAMES_LIMIT: i64 = 100;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FramesQuery {
+    /// Number of frames to skip (default 0)
     pub offset: Option<i64>,
+    /// Page size (default and max 100)
     pub limit: Option<i64>,
 }
 
@@ -101,21 +115,44 @@ fn clamp_frames_pagination(offset: Option<i64>, limit: Option<i64>) -> (i64, i64
     (offset, limit)
 }
 
+/// Cache-Control for a frames page. A finished game's frames never change, so
+/// replay viewers and notebooks re-reading the same pages can cache them; an
+/// in-progress game's pages grow as turns are played.
+fn frames_cache_control(status: GameStatus) -> &'static str {
+    match status {
+        GameStatus::Finished => "public, max-age=86400",
+        GameStatus::Waiting | GameStatus::Running => "no-cache",
+    }
+}
+
 /// Engine-compatible frames list envelope. The legacy engine used lowercase
 /// keys here (unlike the PascalCase frame contents) and the exporter's
 /// `gameFramesResponse` deserializes exactly `count` + `frames`.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GameFramesResponse {
     pub count: usize,
+    /// PascalCase engine frames (`Turn`, `Snakes`, `Food`, `Hazards`)
+    #[schema(value_type = Vec<Object>)]
     pub frames: Vec<serde_json::Value>,
 }
 
 /// GET /api/games/{id}/frames?offset=&limit=
 ///
-/// Engine-compatible paginated frame history, served from the `turns` table.
+/// Engine-compatible paginated frame history, served from the `turns` table
+/// (or, for archived games, fetched back from GCS by `frame_archive`).
 /// Each frame is the same PascalCase JSON blob the websocket path streams
 /// (`turns.frame_data`, produced by `engine::frame::game_to_frame`).
 /// Public: game data is public, matching the legacy engine.
+#[utoipa::path(
+    get,
+    path = "/api/games/{id}/frames",
+    tag = "board viewer",
+    params(("id" = Uuid, Path, description = "Game ID"), FramesQuery),
+    responses(
+        (status = 200, description = "One page of frames, oldest first. Cacheable once the game has finished.", body = GameFramesResponse),
+        (status = 404, description = "Game not found"),
+    )
+)]
 pub async fn get_game_frames(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
@@ -123,7 +160,7 @@ pub async fn get_game_frames(
 ) -> ServerResult<impl IntoResponse, StatusCode> {
     // 404 for unknown games, like the legacy engine (the exporter maps this
     // through to its own 404).
-    get_game_by_id(&state.db, game_id)
+    let game = get_game_by_id(&state.db, game_id)
         .await
         .wrap_err("Failed to fetch game")?
         .ok_or_else(|| {
@@ -135,16 +172,17 @@ pub async fn get_game_frames(
 
     let (offset, limit) = clamp_frames_pagination(query.offset, query.limit);
 
-    let turns = get_turn_frames_page(&state.db, game_id, offset, limit)
+    let frames = frame_archive::load_frames_page(&state, game_id, offset, limit)
         .await
         .wrap_err("Failed to fetch turn frames")?;
 
-    let frames: Vec<serde_json::Value> = turns.into_iter().filter_map(|t| t.frame_data).collect();
-
-    Ok(Json(GameFramesResponse {
-        count: frames.len(),
-        frames,
-    }))
+    Ok((
+        [(header::CACHE_CONTROL, frames_cache_control(game.status))],
+        Json(GameFramesResponse {
+            count: frames.len(),
+            frames,
+        }),
+    ))
 }
 
 /// WebSocket message types for the board viewer
@@ -187,6 +225,15 @@ async fn graceful_close(
     }
 }
 
+/// A turn's frame as JSON for the socket; a frame that won't decode is
+/// logged and skipped rather than ending the stream
+fn frame_json_or_log(turn: &crate::models::turn::Turn) -> Option<serde_json::Value> {
+    turn.frame_json()
+        .inspect_err(|e| tracing::error!(error = ?e, turn_id = %turn.turn_id, "Skipping bad frame"))
+        .ok()
+        .flatten()
+}
+
 async fn handle_game_websocket(socket: WebSocket, state: AppState, game_id: Uuid) {
     let (mut sender, mut receiver) = socket.split();
 
@@ -224,7 +271,7 @@ async fn handle_game_websocket(socket: WebSocket, state: AppState, game_id: Uuid
     let mut broadcast_receiver = state.game_channels.subscribe(game_id).await;
 
     // Fetch existing frames from database
-    let existing_turns = match get_turns_by_game_id(&state.db, game_id).await {
+    let existing_turns = match frame_archive::load_turns(&state, game_id).await {
         Ok(turns) => turns,
         Err(e) => {
             tracing::error!(error = ?e, "Failed to fetch turns for WebSocket");
@@ -246,7 +293,7 @@ async fn handle_game_websocket(socket: WebSocket, state: AppState, game_id: Uuid
 
     // Send all existing frames
     for turn in existing_turns {
-        if let Some(frame_data) = turn.frame_data {
+        if let Some(frame_data) = frame_json_or_log(&turn) {
             let frame_msg = WebSocketMessage {
                 message_type: "frame".to_string(),
                 data: frame_data,
@@ -313,17 +360,18 @@ async fn handle_game_websocket(socket: WebSocket, state: AppState, game_id: Uuid
                             continue;
                         }
 
-                        // Fetch the frame data from DB
+                        // Fetch everything since the last frame sent, which also
+                        // catches up on any notification the listener missed
                         if let Ok(turns) = crate::models::turn::get_turns_from(
                             &state.db,
                             game_id,
-                            turn_notification.turn_number
+                            last_sent_turn + 1
                         ).await {
                             for turn in turns {
                                 if turn.turn_number <= last_sent_turn {
                                     continue;
                                 }
-                                if let Some(frame_data) = turn.frame_data {
+                                if let Some(frame_data) = frame_json_or_log(&turn) {
                                     let frame_msg = WebSocketMessage {
                                         message_type: "frame".to_string(),
                                         data: frame_data,
@@ -417,6 +465,16 @@ mod tests {
         assert_eq!(engine_status(GameStatus::Finished), "complete");
     }
 
+    #[test]
+    fn test_frames_cache_control_only_caches_finished_games() {
+        assert_eq!(
+            frames_cache_control(GameStatus::Finished),
+            "public, max-age=86400"
+        );
+        assert_eq!(frames_cache_control(GameStatus::Running), "no-cache");
+        assert_eq!(frames_cache_control(GameStatus::Waiting), "no-cache");
+    }
+
     #[test]
     fn test_frames_response_serialization() {
         // Engine envelope: lowercase count/frames keys wrapping PascalCase
@@ -545,6 +603,10 @@ mod tests {
         .into_response();
 
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "public, max-age=86400"
+        );
         let json = response_json(response).await;
 
         assert_eq!(json["count"], 2);
@@ -398,6 +398,30 @@ pub async fn create_game(
     Path(flow_id): Path<Uuid>,
     Form(data): Form<ConfigureGameForm>,
 ) -> ServerResult<impl IntoResponse, StatusCode> {
+    if user.is_suspended() {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            "Your account is suspended, so you can't create games.".to_string(),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+        return Ok(Redirect::to(&format!("/games/flow/{}", flow_id)).into_response());
+    }
+    if state.maintenance().await.enabled {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            "Arena is in maintenance mode, so new games are paused. Please try again later."
+                .to_string(),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+        return Ok(Redirect::to(&format!("/games/flow/{}", flow_id)).into_response());
+    }
+
     // Rate limit game creation per account (shared with the API). The
     // attempt is recorded before the check so concurrent requests see each
     // other, and the returned count includes this attempt — reject when it
@@ -469,13 +493,13 @@ pub async fn create_game(
                 .await
                 .wrap_err("Failed to create game")?;
 
-            tracing::info!(
-                event_type = "game_created",
-                game_id = %game_id,
-                board_size = flow.board_size.as_str(),
-                game_type = flow.game_type.as_str(),
-                source = "web_ui",
-                "game created via web UI"
+            crate::events::game_created(
+                game_id,
+                &flow.board_size,
+                &flow.game_type,
+                flow.selected_battlesnake_ids.len(),
+                crate::events::GameSource::WebUi,
+                Some(user.user_id),
             );
 
             // Delete the flow
@@ -0,0 +1,387 @@
+//! The public games list: recent games, newest first, narrowed by status,
+//! snake, leaderboard, and how far back to look. Pages with a keyset cursor
+//! rather than an offset, and never scans further back than the chosen
+//! window.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    customizations::chip_color,
+    errors::{ServerResult, WithStatus},
+    models::battlesnake::{self, Visibility},
+    models::game::{self, GameListFilter, GameStatus},
+    models::game_battlesnake::{self, GameSnakeSummary},
+    models::leaderboard,
+    routes::{auth::OptionalUser, cursor},
+    state::AppState,
+};
+
+const PAGE_SIZE: i64 = 50;
+
+/// How far back the list looks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum GameWindow {
+    #[default]
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+}
+
+impl GameWindow {
+    const ALL: [GameWindow; 3] = [GameWindow::Day, GameWindow::Week, GameWindow::Month];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            GameWindow::Day => "24h",
+            GameWindow::Week => "7d",
+            GameWindow::Month => "30d",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            GameWindow::Day => "24 hours",
+            GameWindow::Week => "7 days",
+            GameWindow::Month => "30 days",
+        }
+    }
+
+    fn duration(&self) -> chrono::Duration {
+        match self {
+            GameWindow::Day => chrono::Duration::hours(24),
+            GameWindow::Week => chrono::Duration::days(7),
+            GameWindow::Month => chrono::Duration::days(30),
+        }
+    }
+}
+
+/// Status, snake, and leaderboard are strings so a stale or hand-edited
+/// link falls back to "any" instead of failing the whole page; so does a
+/// bad cursor, which restarts at the newest game.
+#[derive(Debug, Default, Deserialize)]
+pub struct GamesListParams {
+    status: Option<String>,
+    snake: Option<String>,
+    leaderboard: Option<String>,
+    #[serde(default)]
+    window: GameWindow,
+    before: Option<String>,
+}
+
+/// The list's filters, for building links that keep them
+#[derive(Debug, Clone, Copy)]
+struct ListLink {
+    status: Option<GameStatus>,
+    snake: Option<Uuid>,
+    leaderboard: Option<Uuid>,
+    window: GameWindow,
+}
+
+impl ListLink {
+    fn href(&self, before: Option<&str>) -> String {
+        let mut params = Vec::new();
+        if let Some(status) = self.status {
+            params.push(format!("status={}", status.as_str()));
+        }
+        if let Some(snake) = self.snake {
+            params.push(format!("snake={snake}"));
+        }
+        if let Some(leaderboard) = self.leaderboard {
+            params.push(format!("leaderboard={leaderboard}"));
+        }
+        if self.window != GameWindow::default() {
+            params.push(format!("window={}", self.window.as_str()));
+        }
+        if let Some(before) = before {
+            params.push(format!("before={before}"));
+        }
+        if params.is_empty() {
+            "/games".to_string()
+        } else {
+            format!("/games?{}", params.join("&"))
+        }
+    }
+}
+
+/// GET /games - Recent games, filterable by status and snake
+pub async fn list_games(
+    State(state): State<AppState>,
+    OptionalUser(user): OptionalUser,
+    Query(params): Query<GamesListParams>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let status = params
+        .status
+        .as_deref()
+        .and_then(|s| GameStatus::from_str(s).ok());
+    let before = params.before.as_deref().and_then(cursor::decode);
+
+    // Private snakes can be filtered on by their owner only, as in the API
+    let snake = match params.snake.as_deref().and_then(|s| s.parse().ok()) {
+        Some(battlesnake_id) => Some(
+            battlesnake::get_battlesnake_by_id(&state.db, battlesnake_id)
+                .await
+                .wrap_err("Failed to fetch battlesnake")?
+                .filter(|snake| {
+                    snake.visibility == Visibility::Public
+                        || user.as_ref().is_some_and(|u| u.user_id == snake.user_id)
+                })
+                .ok_or_else(|| "Battlesnake not found".to_string())
+                .with_status(StatusCode::NOT_FOUND)?,
+        ),
+        None => None,
+    };
+
+    // Retired leaderboards still filter their old games, but only active
+    // ones are offered
+    let mut leaderboards = leaderboard::get_all_leaderboards(&state.read_db)
+        .await
+        .wrap_err("Failed to fetch leaderboards")?;
+    let leaderboard_id = params
+        .leaderboard
+        .as_deref()
+        .and_then(|id| id.parse::<Uuid>().ok())
+        .filter(|id| leaderboards.iter().any(|l| l.leaderboard_id == *id));
+    leaderboards.retain(|l| l.disabled_at.is_none() || Some(l.leaderboard_id) == leaderboard_id);
+
+    let filter = GameListFilter {
+        since: chrono::Utc::now() - params.window.duration(),
+        status,
+        battlesnake_id: snake.as_ref().map(|s| s.battlesnake_id),
+        leaderboard_id,
+    };
+    // Fetch one extra row to learn whether there is another page
+    let mut games = game::list_games_page(&state.read_db, &filter, before, PAGE_SIZE + 1)
+        .await
+        .wrap_err("Failed to list games")?;
+    let next_cursor = if games.len() as i64 > PAGE_SIZE {
+        games.truncate(PAGE_SIZE as usize);
+        games
+            .last()
+            .map(|last| cursor::encode(last.created_at, last.game_id))
+    } else {
+        None
+    };
+
+    let game_ids: Vec<Uuid> = games.iter().map(|g| g.game_id).collect();
+    let mut snakes: HashMap<Uuid, Vec<GameSnakeSummary>> = HashMap::new();
+    for summary in game_battlesnake::get_snake_summaries_for_games(&state.read_db, &game_ids)
+        .await
+        .wrap_err("Failed to fetch snakes for games")?
+    {
+        snakes.entry(summary.game_id).or_default().push(summary);
+    }
+
+    let link = ListLink {
+        status,
+        snake: filter.battlesnake_id,
+        leaderboard: leaderboard_id,
+        window: params.window,
+    };
+
+    Ok(page_factory.create_page(
+        "Games".to_string(),
+        Box::new(html! {
+            div class="page-head" {
+                h1 { "Games" }
+                div class="sub" {
+                    @if let Some(snake) = &snake {
+                        "Games with "
+                        a href={"/battlesnakes/"(snake.battlesnake_id)"/profile"} { b { (snake.name) } }
+                        " from the last " (params.window.label()) ". "
+                        a href=(ListLink { snake: None, ..link }.href(None)) { "Show all snakes" }
+                    } @else {
+                        "Games from the last " (params.window.label()) ", newest first."
+                    }
+                }
+            }
+
+            div class="sortbar" {
+                span { "status" }
+                @for option in [None, Some(GameStatus::Waiting), Some(GameStatus::Running), Some(GameStatus::Finished)] {
+                    @let label = option.as_ref().map_or("Any", status_label);
+                    @if option == status {
+                        span class="on" aria-current="true" { (label) }
+                    } @else {
+                        a href=(ListLink { status: option, ..link }.href(None)) { (label) }
+                    }
+                }
+            }
+            @if !leaderboards.is_empty() {
+                div class="sortbar" {
+                    span { "leaderboard" }
+                    @if leaderboard_id.is_none() {
+                        span class="on" aria-current="true" { "Any" }
+                    } @else {
+                        a href=(ListLink { leaderboard: None, ..link }.href(None)) { "Any" }
+                    }
+                    @for l in &leaderboards {
+                        @if Some(l.leaderboard_id) == leaderboard_id {
+                            span class="on" aria-current="true" { (l.name) }
+                        } @else {
+                            a href=(ListLink { leaderboard: Some(l.leaderboard_id), ..link }.href(None)) { (l.name) }
+                        }
+                    }
+                }
+            }
+            div class="sortbar" {
+                span { "since" }
+                @for window in GameWindow::ALL {
+                    @if window == params.window {
+                        span class="on" aria-current="true" { (window.label()) }
+                    } @else {
+                        a href=(ListLink { window, ..link }.href(None)) { (window.label()) }
+                    }
+                }
+            }
+
+            @if games.is_empty() {
+                p class="empty" {
+                    @if before.is_some() {
+                        "No older games in this window."
+                    } @else {
+                        "No games match these filters in the last " (params.window.label()) "."
+                    }
+                }
+            } @else {
+                table class="data" {
+                    thead {
+                        tr {
+                            th { "Game" }
+                            th { "Snakes" }
+                            th class="hide-sm" { "Type" }
+                            th class="hide-md" { "Board" }
+                            th class="r" { "Status" }
+                            th class="r" { "Created" }
+                        }
+                    }
+                    tbody {
+                        @for g in &games {
+                            tr {
+                                td class="num" {
+                                    a href={"/games/"(g.game_id)} { (g.game_id.simple().to_string()[..8]) }
+                                }
+                                td {
+                                    @for s in snakes.get(&g.game_id).map(Vec::as_slice).unwrap_or_default() {
+                                        div class="snake-cell" {
+                                            span class="chip" style={"background:"(chip_color(&s.color))} {}
+                                            span {
+                                                @if s.is_public {
+                                                    a class="name" href=(ListLink { snake: Some(s.battlesnake_id), ..link }.href(None)) { (s.name) }
+                                                } @else {
+                                                    span class="name" { (s.name) }
+                                                }
+                                                @if g.status == GameStatus::Finished && s.placement == Some(1) {
+                                                    " " span class="badge" { "Winner" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                td class="hide-sm" { (g.game_type.as_str()) }
+                                td class="hide-md" { (g.board_size.as_str()) }
+                                td class="r" { (status_label(&g.status)) }
+                                td class="r num" { (g.created_at.format("%Y-%m-%d %H:%M")) }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div class="pager" {
+                @if before.is_some() {
+                    a href=(link.href(None)) { "« Newest" }
+                }
+                @if let Some(next) = &next_cursor {
+                    a href=(link.href(Some(next))) { "Older ›" }
+                }
+            }
+        }),
+    ))
+}
+
+fn status_label(status: &GameStatus) -> &'static str {
+    match status {
+        GameStatus::Waiting => "Waiting",
+        GameStatus::Running => "Running",
+        GameStatus::Finished => "Finished",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_keep_filters_and_drop_defaults() {
+        let link = ListLink {
+            status: None,
+            snake: None,
+            leaderboard: None,
+            window: GameWindow::Day,
+        };
+        assert_eq!(link.href(None), "/games");
+
+        let snake = Uuid::nil();
+        let link = ListLink {
+            status: Some(GameStatus::Running),
+            snake: Some(snake),
+            leaderboard: Some(snake),
+            window: GameWindow::Week,
+        };
+        assert_eq!(
+            link.href(Some("abc")),
+            format!("/games?status=running&snake={snake}&leaderboard={snake}&window=7d&before=abc")
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn lenient_params_still_render(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let app = crate::routes::routes(AppState::test_from_pool(pool));
+        for uri in [
+            "/games",
+            "/games?status=finished&window=30d",
+            "/games?status=bogus&snake=not-a-uuid&before=not-a-cursor",
+            "/games?leaderboard=not-a-uuid",
+            "/games?leaderboard=00000000-0000-0000-0000-000000000000",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::extract::Request::get(uri)
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        }
+
+        let response = app
+            .oneshot(
+                axum::extract::Request::get(format!("/games?snake={}", Uuid::new_v4()))
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
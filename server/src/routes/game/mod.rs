@@ -1,5 +1,6 @@
 pub mod api;
 pub mod create;
+pub mod list;
 pub mod view;
 
 // Re-export the functions we need
@@ -8,4 +9,5 @@ pub use create::{
     add_battlesnake, create_game, new_game, rematch_game, remove_battlesnake,
     reset_snake_selections, search_battlesnakes, show_game_flow,
 };
+pub use list::list_games;
 pub use view::view_game;
@@ -16,13 +16,20 @@ use crate::{
     models::game::GameStatus,
     models::game_battlesnake,
     models::saved_game,
-    routes::auth::OptionalUser,
+    routes::{auth::OptionalUser, preview},
     state::AppState,
+    static_assets::asset_url,
 };
 
-/// Optional viewer params forwarded to the board.battlesnake.com iframe so
-/// shared links can jump to a turn, autoplay, etc. Only params that were
-/// actually provided are passed through.
+/// Replay speed when the link doesn't ask for one, and the range it may ask
+/// for. Matches the board viewer's default.
+const DEFAULT_REPLAY_FPS: u32 = 10;
+const MAX_REPLAY_FPS: u32 = 30;
+
+/// Optional viewer params so shared links can jump to a turn, autoplay, etc.
+/// Forwarded to the board.battlesnake.com iframe for live games (only params
+/// that were actually provided are passed through) and read by the embedded
+/// replay viewer for finished ones.
 #[derive(Debug, Default, Deserialize)]
 pub struct BoardParams {
     turn: Option<u32>,
@@ -43,7 +50,7 @@ pub struct ViewGameParams {
 }
 
 impl ViewGameParams {
-    fn show_spoilers(&self) -> bool {
+    pub(crate) fn show_spoilers(&self) -> bool {
         match self.show_spoilers.as_deref() {
             None => false,
             Some(v) => !matches!(
@@ -71,6 +78,7 @@ pub async fn view_game(
         .with_status(StatusCode::NOT_FOUND)?;
 
     let finished = game.status == GameStatus::Finished;
+    let (board_width, board_height) = game.board_size.dimensions();
 
     let iframe_src = board_iframe_src(&state.config.base_url, game_id, &board_params);
     // A copied ?showSpoilers link keeps the reveal: sharing the spoiler
@@ -107,80 +115,107 @@ pub async fn view_game(
             .wrap_err("Failed to fetch saved game")?,
         None => None,
     };
+    let t = page_factory.locale;
 
     Ok(page_factory.create_theater_page(
-        format!("Game {game_id}"),
+        t.t_with("game.title", &[("id", &game_id)]),
         Box::new(html! {
-            h1 class="vh" { "Game Details" }
+            h1 class="vh" { (t.t("game.heading")) }
             div class="crumb" {
-                a href="/leaderboards" { "Leaderboards" }
-                " / " span { "Game " (game_id) }
+                a href="/leaderboards" { (t.t("nav.leaderboards")) }
+                " / " span { (t.t_with("game.title", &[("id", &game_id)])) }
                 @match game.status {
-                    GameStatus::Waiting => span class="live-pill quiet" { "Waiting" },
-                    GameStatus::Running => span class="live-pill" { span class="live-dot" {} "Live" },
-                    GameStatus::Finished => span class="live-pill quiet" { "Replay" },
+                    GameStatus::Waiting => span class="live-pill quiet" { (t.t("game.waiting")) },
+                    GameStatus::Running => span class="live-pill" { span class="live-dot" {} (t.t("common.live")) },
+                    GameStatus::Finished => span class="live-pill quiet" { (t.t("game.replay")) },
                 }
             }
 
             @if game.status == GameStatus::Waiting {
                 p class="empty" {
-                    "This game is waiting to start. "
-                    a href="" onclick="location.reload(); return false;" class="refresh-link" { "Refresh" }
-                    " to check for updates."
+                    (t.t("game.waiting_notice")) " "
+                    a href="" onclick="location.reload(); return false;" class="refresh-link" { (t.t("game.refresh")) }
+                    " " (t.t("game.refresh_hint"))
                 }
             }
 
             div class="theater" {
                 div {
-                    div class="board-wrap" {
-                        // Board viewer iframe - always show, it handles waiting/empty games
-                        // gracefully. Default aspect-ratio is 16/9; the board sends a RESIZE
-                        // postMessage with its actual dimensions.
-                        div #board-viewer-container style="width: 100%; aspect-ratio: 16 / 9;" {
-                            iframe
-                                id="board-viewer"
-                                src=(iframe_src)
-                                title="Battlesnake Board Viewer"
-                                allow="accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture"
-                                allowfullscreen {}
+                    @if finished {
+                        // Finished games replay from stored frames in the
+                        // embedded viewer; live games stream through the
+                        // board.battlesnake.com iframe.
+                        div #replay class="board-wrap replay"
+                            data-game-id=(game_id)
+                            data-width=(board_width)
+                            data-height=(board_height)
+                            data-fps=(replay_fps(&board_params))
+                            data-turn=[board_params.turn]
+                            data-autoplay=(board_params.autoplay.as_deref().is_some_and(autoplay_is_truthy)) {
+                            div class="replay-board" {}
+                            p class="replay-status" data-replay-status {}
+                            div class="replay-controls" {
+                                button type="button" class="btn sm" data-replay-prev aria-label=(t.t("game.previous_turn")) { "‹" }
+                                button type="button" class="btn sm solid" data-replay-play
+                                    data-play-label=(t.t("game.play"))
+                                    data-pause-label=(t.t("game.pause")) { (t.t("game.play")) }
+                                button type="button" class="btn sm" data-replay-next aria-label=(t.t("game.next_turn")) { "›" }
+                                input type="range" min="0" max="0" value="0" data-replay-scrub aria-label=(t.t("game.turn"));
+                                span class="replay-turn" data-replay-turn {}
+                            }
+                        }
+                        script src=(asset_url("replayViewer.js")) defer {}
+                    } @else {
+                        div class="board-wrap" {
+                            // Board viewer iframe - it handles waiting/empty games
+                            // gracefully. Default aspect-ratio is 16/9; the board sends a RESIZE
+                            // postMessage with its actual dimensions.
+                            div #board-viewer-container style="width: 100%; aspect-ratio: 16 / 9;" {
+                                iframe
+                                    id="board-viewer"
+                                    src=(iframe_src)
+                                    title="Battlesnake Board Viewer"
+                                    allow="accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture"
+                                    allowfullscreen {}
+                            }
                         }
-                    }
 
-                    script {
-                        "window.addEventListener('message', function(e) {"
-                            "if (e.origin !== 'https://board.battlesnake.com') return;"
-                            "var evt = e.data;"
-                            "if (evt.event === 'RESIZE') {"
-                                "document.getElementById('board-viewer-container').style"
-                                    ".setProperty('aspect-ratio', evt.data.width + ' / ' + evt.data.height);"
-                            "}"
-                        "});"
+                        script {
+                            "window.addEventListener('message', function(e) {"
+                                "if (e.origin !== 'https://board.battlesnake.com') return;"
+                                "var evt = e.data;"
+                                "if (evt.event === 'RESIZE') {"
+                                    "document.getElementById('board-viewer-container').style"
+                                        ".setProperty('aspect-ratio', evt.data.width + ' / ' + evt.data.height);"
+                                "}"
+                            "});"
+                        }
                     }
 
                     div class="theater-actions" {
                         @if user.is_some() {
                             @if finished {
                                 form action={"/games/"(game_id)"/rematch"} method="post" style="display: inline;" {
-                                    button type="submit" class="btn" { "Rematch" }
+                                    button type="submit" class="btn" { (t.t("game.rematch")) }
                                 }
                             }
-                            a href="/games/new" class="btn" { "Create Another Game" }
-                            a href="/me" class="btn" { "Back to Profile" }
+                            a href="/games/new" class="btn" { (t.t("game.create_another")) }
+                            a href="/me" class="btn" { (t.t("game.back_to_profile")) }
                         } @else {
-                            a href="/leaderboards" class="btn" { "View Leaderboards" }
-                            a href="/" class="btn" { "Back to Home" }
+                            a href="/leaderboards" class="btn" { (t.t("game.view_leaderboards")) }
+                            a href="/" class="btn" { (t.t("game.back_to_home")) }
                         }
                     }
                 }
 
                 aside {
                     h2 class="theater-rail-head" {
-                        "Game Results"
-                        span class="sub-count" { (battlesnakes.len()) " snakes" }
+                        (t.t("game.results"))
+                        span class="sub-count" { (t.t_with("game.snake_count", &[("count", &battlesnakes.len())])) }
                     }
                     div class="snakes" {
                         @for battlesnake in &battlesnakes {
-                            div .scard .p1[battlesnake.placement == Some(1)] {
+                            div .scard .p1[battlesnake.placement == Some(1)] data-snake-id=(battlesnake.game_battlesnake_id) {
                                 div class="top" {
                                     span class="chip" style={"background:"(chip_color(&battlesnake.color))} {}
                                     div {
@@ -190,7 +225,7 @@ pub async fn view_game(
                                             }
                                         }
                                         div class="owner" {
-                                            "by "
+                                            (t.t("common.by")) " "
                                             a href={"/users/"(battlesnake.owner_login)} { (battlesnake.owner_login) }
                                         }
                                     }
@@ -200,7 +235,16 @@ pub async fn view_game(
                                         } @else if finished {
                                             "—"
                                         } @else {
-                                            "In Progress"
+                                            (t.t("game.in_progress"))
+                                        }
+                                    }
+                                }
+                                @if finished {
+                                    div class="vitals" {
+                                        div class="health-bar" { span data-vital-bar {} }
+                                        div class="vitals-text" {
+                                            span data-vital-health {}
+                                            span data-vital-length {}
                                         }
                                     }
                                 }
@@ -209,20 +253,21 @@ pub async fn view_game(
                     }
 
                     div class="gmeta" {
-                        h3 { "Details" }
+                        h3 { (t.t("game.details")) }
                         dl class="meta-list" {
-                            div { dt { "Board" } dd { (game.board_size.as_str()) } }
-                            div { dt { "Mode" } dd { (game.game_type.as_str()) } }
-                            div { dt { "Status" } dd { (capitalize(game.status.as_str())) } }
-                            div { dt { "Created" } dd { (game.created_at.format("%Y-%m-%d %H:%M UTC")) } }
+                            div { dt { (t.t("game.board")) } dd { (game.board_size.as_str()) } }
+                            div { dt { (t.t("game.mode")) } dd { (game.game_type.as_str()) } }
+                            div { dt { (t.t("game.status")) } dd { (capitalize(game.status.as_str())) } }
+                            div { dt { (t.t("game.created")) } dd { (game.created_at.format("%Y-%m-%d %H:%M UTC")) } }
                         }
                     }
 
                     div class="gmeta" {
-                        h3 { "Share" }
+                        h3 { (t.t("game.share")) }
                         div class="share-row" {
-                            input #share-url type="text" readonly value=(share_url) aria-label="Shareable game link";
-                            button #share-copy class="btn sm" type="button" { "Copy Link" }
+                            input #share-url type="text" readonly value=(share_url) aria-label=(t.t("game.share_link"));
+                            button #share-copy class="btn sm" type="button"
+                                data-copied=(t.t("game.copied")) { (t.t("game.copy_link")) }
                         }
                     }
 
@@ -230,9 +275,10 @@ pub async fn view_game(
                         "(function() {"
                             "var btn = document.getElementById('share-copy');"
                             "var input = document.getElementById('share-url');"
+                            "var label = btn.textContent;"
                             "function done() {"
-                                "btn.textContent = 'Copied!';"
-                                "setTimeout(function() { btn.textContent = 'Copy Link'; }, 1500);"
+                                "btn.textContent = btn.dataset.copied;"
+                                "setTimeout(function() { btn.textContent = label; }, 1500);"
                             "}"
                             "function fallback() {"
                                 "input.focus();"
@@ -252,20 +298,20 @@ pub async fn view_game(
                     @if user.is_some() {
                         div class="gmeta" {
                             @if saved.is_some() {
-                                h3 { "Saved to Your Profile" }
+                                h3 { (t.t("game.saved")) }
                             } @else {
-                                h3 { "Save Game" }
+                                h3 { (t.t("game.save")) }
                             }
                             form action={"/games/"(game_id)"/save"} method="post" {
                                 input
                                     type="text"
                                     name="title"
                                     maxlength="100"
-                                    placeholder="Title (optional)"
+                                    placeholder=(t.t("game.save_title"))
                                     value=[saved.as_ref().map(|s| s.title.as_str())];
                                 " "
                                 button type="submit" class="btn" {
-                                    @if saved.is_some() { "Update" } @else { "Save Game" }
+                                    @if saved.is_some() { (t.t("game.update")) } @else { (t.t("game.save")) }
                                 }
                             }
                         }
@@ -274,7 +320,12 @@ pub async fn view_game(
             }
         }),
     )
-    .with_description(description))
+    .with_description(description)
+    .with_image(preview::game_preview_url(
+        &state.config.base_url,
+        game_id,
+        params.show_spoilers(),
+    )))
 }
 
 /// Query-string suffix (each param prefixed with `&`) for the optional board
@@ -299,6 +350,14 @@ fn board_query_suffix(params: &BoardParams) -> String {
     suffix
 }
 
+/// Frames per second for the embedded replay: the link's `fps` if it gave
+/// one, kept to a watchable range.
+fn replay_fps(params: &BoardParams) -> u32 {
+    params
+        .fps
+        .map_or(DEFAULT_REPLAY_FPS, |fps| fps.clamp(1, MAX_REPLAY_FPS))
+}
+
 /// Bool-ish parse matching how play treated autoplay: present and not an
 /// explicit "off" value means on.
 fn autoplay_is_truthy(value: &str) -> bool {
@@ -310,7 +369,7 @@ fn autoplay_is_truthy(value: &str) -> bool {
 
 /// Build the board.battlesnake.com iframe src, forwarding any provided
 /// viewer params onto the board.
-fn board_iframe_src(base_url: &str, game_id: Uuid, params: &BoardParams) -> String {
+pub(crate) fn board_iframe_src(base_url: &str, game_id: Uuid, params: &BoardParams) -> String {
     format!(
         "https://board.battlesnake.com/?engine={base_url}/api&game={game_id}{}",
         board_query_suffix(params)
@@ -454,6 +513,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replay_fps_defaults_and_clamps() {
+        let fps = |fps| {
+            replay_fps(&BoardParams {
+                fps,
+                ..Default::default()
+            })
+        };
+        assert_eq!(fps(None), DEFAULT_REPLAY_FPS);
+        assert_eq!(fps(Some(4)), 4);
+        assert_eq!(fps(Some(0)), 1);
+        assert_eq!(fps(Some(500)), MAX_REPLAY_FPS);
+    }
+
     fn params(value: Option<&str>) -> ViewGameParams {
         ViewGameParams {
             show_spoilers: value.map(str::to_string),
@@ -228,9 +228,16 @@ pub async fn github_auth_callback(
 
     // If CLI auth, create an API token and redirect to the token display page
     if is_cli_auth {
-        let new_token = api_token::create_api_token(&state.db, user.user_id, "arena-cli")
-            .await
-            .wrap_err("Failed to create API token for CLI")?;
+        // The CLI acts as the user, so it gets every scope
+        let new_token = api_token::create_api_token(
+            &state.db,
+            user.user_id,
+            "arena-cli",
+            &api_token::TokenScope::ALL,
+            None,
+        )
+        .await
+        .wrap_err("Failed to create API token for CLI")?;
 
         // Redirect to the CLI token display page with the token as a query param
         return Ok(Redirect::to(&format!(
@@ -8,7 +8,7 @@ use axum::{
 };
 use chrono_humanize::HumanTime;
 use color_eyre::eyre::Context as _;
-use maud::html;
+use maud::{Markup, html};
 use uuid::Uuid;
 
 use crate::{
@@ -16,13 +16,21 @@ use crate::{
     cron::MATCHMAKER_INTERVAL_SECS,
     customizations::chip_color,
     errors::{ServerResult, WithRedirect},
+    i18n::Locale,
     models::snake_health_status,
     models::{
         battlesnake::{self, Visibility},
+        battlesnake_version::{self, SnakeVersion},
+        follow,
+        game_battlesnake::{self, GameSnakeSummary},
         leaderboard::{self, MIN_GAMES_FOR_RANKING},
-        user,
+        snake_url_verification, user,
+    },
+    routes::{
+        auth::{CurrentUser, OptionalUser},
+        feed::follow_button,
+        preview,
     },
-    routes::auth::{CurrentUser, OptionalUser},
     scoring::EntryScore,
     state::AppState,
 };
@@ -41,33 +49,33 @@ pub async fn list_leaderboards(
     OptionalUser(_user): OptionalUser,
     page_factory: PageFactory,
 ) -> ServerResult<impl IntoResponse, StatusCode> {
-    let leaderboards = leaderboard::get_all_leaderboards(&state.db)
+    let leaderboards = state
+        .leaderboards()
         .await
         .wrap_err("Failed to fetch leaderboards")?;
+    let t = page_factory.locale;
 
     Ok(page_factory.create_page(
-        "Leaderboards".to_string(),
+        t.t("leaderboards.title").to_string(),
         Box::new(html! {
             div class="page-head" {
-                h1 { "Leaderboards" }
-                div class="sub" {
-                    "Ranked ladders, one per game mode. Join with a public snake and the matchmaker takes it from there."
-                }
+                h1 { (t.t("leaderboards.title")) }
+                div class="sub" { (t.t("leaderboards.intro")) }
             }
 
             @if leaderboards.is_empty() {
-                p class="empty" { "No leaderboards available yet." }
+                p class="empty" { (t.t("leaderboards.empty")) }
             } @else {
                 div class="section" {
                     table class="data" {
                         thead {
                             tr {
-                                th { "Leaderboard" }
-                                th class="r" { "Status" }
+                                th { (t.t("leaderboards.leaderboard")) }
+                                th class="r" { (t.t("leaderboards.status")) }
                             }
                         }
                         tbody {
-                            @for lb in &leaderboards {
+                            @for lb in leaderboards.iter() {
                                 tr {
                                     td {
                                         div class="snake-cell" {
@@ -78,9 +86,11 @@ pub async fn list_leaderboards(
                                     }
                                     td class="r" {
                                         @if lb.disabled_at.is_some() {
-                                            span class="badge" { "Inactive" }
+                                            span class="badge" { (t.t("common.inactive")) }
+                                        } @else if lb.frozen_at.is_some() {
+                                            span class="badge" { (t.t("common.frozen")) }
                                         } @else {
-                                            span class="badge ok" { "Active" }
+                                            span class="badge ok" { (t.t("common.active")) }
                                         }
                                     }
                                 }
@@ -102,25 +112,75 @@ pub async fn show_leaderboard(
     Query(pagination): Query<PaginationParams>,
     page_factory: PageFactory,
 ) -> ServerResult<impl IntoResponse, StatusCode> {
-    let lb = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
-        .await
-        .wrap_err("Failed to fetch leaderboard")?
-        .ok_or_else(|| {
-            crate::errors::ServerError(
-                color_eyre::eyre::eyre!("Leaderboard not found"),
-                StatusCode::NOT_FOUND,
-            )
-        })?;
+    let user_id = user.as_ref().map(|u| u.user_id);
+
+    // Everything but the ranked page is independent, so fetch it all at
+    // once rather than one round trip after another
+    let (lb, all_leaderboards, status, placement, activity, top_eaters, user_snakes, user_entries) =
+        tokio::try_join!(
+            async {
+                leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+                    .await
+                    .wrap_err("Failed to fetch leaderboard")
+            },
+            async {
+                state
+                    .leaderboards()
+                    .await
+                    .wrap_err("Failed to fetch leaderboards")
+            },
+            async {
+                state
+                    .leaderboard_status(leaderboard_id)
+                    .await
+                    .wrap_err("Failed to fetch leaderboard status")
+            },
+            async {
+                leaderboard::get_placement_entries(&state.db, leaderboard_id)
+                    .await
+                    .wrap_err("Failed to fetch placement entries")
+            },
+            async {
+                leaderboard::get_activity_feed(&state.db, leaderboard_id, 20)
+                    .await
+                    .wrap_err("Failed to fetch activity feed")
+            },
+            async {
+                leaderboard::get_top_eaters(&state.db, leaderboard_id, 3)
+                    .await
+                    .wrap_err("Failed to fetch top eaters")
+            },
+            // Get user's snakes for the join form
+            async {
+                match user_id {
+                    Some(user_id) => battlesnake::get_battlesnakes_by_user_id(&state.db, user_id)
+                        .await
+                        .wrap_err("Failed to fetch user's battlesnakes"),
+                    None => Ok(vec![]),
+                }
+            },
+            // Get user's entries in this leaderboard
+            async {
+                match user_id {
+                    Some(user_id) => {
+                        leaderboard::get_user_entries(&state.db, leaderboard_id, user_id)
+                            .await
+                            .wrap_err("Failed to fetch user's leaderboard entries")
+                    }
+                    None => Ok(vec![]),
+                }
+            },
+        )?;
 
-    let all_leaderboards = leaderboard::get_all_leaderboards(&state.db)
-        .await
-        .wrap_err("Failed to fetch leaderboards")?;
+    let lb = lb.ok_or_else(|| {
+        crate::errors::ServerError(
+            color_eyre::eyre::eyre!("Leaderboard not found"),
+            StatusCode::NOT_FOUND,
+        )
+    })?;
 
     let per_page: i64 = 50;
-
-    let total_ranked = leaderboard::count_ranked_entries(&state.db, leaderboard_id)
-        .await
-        .wrap_err("Failed to count ranked entries")?;
+    let total_ranked = status.ranked_entries;
 
     let total_pages = if total_ranked > 0 {
         (total_ranked + per_page - 1) / per_page
@@ -129,49 +189,10 @@ pub async fn show_leaderboard(
     };
     let page = pagination.page.unwrap_or(0).clamp(0, total_pages - 1);
 
-    let ranked = leaderboard::get_ranked_entries_paginated(
-        &state.db,
-        leaderboard_id,
-        page,
-        per_page,
-        pagination.sort,
-    )
-    .await
-    .wrap_err("Failed to fetch ranked entries")?;
-
-    let placement = leaderboard::get_placement_entries(&state.db, leaderboard_id)
-        .await
-        .wrap_err("Failed to fetch placement entries")?;
-
-    let status = leaderboard::get_leaderboard_status(&state.db, leaderboard_id)
+    let ranked = state
+        .standings(leaderboard_id, pagination.sort, Some((page, per_page)))
         .await
-        .wrap_err("Failed to fetch leaderboard status")?;
-
-    let activity = leaderboard::get_activity_feed(&state.db, leaderboard_id, 20)
-        .await
-        .wrap_err("Failed to fetch activity feed")?;
-
-    let top_eaters = leaderboard::get_top_eaters(&state.db, leaderboard_id, 3)
-        .await
-        .wrap_err("Failed to fetch top eaters")?;
-
-    // Get user's snakes for the join form
-    let user_snakes = if let Some(ref u) = user {
-        battlesnake::get_battlesnakes_by_user_id(&state.db, u.user_id)
-            .await
-            .wrap_err("Failed to fetch user's battlesnakes")?
-    } else {
-        vec![]
-    };
-
-    // Get user's entries in this leaderboard
-    let user_entries = if let Some(ref u) = user {
-        leaderboard::get_user_entries(&state.db, leaderboard_id, u.user_id)
-            .await
-            .wrap_err("Failed to fetch user's leaderboard entries")?
-    } else {
-        vec![]
-    };
+        .wrap_err("Failed to fetch ranked entries")?;
 
     // Compute next matchmaker run time
     let next_run_str = status.last_game_created_at.map(|last| {
@@ -195,42 +216,60 @@ pub async fn show_leaderboard(
         .collect();
 
     // Fetch per-algorithm scores for only the visible entries
-    let mut algo_scores: Vec<(&str, &str, HashMap<Uuid, EntryScore>)> = vec![];
-    for algo in state.scoring.algorithms() {
-        let scores = algo
-            .get_scores(&state.db, &entry_ids)
+    let (db, entry_ids) = (&state.read_db, &entry_ids);
+    let algo_scores: Vec<(&str, &str, HashMap<Uuid, EntryScore>)> =
+        futures::future::try_join_all(state.scoring.algorithms().iter().map(|algo| async move {
+            let scores = algo
+                .get_scores(db, entry_ids)
+                .await
+                .wrap_err_with(|| format!("Failed to fetch {} scores", algo.key()))?;
+            let map: HashMap<Uuid, EntryScore> = scores
+                .into_iter()
+                .map(|s| (s.leaderboard_entry_id, s))
+                .collect();
+            Ok::<_, color_eyre::Report>((algo.key(), algo.score_column_name(), map))
+        }))
+        .await?;
+
+    let following = match user_id {
+        Some(user_id) => follow::is_following_leaderboard(&state.db, user_id, leaderboard_id)
             .await
-            .wrap_err_with(|| format!("Failed to fetch {} scores", algo.key()))?;
-        let map: HashMap<Uuid, EntryScore> = scores
-            .into_iter()
-            .map(|s| (s.leaderboard_entry_id, s))
-            .collect();
-        algo_scores.push((algo.key(), algo.score_column_name(), map));
-    }
+            .wrap_err("Failed to check leaderboard follow")?,
+        None => false,
+    };
 
     let description = format!(
         "{} leaderboard on Battlesnake Arena — {} ranked snakes, {} games played.",
         lb.name, total_ranked, status.total_games
     );
+    let t = page_factory.locale;
 
     Ok(page_factory.create_page(
         lb.name.clone(),
         Box::new(html! {
             div class="crumb" {
-                a href="/leaderboards" { "Leaderboards" }
+                a href="/leaderboards" { (t.t("leaderboards.title")) }
                 " / " (lb.name)
             }
             div class="page-head" {
                 h1 { (lb.name) }
                 div class="sub" {
-                    "Ranked play — register a public snake, join the ladder, and the "
-                    "matchmaker starts new games every few minutes."
+                    @if let Some(frozen_at) = lb.frozen_at {
+                        (t.t_with("leaderboard.frozen_since", &[("date", &t.format_date(frozen_at))]))
+                    } @else {
+                        (t.t("leaderboard.intro"))
+                    }
+                }
+                @if user.is_some() {
+                    div class="head-actions" {
+                        (follow_button(&format!("/leaderboards/{leaderboard_id}"), following))
+                    }
                 }
             }
 
             @if all_leaderboards.len() > 1 {
-                nav class="modes" aria-label="Game modes" {
-                    @for other in &all_leaderboards {
+                nav class="modes" aria-label=(t.t("leaderboard.game_modes")) {
+                    @for other in all_leaderboards.iter() {
                         @if other.leaderboard_id == leaderboard_id {
                             span class="mode on" aria-current="page" { (other.name) }
                         } @else {
@@ -242,64 +281,67 @@ pub async fn show_leaderboard(
 
             div class="stats" {
                 div class="stat" {
-                    div class="label" { "Ranked snakes" }
+                    div class="label" { (t.t("leaderboard.ranked_snakes")) }
                     div class="value" { (total_ranked) }
                 }
                 div class="stat" {
-                    div class="label" { "Games played" }
+                    div class="label" { (t.t("leaderboard.games_played")) }
                     div class="value" { (status.total_games) }
                 }
                 div class="stat" {
-                    div class="label" { "In progress" }
+                    div class="label" { (t.t("leaderboard.in_progress")) }
                     div class="value" {
-                        span class="live" { (status.games_in_progress) }
-                        small { "live now" }
+                        a href={"/leaderboards/"(leaderboard_id)"/live"} {
+                            span class="live" { (status.games_in_progress) }
+                            small { (t.t("leaderboard.live_now")) }
+                        }
                     }
                 }
                 div class="stat" {
-                    div class="label" { "Next matchmaker run" }
+                    div class="label" { (t.t("leaderboard.next_run")) }
                     div class="value sm" {
                         @if let Some(ref next) = next_run_str {
                             (next)
                         } @else {
-                            "waiting for first games"
+                            (t.t("leaderboard.waiting_first_games"))
                         }
                     }
                 }
             }
 
+            (leaderboard_view_tabs(t, leaderboard_id, status.games_in_progress, false))
+
             div class="grid" {
                 div {
                     div class="sortbar" {
-                        span { "sort" }
+                        span { (t.t("leaderboard.sort")) }
                         @if pagination.sort == leaderboard::LeaderboardSort::Rating {
-                            span class="on" aria-current="true" { "Rating" }
+                            span class="on" aria-current="true" { (t.t("leaderboard.sort_rating")) }
                         } @else {
-                            a href={"/leaderboards/"(leaderboard_id)"?sort=rating"} { "Rating" }
+                            a href={"/leaderboards/"(leaderboard_id)"?sort=rating"} { (t.t("leaderboard.sort_rating")) }
                         }
                         @if pagination.sort == leaderboard::LeaderboardSort::FoodEaten {
-                            span class="on" aria-current="true" { "Food eaten" }
+                            span class="on" aria-current="true" { (t.t("leaderboard.sort_food")) }
                         } @else {
-                            a href={"/leaderboards/"(leaderboard_id)"?sort=food_eaten"} { "Food eaten" }
+                            a href={"/leaderboards/"(leaderboard_id)"?sort=food_eaten"} { (t.t("leaderboard.sort_food")) }
                         }
                     }
 
                     @if ranked.is_empty() {
                         p class="empty" {
-                            "No snakes have completed enough games to be ranked yet. "
-                            "(Minimum: " (MIN_GAMES_FOR_RANKING) " games)"
+                            (t.t_with("leaderboard.none_ranked", &[("min", &MIN_GAMES_FOR_RANKING)]))
                         }
                     } @else {
                         table class="data" {
                             thead {
                                 tr {
                                     th { "#" }
-                                    th { "Battlesnake" }
+                                    th { (t.t("leaderboard.battlesnake")) }
                                     @for (key, col_name, _map) in &algo_scores {
                                         th .r .hide-sm[*key != active_algo_key] { (col_name) }
                                     }
-                                    th class="r hide-md" { "Games" }
-                                    th class="r hide-sm" { "1st place %" }
+                                    th class="r hide-md" { (t.t("leaderboard.games")) }
+                                    th class="r hide-sm" { (t.t("leaderboard.first_place_pct")) }
                                 }
                             }
                             tbody {
@@ -316,9 +358,9 @@ pub async fn show_leaderboard(
                                                         (entry.snake_name)
                                                     }
                                                     span class="owner" {
-                                                        "by "
+                                                        (t.t("common.by")) " "
                                                         a href={"/users/"(entry.owner_login)} { (entry.owner_login) }
-                                                        @if is_you { " — you" }
+                                                        @if is_you { " — " (t.t("common.you")) }
                                                     }
                                                 }
                                             }
@@ -337,7 +379,7 @@ pub async fn show_leaderboard(
                                             @if entry.games_played > 0 {
                                                 (format!("{:.0}%", (entry.first_place_finishes as f64 / entry.games_played as f64) * 100.0))
                                             } @else {
-                                                "N/A"
+                                                (t.t("common.not_available"))
                                             }
                                         }
                                     }
@@ -347,32 +389,35 @@ pub async fn show_leaderboard(
 
                         div class="pager" {
                             @if page > 0 {
-                                a href={"/leaderboards/"(leaderboard_id)"?sort="(sort_param)"&page="(page - 1)} { "‹ Prev" }
+                                a href={"/leaderboards/"(leaderboard_id)"?sort="(sort_param)"&page="(page - 1)} { (t.t("common.prev")) }
                             }
                             @if total_pages > 1 {
-                                span class="cur" { "Page " (page + 1) " of " (total_pages) }
+                                span class="cur" { (t.t_with("common.page_of", &[("page", &(page + 1)), ("total", &total_pages)])) }
                             }
                             @if page < total_pages - 1 {
-                                a href={"/leaderboards/"(leaderboard_id)"?sort="(sort_param)"&page="(page + 1)} { "Next ›" }
+                                a href={"/leaderboards/"(leaderboard_id)"?sort="(sort_param)"&page="(page + 1)} { (t.t("common.next")) }
                             }
                             span class="spacer" {}
                             span {
-                                "Showing " (rank_start + 1) "–" (rank_start + ranked.len() as i64)
-                                " of " (total_ranked) " ranked snakes"
+                                (t.t_with("leaderboard.showing", &[
+                                    ("from", &(rank_start + 1)),
+                                    ("to", &(rank_start + ranked.len() as i64)),
+                                    ("total", &total_ranked),
+                                ]))
                             }
                         }
                     }
 
                     @if !placement.is_empty() {
                         div class="section" {
-                            h2 { "In Placement" }
-                            p class="empty" { "These snakes need more games before appearing in rankings." }
+                            h2 { (t.t("leaderboard.placement_title")) }
+                            p class="empty" { (t.t("leaderboard.placement_intro")) }
                             table class="data" {
                                 thead {
                                     tr {
-                                        th { "Battlesnake" }
-                                        th class="r" { "Games played" }
-                                        th class="r" { "Games remaining" }
+                                        th { (t.t("leaderboard.battlesnake")) }
+                                        th class="r" { (t.t("leaderboard.games_played")) }
+                                        th class="r" { (t.t("leaderboard.games_remaining")) }
                                     }
                                 }
                                 tbody {
@@ -386,7 +431,7 @@ pub async fn show_leaderboard(
                                                             (entry.snake_name)
                                                         }
                                                         span class="owner" {
-                                                            "by "
+                                                            (t.t("common.by")) " "
                                                             a href={"/users/"(entry.owner_login)} { (entry.owner_login) }
                                                         }
                                                     }
@@ -405,7 +450,7 @@ pub async fn show_leaderboard(
                 aside class="rail" {
                     @if !activity.is_empty() {
                         div class="block" {
-                            h3 { span class="live-dot" {} "Recent games" }
+                            h3 { span class="live-dot" {} (t.t("leaderboard.recent_games")) }
                             ul class="feed" {
                                 @for event in &activity {
                                     li {
@@ -431,7 +476,7 @@ pub async fn show_leaderboard(
 
                     @if user.is_some() {
                         div class="block" {
-                            h3 { "Your Snakes" }
+                            h3 { (t.t("leaderboard.your_snakes")) }
                             @for entry in &user_entries {
                                 @if let Some(snake) = user_snakes.iter().find(|s| s.battlesnake_id == entry.battlesnake_id) {
                                     div class="mine" {
@@ -444,17 +489,17 @@ pub async fn show_leaderboard(
                                                     "Auto-paused"
                                                 }
                                             } @else {
-                                                span class="badge" { "Paused" }
+                                                span class="badge" { (t.t("common.paused")) }
                                             }
                                             form action={"/leaderboards/"(leaderboard_id)"/join"} method="post" {
                                                 input type="hidden" name="battlesnake_id" value=(snake.battlesnake_id);
-                                                button type="submit" class="btn sm" aria-label={"Resume " (snake.name)} { "Resume" }
+                                                button type="submit" class="btn sm" aria-label={(t.t("leaderboard.resume")) " " (snake.name)} { (t.t("leaderboard.resume")) }
                                             }
                                         } @else {
-                                            span class="badge ok" { "Active" }
+                                            span class="badge ok" { (t.t("common.active")) }
                                             form action={"/leaderboards/"(leaderboard_id)"/leave"} method="post" {
                                                 input type="hidden" name="leaderboard_entry_id" value=(entry.leaderboard_entry_id);
-                                                button type="submit" class="btn sm" aria-label={"Pause " (snake.name)} { "Pause" }
+                                                button type="submit" class="btn sm" aria-label={(t.t("leaderboard.pause")) " " (snake.name)} { (t.t("leaderboard.pause")) }
                                             }
                                         }
                                     }
@@ -470,17 +515,17 @@ pub async fn show_leaderboard(
                                 .collect();
                             @if !joinable.is_empty() {
                                 form class="join-form" action={"/leaderboards/"(leaderboard_id)"/join"} method="post" {
-                                    select name="battlesnake_id" aria-label="Snake to join with" {
+                                    select name="battlesnake_id" aria-label=(t.t("leaderboard.snake_to_join")) {
                                         @for snake in joinable {
                                             option value=(snake.battlesnake_id) { (snake.name) }
                                         }
                                     }
-                                    button type="submit" class="btn solid sm" { "Join" }
+                                    button type="submit" class="btn solid sm" { (t.t("leaderboard.join")) }
                                 }
                             } @else if user_entries.is_empty() {
                                 p class="railp" {
-                                    "You need a public snake to join. "
-                                    a href="/battlesnakes/new" style="color:var(--pink)" { "Register one" }
+                                    (t.t("leaderboard.need_public_snake")) " "
+                                    a href="/battlesnakes/new" style="color:var(--pink)" { (t.t("leaderboard.register_one")) }
                                 }
                             }
                         }
@@ -488,7 +533,7 @@ pub async fn show_leaderboard(
 
                     @if !top_eaters.is_empty() {
                         div class="block" {
-                            h3 { "Top eaters" }
+                            h3 { (t.t("leaderboard.top_eaters")) }
                             ul class="feed" {
                                 @for (i, eater) in top_eaters.iter().enumerate() {
                                     li {
@@ -498,11 +543,11 @@ pub async fn show_leaderboard(
                                                 b { (eater.snake_name) }
                                             }
                                             span class="owner-inline" {
-                                                " by "
+                                                " " (t.t("common.by")) " "
                                                 a href={"/users/"(eater.owner_login)} { (eater.owner_login) }
                                             }
                                             " · "
-                                            span class="place" { (eater.food_score) " food" }
+                                            span class="place" { (t.t_with("leaderboard.food", &[("count", &eater.food_score)])) }
                                         }
                                     }
                                 }
@@ -512,19 +557,158 @@ pub async fn show_leaderboard(
 
                     @if user.is_none() {
                         div class="block" {
-                            h3 { "Your snake here" }
-                            p class="railp" {
-                                "Deploy a server, register your snake, and the matchmaker "
-                                "takes it from there — new ranked games every few minutes."
-                            }
-                            a class="btn solid sm" style="margin-top:14px; display:inline-block" href="/auth/github" { "Sign in to join" }
+                            h3 { (t.t("leaderboard.your_snake_here")) }
+                            p class="railp" { (t.t("leaderboard.pitch")) }
+                            a class="btn solid sm" style="margin-top:14px; display:inline-block" href="/auth/github" { (t.t("leaderboard.sign_in_to_join")) }
                         }
                     }
                 }
             }
         }),
     )
-    .with_description(description))
+    .with_description(description)
+    .with_image(preview::leaderboard_preview_url(
+        &state.config.base_url,
+        leaderboard_id,
+    )))
+}
+
+/// Games listed on a leaderboard's live tab
+const LIVE_GAMES_LIMIT: i64 = 50;
+
+/// Switches between a leaderboard's standings and its live games
+fn leaderboard_view_tabs(
+    t: Locale,
+    leaderboard_id: Uuid,
+    games_in_progress: i64,
+    live: bool,
+) -> Markup {
+    let live_label = html! {
+        @if games_in_progress > 0 { span class="live-dot" {} }
+        (t.t("common.live"))
+        span class="n" { (games_in_progress) }
+    };
+    html! {
+        nav class="modes" aria-label=(t.t("leaderboard.views")) {
+            @if live {
+                a class="mode" href={"/leaderboards/"(leaderboard_id)} { (t.t("leaderboard.standings")) }
+                span class="mode on" aria-current="page" { (live_label) }
+            } @else {
+                span class="mode on" aria-current="page" { (t.t("leaderboard.standings")) }
+                a class="mode" href={"/leaderboards/"(leaderboard_id)"/live"} { (live_label) }
+            }
+        }
+    }
+}
+
+/// GET /leaderboards/{id}/live — the leaderboard's games in progress, each
+/// linking to its live spectator view
+pub async fn show_leaderboard_live(
+    State(state): State<AppState>,
+    Path(leaderboard_id): Path<Uuid>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let lb = leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard")?
+        .ok_or_else(|| {
+            crate::errors::ServerError(
+                color_eyre::eyre::eyre!("Leaderboard not found"),
+                StatusCode::NOT_FOUND,
+            )
+        })?;
+
+    let games = leaderboard::get_live_games(&state.db, leaderboard_id, LIVE_GAMES_LIMIT)
+        .await
+        .wrap_err("Failed to fetch live games")?;
+    // The list stops at LIVE_GAMES_LIMIT, so the tab badge takes the full
+    // count, the same one the standings tab shows
+    let status = state
+        .leaderboard_status(leaderboard_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard status")?;
+    let game_ids: Vec<Uuid> = games.iter().map(|g| g.game_id).collect();
+    let mut snakes_by_game: HashMap<Uuid, Vec<GameSnakeSummary>> = HashMap::new();
+    for snake in game_battlesnake::get_snake_summaries_for_games(&state.db, &game_ids)
+        .await
+        .wrap_err("Failed to fetch snakes for live games")?
+    {
+        snakes_by_game.entry(snake.game_id).or_default().push(snake);
+    }
+    let t = page_factory.locale;
+
+    Ok(page_factory
+        .create_page(
+            format!("{} — {}", lb.name, t.t("common.live")),
+            Box::new(html! {
+                div class="crumb" {
+                    a href="/leaderboards" { (t.t("leaderboards.title")) }
+                    " / "
+                    a href={"/leaderboards/"(leaderboard_id)} { (lb.name) }
+                    " / " (t.t("common.live"))
+                }
+                div class="page-head" {
+                    h1 { (lb.name) }
+                    div class="sub" { (t.t("leaderboard.live.intro")) }
+                }
+
+                (leaderboard_view_tabs(t, leaderboard_id, status.games_in_progress, true))
+
+                div class="section" {
+                    @if games.is_empty() {
+                        p class="empty" {
+                            @if lb.frozen_at.is_some() {
+                                (t.t("leaderboard.live.frozen"))
+                            } @else {
+                                (t.t("leaderboard.live.empty"))
+                            }
+                        }
+                    } @else {
+                        table class="data" {
+                            thead {
+                                tr {
+                                    th { (t.t("leaderboard.live.snakes")) }
+                                    th class="r" { (t.t("leaderboard.live.turn")) }
+                                    th class="hide-sm" { (t.t("leaderboard.live.game")) }
+                                    th class="r hide-sm" { (t.t("leaderboard.live.started")) }
+                                    th class="r" {}
+                                }
+                            }
+                            tbody {
+                                @for game in &games {
+                                    tr {
+                                        td {
+                                            @for snake in snakes_by_game.get(&game.game_id).map(Vec::as_slice).unwrap_or_default() {
+                                                div class="snake-cell" {
+                                                    span class="chip" style={"background:"(chip_color(&snake.color))} {}
+                                                    span class="name" { (snake.name) }
+                                                }
+                                            }
+                                        }
+                                        td class="r num" {
+                                            @if let Some(turn) = game.turn {
+                                                (turn)
+                                            } @else {
+                                                (t.t("leaderboard.live.starting"))
+                                            }
+                                        }
+                                        td class="hide-sm" { (game.game_type) " · " (game.board_size) }
+                                        td class="r num hide-sm" { (fmt_ago(game.created_at)) }
+                                        td class="r" {
+                                            a class="btn sm solid" href={"/games/"(game.game_id)} { (t.t("common.watch")) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }),
+        )
+        .with_description(format!(
+            "Ranked games being played right now on the {} leaderboard.",
+            lb.name
+        )))
 }
 
 /// GET /leaderboards/:id/entries/:entry_id — snake detail on leaderboard
@@ -585,7 +769,8 @@ pub async fn show_leaderboard_entry(
 
     let per_page: i64 = 20;
 
-    let total_games = leaderboard::count_game_results_for_entry(&state.db, entry_id)
+    // Game history is the heavy part of this page; it reads from the replica
+    let total_games = leaderboard::count_game_results_for_entry(&state.read_db, entry_id)
         .await
         .wrap_err("Failed to count game results")?;
 
@@ -596,13 +781,13 @@ pub async fn show_leaderboard_entry(
     };
     let page = pagination.page.unwrap_or(0).max(0).min(total_pages - 1);
 
-    let history = leaderboard::get_game_history_for_entry(&state.db, entry_id, page, per_page)
+    let history = leaderboard::get_game_history_for_entry(&state.read_db, entry_id, page, per_page)
         .await
         .wrap_err("Failed to fetch game history")?;
 
     let game_ids: Vec<Uuid> = history.iter().map(|h| h.game_id).collect();
     let opponents_list = if !game_ids.is_empty() {
-        leaderboard::get_opponents_for_games(&state.db, &game_ids, entry_id)
+        leaderboard::get_opponents_for_games(&state.read_db, &game_ids, entry_id)
             .await
             .wrap_err("Failed to fetch opponents")?
     } else {
@@ -614,10 +799,14 @@ pub async fn show_leaderboard_entry(
         opponents_map.entry(opp.game_id).or_default().push(opp);
     }
 
-    let rating_points = leaderboard::get_rating_history_for_entry(&state.db, entry_id)
+    let rating_points = leaderboard::get_rating_history_for_entry(&state.read_db, entry_id)
         .await
         .wrap_err("Failed to fetch rating history")?;
 
+    let versions = battlesnake_version::list_for_battlesnake(&state.read_db, snake.battlesnake_id)
+        .await
+        .wrap_err("Failed to fetch snake versions")?;
+
     let rank = leaderboard::get_rank_for_entry(
         &state.db,
         leaderboard_id,
@@ -628,7 +817,7 @@ pub async fn show_leaderboard_entry(
     .wrap_err("Failed to get rank")?;
 
     // Compute SVG chart data
-    let (points_str, grid_y_positions, y_labels) = if rating_points.len() >= 2 {
+    let (points_str, grid_y_positions, y_labels, version_markers) = if rating_points.len() >= 2 {
         let min_score = rating_points
             .iter()
             .map(|p| p.display_score_after)
@@ -677,11 +866,44 @@ pub async fn show_leaderboard_entry(
             })
             .collect();
 
-        (points_str, grid_y, labels)
+        // A dashed line wherever a new version went live mid-chart. The
+        // first version is where the snake started, not a change.
+        let markers: Vec<(String, String)> = versions
+            .iter()
+            .skip(1)
+            .filter(|v| {
+                let ts = v.created_at.timestamp() as f64;
+                ts >= first_ts && ts <= last_ts
+            })
+            .map(|v| {
+                let x = 40.0 + (v.created_at.timestamp() as f64 - first_ts) / ts_range * 560.0;
+                (format!("{x:.0}"), version_note(v))
+            })
+            .collect();
+
+        (points_str, grid_y, labels, markers)
     } else {
-        (String::new(), vec![], vec![])
+        (String::new(), vec![], vec![], vec![])
     };
 
+    // Each game's version, and whether that version went live between it
+    // and the next (older) game on the page, where history gets a marker row
+    let history_versions: Vec<(Option<&SnakeVersion>, bool)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, game)| {
+            let version = battlesnake_version::version_at(&versions, game.game_created_at);
+            let went_live = match (version, history.get(i + 1)) {
+                (Some(v), Some(older)) => {
+                    battlesnake_version::version_at(&versions, older.game_created_at)
+                        .is_none_or(|o| o.battlesnake_version_id != v.battlesnake_version_id)
+                }
+                _ => false,
+            };
+            (version, went_live)
+        })
+        .collect();
+
     // Recent form: always the 5 most recent games (independent of current page)
     let recent_games = leaderboard::get_game_history_for_entry(&state.db, entry_id, 0, 5)
         .await
@@ -835,6 +1057,12 @@ pub async fn show_leaderboard_entry(
                                 line x1="40" y1=(y_line) x2="600" y2=(y_line)
                                      stroke="#eee" stroke-width="1" {}
                             }
+                            @for (x, note) in &version_markers {
+                                line x1=(x) y1="10" x2=(x) y2="210"
+                                     stroke="#d9822b" stroke-width="1" stroke-dasharray="4 3" {
+                                    title { (note) }
+                                }
+                            }
                             polyline
                                 points=(points_str)
                                 fill="none" stroke="#4a90d9" stroke-width="2" {}
@@ -860,11 +1088,12 @@ pub async fn show_leaderboard_entry(
                                 th { "Placement" }
                                 th { "Rating Change" }
                                 th { "Food" }
+                                th { "Version" }
                                 th { "Replay" }
                             }
                         }
                         tbody {
-                            @for game in &history {
+                            @for (game, (version, went_live)) in history.iter().zip(&history_versions) {
                                 tr {
                                     td { (game.game_created_at.format("%Y-%m-%d %H:%M")) }
                                     td {
@@ -897,10 +1126,24 @@ pub async fn show_leaderboard_entry(
                                         }
                                     }
                                     td { (game.food_eaten) }
+                                    td {
+                                        @if let Some(v) = version {
+                                            (v.label())
+                                        } @else {
+                                            span style="color: #999;" { "-" }
+                                        }
+                                    }
                                     td {
                                         a href={"/games/"(game.game_id)} class="btn btn-sm btn-primary" { "Watch" }
                                     }
                                 }
+                                @if let (Some(v), true) = (version, went_live) {
+                                    tr {
+                                        td colspan="7" style="color: #d9822b; font-size: 0.9em;" {
+                                            "▲ " (version_note(v))
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -976,6 +1219,15 @@ pub async fn join_leaderboard(
         return Ok(redirect);
     }
 
+    if user.is_suspended() {
+        flasher
+            .error("Your account is suspended, so your snakes can't join leaderboards.")
+            .await
+            .wrap_err("Failed to flash")
+            .with_redirect(redirect.clone())?;
+        return Ok(redirect);
+    }
+
     // Verify snake belongs to user and is public
     let snake = battlesnake::get_battlesnake_by_id(&state.db, form.battlesnake_id)
         .await
@@ -1008,6 +1260,23 @@ pub async fn join_leaderboard(
         return Ok(redirect);
     }
 
+    let verified = snake_url_verification::is_verified(&state.db, snake.battlesnake_id)
+        .await
+        .wrap_err("Failed to check snake URL verification")
+        .with_redirect(redirect.clone())?;
+    if !verified {
+        flasher
+            .error(format!(
+                "Verify that you control {}'s URL before it joins leaderboards — \
+                 see the verification steps on its profile page.",
+                snake.name
+            ))
+            .await
+            .wrap_err("Failed to flash")
+            .with_redirect(redirect.clone())?;
+        return Ok(redirect);
+    }
+
     // Opt-in (or resume if paused)
     let entry = leaderboard::get_or_create_entry(&state.db, leaderboard_id, form.battlesnake_id)
         .await
@@ -1030,6 +1299,7 @@ pub async fn join_leaderboard(
             .wrap_err("Failed to initialize scoring")
             .with_redirect(redirect.clone())?;
     }
+    state.invalidate_leaderboard(leaderboard_id);
 
     flasher
         .success(format!(
@@ -1099,12 +1369,13 @@ pub async fn leave_leaderboard(
     .await
     .wrap_err("Failed to pause entry")
     .with_redirect(redirect.clone())?;
+    state.invalidate_leaderboard(leaderboard_id);
 
     Ok(redirect)
 }
 
 /// Compact relative time for rail feeds ("2m ago"), where HumanTime is too wordy.
-fn fmt_ago(t: chrono::DateTime<chrono::Utc>) -> String {
+pub(crate) fn fmt_ago(t: chrono::DateTime<chrono::Utc>) -> String {
     let secs = (chrono::Utc::now() - t).num_seconds().max(0);
     match secs {
         0..=59 => format!("{secs}s ago"),
@@ -1114,7 +1385,21 @@ fn fmt_ago(t: chrono::DateTime<chrono::Utc>) -> String {
     }
 }
 
-fn ordinal(n: i32) -> String {
+/// Hover text and marker row text for a version going live
+fn version_note(version: &SnakeVersion) -> String {
+    let what = if version.url_changed {
+        "new URL"
+    } else {
+        "new version"
+    };
+    format!(
+        "Version {} ({what}) went live {}",
+        version.label(),
+        version.created_at.format("%Y-%m-%d %H:%M")
+    )
+}
+
+pub(crate) fn ordinal(n: i32) -> String {
     match n {
         1 => "1st".to_string(),
         2 => "2nd".to_string(),
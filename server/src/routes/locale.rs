@@ -0,0 +1,57 @@
+use axum::{
+    Form,
+    response::{IntoResponse, Redirect},
+};
+use cja::server::cookies::{Cookie, CookieJar};
+use serde::Deserialize;
+
+use crate::i18n::{LOCALE_COOKIE_NAME, Locale};
+
+/// How long a language choice sticks
+const LOCALE_COOKIE_MAX_AGE_DAYS: i64 = 365;
+
+#[derive(Debug, Deserialize)]
+pub struct LocaleForm {
+    pub locale: String,
+    #[serde(default)]
+    pub return_to: String,
+}
+
+/// Only same-site paths, so the picker can't be used as an open redirect
+fn safe_return_to(return_to: &str) -> &str {
+    if return_to.starts_with('/') && !return_to.starts_with("//") && !return_to.contains('\\') {
+        return_to
+    } else {
+        "/"
+    }
+}
+
+/// POST /locale — the footer language picker. Remembers the choice in a
+/// cookie, which wins over `Accept-Language`, and goes back to the page.
+pub async fn set_locale(cookie_jar: CookieJar, Form(form): Form<LocaleForm>) -> impl IntoResponse {
+    if let Some(locale) = Locale::from_tag(&form.locale) {
+        let mut cookie = Cookie::new(LOCALE_COOKIE_NAME, locale.tag());
+        cookie.set_path("/");
+        cookie.set_http_only(true);
+        cookie.set_secure(true);
+        cookie.set_same_site(cja::server::cookies::SameSite::Lax);
+        cookie.set_max_age(time::Duration::days(LOCALE_COOKIE_MAX_AGE_DAYS));
+        cookie_jar.add(cookie);
+    }
+
+    Redirect::to(safe_return_to(&form.return_to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_return_to_stays_on_site() {
+        assert_eq!(safe_return_to("/leaderboards/abc"), "/leaderboards/abc");
+        assert_eq!(safe_return_to("//evil.example"), "/");
+        assert_eq!(safe_return_to("/\\evil.example"), "/");
+        assert_eq!(safe_return_to("https://evil.example"), "/");
+        assert_eq!(safe_return_to(""), "/");
+    }
+}
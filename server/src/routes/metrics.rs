@@ -0,0 +1,142 @@
+//! `GET /metrics` for Prometheus, plus the middleware that feeds its HTTP
+//! latency histograms.
+//!
+//! Admin-only, like `/api/v1/admin/stats`: configure the scraper with an
+//! admin-scoped API token as its bearer token.
+
+use std::fmt::Write as _;
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    metrics::{METRICS, escape_label, write_header},
+    routes::{
+        admin::LiveMetrics,
+        api::error::{ApiError, ApiResult},
+        auth::AdminApiUser,
+    },
+    state::AppState,
+};
+
+/// Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET /metrics - Process counters and histograms plus job queue and game
+/// gauges read from the database at scrape time
+pub async fn metrics(
+    State(state): State<AppState>,
+    AdminApiUser(_admin): AdminApiUser,
+) -> ApiResult<impl IntoResponse> {
+    let live = LiveMetrics::fetch(&state.db)
+        .await
+        .map_err(|e| ApiError::internal("Failed to fetch live metrics", e))?;
+
+    let mut out = METRICS.render();
+    render_live(&mut out, &live);
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], out))
+}
+
+fn render_live(out: &mut String, live: &LiveMetrics) {
+    write_header(
+        out,
+        "arena_job_queue_jobs",
+        "gauge",
+        "Jobs in the queue by state",
+    );
+    for (state, count) in [
+        ("ready", live.job_queue.ready),
+        ("running", live.job_queue.running),
+        ("scheduled", live.job_queue.scheduled),
+    ] {
+        let _ = writeln!(out, "arena_job_queue_jobs{{state=\"{state}\"}} {count}");
+    }
+
+    write_header(
+        out,
+        "arena_jobs_by_name",
+        "gauge",
+        "Jobs in the queue by job name",
+    );
+    for job in &live.jobs_by_name {
+        let _ = writeln!(
+            out,
+            "arena_jobs_by_name{{name=\"{}\"}} {}",
+            escape_label(&job.name),
+            job.count
+        );
+    }
+
+    write_header(out, "arena_games", "gauge", "Games by status");
+    for (status, count) in [
+        ("waiting", live.game_counts.waiting),
+        ("running", live.game_counts.running),
+        ("finished", live.game_counts.finished),
+    ] {
+        let _ = writeln!(out, "arena_games{{status=\"{status}\"}} {count}");
+    }
+}
+
+/// Time every routed request into `arena_http_request_duration_seconds`.
+/// Installed with `route_layer` so the matched route template is known;
+/// requests that fall through to the 404 fallback aren't recorded.
+pub async fn track_http_latency(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let method = request.method().clone();
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    if let Some(route) = route {
+        METRICS.observe_http(
+            method.as_str(),
+            &route,
+            response.status().as_u16(),
+            started.elapsed(),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::admin::{GameCountMetrics, JobNameCount, JobQueueMetrics};
+
+    #[test]
+    fn test_render_live_gauges() {
+        let live = LiveMetrics {
+            job_queue: JobQueueMetrics {
+                ready: 4,
+                running: 1,
+                scheduled: 2,
+                total: 7,
+            },
+            jobs_by_name: vec![JobNameCount {
+                name: "GameRunnerJob".to_string(),
+                count: 5,
+            }],
+            game_counts: GameCountMetrics {
+                waiting: 0,
+                running: 3,
+                finished: 10,
+                total: 13,
+            },
+        };
+
+        let mut out = String::new();
+        render_live(&mut out, &live);
+        assert!(out.contains("# TYPE arena_job_queue_jobs gauge\n"));
+        assert!(out.contains("arena_job_queue_jobs{state=\"ready\"} 4\n"));
+        assert!(out.contains("arena_jobs_by_name{name=\"GameRunnerJob\"} 5\n"));
+        assert!(out.contains("arena_games{status=\"finished\"} 10\n"));
+    }
+}
@@ -0,0 +1,99 @@
+//! The notifications inbox: rank milestones and timeout streaks for the
+//! logged-in user's snakes. What gets raised lives in `crate::notifications`.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+};
+use color_eyre::eyre::Context as _;
+use maud::html;
+
+use crate::{
+    components::page_factory::PageFactory, errors::ServerResult, models::notification,
+    routes::auth::CurrentUser, state::AppState,
+};
+
+/// Notifications shown in the inbox.
+const INBOX_LIMIT: i64 = 50;
+
+/// GET /notifications
+pub async fn inbox(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let notifications = notification::list_for_user(&state.db, user.user_id, INBOX_LIMIT)
+        .await
+        .wrap_err("Failed to list notifications")?;
+    let unread = notifications.iter().filter(|n| n.read_at.is_none()).count();
+
+    Ok(page_factory.create_page(
+        "Notifications".to_string(),
+        Box::new(html! {
+            div class="crumb" { a href="/me" { "My Profile" } " / Notifications" }
+            div class="page-head" {
+                div {
+                    h1 { "Notifications" }
+                    div class="sub" {
+                        "Rank milestones and timeout streaks for your snakes. "
+                        a href="/settings/notifications" { "Choose how you hear about them" }
+                        "."
+                    }
+                }
+                @if unread > 0 {
+                    form action="/notifications/read" method="post" {
+                        button type="submit" class="btn" { "Mark all read" }
+                    }
+                }
+            }
+
+            @if notifications.is_empty() {
+                p class="empty" { "Nothing yet." }
+            } @else {
+                div class="section" {
+                    table class="data" {
+                        thead {
+                            tr {
+                                th { "" }
+                                th { "Notification" }
+                                th class="r hide-sm" { "When" }
+                            }
+                        }
+                        tbody {
+                            @for n in &notifications {
+                                tr {
+                                    td {
+                                        @if n.read_at.is_none() {
+                                            span class="badge ok" { "new" }
+                                        }
+                                    }
+                                    td {
+                                        @if let Some(link) = &n.link {
+                                            a href=(link) { (n.message) }
+                                        } @else {
+                                            (n.message)
+                                        }
+                                    }
+                                    td class="r hide-sm" { (n.created_at.format("%Y-%m-%d %H:%M UTC")) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /notifications/read
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    notification::mark_all_read(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to mark notifications read")?;
+
+    Ok(Redirect::to("/notifications"))
+}
@@ -0,0 +1,322 @@
+//! "Play as a snake": pick one of your snakes, steer it with the keyboard
+//! against snakes from matchmaking, and watch it on the usual board viewer.
+//! Key presses reach the game over a WebSocket; see
+//! [`crate::models::human_player`] for how the runner uses them.
+
+use std::time::Duration;
+
+use axum::{
+    Form,
+    extract::{
+        Path, State, WebSocketUpgrade,
+        ws::{CloseFrame, Message, WebSocket, close_code},
+    },
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use color_eyre::eyre::Context as _;
+use futures::{SinkExt as _, StreamExt as _};
+use maud::html;
+use rules::Direction;
+use serde::Deserialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    errors::{ServerResult, WithStatus},
+    human_play::TURN_INTERVAL,
+    jobs::GameRunnerJob,
+    models::{
+        battlesnake,
+        game::{self, GameBoardSize, GameStatus},
+        game_battlesnake,
+        human_player::{self, MAX_OPPONENTS},
+        session,
+    },
+    routes::{
+        auth::{CurrentUser, CurrentUserWithSession},
+        challenge::game_creation_refusal,
+        game::view::{BoardParams, board_iframe_src},
+    },
+    state::AppState,
+    static_assets::asset_url,
+};
+
+/// How often the input socket checks whether the game has ended
+const GAME_END_POLL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize)]
+pub struct PlayForm {
+    pub battlesnake_id: Uuid,
+    pub board_size: String,
+    pub opponents: i64,
+}
+
+async fn flash_redirect(
+    state: &AppState,
+    session_id: Uuid,
+    message: String,
+) -> ServerResult<Response, StatusCode> {
+    session::set_flash_message(&state.db, session_id, message, session::FLASH_TYPE_ERROR)
+        .await
+        .wrap_err("Failed to set flash message")?;
+    Ok(Redirect::to("/play").into_response())
+}
+
+/// GET /play — pick a snake to steer and start a game
+pub async fn new_play(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let my_snakes = battlesnake::get_battlesnakes_by_user_id(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch user's battlesnakes")?;
+
+    Ok(page_factory.create_page(
+        "Play as a Snake".to_string(),
+        Box::new(html! {
+            div class="page-head" {
+                div {
+                    h1 { "Play as a Snake" }
+                    div class="sub" {
+                        "Steer one of your snakes with the keyboard against snakes from the leaderboards. "
+                        "Turns run every " (TURN_INTERVAL.as_millis()) "ms so you can keep up, "
+                        "and the game doesn't affect any rankings."
+                    }
+                }
+            }
+
+            @if my_snakes.is_empty() {
+                p class="empty" {
+                    "You play as one of your snakes, so you'll need one first. "
+                    a href="/battlesnakes/new" { "Create one" }
+                }
+            } @else {
+                form class="form-stack" action="/play" method="post" {
+                    div class="field" {
+                        label for="battlesnake_id" { "Play As" }
+                        select id="battlesnake_id" name="battlesnake_id" required {
+                            @for snake in &my_snakes {
+                                option value=(snake.battlesnake_id) { (snake.name) }
+                            }
+                        }
+                        p class="help" { "It keeps its colors; its URL isn't called while you steer." }
+                    }
+
+                    div class="field" {
+                        label for="board_size" { "Board Size" }
+                        select id="board_size" name="board_size" required {
+                            option value="7x7" { "7x7 (Small)" }
+                            option value="11x11" selected { "11x11 (Medium)" }
+                            option value="19x19" { "19x19 (Large)" }
+                        }
+                    }
+
+                    div class="field" {
+                        label for="opponents" { "Opponents" }
+                        select id="opponents" name="opponents" required {
+                            @for n in 1..=MAX_OPPONENTS {
+                                option value=(n) selected[n == 1] { (n) }
+                            }
+                        }
+                        p class="help" { "Picked at random from public snakes in matchmaking." }
+                    }
+
+                    div class="form-cta" {
+                        button type="submit" class="btn solid" { "Start Game" }
+                    }
+                }
+            }
+        }),
+    ))
+}
+
+/// POST /play — create the game and send the player to it
+pub async fn create_play(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Form(form): Form<PlayForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let board_size = match GameBoardSize::from_str(&form.board_size) {
+        Ok(GameBoardSize::Custom(_)) | Err(_) => {
+            return Err("Invalid board size".to_string()).with_status(StatusCode::BAD_REQUEST);
+        }
+        Ok(board_size) => board_size,
+    };
+    if let Some(message) = game_creation_refusal(&state, &user, "play").await? {
+        return flash_redirect(&state, session.session_id, message).await;
+    }
+
+    let game = match human_player::create_human_game(
+        &state.db,
+        user.user_id,
+        form.battlesnake_id,
+        board_size,
+        form.opponents,
+    )
+    .await?
+    {
+        Ok(game) => game,
+        Err(message) => return flash_redirect(&state, session.session_id, message).await,
+    };
+
+    game::set_game_enqueued_at(&state.db, game.game_id, chrono::Utc::now())
+        .await
+        .wrap_err("Failed to set enqueued_at")?;
+    cja::jobs::Job::enqueue(
+        GameRunnerJob {
+            game_id: game.game_id,
+        },
+        state.clone(),
+        format!("Game {} created via play", game.game_id),
+        None,
+    )
+    .await
+    .wrap_err("Failed to enqueue game runner job")?;
+    let snake_count = game_battlesnake::get_battlesnakes_by_game_id(&state.db, game.game_id)
+        .await?
+        .len();
+    crate::events::game_created(
+        game.game_id,
+        &game.board_size,
+        &game.game_type,
+        snake_count,
+        crate::events::GameSource::Play,
+        Some(user.user_id),
+    );
+
+    Ok(Redirect::to(&format!("/play/{}", game.game_id)).into_response())
+}
+
+/// GET /play/{id} — the board and the controls, for the game's player.
+/// Everyone else is sent to the ordinary game page.
+pub async fn show_play(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(game_id): Path<Uuid>,
+    page_factory: PageFactory,
+) -> ServerResult<Response, StatusCode> {
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await
+        .wrap_err("Failed to get game")?
+        .ok_or_else(|| "Game not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+    let is_player = human_player::get_for_game(&state.db, game_id)
+        .await?
+        .is_some_and(|human| human.user_id == user.user_id);
+    if !is_player || game.status == GameStatus::Finished {
+        return Ok(Redirect::to(&format!("/games/{game_id}")).into_response());
+    }
+
+    let iframe_src = board_iframe_src(&state.config.base_url, game_id, &BoardParams::default());
+
+    Ok(page_factory
+        .create_page(
+            "Play".to_string(),
+            Box::new(html! {
+                div class="page-head" {
+                    div {
+                        h1 { "You're Playing" }
+                        div class="sub" {
+                            "Arrow keys or WASD to steer. Your snake keeps going the way it faces until you turn it, "
+                            "and can't reverse into itself."
+                        }
+                    }
+                }
+
+                div #play data-game-id=(game_id) {
+                    div class="board-wrap" {
+                        div #board-viewer-container style="width: 100%; aspect-ratio: 16 / 9;" {
+                            iframe
+                                id="board-viewer"
+                                src=(iframe_src)
+                                title="Battlesnake Board Viewer"
+                                allow="autoplay"
+                                tabindex="-1" {}
+                        }
+                    }
+                    script {
+                        "window.addEventListener('message', function(e) {"
+                            "if (e.origin !== 'https://board.battlesnake.com') return;"
+                            "var evt = e.data;"
+                            "if (evt.event === 'RESIZE') {"
+                                "document.getElementById('board-viewer-container').style"
+                                    ".setProperty('aspect-ratio', evt.data.width + ' / ' + evt.data.height);"
+                            "}"
+                        "});"
+                    }
+                    p class="text-muted" data-play-status { "Connecting…" }
+                    div class="reg-actions" aria-label="Steering" {
+                        button type="button" class="btn sm" data-dir="left" { "←" }
+                        button type="button" class="btn sm" data-dir="up" { "↑" }
+                        button type="button" class="btn sm" data-dir="down" { "↓" }
+                        button type="button" class="btn sm" data-dir="right" { "→" }
+                    }
+                    div class="form-cta" {
+                        a href={"/games/"(game_id)} class="btn" { "Game Page" }
+                        a href="/play" class="btn" { "New Game" }
+                    }
+                }
+                script src=(asset_url("playInput.js")) defer {}
+            }),
+        )
+        .into_response())
+}
+
+/// GET /play/{id}/input — WebSocket the player's browser sends headings on,
+/// as `up`, `down`, `left` or `right`. Closed normally once the game ends.
+pub async fn play_input_websocket(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    Path(game_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    human_player::get_for_game(&state.db, game_id)
+        .await?
+        .filter(|human| human.user_id == user.user_id)
+        .ok_or_else(|| "Not your game to steer".to_string())
+        .with_status(StatusCode::FORBIDDEN)?;
+
+    Ok(ws.on_upgrade(move |socket| handle_input(socket, state, game_id, user.user_id)))
+}
+
+async fn handle_input(socket: WebSocket, state: AppState, game_id: Uuid, user_id: Uuid) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut poll = tokio::time::interval(GAME_END_POLL);
+
+    loop {
+        tokio::select! {
+            message = receiver.next() => {
+                let Some(Ok(message)) = message else {
+                    return;
+                };
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let Ok(direction) = Direction::from_str(text.trim()) else {
+                    continue;
+                };
+                if let Err(e) = human_player::set_heading(&state.db, game_id, user_id, direction).await {
+                    tracing::warn!(game_id = %game_id, error = ?e, "Failed to store human heading");
+                }
+            }
+            _ = poll.tick() => {
+                let finished = matches!(
+                    game::get_game_by_id(&state.db, game_id).await,
+                    Ok(Some(game)) if game.status == GameStatus::Finished
+                );
+                if finished {
+                    let _ = sender
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::NORMAL,
+                            reason: "Game over".into(),
+                        })))
+                        .await;
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Open Graph preview images for game and leaderboard links. The URLs are
+//! stable so unfurlers can cache them; the pages point `og:image` here.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use color_eyre::eyre::{Context as _, eyre};
+use uuid::Uuid;
+
+use crate::{
+    engine_models::EngineGameFrame,
+    errors::{ServerError, ServerResult},
+    frame_archive,
+    models::{
+        game::{self, GameStatus},
+        leaderboard::{self, LeaderboardSort},
+    },
+    preview_image::{self, PreviewKey},
+    routes::game::view::ViewGameParams,
+    state::AppState,
+};
+
+/// Absolute URL of a game's preview. Mirrors the game page's spoiler
+/// choice so an unfurl never shows more than the link's description does.
+pub fn game_preview_url(base_url: &str, game_id: Uuid, spoilers: bool) -> String {
+    let suffix = if spoilers { "?showSpoilers" } else { "" };
+    format!("{base_url}/games/{game_id}/preview.png{suffix}")
+}
+
+/// Absolute URL of a leaderboard's podium preview
+pub fn leaderboard_preview_url(base_url: &str, leaderboard_id: Uuid) -> String {
+    format!("{base_url}/leaderboards/{leaderboard_id}/preview.png")
+}
+
+fn png_response(image: axum::body::Bytes, cache_control: &'static str) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        image,
+    )
+}
+
+/// GET /games/{id}/preview.png - The board as a PNG
+///
+/// A finished game shows its opening board unless the link opts into
+/// spoilers, since the final board gives away the winner. Games still in
+/// progress show their latest turn and aren't cached.
+pub async fn game_preview(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    Query(params): Query<ViewGameParams>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let game = game::get_game_by_id(&state.db, game_id)
+        .await
+        .wrap_err("Failed to fetch game")?
+        .ok_or_else(|| ServerError(eyre!("Game not found"), StatusCode::NOT_FOUND))?;
+    let finished = game.status == GameStatus::Finished;
+    let key = PreviewKey::Game {
+        game_id,
+        spoilers: params.show_spoilers(),
+    };
+
+    if finished && let Some(image) = state.preview_image_cache.get(&key) {
+        return Ok(png_response((*image).clone(), "public, max-age=86400"));
+    }
+
+    let frame = if finished && !params.show_spoilers() {
+        frame_archive::load_frames_page(&state, game_id, 0, 1)
+            .await
+            .wrap_err("Failed to load the opening frame")?
+            .into_iter()
+            .next()
+    } else {
+        frame_archive::load_last_frame(&state, game_id)
+            .await
+            .wrap_err("Failed to load the last frame")?
+            .map(|(_, frame)| frame)
+    };
+    let frame: Option<EngineGameFrame> = frame
+        .map(serde_json::from_value)
+        .transpose()
+        .wrap_err("Failed to parse frame")?;
+
+    let (width, height) = game.board_size.dimensions();
+    let image =
+        axum::body::Bytes::from(preview_image::render_board(width, height, frame.as_ref())?);
+
+    if finished {
+        state.preview_image_cache.put(key, image.clone());
+        Ok(png_response(image, "public, max-age=86400"))
+    } else {
+        Ok(png_response(image, "no-cache"))
+    }
+}
+
+/// GET /leaderboards/{id}/preview.png - The top three by rating as a podium
+pub async fn leaderboard_preview(
+    State(state): State<AppState>,
+    Path(leaderboard_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let key = PreviewKey::Leaderboard(leaderboard_id);
+    if let Some(image) = state.preview_image_cache.get(&key) {
+        return Ok(png_response((*image).clone(), "public, max-age=600"));
+    }
+
+    leaderboard::get_leaderboard_by_id(&state.db, leaderboard_id)
+        .await
+        .wrap_err("Failed to fetch leaderboard")?
+        .ok_or_else(|| ServerError(eyre!("Leaderboard not found"), StatusCode::NOT_FOUND))?;
+    let top = state
+        .standings(leaderboard_id, LeaderboardSort::Rating, Some((0, 3)))
+        .await
+        .wrap_err("Failed to fetch standings")?;
+
+    let colors = [0, 1, 2].map(|i| top.get(i).map(|e| e.snake_color.as_str()));
+    let image = axum::body::Bytes::from(preview_image::render_podium(colors)?);
+    state.preview_image_cache.put(key, image.clone());
+    Ok(png_response(image, "public, max-age=600"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::ServiceExt as _;
+
+    async fn get(pool: sqlx::PgPool, uri: &str) -> axum::response::Response {
+        crate::routes::routes(AppState::test_from_pool(pool))
+            .oneshot(
+                axum::extract::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn preview_urls_are_absolute() {
+        let id = Uuid::nil();
+        assert_eq!(
+            game_preview_url("https://arena.example", id, false),
+            format!("https://arena.example/games/{id}/preview.png")
+        );
+        assert_eq!(
+            game_preview_url("https://arena.example", id, true),
+            format!("https://arena.example/games/{id}/preview.png?showSpoilers")
+        );
+        assert_eq!(
+            leaderboard_preview_url("https://arena.example", id),
+            format!("https://arena.example/leaderboards/{id}/preview.png")
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn previews_render_as_png(pool: sqlx::PgPool) -> cja::Result<()> {
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status)
+             VALUES ('11x11', 'Standard', 'waiting') RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        let leaderboard_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO leaderboards (name) VALUES ('Duels') RETURNING leaderboard_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        for uri in [
+            format!("/games/{game_id}/preview.png"),
+            format!("/games/{game_id}/preview.png?showSpoilers"),
+            format!("/leaderboards/{leaderboard_id}/preview.png"),
+        ] {
+            let response = get(pool.clone(), &uri).await;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        }
+
+        let missing = Uuid::new_v4();
+        for uri in [
+            format!("/games/{missing}/preview.png"),
+            format!("/leaderboards/{missing}/preview.png"),
+        ] {
+            assert_eq!(
+                get(pool.clone(), &uri).await.status(),
+                StatusCode::NOT_FOUND,
+                "{uri}"
+            );
+        }
+        Ok(())
+    }
+}
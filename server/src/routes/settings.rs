@@ -1,10 +1,27 @@
-use axum::{Form, extract::State, http::StatusCode};
+use axum::{
+    Form, Json,
+    extract::{Path, RawForm, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Redirect},
+};
+use color_eyre::eyre::Context as _;
+use maud::{Markup, html};
 use serde::Deserialize;
+use uuid::Uuid;
 
 use crate::{
-    errors::ServerResult,
-    models::user::{SITE_THEMES, THEATER_THEMES, update_theme_preferences},
-    routes::auth::CurrentUser,
+    components::{page::Page, page_factory::PageFactory},
+    errors::{ServerResult, WithStatus},
+    jobs::DeleteAccountJob,
+    models::{
+        api_token::{self, ApiToken, NewApiToken, TokenScope},
+        data_export,
+        discord_webhook::{self, DiscordWebhook},
+        notification::{self, NotificationPreferences},
+        session,
+        user::{SITE_THEMES, THEATER_THEMES, update_theme_preferences},
+    },
+    routes::auth::{CurrentUser, CurrentUserWithSession},
     state::AppState,
 };
 
@@ -32,3 +49,741 @@ pub async fn update_appearance(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Lifetimes offered for a new token, in days, as (value, label). The form
+/// also offers "never".
+const TOKEN_EXPIRY_CHOICES: [(i64, &str); 3] = [(30, "30 days"), (90, "90 days"), (365, "1 year")];
+
+/// Preselected lifetime, and the one used when the form doesn't say
+const DEFAULT_TOKEN_EXPIRY_DAYS: i64 = 90;
+
+// Parsed new-token form. Parsed by hand because the scope checkboxes submit
+// a repeated `scopes` key, which `axum::Form` can't deserialize into a Vec.
+struct NewTokenForm {
+    name: String,
+    scopes: Vec<TokenScope>,
+    /// Days until the token expires; `None` never expires
+    expires_in_days: Option<i64>,
+}
+
+fn parse_new_token_form(bytes: &[u8]) -> Result<NewTokenForm, String> {
+    let mut name = None;
+    let mut scopes = Vec::new();
+    let mut expires_in_days = Some(DEFAULT_TOKEN_EXPIRY_DAYS);
+
+    for (key, value) in url::form_urlencoded::parse(bytes) {
+        match key.as_ref() {
+            "name" => name = Some(value.trim().to_string()),
+            "scopes" => scopes.push(
+                value
+                    .parse::<TokenScope>()
+                    .map_err(|_| format!("Invalid scope: {value}"))?,
+            ),
+            "expires_in" => {
+                expires_in_days = match value.as_ref() {
+                    "never" => None,
+                    days => Some(
+                        days.parse::<i64>()
+                            .ok()
+                            .filter(|d| TOKEN_EXPIRY_CHOICES.iter().any(|(c, _)| c == d))
+                            .ok_or_else(|| format!("Invalid expiry: {value}"))?,
+                    ),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(NewTokenForm {
+        name: name
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| "Name is required".to_string())?,
+        scopes,
+        expires_in_days,
+    })
+}
+
+fn token_expiry(token: &ApiToken) -> Markup {
+    html! {
+        @if token.is_expired() {
+            span class="badge warn" { "Expired" }
+        } @else if let Some(expires_at) = token.expires_at {
+            (expires_at.format("%Y-%m-%d"))
+        } @else {
+            "Never"
+        }
+    }
+}
+
+fn scope_badges(scopes: &[TokenScope]) -> Markup {
+    html! {
+        @if scopes.is_empty() {
+            span class="badge" { "read only" }
+        }
+        @for scope in scopes {
+            span class="badge ok" { (scope.as_str()) } " "
+        }
+    }
+}
+
+/// GET /settings/tokens — list, create, rotate, and revoke API tokens
+pub async fn tokens_page(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let tokens = api_token::list_user_tokens(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to list API tokens")?;
+    let grantable = api_token::grantable_scopes(user.is_admin);
+
+    let flash = page_factory.flash.clone();
+
+    Ok(page_factory.create_page_with_flash(
+        "API Tokens".to_string(),
+        Box::new(html! {
+            div class="crumb" { a href="/me" { "My Profile" } " / API tokens" }
+            div class="page-head" {
+                h1 { "API Tokens" }
+                div class="sub" {
+                    "Bearer tokens for the "
+                    a href="/api/docs" { "Arena API" }
+                    ". Any token can read data and manage your snakes; scopes unlock the rest."
+                }
+            }
+
+            @if tokens.is_empty() {
+                p class="empty" { "You don't have any API tokens yet." }
+            } @else {
+                div class="section" {
+                    table class="data" {
+                        thead {
+                            tr {
+                                th { "Name" }
+                                th { "Scopes" }
+                                th class="hide-sm" { "Last used" }
+                                th class="hide-sm" { "Created" }
+                                th { "Expires" }
+                                th class="r" { "Actions" }
+                            }
+                        }
+                        tbody {
+                            @for token in &tokens {
+                                tr {
+                                    td { (token.name) }
+                                    td { (scope_badges(&token.scopes())) }
+                                    td class="hide-sm" {
+                                        @if let Some(used) = token.last_used_at {
+                                            (used.format("%Y-%m-%d %H:%M UTC"))
+                                        } @else {
+                                            "Never"
+                                        }
+                                    }
+                                    td class="hide-sm" { (token.created_at.format("%Y-%m-%d")) }
+                                    td { (token_expiry(token)) }
+                                    td class="r" {
+                                        div class="row-actions" {
+                                            @if !token.is_expired() {
+                                                form action={"/settings/tokens/"(token.id)"/rotate"} method="post" {
+                                                    button type="submit" class="btn sm" onclick="return confirm('Rotate this token? The current secret will stop working immediately.');" { "Rotate" }
+                                                }
+                                            }
+                                            form action={"/settings/tokens/"(token.id)"/revoke"} method="post" {
+                                                button type="submit" class="btn sm danger" onclick="return confirm('Revoke this token? Anything using it will stop working.');" { "Revoke" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            h2 { "New Token" }
+            form class="form-stack" action="/settings/tokens" method="post" {
+                div class="field" {
+                    label for="name" { "Name" }
+                    input type="text" id="name" name="name" required maxlength="100" placeholder="stress-test";
+                    p class="help" { "Something to recognize it by, e.g. where it's used" }
+                }
+
+                div class="field" {
+                    label { "Scopes" }
+                    @for scope in &grantable {
+                        label style="display:flex; align-items:center; gap:6px; font-weight:normal; margin:0;" {
+                            input type="checkbox" name="scopes" value=(scope.as_str());
+                            code { (scope.as_str()) }
+                            " — " (scope.description())
+                        }
+                    }
+                    p class="help" { "Grant only what the token needs." }
+                }
+
+                div class="field" {
+                    label for="expires_in" { "Expiration" }
+                    select id="expires_in" name="expires_in" {
+                        @for (days, label) in TOKEN_EXPIRY_CHOICES {
+                            option value=(days) selected[days == DEFAULT_TOKEN_EXPIRY_DAYS] { (label) }
+                        }
+                        option value="never" { "Never" }
+                    }
+                    p class="help" { "An expired token stops working; make a new one to replace it." }
+                }
+
+                div class="form-cta" {
+                    button type="submit" class="btn solid" { "Create Token" }
+                }
+            }
+        }),
+        flash,
+    ))
+}
+
+// Show a token's secret right after it was created or rotated. Rendered
+// directly rather than flashed so the secret never gets stored in a session.
+fn token_secret_page(page_factory: PageFactory, heading: &str, new_token: &NewApiToken) -> Page {
+    page_factory.create_page(
+            heading.to_string(),
+            Box::new(html! {
+                div class="crumb" { a href="/settings/tokens" { "API tokens" } " / " (new_token.token.name) }
+                div class="page-head" {
+                    h1 { (heading) }
+                    div class="sub" { (scope_badges(&new_token.token.scopes())) }
+                }
+
+                div class="section" {
+                    p { "Copy this token now — it won't be shown again:" }
+                    pre style="word-break: break-all; white-space: pre-wrap;" { (new_token.secret) }
+                    p class="help" { "Send it as " code { "Authorization: Bearer <token>" } "." }
+                    p class="help" {
+                        @if let Some(expires_at) = new_token.token.expires_at {
+                            "It expires on " (expires_at.format("%Y-%m-%d %H:%M UTC")) "."
+                        } @else {
+                            "It never expires."
+                        }
+                    }
+                }
+
+                div class="form-cta" {
+                    a href="/settings/tokens" class="btn" { "Back to API Tokens" }
+                }
+            }),
+        )
+}
+
+/// POST /settings/tokens — create a token and show its secret once
+pub async fn create_token(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    page_factory: PageFactory,
+    RawForm(form_bytes): RawForm,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let form = parse_new_token_form(&form_bytes).with_status(StatusCode::BAD_REQUEST)?;
+
+    let grantable = api_token::grantable_scopes(user.is_admin);
+    if let Some(scope) = form.scopes.iter().find(|s| !grantable.contains(s)) {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            format!("You can't grant the {} scope", scope.as_str()),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+
+        return Ok(Redirect::to("/settings/tokens").into_response());
+    }
+
+    let expires_at = form
+        .expires_in_days
+        .map(|days| chrono::Utc::now() + chrono::Duration::days(days));
+    let new_token = api_token::create_api_token(
+        &state.db,
+        user.user_id,
+        &form.name,
+        &form.scopes,
+        expires_at,
+    )
+    .await
+    .wrap_err("Failed to create API token")?;
+
+    Ok(token_secret_page(page_factory, "Token Created", &new_token).into_response())
+}
+
+/// POST /settings/tokens/{id}/rotate — new secret for an existing token
+pub async fn rotate_token(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+    Path(token_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let rotated = api_token::rotate_token(&state.db, token_id, user.user_id)
+        .await
+        .wrap_err("Failed to rotate API token")?
+        .ok_or_else(|| "Token not found".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    Ok(token_secret_page(page_factory, "Token Rotated", &rotated))
+}
+
+/// POST /settings/tokens/{id}/revoke
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Path(token_id): Path<Uuid>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let revoked = api_token::revoke_token(&state.db, token_id, user.user_id)
+        .await
+        .wrap_err("Failed to revoke API token")?;
+
+    let (message, flash_type) = if revoked {
+        ("Token revoked.", session::FLASH_TYPE_SUCCESS)
+    } else {
+        ("Token not found.", session::FLASH_TYPE_ERROR)
+    };
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        message.to_string(),
+        flash_type,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/settings/tokens"))
+}
+
+/// Notification preferences form. Checkboxes are absent when unchecked.
+#[derive(Deserialize)]
+pub struct NotificationPreferencesForm {
+    #[serde(default)]
+    pub rank_milestones_in_app: bool,
+    #[serde(default)]
+    pub rank_milestones_email: bool,
+    #[serde(default)]
+    pub timeout_streaks_in_app: bool,
+    #[serde(default)]
+    pub timeout_streaks_email: bool,
+}
+
+fn channel_checkbox(name: &str, label: &str, checked: bool) -> Markup {
+    html! {
+        label style="display:flex; align-items:center; gap:6px; font-weight:normal; margin:0;" {
+            input type="checkbox" name=(name) value="true" checked[checked];
+            (label)
+        }
+    }
+}
+
+/// GET /settings/notifications — choose how to hear about your snakes
+pub async fn notifications_page(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let preferences = notification::get_preferences(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch notification preferences")?;
+
+    let flash = page_factory.flash.clone();
+
+    Ok(page_factory.create_page_with_flash(
+        "Notification Settings".to_string(),
+        Box::new(html! {
+            div class="crumb" { a href="/notifications" { "Notifications" } " / Settings" }
+            div class="page-head" {
+                h1 { "Notification Settings" }
+                div class="sub" {
+                    "How to hear about your snakes. Emails go to your GitHub address."
+                }
+            }
+
+            form class="form-stack" action="/settings/notifications" method="post" {
+                div class="field" {
+                    label { "Rank milestones" }
+                    p class="help" {
+                        "A snake moves into or drops out of a leaderboard's top "
+                        (crate::notifications::MILESTONE_RANK) "."
+                    }
+                    (channel_checkbox("rank_milestones_in_app", "In Arena", preferences.rank_milestones_in_app))
+                    (channel_checkbox("rank_milestones_email", "By email", preferences.rank_milestones_email))
+                }
+
+                div class="field" {
+                    label { "Timeout streaks" }
+                    p class="help" {
+                        "A snake times out in " (crate::notifications::TIMEOUT_STREAK_GAMES)
+                        " finished games in a row."
+                    }
+                    (channel_checkbox("timeout_streaks_in_app", "In Arena", preferences.timeout_streaks_in_app))
+                    (channel_checkbox("timeout_streaks_email", "By email", preferences.timeout_streaks_email))
+                }
+
+                div class="form-cta" {
+                    button type="submit" class="btn solid" { "Save" }
+                }
+            }
+        }),
+        flash,
+    ))
+}
+
+/// POST /settings/notifications
+pub async fn update_notifications(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Form(form): Form<NotificationPreferencesForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let preferences = NotificationPreferences {
+        rank_milestones_in_app: form.rank_milestones_in_app,
+        rank_milestones_email: form.rank_milestones_email,
+        timeout_streaks_in_app: form.timeout_streaks_in_app,
+        timeout_streaks_email: form.timeout_streaks_email,
+    };
+    notification::update_preferences(&state.db, user.user_id, &preferences)
+        .await
+        .wrap_err("Failed to update notification preferences")?;
+
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        "Notification settings saved.".to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/settings/notifications"))
+}
+
+#[derive(Deserialize)]
+pub struct DiscordWebhookForm {
+    pub webhook_url: String,
+    #[serde(default)]
+    pub notify_game_results: bool,
+    #[serde(default)]
+    pub notify_rank_changes: bool,
+    #[serde(default)]
+    pub notify_pauses: bool,
+}
+
+/// GET /settings/discord — your own Discord webhook for news about your
+/// leaderboard snakes
+pub async fn discord_page(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let webhook = discord_webhook::get_for_user(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch Discord webhook")?;
+    // A new webhook gets every kind of message until told otherwise
+    let (game_results, rank_changes, pauses) = webhook.as_ref().map_or((true, true, true), |w| {
+        (
+            w.notify_game_results,
+            w.notify_rank_changes,
+            w.notify_pauses,
+        )
+    });
+
+    let flash = page_factory.flash.clone();
+
+    Ok(page_factory.create_page_with_flash(
+        "Discord Webhook".to_string(),
+        Box::new(html! {
+            div class="crumb" { a href="/me" { "My Profile" } " / Discord webhook" }
+            div class="page-head" {
+                h1 { "Discord Webhook" }
+                div class="sub" {
+                    "Post news about your leaderboard snakes to a Discord channel. "
+                    "In Discord: Server Settings → Integrations → Webhooks → New Webhook, then copy its URL."
+                }
+            }
+
+            form class="form-stack" action="/settings/discord" method="post" {
+                div class="field" {
+                    label for="webhook_url" { "Webhook URL" }
+                    input type="url" id="webhook_url" name="webhook_url" required
+                        placeholder="https://discord.com/api/webhooks/…"
+                        value=[webhook.as_ref().map(|w| w.webhook_url.as_str())];
+                }
+
+                div class="field" {
+                    label { "Post when" }
+                    (channel_checkbox("notify_game_results", "A snake finishes a leaderboard game", game_results))
+                    (channel_checkbox(
+                        "notify_rank_changes",
+                        &format!("A snake moves {}+ places in one game", crate::notifications::DISCORD_RANK_CHANGE),
+                        rank_changes,
+                    ))
+                    (channel_checkbox("notify_pauses", "A snake is paused for failing health checks", pauses))
+                }
+
+                div class="form-cta" {
+                    button type="submit" class="btn solid" { "Save" }
+                }
+            }
+
+            @if webhook.is_some() {
+                div class="row-actions" {
+                    form action="/settings/discord/test" method="post" {
+                        button type="submit" class="btn" { "Send Test Message" }
+                    }
+                    form action="/settings/discord/delete" method="post" {
+                        button type="submit" class="btn danger" onclick="return confirm('Stop posting to this webhook?');" { "Remove Webhook" }
+                    }
+                }
+            }
+        }),
+        flash,
+    ))
+}
+
+/// POST /settings/discord
+pub async fn update_discord(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Form(form): Form<DiscordWebhookForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let (message, flash_type) = match discord_webhook::validate_webhook_url(&form.webhook_url) {
+        Ok(webhook_url) => {
+            let webhook = DiscordWebhook {
+                user_id: user.user_id,
+                webhook_url,
+                notify_game_results: form.notify_game_results,
+                notify_rank_changes: form.notify_rank_changes,
+                notify_pauses: form.notify_pauses,
+            };
+            discord_webhook::upsert(&state.db, &webhook)
+                .await
+                .wrap_err("Failed to save Discord webhook")?;
+            (
+                "Discord webhook saved.".to_string(),
+                session::FLASH_TYPE_SUCCESS,
+            )
+        }
+        Err(message) => (message, session::FLASH_TYPE_ERROR),
+    };
+
+    session::set_flash_message(&state.db, session.session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/settings/discord"))
+}
+
+/// POST /settings/discord/test — post a sample embed, reporting whether
+/// Discord accepted it
+pub async fn test_discord(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let webhook = discord_webhook::get_for_user(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to fetch Discord webhook")?
+        .ok_or_else(|| "No Discord webhook set up".to_string())
+        .with_status(StatusCode::NOT_FOUND)?;
+
+    let embed = crate::discord::Embed {
+        title: "Battlesnake Arena is connected".to_string(),
+        description: Some(format!(
+            "News about {}'s leaderboard snakes will show up here.",
+            user.github_login
+        )),
+        url: Some(format!("{}/battlesnakes", state.config.base_url)),
+        color: crate::discord::COLOR_NEUTRAL,
+        fields: Vec::new(),
+    };
+    let (message, flash_type) = match state.discord.send_embed(&webhook.webhook_url, &embed).await {
+        Ok(()) => (
+            "Test message sent.".to_string(),
+            session::FLASH_TYPE_SUCCESS,
+        ),
+        Err(e) => (
+            format!("Discord didn't accept the test message: {e}"),
+            session::FLASH_TYPE_ERROR,
+        ),
+    };
+
+    session::set_flash_message(&state.db, session.session_id, message, flash_type)
+        .await
+        .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/settings/discord"))
+}
+
+/// POST /settings/discord/delete
+pub async fn delete_discord(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    discord_webhook::delete(&state.db, user.user_id)
+        .await
+        .wrap_err("Failed to delete Discord webhook")?;
+
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        "Discord webhook removed.".to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/settings/discord"))
+}
+
+/// GET /settings/export — download everything the arena holds about the
+/// user as one JSON file
+pub async fn export_data(
+    State(state): State<AppState>,
+    CurrentUser(user): CurrentUser,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let filename = format!(
+        "arena-data-{}-{}.json",
+        user.github_login,
+        chrono::Utc::now().format("%Y-%m-%d")
+    );
+    let export = data_export::export_user_data(&state.db, user)
+        .await
+        .wrap_err("Failed to export user data")?;
+
+    Ok((
+        [
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        Json(export),
+    ))
+}
+
+/// GET /settings/account — delete your account
+pub async fn account_page(
+    CurrentUser(user): CurrentUser,
+    page_factory: PageFactory,
+) -> impl IntoResponse {
+    let flash = page_factory.flash.clone();
+
+    page_factory.create_page_with_flash(
+        "Delete Account".to_string(),
+        Box::new(html! {
+            div class="crumb" { a href="/me" { "My Profile" } " / Delete account" }
+            div class="page-head" {
+                h1 { "Delete Account" }
+                div class="sub" {
+                    "This can't be undone. You may want to "
+                    a href="/settings/export" download { "download your data" }
+                    " first."
+                }
+            }
+
+            ul {
+                li { "Your snakes leave every leaderboard and are renamed and made private." }
+                li { "Your profile, email and GitHub details are erased." }
+                li { "Your API tokens stop working and you're signed out everywhere." }
+                li { "Games your snakes already played stay, under the anonymized names." }
+            }
+
+            form class="form-stack" action="/settings/account/delete" method="post" {
+                div class="field" {
+                    label for="confirm_login" {
+                        "Type your GitHub username, " strong { (user.github_login) } ", to confirm"
+                    }
+                    input type="text" id="confirm_login" name="confirm_login" required autocomplete="off";
+                }
+                div class="form-cta" {
+                    button type="submit" class="btn danger" { "Delete My Account" }
+                }
+            }
+        }),
+        flash,
+    )
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAccountForm {
+    pub confirm_login: String,
+}
+
+/// POST /settings/account/delete — queue the deletion and sign out
+pub async fn delete_account(
+    State(state): State<AppState>,
+    CurrentUserWithSession { user, session }: CurrentUserWithSession,
+    Form(form): Form<DeleteAccountForm>,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    if !form
+        .confirm_login
+        .trim()
+        .eq_ignore_ascii_case(&user.github_login)
+    {
+        session::set_flash_message(
+            &state.db,
+            session.session_id,
+            "That username doesn't match, so nothing was deleted.".to_string(),
+            session::FLASH_TYPE_ERROR,
+        )
+        .await
+        .wrap_err("Failed to set flash message")?;
+        return Ok(Redirect::to("/settings/account"));
+    }
+
+    cja::jobs::Job::enqueue(
+        DeleteAccountJob {
+            user_id: user.user_id,
+        },
+        state.clone(),
+        format!("Account deletion requested by {}", user.user_id),
+        None,
+    )
+    .await
+    .wrap_err("Failed to enqueue account deletion")?;
+
+    session::disassociate_user_from_session(&state.db, session.session_id)
+        .await
+        .wrap_err("Failed to sign out")?;
+    session::set_flash_message(
+        &state.db,
+        session.session_id,
+        "Your account is being deleted. Thanks for playing!".to_string(),
+        session::FLASH_TYPE_SUCCESS,
+    )
+    .await
+    .wrap_err("Failed to set flash message")?;
+
+    Ok(Redirect::to("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_new_token_form_collects_scopes() {
+        let form =
+            parse_new_token_form(b"name=+stress+&scopes=games%3Acreate&scopes=admin").unwrap();
+        assert_eq!(form.name, "stress");
+        assert_eq!(
+            form.scopes,
+            vec![TokenScope::GamesCreate, TokenScope::Admin]
+        );
+    }
+
+    #[test]
+    fn test_parse_new_token_form_rejects_bad_input() {
+        assert!(parse_new_token_form(b"scopes=admin").is_err());
+        assert!(parse_new_token_form(b"name=x&scopes=everything").is_err());
+        assert!(parse_new_token_form(b"name=x&expires_in=7").is_err());
+    }
+
+    #[test]
+    fn test_parse_new_token_form_expiry() {
+        let expiry = |body: &[u8]| parse_new_token_form(body).unwrap().expires_in_days;
+        assert_eq!(expiry(b"name=x"), Some(DEFAULT_TOKEN_EXPIRY_DAYS));
+        assert_eq!(expiry(b"name=x&expires_in=365"), Some(365));
+        assert_eq!(expiry(b"name=x&expires_in=never"), None);
+    }
+}
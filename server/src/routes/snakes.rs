@@ -0,0 +1,360 @@
+//! The public snake directory: every public battlesnake, newest first,
+//! searchable by snake or owner name and narrowed by leaderboard and health.
+//! Pages with a keyset cursor like the games list. With `vs` set it doubles
+//! as the picker for the head-to-head comparison page.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use color_eyre::eyre::Context as _;
+use maud::html;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    components::page_factory::PageFactory,
+    customizations::chip_color,
+    errors::ServerResult,
+    models::battlesnake::{self, DirectoryFilter, DirectorySnake},
+    models::leaderboard::{self, SnakeMembership},
+    routes::cursor,
+    state::AppState,
+};
+
+const PAGE_SIZE: i64 = 50;
+
+/// Longest search we'll run; anything past it is cut off
+const MAX_QUERY_CHARS: usize = 100;
+
+/// Leaderboard and health are strings so a stale or hand-edited link falls
+/// back to "any" instead of failing the whole page, as on the games list.
+#[derive(Debug, Default, Deserialize)]
+pub struct DirectoryParams {
+    q: Option<String>,
+    leaderboard: Option<String>,
+    health: Option<String>,
+    /// Snake to compare the picked one with
+    vs: Option<String>,
+    before: Option<String>,
+}
+
+/// The directory's filters, for building links that keep them
+#[derive(Debug, Clone)]
+struct DirectoryLink {
+    query: Option<String>,
+    leaderboard_id: Option<Uuid>,
+    paused: Option<bool>,
+    vs: Option<Uuid>,
+}
+
+impl DirectoryLink {
+    fn href(&self, before: Option<&str>) -> String {
+        let mut params = Vec::new();
+        if let Some(query) = &self.query {
+            params.push(format!("q={}", urlencoding::encode(query)));
+        }
+        if let Some(leaderboard_id) = self.leaderboard_id {
+            params.push(format!("leaderboard={leaderboard_id}"));
+        }
+        if let Some(paused) = self.paused {
+            params.push(format!("health={}", health_param(paused)));
+        }
+        if let Some(vs) = self.vs {
+            params.push(format!("vs={vs}"));
+        }
+        if let Some(before) = before {
+            params.push(format!("before={before}"));
+        }
+        if params.is_empty() {
+            "/snakes".to_string()
+        } else {
+            format!("/snakes?{}", params.join("&"))
+        }
+    }
+}
+
+fn health_param(paused: bool) -> &'static str {
+    if paused { "paused" } else { "active" }
+}
+
+/// Badge text and class for what the health sweeper last saw, or `None`
+/// for a snake it hasn't checked
+fn health_badge(snake: &DirectorySnake) -> Option<(&'static str, &'static str)> {
+    if snake.deactivated_at.is_some() {
+        return Some(("Paused", "badge warn"));
+    }
+    match snake.consecutive_failures? {
+        0 => Some(("Healthy", "badge ok")),
+        _ => Some(("Failing checks", "badge warn")),
+    }
+}
+
+/// GET /snakes - Public battlesnake directory
+pub async fn list_snakes(
+    State(state): State<AppState>,
+    Query(params): Query<DirectoryParams>,
+    page_factory: PageFactory,
+) -> ServerResult<impl IntoResponse, StatusCode> {
+    let query = params
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(|q| q.chars().take(MAX_QUERY_CHARS).collect::<String>());
+    let paused = match params.health.as_deref() {
+        Some("paused") => Some(true),
+        Some("active") => Some(false),
+        _ => None,
+    };
+    let before = params.before.as_deref().and_then(cursor::decode);
+    let vs = match params.vs.as_deref().and_then(|id| id.parse().ok()) {
+        Some(battlesnake_id) => battlesnake::get_battlesnake_by_id(&state.read_db, battlesnake_id)
+            .await
+            .wrap_err("Failed to fetch battlesnake")?,
+        None => None,
+    };
+
+    let leaderboards = leaderboard::get_active_leaderboards(&state.read_db)
+        .await
+        .wrap_err("Failed to fetch leaderboards")?;
+    let leaderboard_id = params
+        .leaderboard
+        .as_deref()
+        .and_then(|id| id.parse::<Uuid>().ok())
+        .filter(|id| leaderboards.iter().any(|l| l.leaderboard_id == *id));
+
+    let filter = DirectoryFilter {
+        query: query.clone(),
+        leaderboard_id,
+        paused,
+    };
+    // Fetch one extra row to learn whether there is another page
+    let mut snakes =
+        battlesnake::list_directory_page(&state.read_db, &filter, before, PAGE_SIZE + 1)
+            .await
+            .wrap_err("Failed to list battlesnakes")?;
+    let next_cursor = if snakes.len() as i64 > PAGE_SIZE {
+        snakes.truncate(PAGE_SIZE as usize);
+        snakes
+            .last()
+            .map(|last| cursor::encode(last.created_at, last.battlesnake_id))
+    } else {
+        None
+    };
+
+    let snake_ids: Vec<Uuid> = snakes.iter().map(|s| s.battlesnake_id).collect();
+    let mut memberships: HashMap<Uuid, Vec<SnakeMembership>> = HashMap::new();
+    for membership in leaderboard::get_memberships_for_battlesnakes(&state.read_db, &snake_ids)
+        .await
+        .wrap_err("Failed to fetch leaderboard memberships")?
+    {
+        memberships
+            .entry(membership.battlesnake_id)
+            .or_default()
+            .push(membership);
+    }
+
+    let link = DirectoryLink {
+        query,
+        leaderboard_id,
+        paused,
+        vs: vs.as_ref().map(|s| s.battlesnake_id),
+    };
+    let filtered = link.query.is_some() || leaderboard_id.is_some() || paused.is_some();
+
+    Ok(page_factory.create_page(
+        "Snakes".to_string(),
+        Box::new(html! {
+            div class="page-head" {
+                h1 { "Snakes" }
+                div class="sub" {
+                    @if let Some(vs) = &vs {
+                        "Pick a snake to compare with "
+                        a href={"/battlesnakes/"(vs.battlesnake_id)"/profile"} { b { (vs.name) } }
+                        ". "
+                        a href=(DirectoryLink { vs: None, ..link.clone() }.href(None)) { "Just browse" }
+                    } @else {
+                        "Public battlesnakes, newest first. Find an opponent or some inspiration."
+                    }
+                }
+            }
+
+            form class="search-form" action="/snakes" method="get" {
+                input type="search" name="q" value=[link.query.as_deref()]
+                    placeholder="Snake name or owner" aria-label="Search snakes";
+                select name="leaderboard" aria-label="Leaderboard" {
+                    option value="" { "Any leaderboard" }
+                    @for l in &leaderboards {
+                        option value=(l.leaderboard_id) selected[leaderboard_id == Some(l.leaderboard_id)] { (l.name) }
+                    }
+                }
+                select name="health" aria-label="Health" {
+                    option value="" { "Any health" }
+                    option value="active" selected[paused == Some(false)] { "Not paused" }
+                    option value="paused" selected[paused == Some(true)] { "Paused" }
+                }
+                @if let Some(vs) = link.vs {
+                    input type="hidden" name="vs" value=(vs);
+                }
+                button type="submit" class="btn" { "Search" }
+                @if filtered {
+                    a href=(DirectoryLink { vs: link.vs, query: None, leaderboard_id: None, paused: None }.href(None)) { "Clear" }
+                }
+            }
+
+            @if snakes.is_empty() {
+                p class="empty" {
+                    @if before.is_some() {
+                        "No more snakes."
+                    } @else if filtered {
+                        "No public snakes match."
+                    } @else {
+                        "No public snakes yet."
+                    }
+                }
+            } @else {
+                table class="data" {
+                    thead {
+                        tr {
+                            th { "Snake" }
+                            th class="hide-sm" { "Leaderboards" }
+                            th class="r" { "Health" }
+                            @if link.vs.is_some() {
+                                th {}
+                            }
+                        }
+                    }
+                    tbody {
+                        @for s in &snakes {
+                            tr {
+                                td {
+                                    div class="snake-cell" {
+                                        span class="chip" style={"background:"(chip_color(&s.color))} title={(s.head) " / " (s.tail)} {}
+                                        span {
+                                            a class="name" href={"/battlesnakes/"(s.battlesnake_id)"/profile"} { (s.name) }
+                                            a class="owner" href={"/users/"(s.owner_login)} {
+                                                (s.owner_display_name.as_deref().filter(|n| !n.is_empty()).unwrap_or(&s.owner_login))
+                                            }
+                                        }
+                                    }
+                                }
+                                td class="hide-sm" {
+                                    @for m in memberships.get(&s.battlesnake_id).map(Vec::as_slice).unwrap_or_default() {
+                                        div {
+                                            a href={"/leaderboards/"(m.leaderboard_id)} { (m.leaderboard_name) }
+                                            @if let Some(rank) = m.rank {
+                                                " · #" (rank)
+                                            }
+                                        }
+                                    }
+                                }
+                                td class="r" {
+                                    @if let Some((label, class)) = health_badge(s) {
+                                        span class=(class) { (label) }
+                                    }
+                                }
+                                @if let Some(vs) = link.vs {
+                                    td class="r" {
+                                        @if vs != s.battlesnake_id {
+                                            a class="btn sm" href={"/compare?snake_a="(vs)"&snake_b="(s.battlesnake_id)} { "Compare" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div class="pager" {
+                @if before.is_some() {
+                    a href=(link.href(None)) { "« Newest" }
+                }
+                @if let Some(next) = &next_cursor {
+                    a href=(link.href(Some(next))) { "Older ›" }
+                }
+            }
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_keep_filters_and_encode_the_query() {
+        let link = DirectoryLink {
+            query: None,
+            leaderboard_id: None,
+            paused: None,
+            vs: None,
+        };
+        assert_eq!(link.href(None), "/snakes");
+
+        let leaderboard_id = Uuid::nil();
+        let link = DirectoryLink {
+            query: Some("noodle & co".to_string()),
+            leaderboard_id: Some(leaderboard_id),
+            paused: Some(false),
+            vs: Some(leaderboard_id),
+        };
+        assert_eq!(
+            link.href(Some("abc")),
+            format!(
+                "/snakes?q=noodle%20%26%20co&leaderboard={leaderboard_id}&health=active&vs={leaderboard_id}&before=abc"
+            )
+        );
+    }
+
+    #[test]
+    fn health_badge_reflects_sweeper_state() {
+        let mut snake = DirectorySnake {
+            battlesnake_id: Uuid::nil(),
+            name: "snake".to_string(),
+            color: String::new(),
+            head: "default".to_string(),
+            tail: "default".to_string(),
+            created_at: chrono::Utc::now(),
+            owner_login: "owner".to_string(),
+            owner_display_name: None,
+            consecutive_failures: None,
+            deactivated_at: None,
+        };
+        assert_eq!(health_badge(&snake), None);
+        snake.consecutive_failures = Some(0);
+        assert_eq!(health_badge(&snake).map(|b| b.0), Some("Healthy"));
+        snake.consecutive_failures = Some(2);
+        assert_eq!(health_badge(&snake).map(|b| b.0), Some("Failing checks"));
+        snake.deactivated_at = Some(chrono::Utc::now());
+        assert_eq!(health_badge(&snake).map(|b| b.0), Some("Paused"));
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn lenient_params_still_render(pool: sqlx::PgPool) {
+        use tower::ServiceExt as _;
+
+        let app = crate::routes::routes(AppState::test_from_pool(pool));
+        for uri in [
+            "/snakes",
+            "/snakes?q=noodle&health=paused",
+            "/snakes?q=%25_%5C&leaderboard=not-a-uuid&health=bogus&before=not-a-cursor",
+            "/snakes?vs=00000000-0000-0000-0000-000000000000",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::extract::Request::get(uri)
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Time budget for HTML pages. A handler stuck on a slow query or a hung
+//! connection is cut off with a 503 once the budget runs out, and reported
+//! like any other server error, rather than holding the browser (and a
+//! pool connection) until the proxy in front gives up.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse as _, Response},
+};
+
+use crate::errors::ServerError;
+
+/// Run the request with `budget` to produce a response. Only the response
+/// head is bounded; a body already streaming (static files) runs to the end.
+pub async fn enforce_budget(
+    State(budget): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request.extensions().get::<MatchedPath>().map_or_else(
+        || request.uri().path().to_string(),
+        |p| p.as_str().to_string(),
+    );
+    let method = request.method().clone();
+
+    match tokio::time::timeout(budget, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                event_type = "request_timeout",
+                method = %method,
+                route = %route,
+                budget_ms = budget.as_millis() as u64,
+                "Request ran out of its time budget"
+            );
+            ServerError(
+                cja::color_eyre::eyre::eyre!(
+                    "{method} {route} took longer than {}s",
+                    budget.as_secs_f32()
+                ),
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use tower::ServiceExt as _;
+
+    #[tokio::test]
+    async fn slow_handlers_are_cut_off() {
+        let app = axum::Router::new()
+            .route("/fast", get(|| async { "ok" }))
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    "too late"
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                Duration::from_millis(50),
+                enforce_budget,
+            ));
+
+        let request =
+            |path: &'static str| Request::get(path).body(axum::body::Body::empty()).unwrap();
+        let response = app.clone().oneshot(request("/fast")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let started = std::time::Instant::now();
+        let response = app.oneshot(request("/slow")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
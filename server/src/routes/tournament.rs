@@ -21,15 +21,15 @@ use crate::{
         game::{GameBoardSize, GameType},
         leaderboard, session,
         tournament::{
-            self, BracketParticipant, CreateTournament, MatchGame, MatchStatus, MatchStyle,
-            RegistrationStatus, Tournament, TournamentMatch, TournamentStatus,
-            TournamentVisibility, UpdateTournamentSettings,
+            self, BracketParticipant, CreateTournament, MatchBracket, MatchGame, MatchStatus,
+            MatchStyle, RegistrationStatus, RegistrationWithDetails, Tournament, TournamentFormat,
+            TournamentMatch, TournamentStatus, TournamentVisibility, UpdateTournamentSettings,
         },
         user,
     },
     routes::auth::{CurrentUser, CurrentUserWithSession, OptionalUser},
     state::AppState,
-    tournament_bracket::persist_bracket,
+    tournament_bracket::{PlayedMatch, Standing, persist_bracket, round_robin_standings},
 };
 
 /// Cap for the leaderboard import qualifier flow.
@@ -47,6 +47,10 @@ const MAX_DESCRIPTION_CHARS: usize = 4000;
 const MAX_SNAKES_PER_USER_LIMIT: i32 = 32;
 const MAX_REQUIRED_PARTICIPANTS: i32 = 128;
 
+/// Largest round robin that can start: every snake plays every other, so the
+/// match count grows with the square of the field.
+const MAX_ROUND_ROBIN_PARTICIPANTS: i64 = 32;
+
 // --- Pure business rules (unit tested below) ---
 
 /// Registrations can only be added/removed/reseeded before the bracket exists.
@@ -148,7 +152,7 @@ fn validate_settings_update(
 
 /// Parse a game type from a form value, rejecting anything outside the
 /// supported dropdown options (GameType::from_str is a catch-all).
-fn parse_game_type(s: &str) -> Result<GameType, String> {
+pub(crate) fn parse_game_type(s: &str) -> Result<GameType, String> {
     match GameType::from_str(s) {
         Ok(GameType::Other(_)) | Err(_) => Err(format!("Invalid game type: {s}")),
         Ok(game_type) => Ok(game_type),
@@ -156,7 +160,7 @@ fn parse_game_type(s: &str) -> Result<GameType, String> {
 }
 
 /// Parse a board size from a form value, rejecting custom sizes.
-fn parse_board_size(s: &str) -> Result<GameBoardSize, String> {
+pub(crate) fn parse_board_size(s: &str) -> Result<GameBoardSize, String> {
     match GameBoardSize::from_str(s) {
         Ok(GameBoardSize::Custom(_)) | Err(_) => Err(format!("Invalid board size: {s}")),
         Ok(board_size) => Ok(board_size),
@@ -284,6 +288,8 @@ async fn register_snake_in_tx(
 /// Start rules (BS-022): the tournament must be in `registration`, and enough
 /// snakes must be registered — at least `required_participants`, and never
 /// fewer than 2 (a bracket needs two sides even if the owner set a lower bar).
+/// Double elimination needs a third snake for a losers bracket, and a round
+/// robin is capped at [`MAX_ROUND_ROBIN_PARTICIPANTS`].
 fn validate_start(tournament: &Tournament, registration_count: i64) -> Result<(), String> {
     if !tournament
         .status
@@ -303,6 +309,20 @@ fn validate_start(tournament: &Tournament, registration_count: i64) -> Result<()
             tournament.required_participants, registration_count
         ));
     }
+    match tournament.format {
+        TournamentFormat::DoubleElimination if registration_count < 3 => {
+            return Err(
+                "At least 3 registered snakes are needed for double elimination".to_string(),
+            );
+        }
+        TournamentFormat::RoundRobin if registration_count > MAX_ROUND_ROBIN_PARTICIPANTS => {
+            return Err(format!(
+                "A round robin can have at most {MAX_ROUND_ROBIN_PARTICIPANTS} snakes, \
+                 but {registration_count} are registered"
+            ));
+        }
+        _ => {}
+    }
     Ok(())
 }
 
@@ -385,29 +405,64 @@ fn bracket_team_row(
     }
 }
 
+/// Card tag for a match: the bracket's last match gets its name, every other
+/// match its round (the number "Run Round" uses) and position.
+fn match_tag(m: &TournamentMatch, format: TournamentFormat, total_rounds: i32) -> String {
+    match m.bracket {
+        MatchBracket::GrandFinal => "Grand Final".to_string(),
+        MatchBracket::Winners
+            if format == TournamentFormat::SingleElimination && m.round == total_rounds =>
+        {
+            "Final".to_string()
+        }
+        _ => format!("R{} · M{}", m.round, m.position + 1),
+    }
+}
+
+/// Header label for one column of a bracket grid. `columns` is how many
+/// columns that bracket spans, so its last one can be named.
+fn column_label(
+    format: TournamentFormat,
+    bracket: MatchBracket,
+    column: i32,
+    columns: i32,
+) -> String {
+    let round = column + 1;
+    match (format, bracket) {
+        (_, MatchBracket::GrandFinal) => "Grand Final".to_string(),
+        (TournamentFormat::DoubleElimination, MatchBracket::Winners) if round == columns => {
+            "Winners Final".to_string()
+        }
+        (TournamentFormat::DoubleElimination, MatchBracket::Winners) => {
+            format!("Winners R{round}")
+        }
+        (_, MatchBracket::Losers) if round == columns => "Losers Final".to_string(),
+        (_, MatchBracket::Losers) => format!("Losers R{round}"),
+        (_, MatchBracket::Pool) => format!("Round {round}"),
+        _ => round_label(round, columns),
+    }
+}
+
+/// A match no one can reach: created canceled, with no participant slots.
+/// Only double elimination has these, when byes empty a losers match.
+fn is_dead_match(m: &TournamentMatch, participants: &[BracketParticipant]) -> bool {
+    m.status == MatchStatus::Canceled && participants.is_empty()
+}
+
 /// One match card, placed on the bracket grid by its visual coordinates.
-/// Round columns are the odd grid columns (connectors live between); each
-/// match spans two grid rows so later rounds center between their feeders.
-fn bracket_match_card(
-    view: &BracketMatchView,
-    match_style: MatchStyle,
-    total_rounds: i32,
-) -> Markup {
+/// Columns are the odd grid columns (connectors live between); each match
+/// spans two grid rows so later rounds center between their feeders.
+fn bracket_match_card(view: &BracketMatchView, match_style: MatchStyle, tag_label: &str) -> Markup {
     let m = view.tournament_match;
     let game_winners: Vec<Option<Uuid>> = view.games.iter().map(|g| g.winner_id).collect();
     let show_wins = match_style != MatchStyle::SingleGame;
-    // Byes only have their single seeded participant persisted.
-    let is_bye = m.round == 1 && view.participants.len() == 1;
+    // Byes and walkovers only have their one live slot persisted.
+    let is_bye = view.participants.len() == 1;
     let is_live = m.status == MatchStatus::InProgress;
 
     let col = m.visual_column * 2 + 1;
     let row = m.visual_row + 1;
 
-    let tag_label = if m.round == total_rounds {
-        "Final".to_string()
-    } else {
-        format!("R{} · M{}", m.round, m.position + 1)
-    };
     let status_label = match m.status {
         MatchStatus::Scheduled => {
             if is_bye {
@@ -468,89 +523,114 @@ fn bracket_match_card(
     }
 }
 
-/// The bracket: rounds as columns on a shared CSS grid (odd columns hold
-/// matches, even columns hold the elbow connectors), a champion slot after
-/// the final, and a horizontal scroll container for small screens. Grid
-/// placement comes from each match's `visual_column`/`visual_row`.
-fn bracket_section(
+/// Per-match bracket data fetched for the detail page.
+struct BracketData<'a> {
+    participants_by_match: &'a HashMap<Uuid, Vec<BracketParticipant>>,
+    games_by_match: &'a HashMap<Uuid, Vec<MatchGame>>,
+}
+
+impl<'a> BracketData<'a> {
+    fn participants(&self, match_id: Uuid) -> &'a [BracketParticipant] {
+        self.participants_by_match
+            .get(&match_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn games(&self, match_id: Uuid) -> &'a [MatchGame] {
+        self.games_by_match
+            .get(&match_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Name and color of a match's winner, via that match's participant
+    /// rows. If the winning snake was deleted since (its participant row
+    /// cascades away), a neutral placeholder rather than nothing.
+    fn winner_of(&self, m: &TournamentMatch) -> Option<(String, Option<String>)> {
+        let winner_id = m.winner_id?;
+        self.participants(m.match_id)
+            .iter()
+            .find(|p| p.battlesnake_id == Some(winner_id))
+            .map(|p| {
+                (
+                    p.snake_name
+                        .clone()
+                        .unwrap_or_else(|| "(deleted snake)".to_string()),
+                    p.snake_color.clone().filter(|c| !c.is_empty()),
+                )
+            })
+            .or(Some(("(deleted snake)".to_string(), None)))
+    }
+}
+
+/// The champion slot at the end of a bracket grid: the match that decides
+/// it, and who won (once the tournament is over).
+struct ChampionSlot<'a> {
+    fed_by: &'a TournamentMatch,
+    champion: Option<(String, Option<String>)>,
+}
+
+/// One bracket grid: `matches` as cards on a shared CSS grid (odd columns
+/// hold matches, even columns hold the connectors), column labels on top,
+/// an optional champion slot after the last column, and a horizontal scroll
+/// container for small screens. Grid placement comes from each match's
+/// `visual_column`/`visual_row`; connectors join each match to the matches
+/// in this grid whose winners it receives.
+fn bracket_grid(
     t: &Tournament,
-    matches: &[TournamentMatch],
-    participants_by_match: &HashMap<Uuid, Vec<BracketParticipant>>,
-    games_by_match: &HashMap<Uuid, Vec<MatchGame>>,
+    data: &BracketData,
+    matches: &[&TournamentMatch],
+    labels: &[String],
+    champion: Option<&ChampionSlot>,
+    aria_label: &str,
 ) -> Markup {
-    let total_rounds = matches.iter().map(|m| m.round).max().unwrap_or(0);
+    let columns = matches
+        .iter()
+        .map(|m| m.visual_column + 1)
+        .max()
+        .unwrap_or(0);
     // No matches means no grid: `repeat(0, ...)` is invalid CSS.
-    if total_rounds == 0 {
+    if columns == 0 {
         return html! {};
     }
+    let total_rounds = matches.iter().map(|m| m.round).max().unwrap_or(0);
 
-    // Champion: the winner of the final (highest round), named via that
-    // match's participant rows. If the winning snake was deleted since (its
-    // participant row cascades away), still show the slot with a neutral
-    // placeholder rather than silently dropping it.
-    let final_match = matches.iter().find(|m| m.round == total_rounds);
-    let champion = (t.status == TournamentStatus::Completed)
-        .then(|| {
-            let final_match = final_match?;
-            let winner_id = final_match.winner_id?;
-            participants_by_match
-                .get(&final_match.match_id)
-                .and_then(|participants| {
-                    participants
-                        .iter()
-                        .find(|p| p.battlesnake_id == Some(winner_id))
-                })
-                .map(|p| {
-                    (
-                        p.snake_name
-                            .clone()
-                            .unwrap_or_else(|| "(deleted snake)".to_string()),
-                        p.snake_color.clone().filter(|c| !c.is_empty()),
-                    )
-                })
-                .or(Some(("(deleted snake)".to_string(), None)))
-        })
-        .flatten();
-
-    // Grid geometry. Matches sit in odd columns (1, 3, ...); the elbow
-    // connectors between rounds sit in the even columns; the champion slot
-    // takes the last odd column. Rows: a bracket of size 2^R needs 2^R rows,
-    // each match spanning two.
-    let grid_rows = 1_i32 << total_rounds;
-    let champ_col = total_rounds * 2 + 1;
+    // Grid geometry: each match spans two rows starting at its visual row.
+    let grid_rows = matches.iter().map(|m| m.visual_row + 2).max().unwrap_or(2);
+    let champ_col = columns * 2 + 1;
     let mut grid_cols = String::new();
-    for _ in 1..=total_rounds {
+    for _ in 1..=columns {
         grid_cols.push_str("minmax(210px, 1fr) 28px ");
     }
-    grid_cols.push_str("minmax(170px, 1fr)");
-    let min_width = total_rounds * 238 + 170;
+    let mut min_width = columns * 238;
+    if champion.is_some() {
+        grid_cols.push_str("minmax(170px, 1fr)");
+        min_width += 170;
+    }
 
     // Feeder rows for connector placement: an elbow spans from the vertical
-    // center of one feeder match to the center of its sibling.
-    let row_of: HashMap<(i32, i32), i32> = matches
-        .iter()
-        .map(|m| ((m.round, m.position), m.visual_row))
-        .collect();
-
-    static EMPTY_PARTICIPANTS: Vec<BracketParticipant> = Vec::new();
-    static EMPTY_GAMES: Vec<MatchGame> = Vec::new();
+    // center of the top feeder to the center of the bottom one.
+    let mut feeder_rows: HashMap<Uuid, Vec<i32>> = HashMap::new();
+    for m in matches {
+        if let Some(next_match_id) = m.next_match_id {
+            feeder_rows
+                .entry(next_match_id)
+                .or_default()
+                .push(m.visual_row);
+        }
+    }
 
     html! {
-        h2 class="vh" { "Bracket" }
-        div class="bracket-note" {
-            (matches.iter().filter(|m| m.status == MatchStatus::Completed).count())
-            " of " (matches.len()) " matches played · "
-            (total_rounds) @if total_rounds == 1 { " round" } @else { " rounds" }
-        }
-        div class="bracket-scroll" tabindex="0" role="region" aria-label="Tournament bracket" {
+        div class="bracket-scroll" tabindex="0" role="region" aria-label=(aria_label) {
             div style={"min-width: "(min_width)"px;"} {
                 div class="round-labels" style={"grid-template-columns: "(grid_cols)";"} {
-                    @for round in 1..=total_rounds {
-                        span style={"grid-column: "(round * 2 - 1)";"} {
-                            (round_label(round, total_rounds))
-                        }
+                    @for (column, label) in labels.iter().enumerate() {
+                        span style={"grid-column: "(column * 2 + 1)";"} { (label) }
+                    }
+                    @if champion.is_some() {
+                        span style={"grid-column: "(champ_col)";"} { "Champion" }
                     }
-                    span style={"grid-column: "(champ_col)";"} { "Champion" }
                 }
                 div class="bracket"
                     style={
@@ -560,38 +640,38 @@ fn bracket_section(
                     @for m in matches {
                         @let view = BracketMatchView {
                             tournament_match: m,
-                            participants: participants_by_match
-                                .get(&m.match_id)
-                                .unwrap_or(&EMPTY_PARTICIPANTS),
-                            games: games_by_match.get(&m.match_id).unwrap_or(&EMPTY_GAMES),
+                            participants: data.participants(m.match_id),
+                            games: data.games(m.match_id),
                         };
-                        (bracket_match_card(&view, t.match_style, total_rounds))
-
-                        // Elbow connector from this match's two feeders.
-                        @if m.round > 1 {
-                            @let top = row_of.get(&(m.round - 1, m.position * 2));
-                            @let bottom = row_of.get(&(m.round - 1, m.position * 2 + 1));
-                            @if let (Some(top), Some(bottom)) = (top, bottom) {
+                        (bracket_match_card(&view, t.match_style, &match_tag(m, t.format, total_rounds)))
+
+                        @if let Some(rows) = feeder_rows.get(&m.match_id) {
+                            @let top = rows.iter().copied().chain([m.visual_row]).min().unwrap_or(m.visual_row);
+                            @let bottom = rows.iter().copied().chain([m.visual_row]).max().unwrap_or(m.visual_row);
+                            @if top == bottom {
+                                // A single feeder level with this match.
+                                div class="conn-h"
+                                    style={"grid-column: "(m.visual_column * 2)"; grid-row: "(top + 1)" / "(top + 3)";"} {}
+                            } @else {
                                 div class="conn"
                                     style={
-                                        "grid-column: "(m.round * 2 - 2)"; "
+                                        "grid-column: "(m.visual_column * 2)"; "
                                         "grid-row: "(top + 2)" / "(bottom + 2)";"
                                     } {}
                             }
                         }
                     }
 
-                    // Champion slot, fed by the final.
-                    @if let Some(final_match) = final_match {
-                        @let frow = final_match.visual_row + 1;
+                    @if let Some(slot) = champion {
+                        @let frow = slot.fed_by.visual_row + 1;
                         div class="conn-h"
                             style={"grid-column: "(champ_col - 1)"; grid-row: "(frow)" / "(frow + 2)";"} {}
-                        div .champ .crowned[champion.is_some()]
+                        div .champ .crowned[slot.champion.is_some()]
                             style={"grid-column: "(champ_col)"; grid-row: "(frow)" / "(frow + 2)";"} {
                             div class="glyph" aria-hidden="true" { "🏆" }
                             div class="label" { "Champion" }
                             div class="who" {
-                                @if let Some((name, color)) = &champion {
+                                @if let Some((name, color)) = &slot.champion {
                                     @if let Some(color) = color {
                                         span class="chip champ-chip" style={"background:"(chip_color(color))} {}
                                     }
@@ -608,10 +688,170 @@ fn bracket_section(
     }
 }
 
+/// Column labels for the matches of one grid, named after the bracket each
+/// column belongs to.
+fn grid_labels(format: TournamentFormat, matches: &[&TournamentMatch]) -> Vec<String> {
+    let columns = matches
+        .iter()
+        .map(|m| m.visual_column + 1)
+        .max()
+        .unwrap_or(0);
+    let bracket_columns = |bracket: MatchBracket| {
+        matches
+            .iter()
+            .filter(|m| m.bracket == bracket)
+            .map(|m| m.visual_column + 1)
+            .max()
+            .unwrap_or(0)
+    };
+    (0..columns)
+        .map(|column| {
+            let bracket = matches
+                .iter()
+                .find(|m| m.visual_column == column)
+                .map_or(MatchBracket::Winners, |m| m.bracket);
+            column_label(format, bracket, column, bracket_columns(bracket))
+        })
+        .collect()
+}
+
+/// Round robin standings, with the leader crowned once every round is done.
+fn standings_table(
+    t: &Tournament,
+    registrations: &[RegistrationWithDetails],
+    standings: &[Standing],
+) -> Markup {
+    let by_snake: HashMap<Uuid, &RegistrationWithDetails> = registrations
+        .iter()
+        .map(|r| (r.battlesnake_id, r))
+        .collect();
+    let finished = t.status == TournamentStatus::Completed;
+
+    html! {
+        div class="section" {
+            h2 { "Standings" }
+            table class="data" {
+                thead {
+                    tr {
+                        th { "#" }
+                        th { "Battlesnake" }
+                        th class="r" { "Played" }
+                        th class="r" { "Match wins" }
+                        th class="r" { "Game wins" }
+                    }
+                }
+                tbody {
+                    @for (i, standing) in standings.iter().enumerate() {
+                        tr {
+                            td class="rank" {
+                                (format!("{:02}", i + 1))
+                                @if finished && i == 0 { " 🏆" }
+                            }
+                            td {
+                                div class="snake-cell" {
+                                    @if let Some(reg) = by_snake.get(&standing.battlesnake_id) {
+                                        span class="chip" style={"background:"(chip_color(&reg.snake_color))} {}
+                                        span {
+                                            a class="name" href={"/battlesnakes/"(reg.battlesnake_id)"/profile"} { (reg.snake_name) }
+                                            span class="owner" { "by " (reg.owner_login) }
+                                        }
+                                    } @else {
+                                        span class="chip tbd" {}
+                                        span { "(deleted snake)" }
+                                    }
+                                }
+                            }
+                            td class="r num" { (standing.played) }
+                            td class="r num" { (standing.match_wins) }
+                            td class="r num" { (standing.game_wins) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The bracket for the tournament's format: one grid ending in a champion
+/// for single elimination; the winners bracket and grand final above the
+/// losers bracket for double elimination; standings over a grid of rounds
+/// for round robin. Matches no one can reach are left out.
+fn bracket_section(
+    t: &Tournament,
+    registrations: &[RegistrationWithDetails],
+    matches: &[TournamentMatch],
+    data: &BracketData,
+) -> Markup {
+    let total_rounds = matches.iter().map(|m| m.round).max().unwrap_or(0);
+    if total_rounds == 0 {
+        return html! {};
+    }
+
+    let shown: Vec<&TournamentMatch> = matches
+        .iter()
+        .filter(|m| !is_dead_match(m, data.participants(m.match_id)))
+        .collect();
+    let playable = matches
+        .iter()
+        .filter(|m| m.status != MatchStatus::Canceled)
+        .count();
+    let played = matches
+        .iter()
+        .filter(|m| m.status == MatchStatus::Completed)
+        .count();
+    let in_bracket = |brackets: &[MatchBracket]| -> Vec<&TournamentMatch> {
+        shown
+            .iter()
+            .copied()
+            .filter(|m| brackets.contains(&m.bracket))
+            .collect()
+    };
+    let finished = t.status == TournamentStatus::Completed;
+
+    html! {
+        h2 class="vh" { "Bracket" }
+        div class="bracket-note" {
+            (t.format.label()) " · "
+            (played) " of " (playable) " matches played · "
+            (total_rounds) @if total_rounds == 1 { " round" } @else { " rounds" }
+        }
+        @match t.format {
+            TournamentFormat::SingleElimination => {
+                @let champion = shown.iter().find(|m| m.round == total_rounds).map(|m| ChampionSlot {
+                    fed_by: m,
+                    champion: finished.then(|| data.winner_of(m)).flatten(),
+                });
+                (bracket_grid(t, data, &shown, &grid_labels(t.format, &shown), champion.as_ref(), "Tournament bracket"))
+            }
+            TournamentFormat::DoubleElimination => {
+                @let upper = in_bracket(&[MatchBracket::Winners, MatchBracket::GrandFinal]);
+                @let lower = in_bracket(&[MatchBracket::Losers]);
+                @let champion = upper.iter().find(|m| m.bracket == MatchBracket::GrandFinal).map(|m| ChampionSlot {
+                    fed_by: m,
+                    champion: finished.then(|| data.winner_of(m)).flatten(),
+                });
+                (bracket_grid(t, data, &upper, &grid_labels(t.format, &upper), champion.as_ref(), "Winners bracket"))
+                div class="bracket-note" { "Losers bracket · one more loss and you're out" }
+                (bracket_grid(t, data, &lower, &grid_labels(t.format, &lower), None, "Losers bracket"))
+            }
+            TournamentFormat::RoundRobin => {
+                @let seeds: Vec<(Uuid, i32)> = registrations.iter().map(|r| (r.battlesnake_id, r.seed)).collect();
+                @let played_matches: Vec<PlayedMatch> = shown.iter().map(|m| PlayedMatch {
+                    participants: data.participants(m.match_id).iter().filter_map(|p| p.battlesnake_id).collect(),
+                    winner_id: m.winner_id,
+                    game_winners: data.games(m.match_id).iter().map(|g| g.winner_id).collect(),
+                }).collect();
+                (standings_table(t, registrations, &round_robin_standings(&seeds, &played_matches)))
+                (bracket_grid(t, data, &shown, &grid_labels(t.format, &shown), None, "Round robin schedule"))
+            }
+        }
+    }
+}
+
 /// Form fields shared by the create and edit pages. When `current` is Some,
 /// fields are pre-filled with the tournament's existing values.
 #[allow(clippy::too_many_lines)]
-fn tournament_form_fields(current: Option<&Tournament>) -> Markup {
+pub(crate) fn tournament_form_fields(current: Option<&Tournament>) -> Markup {
     let name = current.map(|t| t.name.clone()).unwrap_or_default();
     let description = current
         .and_then(|t| t.description.clone())
@@ -619,6 +859,8 @@ fn tournament_form_fields(current: Option<&Tournament>) -> Markup {
     let game_type = current.map_or(GameType::Standard, |t| t.game_type.clone());
     let board_size = current.map_or(GameBoardSize::Medium, |t| t.board_size.clone());
     let match_style = current.map_or(MatchStyle::SingleGame, |t| t.match_style);
+    let format = current.map_or(TournamentFormat::SingleElimination, |t| t.format);
+    let auto_advance = current.is_some_and(|t| t.auto_advance);
     let registration_status = current.map_or(RegistrationStatus::Open, |t| t.registration_status);
     let visibility = current.map_or(TournamentVisibility::Public, |t| t.visibility);
     let max_snakes_per_user = current.map_or(1, |t| t.max_snakes_per_user);
@@ -656,6 +898,23 @@ fn tournament_form_fields(current: Option<&Tournament>) -> Markup {
             div class="help" { "Cannot be changed once snakes have registered" }
         }
 
+        div class="field" {
+            label for="format" { "Format" }
+            select id="format" name="format" required {
+                @for option in [
+                    TournamentFormat::SingleElimination,
+                    TournamentFormat::DoubleElimination,
+                    TournamentFormat::RoundRobin,
+                ] {
+                    option value=(option.as_str()) selected[format == option] { (option.label()) }
+                }
+            }
+            div class="help" {
+                "Double elimination needs 3+ snakes; round robin allows up to "
+                (MAX_ROUND_ROBIN_PARTICIPANTS)
+            }
+        }
+
         div class="field" {
             label for="match_style" { "Match Style" }
             select id="match_style" name="match_style" required {
@@ -665,6 +924,14 @@ fn tournament_form_fields(current: Option<&Tournament>) -> Markup {
             }
         }
 
+        div class="field" {
+            label {
+                input type="checkbox" name="auto_advance" value="true" checked[auto_advance] {}
+                " Run rounds automatically"
+            }
+            div class="help" { "Start each round as soon as the previous one finishes, instead of waiting for \"Run Round\"" }
+        }
+
         div class="field" {
             label for="registration_status" { "Registration" }
             select id="registration_status" name="registration_status" required {
@@ -723,6 +990,10 @@ pub struct TournamentSettingsForm {
     pub game_type: String,
     pub board_size: String,
     pub match_style: MatchStyle,
+    pub format: TournamentFormat,
+    /// Checkbox: absent from the form when unchecked.
+    #[serde(default)]
+    pub auto_advance: bool,
     pub registration_status: RegistrationStatus,
     pub visibility: TournamentVisibility,
     pub max_snakes_per_user: i32,
@@ -730,6 +1001,30 @@ pub struct TournamentSettingsForm {
 }
 
 impl TournamentSettingsForm {
+    /// Validate the form into a new tournament, or a user-facing message.
+    /// Shared by the owner and admin create handlers.
+    pub(crate) fn to_create(&self) -> Result<CreateTournament, String> {
+        validate_tournament_params(
+            &self.name,
+            &self.description,
+            self.required_participants,
+            self.max_snakes_per_user,
+        )?;
+        Ok(CreateTournament {
+            name: self.name.trim().to_string(),
+            description: self.description_opt(),
+            game_type: parse_game_type(&self.game_type)?,
+            board_size: parse_board_size(&self.board_size)?,
+            registration_status: self.registration_status,
+            visibility: self.visibility,
+            match_style: self.match_style,
+            format: self.format,
+            auto_advance: self.auto_advance,
+            max_snakes_per_user: self.max_snakes_per_user,
+            required_participants: self.required_participants,
+        })
+    }
+
     fn description_opt(&self) -> Option<String> {
         let trimmed = self.description.trim();
         if trimmed.is_empty() {
@@ -787,8 +1082,8 @@ pub async fn list_tournaments(
                 div {
                     h1 { "Tournaments" }
                     div class="sub" {
-                        "Single-elimination brackets. Register your snakes, "
-                        "seed the field, and play it out round by round."
+                        "Elimination brackets and round robins. Register your "
+                        "snakes, seed the field, and play it out round by round."
                     }
                 }
                 div class="spacer" {}
@@ -806,6 +1101,7 @@ pub async fn list_tournaments(
                             tr {
                                 th { "Tournament" }
                                 th { "Status" }
+                                th class="hide-sm" { "Format" }
                                 th class="r" { "Snakes" }
                                 th class="r hide-sm" { "Game" }
                                 th class="r hide-sm" { "Created" }
@@ -823,6 +1119,7 @@ pub async fn list_tournaments(
                                         }
                                     }
                                     td { (status_badge(t.status)) }
+                                    td class="hide-sm" { (t.format.label()) }
                                     td class="r num" { (t.registration_count) }
                                     td class="r num hide-sm" { (t.game_type.as_str()) }
                                     td class="r num hide-sm" { (t.created_at.format("%b %-d, %Y")) }
@@ -876,21 +1173,8 @@ pub async fn create_tournament(
     CurrentUserWithSession { user, session }: CurrentUserWithSession,
     Form(form): Form<TournamentSettingsForm>,
 ) -> ServerResult<impl IntoResponse, StatusCode> {
-    let parsed = validate_tournament_params(
-        &form.name,
-        &form.description,
-        form.required_participants,
-        form.max_snakes_per_user,
-    )
-    .and_then(|()| {
-        Ok((
-            parse_game_type(&form.game_type)?,
-            parse_board_size(&form.board_size)?,
-        ))
-    });
-
-    let (game_type, board_size) = match parsed {
-        Ok(values) => values,
+    let params = match form.to_create() {
+        Ok(params) => params,
         Err(message) => {
             return flash_redirect(
                 &state,
@@ -903,23 +1187,9 @@ pub async fn create_tournament(
         }
     };
 
-    let created = tournament::create_tournament(
-        &state.db,
-        user.user_id,
-        CreateTournament {
-            name: form.name.trim().to_string(),
-            description: form.description_opt(),
-            game_type,
-            board_size,
-            registration_status: form.registration_status,
-            visibility: form.visibility,
-            match_style: form.match_style,
-            max_snakes_per_user: form.max_snakes_per_user,
-            required_participants: form.required_participants,
-        },
-    )
-    .await
-    .wrap_err("Failed to create tournament")?;
+    let created = tournament::create_tournament(&state.db, user.user_id, params)
+        .await
+        .wrap_err("Failed to create tournament")?;
 
     flash_redirect(
         &state,
@@ -1048,7 +1318,10 @@ pub async fn show_tournament(
         .map(|(matches, _, _)| {
             (
                 matches.iter().map(|m| m.round).max().unwrap_or(0),
-                matches.len(),
+                matches
+                    .iter()
+                    .filter(|m| m.status != MatchStatus::Canceled)
+                    .count(),
                 matches
                     .iter()
                     .filter(|m| m.status == MatchStatus::Completed)
@@ -1075,6 +1348,7 @@ pub async fn show_tournament(
                     h1 { (t.name) }
                     div class="sub" {
                         "by " (owner_login)
+                        " · " (t.format.label())
                         " · " (t.game_type.as_str())
                         " · " (t.board_size.as_str())
                         " · " (style_label)
@@ -1133,7 +1407,10 @@ pub async fn show_tournament(
 
             // Bracket (in_progress / completed only)
             @if let Some((matches, participants_by_match, games_by_match)) = &bracket_data {
-                (bracket_section(&t, matches, participants_by_match, games_by_match))
+                (bracket_section(&t, &registrations, matches, &BracketData {
+                    participants_by_match,
+                    games_by_match,
+                }))
             }
 
             div class="grid" {
@@ -1271,9 +1548,11 @@ pub async fn show_tournament(
                     div class="block" {
                         h3 { "Format" }
                         dl class="meta-list" {
+                            div { dt { "Bracket" } dd { (t.format.label()) } }
                             div { dt { "Game" } dd { (t.game_type.as_str()) } }
                             div { dt { "Board" } dd { (t.board_size.as_str()) } }
                             div { dt { "Series" } dd { (style_label) } }
+                            div { dt { "Rounds" } dd { @if t.auto_advance { "Run automatically" } @else { "Run by the owner" } } }
                             div { dt { "Registration" } dd { (t.registration_status.as_str()) } }
                             div { dt { "Visibility" } dd { (t.visibility.as_str()) } }
                             div { dt { "Max snakes / user" } dd { (t.max_snakes_per_user) } }
@@ -1426,6 +1705,8 @@ pub async fn update_settings(
             game_type,
             board_size,
             match_style: form.match_style,
+            format: form.format,
+            auto_advance: form.auto_advance,
             registration_status: form.registration_status,
             visibility: form.visibility,
             max_snakes_per_user: form.max_snakes_per_user,
@@ -1830,7 +2111,7 @@ async fn start_tournament_in_tx(
         return Ok(Err(message));
     }
 
-    if let Err(err) = persist_bracket(tx, t.tournament_id, &registrations).await {
+    if let Err(err) = persist_bracket(tx, t.tournament_id, t.format, &registrations).await {
         if crate::tournament_match::is_unique_violation(
             &err,
             "tournament_matches_tournament_id_round_position_key",
@@ -1911,12 +2192,39 @@ pub async fn start_tournament(
                 .await
                 .wrap_err("Failed to commit start transaction")?;
 
+            let message = if !t.auto_advance {
+                format!(
+                    "Tournament started with {snake_count} snakes! Use \"Run Round\" to play each round."
+                )
+            } else if let Some(limit_message) =
+                game_creation_limit_message(&state, user.user_id).await?
+            {
+                format!(
+                    "Tournament started with {snake_count} snakes, but round 1 is on hold. {limit_message}"
+                )
+            } else {
+                // Enqueued after commit (matchmaker pattern); later rounds
+                // are queued by round progression.
+                cja::jobs::Job::enqueue(
+                    crate::jobs::RunTournamentRoundJob {
+                        tournament_id,
+                        round: 1,
+                    },
+                    state.clone(),
+                    format!("Tournament {tournament_id} started with auto-advance"),
+                    None,
+                )
+                .await
+                .wrap_err("Failed to enqueue round job")?;
+                format!(
+                    "Tournament started with {snake_count} snakes! Rounds will run automatically."
+                )
+            };
+
             flash_redirect(
                 &state,
                 session.session_id,
-                format!(
-                    "Tournament started with {snake_count} snakes! Use \"Run Round\" to play each round."
-                ),
+                message,
                 session::FLASH_TYPE_SUCCESS,
                 &detail_url,
             )
@@ -1925,6 +2233,44 @@ pub async fn start_tournament(
     }
 }
 
+/// Every game a round creates is charged to the owner's game-creation budget
+/// (see tournament_match); running a round is where that budget is enforced,
+/// since the match jobs themselves must never fail mid-flight. A round can
+/// overshoot the limit by its own games (they're charged as they're created,
+/// not up front), but the next round gets stopped here — which is what turns
+/// "reset and re-run forever" from unlimited games into the same throughput
+/// cap everyone else has. Returns the message to flash when the owner is
+/// over the limit.
+async fn game_creation_limit_message(
+    state: &AppState,
+    user_id: Uuid,
+) -> cja::Result<Option<String>> {
+    let limit = state.config.game_creation_rate_limit;
+    let window_minutes = state.config.game_creation_rate_limit_window_minutes;
+    let attempts = crate::models::rate_limit::count_recent_game_creation_attempts(
+        &state.db,
+        user_id,
+        window_minutes,
+    )
+    .await
+    .wrap_err("Failed to count game creation attempts")?;
+    if attempts < limit {
+        return Ok(None);
+    }
+    tracing::warn!(
+        event_type = "game_creation_rate_limited",
+        user_id = %user_id,
+        attempts = attempts,
+        limit = limit,
+        source = "tournament",
+        "tournament round blocked by game creation rate limit"
+    );
+    Ok(Some(format!(
+        "You're over the game-creation limit ({limit} games per {window_minutes} \
+         minutes, tournament games included). Wait a bit before running the next round."
+    )))
+}
+
 /// POST /tournaments/{id}/run-round — kick off the current round's matches
 /// (owner only, BS-023). The actual work happens in RunTournamentRoundJob;
 /// the job is enqueued outside any transaction (matchmaker pattern).
@@ -1957,38 +2303,11 @@ pub async fn run_round(
         .await;
     }
 
-    // Every game a round creates is charged to the owner's game-creation
-    // budget (see tournament_match); this gate is where that budget is
-    // enforced, since the match jobs themselves must never fail mid-flight.
-    // A round can overshoot the limit by its own games (they're charged as
-    // they're created, not up front), but the next click gets stopped here —
-    // which is what turns "reset and re-run forever" from unlimited games
-    // into the same throughput cap everyone else has.
-    let limit = state.config.game_creation_rate_limit;
-    let window_minutes = state.config.game_creation_rate_limit_window_minutes;
-    let attempts = crate::models::rate_limit::count_recent_game_creation_attempts(
-        &state.db,
-        user.user_id,
-        window_minutes,
-    )
-    .await
-    .wrap_err("Failed to count game creation attempts")?;
-    if attempts >= limit {
-        tracing::warn!(
-            event_type = "game_creation_rate_limited",
-            user_id = %user.user_id,
-            attempts = attempts,
-            limit = limit,
-            source = "tournament",
-            "tournament round blocked by game creation rate limit"
-        );
+    if let Some(message) = game_creation_limit_message(&state, user.user_id).await? {
         return flash_redirect(
             &state,
             session.session_id,
-            format!(
-                "You're over the game-creation limit ({limit} games per {window_minutes} \
-                 minutes, tournament games included). Wait a bit before running the next round."
-            ),
+            message,
             session::FLASH_TYPE_ERROR,
             &detail_url,
         )
@@ -2286,6 +2605,8 @@ mod tests {
             visibility,
             status,
             match_style: MatchStyle::SingleGame,
+            format: TournamentFormat::SingleElimination,
+            auto_advance: false,
             max_snakes_per_user: 1,
             required_participants: 2,
             current_round: 0,
@@ -2624,6 +2945,8 @@ mod tests {
             registration_status: RegistrationStatus::Open,
             visibility: TournamentVisibility::Public,
             match_style: MatchStyle::SingleGame,
+            format: TournamentFormat::SingleElimination,
+            auto_advance: false,
             max_snakes_per_user,
             required_participants: 2,
         }
@@ -2906,6 +3229,24 @@ mod tests {
         assert!(validate_start(&t, 2).is_ok());
     }
 
+    #[test]
+    fn validate_start_applies_format_limits() {
+        let mut t = test_tournament(
+            TournamentStatus::Registration,
+            RegistrationStatus::Open,
+            TournamentVisibility::Public,
+        );
+
+        t.format = TournamentFormat::DoubleElimination;
+        assert!(validate_start(&t, 2).is_err());
+        assert!(validate_start(&t, 3).is_ok());
+
+        t.format = TournamentFormat::RoundRobin;
+        assert!(validate_start(&t, 2).is_ok());
+        assert!(validate_start(&t, MAX_ROUND_ROBIN_PARTICIPANTS).is_ok());
+        assert!(validate_start(&t, MAX_ROUND_ROBIN_PARTICIPANTS + 1).is_err());
+    }
+
     #[test]
     fn round_labels_name_the_final() {
         assert_eq!(round_label(1, 1), "Final"); // 2-snake tournament
@@ -2914,6 +3255,31 @@ mod tests {
         assert_eq!(round_label(3, 3), "Final");
     }
 
+    #[test]
+    fn column_labels_name_each_bracket() {
+        use MatchBracket::{GrandFinal, Losers, Pool, Winners};
+        use TournamentFormat::{DoubleElimination, RoundRobin, SingleElimination};
+
+        assert_eq!(column_label(SingleElimination, Winners, 0, 3), "Round 1");
+        assert_eq!(column_label(SingleElimination, Winners, 2, 3), "Final");
+        assert_eq!(column_label(DoubleElimination, Winners, 0, 3), "Winners R1");
+        assert_eq!(
+            column_label(DoubleElimination, Winners, 2, 3),
+            "Winners Final"
+        );
+        assert_eq!(
+            column_label(DoubleElimination, GrandFinal, 3, 1),
+            "Grand Final"
+        );
+        assert_eq!(column_label(DoubleElimination, Losers, 1, 4), "Losers R2");
+        assert_eq!(
+            column_label(DoubleElimination, Losers, 3, 4),
+            "Losers Final"
+        );
+        // Every round robin round is just a round, the last included
+        assert_eq!(column_label(RoundRobin, Pool, 2, 3), "Round 3");
+    }
+
     #[test]
     fn win_count_counts_only_the_participants_wins() {
         let a = Uuid::new_v4();
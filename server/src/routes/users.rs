@@ -193,6 +193,7 @@ mod tests {
             is_admin: false,
             site_theme: "system".to_string(),
             theater_theme: "dark".to_string(),
+            suspended_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
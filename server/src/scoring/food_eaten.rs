@@ -7,6 +7,8 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use super::{EntryScore, GameResultEvent, ScoringAlgorithm};
+use crate::models::leaderboard::LeaderboardGameResult;
+use crate::models::turn::StoredFrame;
 
 pub struct FoodEatenScoring;
 
@@ -65,17 +67,21 @@ async fn count_food_eaten(
     conn: &mut sqlx::PgConnection,
     game_id: Uuid,
 ) -> cja::Result<HashMap<String, i32>> {
-    let first_turn = sqlx::query_scalar!(
-        r#"SELECT frame_data FROM turns WHERE game_id = $1 AND turn_number = 0"#,
+    let first_turn = sqlx::query_as!(
+        StoredFrame,
+        r#"SELECT frame_data, frame_bin FROM turns WHERE game_id = $1 AND turn_number = 0"#,
         game_id
     )
     .fetch_optional(&mut *conn)
     .await
     .wrap_err("Failed to fetch first turn")?
+    .map(StoredFrame::into_json)
+    .transpose()?
     .flatten();
 
-    let last_turn = sqlx::query_scalar!(
-        r#"SELECT frame_data FROM turns
+    let last_turn = sqlx::query_as!(
+        StoredFrame,
+        r#"SELECT frame_data, frame_bin FROM turns
          WHERE game_id = $1
          ORDER BY turn_number DESC
          LIMIT 1"#,
@@ -84,6 +90,8 @@ async fn count_food_eaten(
     .fetch_optional(&mut *conn)
     .await
     .wrap_err("Failed to fetch last turn")?
+    .map(StoredFrame::into_json)
+    .transpose()?
     .flatten();
 
     let (Some(first_frame), Some(last_frame)) = (first_turn, last_turn) else {
@@ -196,6 +204,28 @@ impl ScoringAlgorithm for FoodEatenScoring {
         Ok(())
     }
 
+    async fn reverse_game_result(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        results: &[LeaderboardGameResult],
+    ) -> cja::Result<()> {
+        for result in results.iter().filter(|r| r.food_eaten > 0) {
+            sqlx::query!(
+                "UPDATE food_eaten_stats SET \
+                    food_score = GREATEST(food_score - $2, 0), \
+                    updated_at = NOW() \
+                 WHERE leaderboard_entry_id = $1",
+                result.leaderboard_entry_id,
+                result.food_eaten as i64,
+            )
+            .execute(&mut *conn)
+            .await
+            .wrap_err("Failed to reverse food_eaten_stats")?;
+        }
+
+        Ok(())
+    }
+
     async fn get_scores(&self, pool: &PgPool, entry_ids: &[Uuid]) -> cja::Result<Vec<EntryScore>> {
         let rows = sqlx::query!(
             "SELECT leaderboard_entry_id, food_score \
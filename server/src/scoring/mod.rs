@@ -2,6 +2,8 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::models::leaderboard::LeaderboardGameResult;
+
 /// Event representing the results of a completed leaderboard game.
 /// Passed to each scoring algorithm to update its internal state.
 pub struct GameResultEvent {
@@ -58,6 +60,17 @@ pub trait ScoringAlgorithm: Send + Sync {
         event: &GameResultEvent,
     ) -> cja::Result<()>;
 
+    /// Undo a game's contribution, given the results recorded for it. Called
+    /// within a transaction, with the entries locked, when an admin deletes
+    /// the game. Incremental algorithms can only subtract what the game added,
+    /// which is only exact when the game was each entry's latest, so
+    /// [`crate::leaderboard_ratings::reverse_ratings`] calls this only then.
+    async fn reverse_game_result(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        results: &[LeaderboardGameResult],
+    ) -> cja::Result<()>;
+
     /// Batch fetch scores for the given entry IDs.
     /// Callers are responsible for pagination/filtering — this just looks up scores
     /// for the provided IDs. Returns results in no guaranteed order.
@@ -81,6 +94,15 @@ impl ScoringRegistry {
         Self { algorithms: vec![] }
     }
 
+    /// Every algorithm the arena scores leaderboards with
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(weng_lin::WengLinScoring));
+        registry.register(Box::new(win_rate::WinRateScoring));
+        registry.register(Box::new(food_eaten::FoodEatenScoring));
+        registry
+    }
+
     pub fn register(&mut self, algo: Box<dyn ScoringAlgorithm>) {
         self.algorithms.push(algo);
     }
@@ -141,6 +163,14 @@ mod tests {
             Ok(())
         }
 
+        async fn reverse_game_result(
+            &self,
+            _conn: &mut sqlx::PgConnection,
+            _results: &[LeaderboardGameResult],
+        ) -> cja::Result<()> {
+            Ok(())
+        }
+
         async fn get_scores(
             &self,
             _pool: &PgPool,
@@ -5,7 +5,7 @@ use skillratings::weng_lin::{WengLinConfig, WengLinRating, weng_lin_multi_team};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::leaderboard::{self, LeaderboardEntry};
+use crate::models::leaderboard::{self, LeaderboardEntry, LeaderboardGameResult};
 
 use super::{EntryScore, GameResultEvent, ScoringAlgorithm};
 
@@ -217,6 +217,42 @@ impl ScoringAlgorithm for WengLinScoring {
         Ok(())
     }
 
+    async fn reverse_game_result(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        results: &[LeaderboardGameResult],
+    ) -> cja::Result<()> {
+        for result in results {
+            let mu_change = result.mu_after - result.mu_before;
+            let sigma_change = result.sigma_after - result.sigma_before;
+
+            sqlx::query!(
+                "UPDATE weng_lin_ratings SET \
+                   mu = mu - $2, sigma = sigma - $3, \
+                   display_score = (mu - $2) - 3.0 * (sigma - $3), updated_at = NOW() \
+                 WHERE leaderboard_entry_id = $1",
+                result.leaderboard_entry_id,
+                mu_change,
+                sigma_change,
+            )
+            .execute(&mut *conn)
+            .await
+            .wrap_err("Failed to reverse weng_lin_ratings")?;
+
+            leaderboard::reverse_rating(
+                &mut *conn,
+                result.leaderboard_entry_id,
+                mu_change,
+                sigma_change,
+                result.placement == 1,
+            )
+            .await
+            .wrap_err("Failed to reverse leaderboard_entries rating")?;
+        }
+
+        Ok(())
+    }
+
     async fn get_scores(&self, pool: &PgPool, entry_ids: &[Uuid]) -> cja::Result<Vec<EntryScore>> {
         let rows = sqlx::query!(
             "SELECT leaderboard_entry_id, display_score, mu, sigma \
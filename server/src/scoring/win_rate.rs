@@ -4,6 +4,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use super::{EntryScore, GameResultEvent, ScoringAlgorithm};
+use crate::models::leaderboard::LeaderboardGameResult;
 
 /// Win Rate scoring algorithm implementation.
 pub struct WinRateScoring;
@@ -99,6 +100,36 @@ impl ScoringAlgorithm for WinRateScoring {
         Ok(())
     }
 
+    async fn reverse_game_result(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        results: &[LeaderboardGameResult],
+    ) -> cja::Result<()> {
+        for result in results {
+            let is_win = result.placement == 1;
+
+            sqlx::query!(
+                "UPDATE win_rate_stats SET \
+                    games_played = GREATEST(games_played - 1, 0), \
+                    wins = GREATEST(wins - CASE WHEN $2 THEN 1 ELSE 0 END, 0), \
+                    losses = GREATEST(losses - CASE WHEN $2 THEN 0 ELSE 1 END, 0), \
+                    score = CASE WHEN games_played - 1 > 0 \
+                        THEN GREATEST(wins - CASE WHEN $2 THEN 1 ELSE 0 END, 0)::double precision \
+                             / (games_played - 1)::double precision * 100.0 \
+                        ELSE 0.0 END, \
+                    updated_at = NOW() \
+                 WHERE leaderboard_entry_id = $1",
+                result.leaderboard_entry_id,
+                is_win,
+            )
+            .execute(&mut *conn)
+            .await
+            .wrap_err("Failed to reverse win_rate_stats")?;
+        }
+
+        Ok(())
+    }
+
     async fn get_scores(&self, pool: &PgPool, entry_ids: &[Uuid]) -> cja::Result<Vec<EntryScore>> {
         let rows = sqlx::query!(
             "SELECT leaderboard_entry_id, score, wins, losses, games_played \
@@ -0,0 +1,163 @@
+//! Graceful shutdown with running-game draining.
+//!
+//! On SIGTERM (or Ctrl-C) the process stops its job workers from picking up
+//! new jobs and gives in-flight [`GameRunnerJob`]s up to
+//! [`crate::config::JobConfig::shutdown_drain_secs`] to finish. A game still
+//! running when that deadline passes is checkpointed at the next turn
+//! boundary: every turn it has played is already committed, so it goes back
+//! to `waiting` with them intact and a fresh runner job is queued. Whichever
+//! process picks that up rebuilds the board from the last stored frame and
+//! carries on from there (see [`crate::game_runner::run_game`]), rather than
+//! the game sitting stranded in `running` for the stuck-game watchdog.
+
+use std::time::Duration;
+
+use color_eyre::eyre::Context as _;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::jobs::GameRunnerJob;
+use crate::models::game::{self, GameStatus};
+use crate::state::AppState;
+
+/// Process-wide shutdown state, shared through [`AppState`]
+#[derive(Clone, Debug, Default)]
+pub struct Shutdown {
+    /// Cancelled when the signal arrives; job workers stop polling
+    requested: CancellationToken,
+    /// Cancelled once the drain deadline passes; running games checkpoint
+    deadline: CancellationToken,
+}
+
+impl Shutdown {
+    /// Begin shutting down: job workers stop taking new jobs
+    pub fn trigger(&self) {
+        self.requested.cancel();
+    }
+
+    /// Wait out the drain window, then tell running games to checkpoint.
+    /// The shutdown path awaits this alongside the draining workers.
+    pub async fn expire_after(&self, drain: Duration) {
+        tokio::time::sleep(drain).await;
+        self.deadline.cancel();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.is_cancelled()
+    }
+
+    /// Whether running games should stop and checkpoint instead of playing
+    /// another turn
+    pub fn deadline_passed(&self) -> bool {
+        self.deadline.is_cancelled()
+    }
+
+    /// Token handed to the job workers, cancelled when shutdown begins
+    pub fn worker_token(&self) -> CancellationToken {
+        self.requested.child_token()
+    }
+}
+
+/// Resolve on SIGTERM or Ctrl-C
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
+/// Hand a game this process can't finish back to the queue at a turn
+/// boundary: return it to `waiting`, keeping its turns, and enqueue a fresh
+/// runner to resume it from the last one.
+pub async fn checkpoint_game(app_state: &AppState, game_id: Uuid) -> cja::Result<()> {
+    game::update_game_status(&app_state.db, game_id, GameStatus::Waiting).await?;
+    cja::jobs::Job::enqueue(
+        GameRunnerJob { game_id },
+        app_state.clone(),
+        format!("Resume game {game_id} after shutdown"),
+        None,
+    )
+    .await
+    .wrap_err("Failed to re-enqueue checkpointed game runner")?;
+
+    tracing::warn!(
+        event_type = "game_checkpointed",
+        game_id = %game_id,
+        "Shutdown deadline reached; game handed back to the queue"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    #[tokio::test]
+    async fn deadline_passes_after_the_drain_window() {
+        let shutdown = Shutdown::default();
+        let worker_token = shutdown.worker_token();
+        assert!(!shutdown.is_requested());
+
+        shutdown.trigger();
+        assert!(shutdown.is_requested());
+        assert!(worker_token.is_cancelled());
+        assert!(!shutdown.deadline_passed());
+
+        shutdown.expire_after(Duration::from_millis(20)).await;
+        assert!(shutdown.deadline_passed());
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn checkpointed_game_is_requeued_with_its_turns(pool: PgPool) -> cja::Result<()> {
+        let app_state = AppState::test_from_pool(pool.clone());
+        let game_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'running')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+        sqlx::query("INSERT INTO turns (game_id, turn_number) VALUES ($1, 0)")
+            .bind(game_id)
+            .execute(&pool)
+            .await?;
+
+        checkpoint_game(&app_state, game_id).await?;
+
+        let game = game::get_game_by_id(&pool, game_id).await?.unwrap();
+        assert_eq!(game.status, GameStatus::Waiting);
+        // The runner resumes from the last committed turn, so it's kept
+        assert_eq!(
+            crate::models::turn::get_turns_by_game_id(&pool, game_id)
+                .await?
+                .len(),
+            1
+        );
+        let runners = crate::models::job::list_runner_jobs_for_game(&pool, game_id).await?;
+        assert_eq!(runners.len(), 1);
+
+        Ok(())
+    }
+}
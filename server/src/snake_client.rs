@@ -12,6 +12,9 @@ use url::Url;
 
 use crate::engine::EngineGame;
 use crate::engine::frame::SnakeCustomizations;
+use crate::models::turn::MoveError;
+use crate::snake_failure_log::SNAKE_FAILURES;
+use crate::telemetry::{redact_secrets, redact_url};
 use crate::wire;
 
 /// Response from a snake's /move endpoint
@@ -29,6 +32,8 @@ pub struct MoveResult {
     pub direction: Direction,
     pub latency_ms: Option<i64>,
     pub timed_out: bool,
+    /// Why the call failed, if it did; the move is then a fallback
+    pub error: Option<MoveError>,
     pub shout: Option<String>,
 }
 
@@ -77,7 +82,20 @@ pub(crate) fn build_endpoint_url(base_url: &str, endpoint: &str) -> String {
 /// Call a snake's /move endpoint
 ///
 /// On timeout or error, falls back to the last direction (or Up if no last direction).
+/// Failures are logged through [`SNAKE_FAILURES`], a few per snake a minute.
+/// Runs in a `snake_move` span recording the latency and any failure, a
+/// child of the runner's `turn` span.
 #[allow(clippy::too_many_arguments)]
+#[tracing::instrument(
+    name = "snake_move",
+    skip_all,
+    fields(
+        snake_id = %snake_id,
+        latency_ms = tracing::field::Empty,
+        timed_out = tracing::field::Empty,
+        error = tracing::field::Empty,
+    )
+)]
 pub async fn request_move(
     client: &Client,
     url: &str,
@@ -98,32 +116,42 @@ pub async fn request_move(
 
     let elapsed = start.elapsed().as_millis() as i64;
 
-    match result {
+    let move_result = match result {
         Ok(Ok(response)) => {
+            // A non-2xx is still an answer: use its move if it parses, but
+            // record the status so the error report can flag the snake
+            let status_error = (!response.status().is_success())
+                .then(|| MoveError::HttpStatus(response.status().as_u16()));
             match response.json::<MoveResponse>().await {
                 Ok(move_response) => {
-                    let direction = parse_direction(&move_response.direction)
-                        .unwrap_or_else(|| last_direction.unwrap_or(Direction::Up));
+                    let parsed = parse_direction(&move_response.direction);
+                    let error = status_error.or(parsed.is_none().then_some(MoveError::Parse));
+                    let direction =
+                        parsed.unwrap_or_else(|| last_direction.unwrap_or(Direction::Up));
                     MoveResult {
                         snake_id: snake_id.to_string(),
                         direction,
                         latency_ms: Some(elapsed),
                         timed_out: false,
+                        error,
                         shout: move_response.shout,
                     }
                 }
                 Err(e) => {
                     // JSON parse error - use fallback
-                    tracing::warn!(
-                        snake_id = %snake_id,
-                        error = %e,
-                        "Failed to parse move response, using fallback"
-                    );
+                    if SNAKE_FAILURES.record(&redact_url(url), "move_parse") {
+                        tracing::warn!(
+                            snake_id = %snake_id,
+                            error = %redact_secrets(&e.to_string()),
+                            "Failed to parse move response, using fallback"
+                        );
+                    }
                     MoveResult {
                         snake_id: snake_id.to_string(),
                         direction: last_direction.unwrap_or(Direction::Up),
                         latency_ms: Some(elapsed),
                         timed_out: false,
+                        error: Some(status_error.unwrap_or(MoveError::Parse)),
                         shout: None,
                     }
                 }
@@ -131,35 +159,51 @@ pub async fn request_move(
         }
         Ok(Err(e)) => {
             // Network error - continue in same direction
-            tracing::warn!(
-                snake_id = %snake_id,
-                error = %e,
-                "Network error calling snake, using fallback"
-            );
+            if SNAKE_FAILURES.record(&redact_url(url), "move_network") {
+                tracing::warn!(
+                    snake_id = %snake_id,
+                    error = %redact_secrets(&e.to_string()),
+                    "Network error calling snake, using fallback"
+                );
+            }
             MoveResult {
                 snake_id: snake_id.to_string(),
                 direction: last_direction.unwrap_or(Direction::Up),
                 latency_ms: None,
                 timed_out: true,
+                error: Some(MoveError::Network),
                 shout: None,
             }
         }
         Err(_) => {
             // Timeout - continue in same direction
-            tracing::warn!(
-                snake_id = %snake_id,
-                timeout_ms = timeout.as_millis(),
-                "Snake timed out, using fallback"
-            );
+            if SNAKE_FAILURES.record(&redact_url(url), "move_timeout") {
+                tracing::warn!(
+                    snake_id = %snake_id,
+                    timeout_ms = timeout.as_millis(),
+                    "Snake timed out, using fallback"
+                );
+            }
             MoveResult {
                 snake_id: snake_id.to_string(),
                 direction: last_direction.unwrap_or(Direction::Up),
                 latency_ms: None,
                 timed_out: true,
+                error: Some(MoveError::Timeout),
                 shout: None,
             }
         }
+    };
+
+    let span = tracing::Span::current();
+    if let Some(latency) = move_result.latency_ms {
+        span.record("latency_ms", latency);
+    }
+    span.record("timed_out", move_result.timed_out);
+    if let Some(error) = &move_result.error {
+        span.record("error", error.kind());
     }
+    move_result
 }
 
 /// Call /start endpoint (fire and forget, no response expected)
@@ -181,10 +225,18 @@ pub async fn request_start(
             tracing::debug!(snake_id = %snake_id, "Called /start successfully");
         }
         Ok(Err(e)) => {
-            tracing::warn!(snake_id = %snake_id, error = %e, "Failed to call /start");
+            if SNAKE_FAILURES.record(&redact_url(url), "start_network") {
+                tracing::warn!(
+                    snake_id = %snake_id,
+                    error = %redact_secrets(&e.to_string()),
+                    "Failed to call /start"
+                );
+            }
         }
         Err(_) => {
-            tracing::warn!(snake_id = %snake_id, "Timeout calling /start");
+            if SNAKE_FAILURES.record(&redact_url(url), "start_timeout") {
+                tracing::warn!(snake_id = %snake_id, "Timeout calling /start");
+            }
         }
     }
 }
@@ -208,10 +260,18 @@ pub async fn request_end(
             tracing::debug!(snake_id = %snake_id, "Called /end successfully");
         }
         Ok(Err(e)) => {
-            tracing::warn!(snake_id = %snake_id, error = %e, "Failed to call /end");
+            if SNAKE_FAILURES.record(&redact_url(url), "end_network") {
+                tracing::warn!(
+                    snake_id = %snake_id,
+                    error = %redact_secrets(&e.to_string()),
+                    "Failed to call /end"
+                );
+            }
         }
         Err(_) => {
-            tracing::warn!(snake_id = %snake_id, "Timeout calling /end");
+            if SNAKE_FAILURES.record(&redact_url(url), "end_timeout") {
+                tracing::warn!(snake_id = %snake_id, "Timeout calling /end");
+            }
         }
     }
 }
@@ -344,6 +404,8 @@ pub struct SnakeInfoResponse {
     pub tail: Option<String>,
     #[serde(default)]
     pub customizations: Option<InfoCustomizations>,
+    #[serde(default)]
+    pub version: Option<String>,
 }
 
 pub async fn request_info(
@@ -355,16 +417,30 @@ pub async fn request_info(
         Ok(Ok(response)) => match response.json::<SnakeInfoResponse>().await {
             Ok(info) => Some(info),
             Err(e) => {
-                tracing::warn!(url = %url, error = %e, "Failed to parse snake info response");
+                if SNAKE_FAILURES.record(&redact_url(url), "info_parse") {
+                    tracing::warn!(
+                        url = %redact_url(url),
+                        error = %redact_secrets(&e.to_string()),
+                        "Failed to parse snake info response"
+                    );
+                }
                 None
             }
         },
         Ok(Err(e)) => {
-            tracing::warn!(url = %url, error = %e, "Network error fetching snake info");
+            if SNAKE_FAILURES.record(&redact_url(url), "info_network") {
+                tracing::warn!(
+                    url = %redact_url(url),
+                    error = %redact_secrets(&e.to_string()),
+                    "Network error fetching snake info"
+                );
+            }
             None
         }
         Err(_) => {
-            tracing::warn!(url = %url, "Timeout fetching snake info");
+            if SNAKE_FAILURES.record(&redact_url(url), "info_timeout") {
+                tracing::warn!(url = %redact_url(url), "Timeout fetching snake info");
+            }
             None
         }
     }
@@ -511,6 +587,7 @@ mod tests {
             direction: Direction::Up,
             latency_ms: Some(100),
             timed_out: false,
+            error: None,
             shout: Some("hello".to_string()),
         };
         let cloned = result.clone();
@@ -0,0 +1,171 @@
+//! Throttled warnings for failed snake calls.
+//!
+//! A snake that's down fails every call in every game it's in, and the
+//! runner used to log a warning for each one: a single broken snake in a few
+//! dozen concurrent games meant thousands of identical lines a minute. Now
+//! each snake (by URL) gets [`WARNINGS_PER_WINDOW`] warnings per
+//! [`WINDOW`]; the rest are only counted, and once the window is over
+//! [`report_summaries`] logs one `snake_failures_summary` event with the
+//! totals.
+//!
+//! The `snake_move` span still records every failure, so traces are
+//! unaffected.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+const WARNINGS_PER_WINDOW: u32 = 3;
+
+pub static SNAKE_FAILURES: LazyLock<FailureLog> =
+    LazyLock::new(|| FailureLog::new(WINDOW, WARNINGS_PER_WINDOW));
+
+/// Failure counts per snake over the current window
+pub struct FailureLog {
+    window: Duration,
+    warnings_per_window: u32,
+    snakes: Mutex<HashMap<String, FailureCounts>>,
+}
+
+struct FailureCounts {
+    window_start: Instant,
+    failures: u32,
+    by_kind: BTreeMap<&'static str, u32>,
+}
+
+/// One snake's failures over a finished window
+#[derive(Debug, PartialEq, Eq)]
+pub struct FailureSummary {
+    /// The snake's URL, redacted
+    pub snake: String,
+    pub failures: u32,
+    /// Failures that weren't logged on their own
+    pub suppressed: u32,
+    pub by_kind: BTreeMap<&'static str, u32>,
+}
+
+impl FailureLog {
+    fn new(window: Duration, warnings_per_window: u32) -> Self {
+        Self {
+            window,
+            warnings_per_window,
+            snakes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a failed call to `snake`. Returns whether this one should still
+    /// be logged, i.e. it's among the first few of the snake's window.
+    pub fn record(&self, snake: &str, kind: &'static str) -> bool {
+        self.record_at(snake, kind, Instant::now())
+    }
+
+    fn record_at(&self, snake: &str, kind: &'static str, now: Instant) -> bool {
+        let mut snakes = self.snakes.lock().unwrap_or_else(|e| e.into_inner());
+        let counts = snakes
+            .entry(snake.to_string())
+            .or_insert_with(|| FailureCounts {
+                window_start: now,
+                failures: 0,
+                by_kind: BTreeMap::new(),
+            });
+        counts.failures += 1;
+        *counts.by_kind.entry(kind).or_default() += 1;
+        counts.failures <= self.warnings_per_window
+    }
+
+    /// Remove the snakes whose window has ended, returning the ones that had
+    /// failures suppressed
+    fn take_finished(&self, now: Instant) -> Vec<FailureSummary> {
+        let mut snakes = self.snakes.lock().unwrap_or_else(|e| e.into_inner());
+        let finished: Vec<String> = snakes
+            .iter()
+            .filter(|(_, counts)| now.duration_since(counts.window_start) >= self.window)
+            .map(|(snake, _)| snake.clone())
+            .collect();
+
+        let mut summaries: Vec<FailureSummary> = finished
+            .into_iter()
+            .filter_map(|snake| {
+                let counts = snakes.remove(&snake)?;
+                let suppressed = counts.failures.saturating_sub(self.warnings_per_window);
+                (suppressed > 0).then_some(FailureSummary {
+                    snake,
+                    failures: counts.failures,
+                    suppressed,
+                    by_kind: counts.by_kind,
+                })
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.snake.cmp(&b.snake));
+        summaries
+    }
+}
+
+/// Log a summary for every snake whose window ended with failures left
+/// unlogged. Runs for the life of the process alongside the job workers.
+pub async fn report_summaries() -> cja::Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        for summary in SNAKE_FAILURES.take_finished(Instant::now()) {
+            let kinds = summary
+                .by_kind
+                .iter()
+                .map(|(kind, count)| format!("{kind}={count}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            tracing::warn!(
+                event_type = "snake_failures_summary",
+                snake_url = %summary.snake,
+                failures = summary.failures,
+                suppressed = summary.suppressed,
+                kinds = %kinds,
+                "Snake {} failed {} times in the last minute",
+                summary.snake,
+                summary.failures
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_a_few_times_per_window_then_summarizes() {
+        let log = FailureLog::new(Duration::from_secs(60), 2);
+        let start = Instant::now();
+
+        let logged: Vec<bool> = (0..5)
+            .map(|_| log.record_at("http://down.example", "move_timeout", start))
+            .collect();
+        assert_eq!(logged, [true, true, false, false, false]);
+        assert!(log.record_at("http://flaky.example", "move_network", start));
+
+        // Nothing is summarized until the window is over
+        assert!(
+            log.take_finished(start + Duration::from_secs(30))
+                .is_empty()
+        );
+
+        // Only the snake with suppressed failures gets a summary, and both
+        // start a fresh window
+        let summaries = log.take_finished(start + Duration::from_secs(60));
+        assert_eq!(
+            summaries,
+            [FailureSummary {
+                snake: "http://down.example".to_string(),
+                failures: 5,
+                suppressed: 3,
+                by_kind: BTreeMap::from([("move_timeout", 5)]),
+            }]
+        );
+        assert!(log.record_at(
+            "http://down.example",
+            "move_timeout",
+            start + Duration::from_secs(61)
+        ));
+    }
+}
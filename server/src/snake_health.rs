@@ -6,6 +6,9 @@
 //! same paths the game runner uses (`engine::create_initial_game` +
 //! `wire::Game::from_engine_game`), so a snake that passes the test sees the
 //! same wire format a real game would send it.
+//!
+//! The test console reuses the same call and evaluation path for a single
+//! `/move` whose body the snake's owner has edited by hand.
 
 use reqwest::Client;
 use serde::Deserialize;
@@ -218,6 +221,84 @@ pub async fn run_health_check(
     }
 }
 
+/// Largest hand-edited `/move` body the test console will send.
+pub const CONSOLE_MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Result of one hand-crafted `/move` sent from the test console. Unlike the
+/// health check, the raw body is always kept: seeing exactly what came back
+/// is the point.
+pub struct ConsoleMoveResult {
+    pub call: HealthCheckCall,
+    /// Full response body (up to the read cap), when a response arrived
+    pub raw_body: Option<String>,
+}
+
+/// Pretty-printed turn-0 `/move` payload for `snake`, built the same way the
+/// health check builds its payloads. Seeds the console's editor.
+pub fn default_console_payload(snake: &Battlesnake) -> String {
+    let (engine_game, snake_id) = build_test_game(snake);
+    let payload =
+        wire::Game::from_engine_game(&engine_game, &snake_id, &HashMap::new(), &HashMap::new());
+    serde_json::to_string_pretty(&payload).unwrap_or_default()
+}
+
+/// Check an edited console payload before sending it: it has to be a JSON
+/// object of a sane size. Anything else is sent as-is — the console exists to
+/// try payloads a real game wouldn't produce.
+pub fn parse_console_payload(text: &str) -> Result<serde_json::Value, String> {
+    if text.len() > CONSOLE_MAX_PAYLOAD_BYTES {
+        return Err(format!(
+            "Request body is too large (max {} KB)",
+            CONSOLE_MAX_PAYLOAD_BYTES / 1024
+        ));
+    }
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) if value.is_object() => Ok(value),
+        Ok(_) => Err("Request body must be a JSON object".to_string()),
+        Err(e) => Err(format!("Request body is not valid JSON: {e}")),
+    }
+}
+
+/// Send one `POST /move` with a caller-supplied body and judge the response
+/// exactly as the health check would.
+pub async fn send_console_move(
+    client: &Client,
+    url: &str,
+    payload: &serde_json::Value,
+) -> ConsoleMoveResult {
+    let move_url = build_endpoint_url(url, "move");
+    let outcome = execute_call(client.post(&move_url).json(payload), HEALTH_CHECK_TIMEOUT).await;
+    let raw_body = match &outcome {
+        CallOutcome::Response { body, .. } => Some(body.clone()),
+        CallOutcome::Failed { .. } => None,
+    };
+    ConsoleMoveResult {
+        call: evaluate_call("POST /move", &Expectation::Move, outcome),
+        raw_body,
+    }
+}
+
+/// Fetch the snake's `GET /` and check its verification token appears
+/// somewhere in the response body. `Err` explains what went wrong in terms
+/// the owner can act on.
+pub async fn check_url_token(
+    client: &Client,
+    url: &str,
+    token: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    match execute_call(client.get(url), timeout).await {
+        CallOutcome::Failed { summary, .. } => Err(summary),
+        CallOutcome::Response { status, .. } if !(200..300).contains(&status) => {
+            Err(format!("GET / returned non-success HTTP status {status}"))
+        }
+        CallOutcome::Response { body, .. } if body.contains(token) => Ok(()),
+        CallOutcome::Response { .. } => Err(format!(
+            "GET / responded, but the response didn't include your verification token {token}"
+        )),
+    }
+}
+
 /// Execute one HTTP call with a timeout, mirroring how `snake_client` wraps
 /// its requests in `tokio::time::timeout`.
 async fn execute_call(builder: reqwest::RequestBuilder, timeout: Duration) -> CallOutcome {
@@ -688,6 +769,37 @@ mod tests {
         assert_eq!(game.snake_names.get(&snake_id), Some(&snake.name));
     }
 
+    // === test console ===
+
+    #[test]
+    fn default_console_payload_parses_back() {
+        let payload = default_console_payload(&test_snake());
+        let json = parse_console_payload(&payload).unwrap();
+        assert_eq!(json["you"]["name"], "Test Snake");
+        assert_eq!(json["turn"], 0);
+    }
+
+    #[test]
+    fn console_payload_must_be_a_json_object() {
+        assert!(parse_console_payload("{\"turn\": 3}").is_ok());
+        assert!(
+            parse_console_payload("[1, 2]")
+                .unwrap_err()
+                .contains("JSON object")
+        );
+        assert!(
+            parse_console_payload("{\"turn\":")
+                .unwrap_err()
+                .contains("not valid JSON")
+        );
+        let huge = format!("{{\"pad\": \"{}\"}}", "x".repeat(CONSOLE_MAX_PAYLOAD_BYTES));
+        assert!(
+            parse_console_payload(&huge)
+                .unwrap_err()
+                .contains("too large")
+        );
+    }
+
     #[test]
     fn test_game_wire_payload_is_strict() {
         let snake = test_snake();
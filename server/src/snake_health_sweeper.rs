@@ -179,6 +179,21 @@ async fn apply_probe_outcome(
         app_state.config.base_url, snake.battlesnake_id
     );
 
+    if let Err(e) = crate::notifications::notify_discord_snake_paused(
+        app_state,
+        snake,
+        &outcome.failure_summary,
+        &profile_url,
+    )
+    .await
+    {
+        tracing::warn!(
+            battlesnake_id = %snake.battlesnake_id,
+            error = %e,
+            "Failed to send Discord pause notification"
+        );
+    }
+
     match snake_health_status::owner_notification_email(&app_state.db, snake.battlesnake_id).await?
     {
         Some(email) => {
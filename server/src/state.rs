@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use color_eyre::eyre::{Context as _, eyre};
+use google_cloud_storage::client::{Client as GcsClient, ClientConfig};
 use sqlx::{PgPool, postgres::PgPoolOptions};
+use uuid::Uuid;
 
 use crate::config::AppConfig;
 use crate::discord::DiscordNotifier;
@@ -14,9 +16,16 @@ pub struct AppState {
     /// settings from here instead of reaching for `std::env`.
     pub config: Arc<AppConfig>,
     pub db: sqlx::Pool<sqlx::Postgres>,
+    /// Pool for heavy, lag-tolerant reads: the read replica when
+    /// DATABASE_REPLICA_URL is set, otherwise the same pool as `db`. Never
+    /// write through it, and don't read back from it what a request just
+    /// wrote.
+    pub read_db: sqlx::Pool<sqlx::Postgres>,
     pub cookie_key: cja::server::cookies::CookieKey,
     /// Connection to the legacy Battlesnake Engine database (for game backup)
     pub engine_db: Option<sqlx::Pool<sqlx::Postgres>>,
+    /// GCS client for game backups and frame archives, set when GCS_BUCKET is
+    pub gcs: Option<GcsClient>,
     /// Broadcast channels for live game updates
     pub game_channels: GameChannels,
     /// HTTP client for calling snake APIs
@@ -29,8 +38,65 @@ pub struct AppState {
     pub scoring: std::sync::Arc<crate::scoring::ScoringRegistry>,
     /// TTL memo for the anonymous-homepage feed (see HOME_FEED_CACHE_SECS)
     pub home_feed_cache: Arc<crate::cache::TtlCell<crate::models::leaderboard::HomeFeed>>,
+    /// TTL memo of the leaderboards list (see LEADERBOARD_CACHE_SECS)
+    pub leaderboards_cache:
+        Arc<crate::cache::TtlCell<Vec<crate::models::leaderboard::Leaderboard>>>,
+    /// TTL memo of each leaderboard's status counts
+    pub leaderboard_status_cache:
+        Arc<crate::cache::TtlMap<Uuid, crate::models::leaderboard::LeaderboardStatus>>,
+    /// TTL memo of leaderboard standings, keyed by leaderboard, sort, and
+    /// page (`None` for the full standings)
+    pub standings_cache:
+        Arc<crate::cache::TtlMap<StandingsKey, Vec<crate::models::leaderboard::RankedEntry>>>,
+    /// Short TTL memo of the maintenance flag, read on every page render
+    pub maintenance_cache: Arc<crate::cache::TtlCell<crate::models::maintenance::MaintenanceState>>,
+    /// Short TTL memo of the runtime settings, read by the matchmaker and runner
+    pub settings_cache: Arc<crate::cache::TtlCell<crate::models::setting::RuntimeSettings>>,
+    /// Recently downloaded frame archives, so paging through an archived
+    /// replay fetches it from GCS once
+    pub frame_archive_cache: Arc<crate::cache::TtlMap<Uuid, crate::frame_archive::FrameArchive>>,
+    /// Rendered Open Graph preview images, so an unfurl burst for a freshly
+    /// shared link draws the PNG once
+    pub preview_image_cache:
+        Arc<crate::cache::TtlMap<crate::preview_image::PreviewKey, axum::body::Bytes>>,
+    /// Set on SIGTERM; job workers and running games watch it
+    pub shutdown: crate::shutdown::Shutdown,
 }
 
+/// How long each process trusts its cached maintenance flag. Toggles take
+/// effect on other processes within this window.
+const MAINTENANCE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Same idea for the runtime settings
+const SETTINGS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Leaderboard, sort, and `(page, per_page)` of a cached standings slice
+pub type StandingsKey = (
+    Uuid,
+    crate::models::leaderboard::LeaderboardSort,
+    Option<(i64, i64)>,
+);
+
+/// How long a rendered preview image is reused. Long enough to absorb the
+/// burst of fetches when a link is shared, short enough that a leaderboard
+/// podium catches up with its standings.
+const PREVIEW_IMAGE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Preview images kept at once (each is a few tens of KB)
+const PREVIEW_IMAGE_CACHE_CAPACITY: usize = 256;
+
+/// Leaderboards whose status is kept at once (there are a handful)
+const LEADERBOARD_STATUS_CACHE_CAPACITY: usize = 64;
+
+/// Standings slices kept at once. Bots mostly poll the first page or the
+/// full list of a few leaderboards; deep pages age out.
+const STANDINGS_CACHE_CAPACITY: usize = 256;
+
+/// How long a downloaded frame archive is kept, and how many are kept at
+/// once. Long enough for a viewer to page through a replay.
+const FRAME_ARCHIVE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(120);
+const FRAME_ARCHIVE_CACHE_CAPACITY: usize = 16;
+
 impl AppState {
     /// Turn resolved [`AppConfig`] into live resources (pools, clients).
     /// All environment reading already happened in [`AppConfig::from_env`].
@@ -70,6 +136,19 @@ impl AppState {
 
         let pool = setup_db_pool(&config.database_url, config.pg_max_connections).await?;
 
+        // Optional: read replica. No migrations here; it follows the primary.
+        let read_db = match &config.database_replica_url {
+            Some(url) => {
+                tracing::info!("Routing heavy reads to the read replica");
+                PgPoolOptions::new()
+                    .max_connections(config.pg_max_connections)
+                    .connect(url)
+                    .await
+                    .wrap_err("Failed to connect to read replica")?
+            }
+            None => pool.clone(),
+        };
+
         let cookie_key = cja::server::cookies::CookieKey::from_env_or_generate()?;
 
         if config.github.is_some() {
@@ -95,9 +174,17 @@ impl AppState {
             }
         };
 
-        if config.gcs_bucket.is_some() {
-            tracing::info!("GCS bucket configured for game backup");
-        }
+        let gcs = match &config.gcs_bucket {
+            Some(_) => {
+                let gcs_config = ClientConfig::default()
+                    .with_auth()
+                    .await
+                    .wrap_err("Failed to configure GCS client")?;
+                tracing::info!("GCS bucket configured for game backup and frame archives");
+                Some(GcsClient::new(gcs_config))
+            }
+            None => None,
+        };
 
         // HTTP client for calling snake APIs (connection pooling, timeout slightly longer than game timeout)
         let http_client = reqwest::Client::builder()
@@ -141,47 +228,176 @@ impl AppState {
             tracing::info!("DISCORD_WEBHOOK_URL not set, Discord notifications disabled");
         }
 
-        let mut scoring_registry = crate::scoring::ScoringRegistry::new();
-        scoring_registry.register(Box::new(crate::scoring::weng_lin::WengLinScoring));
-        scoring_registry.register(Box::new(crate::scoring::win_rate::WinRateScoring));
-        scoring_registry.register(Box::new(crate::scoring::food_eaten::FoodEatenScoring));
+        let scoring_registry = crate::scoring::ScoringRegistry::standard();
 
         let home_feed_cache = Arc::new(crate::cache::TtlCell::new(std::time::Duration::from_secs(
             config.home_feed_cache_secs,
         )));
 
+        let leaderboard_cache_ttl = std::time::Duration::from_secs(config.leaderboard_cache_secs);
+
         Ok(Self {
             config: Arc::new(config),
             db: pool,
+            read_db,
             cookie_key,
             engine_db,
+            gcs,
             game_channels: GameChannels::new(),
             http_client,
             mailer,
             discord,
             scoring: std::sync::Arc::new(scoring_registry),
             home_feed_cache,
+            leaderboards_cache: Arc::new(crate::cache::TtlCell::new(leaderboard_cache_ttl)),
+            leaderboard_status_cache: Arc::new(crate::cache::TtlMap::new(
+                leaderboard_cache_ttl,
+                LEADERBOARD_STATUS_CACHE_CAPACITY,
+            )),
+            standings_cache: Arc::new(crate::cache::TtlMap::new(
+                leaderboard_cache_ttl,
+                STANDINGS_CACHE_CAPACITY,
+            )),
+            maintenance_cache: Arc::new(crate::cache::TtlCell::new(MAINTENANCE_CACHE_TTL)),
+            settings_cache: Arc::new(crate::cache::TtlCell::new(SETTINGS_CACHE_TTL)),
+            frame_archive_cache: Arc::new(crate::cache::TtlMap::new(
+                FRAME_ARCHIVE_CACHE_TTL,
+                FRAME_ARCHIVE_CACHE_CAPACITY,
+            )),
+            preview_image_cache: Arc::new(crate::cache::TtlMap::new(
+                PREVIEW_IMAGE_CACHE_TTL,
+                PREVIEW_IMAGE_CACHE_CAPACITY,
+            )),
+            shutdown: crate::shutdown::Shutdown::default(),
         })
     }
+
+    /// The current maintenance flag. A failed read is logged and treated as
+    /// "not in maintenance" so a database blip can't take the site down.
+    pub async fn maintenance(&self) -> Arc<crate::models::maintenance::MaintenanceState> {
+        if let Some(state) = self.maintenance_cache.get() {
+            return state;
+        }
+        match crate::models::maintenance::get(&self.db).await {
+            Ok(state) => self.maintenance_cache.put(state),
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to read maintenance mode");
+                Arc::new(Default::default())
+            }
+        }
+    }
+
+    /// Every leaderboard, including inactive ones, from the short TTL cache
+    pub async fn leaderboards(
+        &self,
+    ) -> cja::Result<Arc<Vec<crate::models::leaderboard::Leaderboard>>> {
+        if let Some(leaderboards) = self.leaderboards_cache.get() {
+            return Ok(leaderboards);
+        }
+        let leaderboards = crate::models::leaderboard::get_all_leaderboards(&self.read_db).await?;
+        Ok(self.leaderboards_cache.put(leaderboards))
+    }
+
+    /// A leaderboard's status counts, from the short TTL cache
+    pub async fn leaderboard_status(
+        &self,
+        leaderboard_id: Uuid,
+    ) -> cja::Result<Arc<crate::models::leaderboard::LeaderboardStatus>> {
+        if let Some(status) = self.leaderboard_status_cache.get(&leaderboard_id) {
+            return Ok(status);
+        }
+        let status =
+            crate::models::leaderboard::get_leaderboard_status(&self.read_db, leaderboard_id)
+                .await?;
+        Ok(self.leaderboard_status_cache.put(leaderboard_id, status))
+    }
+
+    /// A leaderboard's ranked entries, from the short TTL cache: one page
+    /// when `page` is `(page, per_page)`, all of them when it's `None`
+    pub async fn standings(
+        &self,
+        leaderboard_id: Uuid,
+        sort: crate::models::leaderboard::LeaderboardSort,
+        page: Option<(i64, i64)>,
+    ) -> cja::Result<Arc<Vec<crate::models::leaderboard::RankedEntry>>> {
+        let key = (leaderboard_id, sort, page);
+        if let Some(entries) = self.standings_cache.get(&key) {
+            return Ok(entries);
+        }
+        let entries = match page {
+            Some((page, per_page)) => {
+                crate::models::leaderboard::get_ranked_entries_paginated(
+                    &self.read_db,
+                    leaderboard_id,
+                    page,
+                    per_page,
+                    sort,
+                )
+                .await?
+            }
+            None => {
+                crate::models::leaderboard::get_ranked_entries(&self.read_db, leaderboard_id, sort)
+                    .await?
+            }
+        };
+        Ok(self.standings_cache.put(key, entries))
+    }
+
+    /// Drop this process's cached standings and status for a leaderboard
+    /// after its ratings or entries change
+    pub fn invalidate_leaderboard(&self, leaderboard_id: Uuid) {
+        self.leaderboard_status_cache.remove(&leaderboard_id);
+        self.standings_cache
+            .remove_where(|(id, _, _)| *id == leaderboard_id);
+    }
+
+    /// The current runtime settings. A failed read is logged and falls back
+    /// to the defaults.
+    pub async fn settings(&self) -> Arc<crate::models::setting::RuntimeSettings> {
+        if let Some(settings) = self.settings_cache.get() {
+            return settings;
+        }
+        match crate::models::setting::get_all(&self.db).await {
+            Ok(settings) => self.settings_cache.put(settings),
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to read runtime settings");
+                Arc::new(Default::default())
+            }
+        }
+    }
 }
 
-#[cfg(test)]
+// The end-to-end tests in `tests/` build on it too, through `crate::testing`
+#[cfg(any(test, feature = "test-support"))]
 impl AppState {
     /// Minimal AppState for DB-backed tests: a real pool, inert everything
     /// else (no OAuth, no engine DB, an empty scoring registry).
     pub fn test_from_pool(db: sqlx::PgPool) -> Self {
         Self {
             config: Arc::new(AppConfig::test_default()),
+            read_db: db.clone(),
             db,
             cookie_key: cja::server::cookies::CookieKey::from_env_or_generate()
                 .expect("failed to generate a test cookie key"),
             engine_db: None,
+            gcs: None,
             game_channels: GameChannels::new(),
             http_client: reqwest::Client::new(),
             mailer: crate::email::Mailer::disabled(),
             discord: crate::discord::DiscordNotifier::disabled(),
             scoring: std::sync::Arc::new(crate::scoring::ScoringRegistry::new()),
             home_feed_cache: Arc::new(crate::cache::TtlCell::new(std::time::Duration::ZERO)),
+            leaderboards_cache: Arc::new(crate::cache::TtlCell::new(std::time::Duration::ZERO)),
+            leaderboard_status_cache: Arc::new(crate::cache::TtlMap::new(
+                std::time::Duration::ZERO,
+                0,
+            )),
+            standings_cache: Arc::new(crate::cache::TtlMap::new(std::time::Duration::ZERO, 0)),
+            maintenance_cache: Arc::new(crate::cache::TtlCell::new(std::time::Duration::ZERO)),
+            settings_cache: Arc::new(crate::cache::TtlCell::new(std::time::Duration::ZERO)),
+            frame_archive_cache: Arc::new(crate::cache::TtlMap::new(std::time::Duration::ZERO, 0)),
+            preview_image_cache: Arc::new(crate::cache::TtlMap::new(std::time::Duration::ZERO, 0)),
+            shutdown: crate::shutdown::Shutdown::default(),
         }
     }
 }
@@ -0,0 +1,190 @@
+//! Watchdog for games stuck in `running`.
+//!
+//! A game's runner job can die without the game ever finishing: a worker
+//! killed mid-game, or a job that exhausts its retries and is deleted by the
+//! job system. The game then sits in `running` forever (the stress test
+//! reports these as "Stuck (running)"). Every few minutes this watchdog finds
+//! games with no new turn for
+//! [`crate::config::AppConfig::stuck_game_timeout_minutes`] and re-enqueues
+//! their [`GameRunnerJob`]; `run_game` is re-entrant and replays from turn 0.
+//! A game that is still stuck after
+//! [`crate::config::AppConfig::stuck_game_max_requeues`] re-enqueues is
+//! ended instead, with no placements and no post-completion jobs, the same as
+//! an admin cancel.
+//!
+//! Every action emits a `stuck_game_alert` event so log-based alerting can
+//! page on it.
+
+use color_eyre::eyre::Context as _;
+
+use crate::jobs::GameRunnerJob;
+use crate::models::game::{self, GameStatus, StuckGame};
+use crate::models::job;
+use crate::state::AppState;
+
+/// What the watchdog did about one stuck game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// A worker still holds the game's runner; the job worker's visibility
+    /// timeout reclaims it if the worker is gone
+    SkippedLocked,
+    Requeued,
+    /// Out of re-enqueues: the game was marked finished without placements
+    Ended,
+}
+
+impl WatchdogAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WatchdogAction::SkippedLocked => "skipped_locked",
+            WatchdogAction::Requeued => "requeued",
+            WatchdogAction::Ended => "ended",
+        }
+    }
+}
+
+/// Run one watchdog pass. Called from the cron-scheduled
+/// [`crate::jobs::StuckGameWatchdogJob`].
+pub async fn run_watchdog(app_state: &AppState) -> cja::Result<()> {
+    let timeout = chrono::Duration::minutes(app_state.config.stuck_game_timeout_minutes);
+    let stuck = game::find_stuck_running_games(&app_state.db, chrono::Utc::now() - timeout).await?;
+
+    for stuck_game in &stuck {
+        match handle_stuck_game(app_state, stuck_game).await {
+            Ok(action) => {
+                tracing::warn!(
+                    event_type = "stuck_game_alert",
+                    game_id = %stuck_game.game_id,
+                    action = action.as_str(),
+                    last_activity = %stuck_game.last_activity,
+                    watchdog_requeues = stuck_game.watchdog_requeues,
+                    "game stuck in running"
+                );
+            }
+            Err(e) => {
+                // One game's failure shouldn't starve the rest; the next
+                // pass retries it.
+                tracing::error!(
+                    game_id = %stuck_game.game_id,
+                    error = %e,
+                    "Stuck-game watchdog failed to handle game"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_stuck_game(app_state: &AppState, stuck: &StuckGame) -> cja::Result<WatchdogAction> {
+    let game_id = stuck.game_id;
+    let runner_jobs = job::list_runner_jobs_for_game(&app_state.db, game_id).await?;
+    if runner_jobs.iter().any(|j| j.locked_at.is_some()) {
+        return Ok(WatchdogAction::SkippedLocked);
+    }
+
+    // Queued runners are dropped either way: a fresh one replaces them, or
+    // the game is over and they'd only replay it
+    job::delete_queued_runner_jobs_for_game(&app_state.db, game_id).await?;
+
+    if stuck.watchdog_requeues >= app_state.config.stuck_game_max_requeues {
+        game::update_game_status(&app_state.db, game_id, GameStatus::Finished).await?;
+        app_state.game_channels.cleanup(game_id).await;
+        return Ok(WatchdogAction::Ended);
+    }
+
+    game::record_watchdog_requeue(&app_state.db, game_id).await?;
+    cja::jobs::Job::enqueue(
+        GameRunnerJob { game_id },
+        app_state.clone(),
+        format!("Stuck-game watchdog for game {game_id}"),
+        None,
+    )
+    .await
+    .wrap_err("Failed to re-enqueue stuck game runner")?;
+
+    Ok(WatchdogAction::Requeued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    /// A `running` game whose last update was an hour ago. Inserted rather
+    /// than updated so the `updated_at` trigger doesn't stamp NOW().
+    async fn insert_running_game(pool: &PgPool, watchdog_requeues: i32) -> cja::Result<Uuid> {
+        let game_id = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status, watchdog_requeues, updated_at)
+             VALUES ('11x11', 'Standard', 'running', $1, NOW() - INTERVAL '1 hour')
+             RETURNING game_id",
+        )
+        .bind(watchdog_requeues)
+        .fetch_one(pool)
+        .await?;
+        Ok(game_id)
+    }
+
+    async fn runner_job_count(pool: &PgPool, game_id: Uuid) -> cja::Result<usize> {
+        Ok(job::list_runner_jobs_for_game(pool, game_id).await?.len())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn watchdog_requeues_then_ends_stuck_games(pool: PgPool) -> cja::Result<()> {
+        let app_state = AppState::test_from_pool(pool.clone());
+        let fresh = insert_running_game(&pool, 0).await?;
+        let exhausted = insert_running_game(&pool, 1).await?;
+        let healthy: Uuid = sqlx::query_scalar(
+            "INSERT INTO games (board_size, game_type, status) VALUES ('11x11', 'Standard', 'running')
+             RETURNING game_id",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        run_watchdog(&app_state).await?;
+
+        let fresh_game = game::get_game_by_id(&pool, fresh).await?.unwrap();
+        assert_eq!(fresh_game.status, GameStatus::Running);
+        assert_eq!(runner_job_count(&pool, fresh).await?, 1);
+
+        let exhausted_game = game::get_game_by_id(&pool, exhausted).await?.unwrap();
+        assert_eq!(exhausted_game.status, GameStatus::Finished);
+        assert_eq!(runner_job_count(&pool, exhausted).await?, 0);
+
+        assert_eq!(runner_job_count(&pool, healthy).await?, 0);
+
+        // The re-enqueue restarted the timeout, so an immediate second pass
+        // leaves the game alone instead of enqueueing a duplicate runner.
+        run_watchdog(&app_state).await?;
+        assert_eq!(runner_job_count(&pool, fresh).await?, 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn watchdog_leaves_games_a_worker_holds(pool: PgPool) -> cja::Result<()> {
+        let app_state = AppState::test_from_pool(pool.clone());
+        let game_id = insert_running_game(&pool, 5).await?;
+        sqlx::query(
+            "INSERT INTO jobs (job_id, name, payload, priority, run_at, context, locked_at, locked_by)
+             VALUES ($1, 'GameRunnerJob', jsonb_build_object('game_id', $2::text), 0, NOW(),
+                     'test', NOW(), 'worker')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(game_id)
+        .execute(&pool)
+        .await?;
+
+        let stuck = game::find_stuck_running_games(&pool, chrono::Utc::now()).await?;
+        let stuck = stuck.iter().find(|g| g.game_id == game_id).unwrap();
+        assert_eq!(
+            handle_stuck_game(&app_state, stuck).await?,
+            WatchdogAction::SkippedLocked
+        );
+        let game = game::get_game_by_id(&pool, game_id).await?.unwrap();
+        assert_eq!(game.status, GameStatus::Running);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,17 @@
-//! Single-elimination bracket generation (BS-019).
+//! Bracket generation (BS-019) for every tournament format.
 //!
-//! `generate_bracket` is pure and fully testable: it produces a
-//! [`BracketPlan`] describing every match in every round, seeded so that
-//! high seeds meet low seeds first and the top seeds can only meet in late
-//! rounds. `persist_bracket` writes a plan to the database for a tournament,
-//! resolving byes immediately (top seeds advance without playing).
+//! The generators are pure and fully testable: each produces a
+//! [`BracketPlan`] describing every match in every round. Elimination
+//! brackets are seeded so that high seeds meet low seeds first and the top
+//! seeds can only meet in late rounds; round robin pairs everyone with
+//! everyone. `persist_bracket` writes a plan to the database for a
+//! tournament, resolving byes immediately (top seeds advance without
+//! playing).
+//!
+//! Rounds are global across brackets: a double-elimination losers match
+//! plays in the round after the latest match feeding it, so the round
+//! orchestration in `tournament_match` runs both brackets side by side
+//! without knowing which is which.
 
 use std::collections::HashMap;
 
@@ -13,40 +20,92 @@ use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::models::tournament::{
-    CreateMatchParticipant, CreateTournamentMatch, MatchStatus, ParticipantType,
-    TournamentRegistration, create_match, create_match_participant,
+    CreateMatchParticipant, CreateTournamentMatch, MatchBracket, MatchStatus, ParticipantType,
+    TournamentFormat, TournamentRegistration, create_match, create_match_participant,
+    set_match_status,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BracketPlan {
     /// Number of rounds; the final is round `total_rounds`.
     pub total_rounds: i32,
-    /// Bracket size: participant count rounded up to a power of two.
+    /// Bracket size: participant count rounded up to a power of two (to an
+    /// even number for round robin).
     pub bracket_size: i32,
     pub matches: Vec<PlannedMatch>,
 }
 
+/// A planned match, by round and position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MatchRef {
+    pub round: i32,
+    pub position: i32,
+}
+
+/// A slot filled by the result of an earlier match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feeder {
+    pub source: MatchRef,
+    /// `Winner` or `Loser` of `source`.
+    pub participant_type: ParticipantType,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlannedMatch {
     /// 1-indexed round number.
     pub round: i32,
     /// 0-indexed position within the round.
     pub position: i32,
-    /// Position of the match in the next round the winner advances to.
-    /// `None` only for the final.
-    pub next_position: Option<i32>,
+    pub bracket: MatchBracket,
+    /// The match the winner advances to. `None` for the final and for every
+    /// round robin match.
+    pub next: Option<MatchRef>,
+    /// Double elimination: the match the loser drops to.
+    pub loser_next: Option<MatchRef>,
     pub visual_column: i32,
     pub visual_row: i32,
-    /// Round-1 matches: the two seed slots. `None` in a slot is a bye.
-    /// Later rounds have `[None, None]` — participants come from feeder
-    /// matches instead.
+    /// Seeded slots: round 1 of a bracket, and every round robin match.
+    /// `None` in a round-1 slot is a bye.
     pub seed_slots: [Option<i32>; 2],
+    /// Slots filled from earlier matches. A slot whose feeder can never
+    /// produce anyone (the loser of a bye, say) is `None`.
+    pub feeders: [Option<Feeder>; 2],
 }
 
 impl PlannedMatch {
     pub fn is_bye(&self) -> bool {
         self.round == 1 && self.seed_slots.iter().filter(|s| s.is_some()).count() == 1
     }
+
+    /// Slots that will ever hold a participant. One is a walkover (the lone
+    /// participant advances without playing); zero is a match that never
+    /// happens.
+    pub fn live_slots(&self) -> usize {
+        (0..2)
+            .filter(|&i| self.seed_slots[i].is_some() || self.feeders[i].is_some())
+            .count()
+    }
+
+    fn at(&self) -> MatchRef {
+        MatchRef {
+            round: self.round,
+            position: self.position,
+        }
+    }
+}
+
+fn winner_of(source: MatchRef) -> Feeder {
+    Feeder {
+        source,
+        participant_type: ParticipantType::Winner,
+    }
+}
+
+fn loser_of(source: MatchRef) -> Feeder {
+    Feeder {
+        source,
+        participant_type: ParticipantType::Loser,
+    }
 }
 
 /// Seed placement order for a bracket of `size` (power of two).
@@ -64,6 +123,18 @@ fn seed_order(size: i32) -> Vec<i32> {
     order
 }
 
+/// Generate the plan for `format` with `participant_count` seeds.
+pub fn generate_plan(
+    format: TournamentFormat,
+    participant_count: usize,
+) -> cja::Result<BracketPlan> {
+    match format {
+        TournamentFormat::SingleElimination => generate_bracket(participant_count),
+        TournamentFormat::DoubleElimination => generate_double_elimination(participant_count),
+        TournamentFormat::RoundRobin => generate_round_robin(participant_count),
+    }
+}
+
 /// Generate a full single-elimination bracket for `participant_count` seeds.
 ///
 /// Participants are identified by seed number (1-indexed). Byes are the
@@ -85,27 +156,46 @@ pub fn generate_bracket(participant_count: usize) -> cja::Result<BracketPlan> {
     for round in 1..=total_rounds {
         let matches_in_round = bracket_size >> round;
         for position in 0..matches_in_round {
-            let seed_slots = if round == 1 {
+            let (seed_slots, feeders) = if round == 1 {
                 let a = order[(position * 2) as usize];
                 let b = order[(position * 2 + 1) as usize];
                 // Seeds beyond the participant count don't exist: byes.
-                [
-                    (a <= participant_count).then_some(a),
-                    (b <= participant_count).then_some(b),
-                ]
+                (
+                    [
+                        (a <= participant_count).then_some(a),
+                        (b <= participant_count).then_some(b),
+                    ],
+                    [None, None],
+                )
             } else {
-                [None, None]
+                // The feeder with the lower position feeds slot 1.
+                let feeder = |p: i32| {
+                    Some(winner_of(MatchRef {
+                        round: round - 1,
+                        position: p,
+                    }))
+                };
+                (
+                    [None, None],
+                    [feeder(position * 2), feeder(position * 2 + 1)],
+                )
             };
 
             matches.push(PlannedMatch {
                 round,
                 position,
-                next_position: (round < total_rounds).then_some(position / 2),
+                bracket: MatchBracket::Winners,
+                next: (round < total_rounds).then_some(MatchRef {
+                    round: round + 1,
+                    position: position / 2,
+                }),
+                loser_next: None,
                 visual_column: round - 1,
                 // Center each match between its two feeders: rows spread by
                 // 2^round with an offset of 2^(round-1) - 1.
                 visual_row: position * (1 << round) + (1 << (round - 1)) - 1,
                 seed_slots,
+                feeders,
             });
         }
     }
@@ -117,33 +207,243 @@ pub fn generate_bracket(participant_count: usize) -> cja::Result<BracketPlan> {
     })
 }
 
-/// Persist a bracket plan for a tournament.
+/// Generate a double-elimination bracket: the single-elimination bracket as
+/// the winners bracket, a losers bracket that every first loss drops into,
+/// and a grand final between the two bracket winners. There is no bracket
+/// reset: the grand final decides the tournament even if the winners-bracket
+/// champion loses it.
+///
+/// With `k` winners rounds the losers bracket has `2(k - 1)` rounds. Losers
+/// round 1 pairs the round-1 losers; each even losers round pits the
+/// survivors against the losers dropping out of the next winners round
+/// (in reverse order, so snakes don't immediately meet whoever they just
+/// played); each odd round after that halves the field.
+///
+/// Byes ripple into the losers bracket: a bye has no loser, so some losers
+/// matches end up with one possible participant (a walkover) or none (never
+/// played, persisted as canceled).
+pub fn generate_double_elimination(participant_count: usize) -> cja::Result<BracketPlan> {
+    if participant_count < 3 {
+        return Err(color_eyre::eyre::eyre!(
+            "Double elimination needs at least 3 participants, got {participant_count}"
+        ));
+    }
+    let winners = generate_bracket(participant_count)?;
+    let bracket_size = winners.bracket_size;
+    let winners_rounds = winners.total_rounds;
+    let mut matches = winners.matches;
+
+    // Positions are per global round: losers matches follow the winners
+    // matches that share their round.
+    let mut round_sizes: HashMap<i32, i32> = HashMap::new();
+    for m in &matches {
+        *round_sizes.entry(m.round).or_default() += 1;
+    }
+    let mut place = |round: i32| {
+        let count = round_sizes.entry(round).or_default();
+        let at = MatchRef {
+            round,
+            position: *count,
+        };
+        *count += 1;
+        at
+    };
+    let winners_match = |round: i32, position: i32| MatchRef { round, position };
+
+    let mut previous: Vec<MatchRef> = Vec::new();
+    for losers_round in 1..=2 * (winners_rounds - 1) {
+        // Losers rounds 2j - 1 and 2j have the same number of matches.
+        let j = (losers_round + 1) / 2;
+        let count = bracket_size >> (j + 1);
+        let spacing = 1 << j;
+        let mut current = Vec::with_capacity(count as usize);
+        for p in 0..count {
+            let feeders = if losers_round == 1 {
+                [
+                    loser_of(winners_match(1, p * 2)),
+                    loser_of(winners_match(1, p * 2 + 1)),
+                ]
+            } else if losers_round % 2 == 0 {
+                [
+                    winner_of(previous[p as usize]),
+                    loser_of(winners_match(j + 1, count - 1 - p)),
+                ]
+            } else {
+                [
+                    winner_of(previous[(p * 2) as usize]),
+                    winner_of(previous[(p * 2 + 1) as usize]),
+                ]
+            };
+            let at = place(losers_round + 1);
+            matches.push(PlannedMatch {
+                round: at.round,
+                position: at.position,
+                bracket: MatchBracket::Losers,
+                next: None,
+                loser_next: None,
+                visual_column: losers_round - 1,
+                visual_row: p * spacing + spacing / 2 - 1,
+                seed_slots: [None, None],
+                feeders: feeders.map(Some),
+            });
+            current.push(at);
+        }
+        previous = current;
+    }
+
+    let grand_final = place(winners_rounds * 2);
+    matches.push(PlannedMatch {
+        round: grand_final.round,
+        position: grand_final.position,
+        bracket: MatchBracket::GrandFinal,
+        next: None,
+        loser_next: None,
+        visual_column: winners_rounds,
+        visual_row: bracket_size / 2 - 1,
+        seed_slots: [None, None],
+        feeders: [
+            Some(winner_of(winners_match(winners_rounds, 0))),
+            Some(winner_of(previous[0])),
+        ],
+    });
+
+    matches.sort_by_key(|m| (m.round, m.position));
+    link_live_feeders(&mut matches);
+
+    Ok(BracketPlan {
+        total_rounds: grand_final.round,
+        bracket_size,
+        matches,
+    })
+}
+
+/// Drop feeders that can never produce anyone, then point every match at the
+/// matches its winner and loser go to. `matches` must be in round order.
+fn link_live_feeders(matches: &mut [PlannedMatch]) {
+    // (winner can exist, loser can exist) per match, filled in round order:
+    // a walkover has a winner but no loser, a dead match has neither.
+    let mut outcomes: HashMap<MatchRef, (bool, bool)> = HashMap::new();
+    for m in matches.iter_mut() {
+        for feeder in &mut m.feeders {
+            let live = feeder.is_some_and(|f| {
+                let (winner, loser) = outcomes.get(&f.source).copied().unwrap_or_default();
+                if f.participant_type == ParticipantType::Loser {
+                    loser
+                } else {
+                    winner
+                }
+            });
+            if !live {
+                *feeder = None;
+            }
+        }
+        let live_slots = m.live_slots();
+        outcomes.insert(m.at(), (live_slots >= 1, live_slots == 2));
+    }
+
+    let index: HashMap<MatchRef, usize> = matches
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.at(), i))
+        .collect();
+    for m in matches.iter_mut() {
+        m.next = None;
+        m.loser_next = None;
+    }
+    for i in 0..matches.len() {
+        let at = matches[i].at();
+        for feeder in matches[i].feeders.into_iter().flatten() {
+            let source = &mut matches[index[&feeder.source]];
+            if feeder.participant_type == ParticipantType::Loser {
+                source.loser_next = Some(at);
+            } else {
+                source.next = Some(at);
+            }
+        }
+    }
+}
+
+/// Generate a round robin: every participant plays every other once.
+///
+/// Uses the circle method: seed 1 stays put while everyone else rotates one
+/// place per round, so `n - 1` rounds (`n` with an odd count, where whoever
+/// lands opposite the empty slot sits the round out) cover every pairing.
+/// The higher seed takes slot 1.
+pub fn generate_round_robin(participant_count: usize) -> cja::Result<BracketPlan> {
+    if participant_count < 2 {
+        return Err(color_eyre::eyre::eyre!(
+            "A round robin needs at least 2 participants, got {participant_count}"
+        ));
+    }
+    let participant_count =
+        i32::try_from(participant_count).wrap_err("Participant count does not fit in an i32")?;
+    let mut ring: Vec<Option<i32>> = (1..=participant_count).map(Some).collect();
+    if participant_count % 2 == 1 {
+        ring.push(None);
+    }
+    let slots = ring.len();
+
+    let mut matches = Vec::new();
+    for round in 1..slots as i32 {
+        let mut position = 0;
+        for i in 0..slots / 2 {
+            let (Some(a), Some(b)) = (ring[i], ring[slots - 1 - i]) else {
+                continue;
+            };
+            matches.push(PlannedMatch {
+                round,
+                position,
+                bracket: MatchBracket::Pool,
+                next: None,
+                loser_next: None,
+                visual_column: round - 1,
+                visual_row: position * 2,
+                seed_slots: [Some(a.min(b)), Some(a.max(b))],
+                feeders: [None, None],
+            });
+            position += 1;
+        }
+        ring[1..].rotate_right(1);
+    }
+
+    Ok(BracketPlan {
+        total_rounds: slots as i32 - 1,
+        bracket_size: slots as i32,
+        matches,
+    })
+}
+
+/// Persist the plan for `format` for a tournament.
 ///
-/// Creates every match with `next_match_id` links and visual coordinates,
-/// round-1 participants from `registrations` (matched by seed), and empty
-/// winner-fed slots for later rounds. Round-1 byes are resolved immediately:
-/// the lone participant is marked the winner and advanced into round 2.
+/// Creates every match with its `next_match_id`/`loser_next_match_id` links
+/// and visual coordinates, seeded participants from `registrations`
+/// (matched by seed), and empty fed slots for everything else. Round-1 byes
+/// are resolved immediately: the lone participant is marked the winner and
+/// advanced. Matches no one can ever reach are created already canceled.
 ///
 /// Slot assignment is deterministic:
-/// - Round 1: the first seed of the pair (the higher seed, e.g. 1 in 1v8)
-///   takes slot 1, the second takes slot 2. A bye leaves slot 2 empty.
-/// - Later rounds: the feeder match with the lower `position` feeds slot 1,
-///   the higher `position` feeds slot 2.
+/// - Seeded matches: the first seed of the pair (the higher seed, e.g. 1 in
+///   1v8) takes slot 1, the second takes slot 2. A bye leaves slot 2 empty.
+/// - Winners bracket: the feeder match with the lower `position` feeds slot
+///   1, the higher `position` slot 2.
+/// - Losers bracket: see [`generate_double_elimination`]; the grand final has
+///   the winners-bracket champion in slot 1.
 ///
 /// Note that non-round-1 matches may be fully populated immediately: when two
 /// round-1 byes feed the same round-2 match (e.g. 5 participants), that
 /// round-2 match ends up with both participants filled while its status stays
-/// `scheduled`. This is intentional — matches are started by the owner-
-/// triggered round-execution scan, not by an event fired when a match fills.
+/// `scheduled`. This is intentional — matches are started by the round
+/// execution scan, not by an event fired when a match fills.
 ///
 /// `registrations` must contain exactly the seeds `1..=len` (the caller
 /// renumbers seeds on unregister, so this holds for any started tournament).
 pub async fn persist_bracket(
     tx: &mut Transaction<'_, Postgres>,
     tournament_id: Uuid,
+    format: TournamentFormat,
     registrations: &[TournamentRegistration],
 ) -> cja::Result<()> {
-    let plan = generate_bracket(registrations.len())?;
+    let plan = generate_plan(format, registrations.len())?;
 
     let by_seed: HashMap<i32, &TournamentRegistration> =
         registrations.iter().map(|r| (r.seed, r)).collect();
@@ -156,33 +456,44 @@ pub async fn persist_bracket(
         }
     }
 
-    // Create matches from the final backward so next_match_id targets exist.
-    let mut match_ids: HashMap<(i32, i32), Uuid> = HashMap::new();
-    let lookup_match_id = |match_ids: &HashMap<(i32, i32), Uuid>, round: i32, position: i32| {
-        match_ids.get(&(round, position)).copied().ok_or_else(|| {
+    // Create matches from the final backward so link targets exist.
+    let mut match_ids: HashMap<MatchRef, Uuid> = HashMap::new();
+    let lookup_match_id = |match_ids: &HashMap<MatchRef, Uuid>, at: MatchRef| {
+        match_ids.get(&at).copied().ok_or_else(|| {
             color_eyre::eyre::eyre!(
-                "Bracket plan is inconsistent: no match at round {round} position {position}"
+                "Bracket plan is inconsistent: no match at round {} position {}",
+                at.round,
+                at.position
             )
         })
     };
     for planned in plan.matches.iter().rev() {
-        let next_match_id = match planned.next_position {
-            Some(next_pos) => Some(lookup_match_id(&match_ids, planned.round + 1, next_pos)?),
-            None => None,
-        };
+        let next_match_id = planned
+            .next
+            .map(|at| lookup_match_id(&match_ids, at))
+            .transpose()?;
+        let loser_next_match_id = planned
+            .loser_next
+            .map(|at| lookup_match_id(&match_ids, at))
+            .transpose()?;
         let created = create_match(
             &mut **tx,
             CreateTournamentMatch {
                 tournament_id,
                 round: planned.round,
                 position: planned.position,
+                bracket: planned.bracket,
                 next_match_id,
+                loser_next_match_id,
                 visual_column: planned.visual_column,
                 visual_row: planned.visual_row,
             },
         )
         .await?;
-        match_ids.insert((planned.round, planned.position), created.match_id);
+        if planned.live_slots() == 0 {
+            set_match_status(&mut **tx, created.match_id, MatchStatus::Canceled).await?;
+        }
+        match_ids.insert(planned.at(), created.match_id);
     }
 
     let snake_for_seed = |seed: i32| {
@@ -192,54 +503,39 @@ pub async fn persist_bracket(
             .ok_or_else(|| color_eyre::eyre::eyre!("Bracket plan references unknown seed {seed}"))
     };
 
-    // Round-1 seeded participants: the first seed of the pair takes slot 1,
-    // the second takes slot 2 (a bye leaves slot 2 empty).
-    for planned in plan.matches.iter().filter(|m| m.round == 1) {
-        let match_id = lookup_match_id(&match_ids, planned.round, planned.position)?;
-        for (slot_index, seed) in planned.seed_slots.iter().enumerate() {
-            let Some(seed) = seed else { continue };
-            create_match_participant(
-                &mut **tx,
+    // One participant row per live slot: seeded slots hold their snake, fed
+    // slots start empty and are filled as their feeder match finishes.
+    for planned in &plan.matches {
+        let match_id = lookup_match_id(&match_ids, planned.at())?;
+        for slot_index in 0..2 {
+            let participant = if let Some(seed) = planned.seed_slots[slot_index] {
                 CreateMatchParticipant {
                     match_id,
                     slot: slot_index as i16 + 1,
-                    battlesnake_id: Some(snake_for_seed(*seed)?),
+                    battlesnake_id: Some(snake_for_seed(seed)?),
                     source_match_id: None,
                     participant_type: ParticipantType::Seed,
-                    seed_position: Some(*seed),
-                },
-            )
-            .await?;
-        }
-    }
-
-    // Later rounds: one empty winner-fed slot per feeder match. The feeder
-    // with the lower position feeds slot 1, the higher position slot 2.
-    for planned in plan.matches.iter().filter(|m| m.round > 1) {
-        let match_id = lookup_match_id(&match_ids, planned.round, planned.position)?;
-        for (slot_index, feeder_position) in [planned.position * 2, planned.position * 2 + 1]
-            .iter()
-            .enumerate()
-        {
-            let source_match_id = lookup_match_id(&match_ids, planned.round - 1, *feeder_position)?;
-            create_match_participant(
-                &mut **tx,
+                    seed_position: Some(seed),
+                }
+            } else if let Some(feeder) = planned.feeders[slot_index] {
                 CreateMatchParticipant {
                     match_id,
                     slot: slot_index as i16 + 1,
                     battlesnake_id: None,
-                    source_match_id: Some(source_match_id),
-                    participant_type: ParticipantType::Winner,
+                    source_match_id: Some(lookup_match_id(&match_ids, feeder.source)?),
+                    participant_type: feeder.participant_type,
                     seed_position: None,
-                },
-            )
-            .await?;
+                }
+            } else {
+                continue;
+            };
+            create_match_participant(&mut **tx, participant).await?;
         }
     }
 
     // Resolve byes: the lone participant wins and advances immediately.
     for planned in plan.matches.iter().filter(|m| m.is_bye()) {
-        let match_id = lookup_match_id(&match_ids, planned.round, planned.position)?;
+        let match_id = lookup_match_id(&match_ids, planned.at())?;
         let seed = planned.seed_slots.iter().flatten().next().ok_or_else(|| {
             color_eyre::eyre::eyre!(
                 "Bye match at round {} position {} has no seed",
@@ -250,17 +546,127 @@ pub async fn persist_bracket(
         let winner_battlesnake_id = snake_for_seed(*seed)?;
 
         complete_match_with_winner(tx, match_id, winner_battlesnake_id).await?;
+        advance_from_match(tx, match_id, winner_battlesnake_id, None).await?;
+    }
 
-        if let Some(next_pos) = planned.next_position {
-            let next_match_id = lookup_match_id(&match_ids, planned.round + 1, next_pos)?;
-            fill_participant_from_source(tx, next_match_id, match_id, winner_battlesnake_id)
-                .await?;
+    Ok(())
+}
+
+/// Move a completed match's winner, and in double elimination its loser,
+/// into the matches they feed.
+///
+/// A fed match with only one live slot is a walkover: it completes the
+/// moment its participant arrives, and that participant advances in turn.
+/// Retry-safe, like the fills and completions it's made of.
+pub async fn advance_from_match(
+    tx: &mut Transaction<'_, Postgres>,
+    match_id: Uuid,
+    winner_battlesnake_id: Uuid,
+    loser_battlesnake_id: Option<Uuid>,
+) -> cja::Result<()> {
+    let mut pending = vec![(match_id, winner_battlesnake_id, loser_battlesnake_id)];
+    while let Some((match_id, winner, loser)) = pending.pop() {
+        let links = sqlx::query!(
+            "SELECT next_match_id, loser_next_match_id FROM tournament_matches WHERE match_id = $1",
+            match_id,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .wrap_err("Failed to fetch match links")?;
+
+        for (target, snake) in [
+            (links.next_match_id, Some(winner)),
+            (links.loser_next_match_id, loser),
+        ] {
+            let (Some(target), Some(snake)) = (target, snake) else {
+                continue;
+            };
+            fill_participant_from_source(tx, target, match_id, snake).await?;
+
+            let slots = sqlx::query_scalar!(
+                "SELECT battlesnake_id FROM match_participants WHERE match_id = $1",
+                target,
+            )
+            .fetch_all(&mut **tx)
+            .await
+            .wrap_err("Failed to fetch participants of the next match")?;
+            if let [Some(walkover_winner)] = slots.as_slice() {
+                complete_match_with_winner(tx, target, *walkover_winner).await?;
+                pending.push((target, *walkover_winner, None));
+            }
         }
     }
 
     Ok(())
 }
 
+/// A finished or in-flight match, as round robin standings see it.
+#[derive(Debug, Clone)]
+pub struct PlayedMatch {
+    pub participants: Vec<Uuid>,
+    /// `None` until the match completes.
+    pub winner_id: Option<Uuid>,
+    /// Per-game winners, `None` for ties and games still running.
+    pub game_winners: Vec<Option<Uuid>>,
+}
+
+/// One participant's line in the round robin standings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub battlesnake_id: Uuid,
+    pub seed: i32,
+    pub played: i32,
+    pub match_wins: i32,
+    pub game_wins: i32,
+}
+
+/// Round robin standings, best first: most match wins, then most game wins,
+/// then the better seed. `seeds` is every participant with their seed; the
+/// top line of a finished round robin is its champion.
+pub fn round_robin_standings(seeds: &[(Uuid, i32)], matches: &[PlayedMatch]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = seeds
+        .iter()
+        .map(|&(battlesnake_id, seed)| Standing {
+            battlesnake_id,
+            seed,
+            played: 0,
+            match_wins: 0,
+            game_wins: 0,
+        })
+        .collect();
+    let index: HashMap<Uuid, usize> = standings
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.battlesnake_id, i))
+        .collect();
+
+    for m in matches {
+        if let Some(winner) = m.winner_id {
+            for participant in &m.participants {
+                if let Some(&i) = index.get(participant) {
+                    standings[i].played += 1;
+                }
+            }
+            if let Some(&i) = index.get(&winner) {
+                standings[i].match_wins += 1;
+            }
+        }
+        for winner in m.game_winners.iter().flatten() {
+            if let Some(&i) = index.get(winner) {
+                standings[i].game_wins += 1;
+            }
+        }
+    }
+
+    standings.sort_by(|a, b| {
+        b.match_wins
+            .cmp(&a.match_wins)
+            .then(b.game_wins.cmp(&a.game_wins))
+            .then(a.seed.cmp(&b.seed))
+    });
+    standings
+}
+
 /// Mark a match completed with the given winner.
 ///
 /// The winner must be one of the match's participants. Idempotent: completing
@@ -408,7 +814,7 @@ mod tests {
         assert_eq!(plan.matches.len(), 1);
         let only = &plan.matches[0];
         assert_eq!(only.seed_slots, [Some(1), Some(2)]);
-        assert_eq!(only.next_position, None);
+        assert_eq!(only.next, None);
     }
 
     #[test]
@@ -512,7 +918,7 @@ mod tests {
 
             // Only the final has no next match.
             for m in &plan.matches {
-                prop_assert_eq!(m.next_position.is_none(), m.round == plan.total_rounds);
+                prop_assert_eq!(m.next.is_none(), m.round == plan.total_rounds);
             }
 
             // Feeder pairing: every non-round-1 match at position k is fed by
@@ -524,7 +930,7 @@ mod tests {
                 let feeders: Vec<_> = plan
                     .matches
                     .iter()
-                    .filter(|f| f.round == m.round - 1 && f.next_position == Some(m.position))
+                    .filter(|f| f.round == m.round - 1 && f.next == Some(m.at()))
                     .collect();
                 prop_assert_eq!(feeders.len(), 2);
                 prop_assert_eq!(
@@ -604,12 +1010,282 @@ mod tests {
         }
     }
 
+    /// Play a plan out in round order, `upset(i)` deciding whether the i-th
+    /// match played goes to the lower seed. Returns the champion and each
+    /// seed's losses, asserting along the way that every match gets exactly
+    /// the participants its live slots promise and nobody plays twice in a
+    /// round.
+    fn play_out(plan: &BracketPlan, upset: impl Fn(usize) -> bool) -> (i32, HashMap<i32, i32>) {
+        let mut results: HashMap<MatchRef, (i32, Option<i32>)> = HashMap::new();
+        let mut losses: HashMap<i32, i32> = HashMap::new();
+        let mut played = 0;
+        let mut champion = None;
+        let mut matches = plan.matches.clone();
+        matches.sort_by_key(|m| (m.round, m.position));
+        let mut busy = std::collections::HashSet::new();
+
+        for m in &matches {
+            let slots: Vec<i32> = (0..2)
+                .filter_map(|i| {
+                    m.seed_slots[i].or_else(|| {
+                        let f = m.feeders[i]?;
+                        let (winner, loser) = results[&f.source];
+                        if f.participant_type == ParticipantType::Loser {
+                            Some(loser.expect("live loser feeder without a loser"))
+                        } else {
+                            Some(winner)
+                        }
+                    })
+                })
+                .collect();
+            assert_eq!(slots.len(), m.live_slots());
+            for seed in &slots {
+                assert!(
+                    busy.insert((m.round, *seed)),
+                    "seed {seed} plays twice in round {}",
+                    m.round
+                );
+            }
+            let (winner, loser) = match slots.as_slice() {
+                [] => continue,
+                [only] => (*only, None),
+                [a, b] => {
+                    let (favorite, underdog) = ((*a).min(*b), (*a).max(*b));
+                    played += 1;
+                    if upset(played) {
+                        (underdog, Some(favorite))
+                    } else {
+                        (favorite, Some(underdog))
+                    }
+                }
+                _ => unreachable!(),
+            };
+            if let Some(loser) = loser {
+                *losses.entry(loser).or_default() += 1;
+            }
+            if m.next.is_none() && m.loser_next.is_none() && m.round == plan.total_rounds {
+                champion = Some(winner);
+            }
+            results.insert(m.at(), (winner, loser));
+        }
+        (champion.expect("no final was played"), losses)
+    }
+
+    #[test]
+    fn double_elimination_needs_three_participants() {
+        assert!(generate_double_elimination(2).is_err());
+        assert!(generate_double_elimination(3).is_ok());
+    }
+
+    #[test]
+    fn four_participants_double_elimination_layout() {
+        let plan = generate_double_elimination(4).unwrap();
+        // W1 x2, then W2 + L1 in round 2, L2 in round 3, grand final in 4.
+        assert_eq!(plan.total_rounds, 4);
+        let brackets: Vec<(i32, MatchBracket)> =
+            plan.matches.iter().map(|m| (m.round, m.bracket)).collect();
+        assert_eq!(
+            brackets,
+            vec![
+                (1, MatchBracket::Winners),
+                (1, MatchBracket::Winners),
+                (2, MatchBracket::Winners),
+                (2, MatchBracket::Losers),
+                (3, MatchBracket::Losers),
+                (4, MatchBracket::GrandFinal),
+            ]
+        );
+        let at = |round, position| MatchRef { round, position };
+        // Both round-1 losers meet in L1; the W2 loser drops into L2.
+        assert_eq!(plan.matches[0].loser_next, Some(at(2, 1)));
+        assert_eq!(plan.matches[1].loser_next, Some(at(2, 1)));
+        assert_eq!(plan.matches[2].loser_next, Some(at(3, 0)));
+        assert_eq!(plan.matches[2].next, Some(at(4, 0)));
+        assert_eq!(plan.matches[4].next, Some(at(4, 0)));
+    }
+
+    #[test]
+    fn byes_leave_walkovers_and_dead_matches_in_the_losers_bracket() {
+        // Five snakes: seeds 1-3 have byes, so only 4v5 produces a round-1
+        // loser. L1 (1-bye, 4v5) is a walkover and L1 (2-bye, 3-bye) is dead.
+        let plan = generate_double_elimination(5).unwrap();
+        let losers_round_one: Vec<usize> = plan
+            .matches
+            .iter()
+            .filter(|m| m.bracket == MatchBracket::Losers && m.visual_column == 0)
+            .map(PlannedMatch::live_slots)
+            .collect();
+        assert_eq!(losers_round_one, vec![1, 0]);
+        // Nothing links into the dead match.
+        let dead = plan
+            .matches
+            .iter()
+            .find(|m| m.bracket == MatchBracket::Losers && m.live_slots() == 0)
+            .unwrap()
+            .at();
+        assert!(
+            plan.matches
+                .iter()
+                .all(|m| m.next != Some(dead) && m.loser_next != Some(dead))
+        );
+    }
+
+    #[test]
+    fn round_robin_pairs_everyone_once() {
+        let plan = generate_round_robin(4).unwrap();
+        assert_eq!(plan.total_rounds, 3);
+        let pairs: Vec<(i32, [Option<i32>; 2])> = plan
+            .matches
+            .iter()
+            .map(|m| (m.round, m.seed_slots))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (1, [Some(1), Some(4)]),
+                (1, [Some(2), Some(3)]),
+                (2, [Some(1), Some(3)]),
+                (2, [Some(2), Some(4)]),
+                (3, [Some(1), Some(2)]),
+                (3, [Some(3), Some(4)]),
+            ]
+        );
+        assert!(plan.matches.iter().all(|m| m.next.is_none()));
+    }
+
+    #[test]
+    fn standings_rank_match_wins_then_game_wins_then_seed() {
+        let snakes = (0..3).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+        let (a, b, c) = (snakes[0], snakes[1], snakes[2]);
+        let seeds = [(a, 1), (b, 2), (c, 3)];
+        let matches = [
+            // c beats a 2-1, b beats c 2-0, a beats b 2-0: one win each,
+            // so game wins decide it.
+            PlayedMatch {
+                participants: vec![a, c],
+                winner_id: Some(c),
+                game_winners: vec![Some(c), Some(a), Some(c)],
+            },
+            PlayedMatch {
+                participants: vec![b, c],
+                winner_id: Some(b),
+                game_winners: vec![Some(b), Some(b)],
+            },
+            PlayedMatch {
+                participants: vec![a, b],
+                winner_id: Some(a),
+                game_winners: vec![Some(a), None, Some(a)],
+            },
+        ];
+        let standings = round_robin_standings(&seeds, &matches);
+        let order: Vec<(Uuid, i32, i32)> = standings
+            .iter()
+            .map(|s| (s.battlesnake_id, s.match_wins, s.game_wins))
+            .collect();
+        // b and c are level on games too: the better seed goes first.
+        assert_eq!(order, vec![(a, 1, 3), (b, 1, 2), (c, 1, 2)]);
+        assert!(standings.iter().all(|s| s.played == 2));
+
+        // A match still in flight counts its games but not the match.
+        let live = [PlayedMatch {
+            participants: vec![b, c],
+            winner_id: None,
+            game_winners: vec![Some(c)],
+        }];
+        let standings = round_robin_standings(&seeds, &live);
+        assert_eq!(standings[0].battlesnake_id, c);
+        assert_eq!(standings[0].played, 0);
+    }
+
+    proptest! {
+        #[test]
+        fn double_elimination_invariants(
+            n in 3usize..=64,
+            upsets in prop::collection::vec(any::<bool>(), 256),
+        ) {
+            let plan = generate_double_elimination(n).unwrap();
+
+            // Links and feeders agree, and always point forward in time.
+            let by_ref: HashMap<MatchRef, &PlannedMatch> =
+                plan.matches.iter().map(|m| (m.at(), m)).collect();
+            for m in &plan.matches {
+                for f in m.feeders.iter().flatten() {
+                    let source = by_ref[&f.source];
+                    prop_assert!(source.round < m.round);
+                    let link = if f.participant_type == ParticipantType::Loser {
+                        source.loser_next
+                    } else {
+                        source.next
+                    };
+                    prop_assert_eq!(link, Some(m.at()));
+                }
+            }
+
+            // Whatever the results, one champion with at most one loss; the
+            // grand final loser may also have one, everyone else went out on
+            // their second.
+            let (champion, losses) = play_out(&plan, |i| upsets[i % upsets.len()]);
+            prop_assert!(losses.get(&champion).copied().unwrap_or(0) <= 1);
+            let one_loss = (1..=n as i32)
+                .filter(|s| *s != champion && losses.get(s).copied().unwrap_or(0) < 2)
+                .count();
+            prop_assert!(one_loss <= 1);
+            for seed in 1..=n as i32 {
+                prop_assert!(losses.get(&seed).copied().unwrap_or(0) <= 2);
+            }
+        }
+
+        #[test]
+        fn single_elimination_plays_out_to_one_champion(
+            n in 2usize..=64,
+            upsets in prop::collection::vec(any::<bool>(), 128),
+        ) {
+            let plan = generate_bracket(n).unwrap();
+            let (champion, losses) = play_out(&plan, |i| upsets[i % upsets.len()]);
+            prop_assert!(!losses.contains_key(&champion));
+            prop_assert_eq!(losses.len(), n - 1);
+        }
+
+        #[test]
+        fn round_robin_invariants(n in 2usize..=32) {
+            let plan = generate_round_robin(n).unwrap();
+            let n = n as i32;
+            prop_assert_eq!(plan.total_rounds, n + n % 2 - 1);
+
+            let mut pairs = Vec::new();
+            for round in 1..=plan.total_rounds {
+                let mut seen = Vec::new();
+                for m in plan.matches.iter().filter(|m| m.round == round) {
+                    let [Some(a), Some(b)] = m.seed_slots else {
+                        return Err(TestCaseError::fail("round robin match missing a seed"));
+                    };
+                    prop_assert!(a < b);
+                    seen.extend([a, b]);
+                    pairs.push((a, b));
+                }
+                // Everyone plays once a round (bar one bye with an odd count).
+                seen.sort_unstable();
+                seen.dedup();
+                prop_assert_eq!(seen.len() as i32, n - n % 2);
+            }
+            pairs.sort_unstable();
+            let expected: Vec<(i32, i32)> = (1..=n)
+                .flat_map(|a| (a + 1..=n).map(move |b| (a, b)))
+                .collect();
+            prop_assert_eq!(pairs, expected);
+        }
+    }
+
     // --- DB tests for persist_bracket and match progression ---
 
     /// Create a tournament with `n` registered snakes (seeds 1..=n) and
     /// persist its bracket. Returns the tournament id and the battlesnake ids
     /// indexed by seed (`snakes[0]` is seed 1).
-    async fn fixture_persisted_bracket(pool: &PgPool, n: usize) -> cja::Result<(Uuid, Vec<Uuid>)> {
+    async fn fixture_persisted_bracket(
+        pool: &PgPool,
+        format: TournamentFormat,
+        n: usize,
+    ) -> cja::Result<(Uuid, Vec<Uuid>)> {
         let user_id: Uuid = sqlx::query_scalar(
             "INSERT INTO users (external_github_id, github_login, github_access_token)
              VALUES ($1, $2, $3) RETURNING user_id",
@@ -631,6 +1307,8 @@ mod tests {
                 registration_status: RegistrationStatus::Open,
                 visibility: TournamentVisibility::Public,
                 match_style: MatchStyle::SingleGame,
+                format,
+                auto_advance: false,
                 max_snakes_per_user: n as i32,
                 required_participants: n as i32,
             },
@@ -661,7 +1339,7 @@ mod tests {
         }
 
         let mut tx = pool.begin().await?;
-        persist_bracket(&mut tx, tournament.tournament_id, &registrations).await?;
+        persist_bracket(&mut tx, tournament.tournament_id, format, &registrations).await?;
         tx.commit().await?;
 
         let snakes = registrations.iter().map(|r| r.battlesnake_id).collect();
@@ -690,7 +1368,8 @@ mod tests {
     async fn five_participants_double_bye_fills_round_two_but_stays_scheduled(
         pool: PgPool,
     ) -> cja::Result<()> {
-        let (tournament_id, snakes) = fixture_persisted_bracket(&pool, 5).await?;
+        let (tournament_id, snakes) =
+            fixture_persisted_bracket(&pool, TournamentFormat::SingleElimination, 5).await?;
         let matches = get_matches_for_tournament(&pool, tournament_id).await?;
         assert_eq!(matches.len(), 7); // bracket size 8
 
@@ -743,7 +1422,8 @@ mod tests {
     async fn completion_and_advancement_are_retry_safe(pool: PgPool) -> cja::Result<()> {
         // Three participants: seed 1 gets a bye into the final (round 2),
         // 2v3 plays round 1.
-        let (tournament_id, snakes) = fixture_persisted_bracket(&pool, 3).await?;
+        let (tournament_id, snakes) =
+            fixture_persisted_bracket(&pool, TournamentFormat::SingleElimination, 3).await?;
         let matches = get_matches_for_tournament(&pool, tournament_id).await?;
         let bye = match_at(&matches, 1, 0);
         let two_v_three = match_at(&matches, 1, 1);
@@ -818,4 +1498,71 @@ mod tests {
 
         Ok(())
     }
+
+    /// Five snakes, double elimination, favorites winning every match. Byes
+    /// and losers-bracket walkovers resolve on their own, the losers match
+    /// fed only by byes is canceled, and seed 2 comes back through the
+    /// losers bracket to meet seed 1 in the grand final.
+    #[sqlx::test(migrations = "../migrations")]
+    async fn double_elimination_plays_out_through_walkovers(pool: PgPool) -> cja::Result<()> {
+        let (tournament_id, snakes) =
+            fixture_persisted_bracket(&pool, TournamentFormat::DoubleElimination, 5).await?;
+        let seed_of = |snake: Uuid| snakes.iter().position(|s| *s == snake).unwrap();
+
+        let matches = get_matches_for_tournament(&pool, tournament_id).await?;
+        assert_eq!(
+            matches
+                .iter()
+                .filter(|m| m.status == MatchStatus::Canceled)
+                .count(),
+            1
+        );
+
+        // Play the earliest ready match until none are left.
+        loop {
+            let mut ready = None;
+            for m in get_matches_for_tournament(&pool, tournament_id).await? {
+                if m.status != MatchStatus::Scheduled {
+                    continue;
+                }
+                let participants = get_participants_for_match(&pool, m.match_id).await?;
+                let filled: Vec<Uuid> = participants
+                    .iter()
+                    .filter_map(|p| p.battlesnake_id)
+                    .collect();
+                if let [a, b] = filled[..] {
+                    ready = Some((m.match_id, a, b));
+                    break;
+                }
+            }
+            let Some((match_id, a, b)) = ready else { break };
+            let (winner, loser) = if seed_of(a) < seed_of(b) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+
+            let mut tx = pool.begin().await?;
+            complete_match_with_winner(&mut tx, match_id, winner).await?;
+            advance_from_match(&mut tx, match_id, winner, Some(loser)).await?;
+            tx.commit().await?;
+        }
+
+        let matches = get_matches_for_tournament(&pool, tournament_id).await?;
+        assert!(
+            matches
+                .iter()
+                .all(|m| matches!(m.status, MatchStatus::Completed | MatchStatus::Canceled))
+        );
+        let grand_final = matches
+            .iter()
+            .find(|m| m.bracket == MatchBracket::GrandFinal)
+            .unwrap();
+        assert_eq!(grand_final.winner_id, Some(snakes[0]));
+        let participants = get_participants_for_match(&pool, grand_final.match_id).await?;
+        assert_eq!(snake_in_slot(&participants, 1), Some(snakes[0]));
+        assert_eq!(snake_in_slot(&participants, 2), Some(snakes[1]));
+
+        Ok(())
+    }
 }
@@ -45,7 +45,7 @@ use crate::models::tournament::{
     set_match_status, set_tournament_current_round, try_set_tournament_status,
 };
 use crate::state::AppState;
-use crate::tournament_bracket::{complete_match_with_winner, fill_participant_from_source};
+use crate::tournament_bracket::{advance_from_match, complete_match_with_winner};
 
 /// Extra tied games allowed beyond [`MatchStyle::max_games_without_ties`]
 /// before a match is force-resolved instead of scheduling yet another game.
@@ -231,7 +231,13 @@ pub async fn run_match(app_state: &AppState, match_id: Uuid) -> cja::Result<()>
     let game_winners: Vec<Option<Uuid>> = match_games.iter().map(|mg| mg.winner_id).collect();
 
     if let Some(winner_battlesnake_id) = match_winner(tournament.match_style, &game_winners) {
-        complete_and_advance(app_state, &tournament_match, winner_battlesnake_id).await?;
+        complete_and_advance(
+            app_state,
+            &tournament_match,
+            &snake_ids,
+            winner_battlesnake_id,
+        )
+        .await?;
 
         tracing::info!(
             match_id = %match_id,
@@ -258,7 +264,13 @@ pub async fn run_match(app_state: &AppState, match_id: Uuid) -> cja::Result<()>
             winner_battlesnake_id = %winner_battlesnake_id,
             "Match hit the tie cap without a winner; forcing deterministic resolution"
         );
-        complete_and_advance(app_state, &tournament_match, winner_battlesnake_id).await?;
+        complete_and_advance(
+            app_state,
+            &tournament_match,
+            &snake_ids,
+            winner_battlesnake_id,
+        )
+        .await?;
         return Ok(());
     }
 
@@ -276,6 +288,7 @@ pub async fn run_match(app_state: &AppState, match_id: Uuid) -> cja::Result<()>
         CreateGame {
             board_size: tournament.board_size.clone(),
             game_type: tournament.game_type.clone(),
+            run_tag: None,
         },
     )
     .await
@@ -354,6 +367,14 @@ pub async fn run_match(app_state: &AppState, match_id: Uuid) -> cja::Result<()>
     .await
     .wrap_err("Failed to enqueue game runner job")?;
 
+    crate::events::game_created(
+        game.game_id,
+        &game.board_size,
+        &game.game_type,
+        snake_ids.len(),
+        crate::events::GameSource::Tournament,
+        Some(tournament.user_id),
+    );
     tracing::info!(
         match_id = %match_id,
         game_id = %game.game_id,
@@ -366,8 +387,9 @@ pub async fn run_match(app_state: &AppState, match_id: Uuid) -> cja::Result<()>
 
 /// Kick off every ready match in the tournament's current round.
 ///
-/// `round` is the round the owner was shown when they clicked "Run Round".
-/// The job queue can delay execution past a round transition (or past a
+/// `round` is the round the owner was shown when they clicked "Run Round"
+/// (or, with auto-advance, the round progression just moved to). The job
+/// queue can delay execution past a round transition (or past a
 /// reset-then-restart that rebuilt the bracket), so a payload that no longer
 /// matches `current_round` is stale and must no-op — auto-running a round the
 /// owner never asked for would break the caster flow.
@@ -425,6 +447,11 @@ pub async fn run_round(app_state: &AppState, tournament_id: Uuid, round: i32) ->
 
 /// Advance `current_round` when a round finishes; complete the tournament
 /// when the final round is done. Safe to run repeatedly.
+///
+/// Rounds with nothing left to play are skipped: in double elimination a
+/// round can hold only walkovers (already completed) and matches no one
+/// reaches (canceled). With `auto_advance`, the round moved to is queued
+/// right away instead of waiting for "Run Round".
 pub async fn update_tournament_progress(
     app_state: &AppState,
     tournament_id: Uuid,
@@ -440,71 +467,105 @@ pub async fn update_tournament_progress(
         return Ok(());
     }
 
-    let unfinished =
-        count_unfinished_matches_in_round(pool, tournament_id, tournament.current_round).await?;
-    if unfinished > 0 {
+    let mut round = tournament.current_round;
+    while count_unfinished_matches_in_round(pool, tournament_id, round).await? == 0 {
+        let next_round = round + 1;
+        if !round_exists(pool, tournament_id, next_round).await? {
+            return complete_tournament(app_state, tournament_id).await;
+        }
+        round = next_round;
+    }
+    if round == tournament.current_round {
         return Ok(());
     }
 
-    let next_round = tournament.current_round + 1;
-    if round_exists(pool, tournament_id, next_round).await? {
-        set_tournament_current_round(pool, tournament_id, next_round).await?;
+    set_tournament_current_round(pool, tournament_id, round).await?;
+    if tournament.auto_advance {
+        cja::jobs::Job::enqueue(
+            crate::jobs::RunTournamentRoundJob {
+                tournament_id,
+                round,
+            },
+            app_state.clone(),
+            format!("Auto-advance to round {round} of tournament {tournament_id}"),
+            None,
+        )
+        .await
+        .wrap_err("Failed to enqueue round job")?;
         tracing::info!(
             tournament_id = %tournament_id,
-            round = next_round,
-            "Round complete; advanced to next round (waiting for Run Round)"
+            round = round,
+            "Round complete; advanced and queued the next round"
         );
     } else {
-        // No next round: the final just finished. Compare-and-swap on the
-        // InProgress status we observed above so a concurrent status change
-        // wins the race — in particular, a completion racing a cancel must
-        // NOT resurrect the tournament (canceled -> completed). Losing the
-        // CAS is benign, not a job failure: whoever changed the status owns
-        // it now.
-        let completed = try_set_tournament_status(
-            pool,
-            tournament_id,
-            TournamentStatus::Completed,
-            TournamentStatus::InProgress,
-        )
-        .await?;
-        if completed {
-            tracing::info!(
-                tournament_id = %tournament_id,
-                "All rounds complete; tournament finished"
-            );
-        } else {
-            tracing::info!(
-                tournament_id = %tournament_id,
-                "Tournament status changed concurrently (e.g. canceled); not marking completed"
-            );
-        }
+        tracing::info!(
+            tournament_id = %tournament_id,
+            round = round,
+            "Round complete; advanced to next round (waiting for Run Round)"
+        );
     }
 
     Ok(())
 }
 
-/// Complete a match, advance the winner into the next match, and enqueue
-/// round progression. Safe to retry: completion and advancement are
-/// idempotent for the same winner, and if the post-commit enqueue fails the
-/// retry hits `run_match`'s completed-match path, which re-enqueues it.
+/// Mark a tournament whose final round just finished as completed.
+///
+/// Compare-and-swap on `in_progress` so a concurrent status change wins the
+/// race — in particular, a completion racing a cancel must NOT resurrect the
+/// tournament (canceled -> completed). Losing the CAS is benign, not a job
+/// failure: whoever changed the status owns it now.
+async fn complete_tournament(app_state: &AppState, tournament_id: Uuid) -> cja::Result<()> {
+    let completed = try_set_tournament_status(
+        &app_state.db,
+        tournament_id,
+        TournamentStatus::Completed,
+        TournamentStatus::InProgress,
+    )
+    .await?;
+    if completed {
+        tracing::info!(
+            tournament_id = %tournament_id,
+            "All rounds complete; tournament finished"
+        );
+    } else {
+        tracing::info!(
+            tournament_id = %tournament_id,
+            "Tournament status changed concurrently (e.g. canceled); not marking completed"
+        );
+    }
+    Ok(())
+}
+
+/// Complete a match, advance the winner (and in double elimination, the
+/// loser) into the matches they feed, and enqueue round progression. Safe to
+/// retry: completion and advancement are idempotent for the same winner, and
+/// if the post-commit enqueue fails the retry hits `run_match`'s
+/// completed-match path, which re-enqueues it.
 async fn complete_and_advance(
     app_state: &AppState,
     tournament_match: &TournamentMatch,
+    snake_ids: &[Uuid],
     winner_battlesnake_id: Uuid,
 ) -> cja::Result<()> {
     let pool = &app_state.db;
     let match_id = tournament_match.match_id;
+    let loser_battlesnake_id = snake_ids
+        .iter()
+        .copied()
+        .find(|id| *id != winner_battlesnake_id);
 
     let mut tx = pool
         .begin()
         .await
         .wrap_err("Failed to start match completion transaction")?;
     complete_match_with_winner(&mut tx, match_id, winner_battlesnake_id).await?;
-    if let Some(next_match_id) = tournament_match.next_match_id {
-        fill_participant_from_source(&mut tx, next_match_id, match_id, winner_battlesnake_id)
-            .await?;
-    }
+    advance_from_match(
+        &mut tx,
+        match_id,
+        winner_battlesnake_id,
+        loser_battlesnake_id,
+    )
+    .await?;
     tx.commit()
         .await
         .wrap_err("Failed to commit match completion")?;
@@ -831,7 +892,7 @@ mod tests {
 
     use crate::models::game::{GameBoardSize, GameType, create_game};
     use crate::models::tournament::{
-        CreateTournament, RegistrationStatus, Tournament, TournamentVisibility,
+        CreateTournament, RegistrationStatus, Tournament, TournamentFormat, TournamentVisibility,
         create_registration, create_tournament, get_matches_for_tournament, set_match_game_winner,
         set_tournament_status,
     };
@@ -844,6 +905,31 @@ mod tests {
         pool: &PgPool,
         match_style: MatchStyle,
     ) -> cja::Result<(Tournament, TournamentMatch, Vec<Uuid>)> {
+        let (tournament, snakes) = fixture_started_tournament(
+            pool,
+            match_style,
+            TournamentFormat::SingleElimination,
+            false,
+            2,
+        )
+        .await?;
+
+        let matches = get_matches_for_tournament(pool, tournament.tournament_id).await?;
+        assert_eq!(matches.len(), 1, "two participants means a single final");
+        let tournament_match = matches.into_iter().next().unwrap();
+        Ok((tournament, tournament_match, snakes))
+    }
+
+    /// Create a tournament of `participants` snakes in the given format with
+    /// its bracket persisted and the tournament moved to in_progress. Returns
+    /// the tournament and the battlesnake ids by seed.
+    async fn fixture_started_tournament(
+        pool: &PgPool,
+        match_style: MatchStyle,
+        format: TournamentFormat,
+        auto_advance: bool,
+        participants: i32,
+    ) -> cja::Result<(Tournament, Vec<Uuid>)> {
         // Random identifiers so the fixture can be used more than once per test.
         let github_id = i64::from(Uuid::new_v4().as_fields().0);
         let user_id: Uuid = sqlx::query_scalar(
@@ -867,15 +953,17 @@ mod tests {
                 registration_status: RegistrationStatus::Open,
                 visibility: TournamentVisibility::Public,
                 match_style,
-                max_snakes_per_user: 2,
-                required_participants: 2,
+                format,
+                auto_advance,
+                max_snakes_per_user: participants,
+                required_participants: participants,
             },
         )
         .await?;
 
         let mut registrations = Vec::new();
         let mut snakes = Vec::new();
-        for seed in 1..=2i32 {
+        for seed in 1..=participants {
             let battlesnake_id: Uuid = sqlx::query_scalar(
                 "INSERT INTO battlesnakes (user_id, name, url)
                  VALUES ($1, $2, $3) RETURNING battlesnake_id",
@@ -902,6 +990,7 @@ mod tests {
         crate::tournament_bracket::persist_bracket(
             &mut tx,
             tournament.tournament_id,
+            format,
             &registrations,
         )
         .await?;
@@ -922,10 +1011,7 @@ mod tests {
         )
         .await?;
 
-        let matches = get_matches_for_tournament(pool, tournament.tournament_id).await?;
-        assert_eq!(matches.len(), 1, "two participants means a single final");
-        let tournament_match = matches.into_iter().next().unwrap();
-        Ok((tournament, tournament_match, snakes))
+        Ok((tournament, snakes))
     }
 
     /// Insert a finished game recorded on the match with the given winner
@@ -941,6 +1027,7 @@ mod tests {
             CreateGame {
                 board_size: GameBoardSize::Medium,
                 game_type: GameType::Standard,
+                run_tag: None,
             },
         )
         .await?;
@@ -962,6 +1049,54 @@ mod tests {
         )
     }
 
+    /// With auto-advance on, finishing a round moves to the next round with
+    /// something left to play and queues it without waiting for the owner.
+    #[sqlx::test(migrations = "../migrations")]
+    async fn auto_advance_skips_finished_rounds_and_queues_the_next(
+        pool: PgPool,
+    ) -> cja::Result<()> {
+        let app_state = AppState::test_from_pool(pool.clone());
+        let (tournament, snakes) = fixture_started_tournament(
+            &pool,
+            MatchStyle::SingleGame,
+            TournamentFormat::RoundRobin,
+            true,
+            3,
+        )
+        .await?;
+        let tournament_id = tournament.tournament_id;
+        set_tournament_current_round(&pool, tournament_id, 1).await?;
+
+        // Three snakes: one match a round, the top seed sits out round 1
+        let round_one = get_matches_for_round(&pool, tournament_id, 1).await?;
+        assert_eq!(round_one.len(), 1);
+        let round_one = &round_one[0];
+
+        // Nothing left to play in round 2 either
+        sqlx::query(
+            "UPDATE tournament_matches SET status = 'canceled'
+             WHERE tournament_id = $1 AND round = 2",
+        )
+        .bind(tournament_id)
+        .execute(&pool)
+        .await?;
+
+        add_finished_game(&pool, round_one.match_id, 1, Some(snakes[1])).await?;
+        run_match(&app_state, round_one.match_id).await?;
+        update_tournament_progress(&app_state, tournament_id).await?;
+
+        let reloaded = get_tournament_by_id(&pool, tournament_id).await?.unwrap();
+        assert_eq!(reloaded.status, TournamentStatus::InProgress);
+        assert_eq!(reloaded.current_round, 3);
+        assert_eq!(count_jobs(&pool, "RunTournamentRoundJob").await?, 1);
+
+        // Re-running progression is a no-op
+        update_tournament_progress(&app_state, tournament_id).await?;
+        assert_eq!(count_jobs(&pool, "RunTournamentRoundJob").await?, 1);
+
+        Ok(())
+    }
+
     #[sqlx::test(migrations = "../migrations")]
     async fn tie_cap_forces_resolution_to_most_wins(pool: PgPool) -> cja::Result<()> {
         let app_state = AppState::test_from_pool(pool.clone());
@@ -1061,6 +1196,7 @@ mod tests {
             CreateGame {
                 board_size: GameBoardSize::Medium,
                 game_type: GameType::Standard,
+                run_tag: None,
             },
         )
         .await?;
@@ -1172,6 +1308,7 @@ mod tests {
             CreateGame {
                 board_size: GameBoardSize::Medium,
                 game_type: GameType::Standard,
+                run_tag: None,
             },
         )
         .await?;
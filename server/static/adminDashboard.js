@@ -0,0 +1,39 @@
+// Re-polls the admin dashboard's job queue and game counts tables in place.
+// The server compares the ready-job count we send back against the current
+// one to render the "trending up/down" indicator.
+(() => {
+  const REFRESH_MS = 5000;
+  const status = document.getElementById("admin-live-status");
+  let timer = null;
+
+  const stop = (message) => {
+    clearInterval(timer);
+    timer = null;
+    if (status) status.textContent = message;
+  };
+
+  const refresh = async () => {
+    const live = document.getElementById("admin-live");
+    if (!live || document.hidden) return;
+
+    const params = new URLSearchParams({ prev_ready: live.dataset.ready });
+    try {
+      const response = await fetch(`/admin/live?${params}`, {
+        credentials: "same-origin",
+      });
+      // A redirect means the session expired and we got the login page
+      if (!response.ok || response.redirected) {
+        stop("Auto-refresh stopped — reload the page");
+        return;
+      }
+      live.outerHTML = await response.text();
+      if (status) {
+        status.textContent = `Updated ${new Date().toLocaleTimeString()}`;
+      }
+    } catch (_) {
+      if (status) status.textContent = "Refresh failed, retrying…";
+    }
+  };
+
+  timer = setInterval(refresh, REFRESH_MS);
+})();
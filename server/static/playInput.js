@@ -0,0 +1,58 @@
+// Keyboard and on-screen controls for /play/{id}. Sends the chosen heading
+// over the game's input socket; the runner uses the latest one each turn.
+(() => {
+  const root = document.getElementById("play");
+  if (!root) return;
+
+  const KEYS = {
+    ArrowUp: "up",
+    ArrowDown: "down",
+    ArrowLeft: "left",
+    ArrowRight: "right",
+    w: "up",
+    s: "down",
+    a: "left",
+    d: "right",
+  };
+  const RECONNECT_MS = 1000;
+
+  const gameId = root.dataset.gameId;
+  const status = root.querySelector("[data-play-status]");
+  let socket = null;
+  let over = false;
+
+  const connect = () => {
+    const scheme = location.protocol === "https:" ? "wss:" : "ws:";
+    socket = new WebSocket(`${scheme}//${location.host}/play/${gameId}/input`);
+    socket.addEventListener("open", () => {
+      status.textContent = "Connected: steer with the arrow keys or WASD.";
+    });
+    socket.addEventListener("close", (event) => {
+      if (event.code === 1000) {
+        over = true;
+        status.textContent = "Game over.";
+        return;
+      }
+      status.textContent = "Reconnecting…";
+      setTimeout(connect, RECONNECT_MS);
+    });
+  };
+
+  const steer = (direction) => {
+    if (over || !socket || socket.readyState !== WebSocket.OPEN) return;
+    socket.send(direction);
+  };
+
+  document.addEventListener("keydown", (event) => {
+    const direction = KEYS[event.key.length === 1 ? event.key.toLowerCase() : event.key];
+    if (!direction || event.target.closest("input, select, textarea")) return;
+    event.preventDefault();
+    steer(direction);
+  });
+
+  root.querySelectorAll("[data-dir]").forEach((button) => {
+    button.addEventListener("click", () => steer(button.dataset.dir));
+  });
+
+  connect();
+})();
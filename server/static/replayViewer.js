@@ -0,0 +1,186 @@
+// Replay viewer for finished games on /games/{id}. Pages the stored frames
+// in from the public frames endpoint, draws each turn as an SVG board, and
+// keeps the health/length vitals on the snake cards in the aside in step.
+(() => {
+  const root = document.getElementById("replay");
+  if (!root) return;
+
+  const SVG_NS = "http://www.w3.org/2000/svg";
+  const PAGE_SIZE = 100;
+  const CELL = 20;
+  const MAX_HEALTH = 100;
+
+  const gameId = root.dataset.gameId;
+  const width = Number(root.dataset.width);
+  const height = Number(root.dataset.height);
+  const fps = Number(root.dataset.fps) || 10;
+  const startTurn = root.dataset.turn === undefined ? null : Number(root.dataset.turn);
+
+  const board = root.querySelector(".replay-board");
+  const playButton = root.querySelector("[data-replay-play]");
+  const scrubber = root.querySelector("[data-replay-scrub]");
+  const turnLabel = root.querySelector("[data-replay-turn]");
+  const status = root.querySelector("[data-replay-status]");
+
+  const frames = [];
+  let current = 0;
+  let timer = null;
+
+  const svg = document.createElementNS(SVG_NS, "svg");
+  svg.setAttribute("viewBox", `0 0 ${width * CELL} ${height * CELL}`);
+  svg.setAttribute("role", "img");
+  svg.setAttribute("aria-label", "Game board");
+  board.appendChild(svg);
+
+  const el = (name, attrs) => {
+    const node = document.createElementNS(SVG_NS, name);
+    for (const [key, value] of Object.entries(attrs)) node.setAttribute(key, value);
+    return node;
+  };
+
+  // Engine coordinates have y=0 at the bottom
+  const cellX = (x) => x * CELL;
+  const cellY = (y) => (height - 1 - y) * CELL;
+
+  const drawGrid = () => {
+    for (let x = 0; x < width; x++) {
+      for (let y = 0; y < height; y++) {
+        svg.appendChild(
+          el("rect", {
+            x: cellX(x) + 1,
+            y: cellY(y) + 1,
+            width: CELL - 2,
+            height: CELL - 2,
+            rx: 3,
+            class: "cell",
+          }),
+        );
+      }
+    }
+  };
+
+  const render = (index) => {
+    const frame = frames[index];
+    if (!frame) return;
+    current = index;
+
+    const layer = el("g", {});
+    for (const h of frame.Hazards || []) {
+      layer.appendChild(
+        el("rect", { x: cellX(h.X), y: cellY(h.Y), width: CELL, height: CELL, class: "hazard" }),
+      );
+    }
+    for (const f of frame.Food || []) {
+      layer.appendChild(
+        el("circle", { cx: cellX(f.X) + CELL / 2, cy: cellY(f.Y) + CELL / 2, r: CELL / 4, class: "food" }),
+      );
+    }
+    for (const snake of frame.Snakes || []) {
+      if (snake.Death) continue;
+      const color = snake.Color || "#888888";
+      snake.Body.forEach((part, i) => {
+        const inset = i === 0 ? 1 : 3;
+        layer.appendChild(
+          el("rect", {
+            x: cellX(part.X) + inset,
+            y: cellY(part.Y) + inset,
+            width: CELL - inset * 2,
+            height: CELL - inset * 2,
+            rx: i === 0 ? 6 : 3,
+            fill: color,
+            class: i === 0 ? "head" : "body",
+          }),
+        );
+      });
+    }
+    svg.querySelector("g")?.remove();
+    svg.appendChild(layer);
+
+    for (const snake of frame.Snakes || []) {
+      const card = document.querySelector(`.scard[data-snake-id="${snake.ID}"]`);
+      if (!card) continue;
+      const dead = Boolean(snake.Death);
+      card.classList.toggle("out", dead);
+      const health = card.querySelector("[data-vital-health]");
+      const bar = card.querySelector("[data-vital-bar]");
+      const length = card.querySelector("[data-vital-length]");
+      if (health) health.textContent = dead ? `out (${snake.Death.Cause})` : `${snake.Health} hp`;
+      if (bar) bar.style.width = `${dead ? 0 : (snake.Health / MAX_HEALTH) * 100}%`;
+      if (length) length.textContent = `length ${snake.Body.length}`;
+    }
+
+    scrubber.value = String(index);
+    turnLabel.textContent = `Turn ${frame.Turn} / ${frames[frames.length - 1].Turn}`;
+  };
+
+  const pause = () => {
+    clearInterval(timer);
+    timer = null;
+    playButton.textContent = playButton.dataset.playLabel || "Play";
+  };
+
+  const play = () => {
+    if (current >= frames.length - 1) render(0);
+    playButton.textContent = playButton.dataset.pauseLabel || "Pause";
+    timer = setInterval(() => {
+      if (current >= frames.length - 1) {
+        pause();
+        return;
+      }
+      render(current + 1);
+    }, 1000 / fps);
+  };
+
+  const step = (delta) => {
+    pause();
+    render(Math.min(Math.max(current + delta, 0), frames.length - 1));
+  };
+
+  const load = async () => {
+    for (let offset = 0; ; offset += PAGE_SIZE) {
+      const response = await fetch(`/api/games/${gameId}/frames?offset=${offset}&limit=${PAGE_SIZE}`);
+      if (!response.ok) throw new Error(`frames request failed: ${response.status}`);
+      const page = await response.json();
+      frames.push(...page.frames);
+      if (page.frames.length < PAGE_SIZE) return;
+    }
+  };
+
+  drawGrid();
+  status.textContent = "Loading replay…";
+  load()
+    .then(() => {
+      if (frames.length === 0) {
+        status.textContent = "No turns were recorded for this game.";
+        return;
+      }
+      status.textContent = "";
+      root.classList.add("ready");
+      scrubber.max = String(frames.length - 1);
+      const start = startTurn === null ? 0 : frames.findIndex((f) => f.Turn >= startTurn);
+      render(start < 0 ? frames.length - 1 : start);
+      if (root.dataset.autoplay === "true") play();
+    })
+    .catch(() => {
+      status.textContent = "Couldn't load this replay. Try refreshing the page.";
+    });
+
+  playButton.addEventListener("click", () => (timer ? pause() : play()));
+  scrubber.addEventListener("input", () => {
+    pause();
+    render(Number(scrubber.value));
+  });
+  root.querySelector("[data-replay-prev]").addEventListener("click", () => step(-1));
+  root.querySelector("[data-replay-next]").addEventListener("click", () => step(1));
+  document.addEventListener("keydown", (e) => {
+    if (!root.classList.contains("ready") || e.target.closest("input, textarea")) return;
+    if (e.key === " ") {
+      e.preventDefault();
+      timer ? pause() : play();
+    } else if (e.key === "ArrowLeft") {
+      step(-1);
+    } else if (e.key === "ArrowRight") {
+      step(1);
+    }
+  });
+})();
@@ -0,0 +1,362 @@
+//! End-to-end coverage of the game loop: snakes registered through the API,
+//! backed by mock snake servers, play a game created through
+//! `POST /api/v1/games`. The queued jobs run inline, just as the worker
+//! would run them, and the outcome is read back through the API and the
+//! database.
+//!
+//! Run with `cargo test -p arena --test game_loop`; needs the same
+//! `DATABASE_URL` as the unit tests.
+
+use std::collections::HashSet;
+
+use arena::testing::{self, AppState};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tower::ServiceExt as _;
+use uuid::Uuid;
+use wiremock::{
+    Mock, MockServer, Respond, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const DIRECTIONS: [(&str, i64, i64); 4] = [
+    ("up", 0, 1),
+    ("right", 1, 0),
+    ("down", 0, -1),
+    ("left", -1, 0),
+];
+
+fn coord(point: &Value) -> (i64, i64) {
+    (point["x"].as_i64().unwrap(), point["y"].as_i64().unwrap())
+}
+
+/// Moves to a free, in-bounds square, staying out of reach of the other
+/// snakes' heads so it never trades heads with them.
+struct Survivor;
+
+impl Respond for Survivor {
+    fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+        let state: Value = request.body_json().expect("move request is JSON");
+        let board = &state["board"];
+        let (width, height) = (
+            board["width"].as_i64().unwrap(),
+            board["height"].as_i64().unwrap(),
+        );
+        let me = state["you"]["id"].as_str().unwrap();
+        let head = coord(&state["you"]["head"]);
+
+        let mut occupied = HashSet::new();
+        let mut threatened = HashSet::new();
+        for snake in board["snakes"].as_array().unwrap() {
+            occupied.extend(snake["body"].as_array().unwrap().iter().map(coord));
+            if snake["id"].as_str() != Some(me) {
+                let (x, y) = coord(&snake["head"]);
+                threatened.extend(DIRECTIONS.iter().map(|(_, dx, dy)| (x + dx, y + dy)));
+            }
+        }
+
+        let free: Vec<(&str, (i64, i64))> = DIRECTIONS
+            .iter()
+            .map(|(name, dx, dy)| (*name, (head.0 + dx, head.1 + dy)))
+            .filter(|(_, (x, y))| (0..width).contains(x) && (0..height).contains(y))
+            .filter(|(_, square)| !occupied.contains(square))
+            .collect();
+        let direction = free
+            .iter()
+            .find(|(_, square)| !threatened.contains(square))
+            .or(free.first())
+            .map_or("up", |(name, _)| name);
+
+        ResponseTemplate::new(200).set_body_json(json!({ "move": direction }))
+    }
+}
+
+/// Heads straight up until it hits the wall
+struct Crasher;
+
+impl Respond for Crasher {
+    fn respond(&self, _request: &wiremock::Request) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({ "move": "up", "shout": "geronimo" }))
+    }
+}
+
+async fn mock_snake(color: &str, moves: impl Respond + 'static) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "apiversion": "1",
+            "color": color,
+            "head": "default",
+            "tail": "default",
+        })))
+        .mount(&server)
+        .await;
+    for endpoint in ["/start", "/end"] {
+        Mock::given(method("POST"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/move"))
+        .respond_with(moves)
+        .mount(&server)
+        .await;
+    server
+}
+
+/// A signed-up user and an API client acting as them
+struct Client {
+    app: Router,
+    token: String,
+}
+
+impl Client {
+    async fn new(state: &AppState, login: &str) -> Self {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (external_github_id, github_login, github_access_token)
+             VALUES ($1, $2, 'token') RETURNING user_id",
+        )
+        .bind(i64::from(Uuid::new_v4().as_fields().0))
+        .bind(login)
+        .fetch_one(&state.db)
+        .await
+        .unwrap();
+        let token = testing::api_token(state, user_id).await.unwrap();
+
+        Self {
+            app: testing::router(state.clone()),
+            token,
+        }
+    }
+
+    async fn send(&self, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(header::CONTENT_TYPE, "application/json");
+        let request = match body {
+            Some(body) => request.body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+
+        let response = self.app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    async fn register_snake(&self, name: &str, server: &MockServer) -> Uuid {
+        let (status, body) = self
+            .send(
+                "POST",
+                "/api/v1/snakes",
+                Some(json!({ "name": name, "url": server.uri(), "is_public": true })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+        body["id"].as_str().unwrap().parse().unwrap()
+    }
+
+    async fn create_game(&self, snakes: &[Uuid]) -> Uuid {
+        let (status, body) = self
+            .send(
+                "POST",
+                "/api/v1/games",
+                Some(json!({ "snakes": snakes, "board": "7x7", "game_type": "standard" })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+        assert_eq!(body["status"], "waiting");
+        body["id"].as_str().unwrap().parse().unwrap()
+    }
+
+    async fn result(&self, game_id: Uuid) -> Value {
+        let (status, body) = self
+            .send("GET", &format!("/api/v1/games/{game_id}/result"), None)
+            .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        body
+    }
+}
+
+/// A survivor and a snake that drives into the wall, in one game
+struct Matchup {
+    client: Client,
+    survivor: Uuid,
+    crasher: Uuid,
+    // Held so the mock snakes keep serving for the whole test
+    _servers: [MockServer; 2],
+}
+
+async fn matchup(state: &AppState) -> Matchup {
+    let client = Client::new(state, "e2e-player").await;
+    let survivor_server = mock_snake("#00ff00", Survivor).await;
+    let crasher_server = mock_snake("#ff0000", Crasher).await;
+    let survivor = client.register_snake("Survivor", &survivor_server).await;
+    let crasher = client.register_snake("Crasher", &crasher_server).await;
+    Matchup {
+        client,
+        survivor,
+        crasher,
+        _servers: [survivor_server, crasher_server],
+    }
+}
+
+fn snake<'a>(result: &'a Value, battlesnake_id: Uuid) -> &'a Value {
+    result["snakes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["id"] == battlesnake_id.to_string())
+        .unwrap_or_else(|| panic!("{battlesnake_id} is not in {result}"))
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_api_game_runs_to_completion(pool: PgPool) {
+    let state = testing::app_state(pool.clone());
+    let Matchup {
+        client,
+        survivor,
+        crasher,
+        _servers,
+    } = matchup(&state).await;
+
+    let game_id = client.create_game(&[survivor, crasher]).await;
+
+    // Queued, not yet played
+    let result = client.result(game_id).await;
+    assert_eq!(result["status"], "waiting");
+    assert!(result["winner"].is_null());
+    assert!(snake(&result, survivor)["placement"].is_null());
+    let enqueued: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM jobs WHERE name = 'GameRunnerJob' AND payload->>'game_id' = $1",
+    )
+    .bind(game_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(enqueued, 1);
+
+    let ran = testing::run_queued_jobs(&state).await.unwrap();
+    assert_eq!(ran.first().map(String::as_str), Some("GameRunnerJob"));
+    assert!(!ran.iter().any(|name| name == "LeaderboardRatingUpdateJob"));
+
+    let result = client.result(game_id).await;
+    assert_eq!(result["status"], "finished");
+    assert_eq!(result["winner"], survivor.to_string());
+    assert!(result["turns"].as_i64().unwrap() > 0);
+    assert!(result["leaderboard"].is_null());
+
+    let (winner, loser) = (snake(&result, survivor), snake(&result, crasher));
+    assert_eq!(winner["placement"], 1);
+    assert!(winner["death"].is_null());
+    assert_eq!(loser["placement"], 2);
+    assert!(loser["death"].is_object(), "{loser}");
+    assert!(winner["latency"]["moves"].as_i64().unwrap() > 0);
+
+    let (status, finished_at): (String, Option<chrono::DateTime<chrono::Utc>>) =
+        sqlx::query_as("SELECT status, finished_at FROM games WHERE game_id = $1")
+            .bind(game_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(status, "finished");
+    assert!(finished_at.is_some());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_leaderboard_game_updates_ratings(pool: PgPool) {
+    let state = testing::app_state(pool.clone());
+    let Matchup {
+        client,
+        survivor,
+        crasher,
+        _servers,
+    } = matchup(&state).await;
+
+    let leaderboard_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO leaderboards (name) VALUES ('Standard') RETURNING leaderboard_id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    for battlesnake_id in [survivor, crasher] {
+        sqlx::query(
+            "INSERT INTO leaderboard_entries (leaderboard_id, battlesnake_id) VALUES ($1, $2)",
+        )
+        .bind(leaderboard_id)
+        .bind(battlesnake_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    // Record the game and its seats against the leaderboard, as the
+    // matchmaker does for the games it creates
+    let game_id = client.create_game(&[survivor, crasher]).await;
+    sqlx::query("INSERT INTO leaderboard_games (leaderboard_id, game_id) VALUES ($1, $2)")
+        .bind(leaderboard_id)
+        .bind(game_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE game_battlesnakes gb SET leaderboard_entry_id = le.leaderboard_entry_id
+         FROM leaderboard_entries le
+         WHERE gb.game_id = $1 AND le.leaderboard_id = $2 AND le.battlesnake_id = gb.battlesnake_id",
+    )
+    .bind(game_id)
+    .bind(leaderboard_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ran = testing::run_queued_jobs(&state).await.unwrap();
+    let position = |name: &str| ran.iter().position(|n| n == name);
+    assert!(
+        position("GameRunnerJob") < position("LeaderboardRatingUpdateJob"),
+        "{ran:?}"
+    );
+
+    let result = client.result(game_id).await;
+    assert_eq!(result["status"], "finished");
+    assert_eq!(result["leaderboard"]["id"], leaderboard_id.to_string());
+    assert!(snake(&result, survivor)["score_change"].as_f64().unwrap() > 0.0);
+    assert!(snake(&result, crasher)["score_change"].as_f64().unwrap() < 0.0);
+
+    let rating = |battlesnake_id: Uuid| {
+        sqlx::query_as::<_, (f64, i32, i32, i32)>(
+            "SELECT mu, games_played, first_place_finishes, non_first_finishes
+             FROM leaderboard_entries
+             WHERE leaderboard_id = $1 AND battlesnake_id = $2",
+        )
+        .bind(leaderboard_id)
+        .bind(battlesnake_id)
+        .fetch_one(&pool)
+    };
+    let (winner_mu, winner_games, winner_firsts, _) = rating(survivor).await.unwrap();
+    let (loser_mu, loser_games, _, loser_others) = rating(crasher).await.unwrap();
+    assert!(
+        winner_mu > 25.0 && loser_mu < 25.0,
+        "{winner_mu} vs {loser_mu}"
+    );
+    assert_eq!((winner_games, loser_games), (1, 1));
+    assert_eq!((winner_firsts, loser_others), (1, 1));
+}